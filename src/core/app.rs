@@ -1,49 +1,192 @@
 use std::cell::{Cell, RefCell};
+use std::collections::{BTreeSet, HashMap};
+use std::path::Path;
 use std::rc::Rc;
 use std::sync::mpsc;
-use egui_term::{BackendSettings, PtyEvent, TerminalBackend};
-use crate::core::commands::list_apps;
-use crate::models::app::LandoGui;
+use std::thread;
+use std::time::Instant;
+use eframe::egui;
+use crate::core::journal::DEFAULT_JOURNAL_LIMIT;
+use crate::core::logging::{parse_log_level, set_log_level, StartupTimer};
+use crate::core::processes::find_orphaned_processes;
+use crate::core::prompts::set_auto_yes;
+use crate::core::secret::set_clear_clipboard;
+use crate::core::storage::{load_app_file, save_app_file};
+use crate::models::app::{LandoGui, ServicesView, WindowGeometry, AUTO_RETRY_INFO_STORAGE_KEY, AUTO_YES_STORAGE_KEY, CLEAR_CLIPBOARD_STORAGE_KEY, JOURNAL_LIMIT_STORAGE_KEY, LOG_LEVEL_STORAGE_KEY, NOTES_LOCATION_STORAGE_KEY, NOTIFICATIONS_STORAGE_KEY, PINNED_PROJECTS_STORAGE_KEY, PROJECT_LAST_OPENED_STORAGE_KEY, RECENT_PROJECTS_STORAGE_KEY, THEME_STORAGE_KEY};
+use crate::models::dashboard::ProjectDashboard;
+use crate::models::drift::ConfigDriftView;
+use crate::models::journal::CommandJournal;
+use crate::models::commands::LandoCommandOutcome;
+use crate::models::terminal::LazyTerminal;
+use crate::models::toasts::ToastManager;
+use crate::ui::diagnostics::{DiagnosticsWindow, FrameTimeOverlay};
+use crate::ui::graph::ServiceGraphView;
+use crate::ui::notes::NotesSearch;
 use crate::ui::service::ServiceUIManager;
+use crate::ui::status_bar::StatusBar;
 
 impl LandoGui {
-    pub fn new(cc: &eframe::CreationContext<'_>) -> Self {
+    pub fn new(cc: &eframe::CreationContext<'_>, mut startup: StartupTimer) -> Self {
+        startup.phase("ventana");
         let (sender, receiver) = mpsc::channel();
 
-        // Channel for the terminal, as required by the constructor.
-        // The receiver is not used because we don't process PTY events.
-        let (pty_sender, _pty_receiver) = mpsc::channel::<(u64, PtyEvent)>();
+        // Nada que bloquee el primer frame: los procesos huérfanos se buscan en
+        // otro hilo, `lando list` se aplaza (ver `ensure_apps_listed`) y el
+        // terminal se crea la primera vez que se muestra
+        let orphan_sender = sender.clone();
+        thread::spawn(move || {
+            let _ = orphan_sender.send(LandoCommandOutcome::OrphanedProcesses(find_orphaned_processes()));
+        });
 
-        // Al iniciar, pedimos la lista de apps
-        list_apps(sender.clone());
+        // Tema guardado en la sesión anterior (por defecto, el del sistema)
+        let theme = cc.storage
+            .and_then(|storage| eframe::get_value(storage, THEME_STORAGE_KEY))
+            .unwrap_or(egui::ThemePreference::System);
+        cc.egui_ctx.set_theme(theme);
+
+        let auto_yes_enabled = cc.storage
+            .and_then(|storage| eframe::get_value(storage, AUTO_YES_STORAGE_KEY))
+            .unwrap_or(false);
+        set_auto_yes(auto_yes_enabled);
+        let auto_retry_info = cc.storage
+            .and_then(|storage| eframe::get_value(storage, AUTO_RETRY_INFO_STORAGE_KEY))
+            .unwrap_or(true);
+        let notifications_enabled = cc.storage
+            .and_then(|storage| eframe::get_value(storage, NOTIFICATIONS_STORAGE_KEY))
+            .unwrap_or(true);
+        let clear_clipboard_secrets = cc.storage
+            .and_then(|storage| eframe::get_value(storage, CLEAR_CLIPBOARD_STORAGE_KEY))
+            .unwrap_or(false);
+        set_clear_clipboard(clear_clipboard_secrets);
+
+        // Se guarda como texto ("info", "debug"...) para no depender de serde en `log`
+        let log_level = parse_log_level(&cc.storage
+            .and_then(|storage| eframe::get_value::<String>(storage, LOG_LEVEL_STORAGE_KEY))
+            .unwrap_or_default());
+        set_log_level(log_level);
+
+        let pinned_projects = cc.storage
+            .and_then(|storage| eframe::get_value(storage, PINNED_PROJECTS_STORAGE_KEY))
+            .unwrap_or_default();
+        let recent_projects = cc.storage
+            .and_then(|storage| eframe::get_value(storage, RECENT_PROJECTS_STORAGE_KEY))
+            .unwrap_or_default();
+        let project_last_opened = cc.storage
+            .and_then(|storage| eframe::get_value(storage, PROJECT_LAST_OPENED_STORAGE_KEY))
+            .unwrap_or_default();
+        let notes_location = cc.storage
+            .and_then(|storage| eframe::get_value(storage, NOTES_LOCATION_STORAGE_KEY))
+            .unwrap_or_default();
+        let journal_limit = cc.storage
+            .and_then(|storage| eframe::get_value(storage, JOURNAL_LIMIT_STORAGE_KEY))
+            .unwrap_or(DEFAULT_JOURNAL_LIMIT);
+        startup.phase("ajustes");
+
+        let command_journal = CommandJournal::load(journal_limit);
+        startup.phase("historial");
+        let db_engine_overrides = Self::load_db_engine_overrides();
+        startup.phase("motores de BD");
 
         Self {
             apps: vec![],
             projects: vec![],
+            pinned_projects,
+            recent_projects,
+            project_last_opened,
+            dashboard: ProjectDashboard::default(),
             selected_project_path: None,
             services: vec![],
             db_query_input: String::new(),
             db_query_result: None,
-            shell_command_input: String::new(),
-            error_message: None,
-            success_message: None,
-            is_loading: Cell::new(true), // Empezamos cargando
+            toasts: ToastManager::default(),
+            is_loading: Cell::new(false),
+            last_refresh: None,
+            apps_requested: false,
+            started_at: Instant::now(),
+            startup: Some(startup),
+            running_commands: Vec::new(),
+            interactive_commands: Vec::new(),
+            orphaned_processes: Vec::new(),
+            exit_dialog_open: false,
+            exit_cancel_started: None,
+            allow_close: false,
+            show_settings: false,
+            notifications_enabled,
+            theme,
+            auto_yes_enabled,
+            auto_retry_info,
+            info_retry_cancel: None,
+            clear_clipboard_secrets,
+            log_level,
+            diagnostics: DiagnosticsWindow::default(),
+            frame_time: FrameTimeOverlay::default(),
             sender,
             receiver,
-            terminal: Rc::new(RefCell::new(
-                TerminalBackend::new(
-                    0,
-                    cc.egui_ctx.clone(),
-                    pty_sender,
-                    BackendSettings::default(),
-                )
-                    .expect("Failed to create TerminalBackend"),
-            )),
+            terminal: Rc::new(RefCell::new(LazyTerminal::new(cc.egui_ctx.clone()))),
             service_ui_manager: Rc::new(RefCell::new(ServiceUIManager::default())),
             open_database_interface: None,
+            detached_database_interface: None,
+            detached_geometry: None,
+            detached_initial_geometry: None,
             show_terminal_popup: false,
             terminal_filter: String::new(),
+            terminal_filter_regex: false,
+            terminal_filter_case_insensitive: false,
+            terminal_filter_compiled: None,
+            terminal_filter_error: None,
             log_buffer: Vec::new(),
+            log_pending_bytes: Vec::new(),
+            phase_tracker: None,
+            log_generation: 0,
+            command_journal,
+            journal_output: None,
+            config_drift: ConfigDriftView::default(),
+            pending_lando_command: None,
+            pending_destroy: None,
+            pending_bulk: None,
+            status_bar: StatusBar::default(),
+            notes: None,
+            notes_location,
+            notes_search: NotesSearch::default(),
+            notes_open_request: false,
+            bulk_run: None,
+            bulk_selection: BTreeSet::new(),
+            bulk_stop_on_error: false,
+            new_project_wizard: None,
+            services_view: ServicesView::Cards,
+            service_graph: ServiceGraphView::default(),
+            scroll_to_service: None,
+            db_engine_overrides,
+            probed_db_engines: HashMap::new(),
+        }
+    }
+}
+
+// Geometría de las ventanas de BD separadas, por proyecto y servicio
+const DATABASE_WINDOWS_FILE: &str = "database_windows.json";
+
+impl LandoGui {
+    pub fn database_window_key(project_path: &Path, service: &str) -> String {
+        format!("{}#{}", project_path.display(), service)
+    }
+
+    pub fn load_database_window_geometry(key: &str) -> Option<WindowGeometry> {
+        load_app_file::<HashMap<String, WindowGeometry>>(DATABASE_WINDOWS_FILE).remove(key)
+    }
+
+    pub fn save_detached_window_geometry(&mut self) {
+        let (Some(service), Some(project_path), Some(geometry)) = (
+            &self.detached_database_interface,
+            &self.selected_project_path,
+            self.detached_geometry,
+        ) else {
+            return;
+        };
+
+        let mut saved: HashMap<String, WindowGeometry> = load_app_file(DATABASE_WINDOWS_FILE);
+        saved.insert(Self::database_window_key(project_path, service), geometry);
+        if let Err(e) = save_app_file(DATABASE_WINDOWS_FILE, &saved) {
+            self.toasts.warning("Ventana de BD", &format!("No se pudo guardar su posición: {}", e));
         }
     }
-}
\ No newline at end of file
+}
@@ -1,39 +1,321 @@
 use std::path::PathBuf;
+use std::sync::atomic::AtomicBool;
 use std::sync::mpsc::Sender;
-use crate::core::commands::run_shell_command;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use crate::models::terminal::LazyTerminal;
+use crate::core::commands::{compose_project_name, decode_utf8_stream, next_request_id, run_container_stats_tagged, run_lando_args, run_shell_capture_tagged, run_shell_stream_tagged_as};
+use crate::core::recipes::{detect_app_name, detect_recipe, detect_tooling, interactive_command_line, set_xdebug_in_lando_file};
 use crate::models::lando::LandoService;
-use crate::ui::appserver::AppServerUI;
+use crate::models::recipes::{quick_actions_for, QuickAction};
+use crate::ui::appserver::{AppServerPendingRequest, AppServerTab, AppServerUI, MaintenanceRun, ServiceStatus};
+use crate::ui::confirm::ConfirmDialog;
 use crate::models::commands::LandoCommandOutcome;
 
+// `grep` sin coincidencias sale con error; con `|| true` la ausencia de modo no falla
+const XDEBUG_STATUS_COMMAND: &str = "php -m; php -i | grep -i '^xdebug.mode' || true";
+const PHP_INI_COMMAND: &str = "php --ini; php -i";
+
+// Ajustes de `php -i` que se muestran en el visor de php.ini
+const PHP_INI_KEYS: &[&str] = &["memory_limit", "max_execution_time", "upload_max_filesize", "post_max_size"];
+
+// Los PHP de Lando van por defecto con Apache (mod_php) en el mismo contenedor;
+// con `via: nginx` el appserver solo lleva php-fpm, que recarga con USR2
+const PHP_RELOAD_COMMAND: &str =
+    "if command -v apachectl >/dev/null 2>&1; then apachectl -t && apachectl graceful; else pkill -USR2 -o php-fpm; fi";
+
+// Cada cuánto se releen las métricas con la pestaña de monitoreo abierta
+pub const MONITORING_REFRESH_INTERVAL: Duration = Duration::from_secs(5);
+
+// /proc existe en cualquier imagen; netstat o ss no siempre están instalados
+const CONNECTIONS_COMMAND: &str = "cat /proc/net/tcp /proc/net/tcp6 2>/dev/null || true";
+const LOADAVG_COMMAND: &str = "cat /proc/loadavg";
+
+// HTTP, HTTPS y php-fpm (con `via: nginx` las peticiones llegan por el 9000)
+const WEB_PORTS: &[u16] = &[80, 443, 8080, 9000];
+
+const COMMAND_HISTORY_LIMIT: usize = 30;
+
+// Conexiones TCP establecidas (estado 01) cuyo puerto local es de servidor web
+pub fn count_web_connections(proc_net_tcp: &str) -> usize {
+    proc_net_tcp.lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let local = fields.get(1)?;
+            let port = u16::from_str_radix(local.rsplit(':').next()?, 16).ok()?;
+            (fields.get(3) == Some(&"01") && WEB_PORTS.contains(&port)).then_some(())
+        })
+        .count()
+}
+
+// `12.34%|45.2MiB / 7.7GiB|0.57%`
+pub fn parse_docker_stats(text: &str) -> Option<(f64, String, f64)> {
+    let line = text.lines().find(|l| !l.trim().is_empty())?;
+    let parts: Vec<&str> = line.split('|').map(str::trim).collect();
+    let percent = |value: &str| value.trim_end_matches('%').parse::<f64>().ok();
+    match parts.as_slice() {
+        [cpu, memory, memory_percent] => Some((percent(cpu)?, memory.to_string(), percent(memory_percent)?)),
+        _ => None,
+    }
+}
+
+// `0.52 0.58 0.59 2/345 1234`: carga a 1, 5 y 15 min y procesos en ejecución/total
+pub fn parse_loadavg(text: &str) -> Option<([f64; 3], String)> {
+    let fields: Vec<&str> = text.split_whitespace().collect();
+    let load = |index: usize| fields.get(index)?.parse::<f64>().ok();
+    Some(([load(0)?, load(1)?, load(2)?], fields.get(3)?.to_string()))
+}
+
+// Recarga sin cortar las conexiones abiertas; None si el tipo no tiene servidor que recargar
+pub fn reload_command(server_type: &str) -> Option<&'static str> {
+    let server_type = server_type.to_lowercase();
+    if server_type.starts_with("nginx") {
+        Some("nginx -t && nginx -s reload")
+    } else if server_type.starts_with("apache") {
+        Some("apachectl -t && apachectl graceful")
+    } else if server_type.starts_with("php") {
+        Some(PHP_RELOAD_COMMAND)
+    } else {
+        None
+    }
+}
+
 impl AppServerUI {
     pub fn get_list_modules_command(&self, server_type: &str) -> String {
-        match server_type.to_lowercase().as_str() {
-            "apache" => "apache2ctl -M".to_string(),
-            "nginx" => "nginx -V".to_string(),
-            "php" => "php -m".to_string(),
-            _ => "status".to_string(),
+        let server_type = server_type.to_lowercase();
+        if server_type.starts_with("apache") {
+            "apachectl -M".to_string()
+        } else if server_type.starts_with("nginx") {
+            "nginx -V".to_string()
+        } else {
+            // Los appserver PHP de Lando no traen ni apachectl ni nginx en el PATH de todos
+            "php -m".to_string()
+        }
+    }
+
+    // El último ejecutado queda al final y sin repetidos
+    fn remember_command(&mut self, command: &str) {
+        self.command_history.retain(|previous| previous != command);
+        self.command_history.push(command.to_string());
+        if self.command_history.len() > COMMAND_HISTORY_LIMIT {
+            let excess = self.command_history.len() - COMMAND_HISTORY_LIMIT;
+            self.command_history.drain(..excess);
         }
     }
+
     pub fn execute_custom_command(
         &mut self,
         service: &LandoService,
         project_path: &PathBuf,
         sender: &Sender<LandoCommandOutcome>,
+        _is_loading: &mut bool,
+    ) {
+        let command = self.command_input.trim().to_string();
+        if command.is_empty() || self.maintenance.is_some() {
+            return;
+        }
+        self.remember_command(&command);
+        // La salida va al panel de logs, no al terminal global
+        self.run_in_logs(&command, command.clone(), None, false, service, project_path, sender);
+    }
+
+    // La receta se relee al cambiar de proyecto
+    pub fn refresh_recipe(&mut self, project_path: &PathBuf) {
+        if self.recipe_project.as_ref() == Some(project_path) {
+            return;
+        }
+        self.recipe = detect_recipe(project_path);
+        self.quick_actions = self.recipe.as_deref().map(quick_actions_for).unwrap_or_default();
+        self.tooling = detect_tooling(project_path);
+        self.composer.has_composer_json = project_path.join("composer.json").exists();
+        self.recipe_project = Some(project_path.clone());
+        self.quick_action_values.clear();
+    }
+
+    // Pide confirmación si la acción la requiere; si no, la lanza directamente
+    pub fn request_quick_action(
+        &mut self,
+        action: QuickAction,
+        service: &LandoService,
+        project_path: &PathBuf,
+        sender: &Sender<LandoCommandOutcome>,
         is_loading: &mut bool,
+        terminal: &mut LazyTerminal,
     ) {
-        if !self.command_input.trim().is_empty() {
-            *is_loading = true;
-            self.command_history.push(self.command_input.clone());
+        let Ok(args) = action.build_args(&self.quick_action_values) else {
+            return;
+        };
 
-            run_shell_command(
-                sender.clone(),
-                project_path.clone(),
-                service.service.clone(),
-                self.command_input.clone(),
+        if action.needs_confirmation {
+            let dialog = ConfirmDialog::new(
+                action.label,
+                "Esta acción modifica el proyecto (base de datos, configuración o archivos).",
+                &format!("lando {}", args.join(" ")),
+                &service.service,
             );
+            self.pending_quick_action = Some((action, args, dialog));
+        } else {
+            self.run_quick_action(&action, args, project_path, sender, is_loading, terminal);
+        }
+    }
+
+    pub fn run_quick_action(
+        &mut self,
+        action: &QuickAction,
+        args: Vec<String>,
+        project_path: &PathBuf,
+        sender: &Sender<LandoCommandOutcome>,
+        is_loading: &mut bool,
+        terminal: &mut LazyTerminal,
+    ) {
+        if action.interactive {
+            // Sesiones como `artisan tinker` necesitan un terminal de verdad
+            let line = interactive_command_line(project_path, &args);
+            terminal.write(line.into_bytes());
+            let _ = sender.send(LandoCommandOutcome::OpenTerminal);
+            return;
+        }
+
+        *is_loading = true;
+        run_lando_args(sender.clone(), args, project_path.clone());
+    }
+
+    pub fn is_php_service(&self, service: &LandoService) -> bool {
+        service.r#type.to_lowercase().starts_with("php")
+    }
+
+    pub fn check_xdebug(&mut self, service: &LandoService, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>) {
+        self.xdebug.checked = true;
+        self.xdebug.loading = true;
+        let request_id = next_request_id();
+        self.pending_requests.insert(request_id, AppServerPendingRequest::XdebugStatus);
+        run_shell_capture_tagged(sender.clone(), project_path.clone(), service.service.clone(), XDEBUG_STATUS_COMMAND.to_string(), request_id);
+    }
+
+    pub fn load_php_ini(&mut self, service: &LandoService, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>) {
+        self.php_ini.open = true;
+        self.php_ini.loading = true;
+        self.php_ini.error = None;
+        let request_id = next_request_id();
+        self.pending_requests.insert(request_id, AppServerPendingRequest::PhpIni);
+        run_shell_capture_tagged(sender.clone(), project_path.clone(), service.service.clone(), PHP_INI_COMMAND.to_string(), request_id);
+    }
+
+    pub fn handle_tagged_result(&mut self, request_id: u64, output: &Result<String, String>) -> bool {
+        let Some(request) = self.pending_requests.remove(&request_id) else {
+            return false;
+        };
+
+        match (request, output) {
+            (AppServerPendingRequest::XdebugStatus, Ok(text)) => {
+                self.xdebug.loading = false;
+                self.xdebug.loaded = Some(text.lines().any(|l| l.trim().eq_ignore_ascii_case("xdebug")));
+                self.xdebug.mode = text.lines()
+                    .find(|l| l.trim_start().to_lowercase().starts_with("xdebug.mode"))
+                    .and_then(|l| l.split("=>").nth(1))
+                    .map(|mode| mode.trim().to_string());
+            }
+            (AppServerPendingRequest::XdebugStatus, Err(err)) => {
+                self.xdebug.loading = false;
+                self.xdebug.loaded = None;
+                self.xdebug.message = Some(Err(err.clone()));
+            }
+            (AppServerPendingRequest::PhpIni, Ok(text)) => {
+                self.php_ini.loading = false;
+                self.php_ini.settings = self.parse_php_ini_settings(text);
+                self.php_ini.output = text.clone();
+            }
+            (AppServerPendingRequest::PhpIni, Err(err)) => {
+                self.php_ini.loading = false;
+                self.php_ini.error = Some(err.clone());
+            }
+            (AppServerPendingRequest::ComposerOutdated, Ok(text)) => self.apply_composer_outdated(text),
+            (AppServerPendingRequest::ComposerOutdated, Err(err)) => {
+                self.composer.loading_outdated = false;
+                self.composer.error = Some(err.clone());
+            }
+            (AppServerPendingRequest::ComposerPhpVersion, Ok(text)) => self.apply_container_php_version(text),
+            (AppServerPendingRequest::ComposerPhpVersion, Err(_)) => self.composer.container_php = None,
+            (
+                request @ (AppServerPendingRequest::ContainerStats
+                | AppServerPendingRequest::Connections
+                | AppServerPendingRequest::LoadAverage),
+                output,
+            ) => self.apply_monitoring_result(request, output),
+        }
+
+        true
+    }
+
+    // `php --ini` da "Clave: valor"; `php -i` da "clave => local => master"
+    pub fn parse_php_ini_settings(&self, text: &str) -> Vec<(String, String)> {
+        let after_colon = |prefix: &str| {
+            text.lines()
+                .find_map(|l| l.trim().strip_prefix(prefix))
+                .map(|value| value.trim().to_string())
+        };
+        let php_i_value = |key: &str| {
+            text.lines()
+                .map(|l| l.split("=>").map(str::trim).collect::<Vec<_>>())
+                .find(|parts| parts.len() >= 2 && parts[0] == key)
+                .map(|parts| parts[1].to_string())
+        };
+
+        let mut settings = vec![
+            ("php.ini cargado".to_string(), after_colon("Loaded Configuration File:").unwrap_or_else(|| "(ninguno)".to_string())),
+            (".ini adicionales".to_string(), after_colon("Additional .ini files parsed:").unwrap_or_else(|| "(ninguno)".to_string())),
+        ];
+        for &key in PHP_INI_KEYS {
+            settings.push((key.to_string(), php_i_value(key).unwrap_or_else(|| "-".to_string())));
+        }
+        settings.push((
+            "opcache.enable".to_string(),
+            php_i_value("opcache.enable").unwrap_or_else(|| "OPcache no cargado".to_string()),
+        ));
+        settings
+    }
+
+    // Con `xdebug-on`/`xdebug-off` en el tooling se usan; si no, se cambia el
+    // `.lando.yml` y se ofrece reconstruir para aplicar el cambio.
+    pub fn set_xdebug(
+        &mut self,
+        enabled: bool,
+        service: &LandoService,
+        project_path: &PathBuf,
+        sender: &Sender<LandoCommandOutcome>,
+        is_loading: &mut bool,
+    ) {
+        let tool = if enabled { "xdebug-on" } else { "xdebug-off" };
+        if self.tooling.iter().any(|t| t == tool) {
+            *is_loading = true;
+            self.xdebug.message = Some(Ok(format!("Lanzado 'lando {}'. Vuelve a comprobar cuando termine.", tool)));
+            run_lando_args(sender.clone(), vec![tool.to_string()], project_path.clone());
+            return;
+        }
+
+        match set_xdebug_in_lando_file(project_path, &service.service, enabled) {
+            Ok(()) => {
+                self.xdebug.message = Some(Ok(format!(
+                    "xdebug: {} guardado en .lando.yml (copia en .lando.yml.bak).",
+                    enabled
+                )));
+                self.pending_rebuild = Some(ConfirmDialog::new(
+                    "Reconstruir app",
+                    "El cambio de xdebug no se aplica hasta reconstruir la app.",
+                    "lando rebuild -y",
+                    &service.service,
+                ));
+            }
+            Err(err) => self.xdebug.message = Some(Err(err)),
         }
     }
 
+    pub fn run_rebuild(&mut self, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>, is_loading: &mut bool) {
+        *is_loading = true;
+        self.xdebug.message = Some(Ok("Reconstruyendo... vuelve a comprobar cuando termine.".to_string()));
+        run_lando_args(sender.clone(), vec!["rebuild".to_string(), "-y".to_string()], project_path.clone());
+    }
+
     pub fn refresh_service_status(&mut self) {}
     pub fn restart_service(&mut self) {}
     pub fn start_service(&mut self) {}
@@ -41,8 +323,127 @@ impl AppServerUI {
     pub fn restart_service_with_feedback(&mut self, _service: &LandoService, _project_path: &PathBuf, _sender: &Sender<LandoCommandOutcome>, _is_loading: &mut bool) {}
     pub fn stop_service_with_feedback(&mut self, _service: &LandoService, _project_path: &PathBuf, _sender: &Sender<LandoCommandOutcome>, _is_loading: &mut bool) {}
     pub fn start_service_with_feedback(&mut self, _service: &LandoService, _project_path: &PathBuf, _sender: &Sender<LandoCommandOutcome>, _is_loading: &mut bool) {}
-    pub fn reload_configuration(&mut self, _service: &LandoService, _project_path: &PathBuf, _sender: &Sender<LandoCommandOutcome>, _is_loading: &mut bool) {}
-    pub fn clear_cache(&mut self, _service: &LandoService, _project_path: &PathBuf, _sender: &Sender<LandoCommandOutcome>, _is_loading: &mut bool) {}
+    pub fn reload_configuration(&mut self, service: &LandoService, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>, _is_loading: &mut bool) {
+        let Some(command) = reload_command(&service.r#type) else {
+            self.append_log(&format!("⚠️ No se sabe recargar un servicio de tipo '{}'", service.r#type));
+            self.current_tab = AppServerTab::Logs;
+            return;
+        };
+        self.run_maintenance("Recarga de configuración", command.to_string(), Some("root"), service, project_path, sender);
+    }
+
+    // Caché del framework si se reconoce; si no, OPcache, que en Apache y
+    // php-fpm solo se vacía recargando el servidor (`opcache_reset` desde la
+    // CLI limpiaría la caché de la CLI, no la de las peticiones web)
+    pub fn clear_cache(&mut self, service: &LandoService, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>, _is_loading: &mut bool) {
+        self.refresh_recipe(project_path);
+        let recipe = self.recipe.as_deref().unwrap_or_default();
+        let framework = if project_path.join("artisan").exists() {
+            Some("php artisan cache:clear")
+        } else if recipe.starts_with("drupal") {
+            Some("drush cr")
+        } else if recipe == "wordpress" {
+            Some("wp cache flush")
+        } else {
+            None
+        };
+
+        if let Some(command) = framework.filter(|_| self.is_php_service(service)) {
+            self.run_maintenance("Limpieza de caché", command.to_string(), None, service, project_path, sender);
+        } else if service.r#type.to_lowercase().starts_with("nginx") {
+            let command = "rm -rf /var/cache/nginx/* && nginx -s reload".to_string();
+            self.run_maintenance("Limpieza de caché", command, Some("root"), service, project_path, sender);
+        } else if let Some(command) = reload_command(&service.r#type) {
+            self.run_maintenance("Limpieza de OPcache", command.to_string(), Some("root"), service, project_path, sender);
+        } else {
+            self.append_log(&format!("⚠️ No se conoce ninguna caché para un servicio de tipo '{}'", service.r#type));
+            self.current_tab = AppServerTab::Logs;
+        }
+    }
+    fn run_maintenance(
+        &mut self,
+        label: &str,
+        command: String,
+        user: Option<&str>,
+        service: &LandoService,
+        project_path: &PathBuf,
+        sender: &Sender<LandoCommandOutcome>,
+    ) {
+        self.run_in_logs(label, command, user, true, service, project_path, sender);
+    }
+
+    // Transmite la salida de `command` al panel de logs. Solo las tareas de
+    // mantenimiento cambian el estado del servicio; un comando suelto que falla no lo rompe.
+    #[allow(clippy::too_many_arguments)]
+    fn run_in_logs(
+        &mut self,
+        label: &str,
+        command: String,
+        user: Option<&str>,
+        updates_status: bool,
+        service: &LandoService,
+        project_path: &PathBuf,
+        sender: &Sender<LandoCommandOutcome>,
+    ) {
+        let request_id = next_request_id();
+        self.append_log(&format!("$ {}\n", command));
+        self.current_tab = AppServerTab::Logs;
+        self.maintenance = Some(MaintenanceRun {
+            request_id,
+            label: label.to_string(),
+            updates_status,
+            pending_bytes: Vec::new(),
+        });
+        run_shell_stream_tagged_as(
+            sender.clone(),
+            project_path.clone(),
+            service.service.clone(),
+            user.map(String::from),
+            command,
+            request_id,
+            Arc::new(AtomicBool::new(false)),
+        );
+    }
+
+    pub fn handle_stream_chunk(&mut self, request_id: u64, chunk: &[u8]) -> bool {
+        let Some(run) = self.maintenance.as_mut().filter(|r| r.request_id == request_id) else {
+            return self.handle_composer_chunk(request_id, chunk);
+        };
+        let text = decode_utf8_stream(&mut run.pending_bytes, chunk);
+        self.logs_output.push_str(&text);
+        true
+    }
+
+    pub fn handle_stream_finished(&mut self, request_id: u64, result: &Result<(), String>) -> bool {
+        let Some(run) = self.maintenance.take_if(|r| r.request_id == request_id) else {
+            return self.handle_composer_finished(request_id, result);
+        };
+        if !run.pending_bytes.is_empty() {
+            self.logs_output.push_str(&String::from_utf8_lossy(&run.pending_bytes));
+        }
+        match result {
+            Ok(()) if run.updates_status => {
+                self.append_log(&format!("✅ {} terminada", run.label));
+                self.service_status = ServiceStatus::Running;
+            }
+            Ok(()) => self.append_log(&format!("✅ '{}' terminó con éxito", run.label)),
+            Err(msg) => {
+                self.append_log(&format!("❌ {}", msg));
+                if run.updates_status {
+                    self.service_status = ServiceStatus::Error(format!("{} fallida", run.label));
+                }
+            }
+        }
+        true
+    }
+
+    fn append_log(&mut self, text: &str) {
+        if !self.logs_output.is_empty() && !self.logs_output.ends_with('\n') {
+            self.logs_output.push('\n');
+        }
+        self.logs_output.push_str(text);
+    }
+
     pub fn test_connection(&mut self, _service: &LandoService, _project_path: &PathBuf, _sender: &Sender<LandoCommandOutcome>, _is_loading: &mut bool) {}
     pub fn refresh_logs(&mut self, _service: &LandoService, _project_path: &PathBuf, _sender: &Sender<LandoCommandOutcome>, _is_loading: &mut bool) {}
     pub fn export_logs(&mut self) {}
@@ -61,7 +462,79 @@ impl AppServerUI {
     }
     pub fn apply_environment_changes(&mut self, _service: &LandoService, _project_path: &PathBuf, _sender: &Sender<LandoCommandOutcome>, _is_loading: &mut bool) {}
     pub fn reload_environment_variables(&mut self, _service: &LandoService, _project_path: &PathBuf, _sender: &Sender<LandoCommandOutcome>, _is_loading: &mut bool) {}
-    pub fn get_server_stats(&mut self, _service: &LandoService, _project_path: &PathBuf, _sender: &Sender<LandoCommandOutcome>, _is_loading: &mut bool) {}
-    pub fn get_active_connections(&mut self, _service: &LandoService, _project_path: &PathBuf, _sender: &Sender<LandoCommandOutcome>, _is_loading: &mut bool) {}
-    pub fn get_performance_metrics(&mut self, _service: &LandoService, _project_path: &PathBuf, _sender: &Sender<LandoCommandOutcome>, _is_loading: &mut bool) {}
+
+    // CPU y memoria del contenedor según Docker, no solo las del proceso del servidor
+    pub fn get_server_stats(&mut self, service: &LandoService, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>, _is_loading: &mut bool) {
+        let app_name = detect_app_name(project_path)
+            .unwrap_or_else(|| project_path.file_name().unwrap_or_default().to_string_lossy().to_string());
+        let request_id = self.monitoring_request(AppServerPendingRequest::ContainerStats);
+        run_container_stats_tagged(sender.clone(), compose_project_name(&app_name), service.service.clone(), request_id);
+    }
+
+    pub fn get_active_connections(&mut self, service: &LandoService, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>, _is_loading: &mut bool) {
+        let request_id = self.monitoring_request(AppServerPendingRequest::Connections);
+        run_shell_capture_tagged(sender.clone(), project_path.clone(), service.service.clone(), CONNECTIONS_COMMAND.to_string(), request_id);
+    }
+
+    pub fn get_performance_metrics(&mut self, service: &LandoService, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>, _is_loading: &mut bool) {
+        let request_id = self.monitoring_request(AppServerPendingRequest::LoadAverage);
+        run_shell_capture_tagged(sender.clone(), project_path.clone(), service.service.clone(), LOADAVG_COMMAND.to_string(), request_id);
+    }
+
+    fn monitoring_request(&mut self, request: AppServerPendingRequest) -> u64 {
+        let request_id = next_request_id();
+        self.pending_requests.insert(request_id, request);
+        self.monitoring.pending += 1;
+        self.monitoring.last_refresh = Some(Instant::now());
+        request_id
+    }
+
+    // Se llama en cada frame con la pestaña abierta; no se solapan lecturas
+    pub fn refresh_monitoring_if_due(&mut self, service: &LandoService, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>, is_loading: &mut bool) {
+        let due = self.monitoring.pending == 0
+            && self.monitoring.last_refresh.is_none_or(|t| t.elapsed() >= MONITORING_REFRESH_INTERVAL);
+        if due {
+            self.monitoring.error = None;
+            self.get_server_stats(service, project_path, sender, is_loading);
+            self.get_active_connections(service, project_path, sender, is_loading);
+            self.get_performance_metrics(service, project_path, sender, is_loading);
+        }
+    }
+
+    fn apply_monitoring_result(&mut self, request: AppServerPendingRequest, output: &Result<String, String>) {
+        self.monitoring.pending = self.monitoring.pending.saturating_sub(1);
+        let text = match output {
+            Ok(text) => text,
+            Err(err) => {
+                self.monitoring.error = Some(err.clone());
+                return;
+            }
+        };
+
+        match request {
+            AppServerPendingRequest::ContainerStats if text.trim().is_empty() => {
+                self.monitoring.cpu_percent = None;
+                self.monitoring.memory_usage = None;
+                self.monitoring.memory_percent = None;
+                self.service_status = ServiceStatus::Stopped;
+            }
+            AppServerPendingRequest::ContainerStats => match parse_docker_stats(text) {
+                Some((cpu, memory, memory_percent)) => {
+                    self.monitoring.cpu_percent = Some(cpu);
+                    self.monitoring.memory_usage = Some(memory);
+                    self.monitoring.memory_percent = Some(memory_percent);
+                    self.service_status = ServiceStatus::Running;
+                }
+                None => self.monitoring.error = Some(format!("Salida de docker stats inesperada: {}", text.trim())),
+            },
+            AppServerPendingRequest::Connections => self.monitoring.connections = Some(count_web_connections(text)),
+            AppServerPendingRequest::LoadAverage => {
+                if let Some((load, processes)) = parse_loadavg(text) {
+                    self.monitoring.load_average = Some(load);
+                    self.monitoring.processes = Some(processes);
+                }
+            }
+            _ => {}
+        }
+    }
 }
\ No newline at end of file
@@ -0,0 +1,230 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::Sender;
+use std::thread;
+use std::time::UNIX_EPOCH;
+
+use crate::core::commands::run_lando_step;
+use crate::core::recipes::detect_app_name;
+use crate::core::result_format::format_day_number;
+use crate::core::runner::system_runner;
+use crate::core::time::now_secs;
+use crate::models::backups::BackupEntry;
+use crate::models::commands::LandoCommandOutcome;
+use crate::models::lando::LandoService;
+use crate::ui::confirm::ConfirmDialog;
+use crate::ui::database::{DatabaseUI, ToolCommand};
+
+// Índice que la app mantiene dentro de la carpeta de backups: guarda el servicio
+// de origen y la fecha de cada export, que el nombre del archivo no siempre da
+const BACKUP_INDEX_FILE: &str = ".lando_gui_backups.json";
+pub const DEFAULT_BACKUP_DIR: &str = "backups";
+const BACKUP_EXTENSIONS: &[&str] = &[".sql.gz", ".sql"];
+
+// `YYYYmmdd-HHMM` en UTC
+pub fn backup_timestamp(secs: u64) -> String {
+    let date = format_day_number((secs / 86400) as f64).replace('-', "");
+    let minutes = (secs % 86400) / 60;
+    format!("{}-{:02}{:02}", date, minutes / 60, minutes % 60)
+}
+
+// `<app>-<service>-YYYYmmdd-HHMM.sql.gz`, sin caracteres raros para la shell
+pub fn backup_file_name(app: &str, service: &str, secs: u64) -> String {
+    let clean = |value: &str| -> String {
+        value.chars()
+            .map(|c| if c.is_ascii_alphanumeric() || c == '_' { c } else { '-' })
+            .collect()
+    };
+    format!("{}-{}-{}.sql.gz", clean(app), clean(service), backup_timestamp(secs))
+}
+
+pub fn is_backup_file(name: &str) -> bool {
+    BACKUP_EXTENSIONS.iter().any(|ext| name.ends_with(ext))
+}
+
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: &[&str] = &["B", "KB", "MB", "GB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 { format!("{} B", bytes) } else { format!("{:.1} {}", size, UNITS[unit]) }
+}
+
+pub fn format_backup_date(secs: u64) -> String {
+    let minutes = (secs % 86400) / 60;
+    format!("{} {:02}:{:02} UTC", format_day_number((secs / 86400) as f64), minutes / 60, minutes % 60)
+}
+
+fn read_index(dir: &Path) -> Vec<BackupEntry> {
+    fs::read_to_string(dir.join(BACKUP_INDEX_FILE))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn write_index(dir: &Path, entries: &[BackupEntry]) -> io::Result<()> {
+    let content = serde_json::to_string_pretty(entries).map_err(io::Error::other)?;
+    fs::write(dir.join(BACKUP_INDEX_FILE), content)
+}
+
+// Une el índice con lo que hay realmente en disco: lo borrado por fuera
+// desaparece, lo que apareció sin pasar por la app se añade sin servicio.
+// Devuelve los backups de más reciente a más antiguo.
+pub fn reconcile_backups(dir: &Path) -> io::Result<Vec<BackupEntry>> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let indexed = read_index(dir);
+    let mut entries = Vec::new();
+    for item in fs::read_dir(dir)? {
+        let item = item?;
+        let file_name = item.file_name().to_string_lossy().to_string();
+        let metadata = item.metadata()?;
+        if !metadata.is_file() || !is_backup_file(&file_name) {
+            continue;
+        }
+
+        let modified = metadata.modified().ok()
+            .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        let known = indexed.iter().find(|e| e.file_name == file_name);
+        entries.push(BackupEntry {
+            file_name,
+            service: known.and_then(|e| e.service.clone()),
+            created_at: known.map(|e| e.created_at).unwrap_or(modified),
+            size: metadata.len(),
+        });
+    }
+
+    entries.sort_by(|a, b| b.created_at.cmp(&a.created_at).then_with(|| b.file_name.cmp(&a.file_name)));
+    // Si la carpeta es de solo lectura se lista igualmente
+    if entries != indexed {
+        let _ = write_index(dir, &entries);
+    }
+    Ok(entries)
+}
+
+// Lo que sobra al conservar los `keep` más recientes de `service`
+pub fn backups_beyond_retention<'a>(entries: &'a [BackupEntry], service: &str, keep: usize) -> Vec<&'a BackupEntry> {
+    entries.iter()
+        .filter(|e| e.service.as_deref() == Some(service))
+        .skip(keep)
+        .collect()
+}
+
+fn send_backups(sender: &Sender<LandoCommandOutcome>, service: &str, dir: &Path) {
+    let result = reconcile_backups(dir)
+        .map_err(|e| format!("No se pudo leer la carpeta de backups '{}': {}", dir.display(), e));
+    let _ = sender.send(LandoCommandOutcome::BackupsLoaded { service: service.to_string(), result });
+}
+
+pub fn load_backups(sender: Sender<LandoCommandOutcome>, service: String, dir: PathBuf) {
+    thread::spawn(move || send_backups(&sender, &service, &dir));
+}
+
+// `lando db-export` transmitiendo al terminal; si termina bien se apunta en el
+// índice y se vuelve a listar la carpeta
+pub fn run_backup_export(sender: Sender<LandoCommandOutcome>, project_path: PathBuf, service: String, dir: PathBuf) {
+    thread::spawn(move || {
+        if let Err(e) = fs::create_dir_all(&dir) {
+            let _ = sender.send(LandoCommandOutcome::Error(format!("No se pudo crear la carpeta de backups '{}': {}", dir.display(), e)));
+            return;
+        }
+
+        let created_at = now_secs();
+        let app = detect_app_name(&project_path).unwrap_or_else(|| "app".to_string());
+        let file_name = backup_file_name(&app, &service, created_at);
+        let target = dir.strip_prefix(&project_path).unwrap_or(&dir).join(&file_name);
+        let args = vec![
+            "db-export".to_string(),
+            target.to_string_lossy().to_string(),
+            "-s".to_string(),
+            service.clone(),
+        ];
+
+        let runner = system_runner();
+        let outcome = run_lando_step(runner.as_ref(), &sender, args, &project_path);
+        if matches!(outcome, LandoCommandOutcome::CommandSuccess(_)) {
+            let mut entries = read_index(&dir);
+            entries.retain(|e| e.file_name != file_name);
+            entries.push(BackupEntry { file_name, service: Some(service.clone()), created_at, size: 0 });
+            let _ = write_index(&dir, &entries);
+        }
+        let _ = sender.send(outcome);
+        send_backups(&sender, &service, &dir);
+    });
+}
+
+pub fn delete_backups(sender: Sender<LandoCommandOutcome>, service: String, dir: PathBuf, files: Vec<String>) {
+    thread::spawn(move || {
+        let failed: Vec<String> = files.iter()
+            .filter(|name| fs::remove_file(dir.join(name)).is_err())
+            .cloned()
+            .collect();
+        if failed.is_empty() {
+            let _ = sender.send(LandoCommandOutcome::CommandSuccess(format!("{} backups eliminados.", files.len())));
+        } else {
+            let _ = sender.send(LandoCommandOutcome::Error(format!("No se pudieron eliminar: {}", failed.join(", "))));
+        }
+        send_backups(&sender, &service, &dir);
+    });
+}
+
+impl DatabaseUI {
+    pub fn backup_dir(&self, project_path: &Path) -> PathBuf {
+        let dir = self.backups.dir.trim();
+        project_path.join(if dir.is_empty() { DEFAULT_BACKUP_DIR } else { dir })
+    }
+
+    pub fn refresh_backups(&mut self, service: &LandoService, project_path: &Path, sender: &Sender<LandoCommandOutcome>) {
+        self.backups.loading = true;
+        self.backups.loaded_for = Some(project_path.to_path_buf());
+        load_backups(sender.clone(), service.service.clone(), self.backup_dir(project_path));
+    }
+
+    pub fn handle_backups_loaded(&mut self, result: Result<Vec<BackupEntry>, String>) {
+        self.backups.loading = false;
+        match result {
+            Ok(entries) => {
+                if self.backups.selected.as_ref().is_some_and(|s| !entries.iter().any(|e| &e.file_name == s)) {
+                    self.backups.selected = None;
+                }
+                self.backups.entries = entries;
+                self.backups.error = None;
+            }
+            Err(e) => self.backups.error = Some(e),
+        }
+    }
+
+    pub fn create_backup(&mut self, service: &LandoService, project_path: &Path, sender: &Sender<LandoCommandOutcome>, is_loading: &mut bool) {
+        *is_loading = true;
+        self.backups.loading = true;
+        run_backup_export(sender.clone(), project_path.to_path_buf(), service.service.clone(), self.backup_dir(project_path));
+    }
+
+    // Restaurar es un `db-import` normal, con su confirmación destructiva
+    pub fn request_restore_backup(&mut self, service: &LandoService, project_path: &Path, file_name: &str) {
+        let path = self.backup_dir(project_path).join(file_name);
+        self.request_import(service, &path);
+    }
+
+    pub fn request_delete_backups(&mut self, service: &LandoService, project_path: &Path, files: Vec<String>) {
+        if files.is_empty() {
+            return;
+        }
+        let dir = self.backup_dir(project_path);
+        let description = format!(
+            "Se borrarán {} archivos de '{}'. Esta acción no se puede deshacer.",
+            files.len(),
+            dir.display()
+        );
+        let dialog = ConfirmDialog::new("Eliminar backups", &description, &files.join("\n"), &service.service).destructive();
+        self.pending_tool = Some((ToolCommand::DeleteBackups(files), dialog));
+    }
+}
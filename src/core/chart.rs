@@ -0,0 +1,84 @@
+use std::fs;
+
+use crate::core::result_format::{build_chart_data, chart_data_to_csv, infer_column_kind, parse_result_set, ColumnKind, ParsedResultSet};
+use crate::ui::database::DatabaseUI;
+
+// Gráfico del resultado seleccionado: se recalcula solo cuando cambia el
+// resultado o las columnas elegidas.
+impl DatabaseUI {
+    pub fn refresh_chart(&mut self) {
+        let Some(result) = self.query_results.get(self.current_result_index) else {
+            self.chart.source = None;
+            self.chart.result_set = None;
+            self.chart.data = None;
+            return;
+        };
+
+        let source = (self.current_result_index, result.timestamp, self.chart.x_column, self.chart.y_column);
+        if self.chart.source == Some(source) {
+            return;
+        }
+
+        let result_changed = self.chart.source.is_none_or(|(index, timestamp, _, _)| {
+            index != self.current_result_index || timestamp != result.timestamp
+        });
+        if result_changed {
+            self.chart.result_set = if result.has_error {
+                None
+            } else {
+                parse_result_set(&result.result).filter(|set| set.columns.len() >= 2)
+            };
+            if let Some(set) = &self.chart.result_set {
+                let (x, y) = Self::default_chart_columns(set);
+                self.chart.x_column = x;
+                self.chart.y_column = y;
+            }
+            self.chart.export_status = None;
+        }
+
+        self.chart.data = self.chart.result_set.as_ref()
+            .map(|set| build_chart_data(set, self.chart.x_column, self.chart.y_column));
+        self.chart.source = Some((self.current_result_index, result.timestamp, self.chart.x_column, self.chart.y_column));
+    }
+
+    // X = primera columna; Y = primera columna numérica después de ella
+    fn default_chart_columns(set: &ParsedResultSet) -> (usize, usize) {
+        let y = (1..set.columns.len())
+            .find(|c| {
+                infer_column_kind(set.rows.iter().filter_map(|r| r.get(*c)).map(String::as_str)) == ColumnKind::Numeric
+            })
+            .unwrap_or(1);
+        (0, y)
+    }
+
+    pub fn chart_column_name(&self, column: usize) -> &str {
+        self.chart.result_set.as_ref()
+            .and_then(|set| set.columns.get(column))
+            .map(String::as_str)
+            .unwrap_or("")
+    }
+
+    pub fn export_chart_to_csv(&mut self) {
+        let Some(data) = &self.chart.data else {
+            return;
+        };
+        let csv = chart_data_to_csv(
+            data,
+            self.chart_column_name(self.chart.x_column),
+            self.chart_column_name(self.chart.y_column),
+        );
+
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter("CSV", &["csv"])
+            .set_file_name("grafico.csv")
+            .save_file()
+        else {
+            return;
+        };
+
+        self.chart.export_status = Some(match fs::write(&path, csv) {
+            Ok(()) => Ok(format!("Datos exportados a {}", path.display())),
+            Err(e) => Err(format!("No se pudo escribir {}: {}", path.display(), e)),
+        });
+    }
+}
@@ -1,47 +1,71 @@
-use std::io::{BufReader, Read};
-use std::path::PathBuf;
-use std::process::{Command, Stdio};
-use std::sync::mpsc::Sender;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, Mutex};
 use std::thread;
+use std::time::Duration;
 use walkdir::WalkDir;
+use crate::models::bulk::BulkStatus;
 use crate::models::commands::LandoCommandOutcome;
-use crate::models::lando::{LandoApp, LandoService};
+use crate::models::journal::Invocation;
+use crate::models::lando::DbConnection;
+use crate::models::secret::SecretString;
+use crate::core::lando_json::{parse_apps, parse_services};
+use crate::core::logging::redact_secrets;
+use crate::core::prompts::{detect_prompt, trim_prompt_tail, with_auto_yes};
+use crate::core::runner::{system_runner, CommandRunner};
+use crate::models::prompts::LandoPrompt;
 
 // Lanza un comando `lando list` en un hilo separado.
 pub fn list_apps(sender: Sender<LandoCommandOutcome>) {
+    list_apps_with(system_runner(), sender);
+}
+
+pub fn list_apps_with(runner: Arc<dyn CommandRunner>, sender: Sender<LandoCommandOutcome>) {
     thread::spawn(move || {
-        let output = Command::new("lando")
-            .args(["list", "--format", "json"])
-            .output();
-
-        let outcome = match output {
-            Ok(output) => {
-                if output.status.success() {
-                    match serde_json::from_slice::<Vec<LandoApp>>(&output.stdout) {
-                        Ok(apps) => LandoCommandOutcome::List(apps),
-                        Err(e) => LandoCommandOutcome::Error(format!("Error al parsear JSON: {}", e)),
+        let _ = sender.send(list_apps_outcome(runner.as_ref()));
+    });
+}
+
+pub fn list_apps_outcome(runner: &dyn CommandRunner) -> LandoCommandOutcome {
+    match runner.run("lando", &to_args(&["list", "--format", "json"]), None) {
+        Ok(output) => {
+            if output.success {
+                match parse_apps(&output.stdout) {
+                    Ok(apps) => LandoCommandOutcome::List(apps),
+                    Err(e) => {
+                        log::warn!("Salida de lando list no reconocida: {}", output.stdout_lossy());
+                        LandoCommandOutcome::Error(format!("Error al parsear JSON: {}", e))
                     }
-                } else {
-                    let stderr = String::from_utf8_lossy(&output.stderr);
-                    LandoCommandOutcome::Error(format!("Error de Lando: {}", stderr))
                 }
+            } else {
+                LandoCommandOutcome::Error(format!("Error de Lando: {}", output.stderr_lossy()))
             }
-            Err(e) => LandoCommandOutcome::Error(format!("No se pudo ejecutar Lando: {}", e)),
-        };
+        }
+        Err(e) => LandoCommandOutcome::Error(format!("No se pudo ejecutar Lando: {}", e)),
+    }
+}
 
-        let _ = sender.send(outcome);
-    });
+pub fn to_args(args: &[&str]) -> Vec<String> {
+    args.iter().map(|a| a.to_string()).collect()
+}
+
+// Cita un valor para `sh -c` (comandos de `lando ssh` o del terminal)
+pub fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
 }
 
-// Escanea un directorio en busca de proyectos Lando (`.lando.yml`)
+// Limita la profundidad del escaneo para no tardar demasiado
+pub const PROJECT_SCAN_DEPTH: usize = 3;
+
+// Escanea un directorio en busca de proyectos Lando (`.lando.yml` o `.lando.yaml`)
 pub fn scan_for_projects(sender: Sender<LandoCommandOutcome>, path_to_scan: PathBuf) {
     thread::spawn(move || {
         let mut projects = vec![];
-        // Limita la profundidad para no tardar demasiado
-        let walker = WalkDir::new(path_to_scan).max_depth(3);
+        let walker = WalkDir::new(path_to_scan).max_depth(PROJECT_SCAN_DEPTH);
 
         for entry in walker.into_iter().filter_map(|e| e.ok()) {
-            if entry.file_name() == ".lando.yml" {
+            if entry.file_name() == ".lando.yml" || entry.file_name() == ".lando.yaml" {
                 if let Some(parent) = entry.path().parent() {
                     projects.push(parent.to_path_buf());
                 }
@@ -52,259 +76,913 @@ pub fn scan_for_projects(sender: Sender<LandoCommandOutcome>, path_to_scan: Path
     });
 }
 
+pub fn is_lando_project(path: &Path) -> bool {
+    [".lando.yml", ".lando.yaml"].iter().any(|name| path.join(name).is_file())
+}
+
+// Elige una carpeta concreta y la añade como proyecto si tiene archivo de Lando
+pub fn add_project_folder(sender: Sender<LandoCommandOutcome>) {
+    thread::spawn(move || {
+        let outcome = match rfd::FileDialog::new().pick_folder() {
+            Some(path) if is_lando_project(&path) => LandoCommandOutcome::Projects(vec![path]),
+            Some(path) => LandoCommandOutcome::Error(format!(
+                "'{}' no contiene un .lando.yml ni un .lando.yaml",
+                path.display()
+            )),
+            None => LandoCommandOutcome::FinishedLoading,
+        };
+        let _ = sender.send(outcome);
+    });
+}
+
+// Lee un archivo .sql en segundo plano para cargarlo en el editor de consultas.
+pub fn load_sql_file(sender: Sender<LandoCommandOutcome>, service: String, path: PathBuf) {
+    thread::spawn(move || {
+        let outcome = match std::fs::read(&path) {
+            Ok(bytes) => match decode_text_file(bytes) {
+                Ok(content) => LandoCommandOutcome::SqlFileLoaded { service, path, content },
+                Err(encoding) => LandoCommandOutcome::Error(format!(
+                    "El archivo '{}' no está en UTF-8 (codificación detectada: {}). Conviértelo antes de cargarlo.",
+                    path.display(),
+                    encoding
+                )),
+            },
+            Err(e) => LandoCommandOutcome::Error(format!("No se pudo leer '{}': {}", path.display(), e)),
+        };
+
+        let _ = sender.send(outcome);
+    });
+}
+
+// Decodifica un archivo de texto. UTF-8 (con o sin BOM) y UTF-16 con BOM se aceptan;
+// para cualquier otra cosa se devuelve la codificación probable en vez de mutilar el texto.
+pub fn decode_text_file(bytes: Vec<u8>) -> Result<String, String> {
+    if let Some(rest) = bytes.strip_prefix(&[0xEF, 0xBB, 0xBF]) {
+        return String::from_utf8(rest.to_vec()).map_err(|_| "UTF-8 con BOM inválido".to_string());
+    }
+
+    let utf16 = |data: &[u8], little_endian: bool| {
+        let units: Vec<u16> = data
+            .chunks_exact(2)
+            .map(|c| if little_endian { u16::from_le_bytes([c[0], c[1]]) } else { u16::from_be_bytes([c[0], c[1]]) })
+            .collect();
+        String::from_utf16(&units).map_err(|_| "UTF-16 inválido".to_string())
+    };
+    if let Some(rest) = bytes.strip_prefix(&[0xFF, 0xFE]) {
+        return utf16(rest, true);
+    }
+    if let Some(rest) = bytes.strip_prefix(&[0xFE, 0xFF]) {
+        return utf16(rest, false);
+    }
+
+    match String::from_utf8(bytes) {
+        Ok(content) => Ok(content),
+        Err(e) => {
+            let bytes = e.into_bytes();
+            if bytes.contains(&0) {
+                Err("UTF-16 sin BOM o binario".to_string())
+            } else {
+                Err("ISO-8859-1 / Windows-1252".to_string())
+            }
+        }
+    }
+}
+
+// Decodifica un fragmento de salida que puede cortar un carácter multibyte a la mitad.
+// Los bytes incompletos del final se guardan en `pending` para el siguiente fragmento.
+pub fn decode_utf8_stream(pending: &mut Vec<u8>, chunk: &[u8]) -> String {
+    pending.extend_from_slice(chunk);
+
+    let mut text = String::new();
+    let mut rest: &[u8] = pending;
+    loop {
+        match std::str::from_utf8(rest) {
+            Ok(valid) => {
+                text.push_str(valid);
+                rest = &[];
+                break;
+            }
+            Err(e) => {
+                let (valid, after) = rest.split_at(e.valid_up_to());
+                text.push_str(&String::from_utf8_lossy(valid));
+                match e.error_len() {
+                    // Bytes inválidos en medio: se sustituyen y se sigue
+                    Some(len) => {
+                        text.push(char::REPLACEMENT_CHARACTER);
+                        rest = &after[len..];
+                    }
+                    // Secuencia incompleta al final: esperar al resto
+                    None => {
+                        rest = after;
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
+    let consumed = pending.len() - rest.len();
+    pending.drain(..consumed);
+    text
+}
+
 // Ejecuta un comando de lando en el directorio de un proyecto y transmite la salida.
 pub fn run_lando_command(sender: Sender<LandoCommandOutcome>, command: String, project_path: PathBuf) {
+    let args = command.split_whitespace().map(String::from).collect();
+    run_lando_args(sender, args, project_path);
+}
+
+// Igual que `run_lando_command` pero con los argumentos ya separados, para valores
+// que pueden contener espacios (rutas de archivos, contraseñas...).
+pub fn run_lando_args(sender: Sender<LandoCommandOutcome>, args: Vec<String>, project_path: PathBuf) {
+    run_lando_args_with(system_runner(), sender, args, project_path);
+}
+
+pub fn run_lando_args_with(runner: Arc<dyn CommandRunner>, sender: Sender<LandoCommandOutcome>, args: Vec<String>, project_path: PathBuf) {
     thread::spawn(move || {
-        let mut child = match Command::new("lando")
-            .arg(command.clone())
-            .current_dir(project_path.clone())
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()
-        {
-            Ok(child) => child,
-            Err(e) => {
-                let _ = sender.send(LandoCommandOutcome::Error(format!(
-                    "No se pudo ejecutar Lando: {}",
-                    e
-                )));
-                return;
+        let outcome = run_lando_step(runner.as_ref(), &sender, args, &project_path);
+        let _ = sender.send(outcome);
+    });
+}
+
+// Ejecuta `lando <args>` en el hilo actual transmitiendo la salida al terminal.
+// Devuelve el mensaje final sin enviarlo, para que quien llama pueda hacer algo
+// más antes (p. ej. apuntar un backup recién exportado).
+pub fn run_lando_step(runner: &dyn CommandRunner, sender: &Sender<LandoCommandOutcome>, args: Vec<String>, project_path: &Path) -> LandoCommandOutcome {
+    // El historial guarda los argumentos sin `-y`: al repetirlo manda el ajuste de ese momento
+    let invocation = Invocation::Lando(args.clone());
+    let args = with_auto_yes(args);
+    let command = args.join(" ");
+    let _ = sender.send(LandoCommandOutcome::CommandStarted {
+        command: command.clone(),
+        project: project_path.to_path_buf(),
+        invocation: Some(invocation),
+    });
+    let cancel = Arc::new(AtomicBool::new(false));
+
+    match stream_interactive(runner, sender, &command, &args, project_path, &cancel) {
+        Ok(true) => LandoCommandOutcome::CommandSuccess(format!(
+            "Comando '{}' finalizado con éxito.",
+            command
+        )),
+        Ok(false) if cancel.load(Ordering::Relaxed) => LandoCommandOutcome::Error(format!(
+            "El comando '{}' fue cancelado.",
+            command
+        )),
+        Ok(false) => LandoCommandOutcome::Error(format!(
+            "El comando '{}' terminó con un error.",
+            command
+        )),
+        Err(e) => LandoCommandOutcome::Error(format!("No se pudo ejecutar Lando ('{}'): {}", command, e)),
+    }
+}
+
+// Ejecuta varios comandos de lando uno tras otro (ej. `db-export` antes de
+// `destroy`). Si uno falla no se lanza el resto y el error dice qué ya se ejecutó.
+// Con `secret` (llevan contraseñas) no se apuntan en el historial de comandos y
+// el texto que llega a la UI (barra de estado, avisos) va sin la contraseña.
+pub fn run_lando_chain(sender: Sender<LandoCommandOutcome>, steps: Vec<Vec<String>>, project_path: PathBuf, secret: bool) {
+    run_lando_chain_with(system_runner(), sender, steps, project_path, secret);
+}
+
+pub fn run_lando_chain_with(
+    runner: Arc<dyn CommandRunner>,
+    sender: Sender<LandoCommandOutcome>,
+    steps: Vec<Vec<String>>,
+    project_path: PathBuf,
+    secret: bool,
+) {
+    thread::spawn(move || {
+        let steps: Vec<Vec<String>> = steps.into_iter().map(with_auto_yes).collect();
+        let commands: Vec<String> = steps
+            .iter()
+            .map(|args| if secret { redact_secrets(&args.join(" ")) } else { args.join(" ") })
+            .collect();
+
+        for (index, (args, command)) in steps.iter().zip(&commands).enumerate() {
+            let _ = sender.send(LandoCommandOutcome::CommandStarted {
+                command: command.clone(),
+                project: project_path.clone(),
+                invocation: (!secret).then(|| Invocation::Lando(args.clone())),
+            });
+            let cancel = Arc::new(AtomicBool::new(false));
+
+            let failure = match stream_interactive(runner.as_ref(), &sender, command, args, &project_path, &cancel) {
+                Ok(true) => {
+                    let _ = sender.send(LandoCommandOutcome::CommandSuccess(format!(
+                        "Comando '{}' finalizado con éxito.",
+                        command
+                    )));
+                    continue;
+                }
+                Ok(false) if cancel.load(Ordering::Relaxed) => format!("El comando '{}' fue cancelado.", command),
+                Ok(false) => format!("El comando '{}' terminó con un error.", command),
+                Err(e) => format!("No se pudo ejecutar Lando ('{}'): {}", command, e),
+            };
+
+            let done = if index == 0 {
+                "ninguno".to_string()
+            } else {
+                commands[..index].iter().map(|c| format!("lando {}", c)).collect::<Vec<_>>().join(", ")
+            };
+            let skipped = commands[index + 1..].iter().map(|c| format!("lando {}", c)).collect::<Vec<_>>();
+            let mut message = format!("{} Secuencia abortada. Ya ejecutados: {}.", failure, done);
+            if !skipped.is_empty() {
+                message.push_str(&format!(" Sin ejecutar: {}.", skipped.join(", ")));
             }
-        };
+            let _ = sender.send(LandoCommandOutcome::Error(message));
+            return;
+        }
+    });
+}
 
-        // Hilo para leer stdout
-        let stdout = child.stdout.take().expect("Failed to open stdout");
-        let sender_stdout = sender.clone();
-        let stdout_thread = thread::spawn(move || {
-            let mut reader = BufReader::new(stdout);
-            let mut buffer = [0; 1024];
-            while let Ok(n) = reader.read(&mut buffer) {
-                if n == 0 { break; }
-                let _ = sender_stdout.send(LandoCommandOutcome::LogOutput(buffer[..n].to_vec()));
+// El mismo comando en varios proyectos, uno detrás de otro para no saturar
+// Docker. Un fallo no para el resto salvo con `stop_on_error`; `cancel` deja sin
+// ejecutar los que faltan.
+pub fn run_lando_bulk(sender: Sender<LandoCommandOutcome>, command: String, projects: Vec<PathBuf>, cancel: Arc<AtomicBool>, stop_on_error: bool) {
+    let runner = system_runner();
+    thread::spawn(move || {
+        for (index, project_path) in projects.iter().enumerate() {
+            if cancel.load(Ordering::Relaxed) {
+                let _ = sender.send(LandoCommandOutcome::BulkProgress { index, status: BulkStatus::Skipped });
+                continue;
             }
-        });
+            let _ = sender.send(LandoCommandOutcome::BulkProgress { index, status: BulkStatus::Running });
 
-        // Hilo para leer stderr
-        let stderr = child.stderr.take().expect("Failed to open stderr");
-        let sender_stderr = sender.clone();
-        let stderr_thread = thread::spawn(move || {
-            let mut reader = BufReader::new(stderr);
-            let mut buffer = [0; 1024];
-            while let Ok(n) = reader.read(&mut buffer) {
-                if n == 0 { break; }
-                let _ = sender_stderr.send(LandoCommandOutcome::LogOutput(buffer[..n].to_vec()));
+            let outcome = run_lando_step(runner.as_ref(), &sender, to_args(&[&command]), project_path);
+            let status = match &outcome {
+                LandoCommandOutcome::CommandSuccess(_) => BulkStatus::Done,
+                LandoCommandOutcome::Error(message) => BulkStatus::Failed(message.clone()),
+                _ => BulkStatus::Done,
+            };
+            if stop_on_error && matches!(status, BulkStatus::Failed(_)) {
+                cancel.store(true, Ordering::Relaxed);
             }
-        });
+            let _ = sender.send(outcome);
+            let _ = sender.send(LandoCommandOutcome::BulkProgress { index, status });
+        }
+        let _ = sender.send(LandoCommandOutcome::BulkFinished);
+    });
+}
 
-        // Esperar a que los hilos de lectura terminen
-        let _ = stdout_thread.join();
-        let _ = stderr_thread.join();
+// Ejecuta `lando init` en la carpeta del nuevo proyecto; si termina bien avisa con
+// `ProjectCreated` para que la UI lo añada a la lista y lo seleccione.
+pub fn run_lando_init(sender: Sender<LandoCommandOutcome>, args: Vec<String>, folder: PathBuf) {
+    let runner = system_runner();
+    thread::spawn(move || {
+        let command = args.join(" ");
+        let _ = sender.send(LandoCommandOutcome::CommandStarted {
+            command: command.clone(),
+            project: folder.clone(),
+            invocation: Some(Invocation::Lando(args.clone())),
+        });
+        let cancel = Arc::new(AtomicBool::new(false));
 
-        // Esperar a que el comando termine y enviar el estado final
-        let status = match child.wait() {
-            Ok(status) => status,
-            Err(e) => {
-                let _ = sender.send(LandoCommandOutcome::Error(format!(
-                    "Error esperando el comando '{}': {}",
-                    command, e
+        let outcome = match stream_interactive(runner.as_ref(), &sender, &command, &args, &folder, &cancel) {
+            Ok(true) => {
+                let _ = sender.send(LandoCommandOutcome::CommandSuccess(format!(
+                    "Comando '{}' finalizado con éxito.",
+                    command
                 )));
-                return;
+                LandoCommandOutcome::ProjectCreated(folder)
             }
-        };
-
-        let outcome = if status.success() {
-            LandoCommandOutcome::CommandSuccess(format!(
-                "Comando '{}' finalizado con éxito.",
-                command
-            ))
-        } else {
-            LandoCommandOutcome::Error(format!(
+            Ok(false) => LandoCommandOutcome::Error(format!(
                 "El comando '{}' terminó con un error.",
                 command
-            ))
+            )),
+            Err(e) => LandoCommandOutcome::Error(format!("No se pudo ejecutar Lando ('{}'): {}", command, e)),
         };
 
         let _ = sender.send(outcome);
     });
 }
 
-pub fn get_project_info(sender: Sender<LandoCommandOutcome>, project_path: PathBuf) {
-    thread::spawn(move || {
-        let output = Command::new("lando")
-            .args(["info", "--format", "json"])
-            .current_dir(project_path)
-            .output();
-
-        let outcome = match output {
-            Ok(output) => {
-                if output.status.success() {
-                    match serde_json::from_slice::<Vec<LandoService>>(&output.stdout) {
-                        Ok(services) => LandoCommandOutcome::Info(services),
-                        Err(e) => LandoCommandOutcome::Error(format!("Error al parsear JSON de lando info: {}", e)),
-                    }
-                } else {
-                    let stderr = String::from_utf8_lossy(&output.stderr);
-                    LandoCommandOutcome::Error(format!("Error de Lando info: {}", stderr))
+// Ejecuta `lando <args>` reenviando stdout/stderr como `LogOutput` a medida que llegan
+fn stream_command(
+    runner: &dyn CommandRunner,
+    sender: &Sender<LandoCommandOutcome>,
+    args: &[String],
+    project_path: &Path,
+) -> std::io::Result<bool> {
+    let on_chunk = |chunk: Vec<u8>| {
+        let _ = sender.send(LandoCommandOutcome::LogOutput(chunk));
+    };
+    runner.run_streaming("lando", args, Some(project_path), &on_chunk, &AtomicBool::new(false), None)
+}
+
+// Como `stream_command`, pero con stdin abierto: la UI recibe el canal para
+// responder (`CommandInput`) y un aviso cada vez que aparece una pregunta nueva.
+fn stream_interactive(
+    runner: &dyn CommandRunner,
+    sender: &Sender<LandoCommandOutcome>,
+    command: &str,
+    args: &[String],
+    project_path: &Path,
+    cancel: &Arc<AtomicBool>,
+) -> std::io::Result<bool> {
+    let (input_sender, input_receiver) = mpsc::channel();
+    let _ = sender.send(LandoCommandOutcome::CommandInput {
+        command: command.to_string(),
+        input: input_sender,
+        cancel: cancel.clone(),
+    });
+
+    let tail: Mutex<(String, Option<LandoPrompt>)> = Mutex::new((String::new(), None));
+    let on_chunk = |chunk: Vec<u8>| {
+        if let Ok(mut state) = tail.lock() {
+            let (text, last_prompt) = &mut *state;
+            text.push_str(&String::from_utf8_lossy(&chunk));
+            trim_prompt_tail(text);
+            let prompt = detect_prompt(text);
+            if let Some(new_prompt) = &prompt {
+                if last_prompt.as_ref() != Some(new_prompt) {
+                    let _ = sender.send(LandoCommandOutcome::PromptDetected {
+                        command: command.to_string(),
+                        prompt: new_prompt.clone(),
+                    });
                 }
             }
-            Err(e) => LandoCommandOutcome::Error(format!("No se pudo ejecutar Lando info: {}", e)),
-        };
+            *last_prompt = prompt;
+        }
+        let _ = sender.send(LandoCommandOutcome::LogOutput(chunk));
+    };
+    runner.run_streaming("lando", args, Some(project_path), &on_chunk, cancel, Some(&input_receiver))
+}
 
-        let _ = sender.send(outcome);
+pub fn get_project_info(sender: Sender<LandoCommandOutcome>, project_path: PathBuf) {
+    get_project_info_with(system_runner(), sender, project_path);
+}
+
+pub fn get_project_info_with(runner: Arc<dyn CommandRunner>, sender: Sender<LandoCommandOutcome>, project_path: PathBuf) {
+    thread::spawn(move || {
+        let _ = sender.send(project_info_outcome(runner.as_ref(), &project_path));
     });
 }
 
-pub fn run_db_query(sender: Sender<LandoCommandOutcome>, project_path: PathBuf, service: String, query: String) {
+pub const INFO_RETRY_ATTEMPTS: u32 = 5;
+pub const INFO_RETRY_BASE_DELAY: Duration = Duration::from_secs(2);
+
+// Justo después de `lando start` los contenedores pueden no responder todavía:
+// `lando info` se reintenta esperando 2, 4, 8... s hasta que funcione, se
+// agoten los intentos o se cancele (el usuario cambió de proyecto)
+pub fn get_project_info_with_retry(sender: Sender<LandoCommandOutcome>, project_path: PathBuf, max_attempts: u32, cancel: Arc<AtomicBool>) {
+    let runner = system_runner();
     thread::spawn(move || {
-        // Intentar primero con credenciales por defecto (root sin contraseña)
-        let output = Command::new("lando")
-            .args(["db-cli", "-s", &service, "-u", "root", "-e", &query])
-            .current_dir(project_path.clone())
-            .output();
-
-        let outcome = match output {
-            Ok(output) => {
-                if output.status.success() {
-                    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-                    LandoCommandOutcome::DbQueryResult(stdout)
-                } else {
-                    // Si falla con root, intentar sin especificar usuario
-                    let output2 = Command::new("lando")
-                        .args(["db-cli", "-s", &service, "-e", &query])
-                        .current_dir(project_path)
-                        .output();
-
-                    match output2 {
-                        Ok(output2) => {
-                            if output2.status.success() {
-                                let stdout = String::from_utf8_lossy(&output2.stdout).to_string();
-                                LandoCommandOutcome::DbQueryResult(stdout)
-                            } else {
-                                let stderr = String::from_utf8_lossy(&output2.stderr).to_string();
-                                LandoCommandOutcome::Error(format!("Error ejecutando la consulta: {}", stderr))
-                            }
-                        }
-                        Err(e) => LandoCommandOutcome::Error(format!("No se pudo ejecutar lando db-cli: {}", e)),
+        let mut delay = INFO_RETRY_BASE_DELAY;
+        for attempt in 1..=max_attempts {
+            if cancel.load(Ordering::Relaxed) {
+                return;
+            }
+            let outcome = project_info_outcome(runner.as_ref(), &project_path);
+            let LandoCommandOutcome::Error(error) = outcome else {
+                if !cancel.load(Ordering::Relaxed) {
+                    let _ = sender.send(outcome);
+                }
+                return;
+            };
+
+            let next_in = (attempt < max_attempts).then_some(delay);
+            log::info!("lando info falló (intento {}/{}): {}", attempt, max_attempts, error);
+            let _ = sender.send(LandoCommandOutcome::InfoRetry { attempt, max: max_attempts, next_in, error });
+            let Some(wait) = next_in else {
+                return;
+            };
+            thread::sleep(wait);
+            delay *= 2;
+        }
+    });
+}
+
+pub fn project_info_outcome(runner: &dyn CommandRunner, project_path: &Path) -> LandoCommandOutcome {
+    match runner.run("lando", &to_args(&["info", "--format", "json"]), Some(project_path)) {
+        Ok(output) => {
+            if output.success {
+                match parse_services(&output.stdout) {
+                    Ok(services) => LandoCommandOutcome::Info(services),
+                    Err(e) => {
+                        log::warn!("Salida de lando info no reconocida: {}", output.stdout_lossy());
+                        LandoCommandOutcome::Error(format!("Error al parsear JSON de lando info: {}", e))
                     }
                 }
+            } else {
+                LandoCommandOutcome::Error(format!("Error de Lando info: {}", output.stderr_lossy()))
             }
-            Err(e) => LandoCommandOutcome::Error(format!("No se pudo ejecutar lando db-cli: {}", e)),
-        };
+        }
+        Err(e) => LandoCommandOutcome::Error(format!("No se pudo ejecutar Lando info: {}", e)),
+    }
+}
 
-        let _ = sender.send(outcome);
+// El resultado vuelve etiquetado con el servicio y `request_id` para que solo lo
+// procese quien lo pidió: una petición de `pending_requests` o el resultado provisional del editor
+pub fn run_db_query(sender: Sender<LandoCommandOutcome>, project_path: PathBuf, connection: DbConnection, query: String, request_id: u64) {
+    run_db_query_with(system_runner(), sender, project_path, connection, query, request_id);
+}
+
+pub fn run_db_query_with(runner: Arc<dyn CommandRunner>, sender: Sender<LandoCommandOutcome>, project_path: PathBuf, connection: DbConnection, query: String, request_id: u64) {
+    thread::spawn(move || {
+        let output = execute_db_query(runner.as_ref(), &project_path, &connection, &query);
+        let _ = sender.send(LandoCommandOutcome::DbTaggedResult { service: connection.service, request_id, output });
     });
 }
 
+// Identificador único para correlacionar una consulta con su resultado
+pub fn next_request_id() -> u64 {
+    static NEXT_REQUEST_ID: AtomicU64 = AtomicU64::new(1);
+    NEXT_REQUEST_ID.fetch_add(1, Ordering::Relaxed)
+}
+
+pub fn execute_db_query(runner: &dyn CommandRunner, project_path: &Path, connection: &DbConnection, query: &str) -> Result<String, String> {
+    match runner.run("lando", &db_cli_args(connection, query), Some(project_path)) {
+        Ok(output) => {
+            if output.success {
+                Ok(output.stdout_lossy())
+            } else {
+                Err(format!("Error ejecutando la consulta: {}", output.stderr_lossy()))
+            }
+        }
+        Err(e) => Err(format!("No se pudo ejecutar lando db-cli: {}", e)),
+    }
+}
+
+// `lando db-cli -s servicio [-u usuario] [--password=...] [-D base] -e consulta`
+fn db_cli_args(connection: &DbConnection, query: &str) -> Vec<String> {
+    let mut args = to_args(&["db-cli", "-s", &connection.service]);
+    if let Some(user) = connection.user.as_deref().filter(|u| !u.is_empty()) {
+        args.extend(to_args(&["-u", user]));
+    }
+    if let Some(password) = connection.password.as_ref().map(SecretString::expose).filter(|p| !p.is_empty()) {
+        args.push(format!("--password={}", password));
+    }
+    if let Some(database) = connection.database.as_deref().filter(|d| !d.is_empty()) {
+        args.extend(to_args(&["-D", database]));
+    }
+    args.extend(to_args(&["-e", query]));
+    args
+}
+
+// Ejecuta un comando en el servicio capturando toda la salida y la devuelve
+// etiquetada con `request_id` (en vez de transmitirla al terminal)
+pub fn run_shell_capture_tagged(sender: Sender<LandoCommandOutcome>, project_path: PathBuf, service: String, command: String, request_id: u64) {
+    let runner = system_runner();
+    thread::spawn(move || {
+        let output = execute_shell_capture(runner.as_ref(), &project_path, &service, &command);
+        let _ = sender.send(LandoCommandOutcome::ShellTaggedResult { request_id, output });
+    });
+}
+
+// Igual que `run_shell_capture_tagged` pero como root, para herramientas que
+// leen ficheros del servicio que el usuario por defecto no puede abrir
+pub fn run_root_shell_capture_tagged(sender: Sender<LandoCommandOutcome>, project_path: PathBuf, service: String, command: String, request_id: u64) {
+    let runner = system_runner();
+    thread::spawn(move || {
+        let output = execute_shell_capture_as(runner.as_ref(), &project_path, &service, Some("root"), &command);
+        let _ = sender.send(LandoCommandOutcome::ShellTaggedResult { request_id, output });
+    });
+}
+
+// Docker Compose etiqueta cada contenedor con su proyecto y servicio; Lando usa
+// como proyecto el nombre de la app en minúsculas y sin `-`, `_` ni `.`
+pub fn compose_project_name(app_name: &str) -> String {
+    app_name.chars()
+        .filter(|c| !matches!(c, '-' | '_' | '.'))
+        .collect::<String>()
+        .to_lowercase()
+}
+
+// `docker stats` de un solo contenedor, localizado por sus etiquetas. La
+// respuesta es `CPU|memoria usada / límite|% memoria`, vacía si no está en marcha
+pub fn run_container_stats_tagged(sender: Sender<LandoCommandOutcome>, project: String, service: String, request_id: u64) {
+    let runner = system_runner();
+    thread::spawn(move || {
+        let output = container_stats(runner.as_ref(), &project, &service);
+        let _ = sender.send(LandoCommandOutcome::ShellTaggedResult { request_id, output });
+    });
+}
+
+pub fn container_stats(runner: &dyn CommandRunner, project: &str, service: &str) -> Result<String, String> {
+    let project_filter = format!("label=com.docker.compose.project={}", project);
+    let service_filter = format!("label=com.docker.compose.service={}", service);
+    let ps = runner.run("docker", &to_args(&["ps", "-q", "--filter", &project_filter, "--filter", &service_filter]), None)
+        .map_err(|e| format!("No se pudo ejecutar docker: {}", e))?;
+    if !ps.success {
+        return Err(format!("docker ps falló: {}", ps.stderr_lossy().trim()));
+    }
+    let Some(container) = ps.stdout_lossy().lines().next().map(|id| id.trim().to_string()) else {
+        return Ok(String::new());
+    };
+
+    let stats = runner.run(
+        "docker",
+        &to_args(&["stats", "--no-stream", "--format", "{{.CPUPerc}}|{{.MemUsage}}|{{.MemPerc}}", &container]),
+        None,
+    ).map_err(|e| format!("No se pudo ejecutar docker: {}", e))?;
+    if stats.success {
+        Ok(stats.stdout_lossy())
+    } else {
+        Err(format!("docker stats falló: {}", stats.stderr_lossy().trim()))
+    }
+}
+
+pub fn execute_shell_capture(runner: &dyn CommandRunner, project_path: &Path, service: &str, command: &str) -> Result<String, String> {
+    execute_shell_capture_as(runner, project_path, service, None, command)
+}
+
+pub fn execute_shell_capture_as(runner: &dyn CommandRunner, project_path: &Path, service: &str, user: Option<&str>, command: &str) -> Result<String, String> {
+    let mut args = vec!["ssh", "-s", service];
+    if let Some(user) = user {
+        args.extend(["-u", user]);
+    }
+    args.extend(["-c", command]);
+    match runner.run("lando", &to_args(&args), Some(project_path)) {
+        Ok(output) => {
+            if output.success {
+                Ok(output.stdout_lossy())
+            } else {
+                Err(format!("El comando shell '{}' terminó con un error: {}", command, output.stderr_lossy()))
+            }
+        }
+        Err(e) => Err(format!("No se pudo ejecutar Lando ssh: {}", e)),
+    }
+}
+
 pub fn test_db_connection(
     sender: Sender<LandoCommandOutcome>,
     project_path: PathBuf,
-    service: String,
+    connection: DbConnection,
+    request_id: u64,
+) {
+    test_db_connection_with(system_runner(), sender, project_path, connection, request_id);
+}
+
+pub fn test_db_connection_with(
+    runner: Arc<dyn CommandRunner>,
+    sender: Sender<LandoCommandOutcome>,
+    project_path: PathBuf,
+    connection: DbConnection,
+    request_id: u64,
 ) {
     thread::spawn(move || {
-        // Usar mysqladmin para verificar si el servidor está vivo
-        let test_command = "mysqladmin -u root ping";
-
-        let output = Command::new("lando")
-            .args(["ssh", "-s", &service, "-c", test_command])
-            .current_dir(project_path)
-            .output();
-
-        let outcome = match output {
-            Ok(output) => {
-                if output.status.success() {
-                    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-                    if stdout.contains("alive") {
-                        LandoCommandOutcome::DbQueryResult("✅ Conexión exitosa".to_string())
-                    } else {
-                        LandoCommandOutcome::Error(format!(
-                            "Error de conexión (salida inesperada): {}",
-                            stdout
-                        ))
-                    }
+        let output = db_connection_check(runner.as_ref(), &project_path, &connection);
+        let _ = sender.send(LandoCommandOutcome::DbTaggedResult { service: connection.service, request_id, output });
+    });
+}
+
+pub fn db_connection_check(runner: &dyn CommandRunner, project_path: &Path, connection: &DbConnection) -> Result<String, String> {
+    // Usar mysqladmin para verificar si el servidor está vivo
+    let test_command = mysqladmin_ping_command(connection);
+
+    match runner.run("lando", &to_args(&["ssh", "-s", &connection.service, "-c", &test_command]), Some(project_path)) {
+        Ok(output) => {
+            if output.success {
+                let stdout = output.stdout_lossy();
+                if stdout.contains("alive") {
+                    Ok("✅ Conexión exitosa".to_string())
                 } else {
-                    let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-                    LandoCommandOutcome::Error(format!("Error probando conexión: {}", stderr))
+                    Err(format!(
+                        "Error de conexión (salida inesperada): {}",
+                        stdout
+                    ))
                 }
+            } else {
+                Err(format!("Error probando conexión: {}", output.stderr_lossy()))
             }
-            Err(e) => LandoCommandOutcome::Error(format!(
-                "No se pudo ejecutar test de conexión: {}",
-                e
+        }
+        Err(e) => Err(format!(
+            "No se pudo ejecutar test de conexión: {}",
+            e
+        )),
+    }
+}
+
+// El comando va por `sh -c`, así que usuario y contraseña se citan
+fn mysqladmin_ping_command(connection: &DbConnection) -> String {
+    let mut command = "mysqladmin".to_string();
+    if let Some(user) = connection.user.as_deref().filter(|u| !u.is_empty()) {
+        command.push_str(&format!(" -u {}", shell_quote(user)));
+    }
+    if let Some(password) = connection.password.as_ref().map(SecretString::expose).filter(|p| !p.is_empty()) {
+        command.push_str(&format!(" --password={}", shell_quote(password)));
+    }
+    command.push_str(" ping");
+    command
+}
+
+pub fn run_shell_command(sender: Sender<LandoCommandOutcome>, project_path: PathBuf, service: String, command: String) {
+    run_shell_command_with(system_runner(), sender, project_path, service, command);
+}
+
+pub fn run_shell_command_with(runner: Arc<dyn CommandRunner>, sender: Sender<LandoCommandOutcome>, project_path: PathBuf, service: String, command: String) {
+    thread::spawn(move || {
+        let _ = sender.send(LandoCommandOutcome::CommandStarted {
+            command: command.clone(),
+            project: project_path.clone(),
+            invocation: Some(Invocation::Shell { service: service.clone(), command: command.clone() }),
+        });
+        let args = to_args(&["ssh", "-s", &service, "-c", &command]);
+        let result = stream_command(runner.as_ref(), &sender, &args, &project_path);
+
+        let outcome = match result {
+            Ok(true) => LandoCommandOutcome::CommandSuccess(format!(
+                "Comando shell '{}' finalizado con éxito.",
+                command
+            )),
+            Ok(false) => LandoCommandOutcome::Error(format!(
+                "El comando shell '{}' terminó con un error.",
+                command
             )),
+            Err(e) => LandoCommandOutcome::Error(format!("No se pudo ejecutar Lando ssh ('{}'): {}", command, e)),
         };
 
         let _ = sender.send(outcome);
     });
 }
 
-pub fn run_shell_command(sender: Sender<LandoCommandOutcome>, project_path: PathBuf, service: String, command: String) {
+// Ejecuta un comando en el servicio transmitiendo la salida etiquetada con `request_id`
+// (para una vista propia, no el terminal global). El comando viaja como un único
+// argumento de `lando ssh -c`, así que las comillas llegan intactas a la shell del contenedor.
+pub fn run_shell_stream_tagged(
+    sender: Sender<LandoCommandOutcome>,
+    project_path: PathBuf,
+    service: String,
+    command: String,
+    request_id: u64,
+    cancel: Arc<AtomicBool>,
+) {
+    run_shell_stream_tagged_as(sender, project_path, service, None, command, request_id, cancel);
+}
+
+// Con `user` el comando se lanza con `lando ssh -u` (p. ej. root para recargar el servidor)
+pub fn run_shell_stream_tagged_as(
+    sender: Sender<LandoCommandOutcome>,
+    project_path: PathBuf,
+    service: String,
+    user: Option<String>,
+    command: String,
+    request_id: u64,
+    cancel: Arc<AtomicBool>,
+) {
+    let runner = system_runner();
     thread::spawn(move || {
-        let mut child = match Command::new("lando")
-            .args(["ssh", "-s", &service, "-c", &command])
-            .current_dir(project_path.clone())
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()
-        {
-            Ok(child) => child,
-            Err(e) => {
-                let _ = sender.send(LandoCommandOutcome::Error(format!(
-                    "No se pudo ejecutar Lando ssh: {}",
-                    e
-                )));
-                return;
-            }
+        let mut args = vec!["ssh", "-s", &service];
+        if let Some(user) = &user {
+            args.extend(["-u", user.as_str()]);
+        }
+        args.extend(["-c", &command]);
+        let args = to_args(&args);
+        let on_chunk = |chunk: Vec<u8>| {
+            let _ = sender.send(LandoCommandOutcome::ShellStreamChunk { request_id, chunk });
         };
 
-        // Hilo para leer stdout
-        let stdout = child.stdout.take().expect("Failed to open stdout");
-        let sender_stdout = sender.clone();
-        let stdout_thread = thread::spawn(move || {
-            let mut reader = BufReader::new(stdout);
-            let mut buffer = [0; 1024];
-            while let Ok(n) = reader.read(&mut buffer) {
-                if n == 0 { break; }
-                let _ = sender_stdout.send(LandoCommandOutcome::LogOutput(buffer[..n].to_vec()));
-            }
-        });
+        let result = match runner.run_streaming("lando", &args, Some(&project_path), &on_chunk, &cancel, None) {
+            Ok(true) => Ok(()),
+            Ok(false) if cancel.load(Ordering::Relaxed) => Err("⏹ Cancelado".to_string()),
+            Ok(false) => Err(format!("El comando shell '{}' terminó con un error.", command)),
+            Err(e) => Err(format!("No se pudo ejecutar Lando ssh: {}", e)),
+        };
 
-        // Hilo para leer stderr
-        let stderr = child.stderr.take().expect("Failed to open stderr");
-        let sender_stderr = sender.clone();
-        let stderr_thread = thread::spawn(move || {
-            let mut reader = BufReader::new(stderr);
-            let mut buffer = [0; 1024];
-            while let Ok(n) = reader.read(&mut buffer) {
-                if n == 0 { break; }
-                let _ = sender_stderr.send(LandoCommandOutcome::LogOutput(buffer[..n].to_vec()));
-            }
-        });
+        let _ = sender.send(LandoCommandOutcome::ShellStreamFinished { request_id, result });
+    });
+}
+
+// Notificación del sistema operativo; se lanza en un hilo para no bloquear la UI
+pub fn send_desktop_notification(summary: String, body: String) {
+    thread::spawn(move || {
+        let _ = notify_rust::Notification::new()
+            .appname("Lando GUI")
+            .summary(&summary)
+            .body(&body)
+            .show();
+    });
+}
 
-        let _ = stdout_thread.join();
-        let _ = stderr_thread.join();
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io;
+    use std::sync::mpsc;
+    use crate::core::runner::{CommandOutput, StubRunner};
+    use crate::models::secret::SecretString;
+    use crate::ui::database::DatabaseUI;
 
-        let status = match child.wait() {
-            Ok(status) => status,
-            Err(e) => {
-                let _ = sender.send(LandoCommandOutcome::Error(format!(
-                    "Error esperando el comando ssh '{}': {}",
-                    command, e
-                )));
-                return;
+    fn db_service(json: &str) -> crate::models::lando::LandoService {
+        parse_services(json.as_bytes()).unwrap().remove(0)
+    }
+
+    // Espera el mensaje final de un comando lanzado en segundo plano
+    fn final_outcome(receiver: &mpsc::Receiver<LandoCommandOutcome>) -> LandoCommandOutcome {
+        receiver.iter()
+            .find(|outcome| matches!(outcome, LandoCommandOutcome::CommandSuccess(_) | LandoCommandOutcome::Error(_)))
+            .unwrap()
+    }
+
+    #[test]
+    fn list_apps_reports_invalid_json_as_error() {
+        let runner = Arc::new(StubRunner::new().respond_ok("esto no es json"));
+        let (sender, receiver) = mpsc::channel();
+        list_apps_with(runner.clone(), sender);
+
+        match receiver.recv().unwrap() {
+            LandoCommandOutcome::Error(msg) => assert!(msg.starts_with("Error al parsear JSON"), "{}", msg),
+            other => panic!("se esperaba Error, llegó {:?}", other),
+        }
+        assert_eq!(runner.calls()[0].args, to_args(&["list", "--format", "json"]));
+    }
+
+    #[test]
+    fn list_apps_parses_running_apps() {
+        let runner = StubRunner::new().respond_ok(r#"[{"name": "blog", "location": "/srv/blog", "running": true}]"#);
+        match list_apps_outcome(&runner) {
+            LandoCommandOutcome::List(apps) => {
+                assert_eq!(apps.len(), 1);
+                assert_eq!(apps[0].name, "blog");
+                assert!(apps[0].running);
             }
-        };
+            other => panic!("se esperaba List, llegó {:?}", other),
+        }
+    }
 
-        let outcome = if status.success() {
-            LandoCommandOutcome::CommandSuccess(format!(
-                "Comando shell '{}' finalizado con éxito.",
-                command
-            ))
-        } else {
-            LandoCommandOutcome::Error(format!(
-                "El comando shell '{}' terminó con un error.",
-                command
-            ))
+    #[test]
+    fn project_info_reports_invalid_json_and_stderr() {
+        let runner = StubRunner::new().respond_ok("{ roto");
+        match project_info_outcome(&runner, Path::new("/srv/blog")) {
+            LandoCommandOutcome::Error(msg) => assert!(msg.starts_with("Error al parsear JSON de lando info"), "{}", msg),
+            other => panic!("se esperaba Error, llegó {:?}", other),
+        }
+
+        let runner = StubRunner::new().respond_err("app not found");
+        match project_info_outcome(&runner, Path::new("/srv/blog")) {
+            LandoCommandOutcome::Error(msg) => assert_eq!(msg, "Error de Lando info: app not found"),
+            other => panic!("se esperaba Error, llegó {:?}", other),
+        }
+        assert_eq!(runner.calls()[0].cwd.as_deref(), Some(Path::new("/srv/blog")));
+    }
+
+    #[test]
+    fn db_query_passes_profile_credentials_to_db_cli() {
+        let connection = DbConnection {
+            service: "database".to_string(),
+            user: Some("app".to_string()),
+            password: Some(SecretString::new("s3cret")),
+            database: Some("blog".to_string()),
         };
+        let runner = StubRunner::new().respond_ok("1");
 
-        let _ = sender.send(outcome);
-    });
+        assert_eq!(execute_db_query(&runner, Path::new("/srv/blog"), &connection, "SELECT 1"), Ok("1".to_string()));
+        assert_eq!(
+            runner.calls()[0].args,
+            to_args(&["db-cli", "-s", "database", "-u", "app", "--password=s3cret", "-D", "blog", "-e", "SELECT 1"]),
+        );
+    }
+
+    #[test]
+    fn db_query_without_profile_leaves_the_user_to_lando() {
+        let connection = DbConnection { service: "database".to_string(), ..Default::default() };
+        let runner = StubRunner::new().respond_ok("");
+
+        let _ = execute_db_query(&runner, Path::new("/srv/blog"), &connection, "SELECT 1");
+        assert_eq!(runner.calls()[0].args, to_args(&["db-cli", "-s", "database", "-e", "SELECT 1"]));
+    }
+
+    #[test]
+    fn db_query_uses_service_credentials_before_root() {
+        let database_ui = DatabaseUI::default();
+        let service = db_service(r#"[{"service": "database", "type": "mysql",
+            "creds": {"user": "app", "password": "s3cret", "database": "blog"}}]"#);
+        let runner = StubRunner::new().respond_ok("");
+
+        let _ = execute_db_query(&runner, Path::new("/srv/blog"), &database_ui.db_connection(&service), "SELECT 1");
+        assert_eq!(
+            runner.calls()[0].args,
+            to_args(&["db-cli", "-s", "database", "-u", "app", "--password=s3cret", "-D", "blog", "-e", "SELECT 1"]),
+        );
+    }
+
+    #[test]
+    fn db_query_falls_back_to_root_without_credentials() {
+        let database_ui = DatabaseUI::default();
+        let connection = database_ui.db_connection(&db_service(r#"[{"service": "database", "type": "mysql"}]"#));
+        let runner = StubRunner::new().respond_ok("1");
+
+        assert_eq!(execute_db_query(&runner, Path::new("/srv/blog"), &connection, "SELECT 1"), Ok("1".to_string()));
+        assert_eq!(runner.calls()[0].args, to_args(&["db-cli", "-s", "database", "-u", "root", "-e", "SELECT 1"]));
+    }
+
+    #[test]
+    fn db_query_propagates_stderr_and_spawn_errors() {
+        let connection = DbConnection { service: "database".to_string(), ..Default::default() };
+
+        let runner = StubRunner::new().respond_err("ERROR 1146: Table 'blog.x' doesn't exist");
+        assert_eq!(
+            execute_db_query(&runner, Path::new("/srv/blog"), &connection, "SELECT * FROM x"),
+            Err("Error ejecutando la consulta: ERROR 1146: Table 'blog.x' doesn't exist".to_string()),
+        );
+
+        let runner = StubRunner::new().respond(Err(io::Error::new(io::ErrorKind::NotFound, "lando no encontrado")));
+        let result = execute_db_query(&runner, Path::new("/srv/blog"), &connection, "SELECT 1");
+        assert_eq!(result, Err("No se pudo ejecutar lando db-cli: lando no encontrado".to_string()));
+    }
+
+    #[test]
+    fn lando_command_reports_success_and_failure_messages() {
+        let (sender, receiver) = mpsc::channel();
+        let runner = Arc::new(StubRunner::new().respond_ok("hecho\n"));
+        run_lando_args_with(runner, sender, to_args(&["info"]), PathBuf::from("/srv/blog"));
+        match final_outcome(&receiver) {
+            LandoCommandOutcome::CommandSuccess(msg) => assert_eq!(msg, "Comando 'info' finalizado con éxito."),
+            other => panic!("se esperaba CommandSuccess, llegó {:?}", other),
+        }
+
+        let (sender, receiver) = mpsc::channel();
+        let runner = Arc::new(StubRunner::new().respond_err("boom"));
+        run_lando_args_with(runner, sender, to_args(&["info"]), PathBuf::from("/srv/blog"));
+        match final_outcome(&receiver) {
+            LandoCommandOutcome::Error(msg) => assert_eq!(msg, "El comando 'info' terminó con un error."),
+            other => panic!("se esperaba Error, llegó {:?}", other),
+        }
+
+        let (sender, receiver) = mpsc::channel();
+        run_lando_args_with(Arc::new(StubRunner::new()), sender, to_args(&["info"]), PathBuf::from("/srv/blog"));
+        match final_outcome(&receiver) {
+            LandoCommandOutcome::Error(msg) => assert!(msg.starts_with("No se pudo ejecutar Lando"), "{}", msg),
+            other => panic!("se esperaba Error, llegó {:?}", other),
+        }
+    }
+
+    #[test]
+    fn lando_command_streams_stdout_and_stderr_to_the_log() {
+        let (sender, receiver) = mpsc::channel();
+        let runner = Arc::new(StubRunner::new().respond(Ok(CommandOutput {
+            success: false,
+            stdout: b"hecho\n".to_vec(),
+            stderr: b"boom".to_vec(),
+        })));
+        run_lando_args_with(runner, sender, to_args(&["rebuild"]), PathBuf::from("/srv/blog"));
+
+        let logs: Vec<Vec<u8>> = receiver.iter()
+            .filter_map(|outcome| match outcome {
+                LandoCommandOutcome::LogOutput(bytes) => Some(bytes),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(logs, vec![b"hecho\n".to_vec(), b"boom".to_vec()]);
+    }
+
+    #[test]
+    fn decode_utf8_stream_joins_emoji_split_across_chunks() {
+        let emoji = "🚀".as_bytes();
+        let mut pending = Vec::new();
+
+        let first = decode_utf8_stream(&mut pending, &[b"ok ".as_slice(), &emoji[..2]].concat());
+        assert_eq!(first, "ok ");
+        assert_eq!(pending, &emoji[..2]);
+
+        let second = decode_utf8_stream(&mut pending, &[&emoji[2..], b" fin".as_slice()].concat());
+        assert_eq!(second, "🚀 fin");
+        assert!(pending.is_empty());
+    }
+
+    #[test]
+    fn decode_utf8_stream_keeps_incomplete_tail_after_invalid_byte() {
+        let emoji = "✅".as_bytes();
+        let mut pending = Vec::new();
+
+        let first = decode_utf8_stream(&mut pending, &[b"a\xFFb".as_slice(), &emoji[..1]].concat());
+        assert_eq!(first, "a\u{FFFD}b");
+        assert_eq!(pending, &emoji[..1]);
+
+        let second = decode_utf8_stream(&mut pending, &emoji[1..]);
+        assert_eq!(second, "✅");
+        assert!(pending.is_empty());
+    }
+
+    #[test]
+    fn secret_chain_never_shows_the_password() {
+        let runner = Arc::new(StubRunner::new().respond_ok("").respond_err("boom"));
+        let (sender, receiver) = mpsc::channel();
+        let steps = vec![
+            to_args(&["config", "--set", "database.creds.password=hunter2"]),
+            to_args(&["rebuild"]),
+            to_args(&["config", "--set", "database.creds.password=hunter2"]),
+        ];
+        run_lando_chain_with(runner.clone(), sender, steps, PathBuf::from("/tmp/proyecto"), true);
+
+        let outcomes: Vec<LandoCommandOutcome> = receiver.iter().collect();
+        let mut started = 0;
+        for outcome in &outcomes {
+            let text = match outcome {
+                LandoCommandOutcome::CommandStarted { command, invocation, .. } => {
+                    assert!(invocation.is_none());
+                    started += 1;
+                    command.clone()
+                }
+                LandoCommandOutcome::CommandSuccess(msg) | LandoCommandOutcome::Error(msg) => msg.clone(),
+                _ => continue,
+            };
+            assert!(!text.contains("hunter2"), "{}", text);
+        }
+        assert_eq!(started, 2);
+        assert!(matches!(outcomes.last(), Some(LandoCommandOutcome::Error(msg)) if msg.contains("password=***")));
+        // El proceso sí recibe la contraseña real
+        assert_eq!(runner.calls()[0].args[2], "database.creds.password=hunter2");
+    }
 }
@@ -0,0 +1,302 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::AtomicBool;
+use std::sync::mpsc::Sender;
+use std::sync::Arc;
+
+use serde_json::Value;
+
+use crate::core::commands::*;
+use crate::models::commands::LandoCommandOutcome;
+use crate::models::lando::LandoService;
+use crate::ui::appserver::{AppServerPendingRequest, AppServerUI, ComposerPackage, ComposerRun};
+
+const COMPOSER_OUTDATED_COMMAND: &str = "composer outdated --direct --format=json";
+const PHP_VERSION_COMMAND: &str = "php -r 'echo PHP_VERSION;'";
+
+// Paquetes de composer: la lista sale de composer.json/composer.lock del
+// proyecto y `composer outdated` marca los que tienen versión nueva.
+impl AppServerUI {
+    // `has_composer_json` se actualiza al cambiar de proyecto (`refresh_recipe`)
+    pub fn has_composer(&self, service: &LandoService) -> bool {
+        let kind = service.r#type.to_lowercase();
+        self.composer.has_composer_json || ["php", "apache", "nginx"].iter().any(|t| kind.starts_with(t))
+    }
+
+    pub fn refresh_composer(&mut self, service: &LandoService, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>) {
+        self.composer.needs_refresh = false;
+        self.composer.loaded_project = Some(project_path.clone());
+        self.load_composer_files(project_path);
+
+        self.composer.loading_outdated = true;
+        let request_id = next_request_id();
+        self.pending_requests.insert(request_id, AppServerPendingRequest::ComposerOutdated);
+        run_shell_capture_tagged(sender.clone(), project_path.clone(), service.service.clone(), COMPOSER_OUTDATED_COMMAND.to_string(), request_id);
+
+        let request_id = next_request_id();
+        self.pending_requests.insert(request_id, AppServerPendingRequest::ComposerPhpVersion);
+        run_shell_capture_tagged(sender.clone(), project_path.clone(), service.service.clone(), PHP_VERSION_COMMAND.to_string(), request_id);
+    }
+
+    // Solo las dependencias directas, como `npm ls --depth=0` en Node
+    pub fn load_composer_files(&mut self, project_path: &Path) {
+        let read_json = |name: &str| {
+            fs::read_to_string(project_path.join(name))
+                .ok()
+                .and_then(|content| serde_json::from_str::<Value>(&content).ok())
+        };
+
+        self.composer.error = None;
+        let Some(manifest) = read_json("composer.json") else {
+            self.composer.packages.clear();
+            self.composer.php_constraint = None;
+            self.composer.platform_php = None;
+            self.composer.error = Some(if project_path.join("composer.json").exists() {
+                "composer.json no es JSON válido".to_string()
+            } else {
+                "No hay composer.json en el proyecto".to_string()
+            });
+            return;
+        };
+        let lock = read_json("composer.lock");
+
+        let locked_version = |name: &str| {
+            let lock = lock.as_ref()?;
+            ["packages", "packages-dev"].iter()
+                .filter_map(|key| lock.get(key)?.as_array())
+                .flatten()
+                .find(|p| p.get("name").and_then(Value::as_str) == Some(name))
+                .and_then(|p| p.get("version")?.as_str().map(String::from))
+        };
+
+        let mut packages = Vec::new();
+        for (key, is_dev) in [("require", false), ("require-dev", true)] {
+            let Some(deps) = manifest.get(key).and_then(Value::as_object) else {
+                continue;
+            };
+            for (name, constraint) in deps {
+                if is_platform_package(name) {
+                    continue;
+                }
+                packages.push(ComposerPackage {
+                    name: name.clone(),
+                    constraint: constraint.as_str().unwrap_or("*").to_string(),
+                    version: locked_version(name),
+                    is_dev,
+                    latest: None,
+                });
+            }
+        }
+        packages.sort_by(|a, b| (a.is_dev, &a.name).cmp(&(b.is_dev, &b.name)));
+
+        self.composer.packages = packages;
+        self.composer.has_lock = lock.is_some();
+        self.composer.php_constraint = manifest.pointer("/require/php").and_then(Value::as_str).map(String::from);
+        self.composer.platform_php = manifest.pointer("/config/platform/php").and_then(Value::as_str).map(String::from);
+    }
+
+    // `composer outdated --format=json`: { "installed": [{ name, version, latest }] }
+    pub fn apply_composer_outdated(&mut self, output: &str) {
+        self.composer.loading_outdated = false;
+        let Some(value) = extract_json(output) else {
+            self.composer.error = Some("No se pudo interpretar la salida de composer outdated".to_string());
+            return;
+        };
+        let installed = value.get("installed").and_then(Value::as_array).cloned().unwrap_or_default();
+
+        for package in &mut self.composer.packages {
+            package.latest = installed.iter()
+                .find(|p| p.get("name").and_then(Value::as_str) == Some(package.name.as_str()))
+                .and_then(|p| p.get("latest")?.as_str())
+                .filter(|latest| Some(*latest) != package.version.as_deref())
+                .map(String::from);
+        }
+    }
+
+    pub fn apply_container_php_version(&mut self, output: &str) {
+        // La versión es la última línea; antes puede haber avisos de PHP
+        self.composer.container_php = output.lines()
+            .map(str::trim)
+            .rfind(|line| line.starts_with(|c: char| c.is_ascii_digit()))
+            .map(String::from);
+    }
+
+    // Some(true) si el PHP del contenedor no cumple `require.php`; None si no se puede saber
+    pub fn composer_php_conflict(&self) -> Option<bool> {
+        let constraint = self.composer.php_constraint.as_deref()?;
+        let version = self.composer.container_php.as_deref()?;
+        php_constraint_matches(constraint, version).map(|ok| !ok)
+    }
+
+    pub fn require_composer_package(&mut self, service: &LandoService, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>) {
+        let package = self.composer.package_name.trim().to_string();
+        if package.is_empty() {
+            return;
+        }
+        let dev = if self.composer.dev { " --dev" } else { "" };
+        self.run_composer_command(service, project_path, sender, &format!("require {}{}", shell_quote(&package), dev), true);
+    }
+
+    pub fn remove_composer_package(&mut self, service: &LandoService, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>, package: &str) {
+        self.run_composer_command(service, project_path, sender, &format!("remove {}", shell_quote(package)), true);
+    }
+
+    pub fn update_composer_package(&mut self, service: &LandoService, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>, package: &str) {
+        self.run_composer_command(service, project_path, sender, &format!("update {}", shell_quote(package)), true);
+    }
+
+    // La salida se transmite al panel; con `refresh_after` la lista se recarga al terminar
+    pub fn run_composer_command(
+        &mut self,
+        service: &LandoService,
+        project_path: &PathBuf,
+        sender: &Sender<LandoCommandOutcome>,
+        args: &str,
+        refresh_after: bool,
+    ) {
+        if self.composer.running.is_some() {
+            return;
+        }
+
+        let command = format!("composer {}", args);
+        let request_id = next_request_id();
+        let cancel = Arc::new(AtomicBool::new(false));
+        if !self.composer.output.is_empty() && !self.composer.output.ends_with('\n') {
+            self.composer.output.push('\n');
+        }
+        self.composer.output.push_str(&format!("$ {}\n", command));
+        self.composer.running = Some(ComposerRun {
+            request_id,
+            command: command.clone(),
+            cancel: cancel.clone(),
+            refresh_after,
+        });
+        run_shell_stream_tagged(sender.clone(), project_path.clone(), service.service.clone(), command, request_id, cancel);
+    }
+
+    pub fn handle_composer_chunk(&mut self, request_id: u64, chunk: &[u8]) -> bool {
+        if !self.composer.running.as_ref().is_some_and(|r| r.request_id == request_id) {
+            return false;
+        }
+        let text = decode_utf8_stream(&mut self.composer.pending_bytes, chunk);
+        self.composer.output.push_str(&text);
+        true
+    }
+
+    pub fn handle_composer_finished(&mut self, request_id: u64, result: &Result<(), String>) -> bool {
+        let Some(run) = self.composer.running.take_if(|r| r.request_id == request_id) else {
+            return false;
+        };
+        if !self.composer.pending_bytes.is_empty() {
+            let rest = String::from_utf8_lossy(&self.composer.pending_bytes).to_string();
+            self.composer.pending_bytes.clear();
+            self.composer.output.push_str(&rest);
+        }
+        match result {
+            Ok(()) => self.composer.output.push_str(&format!("\n✅ {} terminado\n", run.command)),
+            Err(msg) => self.composer.output.push_str(&format!("\n❌ {}\n", msg)),
+        }
+        // Aunque falle puede haber tocado composer.json/lock
+        self.composer.needs_refresh |= run.refresh_after;
+        true
+    }
+}
+
+fn is_platform_package(name: &str) -> bool {
+    name == "php"
+        || name.starts_with("ext-")
+        || name.starts_with("lib-")
+        || name == "composer-plugin-api"
+        || name == "composer-runtime-api"
+}
+
+// Composer suele imprimir avisos (p. ej. de requisitos de plataforma) antes del
+// JSON: se prueba desde cada línea que empieza por `{` o `[`.
+pub fn extract_json(output: &str) -> Option<Value> {
+    let mut offset = 0;
+    for line in output.split_inclusive('\n') {
+        if line.trim_start().starts_with(['{', '[']) {
+            let mut values = serde_json::Deserializer::from_str(&output[offset..]).into_iter::<Value>();
+            if let Some(Ok(value)) = values.next() {
+                return Some(value);
+            }
+        }
+        offset += line.len();
+    }
+    None
+}
+
+// Comprobación simplificada de restricciones de Composer (`^8.1`, `~7.4`,
+// `>=8.0 <8.3`, `7.4.* || ^8.0`...). Devuelve None si no entiende alguna parte.
+pub fn php_constraint_matches(constraint: &str, version: &str) -> Option<bool> {
+    let version = to_triplet(&parse_version_parts(version)?);
+    let mut matches = false;
+    for alternative in constraint.replace("||", "|").split('|') {
+        let mut all = true;
+        for part in alternative.split(|c: char| c == ',' || c.is_whitespace()).filter(|p| !p.is_empty()) {
+            all &= version_satisfies(part, version)?;
+        }
+        matches |= all;
+    }
+    Some(matches)
+}
+
+fn version_satisfies(part: &str, version: [u64; 3]) -> Option<bool> {
+    let (op, rest) = ["<=", ">=", "!=", "==", "<", ">", "=", "^", "~"].iter()
+        .find_map(|op| part.strip_prefix(op).map(|rest| (*op, rest)))
+        .unwrap_or(("", part));
+    let rest = rest.trim();
+    if rest == "*" {
+        return Some(true);
+    }
+
+    let parts = parse_version_parts(rest)?;
+    let base = to_triplet(&parts);
+    let prefix_matches = || parts.iter().enumerate().all(|(i, p)| version[i] == *p);
+    Some(match op {
+        ">=" => version >= base,
+        ">" => version > base,
+        "<=" => version <= base,
+        "<" => version < base,
+        "!=" => version != base,
+        "^" => version >= base && version < caret_upper_bound(&parts),
+        "~" => version >= base && version < tilde_upper_bound(&parts),
+        // `8.1`, `8.1.*` y `=8.1.2`: coinciden los componentes indicados
+        _ => prefix_matches(),
+    })
+}
+
+// "8.1.2" -> [8, 1, 2]; "v7.4" -> [7, 4]; se para en `*`, `x` o sufijos como `-RC1`
+fn parse_version_parts(version: &str) -> Option<Vec<u64>> {
+    let version = version.trim().trim_start_matches('v');
+    let numeric = version.split(|c: char| !(c.is_ascii_digit() || c == '.' || c == '*' || c == 'x')).next()?;
+    let parts: Vec<u64> = numeric.split('.')
+        .take_while(|p| *p != "*" && *p != "x")
+        .map(|p| p.parse().ok())
+        .collect::<Option<_>>()?;
+    if parts.is_empty() || parts.len() > 3 { None } else { Some(parts) }
+}
+
+fn to_triplet(parts: &[u64]) -> [u64; 3] {
+    let mut triplet = [0; 3];
+    triplet[..parts.len().min(3)].copy_from_slice(&parts[..parts.len().min(3)]);
+    triplet
+}
+
+// ^1.2.3 -> <2.0.0; ^0.3 -> <0.4.0
+fn caret_upper_bound(parts: &[u64]) -> [u64; 3] {
+    let index = parts.iter().position(|p| *p != 0).unwrap_or(parts.len() - 1);
+    let mut upper = [0; 3];
+    upper[..index].copy_from_slice(&parts[..index]);
+    upper[index] = parts[index] + 1;
+    upper
+}
+
+// ~7.4 -> <8.0.0; ~7.4.1 -> <7.5.0
+fn tilde_upper_bound(parts: &[u64]) -> [u64; 3] {
+    let index = parts.len().saturating_sub(2);
+    let mut upper = [0; 3];
+    upper[..index].copy_from_slice(&parts[..index]);
+    upper[index] = parts[index] + 1;
+    upper
+}
@@ -0,0 +1,637 @@
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::Sender;
+use std::thread;
+
+use crate::core::commands::{next_request_id, run_db_query};
+use crate::core::result_format::parse_numeric_cell;
+use crate::core::table_editor::{string_literal, NUMERIC_TYPES};
+use crate::models::commands::LandoCommandOutcome;
+use crate::models::csv_import::{BatchErrorMode, CsvEncoding, CsvOptions, CsvPreview, MissingColumns, ShortRows};
+use crate::models::lando::LandoService;
+use crate::ui::database::{ColumnInfo, DatabaseUI, PendingRequest};
+
+pub const CSV_PREVIEW_ROWS: usize = 50;
+pub const DEFAULT_BATCH_SIZE: usize = 500;
+const DETECTION_SAMPLE_BYTES: usize = 64 * 1024;
+const DELIMITER_CANDIDATES: &[char] = &[',', ';', '\t', '|'];
+// La consulta viaja como argumento de `lando db-cli`, y Linux no admite
+// argumentos de más de 128 KB: un lote se corta antes aunque no llegue al tamaño pedido
+const MAX_BATCH_BYTES: usize = 100 * 1024;
+const MAX_REPORTED_ERRORS: usize = 20;
+
+fn decode_bytes(bytes: &[u8], encoding: CsvEncoding) -> String {
+    match encoding {
+        CsvEncoding::Utf8 => String::from_utf8_lossy(bytes).into_owned(),
+        CsvEncoding::Latin1 => bytes.iter().map(|&b| b as char).collect(),
+    }
+}
+
+// Lector de registros que va leyendo del disco línea a línea. Un campo entre
+// comillas puede abarcar varias líneas; `""` dentro de comillas es una comilla.
+pub struct CsvRecordReader<R> {
+    reader: R,
+    options: CsvOptions,
+    pub bytes_read: u64,
+}
+
+impl<R: BufRead> CsvRecordReader<R> {
+    pub fn new(reader: R, options: CsvOptions) -> Self {
+        Self { reader, options, bytes_read: 0 }
+    }
+
+    // Las líneas en blanco entre registros se saltan
+    pub fn next_record(&mut self) -> io::Result<Option<Vec<String>>> {
+        let (delimiter, quote) = (self.options.delimiter, self.options.quote);
+        let mut fields = Vec::new();
+        let mut field = String::new();
+        let mut in_quotes = false;
+        let mut line = Vec::new();
+
+        loop {
+            line.clear();
+            let read = self.reader.read_until(b'\n', &mut line)?;
+            if read == 0 {
+                // Fin de archivo, quizá con unas comillas sin cerrar
+                if !in_quotes {
+                    return Ok(None);
+                }
+                fields.push(field);
+                return Ok(Some(fields));
+            }
+            self.bytes_read += read as u64;
+
+            let text = decode_bytes(&line, self.options.encoding);
+            let mut chars = text.chars().peekable();
+            while let Some(c) = chars.next() {
+                if in_quotes {
+                    if c != quote {
+                        field.push(c);
+                    } else if chars.peek() == Some(&quote) {
+                        field.push(quote);
+                        chars.next();
+                    } else {
+                        in_quotes = false;
+                    }
+                } else if c == quote && field.is_empty() {
+                    in_quotes = true;
+                } else if c == delimiter {
+                    fields.push(std::mem::take(&mut field));
+                } else if c != '\n' && c != '\r' {
+                    field.push(c);
+                }
+            }
+
+            if !in_quotes {
+                if fields.is_empty() && field.is_empty() {
+                    continue;
+                }
+                fields.push(field);
+                return Ok(Some(fields));
+            }
+        }
+    }
+}
+
+// Abre el archivo saltando el BOM de UTF-8, si lo tiene
+pub fn open_csv(path: &Path, options: CsvOptions) -> io::Result<CsvRecordReader<BufReader<File>>> {
+    let mut reader = BufReader::new(File::open(path)?);
+    let has_bom = reader.fill_buf()?.starts_with(&[0xEF, 0xBB, 0xBF]);
+    if has_bom {
+        reader.consume(3);
+    }
+    let mut records = CsvRecordReader::new(reader, options);
+    records.bytes_read = if has_bom { 3 } else { 0 };
+    Ok(records)
+}
+
+// Codificación por validez UTF-8 (el final de la muestra puede cortar un
+// carácter), separador por el candidato que aparece más veces en todas las
+// primeras líneas, y cabecera si la primera fila no tiene ningún número
+pub fn detect_options(sample: &[u8]) -> CsvOptions {
+    let sample = sample.strip_prefix(&[0xEF, 0xBB, 0xBF]).unwrap_or(sample);
+    let encoding = match std::str::from_utf8(sample) {
+        Ok(_) => CsvEncoding::Utf8,
+        Err(e) if e.error_len().is_none() => CsvEncoding::Utf8,
+        Err(_) => CsvEncoding::Latin1,
+    };
+    let text = decode_bytes(sample, encoding);
+    let lines: Vec<&str> = text.lines().filter(|l| !l.trim().is_empty()).take(10).collect();
+
+    let delimiter = DELIMITER_CANDIDATES.iter()
+        .map(|&candidate| {
+            let min_count = lines.iter().map(|l| l.matches(candidate).count()).min().unwrap_or(0);
+            (candidate, min_count)
+        })
+        .filter(|(_, count)| *count > 0)
+        .max_by_key(|(_, count)| *count)
+        .map(|(candidate, _)| candidate)
+        .unwrap_or(',');
+    let quote = if !text.contains('"') && text.contains('\'') { '\'' } else { '"' };
+
+    let mut options = CsvOptions { delimiter, quote, encoding, has_header: true };
+    let mut records = CsvRecordReader::new(text.as_bytes(), CsvOptions { encoding: CsvEncoding::Utf8, ..options });
+    if let Ok(Some(first)) = records.next_record() {
+        options.has_header = first.iter().all(|f| parse_numeric_cell(f).is_none());
+    }
+    options
+}
+
+fn read_preview(path: &Path, options: Option<CsvOptions>) -> io::Result<CsvPreview> {
+    let file_size = std::fs::metadata(path)?.len();
+    let options = match options {
+        Some(options) => options,
+        None => {
+            let mut sample = Vec::new();
+            File::open(path)?.take(DETECTION_SAMPLE_BYTES as u64).read_to_end(&mut sample)?;
+            detect_options(&sample)
+        }
+    };
+
+    let mut records = open_csv(path, options)?;
+    let mut rows = Vec::new();
+    while rows.len() < CSV_PREVIEW_ROWS + 1 {
+        match records.next_record()? {
+            Some(record) => rows.push(record),
+            None => break,
+        }
+    }
+
+    let headers = if options.has_header && !rows.is_empty() {
+        rows.remove(0)
+    } else {
+        let width = rows.iter().map(|r| r.len()).max().unwrap_or(0);
+        (1..=width).map(|i| format!("columna_{}", i)).collect()
+    };
+    rows.truncate(CSV_PREVIEW_ROWS);
+    Ok(CsvPreview { path: path.to_path_buf(), options, headers, rows, file_size })
+}
+
+// Sin `options` se detectan a partir del principio del archivo
+pub fn load_csv_preview(sender: Sender<LandoCommandOutcome>, service: String, path: PathBuf, options: Option<CsvOptions>) {
+    thread::spawn(move || {
+        let result = read_preview(&path, options)
+            .map_err(|e| format!("No se pudo leer '{}': {}", path.display(), e));
+        let _ = sender.send(LandoCommandOutcome::CsvPreviewLoaded { service, result });
+    });
+}
+
+fn normalize_name(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+// Columna de la tabla para cada columna del CSV, emparejando por nombre sin
+// tener en cuenta mayúsculas, espacios ni guiones
+pub fn auto_map_columns(headers: &[String], columns: &[ColumnInfo]) -> Vec<Option<String>> {
+    headers.iter()
+        .map(|header| {
+            let header = normalize_name(header);
+            columns.iter()
+                .find(|c| normalize_name(&c.name) == header)
+                .map(|c| c.name.clone())
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ValueKind {
+    Number,
+    Boolean,
+    Date,
+    Text,
+}
+
+fn value_kind(data_type: &str) -> ValueKind {
+    let data_type = data_type.to_lowercase();
+    if data_type.starts_with("tinyint(1)") || data_type.starts_with("bool") {
+        ValueKind::Boolean
+    } else if NUMERIC_TYPES.iter().any(|t| data_type.contains(t)) && !data_type.contains("interval") && !data_type.contains("point") {
+        ValueKind::Number
+    } else if ["date", "time", "year"].iter().any(|t| data_type.contains(t)) {
+        ValueKind::Date
+    } else {
+        ValueKind::Text
+    }
+}
+
+fn is_plain_number(value: &str) -> bool {
+    value.parse::<f64>().is_ok_and(f64::is_finite)
+        && value.chars().all(|c| c.is_ascii_digit() || matches!(c, '-' | '+' | '.' | 'e' | 'E'))
+}
+
+// Valor SQL de un campo del CSV según el tipo de la columna destino: números
+// sin comillas, booleanos como TRUE/FALSE, fechas y texto como literal tal
+// cual. Un campo vacío es NULL si la columna lo admite, su valor por defecto si
+// tiene uno (solo MySQL/PostgreSQL aceptan DEFAULT en VALUES) o cadena vacía
+// en columnas de texto.
+pub fn csv_sql_value(db_type: &str, column: &ColumnInfo, raw: &str) -> Result<String, String> {
+    let kind = value_kind(&column.data_type);
+    let value = if kind == ValueKind::Text { raw } else { raw.trim() };
+
+    if value.is_empty() {
+        let supports_default = matches!(db_type.to_lowercase().as_str(), "mysql" | "mariadb" | "postgres" | "postgresql");
+        return if column.nullable {
+            Ok("NULL".to_string())
+        } else if column.default_value.is_some() && supports_default {
+            Ok("DEFAULT".to_string())
+        } else if kind == ValueKind::Text {
+            Ok(string_literal(db_type, ""))
+        } else {
+            Err(format!("'{}' no admite valores vacíos", column.name))
+        };
+    }
+
+    match kind {
+        ValueKind::Number if is_plain_number(value) => Ok(value.to_string()),
+        ValueKind::Number => Err(format!("'{}' espera un número y el valor es '{}'", column.name, value)),
+        ValueKind::Boolean => match value.to_lowercase().as_str() {
+            "1" | "true" | "t" | "yes" | "y" | "si" | "sí" => Ok("TRUE".to_string()),
+            "0" | "false" | "f" | "no" | "n" => Ok("FALSE".to_string()),
+            _ => Err(format!("'{}' espera un booleano y el valor es '{}'", column.name, value)),
+        },
+        ValueKind::Date | ValueKind::Text => Ok(string_literal(db_type, value)),
+    }
+}
+
+// Importación en curso: el archivo se lee lote a lote a medida que la BD
+// confirma el anterior, así nunca está entero en memoria
+pub struct CsvImportJob {
+    pub reader: CsvRecordReader<BufReader<File>>,
+    pub table: String,
+    pub file_size: u64,
+    pub row_number: usize, // Filas de datos leídas (sin la cabecera)
+    pub rows_imported: usize,
+    pub rows_skipped: usize,
+    pub batches_sent: usize,
+    pub batches_failed: usize,
+    pub in_flight: Option<(u64, usize)>, // (request_id, filas del lote)
+    pub send_next: bool,
+    pub finished: bool,
+    pub cancelled: bool,
+    pub errors: Vec<String>,
+}
+
+impl CsvImportJob {
+    pub fn progress(&self) -> f32 {
+        if self.finished || self.file_size == 0 {
+            return 1.0;
+        }
+        (self.reader.bytes_read as f64 / self.file_size as f64).min(1.0) as f32
+    }
+
+    fn report(&mut self, error: String) {
+        if self.errors.len() < MAX_REPORTED_ERRORS {
+            self.errors.push(error);
+        }
+    }
+}
+
+impl DatabaseUI {
+    pub fn pick_csv_file(&mut self, service: &LandoService, sender: &Sender<LandoCommandOutcome>) {
+        self.csv_import.loading = true;
+        self.csv_import.error = None;
+        let (sender, service) = (sender.clone(), service.service.clone());
+        thread::spawn(move || {
+            match rfd::FileDialog::new().add_filter("CSV", &["csv", "tsv", "txt"]).pick_file() {
+                Some(path) => load_csv_preview(sender, service, path, None),
+                None => { let _ = sender.send(LandoCommandOutcome::CsvPreviewLoaded { service, result: Err(String::new()) }); }
+            }
+        });
+    }
+
+    // Vuelve a leer la vista previa con el formato elegido a mano
+    pub fn reload_csv_preview(&mut self, service: &LandoService, sender: &Sender<LandoCommandOutcome>, options: CsvOptions) {
+        let Some(preview) = &self.csv_import.preview else {
+            return;
+        };
+        self.csv_import.loading = true;
+        load_csv_preview(sender.clone(), service.service.clone(), preview.path.clone(), Some(options));
+    }
+
+    // Un error vacío es que se canceló el diálogo de archivo
+    pub fn handle_csv_preview(&mut self, result: Result<CsvPreview, String>) {
+        let state = &mut self.csv_import;
+        state.loading = false;
+        match result {
+            Ok(preview) => {
+                if state.preview.as_ref().map(|p| &p.headers) != Some(&preview.headers) {
+                    state.mapped_for = None;
+                }
+                state.preview = Some(preview);
+                state.error = None;
+                if state.job.as_ref().is_some_and(|job| job.finished) {
+                    state.job = None;
+                }
+                state.open = true;
+            }
+            Err(e) if e.is_empty() => {}
+            Err(e) => state.error = Some(e),
+        }
+    }
+
+    pub fn table_columns(&self, table: &str) -> &[ColumnInfo] {
+        self.tables.iter()
+            .find(|t| t.name == table)
+            .map(|t| t.columns.as_slice())
+            .unwrap_or(&[])
+    }
+
+    // Empareja por nombre la primera vez que están las columnas de la tabla elegida
+    pub fn ensure_csv_mapping(&mut self, service: &LandoService, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>) {
+        let table = self.csv_import.table.clone();
+        if table.is_empty() || self.csv_import.mapped_for.as_ref() == Some(&table) {
+            return;
+        }
+        let columns = self.table_columns(&table).to_vec();
+        if columns.is_empty() {
+            if self.csv_import.columns_requested.as_ref() != Some(&table) {
+                self.csv_import.columns_requested = Some(table.clone());
+                self.load_table_columns(service, project_path, sender, &table);
+            }
+            return;
+        }
+        if let Some(preview) = &self.csv_import.preview {
+            self.csv_import.mapping = auto_map_columns(&preview.headers, &columns);
+            self.csv_import.mapped_for = Some(table);
+        }
+    }
+
+    pub fn start_csv_import(&mut self) {
+        let Some(preview) = &self.csv_import.preview else {
+            return;
+        };
+        let mut reader = match open_csv(&preview.path, preview.options) {
+            Ok(reader) => reader,
+            Err(e) => {
+                self.csv_import.error = Some(format!("No se pudo abrir '{}': {}", preview.path.display(), e));
+                return;
+            }
+        };
+        if preview.options.has_header {
+            if let Err(e) = reader.next_record() {
+                self.csv_import.error = Some(format!("No se pudo leer la cabecera: {}", e));
+                return;
+            }
+        }
+        self.csv_import.error = None;
+        self.csv_import.job = Some(CsvImportJob {
+            reader,
+            table: self.csv_import.table.clone(),
+            file_size: preview.file_size,
+            row_number: 0,
+            rows_imported: 0,
+            rows_skipped: 0,
+            batches_sent: 0,
+            batches_failed: 0,
+            in_flight: None,
+            send_next: true,
+            finished: false,
+            cancelled: false,
+            errors: Vec::new(),
+        });
+    }
+
+    pub fn cancel_csv_import(&mut self) {
+        if let Some(job) = &mut self.csv_import.job {
+            job.send_next = false;
+            job.cancelled = true;
+            if job.in_flight.is_none() {
+                job.finished = true;
+            }
+        }
+    }
+
+    // Se llama en cada frame: lanza el siguiente lote cuando el anterior terminó
+    pub fn tick_csv_import(&mut self, service: &LandoService, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>) {
+        if !self.csv_import.job.as_ref().is_some_and(|job| job.send_next && !job.finished) {
+            return;
+        }
+        let db_type = service.db_engine().to_string();
+        let table = self.csv_import.job.as_ref().map(|job| job.table.clone()).unwrap_or_default();
+        let columns = self.table_columns(&table).to_vec();
+
+        // (columna del CSV, columna de la tabla); None en el CSV es NULL explícito
+        let mut targets: Vec<(Option<usize>, ColumnInfo)> = self.csv_import.mapping.iter()
+            .enumerate()
+            .filter_map(|(index, target)| {
+                let column = columns.iter().find(|c| Some(&c.name) == target.as_ref())?;
+                Some((Some(index), column.clone()))
+            })
+            .collect();
+        if self.csv_import.missing_columns == MissingColumns::Null {
+            for column in &columns {
+                if !targets.iter().any(|(_, c)| c.name == column.name) {
+                    targets.push((None, column.clone()));
+                }
+            }
+        }
+        let column_list = targets.iter()
+            .map(|(_, c)| self.quote_identifier(&db_type, &c.name))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let prefix = format!("INSERT INTO {} ({}) VALUES\n", self.quote_identifier(&db_type, &table), column_list);
+        let needed = targets.iter().filter_map(|(index, _)| *index).max().map_or(0, |max| max + 1);
+        let (short_rows, on_error, batch_size) = (self.csv_import.short_rows, self.csv_import.on_error, self.csv_import.batch_size.max(1));
+
+        let Some(job) = &mut self.csv_import.job else {
+            return;
+        };
+        job.send_next = false;
+        let mut values = Vec::new();
+        let mut size = prefix.len();
+        let mut stop = false;
+
+        while values.len() < batch_size && size < MAX_BATCH_BYTES {
+            let mut record = match job.reader.next_record() {
+                Ok(Some(record)) => record,
+                Ok(None) => break,
+                Err(e) => {
+                    job.report(format!("Error leyendo el archivo: {}", e));
+                    stop = true;
+                    break;
+                }
+            };
+            job.row_number += 1;
+
+            if record.len() < needed {
+                if short_rows == ShortRows::Skip {
+                    job.rows_skipped += 1;
+                    job.report(format!("Fila {}: tiene {} campos y se esperaban {}", job.row_number, record.len(), needed));
+                    continue;
+                }
+                record.resize(needed, String::new());
+            }
+
+            let row: Result<Vec<String>, String> = targets.iter()
+                .map(|(index, column)| match index {
+                    Some(index) => csv_sql_value(&db_type, column, &record[*index]),
+                    None => Ok("NULL".to_string()),
+                })
+                .collect();
+            match row {
+                Ok(row) => {
+                    let tuple = format!("({})", row.join(", "));
+                    size += tuple.len() + 2;
+                    values.push(tuple);
+                }
+                Err(e) => {
+                    job.rows_skipped += 1;
+                    job.report(format!("Fila {}: {}", job.row_number, e));
+                    if on_error == BatchErrorMode::Stop {
+                        stop = true;
+                        break;
+                    }
+                }
+            }
+        }
+
+        if stop || values.is_empty() {
+            job.finished = true;
+            return;
+        }
+
+        let request_id = next_request_id();
+        job.in_flight = Some((request_id, values.len()));
+        job.batches_sent += 1;
+        let sql = format!("{}{};", prefix, values.join(",\n"));
+        self.pending_requests.insert(request_id, PendingRequest::CsvImportBatch);
+        run_db_query(sender.clone(), project_path.clone(), self.db_connection(service), sql, request_id);
+    }
+
+    pub fn handle_csv_batch_result(&mut self, output: &Result<String, String>) {
+        let on_error = self.csv_import.on_error;
+        let Some(job) = &mut self.csv_import.job else {
+            return;
+        };
+        let Some((_, rows)) = job.in_flight.take() else {
+            return;
+        };
+
+        match output {
+            Ok(_) => job.rows_imported += rows,
+            Err(e) => {
+                job.batches_failed += 1;
+                job.rows_skipped += rows;
+                let error = format!("Lote {} ({} filas, hasta la fila {}): {}", job.batches_sent, rows, job.row_number, e.lines().next().unwrap_or_default());
+                job.report(error);
+                if on_error == BatchErrorMode::Stop {
+                    job.finished = true;
+                }
+            }
+        }
+        if job.cancelled {
+            job.finished = true;
+        }
+        job.send_next = !job.finished;
+
+        // La tabla cambió: fuera la caché y, si está abierta, se recarga
+        self.query_cache.clear();
+        if self.current_table == self.csv_import.table {
+            self.needs_table_reload = true;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read_all(text: &[u8], options: CsvOptions) -> Vec<Vec<String>> {
+        let mut records = CsvRecordReader::new(text, options);
+        let mut rows = Vec::new();
+        while let Some(record) = records.next_record().unwrap() {
+            rows.push(record);
+        }
+        rows
+    }
+
+    fn row(fields: &[&str]) -> Vec<String> {
+        fields.iter().map(|f| f.to_string()).collect()
+    }
+
+    #[test]
+    fn reads_quoted_fields_with_delimiters_and_doubled_quotes() {
+        let rows = read_all(b"id,name\n1,\"Smith, John\"\n2,\"say \"\"hi\"\"\"\n", CsvOptions::default());
+        assert_eq!(rows, vec![row(&["id", "name"]), row(&["1", "Smith, John"]), row(&["2", "say \"hi\""])]);
+    }
+
+    #[test]
+    fn quoted_field_can_span_several_lines() {
+        let rows = read_all(b"id,notes\r\n1,\"first line\nsecond line\"\r\n2,plain\r\n", CsvOptions::default());
+        assert_eq!(rows, vec![row(&["id", "notes"]), row(&["1", "first line\nsecond line"]), row(&["2", "plain"])]);
+    }
+
+    #[test]
+    fn skips_blank_lines_and_keeps_empty_fields() {
+        let rows = read_all(b"a,b,c\n\n1,,3\n,,\n", CsvOptions::default());
+        assert_eq!(rows, vec![row(&["a", "b", "c"]), row(&["1", "", "3"]), row(&["", "", ""])]);
+    }
+
+    #[test]
+    fn unterminated_quote_returns_the_rest_of_the_file() {
+        let rows = read_all(b"1,\"never closed\n2,x\n", CsvOptions::default());
+        assert_eq!(rows, vec![row(&["1", "never closed\n2,x\n"])]);
+    }
+
+    #[test]
+    fn decodes_latin1() {
+        let options = CsvOptions { encoding: CsvEncoding::Latin1, ..CsvOptions::default() };
+        let rows = read_all(b"nombre\nJos\xe9\n", options);
+        assert_eq!(rows, vec![row(&["nombre"]), row(&["José"])]);
+    }
+
+    #[test]
+    fn open_csv_skips_the_utf8_bom() {
+        let path = std::env::temp_dir().join(format!("lando_gui_bom_{}.csv", std::process::id()));
+        std::fs::write(&path, b"\xEF\xBB\xBFid,name\n1,Ana\n").unwrap();
+        let mut records = open_csv(&path, CsvOptions::default()).unwrap();
+        let header = records.next_record().unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(header, Some(row(&["id", "name"])));
+    }
+
+    #[test]
+    fn detects_the_delimiter() {
+        assert_eq!(detect_options(b"id;name;city\n1;Ana;Madrid\n2;Luis;Lima\n").delimiter, ';');
+        assert_eq!(detect_options(b"id\tname\n1\tAna\n").delimiter, '\t');
+        assert_eq!(detect_options(b"id|name\n1|Ana\n").delimiter, '|');
+        // Las comas del texto no ganan a un separador presente en todas las líneas
+        assert_eq!(detect_options(b"id;note\n1;a, b, c\n2;d\n").delimiter, ';');
+        assert_eq!(detect_options(b"solo\n1\n").delimiter, ',');
+    }
+
+    #[test]
+    fn detects_header_quote_and_encoding() {
+        let options = detect_options(b"\xEF\xBB\xBFid,name\n1,Ana\n");
+        assert!(options.has_header);
+        assert_eq!(options.encoding, CsvEncoding::Utf8);
+
+        assert!(!detect_options(b"1,Ana\n2,Luis\n").has_header);
+        assert_eq!(detect_options(b"id,name\n1,'Ana'\n").quote, '\'');
+        assert_eq!(detect_options(b"nombre\nJos\xe9\n").encoding, CsvEncoding::Latin1);
+        // Un carácter cortado al final de la muestra no la hace Latin-1
+        assert_eq!(detect_options("nombre\nJosé".as_bytes().split_last().unwrap().1).encoding, CsvEncoding::Utf8);
+    }
+
+    #[test]
+    fn sql_values_follow_the_column_type() {
+        let column = |data_type: &str, nullable: bool| ColumnInfo {
+            name: "c".to_string(),
+            data_type: data_type.to_string(),
+            nullable,
+            default_value: None,
+            is_primary_key: false,
+            stats: None,
+        };
+        assert_eq!(csv_sql_value("mysql", &column("int(11)", false), " 42 "), Ok("42".to_string()));
+        assert!(csv_sql_value("mysql", &column("int(11)", false), "4x").is_err());
+        assert_eq!(csv_sql_value("mysql", &column("tinyint(1)", false), "sí"), Ok("TRUE".to_string()));
+        assert_eq!(csv_sql_value("mysql", &column("varchar(20)", true), ""), Ok("NULL".to_string()));
+        assert_eq!(csv_sql_value("mysql", &column("varchar(20)", false), "O'Brien"), Ok("'O''Brien'".to_string()));
+        assert!(csv_sql_value("postgres", &column("integer", false), "").is_err());
+    }
+}
@@ -0,0 +1,163 @@
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::Sender;
+use std::thread;
+
+use crate::core::recipes::{detect_app_name, detect_recipe};
+use crate::core::time::now_secs;
+use crate::models::commands::LandoCommandOutcome;
+use crate::models::dashboard::{CardStatus, ProjectCardInfo, ProjectDashboard};
+use crate::models::lando::LandoApp;
+
+// En Windows las rutas no distinguen mayúsculas y `lando list` puede devolver
+// la unidad o las barras de otra forma que el escaneo
+pub fn same_project_path(a: &Path, b: &Path) -> bool {
+    if cfg!(windows) {
+        let normalize = |path: &Path| path.to_string_lossy().replace('/', "\\").trim_end_matches('\\').to_lowercase();
+        normalize(a) == normalize(b)
+    } else {
+        a == b
+    }
+}
+
+// Lando normaliza el nombre de la app quitando guiones y mayúsculas
+fn normalize_app_name(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .collect::<String>()
+        .to_lowercase()
+}
+
+// Entrada de `lando list` de un proyecto: primero por ubicación y, como
+// respaldo, por nombre de carpeta
+pub fn find_lando_app<'a>(apps: &'a [LandoApp], project_path: &Path) -> Option<&'a LandoApp> {
+    let by_location = apps.iter()
+        .find(|app| !app.location.is_empty() && same_project_path(Path::new(&app.location), project_path));
+    by_location.or_else(|| {
+        let project_name = normalize_app_name(&project_path.file_name().unwrap_or_default().to_string_lossy());
+        if project_name.is_empty() {
+            return None;
+        }
+        apps.iter().find(|app| normalize_app_name(&app.name) == project_name)
+    })
+}
+
+// Proyectos con la app en marcha según `lando list`, salvo los de `keep`. Las
+// apps sin ubicación se buscan entre los descubiertos; si no aparecen, no se
+// puede lanzar nada sobre ellas y se dejan fuera.
+pub fn running_projects_except(apps: &[LandoApp], projects: &[PathBuf], keep: &[PathBuf]) -> Vec<PathBuf> {
+    let mut result: Vec<PathBuf> = Vec::new();
+    for app in apps.iter().filter(|app| app.running) {
+        let path = if app.location.is_empty() {
+            projects.iter().find(|p| find_lando_app(std::slice::from_ref(app), p).is_some()).cloned()
+        } else {
+            Some(PathBuf::from(&app.location))
+        };
+        let Some(path) = path else {
+            continue;
+        };
+        let listed = |list: &[PathBuf]| list.iter().any(|p| same_project_path(p, &path));
+        if !listed(keep) && !listed(&result) {
+            result.push(path);
+        }
+    }
+    result
+}
+
+// La URL https si la hay: es la que Lando muestra primero en `lando info`
+pub fn primary_url(app: &LandoApp) -> Option<&str> {
+    app.urls.iter()
+        .find(|url| url.starts_with("https://"))
+        .or_else(|| app.urls.first())
+        .map(String::as_str)
+}
+
+// Coincidencia difusa: las letras de la búsqueda deben aparecer en orden.
+// Puntúan más las seguidas y las que empiezan palabra; None si no coincide.
+pub fn fuzzy_score(query: &str, text: &str) -> Option<i32> {
+    let query: Vec<char> = query.to_lowercase().chars().filter(|c| !c.is_whitespace()).collect();
+    if query.is_empty() {
+        return Some(0);
+    }
+
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut next = 0;
+    let mut previous_match: Option<usize> = None;
+    for (index, c) in text.iter().enumerate() {
+        if next == query.len() {
+            break;
+        }
+        if *c != query[next] {
+            continue;
+        }
+        score += 1;
+        if previous_match.is_some_and(|p| p + 1 == index) {
+            score += 5;
+        }
+        if index == 0 || !text[index - 1].is_alphanumeric() {
+            score += 3;
+        }
+        previous_match = Some(index);
+        next += 1;
+    }
+    (next == query.len()).then_some(score - text.len() as i32 / 10)
+}
+
+pub fn format_last_opened(opened_at: u64) -> String {
+    let elapsed = now_secs().saturating_sub(opened_at);
+    match elapsed {
+        0..=59 => "hace unos segundos".to_string(),
+        60..=3599 => format!("hace {} min", elapsed / 60),
+        3600..=86_399 => format!("hace {} h", elapsed / 3600),
+        _ => format!("hace {} d", elapsed / 86_400),
+    }
+}
+
+pub fn load_card_info(sender: Sender<LandoCommandOutcome>, project: PathBuf) {
+    thread::spawn(move || {
+        let info = ProjectCardInfo {
+            app_name: detect_app_name(&project),
+            recipe: detect_recipe(&project),
+        };
+        let _ = sender.send(LandoCommandOutcome::DashboardCard { project, info });
+    });
+}
+
+impl ProjectDashboard {
+    // Se llama al dibujar cada tarjeta: solo se lee lo que llega a verse
+    pub fn request_card(&mut self, sender: &Sender<LandoCommandOutcome>, project: &Path) -> &CardStatus {
+        self.cards.entry(project.to_path_buf()).or_insert_with(|| {
+            load_card_info(sender.clone(), project.to_path_buf());
+            CardStatus::Loading
+        })
+    }
+
+    pub fn handle_card(&mut self, project: PathBuf, info: ProjectCardInfo) {
+        self.cards.insert(project, CardStatus::Ready(info));
+    }
+
+    pub fn refresh(&mut self) {
+        self.cards.clear();
+    }
+
+    // Sin búsqueda, el último abierto primero; con búsqueda, por puntuación
+    pub fn visible_projects(&self, projects: &[PathBuf], last_opened: impl Fn(&Path) -> Option<u64>) -> Vec<PathBuf> {
+        let mut scored: Vec<(i32, Option<u64>, &PathBuf)> = projects.iter()
+            .filter_map(|project| {
+                let name = project.file_name().unwrap_or_default().to_string_lossy();
+                let app_name = match self.cards.get(project) {
+                    Some(CardStatus::Ready(info)) => info.app_name.as_deref().unwrap_or_default(),
+                    _ => "",
+                };
+                let score = [fuzzy_score(&self.search, &name), fuzzy_score(&self.search, app_name)]
+                    .into_iter()
+                    .flatten()
+                    .max()
+                    .or_else(|| fuzzy_score(&self.search, &project.to_string_lossy()))?;
+                Some((score, last_opened(project), project))
+            })
+            .collect();
+        scored.sort_by(|a, b| b.0.cmp(&a.0).then(b.1.cmp(&a.1)).then(a.2.cmp(b.2)));
+        scored.into_iter().map(|(_, _, project)| project.clone()).collect()
+    }
+}
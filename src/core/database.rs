@@ -1,47 +1,69 @@
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
 use std::sync::mpsc::Sender;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::sync::Arc;
 use crate::models::commands::LandoCommandOutcome;
 use crate::core::commands::*;
+use crate::core::backups::delete_backups;
+use crate::core::logging::redact_secrets;
 use crate::models::lando::LandoService;
-use crate::ui::database::{ConnectionStatus, DatabaseUI, QueryResult, TableInfo};
+use eframe::egui;
+use crate::ui::confirm::{ConfirmDialog, ConfirmResponse};
+use crate::ui::json_view::show_json;
+use crate::ui::theme::{error_color, warning_color};
+use crate::core::result_format::{parse_result_set, result_set_to_csv, result_set_to_inserts, result_set_to_json, result_set_to_markdown, CopyFormat, ParsedResultSet};
+use crate::core::snippets::{parse_snippet, with_default, SnippetSession};
+use crate::core::sql_lint::{format_sql, lint_sql, statement_count, LintRule, LintWarning, SqlDialect, SQL_LINT_FILE};
+use crate::core::storage::save_app_file;
+use crate::core::time::now_secs;
+use crate::ui::database::{ColumnInfo, ColumnStats, ConnectionStatus, DatabaseTab, DatabaseUI, MaintenanceOp, PendingRequest, PinnedResult, QueryResult, TableInfo, ToolCommand};
 
-impl DatabaseUI {
-    pub fn update_query_result(&mut self, result_text: String, has_error: bool) {
-        let rows_affected = self.extract_rows_affected(&result_text);
-        let execution_time = if let Some(last_result) = self.query_results.last_mut() {
-            let start_time = last_result.timestamp;
-            let current_time = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
-            let exec_time = (current_time - start_time) as f64 * 1000.0; // en ms
-
-            last_result.result = result_text.clone();
-            last_result.execution_time = exec_time;
-            last_result.has_error = has_error;
-            last_result.rows_affected = rows_affected;
+// Último formato elegido en el menú de copiar resultados
+pub const COPY_FORMAT_FILE: &str = "copy_format.json";
 
-            exec_time
-        } else {
-            let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
-            let result = QueryResult {
-                query: self.query_input.clone(),
-                result: result_text.clone(),
-                execution_time: 0.0,
-                timestamp,
-                rows_affected: self.extract_rows_affected(&result_text),
-                has_error,
-            };
-            self.query_results.push(result);
-            self.current_result_index = self.query_results.len() - 1;
-            0.0
-        };
+impl DatabaseUI {
+    // Resultado provisional de una consulta en curso. Devuelve el id con el
+    // que vendrá su respuesta, para rellenar este resultado y no otro.
+    // La consulta se guarda sin contraseñas (`IDENTIFIED BY '...'`).
+    pub fn push_pending_result(&mut self, query: String, message: &str) -> u64 {
+        let request_id = next_request_id();
+        self.query_results.push(QueryResult {
+            query: redact_secrets(&query),
+            result: Arc::from(message),
+            execution_time: 0.0,
+            timestamp: now_secs(),
+            rows_affected: None,
+            has_error: false,
+            cached_at: None,
+            request_id: Some(request_id),
+        });
+        self.current_result_index = self.query_results.len() - 1;
 
         // Limitar el número de resultados guardados
         if self.query_results.len() > 20 {
             self.query_results.remove(0);
-            if self.current_result_index > 0 {
-                self.current_result_index -= 1;
-            }
+            self.current_result_index -= 1;
         }
+        request_id
+    }
+
+    pub fn has_running_queries(&self) -> bool {
+        self.query_results.iter().any(|r| r.request_id.is_some())
+    }
+
+    pub fn update_query_result(&mut self, index: usize, result_text: String, has_error: bool) {
+        let rows_affected = self.extract_rows_affected(&result_text);
+        let Some(result) = self.query_results.get_mut(index) else {
+            return;
+        };
+
+        let current_time = now_secs();
+        result.execution_time = current_time.saturating_sub(result.timestamp) as f64 * 1000.0; // en ms
+        result.result = Arc::from(result_text);
+        result.has_error = has_error;
+        result.rows_affected = rows_affected;
+        result.request_id = None;
     }
 
     pub fn extract_rows_affected(&self, result: &str) -> Option<i32> {
@@ -58,23 +80,39 @@ impl DatabaseUI {
     }
 
     // Métodos auxiliares mejorados
-    pub fn insert_template(&mut self, template: &str) {
+    pub fn append_to_editor(&mut self, text: &str) {
         if !self.query_input.is_empty() {
             self.query_input.push_str("\n\n");
         }
-        self.query_input.push_str(template);
+        self.query_input.push_str(text);
+    }
+
+    // Las plantillas llevan huecos `${1:table_name}`: al insertarlas se
+    // selecciona el primero y Tab/Shift+Tab recorren el resto. Con el esquema
+    // cargado, la tabla seleccionada rellena el primer hueco de tabla.
+    pub fn insert_template(&mut self, template: &str) {
+        let template = match &self.selected_table {
+            Some(table) if !self.tables.is_empty() => with_default(template, "table_name", table),
+            _ => template.to_string(),
+        };
+        let snippet = parse_snippet(&template);
+        self.append_to_editor("");
+        let offset = self.query_input.chars().count();
+        self.query_input.push_str(&snippet.text);
+        self.snippet = SnippetSession::new(&snippet, offset, self.query_input.chars().count());
+        self.snippet_select_pending = self.snippet.is_some();
     }
 
     pub fn get_sql_templates(&self, db_type: &str) -> Vec<(&str, String)> {
         let mut templates = vec![
-            ("📋 SELECT", "SELECT * FROM table_name LIMIT 10;".to_string()),
-            ("🔍 COUNT", "SELECT COUNT(*) FROM table_name;".to_string()),
+            ("📋 SELECT", "SELECT * FROM ${1:table_name} LIMIT ${2:10};".to_string()),
+            ("🔍 COUNT", "SELECT COUNT(*) FROM ${1:table_name};".to_string()),
             ("📊 TABLES", self.get_show_tables_query(db_type)),
             ("🏗️ DESCRIBE", self.get_describe_template(db_type)),
-            ("🔍 WHERE", "SELECT * FROM table_name WHERE column = 'value';".to_string()),
-            ("📈 ORDER BY", "SELECT * FROM table_name ORDER BY column DESC;".to_string()),
-            ("📊 GROUP BY", "SELECT column, COUNT(*) FROM table_name GROUP BY column;".to_string()),
-            ("🔗 JOIN", "SELECT * FROM table1 t1 JOIN table2 t2 ON t1.id = t2.table1_id;".to_string()),
+            ("🔍 WHERE", "SELECT * FROM ${1:table_name} WHERE ${2:column} = '${3:value}';".to_string()),
+            ("📈 ORDER BY", "SELECT * FROM ${1:table_name} ORDER BY ${2:column} ${3:DESC};".to_string()),
+            ("📊 GROUP BY", "SELECT ${2:column}, COUNT(*) FROM ${1:table_name} GROUP BY ${2};".to_string()),
+            ("🔗 JOIN", "SELECT * FROM ${1:table_name} t1 JOIN ${2:other_table} t2 ON t1.${3:id} = t2.${4:table_id};".to_string()),
         ];
 
         // Templates específicos por tipo de BD
@@ -84,13 +122,13 @@ impl DatabaseUI {
                     ("📈 STATUS", "SHOW STATUS;".to_string()),
                     ("🔧 PROCESSES", "SHOW PROCESSLIST;".to_string()),
                     ("💾 DATABASES", "SHOW DATABASES;".to_string()),
-                    ("🔍 INDEX", "SHOW INDEX FROM table_name;".to_string()),
-                    ("📊 VARIABLES", "SHOW VARIABLES LIKE '%buffer%';".to_string()),
+                    ("🔍 INDEX", "SHOW INDEX FROM ${1:table_name};".to_string()),
+                    ("📊 VARIABLES", "SHOW VARIABLES LIKE '%${1:buffer}%';".to_string()),
                     ("🔧 ENGINES", "SHOW ENGINES;".to_string()),
                     ("📈 PERFORMANCE", "SELECT * FROM performance_schema.events_statements_summary_by_digest LIMIT 10;".to_string()),
                     ("🔍 USERS", "SELECT User, Host FROM mysql.user;".to_string()),
                     ("📊 TABLES STATUS", "SHOW TABLE STATUS;".to_string()),
-                    ("🔧 CREATE TABLE", "CREATE TABLE example_table (\n    id INT AUTO_INCREMENT PRIMARY KEY,\n    name VARCHAR(255) NOT NULL,\n    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP\n);".to_string()),
+                    ("🔧 CREATE TABLE", "CREATE TABLE ${1:example_table} (\n    id INT AUTO_INCREMENT PRIMARY KEY,\n    ${2:name} VARCHAR(255) NOT NULL,\n    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP\n);".to_string()),
                 ]);
             },
             "postgresql" | "postgres" => {
@@ -98,33 +136,33 @@ impl DatabaseUI {
                     ("📈 STATS", "SELECT * FROM pg_stat_database;".to_string()),
                     ("🔧 ACTIVITY", "SELECT * FROM pg_stat_activity;".to_string()),
                     ("💾 SCHEMAS", "SELECT schema_name FROM information_schema.schemata;".to_string()),
-                    ("🔍 INDEXES", "SELECT * FROM pg_indexes WHERE tablename = 'table_name';".to_string()),
+                    ("🔍 INDEXES", "SELECT * FROM pg_indexes WHERE tablename = '${1:table_name}';".to_string()),
                     ("📊 TABLES INFO", "SELECT schemaname, tablename, tableowner FROM pg_tables;".to_string()),
                     ("🔧 LOCKS", "SELECT * FROM pg_locks;".to_string()),
                     ("📈 QUERY STATS", "SELECT query, calls, total_time FROM pg_stat_statements ORDER BY total_time DESC LIMIT 10;".to_string()),
                     ("🔍 USERS", "SELECT usename, usesuper FROM pg_user;".to_string()),
-                    ("📊 SIZE", "SELECT pg_size_pretty(pg_total_relation_size('table_name'));".to_string()),
-                    ("🔧 CREATE TABLE", "CREATE TABLE example_table (\n    id SERIAL PRIMARY KEY,\n    name VARCHAR(255) NOT NULL,\n    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP\n);".to_string()),
+                    ("📊 SIZE", "SELECT pg_size_pretty(pg_total_relation_size('${1:table_name}'));".to_string()),
+                    ("🔧 CREATE TABLE", "CREATE TABLE ${1:example_table} (\n    id SERIAL PRIMARY KEY,\n    ${2:name} VARCHAR(255) NOT NULL,\n    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP\n);".to_string()),
                 ]);
             },
             "sqlite" => {
                 templates.extend(vec![
                     ("📈 PRAGMA", "PRAGMA database_list;".to_string()),
-                    ("🔧 INFO", "PRAGMA table_info(table_name);".to_string()),
-                    ("🔍 INDEX", "PRAGMA index_list(table_name);".to_string()),
+                    ("🔧 INFO", "PRAGMA table_info(${1:table_name});".to_string()),
+                    ("🔍 INDEX", "PRAGMA index_list(${1:table_name});".to_string()),
                     ("📊 SCHEMA", "SELECT sql FROM sqlite_master WHERE type='table';".to_string()),
                     ("🔧 VERSION", "SELECT sqlite_version();".to_string()),
                     ("📈 STATS", "PRAGMA stats;".to_string()),
-                    ("🔍 FOREIGN KEYS", "PRAGMA foreign_key_list(table_name);".to_string()),
+                    ("🔍 FOREIGN KEYS", "PRAGMA foreign_key_list(${1:table_name});".to_string()),
                     ("📊 SIZE", "PRAGMA page_count; PRAGMA page_size;".to_string()),
-                    ("🔧 CREATE TABLE", "CREATE TABLE example_table (\n    id INTEGER PRIMARY KEY AUTOINCREMENT,\n    name TEXT NOT NULL,\n    created_at DATETIME DEFAULT CURRENT_TIMESTAMP\n);".to_string()),
+                    ("🔧 CREATE TABLE", "CREATE TABLE ${1:example_table} (\n    id INTEGER PRIMARY KEY AUTOINCREMENT,\n    ${2:name} TEXT NOT NULL,\n    created_at DATETIME DEFAULT CURRENT_TIMESTAMP\n);".to_string()),
                 ]);
             },
             _ => {
                 // Templates genéricos para otros tipos de BD
                 templates.extend(vec![
                     ("📊 INFO", "SELECT * FROM information_schema.tables;".to_string()),
-                    ("🔍 COLUMNS", "SELECT * FROM information_schema.columns WHERE table_name = 'table_name';".to_string()),
+                    ("🔍 COLUMNS", "SELECT * FROM information_schema.columns WHERE table_name = '${1:table_name}';".to_string()),
                     ("📈 STATS", "SELECT * FROM information_schema.table_statistics;".to_string()),
                 ]);
             }
@@ -146,6 +184,15 @@ impl DatabaseUI {
         sql_keywords.iter().any(|&keyword| sql.starts_with(keyword))
     }
 
+    // DML/DDL: sentencias que dejan obsoletos los resultados en caché del servicio
+    pub fn is_mutating_sql(&self, sql: &str) -> bool {
+        let sql = sql.trim().to_lowercase();
+        let mutating_keywords = ["insert", "update", "delete", "create", "drop", "alter", "truncate", "replace", "rename"];
+        sql.split(';')
+            .map(str::trim_start)
+            .any(|statement| mutating_keywords.iter().any(|&keyword| statement.starts_with(keyword)))
+    }
+
     pub fn explain_query(
         &mut self,
         service: &LandoService,
@@ -162,6 +209,41 @@ impl DatabaseUI {
         }
     }
 
+    // Prefijo del plan con tiempos reales según el motor; None si no lo admite
+    pub fn explain_analyze_prefix(&self, db_type: &str) -> Option<&'static str> {
+        match db_type.to_lowercase().as_str() {
+            "postgresql" | "postgres" if self.explain_json => Some("EXPLAIN (ANALYZE, FORMAT JSON)"),
+            "postgresql" | "postgres" | "mysql" => Some("EXPLAIN ANALYZE"),
+            "mariadb" => Some("ANALYZE"),
+            "sqlite" => Some("EXPLAIN QUERY PLAN"),
+            _ => None,
+        }
+    }
+
+    // EXPLAIN ANALYZE ejecuta la consulta de verdad: si modifica datos se
+    // invalida la caché igual que al ejecutarla
+    pub fn explain_analyze_query(
+        &mut self,
+        service: &LandoService,
+        project_path: &PathBuf,
+        sender: &Sender<LandoCommandOutcome>,
+        is_loading: &mut bool,
+    ) {
+        let Some(prefix) = self.explain_analyze_prefix(service.db_engine()) else {
+            return;
+        };
+        if !self.query_input.trim().is_empty() {
+            if self.is_mutating_sql(&self.query_input) {
+                self.query_cache.invalidate_service(&service.service);
+            }
+            let explain_query = format!("{} {}", prefix, self.query_input.trim());
+            let original_query = self.query_input.clone();
+            self.query_input = explain_query;
+            self.execute_query(service, project_path, sender, is_loading);
+            self.query_input = original_query; // Restaurar query original
+        }
+    }
+
     pub fn get_show_tables_query(&self, db_type: &str) -> String {
         match db_type.to_lowercase().as_str() {
             "mysql" | "mariadb" => "SHOW TABLES;".to_string(),
@@ -171,22 +253,16 @@ impl DatabaseUI {
         }
     }
 
-    pub fn format_query(&mut self) {
-        // Formateo básico de SQL
-        self.query_input = self.query_input
-            .replace(",", ",\n    ")
-            .replace(" FROM ", "\nFROM ")
-            .replace(" WHERE ", "\nWHERE ")
-            .replace(" ORDER BY ", "\nORDER BY ")
-            .replace(" GROUP BY ", "\nGROUP BY ");
+    pub fn format_query(&mut self, db_type: &str) {
+        self.query_input = format_sql(&self.query_input, SqlDialect::for_type(db_type));
     }
 
     pub fn get_describe_template(&self, db_type: &str) -> String {
         match db_type.to_lowercase().as_str() {
-            "mysql" | "mariadb" => "DESCRIBE table_name;".to_string(),
-            "postgresql" | "postgres" => "\\d table_name".to_string(),
-            "sqlite" => "PRAGMA table_info(table_name);".to_string(),
-            _ => "DESCRIBE table_name;".to_string(),
+            "mysql" | "mariadb" => "DESCRIBE ${1:table_name};".to_string(),
+            "postgresql" | "postgres" => "\\d ${1:table_name}".to_string(),
+            "sqlite" => "PRAGMA table_info(${1:table_name});".to_string(),
+            _ => "DESCRIBE ${1:table_name};".to_string(),
         }
     }
 
@@ -206,71 +282,333 @@ impl DatabaseUI {
         if !self.query_input.trim().is_empty() {
             *is_loading = true;
 
-            // Agregar al historial si no existe
-            if !self.query_history.contains(&self.query_input) {
+            // Agregar al historial si no existe; las que llevan contraseñas
+            // (`IDENTIFIED BY '...'`, `PASSWORD '...'`) no se guardan
+            let has_secrets = redact_secrets(&self.query_input) != self.query_input;
+            if !has_secrets && !self.query_history.contains(&self.query_input) {
                 self.query_history.push(self.query_input.clone());
                 // Mantener solo los últimos 50 queries
                 if self.query_history.len() > 50 {
-                    self.query_history.remove(0);
+                    let removed = self.query_history.remove(0);
+                    self.query_history_times.remove(&removed);
+                    self.history_selection.remove(&removed);
                 }
             }
+            if !has_secrets {
+                self.query_history_times.insert(self.query_input.clone(), now_secs());
+            }
 
             // Crear resultado placeholder
-            let start_time = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
-            let result = QueryResult {
-                query: self.query_input.clone(),
-                result: "Ejecutando consulta...".to_string(),
-                execution_time: 0.0,
-                timestamp: start_time,
-                rows_affected: None,
-                has_error: false,
-            };
+            let request_id = self.push_pending_result(self.query_input.clone(), "Ejecutando consulta...");
 
-            self.query_results.push(result);
-            self.current_result_index = self.query_results.len() - 1;
+            // En modo transacción la consulta va por la sesión abierta y no pasa por la caché
+            if self.db_session.is_some() {
+                if self.is_mutating_sql(&self.query_input) {
+                    self.query_cache.invalidate_service(&service.service);
+                }
+                if !self.send_session_statement(request_id, self.query_input.clone()) {
+                    self.process_query_result(request_id, "La sesión de base de datos no está lista todavía".to_string(), true);
+                    *is_loading = false;
+                }
+                return;
+            }
+
+            // La ejecución explícita nunca lee de la caché, pero sí la refresca
+            if self.is_mutating_sql(&self.query_input) {
+                self.query_cache.invalidate_service(&service.service);
+            } else if self.enable_query_cache {
+                self.pending_cache_keys.insert(request_id, (service.service.clone(), self.query_input.clone()));
+            }
 
             run_db_query(
                 sender.clone(),
                 project_path.clone(),
-                service.service.clone(),
+                self.db_connection(service),
                 self.query_input.clone(),
+                request_id,
+            );
+        }
+    }
+
+    // Ejecución desde el editor: primero el linter (los avisos que bloquean
+    // exigen "ejecutar de todos modos") y luego la guarda de LIMIT
+    pub fn run_editor_query(
+        &mut self,
+        service: &LandoService,
+        project_path: &PathBuf,
+        sender: &Sender<LandoCommandOutcome>,
+        is_loading: &mut bool,
+    ) {
+        if self.current_lints(SqlDialect::for_type(service.db_engine())).iter().any(|w| w.rule.is_blocking()) {
+            self.lint_blocked = true;
+            return;
+        }
+        self.run_editor_query_limited(service, project_path, sender, is_loading);
+    }
+
+    // Un SELECT sin LIMIT puede volcar millones de filas en la vista de texto,
+    // así que se limita o se pregunta antes
+    fn run_editor_query_limited(
+        &mut self,
+        service: &LandoService,
+        project_path: &PathBuf,
+        sender: &Sender<LandoCommandOutcome>,
+        is_loading: &mut bool,
+    ) {
+        // El aviso `sin-limit` del linter; con varias sentencias no se toca el texto
+        let dialect = SqlDialect::for_type(service.db_engine());
+        let missing_limit = self.current_lints(dialect).iter().any(|w| w.rule == LintRule::MissingLimit);
+        if missing_limit && statement_count(&self.query_input, dialect) == 1 {
+            if self.auto_limit_selects {
+                self.query_input = self.with_row_limit(&self.query_input);
+            } else {
+                self.pending_limit_query = Some(self.query_input.clone());
+                return;
+            }
+        }
+        self.execute_query(service, project_path, sender, is_loading);
+    }
+
+    // Avisos del texto del editor; se recalculan solo cuando cambia
+    pub fn current_lints(&mut self, dialect: SqlDialect) -> &[LintWarning] {
+        if self.lint_cache.as_ref().is_none_or(|(text, _)| text != &self.query_input) {
+            let row_counts: HashMap<String, i64> = self.tables.iter()
+                .filter_map(|t| t.row_count.map(|rows| (t.name.to_lowercase(), rows)))
+                .collect();
+            let warnings = lint_sql(
+                &self.query_input,
+                dialect,
+                &self.lint_settings,
+                &|table| row_counts.get(table).copied(),
+                self.max_rows,
             );
+            self.lint_cache = Some((self.query_input.clone(), warnings));
+            self.lint_blocked = false;
         }
+        self.lint_cache.as_ref().map(|(_, warnings)| warnings.as_slice()).unwrap_or_default()
     }
 
-    // Placeholder methods - implementar según necesidades
-    pub fn export_results_to_csv(&self) {
-        if let Some(result) = self.query_results.get(self.current_result_index) {
-            // En una implementación real, aquí se implementaría la exportación a CSV
-            // Por ahora, simplemente copiamos el resultado al portapapeles
-            println!("Exportando resultado a CSV: {}", result.result);
+    pub fn show_lint_warnings(&mut self, ui: &mut egui::Ui, service: &LandoService, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>, is_loading: &mut bool) {
+        let warnings = self.current_lints(SqlDialect::for_type(service.db_engine())).to_vec();
+        if warnings.is_empty() {
+            return;
+        }
+
+        for warning in &warnings {
+            let (icon, color) = if warning.rule.is_blocking() {
+                ("⛔", error_color(ui.visuals()))
+            } else {
+                ("⚠", warning_color(ui.visuals()))
+            };
+            ui.horizontal(|ui| {
+                ui.colored_label(color, format!("{} {}", icon, warning.message));
+                ui.small(format!("[{}]", warning.rule.name()))
+                    .on_hover_text("Se puede desactivar en Rendimiento › Configuración");
+            });
+        }
+
+        if self.lint_blocked {
+            let run = ui.add_enabled(!*is_loading, egui::Button::new("⚠️ Ejecutar de todos modos")
+                .fill(error_color(ui.visuals()).gamma_multiply(0.2)));
+            if run.clicked() {
+                self.lint_blocked = false;
+                self.run_editor_query_limited(service, project_path, sender, is_loading);
+            }
         }
     }
+
+    pub fn show_lint_settings(&mut self, ui: &mut egui::Ui) {
+        let mut changed = false;
+        ui.collapsing("🧹 Reglas del linter SQL", |ui| {
+            for rule in LintRule::ALL {
+                let mut enabled = !self.lint_settings.disabled.contains(&rule);
+                if ui.checkbox(&mut enabled, format!("{} — {}", rule.name(), rule.description())).changed() {
+                    if enabled {
+                        self.lint_settings.disabled.remove(&rule);
+                    } else {
+                        self.lint_settings.disabled.insert(rule);
+                    }
+                    changed = true;
+                }
+            }
+            ui.horizontal(|ui| {
+                ui.label("select-estrella a partir de:");
+                changed |= ui.add(egui::DragValue::new(&mut self.lint_settings.select_star_min_rows)
+                    .range(0..=100_000_000)
+                    .speed(1000)
+                    .suffix(" filas"))
+                    .changed();
+            });
+        });
+
+        if changed {
+            self.lint_cache = None;
+            let _ = save_app_file(SQL_LINT_FILE, &self.lint_settings);
+        }
+    }
+
+    pub fn with_row_limit(&self, sql: &str) -> String {
+        format!("{}\nLIMIT {};", sql.trim().trim_end_matches(';').trim_end(), self.max_rows)
+    }
+
+    // Visor de una celda JSON abierta desde la rejilla
+    pub fn show_json_viewer(&mut self, ctx: &egui::Context) {
+        let Some((column, value, show_raw)) = self.json_viewer.as_mut() else {
+            return;
+        };
+
+        let mut open = true;
+        egui::Window::new(format!("🧾 {}", column))
+            .id(egui::Id::new("json_cell_viewer"))
+            .open(&mut open)
+            .resizable(true)
+            .default_width(480.0)
+            .show(ctx, |ui| {
+                show_json(ui, "json_cell_viewer", value, show_raw);
+            });
+        if !open {
+            self.json_viewer = None;
+        }
+    }
+
+    pub fn show_limit_prompt(&mut self, ctx: &egui::Context, service: &LandoService, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>, is_loading: &mut bool) {
+        let Some(query) = self.pending_limit_query.clone() else {
+            return;
+        };
+
+        let mut choice = None;
+        egui::Window::new("⚠️ Consulta sin LIMIT")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.label("Esta consulta no tiene LIMIT y puede devolver muchísimas filas.");
+                ui.label(egui::RichText::new(query.trim()).monospace());
+                ui.checkbox(&mut self.auto_limit_selects, "Añadir siempre sin preguntar");
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui.button(format!("➕ Añadir LIMIT {}", self.max_rows)).clicked() {
+                        choice = Some(true);
+                    }
+                    if ui.button("▶️ Ejecutar igualmente").clicked() {
+                        choice = Some(false);
+                    }
+                    if ui.button("Cancelar").clicked() {
+                        self.pending_limit_query = None;
+                    }
+                });
+            });
+
+        if let Some(add_limit) = choice {
+            self.pending_limit_query = None;
+            if add_limit {
+                self.query_input = self.with_row_limit(&query);
+            }
+            self.execute_query(service, project_path, sender, is_loading);
+        }
+    }
+
+    pub fn export_results_to_csv(&mut self) {
+        let Some(set) = self.query_results.get(self.current_result_index)
+            .filter(|result| !result.has_error)
+            .and_then(|result| parse_result_set(&result.result))
+        else {
+            return;
+        };
+
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter("CSV", &["csv"])
+            .set_file_name("resultado.csv")
+            .save_file()
+        else {
+            return;
+        };
+
+        self.results_export_status = Some(match fs::write(&path, result_set_to_csv(&set)) {
+            Ok(()) => Ok(format!("Resultado exportado a {}", path.display())),
+            Err(e) => Err(format!("No se pudo escribir {}: {}", path.display(), e)),
+        });
+    }
+
+    // Texto para el portapapeles. `Text` copia la salida tal cual si se tiene
+    // (`raw`); si no, las filas separadas por tabuladores. Devuelve None si el
+    // formato necesita filas y no se pudieron leer.
+    pub fn format_copy(
+        &self,
+        db_type: &str,
+        format: CopyFormat,
+        raw: Option<&str>,
+        set: Option<&ParsedResultSet>,
+        insert_table: Option<&str>,
+    ) -> Option<String> {
+        if let (CopyFormat::Text, Some(raw)) = (format, raw) {
+            return Some(raw.to_string());
+        }
+        let set = set?;
+        Some(match format {
+            CopyFormat::Text => std::iter::once(&set.columns)
+                .chain(&set.rows)
+                .map(|cells| cells.join("\t") + "\n")
+                .collect(),
+            CopyFormat::Markdown => result_set_to_markdown(set),
+            CopyFormat::Json => result_set_to_json(set),
+            CopyFormat::Csv => result_set_to_csv(set),
+            CopyFormat::Insert => result_set_to_inserts(
+                set,
+                insert_table?,
+                |name| self.quote_identifier(db_type, name),
+                |value| self.sql_string_literal(db_type, value),
+            ),
+        })
+    }
+
+    // Se vuelve a parsear solo cuando cambia el texto del resultado visible
+    pub fn current_result_set(&mut self) -> Option<Arc<ParsedResultSet>> {
+        let result = self.query_results.get(self.current_result_index).filter(|r| !r.has_error)?;
+        let cached = self.parsed_result.as_ref()
+            .filter(|(raw, _)| Arc::ptr_eq(raw, &result.result))
+            .map(|(_, set)| set.clone());
+        if let Some(set) = cached {
+            return set;
+        }
+        let set = parse_result_set(&result.result).map(Arc::new);
+        self.parsed_result = Some((Arc::clone(&result.result), set.clone()));
+        set
+    }
+
+    // Copia el resultado visible al hueco de comparación; el historial de
+    // resultados sigue su curso sin tocarlo
+    pub fn pin_current_result(&mut self) {
+        let Some(result) = self.query_results.get(self.current_result_index).cloned() else {
+            return;
+        };
+        let set = if result.has_error { None } else { self.current_result_set() };
+        self.pinned_result = Some(PinnedResult { result, set, as_text: false });
+    }
+
+    pub fn set_copy_format(&mut self, format: CopyFormat) {
+        if self.copy_format != format {
+            self.copy_format = format;
+            let _ = save_app_file(COPY_FORMAT_FILE, &format);
+        }
+    }
+
     pub fn refresh_schema(&mut self, service: &LandoService, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>, is_loading: &mut bool) {
         if *is_loading { return; }
 
         *is_loading = true;
 
         // Crear placeholder para el resultado
-        let start_time = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
-        let result = QueryResult {
-            query: "Schema refresh".to_string(),
-            result: "Cargando schema...".to_string(),
-            execution_time: 0.0,
-            timestamp: start_time,
-            rows_affected: None,
-            has_error: false,
-        };
-        self.query_results.push(result);
-        self.current_result_index = self.query_results.len() - 1;
+        let request_id = self.push_pending_result("Schema refresh".to_string(), "Cargando schema...");
 
         // Ejecutar comando para obtener tablas
-        let tables_query = self.get_show_tables_query(&service.r#type);
+        let tables_query = self.get_show_tables_query(service.db_engine());
         run_db_query(
             sender.clone(),
             project_path.clone(),
-            service.service.clone(),
+            self.db_connection(service),
             tables_query,
+            request_id,
         );
     }
     pub fn load_table_data(&mut self, service: &LandoService, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>, is_loading: &mut bool) {
@@ -279,7 +617,7 @@ impl DatabaseUI {
         *is_loading = true;
 
         // Crear query con paginación y filtros
-        let mut query = format!("SELECT * FROM {}", self.current_table);
+        let mut query = format!("SELECT * FROM {}", self.quote_identifier(service.db_engine(), &self.current_table));
 
         if !self.table_filter.is_empty() {
             // Filtro básico - en una implementación real se haría más sofisticado
@@ -288,24 +626,15 @@ impl DatabaseUI {
 
         query.push_str(&format!(" LIMIT {} OFFSET {}", self.table_limit, self.table_page * self.table_limit));
 
-        // Crear placeholder para el resultado
-        let start_time = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
-        let result = QueryResult {
-            query: query.clone(),
-            result: "Cargando datos de la tabla...".to_string(),
-            execution_time: 0.0,
-            timestamp: start_time,
-            rows_affected: None,
-            has_error: false,
-        };
-        self.query_results.push(result);
-        self.current_result_index = self.query_results.len() - 1;
-
+        // El resultado vuelve etiquetado para que no se mezcle con el editor de consultas
+        let request_id = next_request_id();
+        self.pending_requests.insert(request_id, PendingRequest::TableData { table: self.current_table.clone() });
         run_db_query(
             sender.clone(),
             project_path.clone(),
-            service.service.clone(),
+            self.db_connection(service),
             query,
+            request_id,
         );
     }
 
@@ -315,149 +644,580 @@ impl DatabaseUI {
         *is_loading = true;
         self.connection_status = ConnectionStatus::Testing;
 
-        println!("🔍 Probando conexión a BD usando lando ssh...");
+        log::info!("Probando conexión a BD de {} con lando ssh", service.service);
 
         // Usar la nueva función de test de conexión que usa lando ssh
+        let request_id = self.push_pending_result("Test de conexión".to_string(), "Probando conexión...");
         test_db_connection(
             sender.clone(),
             project_path.clone(),
-            service.service.clone(),
+            self.db_connection(service),
+            request_id,
         );
     }
 
-    pub fn update_credentials(&mut self, service: &LandoService, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>, is_loading: &mut bool) {
-        if *is_loading { return; }
-
-        *is_loading = true;
-
-        // Comando para actualizar credenciales usando lando
-        let command = format!("config --set database.creds.user={} --set database.creds.password={} --set database.creds.database={}",
-                              self.new_user, self.new_password, self.new_database);
+    // Cada valor va en su propio argumento, sin shell de por medio: espacios y
+    // metacaracteres de la contraseña llegan tal cual y no pueden añadir opciones.
+    // Las credenciales solo se aplican al reconstruir, así que se encadena un rebuild.
+    pub fn update_credentials(&mut self, service: &LandoService) {
+        let user = self.new_user.trim();
+        let database = self.new_database.trim();
+        let missing: Vec<&str> = [("usuario", user), ("contraseña", self.new_password.expose()), ("base de datos", database)]
+            .into_iter()
+            .filter(|(_, value)| value.trim().is_empty())
+            .map(|(field, _)| field)
+            .collect();
+        if !missing.is_empty() {
+            self.credentials_error = Some(format!("Falta: {}", missing.join(", ")));
+            return;
+        }
+        self.credentials_error = None;
 
-        run_lando_command(
-            sender.clone(),
-            command,
-            project_path.clone(),
+        let config = vec![
+            "config".to_string(),
+            "--set".to_string(),
+            format!("database.creds.user={}", user),
+            "--set".to_string(),
+            format!("database.creds.password={}", self.new_password.expose()),
+            "--set".to_string(),
+            format!("database.creds.database={}", database),
+        ];
+        let rebuild = vec!["rebuild".to_string(), "-s".to_string(), service.service.clone()];
+        let preview = [&config, &rebuild].iter()
+            .map(|args| redact_secrets(&format!("lando {}", args.join(" "))))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let dialog = ConfirmDialog::new(
+            "Actualizar credenciales",
+            "Se cambiarán las credenciales del servicio y se reconstruirá para aplicarlas. El servicio estará parado mientras dura el rebuild.",
+            &preview,
+            &service.service,
         );
+        self.pending_tool = Some((ToolCommand::LandoChain { steps: vec![config, rebuild], secret: true }, dialog));
+    }
+    pub fn quote_identifier(&self, db_type: &str, name: &str) -> String {
+        match db_type.to_lowercase().as_str() {
+            "mysql" | "mariadb" => format!("`{}`", name.replace('`', "``")),
+            _ => format!("\"{}\"", name.replace('"', "\"\"")),
+        }
     }
-    pub fn optimize_database(&mut self, service: &LandoService, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>, is_loading: &mut bool) {
-        if *is_loading { return; }
 
-        *is_loading = true;
+    // Genera la sentencia de mantenimiento para las tablas seleccionadas.
+    // MySQL acepta varias tablas en una sola sentencia; el resto necesita una por tabla.
+    pub fn build_maintenance_query(&self, db_type: &str, op: MaintenanceOp, tables: &[String]) -> Option<String> {
+        if tables.is_empty() {
+            return None;
+        }
 
-        let optimize_query = match service.r#type.to_lowercase().as_str() {
-            "mysql" | "mariadb" => "OPTIMIZE TABLE;",
-            "postgresql" | "postgres" => "VACUUM ANALYZE;",
-            "sqlite" => "VACUUM;",
-            _ => "OPTIMIZE TABLE;",
+        let db_type = db_type.to_lowercase();
+        let quoted: Vec<String> = tables.iter().map(|t| self.quote_identifier(&db_type, t)).collect();
+
+        let query = match db_type.as_str() {
+            "postgresql" | "postgres" => {
+                let keyword = match op {
+                    MaintenanceOp::Optimize => "VACUUM ANALYZE",
+                    MaintenanceOp::Repair => "REINDEX TABLE",
+                    MaintenanceOp::Analyze => "ANALYZE",
+                };
+                quoted.iter().map(|t| format!("{} {};", keyword, t)).collect::<Vec<_>>().join("\n")
+            }
+            "sqlite" => match op {
+                // VACUUM no admite tablas concretas en SQLite: se aplica a toda la base
+                MaintenanceOp::Optimize => "VACUUM;".to_string(),
+                MaintenanceOp::Repair => quoted.iter().map(|t| format!("REINDEX {};", t)).collect::<Vec<_>>().join("\n"),
+                MaintenanceOp::Analyze => quoted.iter().map(|t| format!("ANALYZE {};", t)).collect::<Vec<_>>().join("\n"),
+            },
+            _ => {
+                let keyword = match op {
+                    MaintenanceOp::Optimize => "OPTIMIZE TABLE",
+                    MaintenanceOp::Repair => "REPAIR TABLE",
+                    MaintenanceOp::Analyze => "ANALYZE TABLE",
+                };
+                format!("{} {};", keyword, quoted.join(", "))
+            }
         };
 
-        run_db_query(
-            sender.clone(),
-            project_path.clone(),
-            service.service.clone(),
-            optimize_query.to_string(),
-        );
+        Some(query)
     }
 
-    pub fn backup_database(&mut self, service: &LandoService, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>, is_loading: &mut bool) {
-        if *is_loading { return; }
+    pub fn toggle_maintenance_table(&mut self, table: &str) {
+        if let Some(pos) = self.maintenance_tables.iter().position(|t| t == table) {
+            self.maintenance_tables.remove(pos);
+        } else {
+            self.maintenance_tables.push(table.to_string());
+        }
+    }
 
-        *is_loading = true;
+    pub fn request_maintenance(&mut self, service: &LandoService, op: MaintenanceOp) {
+        let Some(query) = self.build_maintenance_query(service.db_engine(), op, &self.maintenance_tables) else {
+            return;
+        };
 
-        // Comando de backup usando lando
-        let backup_command = match service.r#type.to_lowercase().as_str() {
-            "mysql" | "mariadb" => format!("db-export -s {}", service.service),
-            "postgresql" | "postgres" => format!("db-export -s {}", service.service),
-            "sqlite" => format!("db-export -s {}", service.service),
-            _ => format!("db-export -s {}", service.service),
+        let (title, description) = match op {
+            MaintenanceOp::Optimize => ("Optimizar tablas", "Se reorganizarán las tablas seleccionadas. Puede bloquearlas mientras dura la operación."),
+            MaintenanceOp::Repair => ("Reparar tablas", "Se repararán/reindexarán las tablas seleccionadas."),
+            MaintenanceOp::Analyze => ("Analizar tablas", "Se actualizarán las estadísticas de las tablas seleccionadas."),
         };
 
-        run_lando_command(
-            sender.clone(),
-            backup_command,
-            project_path.clone(),
+        let dialog = ConfirmDialog::new(title, description, &query, &service.service);
+        self.pending_tool = Some((ToolCommand::Sql(query), dialog));
+    }
+
+    pub fn request_flush(&mut self, service: &LandoService) {
+        let query = "FLUSH TABLES;".to_string();
+        let dialog = ConfirmDialog::new(
+            "Flush de tablas",
+            "Se cerrarán todas las tablas abiertas y se vaciará la caché de consultas del servidor.",
+            &query,
+            &service.service,
+        ).destructive();
+        self.pending_tool = Some((ToolCommand::Sql(query), dialog));
+    }
+
+    pub fn backup_database(&mut self, service: &LandoService) {
+        let args = vec!["db-export".to_string(), "-s".to_string(), service.service.clone()];
+        let dialog = ConfirmDialog::new(
+            "Backup de base de datos",
+            "Se exportará la base de datos a un archivo en el directorio del proyecto.",
+            &format!("lando {}", args.join(" ")),
+            &service.service,
         );
+        self.pending_tool = Some((ToolCommand::Lando(args), dialog));
     }
 
-    pub fn repair_database(&mut self, service: &LandoService, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>, is_loading: &mut bool) {
-        if *is_loading { return; }
+    pub fn import_data(&mut self, service: &LandoService) {
+        if let Some(file) = rfd::FileDialog::new()
+            .add_filter("SQL", &["sql", "gz"])
+            .pick_file()
+        {
+            self.request_import(service, &file);
+        }
+    }
 
-        *is_loading = true;
+    pub fn request_import(&mut self, service: &LandoService, file: &Path) {
+        let args = vec![
+            "db-import".to_string(),
+            file.to_string_lossy().to_string(),
+            "--host".to_string(),
+            service.service.clone(),
+        ];
+        let dialog = ConfirmDialog::new(
+            "Importar base de datos",
+            "El contenido actual de la base de datos será SOBRESCRITO con el archivo seleccionado.",
+            &format!("lando db-import \"{}\" --host {}", file.display(), service.service),
+            &service.service,
+        ).destructive();
+        self.pending_tool = Some((ToolCommand::Lando(args), dialog));
+    }
 
-        let repair_query = match service.r#type.to_lowercase().as_str() {
-            "mysql" | "mariadb" => "REPAIR TABLE;",
-            "postgresql" | "postgres" => "REINDEX DATABASE;",
-            "sqlite" => "REINDEX;",
-            _ => "REPAIR TABLE;",
+    // Muestra el diálogo pendiente y ejecuta el comando solo si se confirma.
+    // Al cancelar no se toca `is_loading`. Mientras otro comando está en marcha
+    // no se puede confirmar: el diálogo sigue abierto hasta que termine.
+    pub fn show_pending_tool_dialog(&mut self, ctx: &egui::Context, service: &LandoService, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>, is_loading: &mut bool) {
+        let Some((_, dialog)) = self.pending_tool.as_mut() else {
+            return;
         };
+        dialog.busy = *is_loading;
 
-        run_db_query(
-            sender.clone(),
-            project_path.clone(),
-            service.service.clone(),
-            repair_query.to_string(),
-        );
+        match dialog.show(ctx) {
+            ConfirmResponse::Pending => {}
+            ConfirmResponse::Cancelled => {
+                self.pending_tool = None;
+            }
+            ConfirmResponse::Confirmed if *is_loading => {}
+            ConfirmResponse::Confirmed => {
+                if let Some((command, _)) = self.pending_tool.take() {
+                    self.run_tool_command(command, service, project_path, sender, is_loading);
+                }
+            }
+        }
     }
 
-    pub fn analyze_database(&mut self, service: &LandoService, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>, is_loading: &mut bool) {
+    fn run_tool_command(&mut self, command: ToolCommand, service: &LandoService, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>, is_loading: &mut bool) {
         if *is_loading { return; }
 
         *is_loading = true;
 
-        let analyze_query = match service.r#type.to_lowercase().as_str() {
-            "mysql" | "mariadb" => "ANALYZE TABLE;",
-            "postgresql" | "postgres" => "ANALYZE;",
-            "sqlite" => "ANALYZE;",
-            _ => "ANALYZE TABLE;",
+        // Mantenimiento e importaciones pueden cambiar los datos
+        self.query_cache.invalidate_service(&service.service);
+
+        match command {
+            ToolCommand::Sql(query) => {
+                let request_id = self.push_pending_result(query.clone(), "Ejecutando...");
+                run_db_query(
+                    sender.clone(),
+                    project_path.clone(),
+                    self.db_connection(service),
+                    query,
+                    request_id,
+                );
+            }
+            ToolCommand::Editor(query) => {
+                // Como si se lanzara desde el editor: queda en historial y resultados
+                let original_query = std::mem::replace(&mut self.query_input, query);
+                self.execute_query(service, project_path, sender, is_loading);
+                self.query_input = original_query;
+            }
+            ToolCommand::DeleteBackups(files) => {
+                delete_backups(sender.clone(), service.service.clone(), self.backup_dir(project_path), files);
+            }
+            ToolCommand::TableEdits => {
+                // Las sentencias se regeneran por si la página cambió tras confirmar
+                *is_loading = false;
+                self.apply_table_edits(service, project_path, sender, is_loading);
+            }
+            ToolCommand::Lando(args) => {
+                run_lando_args(
+                    sender.clone(),
+                    args,
+                    project_path.clone(),
+                );
+            }
+            ToolCommand::LandoChain { steps, secret } => {
+                run_lando_chain(sender.clone(), steps, project_path.clone(), secret);
+            }
+        }
+    }
+
+    pub fn generate_schema_documentation(&self) {
+        // Generar documentación del schema
+        log::debug!("Generación de documentación del schema aún no implementada");
+    }
+
+    // Coloca el contenido de un archivo .sql en el editor, pidiendo confirmación
+    // si ya hay una consulta escrita.
+    pub fn receive_sql_file(&mut self, path: PathBuf, content: String) {
+        self.current_tab = DatabaseTab::QueryEditor;
+        if self.query_input.trim().is_empty() {
+            self.query_input = content;
+        } else {
+            self.pending_sql_file = Some((path, content));
+        }
+    }
+
+    pub fn show_pending_sql_file_dialog(&mut self, ctx: &egui::Context) {
+        let Some((path, content)) = &self.pending_sql_file else {
+            return;
         };
+        let file_name = path.display().to_string();
+        let line_count = content.lines().count();
 
+        let mut action = None;
+        egui::Window::new("📄 Cargar archivo SQL")
+            .collapsible(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.label(format!("El editor ya contiene una consulta. ¿Qué hacer con '{}'?", file_name));
+                ui.label(format!("{} líneas", line_count));
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui.button("♻️ Reemplazar").clicked() {
+                        action = Some(true);
+                    }
+                    if ui.button("➕ Añadir al final").clicked() {
+                        action = Some(false);
+                    }
+                    if ui.button("❌ Cancelar").clicked() {
+                        self.pending_sql_file = None;
+                    }
+                });
+            });
+
+        if let Some(replace) = action {
+            if let Some((_, content)) = self.pending_sql_file.take() {
+                if replace {
+                    self.query_input = content;
+                } else {
+                    self.append_to_editor(&content);
+                }
+            }
+        }
+    }
+
+    pub fn export_data(&self) {
+        // Exportar datos de la base de datos
+        log::debug!("Exportación de datos aún no implementada");
+    }
+
+    pub fn quote_literal(&self, value: &str) -> String {
+        format!("'{}'", value.replace('\'', "''"))
+    }
+
+    pub fn get_columns_query(&self, db_type: &str, table: &str) -> String {
+        match db_type.to_lowercase().as_str() {
+            "postgresql" | "postgres" => format!(
+                "SELECT c.column_name, c.data_type, c.is_nullable, c.column_default, \
+                 CASE WHEN k.column_name IS NOT NULL THEN 'PRI' ELSE '' END AS key \
+                 FROM information_schema.columns c \
+                 LEFT JOIN (SELECT kcu.column_name FROM information_schema.table_constraints tc \
+                 JOIN information_schema.key_column_usage kcu ON tc.constraint_name = kcu.constraint_name \
+                 WHERE tc.table_name = {0} AND tc.constraint_type = 'PRIMARY KEY') k ON k.column_name = c.column_name \
+                 WHERE c.table_name = {0} ORDER BY c.ordinal_position;",
+                self.quote_literal(table)
+            ),
+            "sqlite" => format!("PRAGMA table_info({});", self.quote_identifier("sqlite", table)),
+            _ => format!("SHOW COLUMNS FROM {};", self.quote_identifier(db_type, table)),
+        }
+    }
+
+    pub fn load_table_columns(&mut self, service: &LandoService, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>, table: &str) {
+        let request_id = next_request_id();
+        self.pending_requests.insert(request_id, PendingRequest::DescribeTable { table: table.to_string() });
         run_db_query(
             sender.clone(),
             project_path.clone(),
-            service.service.clone(),
-            analyze_query.to_string(),
+            self.db_connection(service),
+            self.get_columns_query(service.db_engine(), table),
+            request_id,
         );
     }
-    pub fn generate_schema_documentation(&self) {
-        // Generar documentación del schema
-        println!("Generando documentación del schema...");
+
+    // Interpreta la salida de SHOW COLUMNS, information_schema.columns o PRAGMA table_info
+    pub fn parse_columns(&self, set: &ParsedResultSet) -> Vec<ColumnInfo> {
+        let pick = |row: usize, names: &[&str]| {
+            names.iter().find_map(|name| set.value(row, name)).unwrap_or("").to_string()
+        };
+
+        (0..set.rows.len())
+            .map(|row| {
+                let nullable = pick(row, &["Null", "is_nullable"]);
+                let not_null = pick(row, &["notnull"]);
+                let default = pick(row, &["Default", "column_default", "dflt_value"]);
+                let key = pick(row, &["Key", "key"]);
+                let pk = pick(row, &["pk"]);
+
+                ColumnInfo {
+                    name: pick(row, &["Field", "column_name", "name"]),
+                    data_type: pick(row, &["Type", "data_type", "type"]),
+                    nullable: nullable.eq_ignore_ascii_case("yes") || not_null == "0",
+                    default_value: if default.is_empty() || default == "NULL" { None } else { Some(default) },
+                    is_primary_key: key == "PRI" || (!pk.is_empty() && pk != "0"),
+                    stats: None,
+                }
+            })
+            .filter(|c| !c.name.is_empty())
+            .collect()
     }
 
-    pub fn export_data(&self) {
-        // Exportar datos de la base de datos
-        println!("Exportando datos...");
+    // Tipos para los que MIN/MAX/DISTINCT no tienen sentido o fallan
+    pub fn is_stats_unsupported_type(&self, data_type: &str) -> bool {
+        let data_type = data_type.to_lowercase();
+        ["blob", "binary", "bytea", "json", "geometry", "bit"].iter().any(|t| data_type.contains(t))
+    }
+
+    pub fn is_text_type(&self, data_type: &str) -> bool {
+        let data_type = data_type.to_lowercase();
+        ["char", "text", "enum", "set"].iter().any(|t| data_type.contains(t))
+    }
+
+    pub fn build_column_stats_query(&self, db_type: &str, table: &str, column: &ColumnInfo) -> String {
+        let table = self.quote_identifier(db_type, table);
+        let col = self.quote_identifier(db_type, &column.name);
+
+        if self.is_stats_unsupported_type(&column.data_type) {
+            format!(
+                "SELECT COUNT(*) AS total, SUM(CASE WHEN {0} IS NULL THEN 1 ELSE 0 END) AS nulls FROM {1};",
+                col, table
+            )
+        } else {
+            format!(
+                "SELECT COUNT(*) AS total, COUNT(DISTINCT {0}) AS distinct_count, \
+                 SUM(CASE WHEN {0} IS NULL THEN 1 ELSE 0 END) AS nulls, \
+                 MIN({0}) AS min_value, MAX({0}) AS max_value FROM {1};",
+                col, table
+            )
+        }
+    }
+
+    pub fn build_top_values_query(&self, db_type: &str, table: &str, column: &str) -> String {
+        let table = self.quote_identifier(db_type, table);
+        let col = self.quote_identifier(db_type, column);
+        format!(
+            "SELECT {0} AS value, COUNT(*) AS frequency FROM {1} GROUP BY {0} ORDER BY frequency DESC LIMIT 5;",
+            col, table
+        )
+    }
+
+    pub fn request_column_stats(&mut self, service: &LandoService, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>, table: &str, column_name: &str) {
+        let Some(column) = self.find_column_mut(table, column_name).map(|c| c.clone()) else {
+            return;
+        };
+
+        let stats_query = self.build_column_stats_query(service.db_engine(), table, &column);
+        let wants_top = self.is_text_type(&column.data_type) && !self.is_stats_unsupported_type(&column.data_type);
+        let top_query = wants_top.then(|| self.build_top_values_query(service.db_engine(), table, column_name));
+
+        let mut shown_query = stats_query.clone();
+        if let Some(top_query) = &top_query {
+            shown_query.push('\n');
+            shown_query.push_str(top_query);
+        }
+
+        if let Some(column) = self.find_column_mut(table, column_name) {
+            column.stats = Some(ColumnStats { query: shown_query, loading: true, ..Default::default() });
+        }
+        self.stats_popup = Some((table.to_string(), column_name.to_string()));
+
+        let request_id = next_request_id();
+        self.pending_requests.insert(request_id, PendingRequest::ColumnStats { table: table.to_string(), column: column_name.to_string() });
+        run_db_query(sender.clone(), project_path.clone(), self.db_connection(service), stats_query, request_id);
+
+        if let Some(top_query) = top_query {
+            let request_id = next_request_id();
+            self.pending_requests.insert(request_id, PendingRequest::ColumnTopValues { table: table.to_string(), column: column_name.to_string() });
+            run_db_query(sender.clone(), project_path.clone(), self.db_connection(service), top_query, request_id);
+        }
+    }
+
+    pub fn find_column_mut(&mut self, table: &str, column: &str) -> Option<&mut ColumnInfo> {
+        self.tables.iter_mut()
+            .find(|t| t.name == table)?
+            .columns.iter_mut()
+            .find(|c| c.name == column)
     }
 
-    pub fn import_data(&self) {
-        // Importar datos a la base de datos
-        println!("Importando datos...");
+    // Procesa un resultado etiquetado. Devuelve false si el id no pertenece a esta UI.
+    pub fn handle_tagged_result(&mut self, request_id: u64, output: &Result<String, String>) -> bool {
+        let Some(request) = self.pending_requests.remove(&request_id) else {
+            return false;
+        };
+
+        match request {
+            PendingRequest::WatchQuery => self.handle_watch_result(output),
+            PendingRequest::CsvImportBatch => self.handle_csv_batch_result(output),
+            PendingRequest::DescribeTable { table } => {
+                let columns = match output {
+                    Ok(text) => parse_result_set(text).map(|set| self.parse_columns(&set)).unwrap_or_default(),
+                    Err(_) => Vec::new(),
+                };
+                if let Some(table_info) = self.tables.iter_mut().find(|t| t.name == table) {
+                    table_info.columns = columns;
+                }
+            }
+            PendingRequest::ColumnStats { table, column } => {
+                if let Some(stats) = self.find_column_mut(&table, &column).and_then(|c| c.stats.as_mut()) {
+                    stats.loading = false;
+                    match output {
+                        Ok(text) => {
+                            if let Some(set) = parse_result_set(text) {
+                                if let Some(row) = set.rows.first() {
+                                    stats.values = set.columns.iter().cloned().zip(row.iter().cloned()).collect();
+                                }
+                                let total = set.value(0, "total").and_then(|v| v.trim().parse::<f64>().ok());
+                                let nulls = set.value(0, "nulls").and_then(|v| v.trim().parse::<f64>().ok());
+                                if let (Some(total), Some(nulls)) = (total, nulls) {
+                                    if total > 0.0 {
+                                        stats.values.push(("null_ratio".to_string(), format!("{:.1}%", nulls / total * 100.0)));
+                                    }
+                                }
+                            }
+                        }
+                        Err(msg) => stats.error = Some(msg.clone()),
+                    }
+                }
+            }
+            PendingRequest::TableData { table } => {
+                // Ignorar páginas de una tabla que ya no está seleccionada
+                if table == self.current_table {
+                    match output {
+                        Ok(text) => {
+                            self.table_data = text.clone();
+                            self.table_rows = parse_result_set(text);
+                        }
+                        Err(msg) => {
+                            self.table_data = format!("❌ {}", msg);
+                            self.table_rows = None;
+                        }
+                    }
+                    self.reset_table_edits();
+                    self.table_selection.clear();
+                }
+            }
+            PendingRequest::ApplyTableEdits { table } => {
+                match output {
+                    Ok(_) => {
+                        self.reset_table_edits();
+                        self.table_edit.show_review = false;
+                        self.needs_table_reload = table == self.current_table;
+                    }
+                    Err(msg) => self.table_edit.error = Some(msg.clone()),
+                }
+            }
+            PendingRequest::ForeignKeys { db_type } => self.handle_foreign_keys_result(output, &db_type),
+            PendingRequest::ScheduledQuery { schedule } => self.handle_schedule_result(&schedule, output),
+            PendingRequest::Users { db_type } => self.handle_users_result(&db_type, output),
+            PendingRequest::ProcessList { db_type } => self.handle_process_list_result(&db_type, output),
+            PendingRequest::UserGrants { db_type, user, host } => {
+                self.handle_user_grants_result(&db_type, &user, &host, output)
+            }
+            PendingRequest::ColumnTopValues { table, column } => {
+                if let Some(stats) = self.find_column_mut(&table, &column).and_then(|c| c.stats.as_mut()) {
+                    match output {
+                        Ok(text) => {
+                            if let Some(set) = parse_result_set(text) {
+                                stats.top_values = set.rows.iter()
+                                    .filter(|r| r.len() >= 2)
+                                    .map(|r| (r[0].clone(), r[1].clone()))
+                                    .collect();
+                            }
+                        }
+                        Err(msg) => stats.error = Some(msg.clone()),
+                    }
+                }
+            }
+        }
+
+        true
     }
 
     // Método para procesar resultados de queries y actualizar el estado
-    pub fn process_query_result(&mut self, result_text: String, has_error: bool) {
-        // Actualizar el último resultado
-        self.update_query_result(result_text.clone(), has_error);
+    // Rellena el resultado provisional de `request_id`. Devuelve false si la
+    // consulta no salió de esta interfaz (o su resultado ya se descartó).
+    pub fn process_query_result(&mut self, request_id: u64, result_text: String, has_error: bool) -> bool {
+        let cache_key = self.pending_cache_keys.remove(&request_id);
+        let Some(index) = self.query_results.iter().position(|r| r.request_id == Some(request_id)) else {
+            return false;
+        };
+        self.update_query_result(index, result_text.clone(), has_error);
+        let query = self.query_results[index].query.clone();
+        self.track_session_result(request_id, &query, has_error);
 
-        // Si es un resultado de schema refresh, procesar las tablas
-        if let Some(result) = self.query_results.get(self.current_result_index) {
-            if result.query.contains("SHOW TABLES") || result.query.contains("SELECT tablename") || result.query.contains("SELECT name") {
-                self.parse_tables_from_result(&result_text);
+        if let Some((service, query)) = cache_key {
+            if !has_error {
+                let result = self.query_results[index].clone();
+                self.query_cache.insert(&service, &query, result);
             }
         }
 
+        // Tras crear/borrar usuarios o cambiar permisos se recarga la lista
+        if self.user_manager.reload_after_query {
+            self.user_manager.reload_after_query = false;
+            self.user_manager.loaded = false;
+        }
+        if self.process_list.reload_after_query {
+            self.process_list.reload_after_query = false;
+            self.process_list.stale = true;
+        }
+
+        // Si es un resultado de schema refresh, procesar las tablas
+        if query.contains("SHOW TABLES") || query.contains("SELECT tablename") || query.contains("SELECT name") {
+            self.parse_tables_from_result(&result_text);
+        }
+
         // Actualizar estado de conexión basado en el resultado
         if has_error {
-            println!("❌ Error en consulta: {}", result_text);
+            log::warn!("Error en consulta: {}", redact_secrets(result_text.trim()));
             self.connection_status = ConnectionStatus::Error(format!("Error en la consulta: {}", result_text));
         } else {
-            println!("✅ Consulta exitosa: {}", result_text);
+            log::debug!("Consulta exitosa ({} bytes)", result_text.len());
             self.connection_status = ConnectionStatus::Connected;
         }
+        true
     }
 
     pub fn parse_tables_from_result(&mut self, result: &str) {
         self.tables.clear();
+        self.needs_foreign_keys = true;
 
         // Parsear resultado de SHOW TABLES o similar
         for line in result.lines() {
@@ -471,6 +1231,7 @@ impl DatabaseUI {
                         columns: Vec::new(), // Se cargarían con DESCRIBE
                         row_count: None,
                         table_type: "table".to_string(),
+                        foreign_keys: Vec::new(),
                     };
                     self.tables.push(table_info);
                 }
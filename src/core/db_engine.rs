@@ -0,0 +1,229 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::sync::mpsc::Sender;
+use std::thread;
+
+use serde_yaml::Value;
+
+use crate::core::commands::to_args;
+use crate::core::runner::system_runner;
+use crate::core::storage::{load_app_file, save_app_file};
+use crate::models::app::LandoGui;
+use crate::models::commands::LandoCommandOutcome;
+use crate::models::lando::LandoService;
+
+// Motores que entiende la app, con el nombre que esperan los `for_type`
+pub const DB_ENGINES: &[&str] = &["mysql", "mariadb", "postgres", "mongo", "redis"];
+
+// Motor elegido a mano por servicio (`proyecto#servicio`), para cuando la detección falla
+const DB_ENGINE_OVERRIDES_FILE: &str = "db_engine_overrides.json";
+
+// Se pregunta al contenedor qué cliente tiene instalado; mariadb responde a
+// `mysql --version` con `Distrib 10.6.12-MariaDB`
+const ENGINE_PROBE_COMMAND: &str =
+    "mysql --version 2>/dev/null || psql --version 2>/dev/null || mongod --version 2>/dev/null || redis-server --version 2>/dev/null";
+
+// Nombre normalizado a partir de un tipo, una imagen o la salida de `--version`
+pub fn normalize_engine(text: &str) -> Option<&'static str> {
+    let text = text.to_lowercase();
+    if text.contains("mariadb") {
+        Some("mariadb")
+    } else if text.contains("mysql") {
+        Some("mysql")
+    } else if text.contains("postgres") || text.contains("psql") {
+        Some("postgres")
+    } else if text.contains("mongo") || text.starts_with("db version") {
+        Some("mongo")
+    } else if text.contains("redis") {
+        Some("redis")
+    } else {
+        None
+    }
+}
+
+fn engine_from_port(port: &str) -> Option<&'static str> {
+    match port.trim() {
+        "3306" => Some("mysql"),
+        "5432" => Some("postgres"),
+        "27017" => Some("mongo"),
+        "6379" => Some("redis"),
+        _ => None,
+    }
+}
+
+pub fn read_landofile(project_path: &Path) -> Value {
+    [".lando.yml", ".lando.yaml"].iter()
+        .find_map(|name| fs::read_to_string(project_path.join(name)).ok())
+        .and_then(|content| serde_yaml::from_str(&content).ok())
+        .unwrap_or(Value::Null)
+}
+
+// Imagen de un servicio `compose`/`lando` del `.lando.yml`: va en `services.image`
+// o en `overrides.image`
+fn landofile_image(landofile: &Value, service: &str) -> Option<String> {
+    let config = landofile.get("services")?.get(service)?;
+    [config.get("services"), config.get("overrides"), Some(config)]
+        .into_iter()
+        .flatten()
+        .find_map(|section| section.get("image").and_then(Value::as_str))
+        .map(String::from)
+}
+
+// Reglas en orden de fiabilidad: tipo de lando, imagen (en `lando info` o en el
+// `.lando.yml`) y puerto interno
+pub fn infer_db_engine(service: &LandoService, landofile: &Value) -> Option<String> {
+    let base_type = service.r#type.split(':').next().unwrap_or_default();
+    let raw_image = service.raw.get("image").and_then(serde_json::Value::as_str).map(String::from);
+
+    normalize_engine(base_type)
+        .or_else(|| raw_image.as_deref().and_then(normalize_engine))
+        .or_else(|| landofile_image(landofile, &service.service).as_deref().and_then(normalize_engine))
+        .or_else(|| service.internal_connection.as_ref().and_then(|c| engine_from_port(&c.port)))
+        .map(String::from)
+}
+
+// Sin motor deducido pero con pinta de base de datos: merece preguntar al contenedor
+pub fn is_db_candidate(service: &LandoService) -> bool {
+    service.resolved_db_engine.is_none() && (service.creds.is_some() || service.internal_connection.is_some())
+}
+
+pub fn probe_db_engine(sender: Sender<LandoCommandOutcome>, project_path: &Path, service: &str) {
+    let project_path = project_path.to_path_buf();
+    let service = service.to_string();
+    thread::spawn(move || {
+        let runner = system_runner();
+        let engine = runner
+            .run("lando", &to_args(&["ssh", "-s", &service, "-c", ENGINE_PROBE_COMMAND]), Some(&project_path))
+            .ok()
+            .filter(|output| output.success)
+            .and_then(|output| normalize_engine(&output.stdout_lossy()).map(String::from));
+        let _ = sender.send(LandoCommandOutcome::DbEngineProbed { service, engine });
+    });
+}
+
+impl LandoGui {
+    fn db_engine_key(&self, service: &str) -> Option<String> {
+        self.selected_project_path.as_ref().map(|path| Self::database_window_key(path, service))
+    }
+
+    pub fn load_db_engine_overrides() -> HashMap<String, String> {
+        load_app_file(DB_ENGINE_OVERRIDES_FILE)
+    }
+
+    // Manual > preguntado al contenedor > deducido de `lando info` y el `.lando.yml`
+    pub fn apply_db_engines(&mut self) {
+        let landofile = self.selected_project_path.as_deref().map(read_landofile).unwrap_or(Value::Null);
+        let keys: Vec<Option<String>> = self.services.iter().map(|s| self.db_engine_key(&s.service)).collect();
+        for (service, key) in self.services.iter_mut().zip(keys) {
+            let manual = key.as_ref().and_then(|k| self.db_engine_overrides.get(k)).cloned();
+            let probed = key.as_ref().and_then(|k| self.probed_db_engines.get(k)).cloned().flatten();
+            service.db_engine_override = manual.clone();
+            service.resolved_db_engine = manual.or(probed).or_else(|| infer_db_engine(service, &landofile));
+        }
+    }
+
+    pub fn handle_db_engine_probed(&mut self, service: &str, engine: Option<String>) {
+        let Some(key) = self.db_engine_key(service) else {
+            return;
+        };
+        if let Some(engine) = &engine {
+            self.toasts.info("Motor de BD detectado", &format!("{}: {}", service, engine));
+        }
+        self.probed_db_engines.insert(key, engine);
+        self.apply_db_engines();
+    }
+
+    pub fn set_db_engine_override(&mut self, service: &str, engine: Option<String>) {
+        let Some(key) = self.db_engine_key(service) else {
+            return;
+        };
+        match engine {
+            Some(engine) => self.db_engine_overrides.insert(key, engine),
+            None => self.db_engine_overrides.remove(&key),
+        };
+        if let Err(e) = save_app_file(DB_ENGINE_OVERRIDES_FILE, &self.db_engine_overrides) {
+            self.toasts.warning("No se pudo guardar el motor elegido", &e.to_string());
+        }
+        self.apply_db_engines();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::lando_json::parse_services;
+
+    // `lando info` de una app con servicios de lando, compose y personalizados
+    const LANDO_INFO: &str = r#"[
+        {"service": "database", "type": "mariadb:10.6", "internal_connection": {"host": "database", "port": "3306"}},
+        {"service": "pg", "type": "compose", "image": "postgres:16-alpine"},
+        {"service": "docs", "type": "lando"},
+        {"service": "legacy", "type": "compose", "internal_connection": {"host": "legacy", "port": "27017"}},
+        {"service": "cache", "type": "redis:7"},
+        {"service": "appserver", "type": "php:8.2", "internal_connection": {"host": "appserver", "port": "80"}}
+    ]"#;
+
+    const LANDOFILE: &str = "
+services:
+  docs:
+    type: lando
+    services:
+      image: bitnami/mysql:8.0
+      command: /opt/bitnami/scripts/mysql/run.sh
+  pg:
+    type: compose
+    overrides:
+      image: mariadb:11
+";
+
+    fn engines(landofile: &Value) -> Vec<(String, Option<String>)> {
+        parse_services(LANDO_INFO.as_bytes()).unwrap().iter()
+            .map(|service| (service.service.clone(), infer_db_engine(service, landofile)))
+            .collect()
+    }
+
+    fn engine(name: &str, engine: Option<&str>) -> (String, Option<String>) {
+        (name.to_string(), engine.map(String::from))
+    }
+
+    #[test]
+    fn engines_from_type_image_and_port() {
+        assert_eq!(engines(&serde_yaml::from_str(LANDOFILE).unwrap()), vec![
+            engine("database", Some("mariadb")),
+            // La imagen de `lando info` gana a la del .lando.yml
+            engine("pg", Some("postgres")),
+            engine("docs", Some("mysql")),
+            engine("legacy", Some("mongo")),
+            engine("cache", Some("redis")),
+            engine("appserver", None),
+        ]);
+    }
+
+    #[test]
+    fn without_landofile() {
+        let engines = engines(&Value::Null);
+        assert_eq!(engines[2], engine("docs", None));
+        assert_eq!(engines[1], engine("pg", Some("postgres")));
+    }
+
+    #[test]
+    fn version_output_of_the_probe() {
+        assert_eq!(normalize_engine("mysql  Ver 15.1 Distrib 10.6.12-MariaDB, for debian-linux-gnu"), Some("mariadb"));
+        assert_eq!(normalize_engine("mysql  Ver 8.0.36 for Linux on x86_64 (MySQL Community Server - GPL)"), Some("mysql"));
+        assert_eq!(normalize_engine("psql (PostgreSQL) 15.4"), Some("postgres"));
+        assert_eq!(normalize_engine("db version v6.0.14\nBuild Info: {}"), Some("mongo"));
+        assert_eq!(normalize_engine("Redis server v=7.2.4 sha=00000000:0 malloc=jemalloc-5.3.0"), Some("redis"));
+        assert_eq!(normalize_engine("nginx:1.25"), None);
+        assert_eq!(normalize_engine(""), None);
+    }
+
+    #[test]
+    fn services_worth_probing() {
+        let services = parse_services(LANDO_INFO.as_bytes()).unwrap();
+        assert!(is_db_candidate(&services[5]));
+        assert!(!is_db_candidate(&services[1]));
+        let resolved = LandoService { resolved_db_engine: Some("mysql".to_string()), ..services[5].clone() };
+        assert!(!is_db_candidate(&resolved));
+    }
+}
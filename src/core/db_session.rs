@@ -0,0 +1,620 @@
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::PathBuf;
+use std::process::{Child, ChildStdin, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::core::commands::shell_quote;
+use crate::core::logging::command_for_log;
+use crate::core::platform;
+use crate::models::commands::LandoCommandOutcome;
+use crate::models::db_session::{DbSession, SessionCommand, SessionEvent, SessionStatus, TransactionState};
+use crate::models::lando::{DbConnection, LandoService};
+use crate::models::secret::SecretString;
+use crate::ui::database::DatabaseUI;
+
+// Sesión de base de datos persistente para el modo transacción. Cada sentencia
+// se envía al stdin del cliente seguida de una marca (`SELECT` en MySQL, `\echo`
+// en psql); cuando la marca aparece en stdout la sentencia ha terminado.
+
+const MARKER_PREFIX: &str = "__lando_gui_done_";
+// Cabecera de la columna del SELECT de la marca en MySQL (modo batch)
+const MARKER_COLUMN: &str = "lando_gui_marker";
+// Cada cuánto se comprueba si la shell murió mientras no hay nada que ejecutar
+const IDLE_POLL_INTERVAL: Duration = Duration::from_secs(1);
+const CLOSE_TIMEOUT: Duration = Duration::from_secs(3);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SessionDialect {
+    MySql,
+    Postgres,
+}
+
+impl SessionDialect {
+    pub fn for_type(db_type: &str) -> Option<Self> {
+        match db_type.to_lowercase().as_str() {
+            "mysql" | "mariadb" => Some(Self::MySql),
+            "postgres" | "postgresql" => Some(Self::Postgres),
+            _ => None,
+        }
+    }
+
+    // `--force` y `ON_ERROR_STOP=0` evitan que un error cierre el cliente;
+    // stderr se une a stdout para que los errores lleguen en orden
+    pub fn shell_command(&self, connection: &DbConnection) -> String {
+        let non_empty = |value: &Option<String>| value.clone().filter(|v| !v.is_empty());
+        let mut parts = Vec::new();
+        match self {
+            Self::MySql => {
+                parts.push("mysql --batch --force".to_string());
+                if let Some(user) = non_empty(&connection.user) {
+                    parts.push(format!("-u {}", shell_quote(&user)));
+                }
+                if let Some(password) = connection.password.as_ref().map(SecretString::expose).filter(|p| !p.is_empty()) {
+                    parts.push(shell_quote(&format!("--password={}", password)));
+                }
+                if let Some(database) = non_empty(&connection.database) {
+                    parts.push(shell_quote(&database));
+                }
+            }
+            Self::Postgres => {
+                if let Some(password) = connection.password.as_ref().map(SecretString::expose).filter(|p| !p.is_empty()) {
+                    parts.push(format!("PGPASSWORD={}", shell_quote(&password)));
+                }
+                parts.push("psql -X -q -v ON_ERROR_STOP=0".to_string());
+                if let Some(user) = non_empty(&connection.user) {
+                    parts.push(format!("-U {}", shell_quote(&user)));
+                }
+                if let Some(database) = non_empty(&connection.database) {
+                    parts.push(format!("-d {}", shell_quote(&database)));
+                }
+            }
+        }
+        parts.push("2>&1".to_string());
+        parts.join(" ")
+    }
+
+    pub fn marker_statement(&self, marker: &str) -> String {
+        match self {
+            Self::MySql => format!("SELECT '{}' AS {};", marker, MARKER_COLUMN),
+            Self::Postgres => format!("\\echo {}", marker),
+        }
+    }
+
+    pub fn quit_command(&self) -> &'static str {
+        match self {
+            Self::MySql => "quit",
+            Self::Postgres => "\\q",
+        }
+    }
+}
+
+pub fn session_marker(sequence: u64) -> String {
+    format!("{}{}", MARKER_PREFIX, sequence)
+}
+
+// Sin `;` final el cliente esperaría más texto y la marca quedaría dentro de la sentencia
+pub fn terminate_statement(sql: &str) -> String {
+    let sql = sql.trim();
+    if sql.ends_with(';') { sql.to_string() } else { format!("{};", sql) }
+}
+
+fn is_error_line(line: &str) -> bool {
+    line.starts_with("ERROR") || line.starts_with("FATAL")
+        || line.contains(": ERROR:") || line.contains(": FATAL:")
+}
+
+// Acumula la salida de una sentencia hasta que aparece su marca
+#[derive(Debug, Clone, PartialEq)]
+pub struct StatementOutput {
+    marker: String,
+    lines: Vec<String>,
+    failed: bool,
+}
+
+impl StatementOutput {
+    pub fn new(marker: String) -> Self {
+        Self { marker, lines: Vec::new(), failed: false }
+    }
+
+    // Devuelve true cuando la línea es la marca, es decir, la sentencia terminó
+    pub fn push_line(&mut self, line: &str) -> bool {
+        let line = line.trim_end_matches('\r');
+        if line.trim() == self.marker {
+            return true;
+        }
+        if line.trim() == MARKER_COLUMN {
+            return false;
+        }
+        if is_error_line(line) {
+            self.failed = true;
+        }
+        self.lines.push(line.to_string());
+        false
+    }
+
+    pub fn finish(self) -> Result<String, String> {
+        let text = self.lines.join("\n");
+        if self.failed {
+            Err(format!("Error ejecutando la consulta: {}", text))
+        } else {
+            Ok(text)
+        }
+    }
+}
+
+impl SessionStatus {
+    // Transiciones válidas; un evento que no encaja deja el estado como está
+    pub fn next(&self, event: &SessionEvent) -> SessionStatus {
+        match (self, event) {
+            (SessionStatus::Starting, SessionEvent::Connected) => SessionStatus::Ready,
+            (SessionStatus::Ready, SessionEvent::Submitted(id)) => SessionStatus::Busy(*id),
+            (SessionStatus::Busy(current), SessionEvent::Finished(id)) if current == id => SessionStatus::Ready,
+            (status, _) => status.clone(),
+        }
+    }
+
+    pub fn accepts_statements(&self) -> bool {
+        *self == SessionStatus::Ready
+    }
+}
+
+impl TransactionState {
+    // Estado tras ejecutar `sql`. Las sentencias fallidas no abren ni cierran
+    // la transacción, pero sí cuentan si ya había una abierta.
+    pub fn after_statement(self, sql: &str, succeeded: bool) -> TransactionState {
+        let mut state = self;
+        for statement in sql.split(';').map(str::trim).filter(|s| !s.is_empty()) {
+            let upper = statement.to_uppercase();
+            let words: Vec<&str> = upper.split_whitespace().collect();
+            let opens = matches!(words.as_slice(), ["BEGIN", ..] | ["START", "TRANSACTION", ..]);
+            let closes = matches!(words.as_slice(), ["COMMIT", ..] | ["END", ..])
+                || (words.first() == Some(&"ROLLBACK") && !words.contains(&"TO"));
+
+            state = match state {
+                _ if opens && succeeded => TransactionState::Open { statements: 0 },
+                _ if closes && succeeded => TransactionState::Autocommit,
+                TransactionState::Open { statements } => TransactionState::Open { statements: statements + 1 },
+                TransactionState::Autocommit => TransactionState::Autocommit,
+            };
+        }
+        state
+    }
+
+    pub fn is_open(&self) -> bool {
+        matches!(self, TransactionState::Open { .. })
+    }
+}
+
+impl Drop for DbSession {
+    fn drop(&mut self) {
+        let _ = self.commands.send(SessionCommand::Close { rollback: true });
+    }
+}
+
+// Lanza la shell y el hilo que la gestiona. La sesión no se apunta en el registro
+// de procesos: si la app muere, el cliente recibe EOF en stdin y termina solo
+// (y el servidor deshace la transacción abierta).
+pub fn start_db_session(
+    sender: Sender<LandoCommandOutcome>,
+    project_path: PathBuf,
+    connection: DbConnection,
+    dialect: SessionDialect,
+) -> Result<Sender<SessionCommand>, String> {
+    let shell = dialect.shell_command(&connection);
+    let args = ["ssh".to_string(), "-s".to_string(), connection.service.clone(), "-c".to_string(), shell.clone()];
+    log::info!("Sesión de BD: $ {}", command_for_log("lando", &args));
+    let mut child = platform::command("lando")
+        .args(&args)
+        .current_dir(&project_path)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("No se pudo abrir la sesión de base de datos: {}", e))?;
+
+    let (commands, command_receiver) = mpsc::channel();
+    let lines = spawn_line_readers(&mut child);
+    let stdin = child.stdin.take();
+
+    thread::spawn(move || {
+        let mut worker = SessionWorker { child, stdin, lines, dialect, sequence: 0 };
+        let error = worker.run(&connection.service, &sender, &command_receiver);
+        match &error {
+            Some(error) => log::warn!("Sesión de BD de {} terminada: {}", connection.service, error),
+            None => log::info!("Sesión de BD de {} cerrada", connection.service),
+        }
+        let _ = sender.send(LandoCommandOutcome::DbSessionEnded { service: connection.service, error });
+    });
+
+    Ok(commands)
+}
+
+// stdout y stderr de `lando` (los errores del propio lando, no los del cliente)
+// llegan por el mismo canal, línea a línea. El canal se cierra al morir la shell.
+fn spawn_line_readers(child: &mut Child) -> Receiver<String> {
+    let (line_sender, lines) = mpsc::channel();
+    let readers: [Option<Box<dyn Read + Send>>; 2] = [
+        child.stdout.take().map(|s| Box::new(s) as Box<dyn Read + Send>),
+        child.stderr.take().map(|s| Box::new(s) as Box<dyn Read + Send>),
+    ];
+    for reader in readers.into_iter().flatten() {
+        let line_sender = line_sender.clone();
+        thread::spawn(move || {
+            for line in BufReader::new(reader).lines().map_while(Result::ok) {
+                if line_sender.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+    }
+    lines
+}
+
+struct SessionWorker {
+    child: Child,
+    stdin: Option<ChildStdin>,
+    lines: Receiver<String>,
+    dialect: SessionDialect,
+    sequence: u64,
+}
+
+impl SessionWorker {
+    // Devuelve el motivo si la sesión terminó de forma inesperada
+    fn run(&mut self, service: &str, sender: &Sender<LandoCommandOutcome>, commands: &Receiver<SessionCommand>) -> Option<String> {
+        // La primera marca confirma que el cliente conectó
+        match self.execute("") {
+            Ok(Ok(_)) => {
+                let _ = sender.send(LandoCommandOutcome::DbSessionReady { service: service.to_string() });
+            }
+            Ok(Err(e)) | Err(e) => {
+                self.shutdown(false);
+                return Some(e);
+            }
+        }
+
+        loop {
+            match commands.recv_timeout(IDLE_POLL_INTERVAL) {
+                Ok(SessionCommand::Execute { request_id, sql }) => {
+                    match self.execute(&sql) {
+                        Ok(output) => {
+                            let _ = sender.send(LandoCommandOutcome::DbTaggedResult { service: service.to_string(), request_id, output });
+                        }
+                        Err(e) => {
+                            let _ = sender.send(LandoCommandOutcome::DbTaggedResult { service: service.to_string(), request_id, output: Err(e.clone()) });
+                            self.shutdown(false);
+                            return Some(e);
+                        }
+                    }
+                }
+                Ok(SessionCommand::Close { rollback }) => {
+                    self.shutdown(rollback);
+                    return None;
+                }
+                // La UI soltó la sesión sin cerrarla
+                Err(RecvTimeoutError::Disconnected) => {
+                    self.shutdown(true);
+                    return None;
+                }
+                Err(RecvTimeoutError::Timeout) => {
+                    if let Ok(Some(status)) = self.child.try_wait() {
+                        return Some(format!("La shell de base de datos terminó inesperadamente ({})", status));
+                    }
+                }
+            }
+        }
+    }
+
+    // Error externo: la shell murió. Error interno: la sentencia falló.
+    fn execute(&mut self, sql: &str) -> Result<Result<String, String>, String> {
+        self.sequence += 1;
+        let marker = session_marker(self.sequence);
+        let mut input = String::new();
+        if !sql.trim().is_empty() {
+            input.push_str(&terminate_statement(sql));
+            input.push('\n');
+        }
+        input.push_str(&self.dialect.marker_statement(&marker));
+        input.push('\n');
+        self.write(&input)?;
+
+        let mut output = StatementOutput::new(marker);
+        loop {
+            match self.lines.recv() {
+                Ok(line) => {
+                    if output.push_line(&line) {
+                        return Ok(output.finish());
+                    }
+                }
+                Err(_) => {
+                    let detail = output.finish().unwrap_or_else(|e| e);
+                    return Err(format!("La shell de base de datos se cerró: {}", detail.trim()));
+                }
+            }
+        }
+    }
+
+    fn write(&mut self, input: &str) -> Result<(), String> {
+        let stdin = self.stdin.as_mut().ok_or("La sesión ya está cerrada")?;
+        stdin.write_all(input.as_bytes())
+            .and_then(|_| stdin.flush())
+            .map_err(|e| format!("No se pudo escribir en la sesión: {}", e))
+    }
+
+    // Sale del cliente y espera a la shell; si no termina a tiempo se mata
+    fn shutdown(&mut self, rollback: bool) {
+        let mut input = String::new();
+        if rollback {
+            input.push_str("ROLLBACK;\n");
+        }
+        input.push_str(self.dialect.quit_command());
+        input.push('\n');
+        let _ = self.write(&input);
+        self.stdin = None;
+
+        let started = Instant::now();
+        while started.elapsed() < CLOSE_TIMEOUT {
+            if !matches!(self.child.try_wait(), Ok(None)) {
+                return;
+            }
+            thread::sleep(Duration::from_millis(50));
+        }
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+impl DatabaseUI {
+    pub fn supports_transaction_mode(&self, service: &LandoService) -> bool {
+        SessionDialect::for_type(service.db_engine()).is_some()
+    }
+
+    pub fn start_transaction_mode(&mut self, service: &LandoService, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>) {
+        let Some(dialect) = SessionDialect::for_type(service.db_engine()) else {
+            return;
+        };
+        match start_db_session(sender.clone(), project_path.clone(), self.db_connection(service), dialect) {
+            Ok(commands) => {
+                self.session_warning = None;
+                self.db_session = Some(DbSession {
+                    service: service.service.clone(),
+                    status: SessionStatus::Starting,
+                    transaction: TransactionState::Autocommit,
+                    commands,
+                });
+            }
+            Err(e) => self.session_warning = Some(e),
+        }
+    }
+
+    // Al soltar la sesión su `Drop` pide el cierre con ROLLBACK
+    pub fn stop_transaction_mode(&mut self) {
+        self.db_session = None;
+    }
+
+    pub fn session_accepts_statements(&self) -> bool {
+        self.db_session.as_ref().is_some_and(|s| s.status.accepts_statements())
+    }
+
+    // Envía la sentencia por la sesión; el resultado llega como cualquier consulta
+    // (`DbTaggedResult`) y rellena el placeholder `request_id`
+    pub fn send_session_statement(&mut self, request_id: u64, sql: String) -> bool {
+        let Some(session) = &mut self.db_session else {
+            return false;
+        };
+        if !session.status.accepts_statements() {
+            return false;
+        }
+        if session.commands.send(SessionCommand::Execute { request_id, sql }).is_err() {
+            return false;
+        }
+        session.status = session.status.next(&SessionEvent::Submitted(request_id));
+        true
+    }
+
+    pub fn run_transaction_statement(&mut self, sql: &str, is_loading: &mut bool) {
+        if !self.session_accepts_statements() {
+            return;
+        }
+        *is_loading = true;
+        let request_id = self.push_pending_result(sql.to_string(), "Ejecutando en la sesión...");
+        self.send_session_statement(request_id, sql.to_string());
+    }
+
+    pub fn handle_session_ready(&mut self) {
+        if let Some(session) = &mut self.db_session {
+            session.status = session.status.next(&SessionEvent::Connected);
+        }
+    }
+
+    pub fn track_session_result(&mut self, request_id: u64, query: &str, has_error: bool) {
+        let Some(session) = &mut self.db_session else {
+            return;
+        };
+        if session.status != SessionStatus::Busy(request_id) {
+            return;
+        }
+        session.status = session.status.next(&SessionEvent::Finished(request_id));
+        session.transaction = session.transaction.after_statement(query, !has_error);
+    }
+
+    // Solo importa si la sesión murió sola: un cierre pedido desde la UI ya la
+    // soltó (y puede que ya haya otra abierta)
+    pub fn handle_session_ended(&mut self, error: Option<String>) {
+        let Some(error) = error else {
+            return;
+        };
+        self.db_session = None;
+        self.session_warning = Some(error);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(unix)]
+    use std::process::Command;
+
+    use super::*;
+
+    fn connection(user: &str, password: &str, database: &str) -> DbConnection {
+        DbConnection {
+            service: "database".to_string(),
+            user: Some(user.to_string()),
+            password: Some(SecretString::new(password)),
+            database: Some(database.to_string()),
+        }
+    }
+
+    // Sesión con un script de shell en lugar de `lando ssh`
+    #[cfg(unix)]
+    fn fake_client(script: &str) -> SessionWorker {
+        let mut child = Command::new("sh")
+            .args(["-c", script])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .unwrap();
+        let lines = spawn_line_readers(&mut child);
+        let stdin = child.stdin.take();
+        SessionWorker { child, stdin, lines, dialect: SessionDialect::Postgres, sequence: 0 }
+    }
+
+    // Responde como psql: repite `\echo`, sale con `\q` y marca con ERROR
+    // las sentencias que contienen `boom`
+    #[cfg(unix)]
+    const FAKE_PSQL: &str = r#"while IFS= read -r l; do case "$l" in
+        '\echo '*) echo "${l#*echo }";;
+        '\q') exit 0;;
+        *boom*) echo 'ERROR:  syntax error at or near "boom"';;
+        *) echo "out: $l";;
+    esac; done"#;
+
+    #[test]
+    fn client_commands() {
+        assert_eq!(
+            SessionDialect::MySql.shell_command(&connection("app", "it's", "blog")),
+            "mysql --batch --force -u 'app' '--password=it'\\''s' 'blog' 2>&1",
+        );
+        assert_eq!(
+            SessionDialect::Postgres.shell_command(&connection("postgres", "", "")),
+            "psql -X -q -v ON_ERROR_STOP=0 -U 'postgres' 2>&1",
+        );
+        assert_eq!(SessionDialect::for_type("MariaDB"), Some(SessionDialect::MySql));
+        assert_eq!(SessionDialect::for_type("mongo"), None);
+        assert_eq!(terminate_statement("  SELECT 1 \n"), "SELECT 1;");
+        assert_eq!(terminate_statement("SELECT 1;"), "SELECT 1;");
+    }
+
+    #[test]
+    fn mysql_output_until_the_marker() {
+        let marker = session_marker(3);
+        assert_eq!(SessionDialect::MySql.marker_statement(&marker), "SELECT '__lando_gui_done_3' AS lando_gui_marker;");
+
+        let mut output = StatementOutput::new(marker.clone());
+        assert!(!output.push_line("id\tname\r"));
+        assert!(!output.push_line("1\tada"));
+        assert!(!output.push_line(MARKER_COLUMN));
+        assert!(output.push_line(&marker));
+        assert_eq!(output.finish(), Ok("id\tname\n1\tada".to_string()));
+
+        let mut output = StatementOutput::new(marker);
+        output.push_line("ERROR 1064 (42000) at line 4: You have an error in your SQL syntax");
+        assert!(output.finish().unwrap_err().starts_with("Error ejecutando la consulta: ERROR 1064"));
+    }
+
+    #[test]
+    fn status_transitions() {
+        let status = SessionStatus::Starting;
+        assert!(!status.accepts_statements());
+        assert_eq!(status.next(&SessionEvent::Submitted(1)), SessionStatus::Starting);
+        let status = status.next(&SessionEvent::Connected);
+        assert!(status.accepts_statements());
+        let status = status.next(&SessionEvent::Submitted(4));
+        assert_eq!(status, SessionStatus::Busy(4));
+        assert_eq!(status.next(&SessionEvent::Finished(5)), SessionStatus::Busy(4));
+        assert_eq!(status.next(&SessionEvent::Finished(4)), SessionStatus::Ready);
+    }
+
+    #[test]
+    fn transaction_tracking() {
+        let state = TransactionState::Autocommit.after_statement("begin", true);
+        assert_eq!(state, TransactionState::Open { statements: 0 });
+        let state = state.after_statement("UPDATE posts SET views = 0; DELETE FROM tags;", true);
+        assert_eq!(state, TransactionState::Open { statements: 2 });
+        let state = state.after_statement("SELECT boom", false);
+        assert_eq!(state, TransactionState::Open { statements: 3 });
+        assert_eq!(state.after_statement("ROLLBACK TO SAVEPOINT a", true), TransactionState::Open { statements: 4 });
+        assert_eq!(state.after_statement("COMMIT", false), TransactionState::Open { statements: 4 });
+        assert_eq!(state.after_statement("commit", true), TransactionState::Autocommit);
+        assert_eq!(TransactionState::Autocommit.after_statement("START TRANSACTION", false), TransactionState::Autocommit);
+        assert!(!TransactionState::Autocommit.after_statement("UPDATE posts SET views = 0", true).is_open());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn session_round_trip_with_a_fake_client() {
+        let mut worker = fake_client(FAKE_PSQL);
+        let (sender, outcomes) = mpsc::channel();
+        let (commands, command_receiver) = mpsc::channel();
+        commands.send(SessionCommand::Execute { request_id: 7, sql: "BEGIN".to_string() }).unwrap();
+        commands.send(SessionCommand::Execute { request_id: 8, sql: "SELECT boom".to_string() }).unwrap();
+        commands.send(SessionCommand::Close { rollback: true }).unwrap();
+
+        assert_eq!(worker.run("database", &sender, &command_receiver), None);
+        assert!(worker.child.try_wait().unwrap().is_some());
+
+        let outcomes: Vec<_> = outcomes.try_iter().collect();
+        assert!(matches!(&outcomes[0], LandoCommandOutcome::DbSessionReady { service } if service == "database"));
+        assert!(matches!(&outcomes[1], LandoCommandOutcome::DbTaggedResult { request_id: 7, output: Ok(text), .. } if text == "out: BEGIN;"));
+        assert!(matches!(&outcomes[2], LandoCommandOutcome::DbTaggedResult { request_id: 8, output: Err(e), .. } if e.contains("\"boom\"")));
+        assert_eq!(outcomes.len(), 3);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn a_client_that_cannot_connect_ends_the_session() {
+        let mut worker = fake_client("echo 'psql: error: connection to server failed'");
+        let (sender, outcomes) = mpsc::channel();
+        let (_commands, command_receiver) = mpsc::channel();
+        let error = worker.run("database", &sender, &command_receiver).unwrap();
+        assert!(error.contains("connection to server failed") || error.contains("No se pudo escribir"), "{}", error);
+        assert!(outcomes.try_recv().is_err());
+    }
+
+    #[test]
+    fn database_ui_lifecycle() {
+        let (commands, command_receiver) = mpsc::channel();
+        let mut database_ui = DatabaseUI::default();
+        assert!(!database_ui.send_session_statement(1, "SELECT 1".to_string()));
+
+        database_ui.db_session = Some(DbSession {
+            service: "database".to_string(),
+            status: SessionStatus::Starting,
+            transaction: TransactionState::Autocommit,
+            commands,
+        });
+        let mut is_loading = false;
+        database_ui.run_transaction_statement("BEGIN", &mut is_loading);
+        assert!(!is_loading, "no se envía nada hasta que el cliente responde");
+
+        database_ui.handle_session_ready();
+        database_ui.run_transaction_statement("BEGIN", &mut is_loading);
+        assert!(is_loading);
+        let request_id = database_ui.query_results.last().unwrap().request_id.unwrap();
+        assert_eq!(command_receiver.try_recv(), Ok(SessionCommand::Execute { request_id, sql: "BEGIN".to_string() }));
+        assert!(!database_ui.session_accepts_statements());
+
+        database_ui.track_session_result(request_id, "BEGIN", false);
+        let session = database_ui.db_session.as_ref().unwrap();
+        assert_eq!((&session.status, session.transaction), (&SessionStatus::Ready, TransactionState::Open { statements: 0 }));
+
+        // Cerrar desde la UI deshace la transacción abierta
+        database_ui.stop_transaction_mode();
+        assert_eq!(command_receiver.try_recv(), Ok(SessionCommand::Close { rollback: true }));
+
+        database_ui.handle_session_ended(Some("La shell de base de datos se cerró".to_string()));
+        assert_eq!(database_ui.session_warning.as_deref(), Some("La shell de base de datos se cerró"));
+    }
+}
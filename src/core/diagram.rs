@@ -0,0 +1,98 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::mpsc::Sender;
+
+use eframe::egui;
+
+use crate::core::storage::{load_app_file, save_app_file};
+use crate::models::commands::LandoCommandOutcome;
+use crate::models::lando::LandoService;
+use crate::ui::database::DatabaseUI;
+
+// Tamaño de las cajas del diagrama
+pub const DIAGRAM_NODE_WIDTH: f32 = 200.0;
+pub const DIAGRAM_HEADER_HEIGHT: f32 = 24.0;
+pub const DIAGRAM_ROW_HEIGHT: f32 = 18.0;
+pub const DIAGRAM_MAX_COLUMNS: usize = 12;
+const DIAGRAM_GAP: egui::Vec2 = egui::vec2(60.0, 40.0);
+
+const LAYOUT_FILE: &str = "er_layouts.json";
+
+// Posiciones guardadas: clave de diagrama -> tabla -> [x, y]
+type SavedLayouts = HashMap<String, HashMap<String, [f32; 2]>>;
+
+// Diagrama de relaciones a partir de las claves foráneas
+impl DatabaseUI {
+    // Pide las claves foráneas y las columnas de las tablas que aún no las tienen
+    pub fn load_diagram(&mut self, service: &LandoService, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>) {
+        self.diagram.loading = true;
+        self.diagram.loaded = true;
+        self.diagram.error = None;
+
+        self.load_foreign_keys(service, project_path, sender);
+
+        let missing: Vec<String> = self.tables.iter()
+            .filter(|t| t.columns.is_empty())
+            .map(|t| t.name.clone())
+            .collect();
+        for table in missing {
+            self.load_table_columns(service, project_path, sender, &table);
+        }
+    }
+
+    pub fn diagram_node_size(&self, table: &str) -> egui::Vec2 {
+        let columns = self.tables.iter()
+            .find(|t| t.name == table)
+            .map(|t| t.columns.len().min(DIAGRAM_MAX_COLUMNS + 1))
+            .unwrap_or(0);
+        egui::vec2(DIAGRAM_NODE_WIDTH, DIAGRAM_HEADER_HEIGHT + DIAGRAM_ROW_HEIGHT * columns.max(1) as f32)
+    }
+
+    // Carga las posiciones guardadas al cambiar de proyecto o servicio y coloca
+    // en rejilla las tablas que no tienen posición.
+    pub fn ensure_diagram_layout(&mut self, service: &LandoService, project_path: &PathBuf) {
+        let key = format!("{}#{}", project_path.display(), service.service);
+        if self.diagram.layout_key.as_ref() != Some(&key) {
+            self.diagram.positions = load_app_file::<SavedLayouts>(LAYOUT_FILE)
+                .remove(&key)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|(table, [x, y])| (table, egui::pos2(x, y)))
+                .collect();
+            self.diagram.layout_key = Some(key);
+        }
+
+        let per_row = (self.tables.len() as f32).sqrt().ceil().max(1.0) as usize;
+        let row_height = DIAGRAM_HEADER_HEIGHT + DIAGRAM_ROW_HEIGHT * (DIAGRAM_MAX_COLUMNS / 2) as f32;
+        for (index, table) in self.tables.iter().enumerate() {
+            self.diagram.positions.entry(table.name.clone()).or_insert_with(|| {
+                egui::pos2(
+                    (index % per_row) as f32 * (DIAGRAM_NODE_WIDTH + DIAGRAM_GAP.x),
+                    (index / per_row) as f32 * (row_height + DIAGRAM_GAP.y),
+                )
+            });
+        }
+    }
+
+    pub fn save_diagram_layout(&mut self) {
+        let Some(key) = self.diagram.layout_key.clone() else {
+            return;
+        };
+
+        let mut layouts: SavedLayouts = load_app_file(LAYOUT_FILE);
+        layouts.insert(
+            key,
+            self.diagram.positions.iter().map(|(table, pos)| (table.clone(), [pos.x, pos.y])).collect(),
+        );
+
+        if let Err(e) = save_app_file(LAYOUT_FILE, &layouts) {
+            self.diagram.error = Some(format!("No se pudo guardar la disposición: {}", e));
+        }
+    }
+
+    // Vuelve a la rejilla por defecto en el siguiente frame
+    pub fn reset_diagram_layout(&mut self) {
+        self.diagram.positions.clear();
+        self.save_diagram_layout();
+    }
+}
@@ -0,0 +1,521 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::Sender;
+use std::thread;
+
+use serde_json::Value as JsonValue;
+use serde_yaml::Value;
+
+use crate::core::commands::{compose_project_name, to_args};
+use crate::core::recipes::detect_app_name;
+use crate::core::runner::{system_runner, CommandRunner};
+use crate::models::commands::LandoCommandOutcome;
+use crate::models::drift::{ConfigDifference, ConfigSnapshot, DriftState, ServiceSnapshot};
+
+// Landofiles que Lando combina, en orden de prioridad creciente
+const LANDO_FILES: &[&str] = &[".lando.base.yml", ".lando.dist.yml", ".lando.upstream.yml", ".lando.yml", ".lando.local.yml"];
+
+// Servicios que crea cada receta a partir de `config:` (clave de config, servicio)
+const RECIPE_SERVICES: &[(&str, &str)] = &[("php", "appserver"), ("database", "database")];
+
+fn scalar_to_string(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.trim().to_string()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+// Los mapas se combinan clave a clave; el resto de valores se sustituye
+fn merge_yaml(base: &mut Value, overlay: Value) {
+    match (base, overlay) {
+        (Value::Mapping(base), Value::Mapping(overlay)) => {
+            for (key, value) in overlay {
+                match base.get_mut(&key) {
+                    Some(existing) => merge_yaml(existing, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
+
+// `mariadb:10.4` → (mariadb, 10.4)
+fn split_type(value: &str) -> (Option<String>, Option<String>) {
+    match value.split_once(':') {
+        Some((kind, version)) => (Some(kind.trim().to_string()), Some(version.trim().to_string()).filter(|v| !v.is_empty())),
+        None => (Some(value.trim().to_string()).filter(|t| !t.is_empty()), None),
+    }
+}
+
+// Puerto del host de una entrada de `ports` de compose: `3307:3306`,
+// `127.0.0.1:3307:3306/tcp`... Sin puerto del host (`3306`, `127.0.0.1::3306`) es aleatorio.
+fn host_port(mapping: &str) -> Option<String> {
+    let mapping = mapping.split('/').next().unwrap_or_default();
+    let parts: Vec<&str> = mapping.split(':').collect();
+    if parts.len() < 2 {
+        return None;
+    }
+    Some(parts[parts.len() - 2].trim().to_string()).filter(|port| !port.is_empty())
+}
+
+fn yaml_ports(value: Option<&Value>) -> BTreeSet<String> {
+    let Some(Value::Sequence(ports)) = value else {
+        return BTreeSet::new();
+    };
+    ports.iter()
+        .filter_map(|port| match port {
+            Value::Mapping(long) => long.get("published").and_then(scalar_to_string),
+            other => scalar_to_string(other).and_then(|p| host_port(&p)),
+        })
+        .collect()
+}
+
+// `environment` como mapa o como lista de `CLAVE=valor`
+fn yaml_env(value: Option<&Value>) -> BTreeMap<String, String> {
+    match value {
+        Some(Value::Mapping(map)) => map.iter()
+            .filter_map(|(k, v)| Some((k.as_str()?.to_string(), scalar_to_string(v).unwrap_or_default())))
+            .collect(),
+        Some(Value::Sequence(list)) => list.iter()
+            .filter_map(|item| item.as_str()?.split_once('=').map(|(k, v)| (k.to_string(), v.to_string())))
+            .collect(),
+        _ => BTreeMap::new(),
+    }
+}
+
+fn disk_service(service: &Value) -> ServiceSnapshot {
+    let (r#type, version) = service.get("type")
+        .and_then(scalar_to_string)
+        .map(|t| split_type(&t))
+        .unwrap_or_default();
+
+    // Servicios de Lando: `portforward` y `overrides`; los `compose`: `services`
+    let mut ports = BTreeSet::new();
+    if let Some(Value::Number(port)) = service.get("portforward") {
+        ports.insert(port.to_string());
+    }
+    let mut env = BTreeMap::new();
+    for section in ["overrides", "services"] {
+        let section = service.get(section);
+        ports.extend(yaml_ports(section.and_then(|s| s.get("ports"))));
+        env.extend(yaml_env(section.and_then(|s| s.get("environment"))));
+    }
+
+    ServiceSnapshot { r#type, version, ports, env, inspected: false }
+}
+
+// Configuración combinada de los landofiles normalizada a lo que se compara
+pub fn snapshot_from_landofile(config: &Value) -> ConfigSnapshot {
+    let mut snapshot = ConfigSnapshot::default();
+
+    if config.get("recipe").and_then(scalar_to_string).is_some() {
+        snapshot.from_recipe = true;
+        let recipe_config = config.get("config");
+        for (key, service) in RECIPE_SERVICES {
+            let Some(value) = recipe_config.and_then(|c| c.get(*key)).and_then(scalar_to_string) else {
+                continue;
+            };
+            // `php: '8.1'` es solo versión; `database: mysql:5.7` lleva también el tipo
+            let entry = if *key == "php" {
+                ServiceSnapshot { r#type: Some("php".to_string()), version: Some(value), ..Default::default() }
+            } else {
+                let (r#type, version) = split_type(&value);
+                ServiceSnapshot { r#type, version, ..Default::default() }
+            };
+            snapshot.services.insert(service.to_string(), entry);
+        }
+    }
+
+    if let Some(Value::Mapping(services)) = config.get("services") {
+        for (name, service) in services {
+            let Some(name) = name.as_str() else {
+                continue;
+            };
+            let mut entry = disk_service(service);
+            // Un servicio de la receta retocado en `services:` conserva el tipo de `config:`
+            if let Some(existing) = snapshot.services.get(name) {
+                entry.r#type = entry.r#type.or(existing.r#type.clone());
+                entry.version = entry.version.or(existing.version.clone());
+            }
+            snapshot.services.insert(name.to_string(), entry);
+        }
+    }
+
+    snapshot
+}
+
+pub fn read_landofiles(project_path: &Path) -> Result<Value, String> {
+    let mut merged = Value::Mapping(Default::default());
+    for name in LANDO_FILES {
+        let file = project_path.join(name);
+        let content = match fs::read_to_string(&file) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(format!("No se pudo leer {}: {}", name, e)),
+        };
+        let value: Value = serde_yaml::from_str(&content).map_err(|e| format!("{}: {}", name, e))?;
+        if !value.is_null() {
+            merge_yaml(&mut merged, value);
+        }
+    }
+    Ok(merged)
+}
+
+// Lando guarda el nombre de la app en minúsculas con guiones
+fn app_machine_name(name: &str) -> String {
+    let slug: String = name.to_lowercase()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    slug.split('-').filter(|part| !part.is_empty()).collect::<Vec<_>>().join("-")
+}
+
+// `~/.lando/cache/<app>.compose.cache` se escribe al arrancar la app; `lando info`
+// no sirve porque vuelve a leer el .lando.yml en cada llamada
+fn compose_cache_file(app_name: &str) -> Option<PathBuf> {
+    let home = std::env::var_os("HOME").or_else(|| std::env::var_os("USERPROFILE"))?;
+    Some(PathBuf::from(home).join(".lando").join("cache").join(format!("{}.compose.cache", app_machine_name(app_name))))
+}
+
+fn json_text(value: &JsonValue) -> Option<String> {
+    match value {
+        JsonValue::String(s) => Some(s.trim().to_string()).filter(|s| !s.is_empty()),
+        JsonValue::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+// Tipo y versión de cada servicio según el `info` guardado en la caché
+pub fn snapshot_from_cache(cache: &JsonValue) -> ConfigSnapshot {
+    let mut snapshot = ConfigSnapshot::default();
+    let Some(info) = cache.get("info").and_then(JsonValue::as_array) else {
+        return snapshot;
+    };
+    for service in info {
+        let Some(name) = service.get("service").and_then(json_text) else {
+            continue;
+        };
+        let (r#type, type_version) = service.get("type").and_then(json_text).map(|t| split_type(&t)).unwrap_or_default();
+        let version = service.get("version").and_then(json_text).or(type_version);
+        snapshot.services.insert(name, ServiceSnapshot { r#type, version, ..Default::default() });
+    }
+    snapshot
+}
+
+// Completa la caché con los puertos y variables de los contenedores creados
+// (`docker inspect`), que es lo que de verdad está en uso
+pub fn apply_container_inspect(snapshot: &mut ConfigSnapshot, inspect: &JsonValue) {
+    let Some(containers) = inspect.as_array() else {
+        return;
+    };
+    for container in containers {
+        let Some(service) = container.pointer("/Config/Labels/com.docker.compose.service").and_then(json_text) else {
+            continue;
+        };
+        let entry = snapshot.services.entry(service).or_default();
+        entry.inspected = true;
+
+        if let Some(env) = container.pointer("/Config/Env").and_then(JsonValue::as_array) {
+            for item in env.iter().filter_map(JsonValue::as_str) {
+                if let Some((key, value)) = item.split_once('=') {
+                    entry.env.insert(key.to_string(), value.to_string());
+                }
+            }
+        }
+        if let Some(JsonValue::Object(bindings)) = container.pointer("/HostConfig/PortBindings") {
+            for binding in bindings.values().filter_map(JsonValue::as_array).flatten() {
+                if let Some(port) = binding.get("HostPort").and_then(json_text) {
+                    entry.ports.insert(port);
+                }
+            }
+        }
+    }
+}
+
+fn inspect_containers(runner: &dyn CommandRunner, app_name: &str) -> Result<JsonValue, String> {
+    let filter = format!("label=com.docker.compose.project={}", compose_project_name(app_name));
+    let ps = runner.run("docker", &to_args(&["ps", "-a", "-q", "--filter", &filter]), None)
+        .map_err(|e| format!("No se pudo ejecutar docker: {}", e))?;
+    if !ps.success {
+        return Err(format!("docker ps falló: {}", ps.stderr_lossy().trim()));
+    }
+    let ids: Vec<String> = ps.stdout_lossy().lines().map(|id| id.trim().to_string()).filter(|id| !id.is_empty()).collect();
+    if ids.is_empty() {
+        return Ok(JsonValue::Array(Vec::new()));
+    }
+
+    let mut args = vec!["inspect".to_string()];
+    args.extend(ids);
+    let inspect = runner.run("docker", &args, None).map_err(|e| format!("No se pudo ejecutar docker: {}", e))?;
+    if !inspect.success {
+        return Err(format!("docker inspect falló: {}", inspect.stderr_lossy().trim()));
+    }
+    serde_json::from_str(&inspect.stdout_lossy()).map_err(|e| format!("Salida de docker inspect no válida: {}", e))
+}
+
+fn show_set(set: &BTreeSet<String>) -> String {
+    if set.is_empty() { "—".to_string() } else { set.iter().cloned().collect::<Vec<_>>().join(", ") }
+}
+
+// Solo se compara lo que fija el lado del disco. Con receta no se puede saber qué
+// servicios añade, así que los que solo están en uso no cuentan como borrados.
+pub fn compare_snapshots(in_use: &ConfigSnapshot, on_disk: &ConfigSnapshot) -> Vec<ConfigDifference> {
+    let mut differences = Vec::new();
+
+    for (name, disk) in &on_disk.services {
+        let Some(used) = in_use.services.get(name) else {
+            differences.push(ConfigDifference::OnlyOnDisk(name.clone()));
+            continue;
+        };
+        let mut changed = |field: &str, in_use: String, on_disk: String| {
+            differences.push(ConfigDifference::Changed { service: name.clone(), field: field.to_string(), in_use, on_disk });
+        };
+
+        for (field, disk_value, used_value) in [("tipo", &disk.r#type, &used.r#type), ("versión", &disk.version, &used.version)] {
+            if let Some(disk_value) = disk_value {
+                if used_value.as_ref() != Some(disk_value) {
+                    changed(field, used_value.clone().unwrap_or_else(|| "?".to_string()), disk_value.clone());
+                }
+            }
+        }
+
+        // Sin contenedor no se sabe qué puertos ni variables tiene
+        if !used.inspected {
+            continue;
+        }
+        if !disk.ports.is_empty() && disk.ports != used.ports {
+            changed("puertos", show_set(&used.ports), show_set(&disk.ports));
+        }
+        for (key, value) in &disk.env {
+            let current = used.env.get(key);
+            if current != Some(value) {
+                changed(&format!("env {}", key), current.cloned().unwrap_or_else(|| "—".to_string()), value.clone());
+            }
+        }
+    }
+
+    if !on_disk.from_recipe {
+        for name in in_use.services.keys().filter(|name| !on_disk.services.contains_key(*name)) {
+            differences.push(ConfigDifference::OnlyInUse(name.clone()));
+        }
+    }
+
+    differences
+}
+
+pub fn check_config_drift_with(runner: &dyn CommandRunner, project_path: &Path) -> DriftState {
+    let config = match read_landofiles(project_path) {
+        Ok(config) => config,
+        Err(err) => return DriftState::InvalidYaml(err),
+    };
+    let on_disk = snapshot_from_landofile(&config);
+
+    let app_name = detect_app_name(project_path)
+        .unwrap_or_else(|| project_path.file_name().unwrap_or_default().to_string_lossy().to_string());
+    let Some(cache_file) = compose_cache_file(&app_name) else {
+        return DriftState::Failed("No se encontró el directorio personal para localizar ~/.lando".to_string());
+    };
+    let cache = match fs::read_to_string(&cache_file) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return DriftState::NeverStarted,
+        Err(e) => return DriftState::Failed(format!("No se pudo leer {}: {}", cache_file.display(), e)),
+    };
+    let cache: JsonValue = match serde_json::from_str(&cache) {
+        Ok(cache) => cache,
+        Err(e) => return DriftState::Failed(format!("Caché de Lando no válida ({}): {}", cache_file.display(), e)),
+    };
+
+    let mut in_use = snapshot_from_cache(&cache);
+    // Sin docker se compara al menos el tipo y la versión
+    match inspect_containers(runner, &app_name) {
+        Ok(inspect) => apply_container_inspect(&mut in_use, &inspect),
+        Err(err) => log::warn!("No se pudieron inspeccionar los contenedores de {}: {}", app_name, err),
+    }
+
+    DriftState::Compared(compare_snapshots(&in_use, &on_disk))
+}
+
+pub fn check_config_drift(sender: Sender<LandoCommandOutcome>, project: PathBuf) {
+    let runner = system_runner();
+    thread::spawn(move || {
+        let state = check_config_drift_with(runner.as_ref(), &project);
+        let _ = sender.send(LandoCommandOutcome::ConfigDriftChecked { project, state });
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::runner::StubRunner;
+
+    fn landofile(yaml: &str) -> ConfigSnapshot {
+        snapshot_from_landofile(&serde_yaml::from_str(yaml).unwrap())
+    }
+
+    fn changed(service: &str, field: &str, in_use: &str, on_disk: &str) -> ConfigDifference {
+        ConfigDifference::Changed {
+            service: service.to_string(),
+            field: field.to_string(),
+            in_use: in_use.to_string(),
+            on_disk: on_disk.to_string(),
+        }
+    }
+
+    const LANDOFILE: &str = "
+name: blog
+recipe: lamp
+config:
+  php: '8.1'
+  database: mariadb:10.4
+services:
+  database:
+    portforward: 3307
+  cache:
+    type: redis:7
+    overrides:
+      ports:
+        - '127.0.0.1:6380:6379/tcp'
+        - '6379'
+      environment:
+        - MAXMEMORY=64mb
+";
+
+    const CACHE: &str = r#"{"info": [
+        {"service": "appserver", "type": "php", "version": "8.1"},
+        {"service": "database", "type": "mariadb:10.3"},
+        {"service": "cache", "type": "redis", "version": 7},
+        {"service": "mailhog", "type": "mailhog"}
+    ]}"#;
+
+    const INSPECT: &str = r#"[
+        {"Config": {"Labels": {"com.docker.compose.service": "cache"}, "Env": ["MAXMEMORY=128mb", "PATH=/usr/bin"]},
+         "HostConfig": {"PortBindings": {"6379/tcp": [{"HostIp": "127.0.0.1", "HostPort": "6380"}]}}},
+        {"Config": {"Labels": {"com.docker.compose.service": "database"}, "Env": []},
+         "HostConfig": {"PortBindings": {"3306/tcp": [{"HostPort": "3308"}]}}}
+    ]"#;
+
+    #[test]
+    fn landofile_recipe_services_and_overrides() {
+        let snapshot = landofile(LANDOFILE);
+        assert!(snapshot.from_recipe);
+
+        let appserver = &snapshot.services["appserver"];
+        assert_eq!((appserver.r#type.as_deref(), appserver.version.as_deref()), (Some("php"), Some("8.1")));
+
+        // `services.database` solo retoca el puerto: conserva el tipo de `config:`
+        let database = &snapshot.services["database"];
+        assert_eq!((database.r#type.as_deref(), database.version.as_deref()), (Some("mariadb"), Some("10.4")));
+        assert_eq!(database.ports, BTreeSet::from(["3307".to_string()]));
+
+        let cache = &snapshot.services["cache"];
+        assert_eq!((cache.r#type.as_deref(), cache.version.as_deref()), (Some("redis"), Some("7")));
+        assert_eq!(cache.ports, BTreeSet::from(["6380".to_string()]));
+        assert_eq!(cache.env["MAXMEMORY"], "64mb");
+    }
+
+    #[test]
+    fn compose_ports_and_environment_forms() {
+        assert_eq!(host_port("3307:3306"), Some("3307".to_string()));
+        assert_eq!(host_port("127.0.0.1:3307:3306/tcp"), Some("3307".to_string()));
+        assert_eq!(host_port("127.0.0.1::3306"), None);
+        assert_eq!(host_port("3306"), None);
+
+        let snapshot = landofile("
+services:
+  web:
+    type: compose
+    services:
+      ports:
+        - target: 80
+          published: 8080
+      environment:
+        DEBUG: true
+        WORKERS: 4
+");
+        let web = &snapshot.services["web"];
+        assert!(!snapshot.from_recipe);
+        assert_eq!(web.ports, BTreeSet::from(["8080".to_string()]));
+        assert_eq!(web.env["DEBUG"], "true");
+        assert_eq!(web.env["WORKERS"], "4");
+    }
+
+    #[test]
+    fn later_landofiles_override_earlier_ones() {
+        let mut base: Value = serde_yaml::from_str("services:\n  cache:\n    type: redis:6\n    portforward: 6380\n").unwrap();
+        merge_yaml(&mut base, serde_yaml::from_str("services:\n  cache:\n    type: redis:7\n").unwrap());
+        let cache = &snapshot_from_landofile(&base).services["cache"];
+        assert_eq!(cache.version.as_deref(), Some("7"));
+        assert_eq!(cache.ports, BTreeSet::from(["6380".to_string()]));
+    }
+
+    #[test]
+    fn cache_and_inspect_build_the_snapshot_in_use() {
+        let mut in_use = snapshot_from_cache(&serde_json::from_str(CACHE).unwrap());
+        assert_eq!(in_use.services["database"].version.as_deref(), Some("10.3"));
+        assert_eq!(in_use.services["cache"].version.as_deref(), Some("7"));
+        assert!(!in_use.services["cache"].inspected);
+
+        apply_container_inspect(&mut in_use, &serde_json::from_str(INSPECT).unwrap());
+        let cache = &in_use.services["cache"];
+        assert!(cache.inspected);
+        assert_eq!(cache.ports, BTreeSet::from(["6380".to_string()]));
+        assert_eq!(cache.env["MAXMEMORY"], "128mb");
+        assert!(!in_use.services["appserver"].inspected);
+    }
+
+    #[test]
+    fn differences_between_cache_and_landofile() {
+        let mut in_use = snapshot_from_cache(&serde_json::from_str(CACHE).unwrap());
+        apply_container_inspect(&mut in_use, &serde_json::from_str(INSPECT).unwrap());
+        // Con receta, `mailhog` puede venir de ella y no cuenta como borrado
+        assert_eq!(compare_snapshots(&in_use, &landofile(LANDOFILE)), vec![
+            changed("cache", "env MAXMEMORY", "128mb", "64mb"),
+            changed("database", "versión", "10.3", "10.4"),
+            changed("database", "puertos", "3308", "3307"),
+        ]);
+    }
+
+    #[test]
+    fn services_added_or_removed_without_recipe() {
+        let in_use = snapshot_from_cache(&serde_json::from_str(CACHE).unwrap());
+        let on_disk = landofile("services:\n  cache:\n    type: redis:7\n  search:\n    type: elasticsearch:8\n");
+        assert_eq!(compare_snapshots(&in_use, &on_disk), vec![
+            ConfigDifference::OnlyOnDisk("search".to_string()),
+            ConfigDifference::OnlyInUse("appserver".to_string()),
+            ConfigDifference::OnlyInUse("database".to_string()),
+            ConfigDifference::OnlyInUse("mailhog".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn containers_are_inspected_through_docker() {
+        let runner = StubRunner::new()
+            .respond_ok("3f2a\n\n9c1b\n")
+            .respond_ok(INSPECT);
+        let inspect = inspect_containers(&runner, "My_Blog").unwrap();
+        assert_eq!(inspect.as_array().map(Vec::len), Some(2));
+        let calls = runner.calls();
+        assert_eq!(calls[0].args, to_args(&["ps", "-a", "-q", "--filter", "label=com.docker.compose.project=myblog"]));
+        assert_eq!(calls[1].args, to_args(&["inspect", "3f2a", "9c1b"]));
+
+        // Sin contenedores no se llega a `docker inspect`
+        let runner = StubRunner::new().respond_ok("");
+        assert_eq!(inspect_containers(&runner, "blog"), Ok(JsonValue::Array(Vec::new())));
+        assert_eq!(runner.calls().len(), 1);
+
+        let runner = StubRunner::new().respond_err("Cannot connect to the Docker daemon");
+        assert_eq!(inspect_containers(&runner, "blog"), Err("docker ps falló: Cannot connect to the Docker daemon".to_string()));
+    }
+
+    #[test]
+    fn app_names_match_the_cache_file() {
+        assert_eq!(app_machine_name("My Blog_2"), "my-blog-2");
+        assert_eq!(app_machine_name("--shop--"), "shop");
+    }
+}
@@ -0,0 +1,321 @@
+use serde_json::Value;
+
+// La salida de `mongosh` y del shell `mongo` antiguo no siempre es JSON: trae
+// claves sin comillas, cadenas con comillas simples y constructores como
+// ObjectId("...") o ISODate("..."). Se traduce a EJSON extendido
+// (`{"$oid": "..."}`, `{"$date": "..."}`) para poder parsearla con serde_json.
+
+// Documentos de la salida del shell: cada `{`/`[` al principio de línea abre
+// uno que termina al cerrarse sus llaves. Lo que hay entre ellos (avisos del
+// shell, `Type "it" for more`) se ignora. `None` si alguno no llega a cerrarse.
+pub fn split_documents(text: &str) -> Option<Vec<&str>> {
+    let mut documents = Vec::new();
+    let mut start = None;
+    let mut depth = 0usize;
+    let mut quote: Option<char> = None;
+    let mut escaped = false;
+    let mut line_start = true;
+
+    for (offset, c) in text.char_indices() {
+        if let Some(q) = quote {
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == q {
+                quote = None;
+            }
+            continue;
+        }
+
+        match c {
+            '{' | '[' if depth > 0 || line_start => {
+                if depth == 0 {
+                    start = Some(offset);
+                }
+                depth += 1;
+            }
+            '}' | ']' if depth > 0 => {
+                depth -= 1;
+                if depth == 0 {
+                    if let Some(begin) = start.take() {
+                        documents.push(&text[begin..offset + c.len_utf8()]);
+                    }
+                }
+            }
+            '"' | '\'' if depth > 0 => quote = Some(c),
+            _ => {}
+        }
+
+        if c == '\n' {
+            line_start = true;
+        } else if !c.is_whitespace() {
+            line_start = false;
+        }
+    }
+
+    if depth > 0 { None } else { Some(documents) }
+}
+
+pub fn normalize_relaxed_ejson(text: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut out = String::with_capacity(text.len());
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        if c == '"' || c == '\'' {
+            let (value, next) = read_string(&chars, i);
+            out.push_str(&json_string(&value));
+            i = next;
+        } else if c.is_ascii_digit() || (c == '-' && chars.get(i + 1).is_some_and(char::is_ascii_digit)) {
+            while i < chars.len() && (chars[i].is_ascii_alphanumeric() || matches!(chars[i], '.' | '-' | '+')) {
+                out.push(chars[i]);
+                i += 1;
+            }
+        } else if c.is_alphabetic() || c == '_' || c == '$' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_' || chars[i] == '$') {
+                i += 1;
+            }
+            let word: String = chars[start..i].iter().collect();
+            let next = skip_whitespace(&chars, i);
+            if word == "new" {
+                i = next;
+            } else if chars.get(next) == Some(&'(') {
+                let (args, end) = read_args(&chars, next);
+                out.push_str(&constructor(&word, &args));
+                i = end;
+            } else if chars.get(next) == Some(&':') {
+                out.push_str(&json_string(&word));
+            } else {
+                out.push_str(&match word.as_str() {
+                    "true" | "false" | "null" => word.clone(),
+                    "undefined" => "null".to_string(),
+                    // NaN, Infinity... no existen en JSON: se conservan como texto
+                    _ => json_string(&word),
+                });
+            }
+        } else {
+            out.push(c);
+            i += 1;
+        }
+    }
+    out
+}
+
+// Documentos parseados de la salida del shell. Un único array de documentos
+// (lo que imprime `find().toArray()`) se separa en sus elementos.
+pub fn parse_documents(text: &str) -> Option<Vec<Value>> {
+    let pieces = split_documents(text)?;
+    if pieces.is_empty() {
+        return None;
+    }
+
+    let mut documents = Vec::new();
+    for piece in pieces {
+        documents.push(serde_json::from_str::<Value>(&normalize_relaxed_ejson(piece)).ok()?);
+    }
+    if let [Value::Array(items)] = documents.as_slice() {
+        if items.iter().any(Value::is_object) {
+            return Some(items.clone());
+        }
+    }
+    Some(documents)
+}
+
+fn json_string(value: &str) -> String {
+    serde_json::to_string(value).unwrap_or_else(|_| "\"\"".to_string())
+}
+
+fn skip_whitespace(chars: &[char], mut i: usize) -> usize {
+    while i < chars.len() && chars[i].is_whitespace() {
+        i += 1;
+    }
+    i
+}
+
+// Cadena entre comillas simples o dobles a partir de `start`; devuelve el
+// contenido sin escapes y la posición siguiente a la comilla de cierre
+fn read_string(chars: &[char], start: usize) -> (String, usize) {
+    let quote = chars[start];
+    let mut value = String::new();
+    let mut i = start + 1;
+    while i < chars.len() {
+        let c = chars[i];
+        if c == '\\' {
+            if let Some(&escaped) = chars.get(i + 1) {
+                match escaped {
+                    'n' => value.push('\n'),
+                    't' => value.push('\t'),
+                    'r' => value.push('\r'),
+                    'u' => {
+                        let hex: String = chars.iter().skip(i + 2).take(4).collect();
+                        if let Some(decoded) = u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32) {
+                            value.push(decoded);
+                            i += 4;
+                        }
+                    }
+                    other => value.push(other),
+                }
+                i += 2;
+                continue;
+            }
+        } else if c == quote {
+            return (value, i + 1);
+        }
+        value.push(c);
+        i += 1;
+    }
+    (value, i)
+}
+
+// Argumentos de `Nombre(a, b)` separados por las comas de primer nivel;
+// devuelve el texto de cada uno y la posición siguiente al `)`
+fn read_args(chars: &[char], open: usize) -> (Vec<String>, usize) {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut depth = 0usize;
+    let mut i = open + 1;
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            '"' | '\'' => {
+                let (_, next) = read_string(chars, i);
+                current.extend(&chars[i..next]);
+                i = next;
+                continue;
+            }
+            '(' | '{' | '[' => depth += 1,
+            ')' if depth == 0 => {
+                if !current.trim().is_empty() {
+                    args.push(current.trim().to_string());
+                }
+                return (args, i + 1);
+            }
+            ')' | '}' | ']' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                args.push(current.trim().to_string());
+                current.clear();
+                i += 1;
+                continue;
+            }
+            _ => {}
+        }
+        current.push(c);
+        i += 1;
+    }
+    (args, i)
+}
+
+// Texto de un argumento: sin comillas si es una cadena
+fn arg_text(arg: &str) -> String {
+    let chars: Vec<char> = arg.chars().collect();
+    match chars.first() {
+        Some('"') | Some('\'') => read_string(&chars, 0).0,
+        _ => arg.to_string(),
+    }
+}
+
+// Número tal cual si lo es; si no, como cadena
+fn arg_number(arg: &str) -> String {
+    let text = arg_text(arg);
+    if text.parse::<f64>().is_ok() { text } else { json_string(&text) }
+}
+
+fn constructor(name: &str, args: &[String]) -> String {
+    let first = args.first().map(String::as_str).unwrap_or_default();
+    match name {
+        "ObjectId" => format!("{{\"$oid\": {}}}", json_string(&arg_text(first))),
+        "ISODate" | "Date" if args.is_empty() => "{\"$date\": null}".to_string(),
+        "ISODate" | "Date" => format!("{{\"$date\": {}}}", json_string(&arg_text(first))),
+        "NumberLong" | "Long" => format!("{{\"$numberLong\": {}}}", json_string(&arg_text(first))),
+        "NumberDecimal" | "Decimal128" => format!("{{\"$numberDecimal\": {}}}", json_string(&arg_text(first))),
+        "NumberInt" | "Int32" | "Double" => arg_number(first),
+        "Timestamp" if args.len() == 2 => format!("{{\"$timestamp\": {{\"t\": {}, \"i\": {}}}}}", arg_number(&args[0]), arg_number(&args[1])),
+        "Timestamp" => format!("{{\"$timestamp\": {}}}", normalize_relaxed_ejson(first)),
+        "BinData" if args.len() == 2 => {
+            let subtype = arg_text(&args[0]).parse::<u8>().unwrap_or_default();
+            format!("{{\"$binary\": {{\"base64\": {}, \"subType\": \"{:02x}\"}}}}", json_string(&arg_text(&args[1])), subtype)
+        }
+        "UUID" => format!("{{\"$uuid\": {}}}", json_string(&arg_text(first))),
+        "DBRef" if args.len() >= 2 => format!("{{\"$ref\": {}, \"$id\": {}}}", json_string(&arg_text(&args[0])), normalize_relaxed_ejson(&args[1])),
+        "MinKey" => "{\"$minKey\": 1}".to_string(),
+        "MaxKey" => "{\"$maxKey\": 1}".to_string(),
+        // Constructor desconocido: se conserva como texto
+        _ => json_string(&format!("{}({})", name, args.join(", "))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn parse(text: &str) -> Value {
+        serde_json::from_str(&normalize_relaxed_ejson(text)).unwrap()
+    }
+
+    #[test]
+    fn object_ids_and_dates() {
+        assert_eq!(
+            parse("{ _id: ObjectId('64b7f0c2a1e4c3b2a1f0e9d8'), created: ISODate(\"2024-01-02T03:04:05.000Z\") }"),
+            json!({"_id": {"$oid": "64b7f0c2a1e4c3b2a1f0e9d8"}, "created": {"$date": "2024-01-02T03:04:05.000Z"}}),
+        );
+        assert_eq!(parse("{ at: new Date('2024-01-02'), now: Date() }"), json!({"at": {"$date": "2024-01-02"}, "now": {"$date": null}}));
+    }
+
+    #[test]
+    fn numeric_wrappers() {
+        assert_eq!(
+            parse("{ big: NumberLong(\"9007199254740993\"), small: NumberLong(42), price: NumberDecimal('9.99'), n: NumberInt(7), neg: -1.5e3 }"),
+            json!({
+                "big": {"$numberLong": "9007199254740993"},
+                "small": {"$numberLong": "42"},
+                "price": {"$numberDecimal": "9.99"},
+                "n": 7,
+                "neg": -1500.0,
+            }),
+        );
+        assert_eq!(parse("{ ts: Timestamp({ t: 1700000000, i: 1 }) }"), json!({"ts": {"$timestamp": {"t": 1700000000, "i": 1}}}));
+        assert_eq!(parse("{ ts: Timestamp(1700000000, 2) }"), json!({"ts": {"$timestamp": {"t": 1700000000, "i": 2}}}));
+    }
+
+    #[test]
+    fn relaxed_syntax() {
+        assert_eq!(
+            parse("{ name: 'O\\'Neil', tags: [ 'a', \"b\" ], gone: undefined, ratio: NaN, ok: true, note: \"línea\\n\" }"),
+            json!({"name": "O'Neil", "tags": ["a", "b"], "gone": null, "ratio": "NaN", "ok": true, "note": "línea\n"}),
+        );
+        assert_eq!(parse("{ bin: BinData(4, 'AAEC'), odd: Code('x, y') }"), json!({
+            "bin": {"$binary": {"base64": "AAEC", "subType": "04"}},
+            "odd": "Code('x, y')",
+        }));
+    }
+
+    #[test]
+    fn shell_output_is_split_into_documents() {
+        let output = "Current Mongosh Log ID: 65a\n\
+            { _id: ObjectId('a1'), note: 'has } and { inside' }\n\
+            {\n  _id: ObjectId('a2'),\n  items: [ 1, 2 ]\n}\n\
+            Type \"it\" for more\n";
+        let documents = parse_documents(output).unwrap();
+        assert_eq!(documents, vec![
+            json!({"_id": {"$oid": "a1"}, "note": "has } and { inside"}),
+            json!({"_id": {"$oid": "a2"}, "items": [1, 2]}),
+        ]);
+    }
+
+    #[test]
+    fn a_single_array_is_unwrapped() {
+        let documents = parse_documents("[\n  { _id: 1 },\n  { _id: 2 }\n]\n").unwrap();
+        assert_eq!(documents, vec![json!({"_id": 1}), json!({"_id": 2})]);
+        assert_eq!(parse_documents("[ 1, 2 ]").unwrap(), vec![json!([1, 2])]);
+    }
+
+    #[test]
+    fn unclosed_or_empty_output() {
+        assert_eq!(split_documents("{ _id: 1,\n  name: 'x'"), None);
+        assert_eq!(parse_documents("switched to db blog"), None);
+    }
+}
@@ -0,0 +1,149 @@
+use std::sync::LazyLock;
+
+use regex::{Regex, RegexBuilder};
+
+use crate::models::errors::{ErrorFix, LandoError, LandoErrorKind};
+
+struct ErrorPattern {
+    kind: LandoErrorKind,
+    pattern: &'static str,
+    summary: &'static str,
+    suggestion: &'static str,
+    fix: Option<ErrorFix>,
+}
+
+// Tabla de patrones, en orden de prioridad (gana el primero que coincide).
+// Para reconocer un fallo nuevo basta con añadir una entrada.
+const ERROR_PATTERNS: &[ErrorPattern] = &[
+    ErrorPattern {
+        kind: LandoErrorKind::DockerNotRunning,
+        pattern: r"cannot connect to the docker daemon|is the docker daemon running|docker desktop is not running|docker is not running|error during connect",
+        summary: "Docker no está en marcha",
+        suggestion: "Inicia Docker (o Docker Desktop) y vuelve a intentarlo.",
+        fix: Some(ErrorFix::OpenDocker),
+    },
+    ErrorPattern {
+        kind: LandoErrorKind::DiskFull,
+        pattern: r"no space left on device",
+        summary: "No queda espacio en disco",
+        suggestion: "Libera espacio, por ejemplo con `docker system prune`, y reintenta.",
+        fix: None,
+    },
+    ErrorPattern {
+        kind: LandoErrorKind::PortInUse,
+        pattern: r"port is already allocated|address already in use|ports are not available",
+        summary: "Un puerto ya está en uso",
+        suggestion: "Detén la otra app o proceso que usa ese puerto, o cámbialo en .lando.yml.",
+        fix: None,
+    },
+    ErrorPattern {
+        kind: LandoErrorKind::DatabaseAuth,
+        pattern: r"access denied for user|password authentication failed|authentication failed|role .+ does not exist",
+        summary: "Credenciales de base de datos rechazadas",
+        suggestion: "Revisa el usuario y la contraseña del perfil de conexión.",
+        fix: None,
+    },
+    ErrorPattern {
+        kind: LandoErrorKind::AppNotRunning,
+        pattern: r"app is not running|is not running\.? ?(please )?run .?lando start|no such container|container .+ is not running|service .+ is not running",
+        summary: "La app no está en marcha",
+        suggestion: "Inicia la app con `lando start`.",
+        fix: Some(ErrorFix::StartApp),
+    },
+    ErrorPattern {
+        kind: LandoErrorKind::MissingPlugin,
+        pattern: r"could not find plugin|plugin .+ (is )?not (found|installed)|is not a (valid )?lando command|unknown command",
+        summary: "Falta un plugin o comando de lando",
+        suggestion: "Instala el plugin necesario (`lando plugin-add <plugin>`) o revisa el tooling de .lando.yml.",
+        fix: None,
+    },
+];
+
+static COMPILED_PATTERNS: LazyLock<Vec<(Regex, &'static ErrorPattern)>> = LazyLock::new(|| {
+    ERROR_PATTERNS.iter()
+        .map(|entry| {
+            let regex = RegexBuilder::new(entry.pattern)
+                .case_insensitive(true)
+                .build()
+                .expect("patrón de error inválido");
+            (regex, entry)
+        })
+        .collect()
+});
+
+// Los errores desconocidos conservan el texto tal cual, sin sugerencia
+pub fn classify_error(raw: &str) -> LandoError {
+    match COMPILED_PATTERNS.iter().find(|(regex, _)| regex.is_match(raw)) {
+        Some((_, entry)) => LandoError {
+            kind: entry.kind,
+            raw: raw.to_string(),
+            summary: entry.summary.to_string(),
+            suggestion: Some(entry.suggestion.to_string()),
+            fix: entry.fix,
+        },
+        None => LandoError {
+            kind: LandoErrorKind::Unknown,
+            raw: raw.to_string(),
+            summary: "Error".to_string(),
+            suggestion: None,
+            fix: None,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Salida de error real de lando, docker y los clientes de BD
+    const STDERR_CORPUS: &[(&str, LandoErrorKind)] = &[
+        ("Cannot connect to the Docker daemon at unix:///var/run/docker.sock. Is the docker daemon running?", LandoErrorKind::DockerNotRunning),
+        ("error during connect: This error may indicate that the docker daemon is not running.", LandoErrorKind::DockerNotRunning),
+        ("ERROR: failed to register layer: write /usr/lib/x86_64-linux-gnu/libLLVM.so: no space left on device", LandoErrorKind::DiskFull),
+        ("Error response from daemon: driver failed programming external connectivity on endpoint blog_appserver_1: Bind for 0.0.0.0:80 failed: port is already allocated", LandoErrorKind::PortInUse),
+        ("Error starting userland proxy: listen tcp4 0.0.0.0:3306: bind: address already in use", LandoErrorKind::PortInUse),
+        ("ERROR 1045 (28000): Access denied for user 'lamp'@'172.18.0.4' (using password: YES)", LandoErrorKind::DatabaseAuth),
+        ("psql: error: connection to server at \"database\" (172.18.0.3), port 5432 failed: FATAL:  password authentication failed for user \"postgres\"", LandoErrorKind::DatabaseAuth),
+        ("psql: error: FATAL:  role \"blog\" does not exist", LandoErrorKind::DatabaseAuth),
+        ("Error: No such container: blog_database_1", LandoErrorKind::AppNotRunning),
+        ("Error response from daemon: Container 3f2a is not running", LandoErrorKind::AppNotRunning),
+        ("Looks like your app is not running! Please run lando start", LandoErrorKind::AppNotRunning),
+        ("drush is not a lando command. Run lando for a list of available commands.", LandoErrorKind::MissingPlugin),
+        ("Could not find plugin @lando/platformsh", LandoErrorKind::MissingPlugin),
+        ("error: unknown command 'doctor'", LandoErrorKind::MissingPlugin),
+    ];
+
+    #[test]
+    fn stderr_corpus_is_classified() {
+        for (stderr, kind) in STDERR_CORPUS {
+            let error = classify_error(stderr);
+            assert_eq!(error.kind, *kind, "{}", stderr);
+            assert_eq!(error.raw, *stderr);
+            assert!(error.suggestion.is_some());
+        }
+    }
+
+    #[test]
+    fn fixes_follow_the_kind() {
+        assert_eq!(classify_error(STDERR_CORPUS[0].0).fix, Some(ErrorFix::OpenDocker));
+        assert_eq!(classify_error("Error: No such container: blog_database_1").fix, Some(ErrorFix::StartApp));
+        assert_eq!(classify_error("port is already allocated").fix, None);
+    }
+
+    #[test]
+    fn first_matching_pattern_wins() {
+        // Docker parado también hace fallar la conexión a la BD
+        let stderr = "Cannot connect to the Docker daemon\nERROR 1045 (28000): Access denied for user 'root'@'localhost'";
+        assert_eq!(classify_error(stderr).kind, LandoErrorKind::DockerNotRunning);
+    }
+
+    #[test]
+    fn unknown_errors_keep_the_text() {
+        let error = classify_error("ERROR 1064 (42000): You have an error in your SQL syntax");
+        assert_eq!(error.kind, LandoErrorKind::Unknown);
+        assert_eq!(error.summary, "Error");
+        assert_eq!(error.raw, "ERROR 1064 (42000): You have an error in your SQL syntax");
+        assert_eq!(error.suggestion, None);
+        assert_eq!(error.fix, None);
+    }
+}
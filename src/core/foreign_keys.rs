@@ -0,0 +1,264 @@
+use std::path::PathBuf;
+use std::sync::mpsc::Sender;
+
+use crate::core::commands::*;
+use crate::core::result_format::{parse_result_set, ParsedResultSet};
+use crate::models::commands::LandoCommandOutcome;
+use crate::models::lando::LandoService;
+use crate::ui::database::{DatabaseUI, ForeignKey, ForeignKeyInfo, PendingRequest, TableNavStep};
+
+// Pasos que recuerda el botón "Atrás" del navegador de tablas
+pub const TABLE_NAV_LIMIT: usize = 20;
+
+// Cada motor guarda las claves foráneas en un catálogo distinto. Todas las
+// consultas devuelven las columnas table_name, column_name, ref_table y ref_column.
+pub trait ForeignKeyDialect {
+    fn foreign_keys_query(&self) -> String;
+
+    fn parse_foreign_keys(&self, set: &ParsedResultSet) -> Vec<ForeignKey> {
+        (0..set.rows.len())
+            .filter_map(|row| {
+                Some(ForeignKey {
+                    table: set.value(row, "table_name")?.trim().to_string(),
+                    column: set.value(row, "column_name")?.trim().to_string(),
+                    ref_table: set.value(row, "ref_table")?.trim().to_string(),
+                    ref_column: set.value(row, "ref_column")?.trim().to_string(),
+                })
+            })
+            .filter(|fk| !fk.table.is_empty() && !fk.ref_table.is_empty())
+            .collect()
+    }
+}
+
+pub struct MySqlForeignKeys;
+pub struct PostgresForeignKeys;
+pub struct SqliteForeignKeys;
+
+pub fn foreign_key_dialect(db_type: &str) -> &'static dyn ForeignKeyDialect {
+    match db_type.to_lowercase().as_str() {
+        "postgresql" | "postgres" => &PostgresForeignKeys,
+        "sqlite" => &SqliteForeignKeys,
+        _ => &MySqlForeignKeys,
+    }
+}
+
+impl ForeignKeyDialect for MySqlForeignKeys {
+    fn foreign_keys_query(&self) -> String {
+        "SELECT TABLE_NAME AS table_name, COLUMN_NAME AS column_name, \
+         REFERENCED_TABLE_NAME AS ref_table, REFERENCED_COLUMN_NAME AS ref_column \
+         FROM information_schema.KEY_COLUMN_USAGE \
+         WHERE TABLE_SCHEMA = DATABASE() AND REFERENCED_TABLE_NAME IS NOT NULL;".to_string()
+    }
+}
+
+impl ForeignKeyDialect for PostgresForeignKeys {
+    fn foreign_keys_query(&self) -> String {
+        "SELECT cl.relname AS table_name, att.attname AS column_name, \
+         fcl.relname AS ref_table, fatt.attname AS ref_column \
+         FROM pg_constraint con \
+         JOIN pg_class cl ON cl.oid = con.conrelid \
+         JOIN pg_class fcl ON fcl.oid = con.confrelid \
+         JOIN pg_namespace ns ON ns.oid = cl.relnamespace \
+         CROSS JOIN LATERAL unnest(con.conkey, con.confkey) AS k(attnum, fattnum) \
+         JOIN pg_attribute att ON att.attrelid = con.conrelid AND att.attnum = k.attnum \
+         JOIN pg_attribute fatt ON fatt.attrelid = con.confrelid AND fatt.attnum = k.fattnum \
+         WHERE con.contype = 'f' AND ns.nspname = current_schema();".to_string()
+    }
+}
+
+impl ForeignKeyDialect for SqliteForeignKeys {
+    fn foreign_keys_query(&self) -> String {
+        "SELECT m.name AS table_name, p.\"from\" AS column_name, \
+         p.\"table\" AS ref_table, p.\"to\" AS ref_column \
+         FROM sqlite_master m JOIN pragma_foreign_key_list(m.name) p \
+         WHERE m.type = 'table';".to_string()
+    }
+
+    // `REFERENCES users` sin columna apunta a la clave primaria: el pragma deja `to` a NULL
+    fn parse_foreign_keys(&self, set: &ParsedResultSet) -> Vec<ForeignKey> {
+        (0..set.rows.len())
+            .filter_map(|row| {
+                let ref_column = set.value(row, "ref_column").map(str::trim).unwrap_or_default();
+                Some(ForeignKey {
+                    table: set.value(row, "table_name")?.trim().to_string(),
+                    column: set.value(row, "column_name")?.trim().to_string(),
+                    ref_table: set.value(row, "ref_table")?.trim().to_string(),
+                    ref_column: match ref_column {
+                        "" | "NULL" => "rowid".to_string(),
+                        column => column.to_string(),
+                    },
+                })
+            })
+            .filter(|fk| !fk.table.is_empty() && !fk.ref_table.is_empty())
+            .collect()
+    }
+}
+
+// Navegación por claves foráneas en el navegador de tablas
+impl DatabaseUI {
+    pub fn load_foreign_keys(&mut self, service: &LandoService, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>) {
+        let request_id = next_request_id();
+        self.pending_requests.insert(request_id, PendingRequest::ForeignKeys { db_type: service.db_engine().to_string() });
+        run_db_query(
+            sender.clone(),
+            project_path.clone(),
+            self.db_connection(service),
+            foreign_key_dialect(service.db_engine()).foreign_keys_query(),
+            request_id,
+        );
+    }
+
+    pub fn handle_foreign_keys_result(&mut self, output: &Result<String, String>, db_type: &str) {
+        self.diagram.loading = false;
+        match output {
+            Ok(text) => {
+                let foreign_keys = parse_result_set(text)
+                    .map(|set| foreign_key_dialect(db_type).parse_foreign_keys(&set))
+                    .unwrap_or_default();
+                for table in &mut self.tables {
+                    table.foreign_keys = foreign_keys.iter()
+                        .filter(|fk| fk.table == table.name)
+                        .map(|fk| ForeignKeyInfo {
+                            column: fk.column.clone(),
+                            ref_table: fk.ref_table.clone(),
+                            ref_column: fk.ref_column.clone(),
+                        })
+                        .collect();
+                }
+                self.diagram.foreign_keys = foreign_keys;
+            }
+            Err(msg) => self.diagram.error = Some(msg.clone()),
+        }
+    }
+
+    pub fn current_table_foreign_keys(&self) -> &[ForeignKeyInfo] {
+        self.tables.iter()
+            .find(|t| t.name == self.current_table)
+            .map(|t| t.foreign_keys.as_slice())
+            .unwrap_or(&[])
+    }
+
+    // Abre la tabla referenciada filtrada por el valor de la celda
+    pub fn follow_foreign_key(
+        &mut self,
+        service: &LandoService,
+        project_path: &PathBuf,
+        sender: &Sender<LandoCommandOutcome>,
+        is_loading: &mut bool,
+        fk: &ForeignKeyInfo,
+        value: &str,
+    ) {
+        if *is_loading { return; }
+
+        if self.table_nav.len() >= TABLE_NAV_LIMIT {
+            self.table_nav.remove(0);
+        }
+        self.table_nav.push(TableNavStep {
+            table: self.current_table.clone(),
+            page: self.table_page,
+            filter: self.table_filter.clone(),
+            target: format!("{} #{}", fk.ref_table, value),
+        });
+
+        let db_type = service.db_engine();
+        self.table_filter = format!(
+            "{} = {}",
+            self.quote_identifier(db_type, &fk.ref_column),
+            self.sql_string_literal(db_type, value),
+        );
+        self.current_table = fk.ref_table.clone();
+        self.table_page = 0;
+        self.table_selection.clear();
+        self.load_table_data(service, project_path, sender, is_loading);
+    }
+
+    pub fn navigate_back(&mut self, service: &LandoService, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>, is_loading: &mut bool) {
+        if *is_loading { return; }
+        let Some(step) = self.table_nav.pop() else {
+            return;
+        };
+        self.current_table = step.table;
+        self.table_page = step.page;
+        self.table_filter = step.filter;
+        self.table_selection.clear();
+        self.load_table_data(service, project_path, sender, is_loading);
+    }
+
+    // "orders → users #42 → countries #3"
+    pub fn table_breadcrumb(&self) -> Option<String> {
+        let first = self.table_nav.first()?;
+        let mut parts = vec![first.table.clone()];
+        parts.extend(self.table_nav.iter().map(|step| step.target.clone()));
+        Some(parts.join(" → "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fk(table: &str, column: &str, ref_table: &str, ref_column: &str) -> ForeignKey {
+        ForeignKey {
+            table: table.to_string(),
+            column: column.to_string(),
+            ref_table: ref_table.to_string(),
+            ref_column: ref_column.to_string(),
+        }
+    }
+
+    fn parse(dialect: &str, text: &str) -> Vec<ForeignKey> {
+        foreign_key_dialect(dialect).parse_foreign_keys(&parse_result_set(text).unwrap())
+    }
+
+    #[test]
+    fn mysql_batch_output() {
+        let text = "table_name\tcolumn_name\tref_table\tref_column\n\
+            orders\tuser_id\tusers\tid\n\
+            users\tcountry_id\tcountries\tid\n";
+        assert_eq!(parse("mariadb", text), vec![
+            fk("orders", "user_id", "users", "id"),
+            fk("users", "country_id", "countries", "id"),
+        ]);
+    }
+
+    #[test]
+    fn postgres_aligned_output() {
+        let text = " table_name | column_name | ref_table | ref_column \n\
+            ------------+-------------+-----------+------------\n \
+            orders     | user_id     | users     | id\n \
+            order_item | order_id    | orders    | id\n\
+            (2 rows)\n";
+        assert_eq!(parse("postgres", text), vec![
+            fk("orders", "user_id", "users", "id"),
+            fk("order_item", "order_id", "orders", "id"),
+        ]);
+    }
+
+    #[test]
+    fn sqlite_null_target_points_to_rowid() {
+        let text = "table_name\tcolumn_name\tref_table\tref_column\n\
+            orders\tuser_id\tusers\tid\n\
+            orders\tshop_id\tshops\tNULL\n\
+            tags\tpost_id\tposts\t\n";
+        assert_eq!(parse("sqlite", text), vec![
+            fk("orders", "user_id", "users", "id"),
+            fk("orders", "shop_id", "shops", "rowid"),
+            fk("tags", "post_id", "posts", "rowid"),
+        ]);
+    }
+
+    #[test]
+    fn rows_without_tables_are_skipped() {
+        let text = "table_name\tcolumn_name\tref_table\tref_column\n\
+            orders\tuser_id\t\tid\n\
+            \tuser_id\tusers\tid\n\
+            orders\tuser_id\tusers\tid\n";
+        assert_eq!(parse("mysql", text), vec![fk("orders", "user_id", "users", "id")]);
+        assert_eq!(parse("sqlite", text), vec![fk("orders", "user_id", "users", "id")]);
+    }
+
+    #[test]
+    fn missing_columns_yield_nothing() {
+        assert!(parse("mysql", "table_name\tcolumn_name\norders\tuser_id\n").is_empty());
+    }
+}
@@ -0,0 +1,94 @@
+use std::path::PathBuf;
+use std::sync::atomic::AtomicBool;
+use std::sync::mpsc::Sender;
+use std::sync::Arc;
+use crate::core::commands::*;
+use crate::models::commands::LandoCommandOutcome;
+use crate::models::lando::LandoService;
+use crate::ui::generic::{GenericServiceUI, RunningShellCommand};
+
+// Límite de la salida conservada por tarjeta, para que `ps aux` repetido no crezca sin fin
+const SCROLLBACK_LIMIT: usize = 200_000;
+
+impl GenericServiceUI {
+    pub fn run_command(&mut self, service: &LandoService, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>, command: &str) {
+        let command = command.trim();
+        if command.is_empty() || self.running.is_some() {
+            return;
+        }
+
+        if self.history.last().map(String::as_str) != Some(command) {
+            self.history.push(command.to_string());
+        }
+        self.history_cursor = None;
+        self.command_input.clear();
+        self.pending_bytes.clear();
+        self.append_output(&format!("$ {}\n", command));
+
+        let request_id = next_request_id();
+        let cancel = Arc::new(AtomicBool::new(false));
+        self.running = Some(RunningShellCommand { request_id, cancel: cancel.clone() });
+        run_shell_stream_tagged(
+            sender.clone(),
+            project_path.clone(),
+            service.service.clone(),
+            command.to_string(),
+            request_id,
+            cancel,
+        );
+    }
+
+    // ↑ recorre hacia comandos más antiguos, ↓ hacia los recientes hasta volver al campo vacío
+    pub fn recall_history(&mut self, older: bool) {
+        if self.history.is_empty() {
+            return;
+        }
+        let last = self.history.len() - 1;
+        self.history_cursor = match (self.history_cursor, older) {
+            (None, true) => Some(last),
+            (None, false) => None,
+            (Some(i), true) => Some(i.saturating_sub(1)),
+            (Some(i), false) if i < last => Some(i + 1),
+            (Some(_), false) => None,
+        };
+        self.command_input = self.history_cursor
+            .map(|i| self.history[i].clone())
+            .unwrap_or_default();
+    }
+
+    pub fn handle_stream_chunk(&mut self, request_id: u64, chunk: &[u8]) -> bool {
+        if !self.running.as_ref().is_some_and(|r| r.request_id == request_id) {
+            return false;
+        }
+        let text = decode_utf8_stream(&mut self.pending_bytes, chunk);
+        self.append_output(&text);
+        true
+    }
+
+    pub fn handle_stream_finished(&mut self, request_id: u64, result: &Result<(), String>) -> bool {
+        if !self.running.as_ref().is_some_and(|r| r.request_id == request_id) {
+            return false;
+        }
+        self.running = None;
+        if !self.pending_bytes.is_empty() {
+            let rest = String::from_utf8_lossy(&self.pending_bytes).to_string();
+            self.pending_bytes.clear();
+            self.append_output(&rest);
+        }
+        if let Err(msg) = result {
+            self.append_output(&format!("\n{}\n", msg));
+        }
+        true
+    }
+
+    fn append_output(&mut self, text: &str) {
+        self.scrollback.push_str(text);
+        if self.scrollback.len() > SCROLLBACK_LIMIT {
+            let mut cut = self.scrollback.len() - SCROLLBACK_LIMIT;
+            while !self.scrollback.is_char_boundary(cut) {
+                cut += 1;
+            }
+            self.scrollback.drain(..cut);
+        }
+    }
+}
@@ -0,0 +1,165 @@
+use std::path::Path;
+
+use serde_yaml::Value;
+
+use crate::core::db_engine::read_landofile;
+use crate::models::graph::{GraphEdge, GraphEdgeKind, GraphNode, GraphNodeKind, ServiceGraph};
+use crate::models::lando::LandoService;
+
+const PROXY_NODE_PREFIX: &str = "proxy:";
+
+pub fn node_kind(name: &str, service_type: &str) -> GraphNodeKind {
+    let name = name.to_lowercase();
+    let service_type = service_type.to_lowercase();
+    let base = service_type.split(':').next().unwrap_or_default();
+    match base {
+        "mysql" | "mariadb" | "postgres" | "postgresql" | "mongo" | "mongodb" | "mssql" | "sqlite" => GraphNodeKind::Database,
+        "redis" | "memcached" | "varnish" | "solr" | "elasticsearch" => GraphNodeKind::Cache,
+        "node" => GraphNodeKind::Node,
+        "php" | "python" | "ruby" | "go" | "dotnet" | "java" | "tomcat" | "nginx" | "apache" => GraphNodeKind::AppServer,
+        _ if name == "database" => GraphNodeKind::Database,
+        _ if name == "appserver" => GraphNodeKind::AppServer,
+        _ if name == "node" => GraphNodeKind::Node,
+        _ => GraphNodeKind::Other,
+    }
+}
+
+// `depends_on` puede ir en el servicio o en sus `overrides` (docker compose),
+// como lista o como mapa
+fn depends_on(config: &Value) -> Vec<String> {
+    [config.get("depends_on"), config.get("overrides").and_then(|o| o.get("depends_on"))]
+        .into_iter()
+        .flatten()
+        .flat_map(|value| match value {
+            Value::Sequence(items) => items.iter().filter_map(Value::as_str).map(String::from).collect(),
+            Value::Mapping(map) => map.keys().filter_map(Value::as_str).map(String::from).collect(),
+            Value::String(single) => vec![single.clone()],
+            _ => Vec::new(),
+        })
+        .collect()
+}
+
+// Cada ruta es un hostname o un mapa con `hostname`
+fn proxy_routes(config: &Value) -> Vec<(String, String)> {
+    let Some(proxy) = config.get("proxy").and_then(Value::as_mapping) else {
+        return Vec::new();
+    };
+    proxy.iter()
+        .filter_map(|(service, routes)| Some((service.as_str()?.to_string(), routes.as_sequence()?)))
+        .flat_map(|(service, routes)| {
+            routes.iter()
+                .filter_map(|route| route.as_str().or_else(|| route.get("hostname")?.as_str()))
+                .map(move |route| (route.to_string(), service.clone()))
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+fn tooling_for(config: &Value, service: &str) -> Vec<String> {
+    config.get("tooling")
+        .and_then(Value::as_mapping)
+        .map(|tooling| {
+            tooling.iter()
+                .filter(|(_, command)| command.get("service").and_then(Value::as_str) == Some(service))
+                .filter_map(|(name, _)| name.as_str().map(String::from))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+// Una app usa una BD si su configuración menciona el servicio o sus
+// credenciales; con receta, el appserver siempre usa `database`
+fn uses_database(app_config: Option<&Value>, has_recipe: bool, app: &str, database: &LandoService) -> bool {
+    if has_recipe && app == "appserver" && database.service == "database" {
+        return true;
+    }
+    let Some(text) = app_config.and_then(|c| serde_yaml::to_string(c).ok()) else {
+        return false;
+    };
+    let creds = database.creds.as_ref();
+    [Some(&database.service), creds.and_then(|c| c.database.as_ref())]
+        .into_iter()
+        .flatten()
+        .filter(|needle| needle.len() > 2)
+        .any(|needle| text.contains(needle.as_str()))
+}
+
+fn push_edge(edges: &mut Vec<GraphEdge>, from: &str, to: &str, kind: GraphEdgeKind) {
+    if from != to && !edges.iter().any(|e| e.from == from && e.to == to) {
+        edges.push(GraphEdge { from: from.to_string(), to: to.to_string(), kind });
+    }
+}
+
+// Combina `lando info` con el `.lando.yml`. Sin `.lando.yml` legible el grafo
+// tiene solo los nodos de `lando info`.
+pub fn build_service_graph(project_path: &Path, services: &[LandoService]) -> ServiceGraph {
+    let config = read_landofile(project_path);
+    let service_configs = config.get("services").and_then(Value::as_mapping);
+    let service_config = |name: &str| service_configs.and_then(|m| m.get(name));
+
+    let mut graph = ServiceGraph::default();
+    let mut names: Vec<(String, String)> = services.iter()
+        .map(|s| (s.service.clone(), s.db_engine().to_string()))
+        .collect();
+    // Servicios declarados que `lando info` no devolvió (p. ej. aún sin construir)
+    if let Some(configs) = service_configs {
+        for (name, value) in configs {
+            let Some(name) = name.as_str() else { continue };
+            if !names.iter().any(|(n, _)| n == name) {
+                let service_type = value.get("type").and_then(Value::as_str).unwrap_or_default();
+                names.push((name.to_string(), service_type.to_string()));
+            }
+        }
+    }
+
+    for (name, service_type) in &names {
+        graph.nodes.push(GraphNode {
+            id: name.clone(),
+            label: name.clone(),
+            kind: node_kind(name, service_type),
+            service: Some(name.clone()),
+            detail: service_type.clone(),
+            tooling: tooling_for(&config, name),
+        });
+    }
+
+    for (name, _) in &names {
+        for dependency in service_config(name).map(depends_on).unwrap_or_default() {
+            if names.iter().any(|(n, _)| *n == dependency) {
+                push_edge(&mut graph.edges, name, &dependency, GraphEdgeKind::DependsOn);
+            }
+        }
+    }
+
+    for (route, target) in proxy_routes(&config) {
+        if !names.iter().any(|(n, _)| *n == target) {
+            continue;
+        }
+        let id = format!("{}{}", PROXY_NODE_PREFIX, route);
+        if !graph.nodes.iter().any(|n| n.id == id) {
+            graph.nodes.push(GraphNode {
+                id: id.clone(),
+                label: route.clone(),
+                kind: GraphNodeKind::Proxy,
+                service: None,
+                detail: format!("→ {}", target),
+                tooling: Vec::new(),
+            });
+        }
+        push_edge(&mut graph.edges, &id, &target, GraphEdgeKind::Proxy);
+    }
+
+    let has_recipe = config.get("recipe").is_some();
+    let kinds: Vec<(String, GraphNodeKind)> = graph.nodes.iter().map(|n| (n.id.clone(), n.kind)).collect();
+    for database in services.iter().filter(|s| node_kind(&s.service, s.db_engine()) == GraphNodeKind::Database) {
+        for (app, kind) in &kinds {
+            if matches!(kind, GraphNodeKind::AppServer | GraphNodeKind::Node)
+                && uses_database(service_config(app), has_recipe, app, database)
+            {
+                push_edge(&mut graph.edges, app, &database.service, GraphEdgeKind::Database);
+            }
+        }
+    }
+
+    graph
+}
@@ -0,0 +1,96 @@
+use std::sync::mpsc::Sender;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::core::http::{connect, http_status, local_host, plain_http_url, split_url};
+use crate::models::commands::LandoCommandOutcome;
+use crate::models::health::{ProbeResult, ProbeTarget};
+use crate::models::lando::LandoService;
+
+const PROBE_TIMEOUT: Duration = Duration::from_secs(3);
+
+// Conexión externa por TCP y las URLs con GET. El cliente no habla TLS: una
+// URL https se comprueba por su versión http si el proxy la sirve y, si no,
+// solo por TCP.
+pub fn probe_targets(service: &LandoService) -> Vec<ProbeTarget> {
+    let mut targets = Vec::new();
+    if let Some(connection) = &service.external_connection {
+        if let Ok(port) = connection.port.parse::<u16>() {
+            targets.push(ProbeTarget::Tcp { host: local_host(&connection.host).to_string(), port });
+        }
+    }
+    for url in &service.urls {
+        let target = if url.starts_with("http://") {
+            ProbeTarget::Http { url: url.clone() }
+        } else if let Some(url) = plain_http_url(url) {
+            ProbeTarget::Http { url }
+        } else if let Some((host, port, _)) = split_url(url) {
+            ProbeTarget::Tcp { host, port }
+        } else {
+            continue;
+        };
+        if !targets.contains(&target) {
+            targets.push(target);
+        }
+    }
+    targets
+}
+
+fn run_probe(target: ProbeTarget) -> ProbeResult {
+    let started = Instant::now();
+    let outcome = match &target {
+        ProbeTarget::Tcp { host, port } => connect(host, *port, PROBE_TIMEOUT)
+            .map(|_| "conectado".to_string())
+            .map_err(|e| e.to_string()),
+        // Los 5xx cuentan como caído: el puerto responde pero la app no
+        ProbeTarget::Http { url } => http_status(url, PROBE_TIMEOUT).and_then(|code| {
+            if code < 500 { Ok(format!("HTTP {}", code)) } else { Err(format!("HTTP {}", code)) }
+        }),
+    };
+    ProbeResult { target, latency_ms: started.elapsed().as_secs_f64() * 1000.0, outcome }
+}
+
+pub fn run_health_probe(sender: Sender<LandoCommandOutcome>, service: String, targets: Vec<ProbeTarget>) {
+    thread::spawn(move || {
+        let results = targets.into_iter().map(run_probe).collect();
+        let _ = sender.send(LandoCommandOutcome::HealthProbed { service, results });
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::http::serve_once;
+    use crate::core::lando_json::parse_services;
+
+    fn service(json: &str) -> LandoService {
+        parse_services(json.as_bytes()).unwrap().remove(0)
+    }
+
+    #[test]
+    fn targets_from_lando_info() {
+        let appserver = service(r#"[{"service": "appserver", "type": "php:8.2",
+            "urls": ["https://blog.lndo.site/", "http://blog.lndo.site/", "http://localhost:32771", "https://localhost:32772"]}]"#);
+        assert_eq!(probe_targets(&appserver), vec![
+            ProbeTarget::Http { url: "http://blog.lndo.site/".to_string() },
+            ProbeTarget::Http { url: "http://localhost:32771".to_string() },
+            ProbeTarget::Tcp { host: "localhost".to_string(), port: 32772 },
+        ]);
+
+        let database = service(r#"[{"service": "database", "type": "mysql:8.0",
+            "external_connection": {"host": "0.0.0.0", "port": "32773"}}]"#);
+        assert_eq!(probe_targets(&database), vec![ProbeTarget::Tcp { host: "127.0.0.1".to_string(), port: 32773 }]);
+
+        let not_started = service(r#"[{"service": "database", "type": "mysql:8.0",
+            "external_connection": {"host": "127.0.0.1", "port": "not forwarded"}}]"#);
+        assert!(probe_targets(&not_started).is_empty());
+    }
+
+    #[test]
+    fn server_errors_count_as_down() {
+        let up = run_probe(ProbeTarget::Http { url: serve_once(vec!["HTTP/1.1 302 Found\r\n\r\n"]) });
+        assert_eq!(up.outcome, Ok("HTTP 302".to_string()));
+        let down = run_probe(ProbeTarget::Http { url: serve_once(vec!["HTTP/1.1 502 Bad Gateway\r\n\r\n"]) });
+        assert_eq!(down.outcome, Err("HTTP 502".to_string()));
+    }
+}
@@ -0,0 +1,122 @@
+use std::fs;
+
+use serde::Serialize;
+
+use crate::core::backups::format_backup_date;
+use crate::ui::database::DatabaseUI;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HistoryExportFormat {
+    Sql,  // Cada consulta precedida de un comentario con su fecha
+    Json,
+}
+
+impl HistoryExportFormat {
+    pub const ALL: [HistoryExportFormat; 2] = [HistoryExportFormat::Sql, HistoryExportFormat::Json];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            HistoryExportFormat::Sql => "SQL (.sql)",
+            HistoryExportFormat::Json => "JSON (.json)",
+        }
+    }
+
+    fn extension(self) -> &'static str {
+        match self {
+            HistoryExportFormat::Sql => "sql",
+            HistoryExportFormat::Json => "json",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HistoryExportEntry {
+    pub query: String,
+    pub timestamp: Option<u64>, // Segundos Unix de la última ejecución
+    pub executed_at: Option<String>,
+    pub execution_time_ms: Option<f64>, // Del último resultado terminado
+    pub has_error: Option<bool>,
+}
+
+// Consultas en orden de ejecución, la más antigua primero
+pub fn history_to_sql(entries: &[HistoryExportEntry]) -> String {
+    let mut out = format!("-- Historial de consultas exportado desde Lando GUI ({} consultas)\n", entries.len());
+    for entry in entries {
+        out.push('\n');
+        let mut comment = entry.executed_at.clone().unwrap_or_else(|| "Sin fecha".to_string());
+        if let Some(ms) = entry.execution_time_ms {
+            comment.push_str(&format!(" · {:.2} ms", ms));
+        }
+        if entry.has_error == Some(true) {
+            comment.push_str(" · con error");
+        }
+        out.push_str(&format!("-- {}\n", comment));
+
+        let query = entry.query.trim();
+        out.push_str(query);
+        if !query.ends_with(';') {
+            out.push(';');
+        }
+        out.push('\n');
+    }
+    out
+}
+
+pub fn history_to_json(entries: &[HistoryExportEntry]) -> String {
+    serde_json::to_string_pretty(entries).unwrap_or_default()
+}
+
+impl DatabaseUI {
+    // Entradas del historial que se exportan. Las que solo tienen un resultado
+    // "Ejecutando..." todavía no cuentan; con `selected_only`, solo las marcadas.
+    pub fn history_export_entries(&self, selected_only: bool) -> Vec<HistoryExportEntry> {
+        self.query_history.iter()
+            .filter(|query| !query.trim().is_empty())
+            .filter(|query| !selected_only || self.history_selection.contains(*query))
+            .filter_map(|query| {
+                let finished = self.query_results.iter()
+                    .rev()
+                    .find(|r| r.query == *query && r.request_id.is_none());
+                let pending = self.query_results.iter().any(|r| r.query == *query && r.request_id.is_some());
+                if finished.is_none() && pending {
+                    return None;
+                }
+
+                let timestamp = self.history_timestamp(query);
+                Some(HistoryExportEntry {
+                    query: query.clone(),
+                    timestamp,
+                    executed_at: timestamp.map(format_backup_date),
+                    execution_time_ms: finished.map(|r| r.execution_time),
+                    has_error: finished.map(|r| r.has_error),
+                })
+            })
+            .collect()
+    }
+
+    pub fn export_history(&mut self, format: HistoryExportFormat, selected_only: bool) {
+        let entries = self.history_export_entries(selected_only);
+        if entries.is_empty() {
+            self.history_export_status = Some(Err("No hay consultas terminadas que exportar".to_string()));
+            return;
+        }
+
+        let extension = format.extension();
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter(format.label(), &[extension])
+            .set_file_name(format!("historial.{}", extension))
+            .save_file()
+        else {
+            return;
+        };
+
+        let content = match format {
+            HistoryExportFormat::Sql => history_to_sql(&entries),
+            HistoryExportFormat::Json => history_to_json(&entries),
+        };
+        self.history_export_status = Some(match fs::write(&path, content) {
+            Ok(()) => Ok(format!("{} consultas exportadas a {}", entries.len(), path.display())),
+            Err(e) => Err(format!("No se pudo escribir {}: {}", path.display(), e)),
+        });
+    }
+}
@@ -0,0 +1,184 @@
+use std::io::{self, Read, Write};
+use std::net::{SocketAddr, TcpStream, ToSocketAddrs};
+use std::time::Duration;
+
+// Cliente HTTP mínimo para hablar con los servicios locales (salud, Solr,
+// Elasticsearch). Solo `http://`: se pide HTTP/1.0 para que la respuesta no
+// venga troceada (`chunked`) y se lee hasta que el servidor cierra.
+
+// Una línea de estado más larga no es de un servidor HTTP
+const STATUS_LINE_LIMIT: usize = 1024;
+
+// Respuesta ya leída entera
+#[derive(Debug, Clone)]
+pub struct HttpResponse {
+    pub status: u16,
+    pub body: String,
+}
+
+// `http://host:puerto/ruta` -> (host, puerto, ruta)
+pub fn split_url(url: &str) -> Option<(String, u16, String)> {
+    let (default_port, rest) = if let Some(rest) = url.strip_prefix("http://") {
+        (80, rest)
+    } else if let Some(rest) = url.strip_prefix("https://") {
+        (443, rest)
+    } else {
+        return None;
+    };
+    let (authority, path) = rest.split_once('/').map(|(a, p)| (a, format!("/{}", p))).unwrap_or((rest, "/".to_string()));
+    let (host, port) = match authority.rsplit_once(':') {
+        Some((host, port)) => (host, port.parse().ok()?),
+        None => (authority, default_port),
+    };
+    Some((host.to_string(), port, path))
+}
+
+// El proxy de lando sirve cada dominio por http y https: `https://blog.lndo.site/`
+// se puede consultar como `http://blog.lndo.site/`. Con puerto explícito no,
+// porque ese puerto solo habla TLS.
+pub fn plain_http_url(url: &str) -> Option<String> {
+    let rest = url.strip_prefix("https://")?;
+    let authority = rest.split('/').next().unwrap_or_default();
+    (!authority.is_empty() && !authority.contains(':')).then(|| format!("http://{}", rest))
+}
+
+// Lando publica a veces la conexión externa en `0.0.0.0`
+pub fn local_host(host: &str) -> &str {
+    match host {
+        "" | "0.0.0.0" => "127.0.0.1",
+        host => host,
+    }
+}
+
+pub fn connect(host: &str, port: u16, timeout: Duration) -> io::Result<TcpStream> {
+    let addresses: Vec<SocketAddr> = (host, port).to_socket_addrs()?.collect();
+    let mut last_error = io::Error::new(io::ErrorKind::NotFound, format!("no se pudo resolver {}", host));
+    for address in addresses {
+        match TcpStream::connect_timeout(&address, timeout) {
+            Ok(stream) => return Ok(stream),
+            Err(e) => last_error = e,
+        }
+    }
+    Err(last_error)
+}
+
+// Abre la conexión y envía la petición; el cuerpo siempre como JSON
+fn send_request(method: &str, url: &str, body: Option<&str>, timeout: Duration) -> Result<TcpStream, String> {
+    if url.starts_with("https://") {
+        return Err(format!("HTTPS no está soportado: {}", url));
+    }
+    let (host, port, path) = split_url(url).ok_or_else(|| format!("URL no válida: {}", url))?;
+    let mut stream = connect(&host, port, timeout).map_err(|e| format!("No se pudo conectar con {}:{}: {}", host, port, e))?;
+    stream.set_read_timeout(Some(timeout)).map_err(|e| e.to_string())?;
+    stream.set_write_timeout(Some(timeout)).map_err(|e| e.to_string())?;
+
+    let mut request = format!(
+        "{} {} HTTP/1.0\r\nHost: {}:{}\r\nUser-Agent: lando-gui\r\nAccept: application/json\r\nConnection: close\r\n",
+        method, path, host, port
+    );
+    if let Some(body) = body {
+        request.push_str(&format!("Content-Type: application/json\r\nContent-Length: {}\r\n\r\n{}", body.len(), body));
+    } else {
+        request.push_str("\r\n");
+    }
+    stream.write_all(request.as_bytes()).map_err(|e| e.to_string())?;
+    Ok(stream)
+}
+
+fn parse_status(head: &str) -> Result<u16, String> {
+    head.split_whitespace()
+        .nth(1)
+        .and_then(|code| code.parse().ok())
+        .ok_or_else(|| "respuesta que no es HTTP".to_string())
+}
+
+// Solo la línea de estado, sin esperar al cuerpo. Puede llegar en varios
+// trozos: se lee hasta el primer `\r\n` (o hasta que el servidor cierre).
+pub fn http_status(url: &str, timeout: Duration) -> Result<u16, String> {
+    let mut stream = send_request("GET", url, None, timeout)?;
+    let mut head = Vec::new();
+    let mut buffer = [0; 256];
+    while !head.windows(2).any(|w| w == b"\r\n") && head.len() < STATUS_LINE_LIMIT {
+        let read = stream.read(&mut buffer).map_err(|e| e.to_string())?;
+        if read == 0 {
+            break;
+        }
+        head.extend_from_slice(&buffer[..read]);
+    }
+    let head = String::from_utf8_lossy(&head);
+    parse_status(head.split("\r\n").next().unwrap_or_default())
+}
+
+pub fn http_request(method: &str, url: &str, body: Option<&str>, timeout: Duration) -> Result<HttpResponse, String> {
+    let mut stream = send_request(method, url, body, timeout)?;
+    let mut bytes = Vec::new();
+    stream.read_to_end(&mut bytes).map_err(|e| e.to_string())?;
+
+    let text = String::from_utf8_lossy(&bytes);
+    let (head, body) = text.split_once("\r\n\r\n").unwrap_or((&text, ""));
+    Ok(HttpResponse { status: parse_status(head)?, body: body.to_string() })
+}
+
+// Servidor de una sola conexión para los tests: responde con `chunks`, uno tras otro
+#[cfg(test)]
+pub fn serve_once(chunks: Vec<&'static str>) -> String {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+    std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut request = [0; 1024];
+        let _ = stream.read(&mut request);
+        for chunk in chunks {
+            let _ = stream.write_all(chunk.as_bytes());
+            let _ = stream.flush();
+            std::thread::sleep(Duration::from_millis(20));
+        }
+    });
+    format!("http://{}/health", address)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn urls() {
+        assert_eq!(split_url("http://blog.lndo.site"), Some(("blog.lndo.site".to_string(), 80, "/".to_string())));
+        assert_eq!(split_url("https://localhost:8443/a/b?c=1"), Some(("localhost".to_string(), 8443, "/a/b?c=1".to_string())));
+        assert_eq!(split_url("ftp://blog.lndo.site"), None);
+        assert_eq!(split_url("http://localhost:puerto/"), None);
+        assert_eq!(local_host("0.0.0.0"), "127.0.0.1");
+        assert_eq!(local_host("localhost"), "localhost");
+    }
+
+    #[test]
+    fn https_falls_back_to_http_through_the_proxy() {
+        assert_eq!(plain_http_url("https://blog.lndo.site/"), Some("http://blog.lndo.site/".to_string()));
+        assert_eq!(plain_http_url("https://blog.lndo.site"), Some("http://blog.lndo.site".to_string()));
+        assert_eq!(plain_http_url("https://localhost:32770"), None);
+        assert_eq!(plain_http_url("http://blog.lndo.site/"), None);
+        assert_eq!(
+            http_status("https://localhost:32770/", Duration::from_secs(1)),
+            Err("HTTPS no está soportado: https://localhost:32770/".to_string()),
+        );
+    }
+
+    #[test]
+    fn status_line_split_across_reads() {
+        let url = serve_once(vec!["HTTP/1.1 2", "04 No Con", "tent\r\nServer: test\r\n\r\n"]);
+        assert_eq!(http_status(&url, Duration::from_secs(2)), Ok(204));
+
+        let url = serve_once(vec!["HTTP/1.0 503 Service Unavailable"]);
+        assert_eq!(http_status(&url, Duration::from_secs(2)), Ok(503));
+
+        let url = serve_once(vec!["SSH-2.0-OpenSSH_9.6\r\n"]);
+        assert_eq!(http_status(&url, Duration::from_secs(2)), Err("respuesta que no es HTTP".to_string()));
+    }
+
+    #[test]
+    fn full_responses() {
+        let url = serve_once(vec!["HTTP/1.0 200 OK\r\nContent-Type: application/json\r\n\r\n", "{\"status\":", " \"green\"}"]);
+        let response = http_request("GET", &url, None, Duration::from_secs(2)).unwrap();
+        assert_eq!((response.status, response.body.as_str()), (200, "{\"status\": \"green\"}"));
+    }
+}
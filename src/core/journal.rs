@@ -0,0 +1,143 @@
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::Sender;
+use std::time::Instant;
+
+use crate::core::commands::{run_lando_args, run_shell_command, shell_quote};
+use crate::core::time::now_secs;
+use crate::core::storage::{load_app_file, save_app_file};
+use crate::models::commands::LandoCommandOutcome;
+use crate::models::journal::{CommandJournal, Invocation, JournalEntry, JournalStatus, LogSpan, ProjectJournal};
+
+const JOURNAL_FILE: &str = "command_journal.json";
+pub const DEFAULT_JOURNAL_LIMIT: usize = 200;
+
+impl Invocation {
+    pub fn label(&self) -> String {
+        match self {
+            Invocation::Lando(args) => format!("lando {}", args.join(" ")),
+            Invocation::Shell { service, command } => format!("lando ssh -s {} -c {}", service, shell_quote(command)),
+        }
+    }
+
+    pub fn service(&self) -> Option<&str> {
+        match self {
+            Invocation::Lando(_) => None,
+            Invocation::Shell { service, .. } => Some(service),
+        }
+    }
+
+    pub fn run(&self, sender: &Sender<LandoCommandOutcome>, project_path: &Path) {
+        match self {
+            Invocation::Lando(args) => run_lando_args(sender.clone(), args.clone(), project_path.to_path_buf()),
+            Invocation::Shell { service, command } => {
+                run_shell_command(sender.clone(), project_path.to_path_buf(), service.clone(), command.clone())
+            }
+        }
+    }
+}
+
+pub fn format_duration_ms(duration_ms: u64) -> String {
+    match duration_ms {
+        0..=999 => format!("{} ms", duration_ms),
+        1000..=59_999 => format!("{:.1} s", duration_ms as f64 / 1000.0),
+        _ => format!("{} min {} s", duration_ms / 60_000, duration_ms / 1000 % 60),
+    }
+}
+
+impl CommandJournal {
+    // Lo que seguía en marcha al cerrar la app ya no va a terminar
+    pub fn load(limit: usize) -> Self {
+        let mut projects = load_app_file::<std::collections::HashMap<PathBuf, ProjectJournal>>(JOURNAL_FILE);
+        for journal in projects.values_mut() {
+            for entry in &mut journal.entries {
+                if entry.status == JournalStatus::Running {
+                    entry.status = JournalStatus::Interrupted;
+                }
+            }
+        }
+        let mut journal = Self { projects, limit };
+        journal.set_limit(limit);
+        journal
+    }
+
+    pub fn save(&self) {
+        if let Err(e) = save_app_file(JOURNAL_FILE, &self.projects) {
+            log::warn!("No se pudo guardar el historial de comandos: {}", e);
+        }
+    }
+
+    pub fn set_limit(&mut self, limit: usize) {
+        self.limit = limit.max(1);
+        for journal in self.projects.values_mut() {
+            let excess = journal.entries.len().saturating_sub(self.limit);
+            journal.entries.drain(..excess);
+        }
+    }
+
+    pub fn entries(&self, project: &Path) -> &[JournalEntry] {
+        self.projects.get(project).map(|j| j.entries.as_slice()).unwrap_or_default()
+    }
+
+    pub fn favorites(&self, project: &Path) -> &[Invocation] {
+        self.projects.get(project).map(|j| j.favorites.as_slice()).unwrap_or_default()
+    }
+
+    pub fn is_favorite(&self, project: &Path, invocation: &Invocation) -> bool {
+        self.favorites(project).contains(invocation)
+    }
+
+    pub fn toggle_favorite(&mut self, project: &Path, invocation: &Invocation) {
+        let favorites = &mut self.projects.entry(project.to_path_buf()).or_default().favorites;
+        match favorites.iter().position(|f| f == invocation) {
+            Some(index) => {
+                favorites.remove(index);
+            }
+            None => favorites.push(invocation.clone()),
+        }
+        self.save();
+    }
+
+    pub fn start(&mut self, project: PathBuf, invocation: Invocation, command: String, log: LogSpan) {
+        let limit = self.limit;
+        let journal = self.projects.entry(project).or_default();
+        journal.entries.push(JournalEntry {
+            invocation,
+            command,
+            started_at: now_secs(),
+            duration_ms: None,
+            status: JournalStatus::Running,
+            started: Some(Instant::now()),
+            log: Some(log),
+        });
+        let excess = journal.entries.len().saturating_sub(limit);
+        journal.entries.drain(..excess);
+    }
+
+    // Los mensajes de fin llevan el comando entre comillas; se cierra la
+    // entrada en marcha más antigua que coincida
+    pub fn finish(&mut self, message: &str, success: bool, log_end: usize) {
+        let entry = self.projects.values_mut()
+            .flat_map(|journal| journal.entries.iter_mut())
+            .filter(|entry| entry.status == JournalStatus::Running && message.contains(&format!("'{}'", entry.command)))
+            .min_by_key(|entry| entry.started);
+        let Some(entry) = entry else {
+            return;
+        };
+
+        entry.status = if success { JournalStatus::Success } else { JournalStatus::Failed };
+        entry.duration_ms = entry.started.map(|started| started.elapsed().as_millis() as u64);
+        if let Some(log) = entry.log.as_mut() {
+            log.end = Some(log_end);
+        }
+        self.save();
+    }
+
+    // Salida del terminal mientras corría el comando, si no se ha limpiado desde
+    // entonces. Con varios comandos a la vez su salida aparece mezclada.
+    pub fn entry_output(entry: &JournalEntry, generation: u64, log_buffer: &[String]) -> Option<String> {
+        let log = entry.log.filter(|log| log.generation == generation)?;
+        let end = log.end.unwrap_or(log_buffer.len()).min(log_buffer.len());
+        let chunks = log_buffer.get(log.start..end)?;
+        Some(chunks.concat())
+    }
+}
@@ -0,0 +1,96 @@
+use serde_json::Value;
+
+// Modelo del visor de JSON: cada nodo sabe su ruta (`items[0].name`) y si
+// coincide con la búsqueda. Con búsqueda solo quedan los nodos que coinciden
+// y sus antepasados; si coincide un objeto o array se conserva entero.
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonKey {
+    Field(String),
+    Index(usize),
+}
+
+#[derive(Debug)]
+pub struct JsonTreeNode<'a> {
+    pub key: Option<JsonKey>,
+    pub path: String,
+    pub value: &'a Value,
+    pub children: Vec<JsonTreeNode<'a>>,
+    pub total_children: usize, // Hijos antes de filtrar
+    pub is_match: bool,
+}
+
+// `None` si hay búsqueda y nada del documento coincide
+pub fn build_json_tree<'a>(value: &'a Value, search: &str) -> Option<JsonTreeNode<'a>> {
+    let needle = search.trim().to_lowercase();
+    build_node(None, String::new(), value, (!needle.is_empty()).then_some(needle.as_str()))
+}
+
+pub fn count_matches(node: &JsonTreeNode) -> usize {
+    usize::from(node.is_match) + node.children.iter().map(count_matches).sum::<usize>()
+}
+
+// Texto de una hoja tal como se busca y se copia: las cadenas sin comillas
+pub fn leaf_text(value: &Value) -> Option<String> {
+    match value {
+        Value::String(text) => Some(text.clone()),
+        Value::Number(number) => Some(number.to_string()),
+        Value::Bool(flag) => Some(flag.to_string()),
+        Value::Null => Some("null".to_string()),
+        _ => None,
+    }
+}
+
+// Valor que copia "Copiar valor": la hoja en texto plano, el resto como JSON formateado
+pub fn copy_value_text(value: &Value) -> String {
+    leaf_text(value).unwrap_or_else(|| serde_json::to_string_pretty(value).unwrap_or_default())
+}
+
+fn child_path(parent: &str, key: &JsonKey) -> String {
+    match key {
+        JsonKey::Index(index) => format!("{}[{}]", parent, index),
+        JsonKey::Field(name) => {
+            let simple = name.chars().next().is_some_and(|c| c.is_alphabetic() || c == '_' || c == '$')
+                && name.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '$');
+            if !simple {
+                format!("{}[{}]", parent, serde_json::to_string(name).unwrap_or_default())
+            } else if parent.is_empty() {
+                name.clone()
+            } else {
+                format!("{}.{}", parent, name)
+            }
+        }
+    }
+}
+
+fn build_node<'a>(key: Option<JsonKey>, path: String, value: &'a Value, needle: Option<&str>) -> Option<JsonTreeNode<'a>> {
+    let key_matches = match (&key, needle) {
+        (Some(JsonKey::Field(name)), Some(needle)) => name.to_lowercase().contains(needle),
+        _ => false,
+    };
+    let value_matches = needle.is_some_and(|needle| {
+        leaf_text(value).is_some_and(|text| text.to_lowercase().contains(needle))
+    });
+    let is_match = key_matches || value_matches;
+
+    let entries: Vec<(JsonKey, &Value)> = match value {
+        Value::Object(map) => map.iter().map(|(k, v)| (JsonKey::Field(k.clone()), v)).collect(),
+        Value::Array(items) => items.iter().enumerate().map(|(i, v)| (JsonKey::Index(i), v)).collect(),
+        _ => Vec::new(),
+    };
+    let total_children = entries.len();
+
+    // Bajo un nodo que ya coincide se muestra todo sin seguir filtrando
+    let child_needle = if is_match { None } else { needle };
+    let children: Vec<JsonTreeNode> = entries.into_iter()
+        .filter_map(|(child_key, child)| {
+            let child_path = child_path(&path, &child_key);
+            build_node(Some(child_key), child_path, child, child_needle)
+        })
+        .collect();
+
+    if needle.is_some() && !is_match && children.is_empty() {
+        return None;
+    }
+    Some(JsonTreeNode { key, path, value, children, total_children, is_match })
+}
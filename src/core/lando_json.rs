@@ -0,0 +1,155 @@
+use serde_json::{Map, Value};
+
+use crate::models::lando::{LandoApp, LandoService, ServiceConnectionInfo, ServiceCreds};
+use crate::models::secret::SecretString;
+
+// Lectura tolerante del JSON de `lando list` y `lando info`: la forma cambia entre
+// versiones (v3/v4), así que se extrae campo a campo lo que se reconoce. Lo que no
+// encaja se descarta con un aviso en el log en vez de hacer fallar toda la llamada.
+
+const MAX_LOGGED_PAYLOAD: usize = 500;
+
+fn truncated(value: &Value) -> String {
+    let text = value.to_string();
+    match text.char_indices().nth(MAX_LOGGED_PAYLOAD) {
+        Some((end, _)) => format!("{}…", &text[..end]),
+        None => text,
+    }
+}
+
+// Lando a veces escribe avisos (actualizaciones, plugins) antes del JSON
+fn parse_payload(bytes: &[u8]) -> Result<Value, String> {
+    let text = String::from_utf8_lossy(bytes);
+    let start = text.find(['[', '{']).ok_or_else(|| "la salida no contiene JSON".to_string())?;
+    if start > 0 {
+        log::warn!("Texto antes del JSON de lando ignorado: {}", text[..start].trim());
+    }
+    serde_json::from_str(&text[start..]).map_err(|e| e.to_string())
+}
+
+// Lista de elementos: un array, un objeto con un array dentro (`{"apps": [...]}`)
+// o un mapa `nombre -> elemento`, en cuyo caso el nombre se copia en `name_key`
+fn payload_items(payload: Value, name_key: &str) -> Vec<Value> {
+    match payload {
+        Value::Array(items) => items,
+        Value::Object(map) => {
+            if let Some(items) = map.values().find_map(Value::as_array) {
+                return items.clone();
+            }
+            map.into_iter()
+                .map(|(name, mut item)| {
+                    if let Some(object) = item.as_object_mut() {
+                        object.entry(name_key).or_insert(Value::String(name));
+                    }
+                    item
+                })
+                .collect()
+        }
+        other => {
+            log::warn!("JSON de lando con forma inesperada: {}", truncated(&other));
+            Vec::new()
+        }
+    }
+}
+
+// Cadenas, números y booleanos valen como texto
+fn as_text(value: &Value) -> Option<String> {
+    match value {
+        Value::String(text) => Some(text.clone()),
+        Value::Number(number) => Some(number.to_string()),
+        Value::Bool(flag) => Some(flag.to_string()),
+        _ => None,
+    }
+}
+
+fn text_field(object: &Map<String, Value>, keys: &[&str]) -> Option<String> {
+    keys.iter().find_map(|key| object.get(*key).and_then(as_text))
+}
+
+// Lista de cadenas o una cadena suelta
+fn text_list(object: &Map<String, Value>, key: &str) -> Vec<String> {
+    match object.get(key) {
+        Some(Value::Array(items)) => items.iter().filter_map(as_text).collect(),
+        Some(value) => as_text(value).into_iter().collect(),
+        None => Vec::new(),
+    }
+}
+
+// `true`, `"yes"`, `"running"`...
+fn is_truthy(value: &Value) -> bool {
+    match value {
+        Value::Bool(flag) => *flag,
+        Value::Number(number) => number.as_i64().is_some_and(|n| n != 0),
+        Value::String(text) => matches!(text.to_lowercase().as_str(), "true" | "yes" | "running" | "up" | "1"),
+        _ => false,
+    }
+}
+
+fn connection(object: &Map<String, Value>, key: &str) -> Option<ServiceConnectionInfo> {
+    let connection = object.get(key)?.as_object()?;
+    Some(ServiceConnectionInfo {
+        host: text_field(connection, &["host", "hostname"]).unwrap_or_default(),
+        port: text_field(connection, &["port"]).unwrap_or_default(),
+    })
+}
+
+fn creds(object: &Map<String, Value>) -> Option<ServiceCreds> {
+    let creds = object.get("creds")?.as_object()?;
+    Some(ServiceCreds {
+        user: text_field(creds, &["user", "username"]),
+        password: text_field(creds, &["password"]).map(SecretString::from),
+        database: text_field(creds, &["database", "db"]),
+    })
+}
+
+fn app_from_value(item: &Value) -> Option<LandoApp> {
+    let object = item.as_object()?;
+    let name = text_field(object, &["name", "app"])?;
+    let running = ["running", "status", "state"].iter()
+        .find_map(|key| object.get(*key))
+        .is_some_and(is_truthy);
+    Some(LandoApp {
+        name,
+        location: text_field(object, &["location", "root", "dir"]).unwrap_or_default(),
+        urls: text_list(object, "urls"),
+        running,
+    })
+}
+
+fn service_from_value(item: &Value) -> Option<LandoService> {
+    let object = item.as_object()?;
+    let service = text_field(object, &["service", "name"])?;
+    Some(LandoService {
+        service,
+        r#type: text_field(object, &["type"]).unwrap_or_default(),
+        urls: text_list(object, "urls"),
+        version: text_field(object, &["version"]).unwrap_or_default(),
+        internal_connection: connection(object, "internal_connection"),
+        external_connection: connection(object, "external_connection"),
+        creds: creds(object),
+        raw: item.clone(),
+        ..Default::default()
+    })
+}
+
+fn parse_items<T>(bytes: &[u8], name_key: &str, what: &str, convert: fn(&Value) -> Option<T>) -> Result<Vec<T>, String> {
+    let items = payload_items(parse_payload(bytes)?, name_key);
+    Ok(items.iter()
+        .filter_map(|item| {
+            let parsed = convert(item);
+            if parsed.is_none() {
+                log::warn!("Elemento de {} sin los campos esperados, ignorado: {}", what, truncated(item));
+            }
+            parsed
+        })
+        .collect())
+}
+
+pub fn parse_apps(bytes: &[u8]) -> Result<Vec<LandoApp>, String> {
+    parse_items(bytes, "name", "lando list", app_from_value)
+}
+
+// Cada servicio guarda además su JSON original
+pub fn parse_services(bytes: &[u8]) -> Result<Vec<LandoService>, String> {
+    parse_items(bytes, "service", "lando info", service_from_value)
+}
@@ -0,0 +1,251 @@
+use std::collections::VecDeque;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use regex::Regex;
+
+use crate::core::result_format::format_day_number;
+
+// Log propio de la app en el directorio de datos de eframe. Se escribe sin
+// buffer para que lo último antes de un cierre inesperado llegue al disco.
+pub const LOG_FILE_NAME: &str = "lando_gui.log";
+const MAX_LOG_FILE_BYTES: u64 = 2 * 1024 * 1024;
+const LOG_FILES: usize = 3; // El actual y dos rotados
+pub const LOG_TAIL_LINES: usize = 200;
+pub const DEFAULT_LOG_LEVEL: LevelFilter = LevelFilter::Info;
+pub const LOG_LEVELS: &[LevelFilter] = &[
+    LevelFilter::Error,
+    LevelFilter::Warn,
+    LevelFilter::Info,
+    LevelFilter::Debug,
+    LevelFilter::Trace,
+];
+
+// Contraseñas que pueden aparecer en los argumentos: `--password=...`,
+// `PGPASSWORD=...`, `-p '...'` (mongosh), `-a '...'` (redis-cli) y las de
+// `CREATE USER ... IDENTIFIED BY '...'` dentro de una consulta
+static SECRET_PATTERNS: LazyLock<Vec<(Regex, &'static str)>> = LazyLock::new(|| {
+    vec![
+        (Regex::new(r#"(?i)(password=|pgpassword=|mysql_pwd=)('[^']*'|"[^"]*"|[^\s'"]+)"#).unwrap(), "${1}***"),
+        (Regex::new(r#"(?i)(identified\s+by|password)(\s+)('[^']*'|"[^"]*")"#).unwrap(), "${1}${2}'***'"),
+        (Regex::new(r#"(\s-[pa]\s+)('[^']*'|"[^"]*"|\S+)"#).unwrap(), "${1}***"),
+    ]
+});
+
+pub fn redact_secrets(text: &str) -> String {
+    SECRET_PATTERNS.iter().fold(text.to_string(), |acc, (regex, replacement)| {
+        regex.replace_all(&acc, *replacement).to_string()
+    })
+}
+
+// `programa arg1 arg2` listo para el log, sin contraseñas
+pub fn command_for_log(program: &str, args: &[String]) -> String {
+    redact_secrets(&format!(" {} {}", program, args.join(" "))).trim().to_string()
+}
+
+fn log_dir() -> Option<PathBuf> {
+    eframe::storage_dir("Lando GUI").map(|dir| dir.join("logs"))
+}
+
+pub fn log_file_path() -> Option<PathBuf> {
+    log_dir().map(|dir| dir.join(LOG_FILE_NAME))
+}
+
+// 0 es el archivo actual; 1 y 2 los anteriores
+fn rotated_path(dir: &Path, index: usize) -> PathBuf {
+    if index == 0 {
+        dir.join(LOG_FILE_NAME)
+    } else {
+        dir.join(format!("lando_gui.{}.log", index))
+    }
+}
+
+// `YYYY-mm-dd HH:MM:SS` en UTC
+fn log_timestamp() -> String {
+    let secs = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+    let seconds = secs % 86400;
+    format!(
+        "{} {:02}:{:02}:{:02}",
+        format_day_number((secs / 86400) as f64),
+        seconds / 3600,
+        (seconds % 3600) / 60,
+        seconds % 60
+    )
+}
+
+struct LogFile {
+    file: File,
+    size: u64,
+}
+
+struct FileLogger {
+    dir: PathBuf,
+    current: Mutex<Option<LogFile>>,
+}
+
+impl FileLogger {
+    fn open(dir: &Path) -> io::Result<LogFile> {
+        fs::create_dir_all(dir)?;
+        let file = OpenOptions::new().create(true).append(true).open(rotated_path(dir, 0))?;
+        let size = file.metadata()?.len();
+        Ok(LogFile { file, size })
+    }
+
+    // El archivo actual tiene que estar cerrado antes de renombrarlo (Windows)
+    fn rotate(&self) -> io::Result<LogFile> {
+        for index in (1..LOG_FILES).rev() {
+            let from = rotated_path(&self.dir, index - 1);
+            if from.exists() {
+                fs::rename(&from, rotated_path(&self.dir, index))?;
+            }
+        }
+        Self::open(&self.dir)
+    }
+}
+
+impl Log for FileLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        // De las dependencias (eframe, wgpu...) solo interesan avisos y errores
+        metadata.level() <= log::max_level()
+            && (metadata.target().starts_with("lando_gui") || metadata.level() <= Level::Warn)
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+
+        let line = format!("{} {:<5} [{}] {}\n", log_timestamp(), record.level(), record.target(), record.args());
+        let Ok(mut current) = self.current.lock() else {
+            return;
+        };
+        if current.as_ref().is_some_and(|c| c.size + line.len() as u64 > MAX_LOG_FILE_BYTES) {
+            *current = None;
+            *current = self.rotate().ok();
+        }
+
+        // Sin archivo (carpeta de solo lectura, disco lleno) al menos queda stderr
+        match current.as_mut() {
+            Some(log_file) => match log_file.file.write_all(line.as_bytes()) {
+                Ok(()) => log_file.size += line.len() as u64,
+                Err(_) => eprint!("{}", line),
+            },
+            None => eprint!("{}", line),
+        }
+    }
+
+    fn flush(&self) {
+        if let Ok(mut current) = self.current.lock() {
+            if let Some(log_file) = current.as_mut() {
+                let _ = log_file.file.flush();
+            }
+        }
+    }
+}
+
+// Instala el logger y un hook de pánico que deja el mensaje en el log antes
+// de seguir con el comportamiento normal
+pub fn init_logging() {
+    let Some(dir) = log_dir() else {
+        return;
+    };
+    let current = FileLogger::open(&dir).ok();
+    let logger = FileLogger { dir, current: Mutex::new(current) };
+    if log::set_boxed_logger(Box::new(logger)).is_err() {
+        return;
+    }
+    log::set_max_level(DEFAULT_LOG_LEVEL);
+
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        log::error!("Pánico: {}", info);
+        log::logger().flush();
+        default_hook(info);
+    }));
+
+    log::info!(
+        "Lando GUI {} iniciado ({} {})",
+        env!("CARGO_PKG_VERSION"),
+        std::env::consts::OS,
+        std::env::consts::ARCH
+    );
+}
+
+// Se puede cambiar en cualquier momento desde los ajustes
+pub fn set_log_level(level: LevelFilter) {
+    log::set_max_level(level);
+}
+
+pub fn parse_log_level(value: &str) -> LevelFilter {
+    value.parse().unwrap_or(DEFAULT_LOG_LEVEL)
+}
+
+// Nivel de una línea escrita por el logger; las líneas de continuación de un
+// mensaje multilínea no tienen y heredan el de la anterior
+pub fn line_level(line: &str) -> Option<Level> {
+    line.split_whitespace().nth(2).and_then(|level| level.parse().ok())
+}
+
+// Últimas `max_lines` líneas, tirando del archivo rotado si el actual es corto
+pub fn read_log_tail(max_lines: usize) -> io::Result<Vec<String>> {
+    let dir = log_dir().ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "sin directorio de datos"))?;
+    let mut tail = VecDeque::with_capacity(max_lines);
+    for index in (0..LOG_FILES).rev() {
+        let Ok(file) = File::open(rotated_path(&dir, index)) else {
+            continue;
+        };
+        for line in BufReader::new(file).lines().map_while(Result::ok) {
+            if tail.len() == max_lines {
+                tail.pop_front();
+            }
+            tail.push_back(line);
+        }
+    }
+    Ok(tail.into())
+}
+
+// Primer frame más lento que esto se avisa en el log
+pub const STARTUP_FIRST_FRAME_TARGET: Duration = Duration::from_millis(200);
+
+// Tiempos del arranque: cada fase se mide desde la anterior y se escriben
+// todas juntas al pintar el primer frame
+pub struct StartupTimer {
+    started: Instant,
+    last: Instant,
+    phases: Vec<(&'static str, Duration)>,
+}
+
+impl StartupTimer {
+    pub fn start() -> Self {
+        let now = Instant::now();
+        Self { started: now, last: now, phases: Vec::new() }
+    }
+
+    pub fn phase(&mut self, name: &'static str) {
+        let now = Instant::now();
+        self.phases.push((name, now - self.last));
+        self.last = now;
+    }
+
+    // "ventana 85 ms · ajustes 2 ms · ..."
+    pub fn summary(&self) -> String {
+        self.phases.iter()
+            .map(|(name, duration)| format!("{} {} ms", name, duration.as_millis()))
+            .collect::<Vec<_>>()
+            .join(" · ")
+    }
+
+    pub fn finish(mut self) {
+        self.phase("primer frame");
+        let total = self.started.elapsed();
+        if total > STARTUP_FIRST_FRAME_TARGET {
+            log::warn!("Arranque lento: primer frame a los {} ms ({})", total.as_millis(), self.summary());
+        } else {
+            log::info!("Primer frame a los {} ms ({})", total.as_millis(), self.summary());
+        }
+    }
+}
@@ -1,5 +1,57 @@
-mod appserver;
-mod database;
-mod node;
+pub(crate) mod appserver;
+pub(crate) mod database;
+pub(crate) mod node;
 pub(crate) mod commands;
-mod app;
\ No newline at end of file
+pub(crate) mod result_format;
+pub(crate) mod runner;
+mod app;
+mod wizard;
+mod toasts;
+pub(crate) mod table_editor;
+mod generic;
+mod chart;
+pub(crate) mod recipes;
+pub(crate) mod diagram;
+pub(crate) mod prompts;
+pub(crate) mod storage;
+pub(crate) mod profiles;
+mod composer;
+mod redis;
+pub(crate) mod query_cache;
+pub(crate) mod time;
+mod mongo;
+pub(crate) mod schedules;
+pub(crate) mod privileges;
+pub(crate) mod platform;
+pub(crate) mod processes;
+pub(crate) mod schema_search;
+pub(crate) mod errors;
+pub(crate) mod db_session;
+pub(crate) mod backups;
+pub(crate) mod process_list;
+pub(crate) mod graph;
+pub(crate) mod query_watch;
+pub(crate) mod csv_import;
+pub(crate) mod logging;
+pub(crate) mod db_engine;
+pub(crate) mod lando_json;
+pub(crate) mod health;
+pub(crate) mod http;
+pub(crate) mod search;
+pub(crate) mod status;
+pub(crate) mod sql_lint;
+pub(crate) mod phases;
+pub(crate) mod notes;
+pub(crate) mod varnish;
+pub(crate) mod dashboard;
+pub(crate) mod secret;
+pub(crate) mod snippets;
+pub(crate) mod resources;
+pub(crate) mod journal;
+pub(crate) mod drift;
+pub(crate) mod foreign_keys;
+pub(crate) mod ejson;
+pub(crate) mod json_tree;
+pub(crate) mod query_search;
+pub(crate) mod terminal;
+pub(crate) mod history_export;
@@ -0,0 +1,168 @@
+use std::path::PathBuf;
+use std::sync::mpsc::Sender;
+use serde_json::Value;
+use crate::core::commands::*;
+use crate::core::composer::extract_json;
+use crate::core::ejson::parse_documents;
+use crate::models::commands::LandoCommandOutcome;
+use crate::models::lando::LandoService;
+use crate::models::secret::SecretString;
+use crate::ui::mongo::{MongoPendingRequest, MongoResult, MongoUI};
+
+impl MongoUI {
+    pub fn run_command(&mut self, service: &LandoService, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>) {
+        let command = self.command_input.trim().trim_end_matches(';').trim().to_string();
+        if command.is_empty() || !self.pending_requests.is_empty() {
+            return;
+        }
+
+        if self.history.last() != Some(&command) {
+            self.history.push(command.clone());
+        }
+
+        // `use otra_db` solo cambia la base de datos de las siguientes evaluaciones
+        if let Some(database) = command.strip_prefix("use ") {
+            self.database = database.trim().to_string();
+            self.collections.clear();
+            return;
+        }
+
+        let request_id = next_request_id();
+        self.pending_requests.insert(request_id, MongoPendingRequest::Eval { command: command.clone() });
+        run_shell_capture_tagged(
+            sender.clone(),
+            project_path.clone(),
+            service.service.clone(),
+            mongo_shell_command(service, &self.database, &to_expression(&command)),
+            request_id,
+        );
+    }
+
+    pub fn load_collections(&mut self, service: &LandoService, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>) {
+        if self.pending_requests.values().any(|r| *r == MongoPendingRequest::Collections) {
+            return;
+        }
+
+        let request_id = next_request_id();
+        self.pending_requests.insert(request_id, MongoPendingRequest::Collections);
+        run_shell_capture_tagged(
+            sender.clone(),
+            project_path.clone(),
+            service.service.clone(),
+            mongo_shell_command(service, &self.database, "db.getCollectionNames()"),
+            request_id,
+        );
+    }
+
+    pub fn handle_tagged_result(&mut self, request_id: u64, output: &Result<String, String>) -> bool {
+        let Some(request) = self.pending_requests.remove(&request_id) else {
+            return false;
+        };
+
+        match (request, output) {
+            (MongoPendingRequest::Collections, Ok(text)) => {
+                self.collections = extract_json(text)
+                    .and_then(|value| value.as_array().cloned())
+                    .map(|names| names.iter().filter_map(|n| n.as_str().map(String::from)).collect())
+                    .unwrap_or_default();
+                self.collections.sort();
+            }
+            (MongoPendingRequest::Collections, Err(err)) => {
+                self.result = Some(MongoResult {
+                    command: "show collections".to_string(),
+                    documents: Vec::new(),
+                    pretty: String::new(),
+                    error: Some(err.clone()),
+                });
+            }
+            (MongoPendingRequest::Eval { command }, Ok(text)) => {
+                self.result = Some(parse_mongo_output(command, text));
+            }
+            (MongoPendingRequest::Eval { command }, Err(err)) => {
+                self.result = Some(MongoResult {
+                    command,
+                    documents: Vec::new(),
+                    pretty: String::new(),
+                    error: Some(err.clone()),
+                });
+            }
+        }
+        true
+    }
+}
+
+// Los helpers `show ...` no existen en `--eval` del shell antiguo, así que se
+// traducen a su equivalente como expresión
+fn to_expression(command: &str) -> String {
+    match command.to_lowercase().as_str() {
+        "show dbs" | "show databases" => "db.adminCommand({ listDatabases: 1 }).databases".to_string(),
+        "show collections" | "show tables" => "db.getCollectionNames()".to_string(),
+        _ => command.to_string(),
+    }
+}
+
+// Evalúa la expresión e imprime el resultado como JSON (los cursores se
+// materializan con `toArray`). Usa mongosh y, si no está, el shell `mongo`.
+fn mongo_shell_command(service: &LandoService, database: &str, expression: &str) -> String {
+    let mut args = String::from("--quiet");
+    if let Some(creds) = &service.creds {
+        if let Some(user) = creds.user.as_deref().filter(|u| !u.is_empty()) {
+            args.push_str(&format!(" -u {} --authenticationDatabase admin", shell_quote(user)));
+            if let Some(password) = creds.password.as_ref().map(SecretString::expose).filter(|p| !p.is_empty()) {
+                args.push_str(&format!(" -p {}", shell_quote(password)));
+            }
+        }
+    }
+    if !database.is_empty() {
+        args.push_str(&format!(" {}", shell_quote(database)));
+    }
+
+    let materialize = "__r && typeof __r.toArray === 'function' ? __r.toArray() : __r";
+    let mongosh_script = format!(
+        "const __r = ({}); print(EJSON.stringify({}, null, 2, {{ relaxed: true }}))",
+        expression, materialize
+    );
+    let legacy_script = format!("var __r = ({}); printjson({})", expression, materialize);
+
+    format!(
+        "if command -v mongosh >/dev/null 2>&1; then mongosh {args} --eval {}; else mongo {args} --eval {}; fi",
+        shell_quote(&mongosh_script),
+        shell_quote(&legacy_script),
+        args = args,
+    )
+}
+
+fn parse_mongo_output(command: String, text: &str) -> MongoResult {
+    // Documentos sueltos o un array de documentos, aunque vengan en EJSON relajado
+    // (`ObjectId(...)`, claves sin comillas) como los imprime el shell
+    if let Some(values) = parse_documents(text) {
+        let pretty = values.iter()
+            .map(|value| serde_json::to_string_pretty(value).unwrap_or_default())
+            .collect::<Vec<_>>()
+            .join("\n");
+        let documents = values.into_iter()
+            .enumerate()
+            .map(|(index, value)| (document_label(index, &value), value))
+            .collect();
+        return MongoResult { command, documents, pretty, error: None };
+    }
+
+    // Un número, una cadena... se muestran formateados; lo que no se entiende, tal cual
+    let pretty = extract_json(text)
+        .and_then(|value| serde_json::to_string_pretty(&value).ok())
+        .unwrap_or_else(|| text.trim_end().to_string());
+    MongoResult { command, documents: Vec::new(), pretty, error: None }
+}
+
+fn document_label(index: usize, document: &Value) -> String {
+    let id = match document.get("_id").or_else(|| document.get("name")) {
+        Some(Value::String(id)) => id.clone(),
+        Some(Value::Object(map)) => map.get("$oid")
+            .and_then(Value::as_str)
+            .map(String::from)
+            .unwrap_or_else(|| Value::Object(map.clone()).to_string()),
+        Some(other) => other.to_string(),
+        None => return format!("{}", index + 1),
+    };
+    format!("{} — {}", index + 1, id)
+}
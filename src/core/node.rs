@@ -1,15 +1,285 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::mpsc::Sender;
+use std::time::{Duration, Instant};
+use crate::core::backups::format_size;
+use crate::core::time::now_secs;
 use crate::models::commands::LandoCommandOutcome;
 use crate::models::lando::LandoService;
 use crate::core::commands::*;
-use crate::ui::node::{DependencyType, NodeUI};
+use crate::ui::node::{CoverageSummary, DependencyType, EnvironmentMode, NodeUI, NodeLogSource, NodePendingRequest, NodeTab, NpmConfigEntry, NpmConfigScope, NpmrcEditor, PackageInfo, PackageManager, PM2Process};
+
+// Líneas que se piden a `pm2 logs` y a `tail` en la pestaña de logs
+const LOG_LINES: usize = 200;
+
+// Cada cuánto se releen los logs con "Auto" marcado
+pub const LOGS_REFRESH_INTERVAL: Duration = Duration::from_secs(5);
+
+// npm guarda un log de depuración por ejecución en ~/.npm/_logs
+const NPM_LOGS_COMMAND: &str = "f=$(ls -t ~/.npm/_logs/*.log 2>/dev/null | head -n 1); \
+    if [ -n \"$f\" ]; then echo \"==> $f\"; tail -n 200 \"$f\"; else echo 'No hay logs de npm en ~/.npm/_logs'; fi";
+
+// La primera línea es la ruta del npmrc global; el resto, su contenido (puede no existir)
+const GLOBAL_NPMRC_READ_COMMAND: &str = "f=$(npm config get globalconfig) && echo \"$f\" && { cat \"$f\" 2>/dev/null || true; }";
+
+// Ficheros del directorio de trabajo del servicio y el campo `packageManager` de package.json (corepack)
+const DETECT_PACKAGE_MANAGER_COMMAND: &str = "ls -1A; grep -o '\"packageManager\" *: *\"[a-z]*' package.json 2>/dev/null || true";
+
+// Lockfiles por orden de preferencia si hay varios
+const LOCKFILES: &[(&str, PackageManager)] = &[
+    ("pnpm-lock.yaml", PackageManager::Pnpm),
+    ("yarn.lock", PackageManager::Yarn),
+    ("package-lock.json", PackageManager::Npm),
+    ("npm-shrinkwrap.json", PackageManager::Npm),
+];
+
+// `yarn list` incluye todo lo que se ha elevado a node_modules, así que las versiones
+// de las dependencias declaradas se leen directamente. Devuelve el mismo JSON que
+// `npm ls --json`. No sirve con Plug'n'Play (sin node_modules).
+const YARN_LIST_COMMAND: &str = "node -e '\
+const fs = require(\"fs\"); const pkg = require(\"./package.json\"); \
+const out = { dependencies: {}, devDependencies: {} }; \
+for (const kind of [\"dependencies\", \"devDependencies\"]) { \
+  for (const name of Object.keys(pkg[kind] || {})) { \
+    let version = \"?\"; \
+    try { version = JSON.parse(fs.readFileSync(\"node_modules/\" + name + \"/package.json\")).version; } catch (e) {} \
+    out[kind][name] = { version }; out.dependencies[name] = { version }; \
+  } \
+} \
+console.log(JSON.stringify(out));'";
+
+// Nombres que PM2 reconoce como ecosystem file (`pm2 init` crea el primero)
+const ECOSYSTEM_FILE_NAMES: &[&str] = &[
+    "ecosystem.config.js",
+    "ecosystem.config.cjs",
+    "ecosystem.config.mjs",
+    "ecosystem.json",
+    "ecosystem.yml",
+    "ecosystem.yaml",
+    "pm2.json",
+    "process.json",
+];
+
+pub fn detect_ecosystem_files(project_path: &Path) -> Vec<String> {
+    ECOSYSTEM_FILE_NAMES.iter()
+        .filter(|name| project_path.join(name).is_file())
+        .map(|name| name.to_string())
+        .collect()
+}
+
+fn format_uptime(started_at_ms: u64) -> String {
+    let elapsed = now_secs().saturating_sub(started_at_ms / 1000);
+    match elapsed {
+        0..=59 => format!("{}s", elapsed),
+        60..=3599 => format!("{}m", elapsed / 60),
+        3600..=86_399 => format!("{}h", elapsed / 3600),
+        _ => format!("{}d", elapsed / 86_400),
+    }
+}
+
+// `pm2 jlist`: [{ name, pm_id, monit: { cpu, memory }, pm2_env: { status, pm_uptime } }].
+// La primera vez que arranca el demonio PM2 imprime un aviso antes del JSON.
+// `npm config list` agrupa los valores por origen con cabeceras de comentario:
+// `; "project" config from /app/.npmrc` (npm 7+) o `; project config /app/.npmrc` (npm 6).
+// Los valores sobrescritos salen comentados y se ignoran igual que el resto de comentarios.
+pub fn parse_npm_config_list(output: &str) -> Vec<NpmConfigEntry> {
+    let mut entries = Vec::new();
+    let mut source = String::new();
+
+    for line in output.lines() {
+        let line = line.trim();
+        if let Some(comment) = line.strip_prefix(';') {
+            let comment = comment.trim();
+            if comment.contains(" config") && !comment.contains(" = ") {
+                let kind = comment.split_whitespace().next().unwrap_or_default();
+                source = kind.trim_matches('"').to_string();
+            }
+            continue;
+        }
+        let Some((key, value)) = line.split_once(" = ") else {
+            continue;
+        };
+        // Los textos vienen entre comillas con escapes de JSON
+        let value = serde_json::from_str::<String>(value).unwrap_or_else(|_| value.to_string());
+        entries.push(NpmConfigEntry { source: source.clone(), key: key.trim().to_string(), value });
+    }
+
+    entries
+}
+
+impl PackageManager {
+    pub const ALL: [PackageManager; 3] = [PackageManager::Npm, PackageManager::Yarn, PackageManager::Pnpm];
+
+    pub fn binary(&self) -> &'static str {
+        match self {
+            PackageManager::Npm => "npm",
+            PackageManager::Yarn => "yarn",
+            PackageManager::Pnpm => "pnpm",
+        }
+    }
+
+    // `run` también en yarn para que un script no choque con un comando propio (`yarn install`)
+    pub fn run_script_command(&self, script: &str) -> String {
+        format!("{} run {}", self.binary(), shell_quote(script))
+    }
+
+    pub fn add_command(&self, package: &str, dependency_type: &DependencyType) -> String {
+        let flag = match (self, dependency_type) {
+            (PackageManager::Yarn, DependencyType::Development) => " --dev",
+            (PackageManager::Yarn, DependencyType::Peer) => " --peer",
+            (PackageManager::Yarn, DependencyType::Optional) => " --optional",
+            (PackageManager::Yarn, DependencyType::Production) => "",
+            (_, DependencyType::Development) => " --save-dev",
+            (_, DependencyType::Peer) => " --save-peer",
+            (_, DependencyType::Optional) => " --save-optional",
+            (PackageManager::Pnpm, DependencyType::Production) => "",
+            (PackageManager::Npm, DependencyType::Production) => " --save",
+        };
+        let verb = if *self == PackageManager::Npm { "install" } else { "add" };
+        format!("{} {} {}{}", self.binary(), verb, shell_quote(package), flag)
+    }
+
+    pub fn remove_command(&self, package: &str) -> String {
+        let verb = if *self == PackageManager::Npm { "uninstall" } else { "remove" };
+        format!("{} {} {}", self.binary(), verb, shell_quote(package))
+    }
+
+    // Yarn clásico; en Yarn 2+ el equivalente es `yarn up`
+    pub fn update_command(&self, package: &str) -> String {
+        let verb = if *self == PackageManager::Yarn { "upgrade" } else { "update" };
+        format!("{} {} {}", self.binary(), verb, shell_quote(package))
+    }
+
+    // Los listados también salen con error ante dependencias extrañas o ausentes,
+    // pero la salida sigue siendo válida
+    pub fn list_command(&self, global: bool) -> &'static str {
+        match (self, global) {
+            (PackageManager::Npm, true) => "npm ls -g --json --depth=0 || true",
+            (PackageManager::Npm, false) => "npm ls --json --depth=0 --long || true",
+            (PackageManager::Pnpm, true) => "pnpm ls -g --json --depth=0 || true",
+            (PackageManager::Pnpm, false) => "pnpm ls --json --depth=0 || true",
+            (PackageManager::Yarn, true) => "yarn global list || true",
+            (PackageManager::Yarn, false) => YARN_LIST_COMMAND,
+        }
+    }
+
+    // Salen con código 1 cuando hay paquetes desactualizados
+    pub fn outdated_command(&self) -> &'static str {
+        match self {
+            PackageManager::Npm => "npm outdated --json || true",
+            PackageManager::Pnpm => "pnpm outdated --format json || true",
+            PackageManager::Yarn => "yarn outdated --json || true",
+        }
+    }
+}
+
+// Devuelve el gestor y el motivo; `packageManager` de package.json manda sobre los lockfiles
+pub fn detect_package_manager(listing: &str) -> Option<(PackageManager, String)> {
+    let declared = listing.lines()
+        .filter(|line| line.contains("\"packageManager\""))
+        .filter_map(|line| line.trim().rsplit_once('"').map(|(_, name)| name))
+        .find_map(|name| PackageManager::ALL.into_iter().find(|m| m.binary() == name));
+    if let Some(manager) = declared {
+        return Some((manager, "packageManager".to_string()));
+    }
+    let files: Vec<&str> = listing.lines().map(str::trim).collect();
+    LOCKFILES.iter()
+        .find(|(file, _)| files.contains(file))
+        .map(|(file, manager)| (*manager, file.to_string()))
+}
+
+// `pnpm ls --json`: [{ "dependencies": { nombre: { version } }, "devDependencies": {...} }]
+pub fn parse_pnpm_ls(json: &str) -> Option<Vec<PackageInfo>> {
+    let value: serde_json::Value = serde_json::from_str(json.trim()).ok()?;
+    let project = match &value {
+        serde_json::Value::Array(projects) => projects.first()?,
+        other => other,
+    };
+
+    let mut packages = Vec::new();
+    for (key, is_dev) in [("dependencies", false), ("devDependencies", true), ("optionalDependencies", false)] {
+        let Some(deps) = project.get(key).and_then(|d| d.as_object()) else {
+            continue;
+        };
+        packages.extend(deps.iter().map(|(name, info)| PackageInfo {
+            name: name.clone(),
+            version: info.get("version").and_then(|v| v.as_str()).unwrap_or("?").to_string(),
+            description: None,
+            is_dev_dependency: is_dev,
+            is_outdated: false,
+        }));
+    }
+    packages.sort_by(|a, b| a.name.cmp(&b.name));
+    Some(packages)
+}
+
+// `yarn global list`: `info "nombre@1.2.3" has binaries:` por paquete
+pub fn parse_yarn_global_list(output: &str) -> Vec<PackageInfo> {
+    let mut packages: Vec<PackageInfo> = output.lines()
+        .filter_map(|line| {
+            let spec = line.trim().strip_prefix("info \"")?.split('"').next()?;
+            // `@scope/nombre@1.0.0`: la versión va tras la última @
+            let (name, version) = spec.rsplit_once('@').filter(|(name, _)| !name.is_empty())?;
+            Some(PackageInfo {
+                name: name.to_string(),
+                version: version.to_string(),
+                description: None,
+                is_dev_dependency: false,
+                is_outdated: false,
+            })
+        })
+        .collect();
+    packages.sort_by(|a, b| a.name.cmp(&b.name));
+    packages
+}
+
+// Nombres de los paquetes desactualizados. npm y pnpm devuelven un objeto por
+// nombre; yarn, líneas JSON con una tabla cuya primera columna es el paquete.
+pub fn outdated_package_names(manager: PackageManager, output: &str) -> Vec<String> {
+    if manager != PackageManager::Yarn {
+        return match serde_json::from_str::<serde_json::Value>(output.trim()) {
+            Ok(serde_json::Value::Object(outdated)) => outdated.keys().cloned().collect(),
+            _ => Vec::new(),
+        };
+    }
+    output.lines()
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+        .filter(|value| value.get("type").and_then(|t| t.as_str()) == Some("table"))
+        .filter_map(|value| value.pointer("/data/body").and_then(|b| b.as_array()).cloned())
+        .flatten()
+        .filter_map(|row| row.get(0).and_then(|name| name.as_str()).map(String::from))
+        .collect()
+}
+
+pub fn parse_pm2_jlist(output: &str) -> Option<Vec<PM2Process>> {
+    let json = &output[output.find('[')?..];
+    let value: serde_json::Value = serde_json::from_str(json.trim()).ok()?;
+    let processes = value.as_array()?.iter()
+        .map(|process| {
+            let env = process.get("pm2_env");
+            let monit = process.get("monit");
+            let status = env.and_then(|e| e.get("status")).and_then(|s| s.as_str()).unwrap_or("?").to_string();
+            let uptime = match env.and_then(|e| e.get("pm_uptime")).and_then(|u| u.as_u64()) {
+                Some(started) if status == "online" => format_uptime(started),
+                _ => "—".to_string(),
+            };
+            PM2Process {
+                name: process.get("name").and_then(|n| n.as_str()).unwrap_or("?").to_string(),
+                id: process.get("pm_id").and_then(|i| i.as_u64()).unwrap_or_default() as u32,
+                status,
+                cpu: format!("{}%", monit.and_then(|m| m.get("cpu")).and_then(|c| c.as_f64()).unwrap_or_default()),
+                memory: format_size(monit.and_then(|m| m.get("memory")).and_then(|m| m.as_u64()).unwrap_or_default()),
+                uptime,
+            }
+        })
+        .collect();
+    Some(processes)
+}
 
 impl NodeUI {
 
     pub fn run_npm_script(&mut self, service: &LandoService, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>, is_loading: &mut bool, script: &str) {
         *is_loading = true;
-        let command = format!("npm run {}", script);
+        let command = self.package_manager.run_script_command(script);
         run_shell_command(
             sender.clone(),
             project_path.clone(),
@@ -21,7 +291,7 @@ impl NodeUI {
     pub fn execute_npm_command(&mut self, service: &LandoService, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>, is_loading: &mut bool) {
         if !self.npm_command_input.trim().is_empty() {
             *is_loading = true;
-            let command = format!("npm {}", self.npm_command_input);
+            let command = format!("{} {}", self.package_manager.binary(), self.npm_command_input);
             run_shell_command(
                 sender.clone(),
                 project_path.clone(),
@@ -34,54 +304,549 @@ impl NodeUI {
     pub fn install_package(&mut self, service: &LandoService, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>, is_loading: &mut bool) {
         if !self.package_name.trim().is_empty() {
             *is_loading = true;
-            let version_part = if !self.package_version.is_empty() {
-                format!("@{}", self.package_version)
+            let version = self.package_version.trim();
+            let version_part = if !version.is_empty() {
+                format!("@{}", version)
             } else {
                 String::new()
             };
 
-            let flag = match self.dependency_type {
-                DependencyType::Development => " --save-dev",
-                DependencyType::Peer => " --save-peer",
-                DependencyType::Optional => " --save-optional",
-                _ => " --save",
-            };
+            let package = format!("{}{}", self.package_name.trim(), version_part);
+            let command = self.package_manager.add_command(&package, &self.dependency_type);
+            self.run_package_change(service, project_path, sender, command);
+        }
+    }
 
-            let command = format!("npm install {}{}{}", self.package_name, version_part, flag);
-            run_shell_command(
+    pub fn uninstall_package(&mut self, service: &LandoService, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>, is_loading: &mut bool, package: &str) {
+        *is_loading = true;
+        self.run_package_change(service, project_path, sender, self.package_manager.remove_command(package));
+    }
+
+    pub fn update_package(&mut self, service: &LandoService, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>, is_loading: &mut bool, package: &str) {
+        *is_loading = true;
+        self.run_package_change(service, project_path, sender, self.package_manager.update_command(package));
+    }
+
+    // Install/uninstall/update: la salida va al panel de logs y al terminar se recarga la lista
+    fn run_package_change(&mut self, service: &LandoService, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>, command: String) {
+        let request_id = next_request_id();
+        self.pending_requests.insert(request_id, NodePendingRequest::PackageChange { command: command.clone() });
+        run_shell_capture_tagged(sender.clone(), project_path.clone(), service.service.clone(), command, request_id);
+    }
+
+    pub fn search_package(&mut self, service: &LandoService, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>, is_loading: &mut bool) {
+        let query = self.package_name.trim().to_string();
+        if query.is_empty() {
+            return;
+        }
+
+        *is_loading = true;
+        let request_id = next_request_id();
+        self.pending_requests.insert(request_id, NodePendingRequest::SearchPackage { query: query.clone() });
+        run_shell_capture_tagged(
+            sender.clone(),
+            project_path.clone(),
+            service.service.clone(),
+            format!("npm search {}", shell_quote(&query)),
+            request_id,
+        );
+    }
+
+    pub fn refresh_packages_list(&mut self, service: &LandoService, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>, is_loading: &mut bool) {
+        *is_loading = true;
+        let global = self.show_global_packages;
+        let manager = self.package_manager;
+
+        let request_id = next_request_id();
+        self.pending_requests.insert(request_id, NodePendingRequest::ListPackages { global, manager });
+        run_shell_capture_tagged(sender.clone(), project_path.clone(), service.service.clone(), manager.list_command(global).to_string(), request_id);
+
+        if !global {
+            let request_id = next_request_id();
+            self.pending_requests.insert(request_id, NodePendingRequest::OutdatedPackages { manager });
+            run_shell_capture_tagged(
                 sender.clone(),
                 project_path.clone(),
                 service.service.clone(),
-                command,
+                manager.outdated_command().to_string(),
+                request_id,
             );
         }
     }
 
+    pub fn detect_package_manager(&mut self, service: &LandoService, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>) {
+        let request_id = next_request_id();
+        self.pending_requests.insert(request_id, NodePendingRequest::DetectPackageManager);
+        run_shell_capture_tagged(sender.clone(), project_path.clone(), service.service.clone(), DETECT_PACKAGE_MANAGER_COMMAND.to_string(), request_id);
+    }
+
+    // `npm ls --json`: { "dependencies": { nombre: { version, description? } }, "devDependencies"?: {...} }
+    pub fn parse_npm_ls(&self, json: &str) -> Option<Vec<PackageInfo>> {
+        let value: serde_json::Value = serde_json::from_str(json.trim()).ok()?;
+        let dev_names: Vec<&String> = value.get("devDependencies")
+            .and_then(|d| d.as_object())
+            .map(|d| d.keys().collect())
+            .unwrap_or_default();
+
+        let mut packages: Vec<PackageInfo> = value.get("dependencies")
+            .and_then(|d| d.as_object())
+            .map(|deps| {
+                deps.iter()
+                    .map(|(name, info)| PackageInfo {
+                        name: name.clone(),
+                        version: info.get("version").and_then(|v| v.as_str()).unwrap_or("?").to_string(),
+                        description: info.get("description").and_then(|v| v.as_str()).map(String::from),
+                        is_dev_dependency: dev_names.contains(&name),
+                        is_outdated: false,
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        packages.sort_by(|a, b| a.name.cmp(&b.name));
+        Some(packages)
+    }
+
+    pub fn apply_outdated(&mut self, manager: PackageManager, output: &str) {
+        let outdated = outdated_package_names(manager, output);
+        for package in &mut self.installed_packages {
+            package.is_outdated = outdated.contains(&package.name);
+        }
+    }
+
+    pub fn handle_tagged_result(&mut self, request_id: u64, output: &Result<String, String>) -> bool {
+        let Some(request) = self.pending_requests.remove(&request_id) else {
+            return false;
+        };
+
+        match (request, output) {
+            (NodePendingRequest::ListPackages { global, manager }, Ok(text)) => {
+                let packages = match (manager, global) {
+                    (PackageManager::Npm, _) | (PackageManager::Yarn, false) => self.parse_npm_ls(text),
+                    (PackageManager::Pnpm, _) => parse_pnpm_ls(text),
+                    (PackageManager::Yarn, true) => Some(parse_yarn_global_list(text)),
+                };
+                match packages {
+                    Some(packages) => self.installed_packages = packages,
+                    None => self.append_log(&format!("⚠️ No se pudo interpretar el listado de paquetes de {}", manager.binary())),
+                }
+            }
+            (NodePendingRequest::OutdatedPackages { manager }, Ok(text)) => self.apply_outdated(manager, text),
+            (NodePendingRequest::DetectPackageManager, Ok(text)) => {
+                if !self.package_manager_manual {
+                    if let Some((manager, hint)) = detect_package_manager(text) {
+                        let changed = manager != self.package_manager;
+                        self.package_manager = manager;
+                        self.package_manager_hint = Some(hint);
+                        // La lista se pudo pedir con npm antes de terminar la detección
+                        self.needs_packages_refresh |= changed && !self.installed_packages.is_empty();
+                    }
+                }
+            }
+            (NodePendingRequest::PackageChange { command }, Ok(text)) => {
+                self.append_log(&format!("$ {}\n{}", command, text));
+                self.needs_packages_refresh = true;
+            }
+            (NodePendingRequest::PackageChange { command }, Err(err)) => {
+                self.append_log(&format!("$ {}\n❌ {}", command, err));
+                self.needs_packages_refresh = true;
+            }
+            (NodePendingRequest::Coverage { command }, Ok(text)) => {
+                self.append_log(&format!("$ {}\n{}", command, text));
+                self.coverage_summary = self.parse_coverage_summary(text);
+                if self.coverage_summary.is_none() {
+                    self.append_log("⚠️ No se encontró el resumen de cobertura en la salida");
+                }
+            }
+            (NodePendingRequest::Pm2List, Ok(text)) => {
+                match parse_pm2_jlist(text) {
+                    Some(processes) => self.pm2_processes = processes,
+                    None => self.append_log(&format!("⚠️ No se pudo interpretar la salida de pm2 jlist\n{}", text.trim())),
+                }
+            }
+            (NodePendingRequest::Pm2Command { command }, Ok(text)) => {
+                self.append_log(&format!("$ {}\n{}", command, text));
+                self.needs_pm2_refresh = true;
+            }
+            (NodePendingRequest::Pm2Command { command }, Err(err)) => {
+                self.append_log(&format!("$ {}\n❌ {}", command, err));
+                self.current_tab = NodeTab::Logs;
+                self.needs_pm2_refresh = true;
+            }
+            // Cada lectura sustituye a la anterior: es una vista del final del log
+            (NodePendingRequest::Logs { command }, Ok(text)) => {
+                self.logs_output = format!("$ {}\n{}", command, text);
+            }
+            (NodePendingRequest::Logs { command }, Err(err)) => {
+                self.logs_output = format!("$ {}\n❌ {}", command, err);
+                self.logs_auto_refresh = false;
+            }
+            (NodePendingRequest::NpmConfigList, Ok(text)) => {
+                self.npm_config = Some(parse_npm_config_list(text));
+            }
+            (NodePendingRequest::NpmrcRead, Ok(text)) => {
+                let (path, content) = text.split_once('\n').unwrap_or((text.as_str(), ""));
+                let path = path.trim();
+                if path.is_empty() {
+                    self.append_log("⚠️ npm no devolvió la ruta de la configuración global");
+                } else {
+                    self.npmrc_editor = Some(NpmrcEditor {
+                        scope: NpmConfigScope::Global,
+                        path: path.to_string(),
+                        original: content.to_string(),
+                        content: content.to_string(),
+                    });
+                }
+            }
+            (NodePendingRequest::NpmrcWrite { path }, Ok(_)) => {
+                self.append_log(&format!("✅ Guardado {}", path));
+                self.npmrc_editor = None;
+                self.needs_npm_config_refresh = true;
+            }
+            (NodePendingRequest::NpmrcWrite { path }, Err(err)) => {
+                self.append_log(&format!("❌ No se pudo guardar {}: {}", path, err));
+                self.current_tab = NodeTab::Logs;
+            }
+            (NodePendingRequest::SearchPackage { query }, Ok(text)) => {
+                self.append_log(&format!("$ npm search {}\n{}", query, text));
+                self.current_tab = NodeTab::Logs;
+            }
+            (_, Err(err)) => self.append_log(&format!("❌ {}", err)),
+        }
+
+        true
+    }
+
+    fn append_log(&mut self, text: &str) {
+        if !self.logs_output.is_empty() && !self.logs_output.ends_with('\n') {
+            self.logs_output.push('\n');
+        }
+        self.logs_output.push_str(text);
+    }
+
+    pub fn run_eslint(&mut self, service: &LandoService, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>, is_loading: &mut bool) {
+        self.run_dev_tool(service, project_path, sender, is_loading, "npx eslint .");
+    }
+
+    pub fn run_prettier(&mut self, service: &LandoService, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>, is_loading: &mut bool, write: bool) {
+        let command = if write { "npx prettier --write ." } else { "npx prettier --check ." };
+        self.run_dev_tool(service, project_path, sender, is_loading, command);
+    }
+
+    pub fn run_tests(&mut self, service: &LandoService, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>, is_loading: &mut bool) {
+        let command = format!("{} test", self.package_manager.binary());
+        self.run_dev_tool(service, project_path, sender, is_loading, &command);
+    }
+
+    fn run_dev_tool(&mut self, service: &LandoService, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>, is_loading: &mut bool, command: &str) {
+        *is_loading = true;
+        self.append_log(&format!("$ {}", command));
+        run_shell_command(sender.clone(), project_path.clone(), service.service.clone(), command.to_string());
+    }
+
+    // La salida se captura (no se transmite) para poder extraer el resumen de porcentajes.
+    // Jest escribe los resultados de los tests en stderr y sale con error si alguno falla.
+    pub fn run_coverage(&mut self, service: &LandoService, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>, is_loading: &mut bool) {
+        let base = if self.available_scripts.iter().any(|s| s == "coverage") {
+            self.package_manager.run_script_command("coverage")
+        } else {
+            "npx jest --coverage".to_string()
+        };
+        let command = format!("{} 2>&1 || true", base);
+
+        *is_loading = true;
+        self.coverage_summary = None;
+        let request_id = next_request_id();
+        self.pending_requests.insert(request_id, NodePendingRequest::Coverage { command: base.clone() });
+        run_shell_capture_tagged(sender.clone(), project_path.clone(), service.service.clone(), command, request_id);
+    }
+
+    // Acepta la tabla de Istanbul (`All files | 85.71 | 50 | 100 | 85.71 |`)
+    // y el formato text-summary (`Statements   : 85.71% ( 6/7 )`)
+    pub fn parse_coverage_summary(&self, output: &str) -> Option<CoverageSummary> {
+        let parse_pct = |s: &str| s.trim().trim_end_matches('%').trim().parse::<f32>().ok();
+
+        if let Some(line) = output.lines().find(|l| l.trim_start().starts_with("All files")) {
+            let cells: Vec<&str> = line.split('|').map(str::trim).collect();
+            if cells.len() >= 5 {
+                return Some(CoverageSummary {
+                    statements: parse_pct(cells[1]),
+                    branches: parse_pct(cells[2]),
+                    functions: parse_pct(cells[3]),
+                    lines: parse_pct(cells[4]),
+                });
+            }
+        }
+
+        let mut summary = CoverageSummary::default();
+        for line in output.lines() {
+            let Some((label, rest)) = line.split_once(':') else { continue };
+            let value = rest.split_whitespace().next().and_then(parse_pct);
+            match label.trim() {
+                "Statements" => summary.statements = value,
+                "Branches" => summary.branches = value,
+                "Functions" => summary.functions = value,
+                "Lines" => summary.lines = value,
+                _ => {}
+            }
+        }
+
+        (summary != CoverageSummary::default()).then_some(summary)
+    }
+
+    pub fn start_debug_session(&mut self, service: &LandoService, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>, is_loading: &mut bool) {
+        self.launch_node_debug(service, project_path, sender, is_loading, "--inspect");
+    }
+
+    // Igual que la sesión de debug pero detenido en la primera línea hasta que se conecte el depurador
+    pub fn start_inspector(&mut self, service: &LandoService, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>, is_loading: &mut bool) {
+        self.launch_node_debug(service, project_path, sender, is_loading, "--inspect-brk");
+    }
+
+    // Perfil de CPU en `.profiles/` del proyecto (montado en el host), abrible desde DevTools
+    pub fn start_profiling(&mut self, service: &LandoService, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>, is_loading: &mut bool) {
+        let env = self.node_env();
+        let command = format!(
+            "NODE_ENV={} node --cpu-prof --cpu-prof-dir=.profiles {}",
+            env,
+            self.debug_entry_arg()
+        );
+        *is_loading = true;
+        self.append_log(&format!("$ {}\n📊 El perfil .cpuprofile se guardará en .profiles/ al terminar el proceso", command));
+        run_shell_command(sender.clone(), project_path.clone(), service.service.clone(), command);
+    }
+
+    fn launch_node_debug(&mut self, service: &LandoService, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>, is_loading: &mut bool, inspect_flag: &str) {
+        let port = match self.debug_port.trim().parse::<u16>() {
+            Ok(port) if port > 0 => port,
+            _ => {
+                self.debug_warning = Some(format!("Puerto de debug no válido: '{}'", self.debug_port));
+                return;
+            }
+        };
+
+        // 0.0.0.0 para que el inspector escuche fuera del contenedor
+        let command = format!(
+            "NODE_ENV={} node {}=0.0.0.0:{} {}",
+            self.node_env(),
+            inspect_flag,
+            port,
+            self.debug_entry_arg()
+        );
+
+        let host = service.external_connection.as_ref()
+            .map(|c| c.host.clone())
+            .filter(|h| !h.is_empty())
+            .unwrap_or_else(|| "localhost".to_string());
+        self.debug_urls = vec![
+            "chrome://inspect/#devices".to_string(),
+            format!("ws://{}:{}", host, port),
+            format!("devtools://devtools/bundled/js_app.html?experiments=true&v8only=true&ws={}:{}", host, port),
+        ];
+        self.debug_warning = self.debug_port_warning(service, port);
+
+        *is_loading = true;
+        self.append_log(&format!("$ {}\n🐛 Inspector en ws://{}:{}", command, host, port));
+        run_shell_command(sender.clone(), project_path.clone(), service.service.clone(), command);
+    }
+
+    // El inspector solo es alcanzable desde el host si Lando publica el puerto
+    fn debug_port_warning(&self, service: &LandoService, port: u16) -> Option<String> {
+        let port_str = port.to_string();
+        let exposed_by_connection = service.external_connection.as_ref().is_some_and(|c| c.port == port_str);
+        let exposed_by_url = service.urls.iter().any(|u| u.contains(&format!(":{}", port_str)));
+
+        if exposed_by_connection || exposed_by_url {
+            None
+        } else {
+            Some(format!(
+                "El puerto {0} no parece expuesto al host. Añade en .lando.yml: services > {1} > overrides > ports: ['{0}:{0}'] y ejecuta lando rebuild",
+                port, service.service
+            ))
+        }
+    }
+
+    fn node_env(&self) -> String {
+        match &self.environment_mode {
+            EnvironmentMode::Development => "development".to_string(),
+            EnvironmentMode::Production => "production".to_string(),
+            EnvironmentMode::Test => "test".to_string(),
+            EnvironmentMode::Custom(env) => env.clone(),
+        }
+    }
+
+    fn debug_entry_arg(&self) -> String {
+        let entry = self.debug_entry.trim();
+        if entry.is_empty() { ".".to_string() } else { entry.to_string() }
+    }
+
+    pub fn refresh_pm2_processes(&mut self, service: &LandoService, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>, is_loading: &mut bool) {
+        *is_loading = true;
+        self.ecosystem_files = None;
+        let request_id = next_request_id();
+        self.pending_requests.insert(request_id, NodePendingRequest::Pm2List);
+        run_shell_capture_tagged(sender.clone(), project_path.clone(), service.service.clone(), "pm2 jlist".to_string(), request_id);
+    }
+
+    // Vale tanto un ecosystem file como un script suelto (`pm2 start server.js`)
+    pub fn pm2_start(&mut self, service: &LandoService, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>, is_loading: &mut bool) {
+        let target = self.pm2_start_target.trim();
+        if target.is_empty() {
+            self.append_log("⚠️ Indica el ecosystem file o el script que debe arrancar PM2");
+            return;
+        }
+        let command = format!("pm2 start {}", shell_quote(target));
+        self.run_pm2_command(service, project_path, sender, is_loading, command);
+    }
+
+    pub fn pm2_stop_all(&mut self, service: &LandoService, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>, is_loading: &mut bool) {
+        self.run_pm2_command(service, project_path, sender, is_loading, "pm2 stop all".to_string());
+    }
+
+    pub fn pm2_restart_all(&mut self, service: &LandoService, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>, is_loading: &mut bool) {
+        self.run_pm2_command(service, project_path, sender, is_loading, "pm2 restart all".to_string());
+    }
+
+    pub fn pm2_delete_process(&mut self, service: &LandoService, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>, is_loading: &mut bool, name: &str) {
+        self.run_pm2_command(service, project_path, sender, is_loading, format!("pm2 delete {}", shell_quote(name)));
+    }
+
+    pub fn pm2_stop_process(&mut self, service: &LandoService, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>, is_loading: &mut bool, name: &str) {
+        self.run_pm2_command(service, project_path, sender, is_loading, format!("pm2 stop {}", shell_quote(name)));
+    }
+
+    pub fn pm2_restart_process(&mut self, service: &LandoService, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>, is_loading: &mut bool, name: &str) {
+        self.run_pm2_command(service, project_path, sender, is_loading, format!("pm2 restart {}", shell_quote(name)));
+    }
+
+    // Como los cambios de paquetes: salida al panel de logs y la lista se relee al terminar
+    fn run_pm2_command(&mut self, service: &LandoService, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>, is_loading: &mut bool, command: String) {
+        *is_loading = true;
+        let request_id = next_request_id();
+        self.pending_requests.insert(request_id, NodePendingRequest::Pm2Command { command: command.clone() });
+        run_shell_capture_tagged(sender.clone(), project_path.clone(), service.service.clone(), command, request_id);
+    }
+
+    // Repite la última consulta de logs; la primera vez, los de PM2
+    pub fn refresh_logs(&mut self, service: &LandoService, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>, is_loading: &mut bool) {
+        match self.logs_source.clone() {
+            Some(NodeLogSource::Npm) => self.show_npm_logs(service, project_path, sender, is_loading),
+            Some(NodeLogSource::Pm2(_)) | None => self.show_pm2_logs(service, project_path, sender, is_loading),
+        }
+    }
+
+    pub fn show_npm_logs(&mut self, service: &LandoService, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>, is_loading: &mut bool) {
+        self.logs_source = Some(NodeLogSource::Npm);
+        self.fetch_logs(service, project_path, sender, is_loading, NPM_LOGS_COMMAND.to_string());
+    }
+
+    // `--nostream` saca las últimas líneas y termina en vez de quedarse siguiendo el log
+    pub fn show_pm2_logs(&mut self, service: &LandoService, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>, is_loading: &mut bool) {
+        let target = self.pm2_log_target.clone();
+        let command = match &target {
+            Some(name) => format!("pm2 logs {} --lines {} --nostream", shell_quote(name), LOG_LINES),
+            None => format!("pm2 logs --lines {} --nostream", LOG_LINES),
+        };
+        self.logs_source = Some(NodeLogSource::Pm2(target));
+        self.fetch_logs(service, project_path, sender, is_loading, command);
+    }
+
+    fn fetch_logs(&mut self, service: &LandoService, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>, is_loading: &mut bool, command: String) {
+        // Con la auto-recarga no se encadenan peticiones si la anterior sigue en curso
+        if self.pending_requests.values().any(|r| matches!(r, NodePendingRequest::Logs { .. })) {
+            return;
+        }
+        *is_loading = true;
+        self.logs_refreshed_at = Some(Instant::now());
+        let request_id = next_request_id();
+        self.pending_requests.insert(request_id, NodePendingRequest::Logs { command: command.clone() });
+        run_shell_capture_tagged(sender.clone(), project_path.clone(), service.service.clone(), command, request_id);
+    }
+
+    pub fn show_npm_config(&mut self, service: &LandoService, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>, is_loading: &mut bool) {
+        *is_loading = true;
+        let request_id = next_request_id();
+        self.pending_requests.insert(request_id, NodePendingRequest::NpmConfigList);
+        run_shell_capture_tagged(sender.clone(), project_path.clone(), service.service.clone(), "npm config list".to_string(), request_id);
+    }
+
+    // El .npmrc del proyecto está montado en el contenedor y se edita desde el host;
+    // el global solo existe dentro del contenedor
+    pub fn edit_npm_config(&mut self, service: &LandoService, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>, is_loading: &mut bool) {
+        match self.npm_config_scope {
+            NpmConfigScope::Project => {
+                let path = project_path.join(".npmrc");
+                let content = match std::fs::read_to_string(&path) {
+                    Ok(content) => content,
+                    Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+                    Err(e) => {
+                        self.append_log(&format!("❌ No se pudo leer {}: {}", path.display(), e));
+                        return;
+                    }
+                };
+                self.npmrc_editor = Some(NpmrcEditor {
+                    scope: NpmConfigScope::Project,
+                    path: path.display().to_string(),
+                    original: content.clone(),
+                    content,
+                });
+            }
+            NpmConfigScope::Global => {
+                *is_loading = true;
+                let request_id = next_request_id();
+                self.pending_requests.insert(request_id, NodePendingRequest::NpmrcRead);
+                run_shell_capture_tagged(sender.clone(), project_path.clone(), service.service.clone(), GLOBAL_NPMRC_READ_COMMAND.to_string(), request_id);
+            }
+        }
+    }
+
+    pub fn save_npmrc(&mut self, service: &LandoService, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>, is_loading: &mut bool) {
+        let Some(editor) = self.npmrc_editor.clone() else {
+            return;
+        };
+
+        match editor.scope {
+            NpmConfigScope::Project => {
+                if let Err(e) = std::fs::write(&editor.path, &editor.content) {
+                    self.append_log(&format!("❌ No se pudo guardar {}: {}", editor.path, e));
+                    return;
+                }
+                self.append_log(&format!("✅ Guardado {}", editor.path));
+                self.npmrc_editor = None;
+                self.show_npm_config(service, project_path, sender, is_loading);
+            }
+            NpmConfigScope::Global => {
+                *is_loading = true;
+                let path = shell_quote(&editor.path);
+                let command = format!(
+                    "mkdir -p \"$(dirname {})\" && printf '%s' {} > {}",
+                    path, shell_quote(&editor.content), path
+                );
+                let request_id = next_request_id();
+                self.pending_requests.insert(request_id, NodePendingRequest::NpmrcWrite { path: editor.path });
+                // El npmrc global suele estar bajo /usr/local, que solo puede escribir root
+                run_root_shell_capture_tagged(sender.clone(), project_path.clone(), service.service.clone(), command, request_id);
+            }
+        }
+    }
+
     // Implementaciones básicas para otros métodos (placeholders)
     pub fn load_package_json(&mut self, _service: &LandoService, _project_path: &PathBuf, _sender: &Sender<LandoCommandOutcome>, _is_loading: &mut bool) {}
     pub fn save_package_json(&mut self, _service: &LandoService, _project_path: &PathBuf, _sender: &Sender<LandoCommandOutcome>, _is_loading: &mut bool) {}
-    pub fn search_package(&mut self, _service: &LandoService, _project_path: &PathBuf, _sender: &Sender<LandoCommandOutcome>, _is_loading: &mut bool) {}
-    pub fn refresh_packages_list(&mut self, _service: &LandoService, _project_path: &PathBuf, _sender: &Sender<LandoCommandOutcome>, _is_loading: &mut bool) {}
-    pub fn uninstall_package(&mut self, _service: &LandoService, _project_path: &PathBuf, _sender: &Sender<LandoCommandOutcome>, _is_loading: &mut bool, _package: &str) {}
-    pub fn update_package(&mut self, _service: &LandoService, _project_path: &PathBuf, _sender: &Sender<LandoCommandOutcome>, _is_loading: &mut bool, _package: &str) {}
-    pub fn start_debug_session(&mut self, _service: &LandoService, _project_path: &PathBuf, _sender: &Sender<LandoCommandOutcome>, _is_loading: &mut bool) {}
-    pub fn start_inspector(&mut self, _service: &LandoService, _project_path: &PathBuf, _sender: &Sender<LandoCommandOutcome>, _is_loading: &mut bool) {}
-    pub fn start_profiling(&mut self, _service: &LandoService, _project_path: &PathBuf, _sender: &Sender<LandoCommandOutcome>, _is_loading: &mut bool) {}
-    pub fn run_eslint(&mut self, _service: &LandoService, _project_path: &PathBuf, _sender: &Sender<LandoCommandOutcome>, _is_loading: &mut bool) {}
-    pub fn run_prettier(&mut self, _service: &LandoService, _project_path: &PathBuf, _sender: &Sender<LandoCommandOutcome>, _is_loading: &mut bool) {}
-    pub fn run_tests(&mut self, _service: &LandoService, _project_path: &PathBuf, _sender: &Sender<LandoCommandOutcome>, _is_loading: &mut bool) {}
-    pub fn run_coverage(&mut self, _service: &LandoService, _project_path: &PathBuf, _sender: &Sender<LandoCommandOutcome>, _is_loading: &mut bool) {}
-    pub fn show_npm_config(&mut self, _service: &LandoService, _project_path: &PathBuf, _sender: &Sender<LandoCommandOutcome>, _is_loading: &mut bool) {}
-    pub fn edit_npm_config(&mut self, _service: &LandoService, _project_path: &PathBuf, _sender: &Sender<LandoCommandOutcome>, _is_loading: &mut bool) {}
-    pub fn refresh_pm2_processes(&mut self, _service: &LandoService, _project_path: &PathBuf, _sender: &Sender<LandoCommandOutcome>, _is_loading: &mut bool) {}
-    pub fn pm2_start(&mut self, _service: &LandoService, _project_path: &PathBuf, _sender: &Sender<LandoCommandOutcome>, _is_loading: &mut bool) {}
-    pub fn pm2_stop_all(&mut self, _service: &LandoService, _project_path: &PathBuf, _sender: &Sender<LandoCommandOutcome>, _is_loading: &mut bool) {}
-    pub fn pm2_restart_all(&mut self, _service: &LandoService, _project_path: &PathBuf, _sender: &Sender<LandoCommandOutcome>, _is_loading: &mut bool) {}
-    pub fn pm2_delete_process(&mut self, _service: &LandoService, _project_path: &PathBuf, _sender: &Sender<LandoCommandOutcome>, _is_loading: &mut bool, _name: &str) {}
-    pub fn pm2_stop_process(&mut self, _service: &LandoService, _project_path: &PathBuf, _sender: &Sender<LandoCommandOutcome>, _is_loading: &mut bool, _name: &str) {}
-    pub fn pm2_restart_process(&mut self, _service: &LandoService, _project_path: &PathBuf, _sender: &Sender<LandoCommandOutcome>, _is_loading: &mut bool, _name: &str) {}
-    pub fn refresh_logs(&mut self, _service: &LandoService, _project_path: &PathBuf, _sender: &Sender<LandoCommandOutcome>, _is_loading: &mut bool) {}
-    pub fn show_npm_logs(&mut self, _service: &LandoService, _project_path: &PathBuf, _sender: &Sender<LandoCommandOutcome>, _is_loading: &mut bool) {}
-    pub fn show_pm2_logs(&mut self, _service: &LandoService, _project_path: &PathBuf, _sender: &Sender<LandoCommandOutcome>, _is_loading: &mut bool) {}
-
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn package_commands_quote_user_input() {
+        let package = "left-pad; rm -rf /";
+        assert_eq!(
+            PackageManager::Npm.add_command(package, &DependencyType::Development),
+            "npm install 'left-pad; rm -rf /' --save-dev"
+        );
+        assert_eq!(PackageManager::Yarn.remove_command(package), "yarn remove 'left-pad; rm -rf /'");
+        assert_eq!(PackageManager::Pnpm.update_command("it's"), "pnpm update 'it'\\''s'");
+        assert_eq!(PackageManager::Yarn.run_script_command("build:prod"), "yarn run 'build:prod'");
+    }
+}
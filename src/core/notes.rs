@@ -0,0 +1,150 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use crate::models::notes::{NoteMatch, NotesLocation, ProjectNotes};
+
+pub const NOTES_FILE_NAME: &str = ".lando-gui-notes.md";
+// Pausa tras la última tecla antes de escribir el fichero
+pub const NOTES_AUTOSAVE_DELAY: Duration = Duration::from_secs(1);
+// Cada cuánto se mira si otro programa tocó el fichero
+pub const NOTES_DISK_CHECK_INTERVAL: Duration = Duration::from_secs(2);
+pub const NOTES_SEARCH_MAX_RESULTS: usize = 50;
+
+// En los datos de la app el nombre sale de la ruta del proyecto, así dos
+// proyectos con el mismo nombre de carpeta no comparten notas
+pub fn notes_path(project: &Path, location: NotesLocation) -> Option<PathBuf> {
+    match location {
+        NotesLocation::Project => Some(project.join(NOTES_FILE_NAME)),
+        NotesLocation::AppData => {
+            let name: String = project.to_string_lossy()
+                .chars()
+                .map(|c| if c.is_alphanumeric() || c == '-' { c } else { '_' })
+                .collect();
+            eframe::storage_dir("Lando GUI").map(|dir| dir.join("notes").join(format!("{}.md", name.trim_matches('_'))))
+        }
+    }
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+pub fn modified_secs(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or_default()
+}
+
+impl ProjectNotes {
+    // Un fichero que no existe son unas notas vacías; se crea al escribir
+    pub fn load(project: &Path, location: NotesLocation) -> Self {
+        let path = notes_path(project, location);
+        let (text, error) = match path.as_deref().map(fs::read_to_string) {
+            Some(Ok(text)) => (text, None),
+            Some(Err(e)) if e.kind() == io::ErrorKind::NotFound => (String::new(), None),
+            Some(Err(e)) => (String::new(), Some(e.to_string())),
+            None => (String::new(), Some("sin directorio de datos".to_string())),
+        };
+        Self {
+            project: project.to_path_buf(),
+            modified: path.as_deref().and_then(modified_time),
+            path,
+            text,
+            editing: false,
+            dirty_since: None,
+            changed_on_disk: false,
+            last_disk_check: Instant::now(),
+            error,
+        }
+    }
+
+    pub fn mark_dirty(&mut self) {
+        if self.dirty_since.is_none() {
+            self.dirty_since = Some(Instant::now());
+        }
+    }
+
+    // No se pisa un fichero que cambió fuera hasta que el usuario decida
+    pub fn autosave_due(&self) -> bool {
+        !self.changed_on_disk && self.dirty_since.is_some_and(|t| t.elapsed() >= NOTES_AUTOSAVE_DELAY)
+    }
+
+    pub fn save(&mut self) {
+        let Some(path) = self.path.clone() else {
+            return;
+        };
+        let result = path.parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|_| fs::write(&path, &self.text));
+        match result {
+            Ok(()) => {
+                self.dirty_since = None;
+                self.changed_on_disk = false;
+                self.modified = modified_time(&path);
+                self.error = None;
+            }
+            Err(e) => {
+                log::warn!("No se pudieron guardar las notas en {}: {}", path.display(), e);
+                self.error = Some(e.to_string());
+                self.dirty_since = None; // Se reintenta con la siguiente edición
+            }
+        }
+    }
+
+    // Guarda lo pendiente antes de cambiar de proyecto o de ubicación
+    pub fn flush(&mut self) {
+        if self.dirty_since.is_some() && !self.changed_on_disk {
+            self.save();
+        }
+    }
+
+    pub fn check_disk(&mut self) {
+        if self.last_disk_check.elapsed() < NOTES_DISK_CHECK_INTERVAL {
+            return;
+        }
+        self.last_disk_check = Instant::now();
+        if let Some(path) = &self.path {
+            let on_disk = modified_time(path);
+            if on_disk.is_some() && on_disk != self.modified {
+                self.changed_on_disk = true;
+            }
+        }
+    }
+
+    // Descarta lo que no se guardó y vuelve a leer el fichero
+    pub fn reload(&mut self, location: NotesLocation) {
+        let editing = self.editing;
+        *self = Self::load(&self.project.clone(), location);
+        self.editing = editing;
+    }
+}
+
+// Busca en las notas de todos los proyectos, sin distinguir mayúsculas.
+// Todas las palabras de la búsqueda tienen que aparecer en la línea.
+pub fn search_notes(projects: &[PathBuf], location: NotesLocation, query: &str) -> Vec<NoteMatch> {
+    let words: Vec<String> = query.split_whitespace().map(str::to_lowercase).collect();
+    if words.is_empty() {
+        return Vec::new();
+    }
+
+    let mut matches = Vec::new();
+    for project in projects {
+        let Some(text) = notes_path(project, location).and_then(|path| fs::read_to_string(path).ok()) else {
+            continue;
+        };
+        for (index, line) in text.lines().enumerate() {
+            let lower = line.to_lowercase();
+            if words.iter().all(|w| lower.contains(w)) {
+                matches.push(NoteMatch {
+                    project: project.clone(),
+                    line_number: index + 1,
+                    line: line.trim().to_string(),
+                });
+                if matches.len() >= NOTES_SEARCH_MAX_RESULTS {
+                    return matches;
+                }
+            }
+        }
+    }
+    matches
+}
@@ -0,0 +1,214 @@
+use std::sync::LazyLock;
+use std::time::Instant;
+
+use regex::Regex;
+
+use crate::models::phases::{BuildPhase, PhaseState, PhaseTracker};
+
+// Comandos cuya salida tiene fases reconocibles
+pub const PHASE_COMMANDS: &[&str] = &["start", "rebuild", "restart", "destroy"];
+
+// Regla de la tabla de fases. `{}` en la clave y las etiquetas se sustituye
+// por el primer grupo capturado (el servicio), si lo hay.
+struct PhaseRule {
+    pattern: &'static str,
+    key: &'static str,
+    label: &'static str,
+    done_label: &'static str,
+    completes: bool, // La línea cierra la fase en vez de abrirla
+}
+
+// Salida de lando v3 con docker-compose v1 (`Pulling appserver ... done`) y
+// v2 (` appserver Pulled`). Lo que no encaja solo va al terminal.
+const PHASE_RULES: &[PhaseRule] = &[
+    PhaseRule { pattern: r"^Pulling\s+([\w.-]+)\s*\.\.\.\s*done", key: "pull:{}", label: "⬇️ Descargando imagen {}", done_label: "🟢 Imagen descargada {}", completes: true },
+    PhaseRule { pattern: r"^Pulling\s+([\w.-]+)", key: "pull:{}", label: "⬇️ Descargando imagen {}", done_label: "🟢 Imagen descargada {}", completes: false },
+    PhaseRule { pattern: r"^([\w.-]+)\s+Pulled\b", key: "pull:{}", label: "⬇️ Descargando imagen {}", done_label: "🟢 Imagen descargada {}", completes: true },
+    PhaseRule { pattern: r"^([\w.-]+)\s+Pulling\b", key: "pull:{}", label: "⬇️ Descargando imagen {}", done_label: "🟢 Imagen descargada {}", completes: false },
+    PhaseRule { pattern: r"^Building\s+([\w.-]+)", key: "build:{}", label: "🔨 Construyendo {}", done_label: "🟢 Imagen construida {}", completes: false },
+    PhaseRule { pattern: r"^(?:Creating|Starting|Recreating)\s+[\w.-]+|^Container\s+[\w.-]+\s+(?:Creat|Start)", key: "containers", label: "🚀 Arrancando contenedores", done_label: "🟢 Contenedores arrancados", completes: false },
+    PhaseRule { pattern: r"(?i)build\s*steps?(?:\s+(?:for|on)\s+([\w.-]+))?", key: "steps:{}", label: "🔄 Ejecutando build steps {}", done_label: "🟢 Build steps {}", completes: false },
+    PhaseRule { pattern: r"(?i)waiting until\s+([\w.-]+)\s+service is ready", key: "wait:{}", label: "⏳ Esperando a {}", done_label: "🟢 {} listo", completes: false },
+    PhaseRule { pattern: r"(?i)^scanning", key: "scan", label: "⏳ Escaneando URLs", done_label: "🟢 URLs comprobadas", completes: false },
+    PhaseRule { pattern: r"^(?:Stopping|Removing)\s+[\w.-]+|^Container\s+[\w.-]+\s+(?:Stopp|Remov)", key: "remove", label: "🧹 Parando y eliminando contenedores", done_label: "🟢 Contenedores eliminados", completes: false },
+    PhaseRule { pattern: r"(?i)boomshakalaka|started up correctly|app destroyed|has been destroyed|rebuilt", key: "done", label: "✅ Listo", done_label: "✅ Listo", completes: true },
+];
+
+static COMPILED_RULES: LazyLock<Vec<(Regex, &'static PhaseRule)>> = LazyLock::new(|| {
+    PHASE_RULES.iter().map(|rule| (Regex::new(rule.pattern).unwrap(), rule)).collect()
+});
+
+// Colores y movimientos de cursor que lando mete en la salida
+static ANSI_ESCAPE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\x1b\[[0-9;?]*[A-Za-z]").unwrap());
+
+pub fn tracks_phases(command: &str) -> bool {
+    command.split_whitespace().next().is_some_and(|verb| PHASE_COMMANDS.contains(&verb))
+}
+
+fn fill(template: &str, service: &str) -> String {
+    template.replace("{}", service).trim().to_string()
+}
+
+// `pull:appserver` -> `pull`: las fases del mismo grupo pueden ir en paralelo
+fn group(key: &str) -> &str {
+    key.split(':').next().unwrap_or(key)
+}
+
+impl PhaseTracker {
+    pub fn new(command: &str) -> Self {
+        Self {
+            command: command.to_string(),
+            phases: Vec::new(),
+            started: Instant::now(),
+            result: None,
+            line_buffer: String::new(),
+        }
+    }
+
+    // Trozo de salida tal como llega; las líneas incompletas esperan al siguiente
+    pub fn feed(&mut self, text: &str) {
+        self.line_buffer.push_str(text);
+        while let Some(end) = self.line_buffer.find(['\n', '\r']) {
+            let line: String = self.line_buffer.drain(..=end).collect();
+            let line = ANSI_ESCAPE.replace_all(&line, "");
+            self.feed_line(line.trim());
+        }
+    }
+
+    fn feed_line(&mut self, line: &str) {
+        if line.is_empty() {
+            return;
+        }
+        let Some((captures, rule)) = COMPILED_RULES.iter().find_map(|(regex, rule)| regex.captures(line).map(|c| (c, *rule))) else {
+            return;
+        };
+        let service = captures.get(1).map(|m| m.as_str()).unwrap_or_default();
+        let key = fill(rule.key, service);
+        let now = Instant::now();
+
+        if key == "done" {
+            self.complete_running(|_| true, PhaseState::Done);
+        }
+        if let Some(phase) = self.phases.iter_mut().find(|p| p.key == key) {
+            if rule.completes && phase.state == PhaseState::Running {
+                phase.state = PhaseState::Done;
+                phase.finished = Some(now);
+            }
+            return;
+        }
+
+        // Empieza otra etapa: las de otros grupos que seguían abiertas han terminado
+        let new_group = group(&key).to_string();
+        self.complete_running(|p| group(&p.key) != new_group, PhaseState::Done);
+        self.phases.push(BuildPhase {
+            key,
+            label: fill(rule.label, service),
+            done_label: fill(rule.done_label, service),
+            started: now,
+            finished: rule.completes.then_some(now),
+            state: if rule.completes { PhaseState::Done } else { PhaseState::Running },
+        });
+    }
+
+    fn complete_running(&mut self, filter: impl Fn(&BuildPhase) -> bool, state: PhaseState) {
+        let now = Instant::now();
+        for phase in self.phases.iter_mut().filter(|p| p.state == PhaseState::Running) {
+            if filter(phase) {
+                phase.state = state;
+                phase.finished = Some(now);
+            }
+        }
+    }
+
+    pub fn finish(&mut self, success: bool) {
+        let rest = std::mem::take(&mut self.line_buffer);
+        self.feed_line(rest.trim());
+        self.complete_running(|_| true, if success { PhaseState::Done } else { PhaseState::Failed });
+        self.result = Some(success);
+    }
+
+    // (terminadas, total) para el chip "3/6 pasos"
+    pub fn progress(&self) -> (usize, usize) {
+        let done = self.phases.iter().filter(|p| p.state != PhaseState::Running).count();
+        (done, self.phases.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn phases(tracker: &PhaseTracker) -> Vec<(&str, PhaseState)> {
+        tracker.phases.iter().map(|p| (p.key.as_str(), p.state)).collect()
+    }
+
+    #[test]
+    fn compose_v1_start_transcript() {
+        let mut tracker = PhaseTracker::new("start");
+        tracker.feed("Pulling appserver ... \nPulling database  ... \n");
+        tracker.feed("Pulling appserver ... done\n");
+        assert_eq!(phases(&tracker), vec![("pull:appserver", PhaseState::Done), ("pull:database", PhaseState::Running)]);
+
+        tracker.feed("Building appserver\nStep 1/4 : FROM php:8.1\n");
+        assert_eq!(tracker.phases[1].state, PhaseState::Done);
+        tracker.feed("Creating blog_database_1 ... \nCreating blog_appserver_1 ... done\n");
+        tracker.feed("Waiting until database service is ready...\nScanning to determine which services are ready.\n");
+        tracker.feed("BOOMSHAKALAKA!!!\n");
+        tracker.finish(true);
+
+        assert_eq!(phases(&tracker), vec![
+            ("pull:appserver", PhaseState::Done),
+            ("pull:database", PhaseState::Done),
+            ("build:appserver", PhaseState::Done),
+            ("containers", PhaseState::Done),
+            ("wait:database", PhaseState::Done),
+            ("scan", PhaseState::Done),
+            ("done", PhaseState::Done),
+        ]);
+        assert_eq!(tracker.phases[2].label, "🔨 Construyendo appserver");
+        assert_eq!(tracker.progress(), (7, 7));
+        assert_eq!(tracker.result, Some(true));
+    }
+
+    #[test]
+    fn compose_v2_rebuild_with_colors_and_split_chunks() {
+        let mut tracker = PhaseTracker::new("rebuild -y");
+        tracker.feed(" \x1b[32mappserver\x1b[0m Pulling \n database Pull");
+        tracker.feed("ing\r\n appserver Pulled\n");
+        assert_eq!(phases(&tracker), vec![("pull:appserver", PhaseState::Done), ("pull:database", PhaseState::Running)]);
+
+        tracker.feed(" Container blog-appserver-1  Creating\n Container blog-appserver-1  Started\n");
+        tracker.feed("Running build steps for appserver\n");
+        assert_eq!(tracker.progress(), (3, 4));
+        assert_eq!(tracker.phases[3].label, "🔄 Ejecutando build steps appserver");
+    }
+
+    #[test]
+    fn destroy_transcript() {
+        let mut tracker = PhaseTracker::new("destroy -y");
+        tracker.feed("Stopping blog_appserver_1 ... done\nRemoving blog_appserver_1 ... done\n");
+        tracker.feed("Your app has been destroyed!");
+        assert_eq!(phases(&tracker), vec![("remove", PhaseState::Running)]);
+        // La última línea sin salto se procesa al terminar
+        tracker.finish(true);
+        assert_eq!(phases(&tracker), vec![("remove", PhaseState::Done), ("done", PhaseState::Done)]);
+    }
+
+    #[test]
+    fn failures_mark_running_phases() {
+        let mut tracker = PhaseTracker::new("start");
+        tracker.feed("Pulling appserver ... done\nCreating blog_appserver_1 ... \nERROR: port is already allocated\n");
+        tracker.finish(false);
+        assert_eq!(phases(&tracker), vec![("pull:appserver", PhaseState::Done), ("containers", PhaseState::Failed)]);
+        assert_eq!(tracker.result, Some(false));
+    }
+
+    #[test]
+    fn only_lifecycle_commands_track_phases() {
+        assert!(tracks_phases("start"));
+        assert!(tracks_phases("rebuild -y"));
+        assert!(!tracks_phases("stop"));
+        assert!(!tracks_phases("logs -s appserver"));
+        assert!(!tracks_phases(""));
+    }
+}
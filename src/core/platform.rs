@@ -0,0 +1,253 @@
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::LazyLock;
+
+// En Windows cada proceso de consola lanzado desde una app GUI abre su propia
+// ventana un instante; con esta bandera se crea sin ninguna
+#[cfg(windows)]
+const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+
+#[cfg(windows)]
+pub fn hide_console(command: &mut Command) -> &mut Command {
+    use std::os::windows::process::CommandExt;
+    command.creation_flags(CREATE_NO_WINDOW)
+}
+
+#[cfg(not(windows))]
+pub fn hide_console(command: &mut Command) -> &mut Command {
+    command
+}
+
+// Busca `program` en las carpetas de `path_var` probando las extensiones de
+// `pathext` (`.COM;.EXE;.BAT;.CMD` en Windows; vacío en Unix). Si el nombre ya
+// lleva extensión o ruta se devuelve tal cual. Las entradas vacías del PATH
+// se saltan: no se busca en la carpeta actual.
+pub fn resolve_in_path(
+    program: &str,
+    path_var: &OsStr,
+    pathext: &str,
+    is_file: impl Fn(&Path) -> bool,
+) -> Option<PathBuf> {
+    let program_path = Path::new(program);
+    if program_path.components().count() > 1 || program_path.extension().is_some() {
+        return None;
+    }
+    let extensions: Vec<&str> = if pathext.is_empty() {
+        vec![""]
+    } else {
+        pathext.split(';').map(str::trim).filter(|e| !e.is_empty()).collect()
+    };
+    std::env::split_paths(path_var).filter(|dir| !dir.as_os_str().is_empty()).find_map(|dir| {
+        extensions.iter()
+            .map(|ext| dir.join(format!("{}{}", program, ext.to_lowercase())))
+            .find(|candidate| is_file(candidate))
+    })
+}
+
+// `lando` resuelto una vez. En Windows el instalador deja a veces solo
+// `lando.cmd`, que `Command::new("lando")` no encuentra; con la ruta completa
+// y la extensión, la biblioteca estándar lo lanza a través de cmd.exe escapando
+// los argumentos, y stdout/stderr siguen siendo tuberías sin buffer extra.
+static LANDO_PROGRAM: LazyLock<PathBuf> = LazyLock::new(|| {
+    let pathext = if cfg!(windows) {
+        std::env::var("PATHEXT").unwrap_or_else(|_| ".COM;.EXE;.BAT;.CMD".to_string())
+    } else {
+        String::new()
+    };
+    let resolved = std::env::var_os("PATH")
+        .and_then(|path| resolve_in_path("lando", &path, &pathext, Path::is_file));
+    match resolved {
+        Some(path) => {
+            log::info!("lando encontrado en {}", path.display());
+            path
+        }
+        None => {
+            log::warn!("No se encontró lando en el PATH; se delega en el sistema");
+            PathBuf::from("lando")
+        }
+    }
+});
+
+// Punto único para crear procesos en segundo plano: resuelve `lando` y
+// evita la ventana de consola en Windows
+pub fn command(program: &str) -> Command {
+    let mut command = if program == "lando" {
+        Command::new(LANDO_PROGRAM.as_path())
+    } else {
+        Command::new(program)
+    };
+    hide_console(&mut command);
+    command
+}
+
+// Consultas sobre procesos por pid que dependen del sistema operativo
+
+#[cfg(unix)]
+pub fn process_alive(pid: u32) -> bool {
+    command("kill")
+        .args(["-0", &pid.to_string()])
+        .output()
+        .is_ok_and(|output| output.status.success())
+}
+
+#[cfg(windows)]
+pub fn process_alive(pid: u32) -> bool {
+    let Ok(output) = command("tasklist")
+        .args(["/FI", &format!("PID eq {}", pid), "/FO", "CSV", "/NH"])
+        .output()
+    else {
+        return false;
+    };
+    // "lando.exe","1234",...; sin coincidencias imprime un mensaje "INFO: ..."
+    String::from_utf8_lossy(&output.stdout).trim().starts_with('"')
+}
+
+// Línea de comandos completa, con los argumentos
+#[cfg(unix)]
+pub fn process_command_line(pid: u32) -> Option<String> {
+    let output = command("ps")
+        .args(["-o", "args=", "-p", &pid.to_string()])
+        .output()
+        .ok()?;
+    let line = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (output.status.success() && !line.is_empty()).then_some(line)
+}
+
+// `tasklist` solo da el ejecutable, y los shims `lando.cmd` corren como
+// `cmd.exe`: la línea completa sale de WMI
+#[cfg(windows)]
+pub fn process_command_line(pid: u32) -> Option<String> {
+    let query = format!("(Get-CimInstance Win32_Process -Filter 'ProcessId = {}').CommandLine", pid);
+    let output = command("powershell")
+        .args(["-NoProfile", "-NonInteractive", "-Command", &query])
+        .output()
+        .ok()?;
+    let line = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (output.status.success() && !line.is_empty()).then_some(line)
+}
+
+// Mata el proceso y los que haya lanzado. En Unix los comandos en streaming
+// tienen grupo propio (pgid = pid), así que se señala al grupo entero.
+#[cfg(unix)]
+pub fn kill_process_tree(pid: u32) {
+    let _ = command("kill")
+        .args(["-TERM", &format!("-{}", pid)])
+        .status();
+}
+
+#[cfg(windows)]
+pub fn kill_process_tree(pid: u32) {
+    let _ = command("taskkill")
+        .args(["/T", "/F", "/PID", &pid.to_string()])
+        .status();
+}
+
+// Arranca Docker Desktop; en Linux como servicio de usuario
+#[cfg(target_os = "macos")]
+pub fn start_docker() -> std::io::Result<()> {
+    Command::new("open").args(["-a", "Docker"]).spawn().map(|_| ())
+}
+
+#[cfg(windows)]
+pub fn start_docker() -> std::io::Result<()> {
+    command("cmd")
+        .args(["/C", "start", "", "Docker Desktop"])
+        .spawn()
+        .map(|_| ())
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+pub fn start_docker() -> std::io::Result<()> {
+    command("systemctl")
+        .args(["--user", "start", "docker-desktop"])
+        .spawn()
+        .map(|_| ())
+}
+
+// Abre el gestor de archivos con el archivo seleccionado (en Linux, su carpeta)
+#[cfg(target_os = "macos")]
+pub fn reveal_in_file_manager(path: &std::path::Path) -> std::io::Result<()> {
+    Command::new("open").arg("-R").arg(path).spawn().map(|_| ())
+}
+
+#[cfg(windows)]
+pub fn reveal_in_file_manager(path: &std::path::Path) -> std::io::Result<()> {
+    Command::new("explorer")
+        .arg(format!("/select,{}", path.display()))
+        .spawn()
+        .map(|_| ())
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+pub fn reveal_in_file_manager(path: &std::path::Path) -> std::io::Result<()> {
+    open::that(path.parent().unwrap_or(path))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use std::ffi::OsString;
+
+    use super::*;
+
+    fn path_var(dirs: &[&str]) -> OsString {
+        std::env::join_paths(dirs).unwrap()
+    }
+
+    fn resolve(dirs: &[&str], pathext: &str, files: &[&str]) -> Option<PathBuf> {
+        // Con `join`, como los candidatos, para que el separador coincida en Windows
+        let files: HashSet<PathBuf> = files.iter()
+            .map(|file| match file.rsplit_once('/') {
+                Some((dir, name)) => Path::new(dir).join(name),
+                None => PathBuf::from(file),
+            })
+            .collect();
+        resolve_in_path("lando", &path_var(dirs), pathext, |candidate| files.contains(candidate))
+    }
+
+    #[test]
+    fn finds_lando_cmd_when_there_is_no_exe() {
+        let found = resolve(&["/tools", "/lando/bin"], ".COM;.EXE;.BAT;.CMD", &["/lando/bin/lando.cmd", "/lando/bin/lando"]);
+        assert_eq!(found, Some(Path::new("/lando/bin").join("lando.cmd")));
+    }
+
+    #[test]
+    fn prefers_the_pathext_order_within_a_folder() {
+        let found = resolve(&["/lando/bin"], ".EXE;.CMD", &["/lando/bin/lando.cmd", "/lando/bin/lando.exe"]);
+        assert_eq!(found, Some(Path::new("/lando/bin").join("lando.exe")));
+    }
+
+    #[test]
+    fn finds_plain_lando_without_pathext() {
+        let found = resolve(&["/usr/bin", "/usr/local/bin"], "", &["/usr/local/bin/lando"]);
+        assert_eq!(found, Some(Path::new("/usr/local/bin").join("lando")));
+        assert_eq!(resolve(&["/usr/local/bin"], "", &["/usr/local/bin/lando.cmd"]), None);
+    }
+
+    #[test]
+    fn first_folder_in_path_wins() {
+        let found = resolve(&["/first", "/second"], "", &["/second/lando", "/first/lando"]);
+        assert_eq!(found, Some(Path::new("/first").join("lando")));
+    }
+
+    #[test]
+    fn skips_empty_path_entries() {
+        let found = resolve(&["", "/usr/local/bin", ""], "", &["lando", "/usr/local/bin/lando"]);
+        assert_eq!(found, Some(Path::new("/usr/local/bin").join("lando")));
+        assert_eq!(resolve(&[""], "", &["lando"]), None);
+    }
+
+    #[test]
+    fn ignores_empty_pathext_entries() {
+        let found = resolve(&["/lando/bin"], ";;.CMD;", &["/lando/bin/lando.cmd"]);
+        assert_eq!(found, Some(Path::new("/lando/bin").join("lando.cmd")));
+    }
+
+    #[test]
+    fn names_with_a_path_or_extension_are_left_alone() {
+        let files = |_: &Path| true;
+        assert_eq!(resolve_in_path("./lando", &path_var(&["/usr/bin"]), "", files), None);
+        assert_eq!(resolve_in_path("lando.exe", &path_var(&["/usr/bin"]), ".EXE", files), None);
+    }
+}
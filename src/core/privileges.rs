@@ -0,0 +1,550 @@
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::path::PathBuf;
+use std::sync::mpsc::Sender;
+
+use crate::core::commands::*;
+use crate::core::result_format::{parse_result_set, ParsedResultSet};
+use crate::core::table_editor::string_literal;
+use crate::models::commands::LandoCommandOutcome;
+use crate::models::lando::LandoService;
+use crate::ui::confirm::ConfirmDialog;
+use crate::ui::database::{DatabaseUI, DbUser, PendingRequest, ToolCommand, UserAction, UserGrant, UserManagerState};
+
+const PASSWORD_CHARSET: &[u8] = b"ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz23456789-_.";
+
+// SQL de gestión de usuarios de cada motor: catálogos, sintaxis de GRANT y
+// la forma de consultar los permisos cambian por completo entre MySQL y PostgreSQL
+pub trait PrivilegeDialect {
+    fn list_users_query(&self) -> String;
+    fn parse_users(&self, set: &ParsedResultSet) -> Vec<DbUser>;
+    fn grants_query(&self, user: &DbUser) -> String;
+    fn parse_grants(&self, set: &ParsedResultSet) -> Vec<UserGrant>;
+    fn privileges(&self) -> &'static [&'static str];
+    // Host solo tiene sentido en MySQL (`'usuario'@'host'`)
+    fn uses_host(&self) -> bool;
+    fn create_user(&self, name: &str, host: &str, password: &str) -> String;
+    fn drop_user(&self, user: &DbUser) -> String;
+    fn change_password(&self, user: &DbUser, password: &str) -> String;
+    // `table` vacío aplica a toda la base de datos
+    fn grant(&self, user: &DbUser, privileges: &[String], database: &str, table: &str) -> String;
+    fn revoke(&self, user: &DbUser, privileges: &[String], database: &str, table: &str) -> String;
+}
+
+pub struct MySqlPrivileges;
+pub struct PostgresPrivileges;
+
+pub fn privilege_dialect(db_type: &str) -> Option<&'static dyn PrivilegeDialect> {
+    match db_type.to_lowercase().as_str() {
+        "mysql" | "mariadb" => Some(&MySqlPrivileges),
+        "postgresql" | "postgres" => Some(&PostgresPrivileges),
+        _ => None,
+    }
+}
+
+fn mysql_account(name: &str, host: &str) -> String {
+    format!("{}@{}", string_literal("mysql", name), string_literal("mysql", host))
+}
+
+fn mysql_identifier(name: &str) -> String {
+    format!("`{}`", name.replace('`', "``"))
+}
+
+fn pg_identifier(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+// Separa por comas fuera de paréntesis: `SELECT (a, b), INSERT` son dos privilegios
+fn split_privileges(text: &str) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut current = String::new();
+    let mut depth = 0usize;
+    for c in text.chars() {
+        match c {
+            '(' => { depth += 1; current.push(c); }
+            ')' => { depth = depth.saturating_sub(1); current.push(c); }
+            ',' if depth == 0 => parts.push(std::mem::take(&mut current)),
+            _ => current.push(c),
+        }
+    }
+    parts.push(current);
+    parts.into_iter()
+        .map(|p| p.trim().to_string())
+        .filter(|p| !p.is_empty())
+        .collect()
+}
+
+impl PrivilegeDialect for MySqlPrivileges {
+    fn list_users_query(&self) -> String {
+        "SELECT User, Host FROM mysql.user ORDER BY User, Host;".to_string()
+    }
+
+    fn parse_users(&self, set: &ParsedResultSet) -> Vec<DbUser> {
+        (0..set.rows.len())
+            .filter_map(|row| Some(DbUser {
+                name: set.value(row, "User")?.trim().to_string(),
+                host: set.value(row, "Host").map(|h| h.trim().to_string()),
+                attributes: Vec::new(),
+                grants: Vec::new(),
+                grants_loaded: false,
+            }))
+            .collect()
+    }
+
+    fn grants_query(&self, user: &DbUser) -> String {
+        format!("SHOW GRANTS FOR {};", mysql_account(&user.name, user.host.as_deref().unwrap_or("%")))
+    }
+
+    // Una fila por sentencia: "GRANT SELECT, INSERT ON `db`.* TO `u`@`%` WITH GRANT OPTION".
+    // Los roles se conceden sin `ON` ("GRANT `rol` TO `u`@`%`").
+    fn parse_grants(&self, set: &ParsedResultSet) -> Vec<UserGrant> {
+        set.rows.iter()
+            .filter_map(|row| row.first())
+            .filter_map(|line| {
+                let line = line.trim();
+                let body = line.strip_prefix("GRANT ")?;
+                let grant_option = line.contains("WITH GRANT OPTION");
+                match body.split_once(" ON ") {
+                    Some((privileges, rest)) => {
+                        let target = rest.split(" TO ").next().unwrap_or(rest).trim();
+                        Some(UserGrant {
+                            privileges: split_privileges(privileges),
+                            target: target.replace('`', ""),
+                            grant_option,
+                        })
+                    }
+                    None => {
+                        let roles = body.split(" TO ").next().unwrap_or(body);
+                        Some(UserGrant {
+                            privileges: split_privileges(roles).into_iter().map(|r| r.replace('`', "")).collect(),
+                            target: "(roles)".to_string(),
+                            grant_option: line.contains("WITH ADMIN OPTION"),
+                        })
+                    }
+                }
+            })
+            .collect()
+    }
+
+    fn privileges(&self) -> &'static [&'static str] {
+        &["ALL PRIVILEGES", "SELECT", "INSERT", "UPDATE", "DELETE", "CREATE", "DROP", "ALTER", "INDEX",
+          "CREATE VIEW", "SHOW VIEW", "TRIGGER", "EXECUTE", "LOCK TABLES", "REFERENCES"]
+    }
+
+    fn uses_host(&self) -> bool {
+        true
+    }
+
+    fn create_user(&self, name: &str, host: &str, password: &str) -> String {
+        format!("CREATE USER {} IDENTIFIED BY {};", mysql_account(name, host), string_literal("mysql", password))
+    }
+
+    fn drop_user(&self, user: &DbUser) -> String {
+        format!("DROP USER {};", mysql_account(&user.name, user.host.as_deref().unwrap_or("%")))
+    }
+
+    fn change_password(&self, user: &DbUser, password: &str) -> String {
+        format!(
+            "ALTER USER {} IDENTIFIED BY {};",
+            mysql_account(&user.name, user.host.as_deref().unwrap_or("%")),
+            string_literal("mysql", password)
+        )
+    }
+
+    fn grant(&self, user: &DbUser, privileges: &[String], database: &str, table: &str) -> String {
+        format!(
+            "GRANT {} ON {} TO {};",
+            privileges.join(", "),
+            mysql_target(database, table),
+            mysql_account(&user.name, user.host.as_deref().unwrap_or("%"))
+        )
+    }
+
+    fn revoke(&self, user: &DbUser, privileges: &[String], database: &str, table: &str) -> String {
+        format!(
+            "REVOKE {} ON {} FROM {};",
+            privileges.join(", "),
+            mysql_target(database, table),
+            mysql_account(&user.name, user.host.as_deref().unwrap_or("%"))
+        )
+    }
+}
+
+fn mysql_target(database: &str, table: &str) -> String {
+    let database = if database.is_empty() { "*".to_string() } else { mysql_identifier(database) };
+    let table = if table.is_empty() { "*".to_string() } else { mysql_identifier(table) };
+    format!("{}.{}", database, table)
+}
+
+impl PrivilegeDialect for PostgresPrivileges {
+    // Equivalente a `\du`: atributos de rol en lugar de host
+    fn list_users_query(&self) -> String {
+        "SELECT usename, usesuper, usecreatedb, userepl FROM pg_catalog.pg_user ORDER BY usename;".to_string()
+    }
+
+    fn parse_users(&self, set: &ParsedResultSet) -> Vec<DbUser> {
+        let flag = |row: usize, column: &str| set.value(row, column).is_some_and(|v| matches!(v.trim(), "t" | "true" | "1"));
+        (0..set.rows.len())
+            .filter_map(|row| {
+                let name = set.value(row, "usename")?.trim().to_string();
+                let attributes = [("usesuper", "Superusuario"), ("usecreatedb", "Crear BD"), ("userepl", "Replicación")]
+                    .into_iter()
+                    .filter(|(column, _)| flag(row, column))
+                    .map(|(_, label)| label.to_string())
+                    .collect();
+                Some(DbUser { name, host: None, attributes, grants: Vec::new(), grants_loaded: false })
+            })
+            .collect()
+    }
+
+    // Permisos de base de datos (has_database_privilege) y de tabla
+    // (role_table_grants), con las columnas target/privileges/grantable
+    fn grants_query(&self, user: &DbUser) -> String {
+        let name = string_literal("postgresql", &user.name);
+        format!(
+            "SELECT 'DATABASE ' || d.datname AS target, \
+             concat_ws(', ', \
+               CASE WHEN has_database_privilege({name}, d.datname, 'CONNECT') THEN 'CONNECT' END, \
+               CASE WHEN has_database_privilege({name}, d.datname, 'CREATE') THEN 'CREATE' END, \
+               CASE WHEN has_database_privilege({name}, d.datname, 'TEMPORARY') THEN 'TEMPORARY' END) AS privileges, \
+             'f' AS grantable \
+             FROM pg_database d WHERE NOT d.datistemplate \
+             UNION ALL \
+             SELECT table_schema || '.' || table_name, string_agg(privilege_type, ', ' ORDER BY privilege_type), \
+             CASE WHEN bool_or(is_grantable = 'YES') THEN 't' ELSE 'f' END \
+             FROM information_schema.role_table_grants WHERE grantee = {name} \
+             GROUP BY table_schema, table_name;",
+            name = name
+        )
+    }
+
+    fn parse_grants(&self, set: &ParsedResultSet) -> Vec<UserGrant> {
+        (0..set.rows.len())
+            .filter_map(|row| {
+                let privileges = split_privileges(set.value(row, "privileges")?);
+                if privileges.is_empty() {
+                    return None;
+                }
+                Some(UserGrant {
+                    privileges,
+                    target: set.value(row, "target")?.trim().to_string(),
+                    grant_option: set.value(row, "grantable").is_some_and(|v| v.trim() == "t"),
+                })
+            })
+            .collect()
+    }
+
+    fn privileges(&self) -> &'static [&'static str] {
+        &["ALL PRIVILEGES", "SELECT", "INSERT", "UPDATE", "DELETE", "TRUNCATE", "REFERENCES", "TRIGGER",
+          "CONNECT", "CREATE", "TEMPORARY"]
+    }
+
+    fn uses_host(&self) -> bool {
+        false
+    }
+
+    fn create_user(&self, name: &str, _host: &str, password: &str) -> String {
+        format!("CREATE USER {} WITH PASSWORD {};", pg_identifier(name), string_literal("postgresql", password))
+    }
+
+    fn drop_user(&self, user: &DbUser) -> String {
+        format!("DROP USER {};", pg_identifier(&user.name))
+    }
+
+    fn change_password(&self, user: &DbUser, password: &str) -> String {
+        format!("ALTER USER {} WITH PASSWORD {};", pg_identifier(&user.name), string_literal("postgresql", password))
+    }
+
+    fn grant(&self, user: &DbUser, privileges: &[String], database: &str, table: &str) -> String {
+        format!("GRANT {} ON {} TO {};", privileges.join(", "), pg_target(database, table), pg_identifier(&user.name))
+    }
+
+    fn revoke(&self, user: &DbUser, privileges: &[String], database: &str, table: &str) -> String {
+        format!("REVOKE {} ON {} FROM {};", privileges.join(", "), pg_target(database, table), pg_identifier(&user.name))
+    }
+}
+
+// Sin tabla el permiso se aplica a la base de datos; con tabla, a esa tabla
+// del esquema por defecto (la conexión ya apunta a la base de datos)
+fn pg_target(database: &str, table: &str) -> String {
+    if table.is_empty() {
+        format!("DATABASE {}", pg_identifier(database))
+    } else {
+        format!("TABLE {}", pg_identifier(table))
+    }
+}
+
+// Contraseña aleatoria para entornos locales. Cada `RandomState` trae una
+// semilla nueva del sistema, suficiente sin añadir una dependencia de `rand`.
+pub fn generate_password(length: usize) -> String {
+    let mut hasher = RandomState::new().build_hasher();
+    (0..length)
+        .map(|i| {
+            hasher.write_usize(i);
+            PASSWORD_CHARSET[(hasher.finish() % PASSWORD_CHARSET.len() as u64) as usize] as char
+        })
+        .collect()
+}
+
+impl UserManagerState {
+    pub fn selected_user(&self) -> Option<&DbUser> {
+        self.selected.and_then(|index| self.users.get(index))
+    }
+}
+
+impl DatabaseUI {
+    pub fn supports_user_management(&self, db_type: &str) -> bool {
+        privilege_dialect(db_type).is_some()
+    }
+
+    pub fn load_users(&mut self, service: &LandoService, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>) {
+        let Some(dialect) = privilege_dialect(service.db_engine()) else {
+            return;
+        };
+        if self.pending_requests.values().any(|r| matches!(r, PendingRequest::Users { .. })) {
+            return;
+        }
+
+        self.user_manager.loading = true;
+        self.user_manager.loaded = true;
+        self.user_manager.error = None;
+
+        let request_id = next_request_id();
+        self.pending_requests.insert(request_id, PendingRequest::Users { db_type: service.db_engine().to_string() });
+        run_db_query(
+            sender.clone(),
+            project_path.clone(),
+            self.db_connection(service),
+            dialect.list_users_query(),
+            request_id,
+        );
+    }
+
+    pub fn handle_users_result(&mut self, db_type: &str, output: &Result<String, String>) {
+        self.user_manager.loading = false;
+        let Some(dialect) = privilege_dialect(db_type) else {
+            return;
+        };
+
+        match output {
+            Ok(text) => {
+                let selected = self.user_manager.selected_user().cloned();
+                self.user_manager.users = parse_result_set(text)
+                    .map(|set| dialect.parse_users(&set))
+                    .unwrap_or_default();
+                // Conservar la selección tras recargar si el usuario sigue existiendo
+                self.user_manager.selected = selected.and_then(|s| {
+                    self.user_manager.users.iter().position(|u| u.name == s.name && u.host == s.host)
+                });
+            }
+            Err(e) => {
+                self.user_manager.error = Some(format!("No se pudieron listar los usuarios: {}", e));
+            }
+        }
+    }
+
+    pub fn load_user_grants(&mut self, service: &LandoService, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>, index: usize) {
+        let (Some(dialect), Some(user)) = (privilege_dialect(service.db_engine()), self.user_manager.users.get(index)) else {
+            return;
+        };
+
+        let request_id = next_request_id();
+        let query = dialect.grants_query(user);
+        self.pending_requests.insert(request_id, PendingRequest::UserGrants {
+            db_type: service.db_engine().to_string(),
+            user: user.name.clone(),
+            host: user.host.clone(),
+        });
+        run_db_query(sender.clone(), project_path.clone(), self.db_connection(service), query, request_id);
+    }
+
+    pub fn handle_user_grants_result(&mut self, db_type: &str, user: &str, host: &Option<String>, output: &Result<String, String>) {
+        let Some(dialect) = privilege_dialect(db_type) else {
+            return;
+        };
+        let Some(entry) = self.user_manager.users.iter_mut().find(|u| u.name == user && &u.host == host) else {
+            return;
+        };
+
+        entry.grants_loaded = true;
+        match output {
+            Ok(text) => {
+                entry.grants = parse_result_set(text)
+                    .map(|set| dialect.parse_grants(&set))
+                    .unwrap_or_default();
+            }
+            Err(e) => {
+                entry.grants.clear();
+                self.user_manager.error = Some(format!("No se pudieron leer los permisos de {}: {}", user, e));
+            }
+        }
+    }
+
+    // SQL que generaría el formulario actual, o el motivo por el que aún no se puede
+    pub fn build_user_sql(&self, db_type: &str) -> Result<String, String> {
+        let dialect = privilege_dialect(db_type).ok_or("Este motor no admite gestión de usuarios")?;
+        let form = &self.user_manager;
+        let selected = form.selected_user();
+
+        match form.action {
+            UserAction::Create => {
+                if form.name.trim().is_empty() {
+                    return Err("Indica el nombre del usuario".to_string());
+                }
+                if form.password.is_empty() {
+                    return Err("Indica una contraseña o genera una".to_string());
+                }
+                let host = if form.host.trim().is_empty() { "%" } else { form.host.trim() };
+                Ok(dialect.create_user(form.name.trim(), host, form.password.expose()))
+            }
+            UserAction::ChangePassword => {
+                let user = selected.ok_or("Selecciona un usuario")?;
+                if form.password.is_empty() {
+                    return Err("Indica la nueva contraseña".to_string());
+                }
+                Ok(dialect.change_password(user, form.password.expose()))
+            }
+            UserAction::Drop => {
+                let user = selected.ok_or("Selecciona un usuario")?;
+                Ok(dialect.drop_user(user))
+            }
+            UserAction::Grant | UserAction::Revoke => {
+                let user = selected.ok_or("Selecciona un usuario")?;
+                if form.privileges.is_empty() {
+                    return Err("Marca al menos un privilegio".to_string());
+                }
+                if !dialect.uses_host() && form.database.trim().is_empty() && form.table.is_empty() {
+                    return Err("Elige la base de datos o una tabla".to_string());
+                }
+                let (database, table) = (form.database.trim(), form.table.as_str());
+                Ok(match form.action {
+                    UserAction::Grant => dialect.grant(user, &form.privileges, database, table),
+                    _ => dialect.revoke(user, &form.privileges, database, table),
+                })
+            }
+        }
+    }
+
+    // Todo pasa por el diálogo de confirmación con el SQL exacto; borrar un
+    // usuario exige además marcar "entiendo"
+    pub fn request_user_action(&mut self, service: &LandoService) {
+        let sql = match self.build_user_sql(service.db_engine()) {
+            Ok(sql) => sql,
+            Err(e) => {
+                self.user_manager.error = Some(e);
+                return;
+            }
+        };
+        self.user_manager.error = None;
+
+        let (title, description) = match self.user_manager.action {
+            UserAction::Create => ("Crear usuario", "Se creará un usuario nuevo en el servidor."),
+            UserAction::ChangePassword => ("Cambiar contraseña", "Las conexiones que usen la contraseña anterior dejarán de funcionar."),
+            UserAction::Drop => ("Eliminar usuario", "El usuario y todos sus permisos se borrarán del servidor."),
+            UserAction::Grant => ("Conceder privilegios", "Se añadirán los privilegios seleccionados."),
+            UserAction::Revoke => ("Revocar privilegios", "Se retirarán los privilegios seleccionados."),
+        };
+
+        let mut dialog = ConfirmDialog::new(title, description, &sql, &service.service);
+        if self.user_manager.action == UserAction::Drop {
+            dialog = dialog.destructive();
+        }
+        self.user_manager.reload_after_query = true;
+        self.pending_tool = Some((ToolCommand::Editor(sql), dialog));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grant(privileges: &[&str], target: &str, grant_option: bool) -> UserGrant {
+        UserGrant {
+            privileges: privileges.iter().map(|p| p.to_string()).collect(),
+            target: target.to_string(),
+            grant_option,
+        }
+    }
+
+    fn user(name: &str, host: Option<&str>) -> DbUser {
+        DbUser { name: name.to_string(), host: host.map(String::from), attributes: Vec::new(), grants: Vec::new(), grants_loaded: false }
+    }
+
+    // Salida de `SHOW GRANTS` en el modo batch de `lando db-cli`
+    const MYSQL_SHOW_GRANTS: &str = "Grants for app@%\n\
+        GRANT USAGE ON *.* TO `app`@`%`\n\
+        GRANT SELECT, INSERT, UPDATE ON `blog`.* TO `app`@`%` WITH GRANT OPTION\n\
+        GRANT SELECT (`id`, `title`), UPDATE (`title`) ON `blog`.`posts` TO `app`@`%`\n\
+        GRANT `reader`@`%`,`writer`@`%` TO `app`@`%` WITH ADMIN OPTION\n";
+
+    #[test]
+    fn mysql_parses_show_grants() {
+        let set = parse_result_set(MYSQL_SHOW_GRANTS).unwrap();
+        assert_eq!(MySqlPrivileges.parse_grants(&set), vec![
+            grant(&["USAGE"], "*.*", false),
+            grant(&["SELECT", "INSERT", "UPDATE"], "blog.*", true),
+            grant(&["SELECT (`id`, `title`)", "UPDATE (`title`)"], "blog.posts", false),
+            grant(&["reader@%", "writer@%"], "(roles)", true),
+        ]);
+    }
+
+    #[test]
+    fn mysql_parses_the_user_list() {
+        let set = parse_result_set("User\tHost\napp\t%\nroot\tlocalhost\n").unwrap();
+        assert_eq!(MySqlPrivileges.parse_users(&set), vec![user("app", Some("%")), user("root", Some("localhost"))]);
+    }
+
+    #[test]
+    fn postgres_parses_role_attributes_and_grants() {
+        let users = " usename  | usesuper | usecreatedb | userepl \n\
+            ----------+----------+-------------+---------\n \
+            app      | f        | t           | f\n \
+            postgres | t        | t           | t\n\
+            (2 rows)\n";
+        let users = PostgresPrivileges.parse_users(&parse_result_set(users).unwrap());
+        assert_eq!(users[0].name, "app");
+        assert_eq!(users[0].attributes, vec!["Crear BD"]);
+        assert_eq!(users[1].attributes, vec!["Superusuario", "Crear BD", "Replicación"]);
+
+        let grants = "       target        |        privileges        | grantable \n\
+            ---------------------+--------------------------+-----------\n \
+            DATABASE blog       | CONNECT, TEMPORARY       | f\n \
+            DATABASE postgres   |                          | f\n \
+            public.posts        | INSERT, SELECT, UPDATE   | t\n\
+            (3 rows)\n";
+        assert_eq!(PostgresPrivileges.parse_grants(&parse_result_set(grants).unwrap()), vec![
+            grant(&["CONNECT", "TEMPORARY"], "DATABASE blog", false),
+            grant(&["INSERT", "SELECT", "UPDATE"], "public.posts", true),
+        ]);
+    }
+
+    #[test]
+    fn statements_quote_names_per_dialect() {
+        let app = user("o'neil", Some("%"));
+        let privileges = vec!["SELECT".to_string(), "INSERT".to_string()];
+        assert_eq!(MySqlPrivileges.grants_query(&app), "SHOW GRANTS FOR 'o''neil'@'%';");
+        assert_eq!(
+            MySqlPrivileges.grant(&app, &privileges, "blog", ""),
+            "GRANT SELECT, INSERT ON `blog`.* TO 'o''neil'@'%';",
+        );
+        assert_eq!(
+            MySqlPrivileges.revoke(&app, &privileges, "", ""),
+            "REVOKE SELECT, INSERT ON *.* FROM 'o''neil'@'%';",
+        );
+        assert_eq!(
+            PostgresPrivileges.grant(&app, &privileges, "blog", "posts"),
+            "GRANT SELECT, INSERT ON TABLE \"posts\" TO \"o'neil\";",
+        );
+        assert_eq!(
+            PostgresPrivileges.create_user("app", "%", "it's"),
+            "CREATE USER \"app\" WITH PASSWORD 'it''s';",
+        );
+    }
+
+    #[test]
+    fn generated_passwords_use_the_charset() {
+        let password = generate_password(20);
+        assert_eq!(password.len(), 20);
+        assert!(password.bytes().all(|b| PASSWORD_CHARSET.contains(&b)));
+    }
+}
@@ -0,0 +1,149 @@
+use std::path::PathBuf;
+use std::sync::mpsc::Sender;
+
+use crate::core::commands::*;
+use crate::core::result_format::{parse_result_set, ParsedResultSet};
+use crate::models::commands::LandoCommandOutcome;
+use crate::models::lando::LandoService;
+use crate::ui::confirm::ConfirmDialog;
+use crate::ui::database::{DatabaseUI, DbProcess, PendingRequest, ToolCommand};
+
+// Consultas activas del servidor y cómo cortarlas en cada motor
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ProcessDialect {
+    MySql,
+    Postgres,
+}
+
+// Cancelar solo detiene la sentencia; terminar cierra también la conexión
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum KillMode {
+    CancelQuery,
+    Terminate,
+}
+
+impl ProcessDialect {
+    pub fn for_type(db_type: &str) -> Option<Self> {
+        match db_type.to_lowercase().as_str() {
+            "mysql" | "mariadb" => Some(Self::MySql),
+            "postgres" | "postgresql" => Some(Self::Postgres),
+            _ => None,
+        }
+    }
+
+    pub fn list_query(&self) -> &'static str {
+        match self {
+            Self::MySql => "SHOW FULL PROCESSLIST;",
+            Self::Postgres => "SELECT pid, usename, datname, state, \
+                EXTRACT(EPOCH FROM (now() - query_start))::int AS seconds, query \
+                FROM pg_stat_activity \
+                WHERE pid <> pg_backend_pid() AND backend_type = 'client backend' \
+                ORDER BY query_start;",
+        }
+    }
+
+    // La conexión de `db-cli` que lanzó el listado aparece en él: se omite
+    pub fn parse(&self, set: &ParsedResultSet) -> Vec<DbProcess> {
+        let text = |row: usize, column: &str| {
+            set.value(row, column)
+                .map(str::trim)
+                .filter(|v| !v.is_empty() && *v != "NULL")
+                .map(String::from)
+        };
+        let (id, user, database, state, time, query) = match self {
+            Self::MySql => ("Id", "User", "db", "State", "Time", "Info"),
+            Self::Postgres => ("pid", "usename", "datname", "state", "seconds", "query"),
+        };
+
+        (0..set.rows.len())
+            .filter_map(|row| {
+                let process = DbProcess {
+                    id: text(row, id)?.parse().ok()?,
+                    user: text(row, user).unwrap_or_default(),
+                    database: text(row, database),
+                    state: text(row, state)
+                        .or_else(|| text(row, "Command"))
+                        .unwrap_or_default(),
+                    seconds: text(row, time).and_then(|t| t.parse().ok()),
+                    query: text(row, query),
+                };
+                let own = process.query.as_deref().is_some_and(|q| q.eq_ignore_ascii_case(self.list_query().trim_end_matches(';')));
+                (!own).then_some(process)
+            })
+            .collect()
+    }
+
+    // El id se formatea desde un número: no hay nada que escapar
+    pub fn kill_statement(&self, id: u64, mode: KillMode) -> String {
+        match (self, mode) {
+            (Self::MySql, KillMode::CancelQuery) => format!("KILL QUERY {};", id),
+            (Self::MySql, KillMode::Terminate) => format!("KILL {};", id),
+            (Self::Postgres, KillMode::CancelQuery) => format!("SELECT pg_cancel_backend({});", id),
+            (Self::Postgres, KillMode::Terminate) => format!("SELECT pg_terminate_backend({});", id),
+        }
+    }
+}
+
+impl DatabaseUI {
+    pub fn supports_process_list(&self, service: &LandoService) -> bool {
+        ProcessDialect::for_type(service.db_engine()).is_some()
+    }
+
+    pub fn load_process_list(&mut self, service: &LandoService, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>) {
+        let Some(dialect) = ProcessDialect::for_type(service.db_engine()) else {
+            return;
+        };
+        if self.pending_requests.values().any(|r| matches!(r, PendingRequest::ProcessList { .. })) {
+            return;
+        }
+
+        self.process_list.loading = true;
+        self.process_list.stale = false;
+        self.process_list.error = None;
+
+        let request_id = next_request_id();
+        self.pending_requests.insert(request_id, PendingRequest::ProcessList { db_type: service.db_engine().to_string() });
+        run_db_query(
+            sender.clone(),
+            project_path.clone(),
+            self.db_connection(service),
+            dialect.list_query().to_string(),
+            request_id,
+        );
+    }
+
+    pub fn handle_process_list_result(&mut self, db_type: &str, output: &Result<String, String>) {
+        self.process_list.loading = false;
+        let Some(dialect) = ProcessDialect::for_type(db_type) else {
+            return;
+        };
+
+        match output {
+            Ok(text) => {
+                self.process_list.processes = parse_result_set(text)
+                    .map(|set| dialect.parse(&set))
+                    .unwrap_or_default();
+                self.process_list.loaded = true;
+            }
+            Err(e) => self.process_list.error = Some(e.clone()),
+        }
+    }
+
+    // Tras el KILL la lista se vuelve a cargar (ver `process_query_result`)
+    pub fn request_kill_process(&mut self, service: &LandoService, process: &DbProcess, mode: KillMode) {
+        let Some(dialect) = ProcessDialect::for_type(service.db_engine()) else {
+            return;
+        };
+        let sql = dialect.kill_statement(process.id, mode);
+        let (title, description) = match mode {
+            KillMode::CancelQuery => ("Cancelar consulta", "Se interrumpirá la consulta en curso; la conexión seguirá abierta."),
+            KillMode::Terminate => ("Terminar conexión", "Se cerrará la conexión y se deshará su transacción abierta, si la tiene."),
+        };
+        let mut dialog = ConfirmDialog::new(title, description, &sql, &service.service);
+        if mode == KillMode::Terminate {
+            dialog = dialog.destructive();
+        }
+        self.process_list.reload_after_query = true;
+        self.pending_tool = Some((ToolCommand::Sql(sql), dialog));
+    }
+}
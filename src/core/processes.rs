@@ -0,0 +1,191 @@
+use std::path::Path;
+use std::sync::Mutex;
+
+use crate::core::logging::redact_secrets;
+use crate::core::platform::{kill_process_tree, process_alive, process_command_line};
+use crate::core::storage::{load_app_file, save_app_file};
+use crate::core::time::now_secs;
+use crate::models::processes::RunningProcess;
+
+// Procesos vivos de esta sesión y huérfanos de sesiones anteriores aún sin resolver
+const RUNNING_PROCESSES_FILE: &str = "running_processes.json";
+
+// Los comandos arrancan y terminan desde hilos distintos: el fichero se
+// lee y reescribe siempre bajo este cerrojo
+static STATE_LOCK: Mutex<()> = Mutex::new(());
+static ACTIVE: Mutex<Vec<RunningProcess>> = Mutex::new(Vec::new());
+
+fn update_state_file(change: impl FnOnce(&mut Vec<RunningProcess>)) {
+    let _guard = STATE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut processes: Vec<RunningProcess> = load_app_file(RUNNING_PROCESSES_FILE);
+    change(&mut processes);
+    let _ = save_app_file(RUNNING_PROCESSES_FILE, &processes);
+}
+
+// Mientras vive, el proceso queda apuntado en memoria y en el fichero de estado
+pub struct ProcessRegistration {
+    pid: u32,
+}
+
+impl ProcessRegistration {
+    pub fn new(pid: u32, command: String, project: Option<&Path>) -> Self {
+        let process = RunningProcess {
+            pid,
+            command,
+            project: project.map(Path::to_path_buf).unwrap_or_default(),
+            started_at: now_secs(),
+        };
+        ACTIVE.lock().unwrap_or_else(|e| e.into_inner()).push(process.clone());
+        update_state_file(|processes| {
+            processes.retain(|p| p.pid != pid);
+            processes.push(process);
+        });
+        Self { pid }
+    }
+}
+
+impl Drop for ProcessRegistration {
+    fn drop(&mut self) {
+        let pid = self.pid;
+        ACTIVE.lock().unwrap_or_else(|e| e.into_inner()).retain(|p| p.pid != pid);
+        update_state_file(|processes| processes.retain(|p| p.pid != pid));
+    }
+}
+
+pub fn active_processes() -> Vec<RunningProcess> {
+    ACTIVE.lock().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+// Un pid reutilizado por otro programa (o por esta misma app) no cuenta: la
+// línea de comandos tiene que seguir llevando las palabras del comando apuntado.
+// Se compara palabra a palabra porque el ejecutable puede salir con ruta,
+// extensión (`lando.cmd`) o detrás de `cmd.exe /c`, y las contraseñas ya se
+// guardaron ocultas.
+fn looks_like_ours(process: &RunningProcess) -> bool {
+    if process.pid == std::process::id() || !process_alive(process.pid) {
+        return false;
+    }
+    let Some(line) = process_command_line(process.pid) else {
+        return false;
+    };
+    let line = redact_secrets(&format!(" {}", line)).to_lowercase();
+    let expected = process.command.to_lowercase();
+    let mut words = expected.split_whitespace().peekable();
+    words.peek().is_some() && words.all(|word| line.contains(word))
+}
+
+// Al arrancar: entradas del fichero cuyo proceso sigue vivo. Las muertas se
+// descartan del fichero; las vivas se conservan hasta terminarlas o ignorarlas.
+pub fn find_orphaned_processes() -> Vec<RunningProcess> {
+    let mut orphaned = Vec::new();
+    update_state_file(|processes| {
+        processes.retain(looks_like_ours);
+        orphaned = processes.clone();
+    });
+    orphaned
+}
+
+pub fn forget_process(pid: u32) {
+    update_state_file(|processes| processes.retain(|p| p.pid != pid));
+}
+
+pub fn terminate_process(pid: u32) {
+    kill_process_tree(pid);
+    forget_process(pid);
+}
+
+// Para salir: se matan los procesos de la sesión; cada uno se desapunta
+// solo cuando el hilo que lo lanzó ve que ha terminado
+pub fn terminate_active_processes() {
+    for process in active_processes() {
+        kill_process_tree(process.pid);
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use std::process::{Child, Command};
+
+    use super::*;
+
+    fn process(pid: u32) -> RunningProcess {
+        RunningProcess { pid, command: "lando rebuild -y".to_string(), project: Default::default(), started_at: 0 }
+    }
+
+    // Proceso hijo cuya línea de comandos lleva `command`, como lo vería `ps`
+    fn spawn_looking_like(command: &str) -> Child {
+        Command::new("sh").arg("-c").arg(format!("sleep 30; : {}", command)).spawn().unwrap()
+    }
+
+    fn stop(mut child: Child) {
+        let _ = child.kill();
+        let _ = child.wait();
+    }
+
+    #[test]
+    fn live_lando_processes_are_ours() {
+        let child = spawn_looking_like("/usr/local/bin/lando rebuild -y");
+        let ours = looks_like_ours(&process(child.id()));
+        stop(child);
+        assert!(ours);
+    }
+
+    #[test]
+    fn own_process_is_never_ours() {
+        let mut own = process(std::process::id());
+        own.command = "lando_gui".to_string();
+        assert!(!looks_like_ours(&own));
+    }
+
+    #[test]
+    fn reused_pids_are_not_ours() {
+        let other = Command::new("sleep").arg("30").spawn().unwrap();
+        let alive = looks_like_ours(&process(other.id()));
+        stop(other);
+        assert!(!alive);
+
+        // Otro comando de lando tampoco es el que se apuntó
+        let other = spawn_looking_like("lando logs -f");
+        let alive = looks_like_ours(&process(other.id()));
+        stop(other);
+        assert!(!alive);
+    }
+
+    #[test]
+    fn redacted_commands_still_match() {
+        let child = spawn_looking_like("lando config --set database.creds.password=hunter2");
+        let mut entry = process(child.id());
+        entry.command = "lando config --set database.creds.password=***".to_string();
+        let ours = looks_like_ours(&entry);
+        stop(child);
+        assert!(ours);
+    }
+
+    #[test]
+    fn state_file_round_trip() {
+        let child = spawn_looking_like("lando logs -f");
+        let pid = child.id();
+        let saved = |pid: u32| load_app_file::<Vec<RunningProcess>>(RUNNING_PROCESSES_FILE).into_iter().find(|p| p.pid == pid);
+
+        let registration = ProcessRegistration::new(pid, "lando logs -f".to_string(), Some(Path::new("/srv/blog")));
+        let entry = saved(pid).unwrap();
+        assert_eq!((entry.command.as_str(), entry.project.as_path()), ("lando logs -f", Path::new("/srv/blog")));
+        assert!(entry.started_at > 0);
+        assert!(active_processes().contains(&entry));
+        // Si la app se cerrara ahora, al volver a abrirla sería un huérfano
+        let orphaned = find_orphaned_processes();
+        stop(child);
+        assert!(orphaned.contains(&entry));
+
+        drop(registration);
+        assert_eq!(saved(pid), None);
+        assert!(active_processes().iter().all(|p| p.pid != pid));
+    }
+
+    #[test]
+    fn dead_processes_are_not_ours() {
+        let mut finished = Command::new("true").spawn().unwrap();
+        finished.wait().unwrap();
+        assert!(!looks_like_ours(&process(finished.id())));
+    }
+}
@@ -0,0 +1,346 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::commands::shell_quote;
+use crate::core::storage::{load_app_file, save_app_file};
+use crate::models::lando::{DbConnection, LandoService};
+use crate::ui::database::{ConnectionProfile, DatabaseUI};
+
+// Las contraseñas de los perfiles no se guardan aquí: solo viven en memoria
+const PROFILES_FILE: &str = "connection_profiles.json";
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct SavedProfiles {
+    #[serde(default)]
+    profiles: Vec<ConnectionProfile>,
+    #[serde(default)]
+    active: Option<String>,
+}
+
+const MASKED_PASSWORD: &str = "••••••";
+
+// Formatos del menú "Copiar conexión"
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ConnectionStringFormat {
+    #[default]
+    Uri,
+    Jdbc,
+    DotEnv,
+    LandoDbCli,
+}
+
+impl ConnectionStringFormat {
+    pub const ALL: [ConnectionStringFormat; 4] = [
+        ConnectionStringFormat::Uri,
+        ConnectionStringFormat::Jdbc,
+        ConnectionStringFormat::DotEnv,
+        ConnectionStringFormat::LandoDbCli,
+    ];
+
+    // La URI lleva el esquema del motor: `postgresql://` (libpq) o `mysql://`
+    pub fn label(self, db_type: &str) -> &'static str {
+        match self {
+            ConnectionStringFormat::Uri if db_type.to_lowercase().starts_with("postgres") => "URI libpq (postgresql://)",
+            ConnectionStringFormat::Uri => "URI MySQL (mysql://)",
+            ConnectionStringFormat::Jdbc => "URL JDBC",
+            ConnectionStringFormat::DotEnv => "Bloque .env",
+            ConnectionStringFormat::LandoDbCli => "Comando lando db-cli",
+        }
+    }
+}
+
+// Esquema de las URIs del motor; `None` si no es MySQL/MariaDB ni PostgreSQL
+fn uri_scheme(db_type: &str) -> Option<&'static str> {
+    let db_type = db_type.to_lowercase();
+    if db_type.starts_with("postgres") {
+        Some("postgresql")
+    } else if db_type.starts_with("mysql") || db_type.starts_with("mariadb") {
+        Some("mysql")
+    } else {
+        None
+    }
+}
+
+// Los valores de un .env con espacios, `#` o comillas van entre comillas dobles
+fn dotenv_value(value: &str) -> String {
+    if value.chars().any(|c| c.is_whitespace() || matches!(c, '#' | '"' | '\'' | '$' | '\\')) {
+        format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\"").replace('$', "\\$"))
+    } else {
+        value.to_string()
+    }
+}
+
+// Perfiles de conexión guardados por proyecto y servicio
+impl DatabaseUI {
+    pub fn ensure_connection_profiles(&mut self, service: &LandoService, project_path: &PathBuf) {
+        let key = format!("{}#{}", project_path.display(), service.service);
+        if self.profiles_key.as_ref() == Some(&key) {
+            return;
+        }
+
+        let saved = load_app_file::<HashMap<String, SavedProfiles>>(PROFILES_FILE)
+            .remove(&key)
+            .unwrap_or_default();
+        self.active_profile = saved.active
+            .and_then(|name| saved.profiles.iter().position(|p| p.name == name));
+        self.connection_profiles = saved.profiles;
+        self.profiles_key = Some(key);
+        self.profile_error = None;
+    }
+
+    // Credenciales con las que se lanzan las consultas de este servicio: el
+    // perfil activo, si no las de `lando info` y, solo sin ninguna de las dos, root.
+    pub fn db_connection(&self, service: &LandoService) -> DbConnection {
+        let non_empty = |value: Option<&str>| value.filter(|v| !v.is_empty()).map(String::from);
+
+        if let Some(profile) = self.active_profile.and_then(|index| self.connection_profiles.get(index)) {
+            return DbConnection {
+                service: service.service.clone(),
+                user: non_empty(Some(profile.user.as_str())),
+                password: Some(profile.password.clone()).filter(|p| !p.is_empty()),
+                database: non_empty(Some(profile.database.as_str())),
+            };
+        }
+
+        let creds = service.creds.as_ref();
+        match creds.and_then(|c| non_empty(c.user.as_deref())) {
+            Some(user) => DbConnection {
+                service: service.service.clone(),
+                user: Some(user),
+                password: creds.and_then(|c| c.password.clone()).filter(|p| !p.is_empty()),
+                database: creds.and_then(|c| non_empty(c.database.as_deref())),
+            },
+            None => DbConnection {
+                service: service.service.clone(),
+                user: Some("root".to_string()),
+                password: None,
+                database: creds.and_then(|c| non_empty(c.database.as_deref())),
+            },
+        }
+    }
+
+    // URI para abrir el servicio en un cliente externo (TablePlus, DBeaver,
+    // psql...) con el host y puerto que lando publica fuera del contenedor.
+    // Con `mask_password` la contraseña se sustituye para mostrarla en pantalla.
+    pub fn external_connection_uri(&self, service: &LandoService, mask_password: bool) -> Option<String> {
+        let external = service.external_connection.as_ref()
+            .filter(|c| !c.host.is_empty() && c.port.parse::<u16>().is_ok())?;
+        let scheme = uri_scheme(service.db_engine())?;
+
+        let connection = self.db_connection(service);
+        let mut uri = format!("{}://", scheme);
+        if let Some(user) = &connection.user {
+            uri.push_str(&encode_uri_component(user));
+            if let Some(password) = &connection.password {
+                uri.push(':');
+                if mask_password {
+                    uri.push_str(MASKED_PASSWORD);
+                } else {
+                    uri.push_str(&encode_uri_component(password.expose()));
+                }
+            }
+            uri.push('@');
+        }
+        uri.push_str(&format!("{}:{}", external.host, external.port));
+        if let Some(database) = &connection.database {
+            uri.push('/');
+            uri.push_str(&encode_uri_component(database));
+        }
+        Some(uri)
+    }
+
+    // La conexión del servicio en el formato pedido, con el host y puerto
+    // publicados y las credenciales de `db_connection`. `None` si el formato
+    // no aplica (motor sin URI o servicio sin puerto externo).
+    pub fn connection_string(&self, service: &LandoService, format: ConnectionStringFormat, mask_password: bool) -> Option<String> {
+        let connection = self.db_connection(service);
+        let password = connection.password.as_ref().map(|p| {
+            if mask_password { MASKED_PASSWORD.to_string() } else { p.expose().to_string() }
+        });
+
+        match format {
+            ConnectionStringFormat::Uri => self.external_connection_uri(service, mask_password),
+            ConnectionStringFormat::Jdbc => {
+                let external = service.external_connection.as_ref()
+                    .filter(|c| !c.host.is_empty() && c.port.parse::<u16>().is_ok())?;
+                let scheme = uri_scheme(service.db_engine())?;
+                let mut url = format!("jdbc:{}://{}:{}/", scheme, external.host, external.port);
+                if let Some(database) = &connection.database {
+                    url.push_str(&encode_uri_component(database));
+                }
+                let mut params = Vec::new();
+                if let Some(user) = &connection.user {
+                    params.push(format!("user={}", encode_uri_component(user)));
+                }
+                if let Some(password) = &password {
+                    let password = if mask_password { password.clone() } else { encode_uri_component(password) };
+                    params.push(format!("password={}", password));
+                }
+                if !params.is_empty() {
+                    url.push('?');
+                    url.push_str(&params.join("&"));
+                }
+                Some(url)
+            }
+            ConnectionStringFormat::DotEnv => {
+                let external = service.external_connection.as_ref()
+                    .filter(|c| !c.host.is_empty() && c.port.parse::<u16>().is_ok())?;
+                let driver = match uri_scheme(service.db_engine())? {
+                    "postgresql" => "pgsql",
+                    _ => "mysql",
+                };
+                let lines = [
+                    ("DB_CONNECTION", Some(driver.to_string())),
+                    ("DB_HOST", Some(external.host.clone())),
+                    ("DB_PORT", Some(external.port.clone())),
+                    ("DB_DATABASE", connection.database.clone()),
+                    ("DB_USERNAME", connection.user.clone()),
+                    ("DB_PASSWORD", password),
+                ];
+                Some(lines.iter()
+                    .map(|(key, value)| format!("{}={}", key, value.as_deref().map(dotenv_value).unwrap_or_default()))
+                    .collect::<Vec<_>>()
+                    .join("\n"))
+            }
+            ConnectionStringFormat::LandoDbCli => {
+                uri_scheme(service.db_engine())?;
+                let mut command = format!("lando db-cli -s {}", shell_quote(&connection.service));
+                if let Some(user) = &connection.user {
+                    command.push_str(&format!(" -u {}", shell_quote(user)));
+                }
+                if let Some(password) = &password {
+                    command.push_str(&format!(" --password={}", shell_quote(password)));
+                }
+                if let Some(database) = &connection.database {
+                    command.push_str(&format!(" -D {}", shell_quote(database)));
+                }
+                Some(command)
+            }
+        }
+    }
+
+    pub fn open_in_external_client(&mut self, service: &LandoService) {
+        let Some(uri) = self.external_connection_uri(service, false) else {
+            return;
+        };
+        self.external_client_error = open::that(&uri)
+            .err()
+            .map(|e| format!("No hay ningún cliente registrado para {}: {}", uri.split("://").next().unwrap_or_default(), e));
+    }
+
+    pub fn select_connection_profile(&mut self, index: Option<usize>) {
+        self.active_profile = index.filter(|i| *i < self.connection_profiles.len());
+        // Cargar el perfil en el formulario para poder editarlo
+        if let Some(profile) = self.active_profile.and_then(|i| self.connection_profiles.get(i)) {
+            self.profile_name_input = profile.name.clone();
+            self.new_user = profile.user.clone();
+            self.new_password = profile.password.clone();
+            self.new_database = profile.database.clone();
+        }
+        self.persist_connection_profiles();
+    }
+
+    // Guarda el formulario como perfil; si ya existe uno con ese nombre se reemplaza
+    pub fn save_connection_profile(&mut self) {
+        let name = self.profile_name_input.trim().to_string();
+        if name.is_empty() || self.new_user.trim().is_empty() {
+            self.profile_error = Some("El perfil necesita nombre y usuario".to_string());
+            return;
+        }
+
+        let profile = ConnectionProfile {
+            name: name.clone(),
+            user: self.new_user.trim().to_string(),
+            password: self.new_password.clone(),
+            database: self.new_database.trim().to_string(),
+        };
+        let index = match self.connection_profiles.iter().position(|p| p.name == name) {
+            Some(index) => {
+                self.connection_profiles[index] = profile;
+                index
+            }
+            None => {
+                self.connection_profiles.push(profile);
+                self.connection_profiles.len() - 1
+            }
+        };
+        self.active_profile = Some(index);
+        self.persist_connection_profiles();
+    }
+
+    pub fn delete_active_profile(&mut self) {
+        let Some(index) = self.active_profile.take() else {
+            return;
+        };
+        if index < self.connection_profiles.len() {
+            self.connection_profiles.remove(index);
+        }
+        self.persist_connection_profiles();
+    }
+
+    fn persist_connection_profiles(&mut self) {
+        let Some(key) = self.profiles_key.clone() else {
+            return;
+        };
+
+        let mut saved: HashMap<String, SavedProfiles> = load_app_file(PROFILES_FILE);
+        saved.insert(key, SavedProfiles {
+            profiles: self.connection_profiles.clone(),
+            active: self.active_profile
+                .and_then(|i| self.connection_profiles.get(i))
+                .map(|p| p.name.clone()),
+        });
+
+        self.profile_error = save_app_file(PROFILES_FILE, &saved)
+            .err()
+            .map(|e| format!("No se pudieron guardar los perfiles: {}", e));
+    }
+}
+
+// Usuario, contraseña y base de datos pueden llevar `@`, `:` o `/`
+pub fn encode_uri_component(value: &str) -> String {
+    value.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => (b as char).to_string(),
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::secret::SecretString;
+
+    #[test]
+    fn saved_profiles_leave_the_password_out() {
+        let saved = SavedProfiles {
+            profiles: vec![ConnectionProfile {
+                name: "app".to_string(),
+                user: "drupal".to_string(),
+                password: SecretString::new("s3cret"),
+                database: "drupal".to_string(),
+            }],
+            active: Some("app".to_string()),
+        };
+        let json = serde_json::to_string(&saved).unwrap();
+        assert!(!json.contains("s3cret"), "{}", json);
+        assert!(!json.contains("password"), "{}", json);
+
+        // Los archivos de versiones anteriores aún pueden traerla
+        let loaded: SavedProfiles = serde_json::from_str(
+            r#"{"profiles": [{"name": "app", "user": "drupal", "password": "old", "database": "drupal"}]}"#,
+        ).unwrap();
+        assert_eq!(loaded.profiles[0].password.expose(), "old");
+    }
+
+    #[test]
+    fn pending_results_keep_the_query_without_passwords() {
+        let mut database_ui = DatabaseUI::default();
+        database_ui.push_pending_result("CREATE USER 'app'@'%' IDENTIFIED BY 's3cret'".to_string(), "Ejecutando...");
+        let query = &database_ui.query_results.last().unwrap().query;
+        assert!(!query.contains("s3cret"), "{}", query);
+    }
+}
@@ -0,0 +1,227 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+use crate::models::prompts::{LandoPrompt, PromptKind};
+
+// Subcomandos que aceptan un flag para no preguntar
+const AUTO_YES_FLAGS: &[(&str, &str)] = &[
+    ("destroy", "-y"),
+    ("rebuild", "-y"),
+];
+
+// Bytes que se conservan de la salida para buscar preguntas
+pub const PROMPT_TAIL_BYTES: usize = 4096;
+
+// Desactivado hasta que se active en ajustes: `destroy -y` no pide confirmación
+static AUTO_YES: AtomicBool = AtomicBool::new(false);
+
+static ANSI_ESCAPE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\x1b\[[0-9;?]*[A-Za-z]|\x1b[()][A-Za-z0-9]|\r").unwrap()
+});
+
+// `(y/N)`, `[Y/n]`, `(yes/no)`... al final de la línea
+static CONFIRM_SUFFIX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)[(\[]\s*(y(?:es)?)\s*/\s*(n(?:o)?)\s*[)\]]\s*:?\s*$").unwrap()
+});
+
+pub fn set_auto_yes(enabled: bool) {
+    AUTO_YES.store(enabled, Ordering::Relaxed);
+}
+
+// Añade `-y` a los subcomandos que lo admiten (si está activado en ajustes)
+pub fn with_auto_yes(mut args: Vec<String>) -> Vec<String> {
+    if !AUTO_YES.load(Ordering::Relaxed) {
+        return args;
+    }
+    let Some(flag) = args.first().and_then(|sub| {
+        AUTO_YES_FLAGS.iter().find(|(name, _)| name == sub).map(|(_, flag)| *flag)
+    }) else {
+        return args;
+    };
+    if !args.iter().any(|a| a == "-y" || a == "--yes") {
+        args.push(flag.to_string());
+    }
+    args
+}
+
+pub fn strip_ansi(text: &str) -> String {
+    ANSI_ESCAPE.replace_all(text, "").to_string()
+}
+
+// Recorta el buffer a los últimos `PROMPT_TAIL_BYTES` sin partir caracteres
+pub fn trim_prompt_tail(tail: &mut String) {
+    if tail.len() <= PROMPT_TAIL_BYTES {
+        return;
+    }
+    let mut cut = tail.len() - PROMPT_TAIL_BYTES;
+    while !tail.is_char_boundary(cut) {
+        cut += 1;
+    }
+    tail.drain(..cut);
+}
+
+// Busca una pregunta pendiente al final de la salida de un comando.
+// Cubre las confirmaciones `(y/N)`, los menús de inquirer (`❯ opción`) y las
+// preguntas abiertas de inquirer (`? texto` sin salto de línea final).
+pub fn detect_prompt(tail: &str) -> Option<LandoPrompt> {
+    let clean = strip_ansi(tail);
+    let waiting_on_line = !clean.ends_with('\n');
+    let lines: Vec<&str> = clean.lines().map(str::trim_end).filter(|l| !l.trim().is_empty()).collect();
+    let last = *lines.last()?;
+
+    if let Some(captures) = CONFIRM_SUFFIX.captures(last) {
+        let default_yes = captures.get(1).is_some_and(|m| m.as_str().starts_with('Y'));
+        return Some(LandoPrompt { text: prompt_text(last), kind: PromptKind::Confirm { default_yes } });
+    }
+
+    // Menú: la pregunta (`? ...`) seguida de las opciones, una marcada con `❯`
+    let recent = &lines[lines.len().saturating_sub(20)..];
+    if recent.iter().any(|l| l.contains('❯')) {
+        let question = recent.iter().rposition(|l| l.trim_start().starts_with('?'))?;
+        let options: Vec<&str> = recent[question + 1..].to_vec();
+        let selected = options.iter().position(|l| l.contains('❯'))?;
+        return Some(LandoPrompt {
+            text: prompt_text(recent[question]),
+            kind: PromptKind::Choice {
+                options: options.iter().map(|o| o.replace('❯', "").trim().to_string()).collect(),
+                selected,
+            },
+        });
+    }
+
+    if waiting_on_line && last.trim_start().starts_with('?') {
+        return Some(LandoPrompt { text: prompt_text(last), kind: PromptKind::Text });
+    }
+
+    None
+}
+
+fn prompt_text(line: &str) -> String {
+    line.trim().trim_start_matches('?').trim().to_string()
+}
+
+// Lo que se escribe en stdin para cada respuesta
+pub fn confirm_answer(yes: bool) -> Vec<u8> {
+    if yes { b"y\n".to_vec() } else { b"n\n".to_vec() }
+}
+
+// Los menús de inquirer se manejan con las flechas: se baja o sube desde la
+// opción marcada hasta la elegida y se pulsa Enter.
+pub fn choice_answer(selected: usize, chosen: usize) -> Vec<u8> {
+    let (arrow, steps) = if chosen >= selected {
+        ("\x1b[B", chosen - selected)
+    } else {
+        ("\x1b[A", selected - chosen)
+    };
+    let mut bytes = arrow.repeat(steps).into_bytes();
+    bytes.push(b'\r');
+    bytes
+}
+
+pub fn text_answer(text: &str) -> Vec<u8> {
+    format!("{}\n", text).into_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_confirm_prompts_and_their_default() {
+        let prompt = detect_prompt("Destroying myapp\n? Are you sure you want to destroy this app? (y/N) ").unwrap();
+        assert_eq!(prompt.text, "Are you sure you want to destroy this app? (y/N)");
+        assert_eq!(prompt.kind, PromptKind::Confirm { default_yes: false });
+
+        let prompt = detect_prompt("? Rebuild the app? [Y/n]").unwrap();
+        assert_eq!(prompt.kind, PromptKind::Confirm { default_yes: true });
+
+        let prompt = detect_prompt("Overwrite the existing database? (yes/no): ").unwrap();
+        assert_eq!(prompt.kind, PromptKind::Confirm { default_yes: false });
+    }
+
+    #[test]
+    fn detects_inquirer_menus() {
+        let tail = "? What recipe do you want to use? (Use arrow keys)\n  drupal10\n❯ lamp\n  wordpress\n";
+        let prompt = detect_prompt(tail).unwrap();
+        assert_eq!(prompt.text, "What recipe do you want to use? (Use arrow keys)");
+        assert_eq!(prompt.kind, PromptKind::Choice {
+            options: vec!["drupal10".to_string(), "lamp".to_string(), "wordpress".to_string()],
+            selected: 1,
+        });
+    }
+
+    #[test]
+    fn detects_open_questions_only_while_waiting_on_the_line() {
+        let prompt = detect_prompt("Setting up\n? What do you want to call this app? ").unwrap();
+        assert_eq!(prompt.text, "What do you want to call this app?");
+        assert_eq!(prompt.kind, PromptKind::Text);
+
+        // Con salto de línea final ya no está esperando respuesta
+        assert_eq!(detect_prompt("? What do you want to call this app? myapp\n"), None);
+    }
+
+    #[test]
+    fn ignores_regular_output() {
+        assert_eq!(detect_prompt("Starting myapp_appserver_1 ... done\n"), None);
+        assert_eq!(detect_prompt("Pulling images (1/3)\n"), None);
+        assert_eq!(detect_prompt(""), None);
+    }
+
+    #[test]
+    fn detects_prompts_wrapped_in_ansi_sequences() {
+        let tail = "\x1b[32m?\x1b[39m \x1b[1mAre you sure you want to destroy this app?\x1b[22m \x1b[2m(y/N)\x1b[22m \x1b[?25h";
+        let prompt = detect_prompt(tail).unwrap();
+        assert_eq!(prompt.text, "Are you sure you want to destroy this app? (y/N)");
+        assert_eq!(prompt.kind, PromptKind::Confirm { default_yes: false });
+
+        let tail = "\x1b[32m?\x1b[39m Pick one\n  a\n\x1b[36m❯ b\x1b[39m\r\n";
+        let prompt = detect_prompt(tail).unwrap();
+        assert_eq!(prompt.kind, PromptKind::Choice { options: vec!["a".to_string(), "b".to_string()], selected: 1 });
+    }
+
+    #[test]
+    fn detects_a_prompt_split_across_chunks_once_complete() {
+        let mut tail = String::new();
+        tail.push_str("? Are you sure you want to destroy this ");
+        assert_eq!(detect_prompt(&tail).map(|p| p.kind), Some(PromptKind::Text));
+        tail.push_str("app? (y/");
+        assert_eq!(detect_prompt(&tail).map(|p| p.kind), Some(PromptKind::Text));
+        tail.push_str("N) ");
+        assert_eq!(detect_prompt(&tail).map(|p| p.kind), Some(PromptKind::Confirm { default_yes: false }));
+    }
+
+    #[test]
+    fn strip_ansi_removes_colors_cursor_codes_and_carriage_returns() {
+        assert_eq!(strip_ansi("\x1b[1;32mdone\x1b[0m\r\n"), "done\n");
+        assert_eq!(strip_ansi("\x1b[?25lhidden cursor\x1b[?25h"), "hidden cursor");
+        assert_eq!(strip_ansi("\x1b(Bplain"), "plain");
+        assert_eq!(strip_ansi("sin escapes"), "sin escapes");
+    }
+
+    #[test]
+    fn trim_prompt_tail_keeps_the_end_without_splitting_characters() {
+        let mut short = "? pregunta".to_string();
+        trim_prompt_tail(&mut short);
+        assert_eq!(short, "? pregunta");
+
+        let mut tail = "ñ".repeat(PROMPT_TAIL_BYTES) + "? Are you sure? (y/N)";
+        trim_prompt_tail(&mut tail);
+        assert!(tail.len() <= PROMPT_TAIL_BYTES);
+        assert!(tail.ends_with("? Are you sure? (y/N)"));
+        assert!(tail.starts_with('ñ'));
+    }
+
+    #[test]
+    fn auto_yes_is_off_by_default_and_only_for_known_subcommands() {
+        let args = |list: &[&str]| list.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        assert_eq!(with_auto_yes(args(&["destroy"])), args(&["destroy"]));
+
+        set_auto_yes(true);
+        assert_eq!(with_auto_yes(args(&["destroy"])), args(&["destroy", "-y"]));
+        assert_eq!(with_auto_yes(args(&["rebuild", "--yes"])), args(&["rebuild", "--yes"]));
+        assert_eq!(with_auto_yes(args(&["start"])), args(&["start"]));
+        set_auto_yes(false);
+    }
+}
@@ -0,0 +1,204 @@
+use std::path::PathBuf;
+use std::sync::mpsc::Sender;
+use crate::core::time::now_secs;
+use crate::models::commands::LandoCommandOutcome;
+use crate::models::lando::LandoService;
+use crate::ui::database::{CachedQuery, DatabaseUI, QueryCache, QueryResult};
+
+// Espacios colapsados y sin `;` final, para que `SELECT 1;` y `SELECT  1` compartan entrada
+pub fn normalize_query(query: &str) -> String {
+    query.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .trim_end_matches(';')
+        .trim_end()
+        .to_string()
+}
+
+pub fn format_cache_age(fetched_at: u64) -> String {
+    let elapsed = now_secs().saturating_sub(fetched_at);
+    match elapsed {
+        0..=59 => "hace unos segundos".to_string(),
+        60..=3599 => format!("hace {} min", elapsed / 60),
+        _ => format!("hace {} h", elapsed / 3600),
+    }
+}
+
+impl CachedQuery {
+    pub fn size(&self) -> usize {
+        self.query.len() + self.result.result.len()
+    }
+}
+
+impl QueryCache {
+    // Devuelve la entrada y la marca como la usada más recientemente
+    pub fn get(&mut self, service: &str, query: &str) -> Option<&CachedQuery> {
+        let query = normalize_query(query);
+        let index = self.entries.iter().position(|e| e.service == service && e.query == query)?;
+        let entry = self.entries.remove(index);
+        self.entries.push(entry);
+        self.entries.last()
+    }
+
+    pub fn insert(&mut self, service: &str, query: &str, result: QueryResult) {
+        let query = normalize_query(query);
+        self.entries.retain(|e| !(e.service == service && e.query == query));
+        self.entries.push(CachedQuery {
+            service: service.to_string(),
+            query,
+            result,
+            fetched_at: now_secs(),
+        });
+        self.enforce_limits();
+    }
+
+    pub fn invalidate_service(&mut self, service: &str) {
+        self.entries.retain(|e| e.service != service);
+    }
+
+    pub fn remove(&mut self, index: usize) {
+        if index < self.entries.len() {
+            self.entries.remove(index);
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    pub fn total_bytes(&self) -> usize {
+        self.entries.iter().map(CachedQuery::size).sum()
+    }
+
+    // Expulsa las entradas menos usadas hasta cumplir ambos límites. Un
+    // resultado que por sí solo supera `max_bytes` tampoco se conserva.
+    pub fn enforce_limits(&mut self) {
+        while !self.entries.is_empty()
+            && (self.entries.len() > self.max_entries || self.total_bytes() > self.max_bytes)
+        {
+            self.entries.remove(0);
+        }
+    }
+}
+
+impl DatabaseUI {
+    // Muestra el resultado en caché de la consulta del editor, si lo hay
+    pub fn show_cached_result(&mut self, service: &LandoService) -> bool {
+        if !self.enable_query_cache {
+            return false;
+        }
+        let Some(entry) = self.query_cache.get(&service.service, &self.query_input) else {
+            return false;
+        };
+
+        let mut result = entry.result.clone();
+        result.cached_at = Some(entry.fetched_at);
+        self.query_results.push(result);
+        self.current_result_index = self.query_results.len() - 1;
+        if self.query_results.len() > 20 {
+            self.query_results.remove(0);
+            self.current_result_index -= 1;
+        }
+        true
+    }
+
+    // Reejecución desde historial o consultas guardadas: se sirve de la caché
+    // cuando está habilitada; "Ejecutar" siempre va a lando
+    pub fn run_query_cached(
+        &mut self,
+        service: &LandoService,
+        project_path: &PathBuf,
+        sender: &Sender<LandoCommandOutcome>,
+        is_loading: &mut bool,
+    ) {
+        if !self.show_cached_result(service) {
+            self.execute_query(service, project_path, sender, is_loading);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+
+    fn result(text: &str) -> QueryResult {
+        QueryResult {
+            query: String::new(),
+            result: Arc::from(text),
+            execution_time: 0.0,
+            timestamp: 0,
+            rows_affected: None,
+            has_error: false,
+            cached_at: None,
+            request_id: None,
+        }
+    }
+
+    fn cached_queries(cache: &QueryCache) -> Vec<&str> {
+        cache.entries.iter().map(|e| e.query.as_str()).collect()
+    }
+
+    #[test]
+    fn normalized_queries_share_an_entry() {
+        let mut cache = QueryCache::default();
+        cache.insert("database", "SELECT  1;", result("1"));
+        assert!(cache.get("database", "SELECT 1").is_some());
+        assert!(cache.get("database", "  SELECT\n1 ; ").is_some());
+        assert!(cache.get("other", "SELECT 1").is_none());
+        assert_eq!(normalize_query("SELECT\t*\nFROM t;"), "SELECT * FROM t");
+    }
+
+    #[test]
+    fn evicts_the_least_recently_used_entry() {
+        let mut cache = QueryCache { max_entries: 2, ..QueryCache::default() };
+        cache.insert("db", "SELECT 1", result("1"));
+        cache.insert("db", "SELECT 2", result("2"));
+        // Leer la primera la convierte en la más reciente
+        cache.get("db", "SELECT 1");
+        cache.insert("db", "SELECT 3", result("3"));
+        assert_eq!(cached_queries(&cache), vec!["SELECT 1", "SELECT 3"]);
+    }
+
+    #[test]
+    fn reinserting_replaces_the_entry() {
+        let mut cache = QueryCache::default();
+        cache.insert("db", "SELECT 1", result("old"));
+        cache.insert("db", "SELECT 2", result("2"));
+        cache.insert("db", "SELECT 1;", result("new"));
+        assert_eq!(cached_queries(&cache), vec!["SELECT 2", "SELECT 1"]);
+        assert_eq!(&*cache.get("db", "SELECT 1").unwrap().result.result, "new");
+    }
+
+    #[test]
+    fn evicts_by_size_and_drops_results_larger_than_the_limit() {
+        let mut cache = QueryCache { max_bytes: 40, ..QueryCache::default() };
+        cache.insert("db", "SELECT 1", result(&"a".repeat(10)));
+        cache.insert("db", "SELECT 2", result(&"b".repeat(10)));
+        cache.insert("db", "SELECT 3", result(&"c".repeat(10)));
+        assert_eq!(cached_queries(&cache), vec!["SELECT 2", "SELECT 3"]);
+        assert!(cache.total_bytes() <= 40);
+
+        cache.insert("db", "SELECT 4", result(&"d".repeat(100)));
+        assert!(cache.entries.is_empty());
+    }
+
+    #[test]
+    fn invalidating_a_service_keeps_the_others() {
+        let mut cache = QueryCache::default();
+        cache.insert("database", "SELECT 1", result("1"));
+        cache.insert("postgres", "SELECT 1", result("1"));
+        cache.invalidate_service("database");
+        assert!(cache.get("database", "SELECT 1").is_none());
+        assert!(cache.get("postgres", "SELECT 1").is_some());
+    }
+
+    #[test]
+    fn mutating_statements_are_detected_for_invalidation() {
+        let database_ui = DatabaseUI::default();
+        assert!(database_ui.is_mutating_sql("  UPDATE users SET a = 1"));
+        assert!(database_ui.is_mutating_sql("SELECT 1; delete from users"));
+        assert!(!database_ui.is_mutating_sql("SELECT * FROM users"));
+    }
+}
@@ -0,0 +1,130 @@
+use crate::core::result_format::parse_date_cell;
+use crate::ui::database::DatabaseUI;
+
+// Resultados que se muestran como mucho en la búsqueda de consultas
+pub const QUERY_SEARCH_MAX_HITS: usize = 100;
+const EXCERPT_MAX_CHARS: usize = 120;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum QuerySearchSource {
+    History,
+    Saved(String), // Nombre de la consulta guardada
+}
+
+#[derive(Debug, Clone)]
+pub struct QuerySearchHit {
+    pub source: QuerySearchSource,
+    pub query: String,
+    pub timestamp: Option<u64>,
+    pub result_excerpt: Option<String>, // Línea del último resultado que coincide
+}
+
+// Filtros del panel "Buscar consultas" de Herramientas
+#[derive(Debug, Clone, Default)]
+pub struct QuerySearchState {
+    pub text: String,
+    pub from: String, // YYYY-MM-DD
+    pub to: String,
+    pub include_results: bool,
+}
+
+// Día `YYYY-MM-DD` en segundos UTC; con `end` se toma el último segundo del día.
+// Vacío: sin límite.
+pub fn parse_day_bound(text: &str, end: bool) -> Result<Option<u64>, String> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Ok(None);
+    }
+    let days = parse_date_cell(text)
+        .filter(|days| *days >= 0.0)
+        .ok_or_else(|| format!("Fecha no válida: '{}' (usa AAAA-MM-DD)", text))?;
+    let start = days.floor() as u64 * 86400;
+    Ok(Some(if end { start + 86399 } else { start }))
+}
+
+fn excerpt(line: &str) -> String {
+    let line = line.trim();
+    if line.chars().count() > EXCERPT_MAX_CHARS {
+        format!("{}…", line.chars().take(EXCERPT_MAX_CHARS).collect::<String>())
+    } else {
+        line.to_string()
+    }
+}
+
+impl DatabaseUI {
+    // Cuándo se ejecutó por última vez: lo apuntado en el historial o, si no,
+    // el resultado más reciente de esa consulta
+    pub fn history_timestamp(&self, query: &str) -> Option<u64> {
+        self.query_history_times.get(query).copied().or_else(|| {
+            self.query_results.iter()
+                .filter(|r| r.query == query && r.request_id.is_none())
+                .map(|r| r.timestamp)
+                .max()
+        })
+    }
+
+    // Busca en el historial y en las consultas guardadas (nombre y SQL) y,
+    // si se pide, en el último resultado de cada consulta del historial.
+    // Sin texto ni fechas no devuelve nada.
+    pub fn search_queries(&self) -> Result<Vec<QuerySearchHit>, String> {
+        let search = &self.query_search;
+        let needle = search.text.trim().to_lowercase();
+        let from = parse_day_bound(&search.from, false)?;
+        let to = parse_day_bound(&search.to, true)?;
+        if needle.is_empty() && from.is_none() && to.is_none() {
+            return Ok(Vec::new());
+        }
+
+        // Con rango de fechas, lo que no tiene fecha se descarta
+        let in_range = |timestamp: Option<u64>| match timestamp {
+            Some(ts) => from.is_none_or(|f| ts >= f) && to.is_none_or(|t| ts <= t),
+            None => from.is_none() && to.is_none(),
+        };
+        let contains = |text: &str| needle.is_empty() || text.to_lowercase().contains(&needle);
+
+        let mut hits = Vec::new();
+        for query in self.query_history.iter().rev() {
+            let timestamp = self.history_timestamp(query);
+            if !in_range(timestamp) {
+                continue;
+            }
+
+            let result_excerpt = (search.include_results && !needle.is_empty())
+                .then(|| {
+                    self.query_results.iter()
+                        .rev()
+                        .find(|r| r.query == *query && r.request_id.is_none())
+                        .and_then(|r| r.result.lines().find(|line| contains(line)).map(excerpt))
+                })
+                .flatten();
+            if contains(query) || result_excerpt.is_some() {
+                hits.push(QuerySearchHit {
+                    source: QuerySearchSource::History,
+                    query: query.clone(),
+                    timestamp,
+                    result_excerpt,
+                });
+            }
+        }
+
+        let mut names: Vec<&String> = self.saved_queries.keys().collect();
+        names.sort();
+        for name in names {
+            let query = &self.saved_queries[name];
+            let timestamp = self.saved_query_times.get(name).copied();
+            if in_range(timestamp) && (contains(name) || contains(query)) {
+                hits.push(QuerySearchHit {
+                    source: QuerySearchSource::Saved(name.clone()),
+                    query: query.clone(),
+                    timestamp,
+                    result_excerpt: None,
+                });
+            }
+        }
+
+        // Lo más reciente primero; lo que no tiene fecha, al final
+        hits.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+        hits.truncate(QUERY_SEARCH_MAX_HITS);
+        Ok(hits)
+    }
+}
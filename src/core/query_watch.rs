@@ -0,0 +1,168 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::mpsc::Sender;
+use std::time::{Duration, Instant};
+
+use crate::core::commands::{next_request_id, run_db_query};
+use crate::core::result_format::{parse_result_set, ParsedResultSet};
+use crate::models::commands::LandoCommandOutcome;
+use crate::models::lando::LandoService;
+use crate::ui::database::{DatabaseTab, DatabaseUI, PendingRequest};
+
+pub const WATCH_MIN_INTERVAL: u64 = 2;
+pub const WATCH_MAX_INTERVAL: u64 = 300;
+const WATCH_MAX_FAILURES: u32 = 3;
+
+// Diferencias entre dos ejecuciones: filas nuevas (índices del resultado
+// actual), filas que ya no están (copiadas del anterior) y celdas cambiadas
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WatchDiff {
+    pub added: BTreeSet<usize>,
+    pub removed: Vec<Vec<String>>,
+    pub changed: HashSet<(usize, usize)>,
+}
+
+impl WatchDiff {
+    pub fn change_count(&self) -> usize {
+        self.added.len() + self.removed.len() + self.changed.len()
+    }
+}
+
+fn unique_keys(set: &ParsedResultSet) -> Option<HashMap<&str, usize>> {
+    let mut keys = HashMap::new();
+    for (index, row) in set.rows.iter().enumerate() {
+        if keys.insert(row.first()?.as_str(), index).is_some() {
+            return None;
+        }
+    }
+    Some(keys)
+}
+
+// Si las columnas coinciden y la primera no se repite se usa como clave de
+// fila, lo que permite marcar celdas cambiadas. Si no, se comparan filas
+// completas: una fila modificada cuenta como quitada y añadida.
+pub fn diff_result_sets(previous: &ParsedResultSet, current: &ParsedResultSet) -> WatchDiff {
+    let mut diff = WatchDiff::default();
+
+    let keyed = (previous.columns == current.columns)
+        .then(|| unique_keys(previous).zip(unique_keys(current)))
+        .flatten();
+    if let Some((previous_keys, current_keys)) = keyed {
+        for (index, row) in current.rows.iter().enumerate() {
+            match previous_keys.get(row[0].as_str()) {
+                None => { diff.added.insert(index); }
+                Some(&old) => {
+                    let old_row = &previous.rows[old];
+                    for (column, value) in row.iter().enumerate() {
+                        if old_row.get(column) != Some(value) {
+                            diff.changed.insert((index, column));
+                        }
+                    }
+                }
+            }
+        }
+        diff.removed = previous.rows.iter()
+            .filter(|row| !current_keys.contains_key(row[0].as_str()))
+            .cloned()
+            .collect();
+        return diff;
+    }
+
+    let mut unmatched: Vec<Option<&Vec<String>>> = previous.rows.iter().map(Some).collect();
+    for (index, row) in current.rows.iter().enumerate() {
+        match unmatched.iter_mut().find(|old| **old == Some(row)) {
+            Some(old) => *old = None,
+            None => { diff.added.insert(index); }
+        }
+    }
+    diff.removed = unmatched.into_iter().flatten().cloned().collect();
+    diff
+}
+
+// Modo vigilancia del editor: vuelve a lanzar la consulta cada N segundos
+// mientras la interfaz está abierta. Va por las peticiones etiquetadas, así
+// que no ocupa el historial de resultados ni bloquea el editor.
+impl DatabaseUI {
+    pub fn start_query_watch(&mut self) {
+        let query = self.query_input.trim().to_string();
+        if query.is_empty() {
+            return;
+        }
+        let watch = &mut self.query_watch;
+        watch.enabled = true;
+        watch.query = query;
+        watch.next_run = None;
+        watch.failures = 0;
+        watch.current = None;
+        watch.diff = None;
+        watch.error = None;
+        watch.stop_reason = None;
+    }
+
+    pub fn stop_query_watch(&mut self, reason: Option<&str>) {
+        let watch = &mut self.query_watch;
+        if !watch.enabled {
+            return;
+        }
+        watch.enabled = false;
+        watch.next_run = None;
+        watch.stop_reason = reason.map(String::from);
+        // La respuesta en vuelo, si llega, se descarta
+        if let Some(request_id) = watch.request_id.take() {
+            self.pending_requests.remove(&request_id);
+        }
+    }
+
+    pub fn tick_query_watch(&mut self, service: &LandoService, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>) {
+        if !self.query_watch.enabled {
+            return;
+        }
+        if self.current_tab != DatabaseTab::QueryEditor {
+            self.stop_query_watch(Some("Se cambió de pestaña"));
+            return;
+        }
+        // Se salta el intervalo si la ejecución anterior no ha terminado
+        if self.query_watch.request_id.is_some() {
+            return;
+        }
+
+        let now = Instant::now();
+        let next_run = *self.query_watch.next_run.get_or_insert(now);
+        if now < next_run {
+            return;
+        }
+
+        let request_id = next_request_id();
+        self.query_watch.request_id = Some(request_id);
+        self.query_watch.next_run = Some(now + Duration::from_secs(self.query_watch.interval_secs));
+        self.pending_requests.insert(request_id, PendingRequest::WatchQuery);
+        run_db_query(sender.clone(), project_path.clone(), self.db_connection(service), self.query_watch.query.clone(), request_id);
+    }
+
+    pub fn handle_watch_result(&mut self, output: &Result<String, String>) {
+        let watch = &mut self.query_watch;
+        watch.request_id = None;
+
+        match output.as_ref().map(|text| parse_result_set(text)) {
+            Ok(Some(set)) => {
+                watch.failures = 0;
+                watch.error = None;
+                watch.diff = watch.current.take().map(|previous| diff_result_sets(&previous, &set));
+                watch.current = Some(set);
+                watch.last_update = Some(Instant::now());
+            }
+            Ok(None) => {
+                watch.failures = 0;
+                watch.error = Some("La consulta no devolvió filas".to_string());
+                watch.last_update = Some(Instant::now());
+            }
+            Err(msg) => {
+                watch.failures += 1;
+                watch.error = Some(msg.lines().next().unwrap_or_default().to_string());
+                if watch.failures >= WATCH_MAX_FAILURES {
+                    self.stop_query_watch(Some("Se detuvo tras 3 errores seguidos"));
+                }
+            }
+        }
+    }
+}
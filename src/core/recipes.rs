@@ -0,0 +1,102 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use serde_yaml::{Mapping, Value};
+
+use crate::core::commands::shell_quote;
+use crate::models::recipes::QuickAction;
+
+// Lee la clave `recipe:` del `.lando.yml` del proyecto
+pub fn detect_recipe(project_path: &Path) -> Option<String> {
+    let content = fs::read_to_string(project_path.join(".lando.yml")).ok()?;
+    let config: Value = serde_yaml::from_str(&content).ok()?;
+    config.get("recipe")?.as_str().map(|r| r.trim().to_string()).filter(|r| !r.is_empty())
+}
+
+// Lee la clave `name:` del `.lando.yml` del proyecto
+pub fn detect_app_name(project_path: &Path) -> Option<String> {
+    let content = fs::read_to_string(project_path.join(".lando.yml")).ok()?;
+    let config: Value = serde_yaml::from_str(&content).ok()?;
+    config.get("name")?.as_str().map(|n| n.trim().to_string()).filter(|n| !n.is_empty())
+}
+
+// Nombres de los comandos declarados en `tooling:` del `.lando.yml`
+pub fn detect_tooling(project_path: &Path) -> Vec<String> {
+    let Some(config) = fs::read_to_string(project_path.join(".lando.yml"))
+        .ok()
+        .and_then(|content| serde_yaml::from_str::<Value>(&content).ok())
+    else {
+        return Vec::new();
+    };
+    config.get("tooling")
+        .and_then(Value::as_mapping)
+        .map(|tooling| tooling.keys().filter_map(Value::as_str).map(String::from).collect())
+        .unwrap_or_default()
+}
+
+// Activa o desactiva xdebug en el `.lando.yml`. Con receta el appserver se
+// configura en `config.xdebug`; el resto de servicios en `services.<nombre>.xdebug`.
+// serde_yaml no conserva los comentarios, así que antes se copia a `.lando.yml.bak`.
+pub fn set_xdebug_in_lando_file(project_path: &Path, service: &str, enabled: bool) -> Result<(), String> {
+    let file = project_path.join(".lando.yml");
+    let content = fs::read_to_string(&file)
+        .map_err(|e| format!("No se pudo leer {}: {}", file.display(), e))?;
+    let mut config: Value = serde_yaml::from_str(&content)
+        .map_err(|e| format!("El .lando.yml no es YAML válido: {}", e))?;
+    let root = config.as_mapping_mut()
+        .ok_or_else(|| "El .lando.yml no tiene claves de primer nivel".to_string())?;
+
+    let section = if root.contains_key("recipe") && service == "appserver" {
+        mapping_entry(root, "config")
+    } else {
+        mapping_entry(mapping_entry(root, "services"), service)
+    };
+    section.insert(Value::from("xdebug"), Value::from(enabled));
+
+    let output = serde_yaml::to_string(&config)
+        .map_err(|e| format!("No se pudo generar el YAML: {}", e))?;
+    fs::copy(&file, project_path.join(".lando.yml.bak"))
+        .map_err(|e| format!("No se pudo crear la copia .lando.yml.bak: {}", e))?;
+    fs::write(&file, output).map_err(|e| format!("No se pudo escribir {}: {}", file.display(), e))
+}
+
+// Devuelve el mapa bajo `key`, creándolo si falta o no es un mapa
+pub fn mapping_entry<'a>(map: &'a mut Mapping, key: &str) -> &'a mut Mapping {
+    let value = map.entry(Value::from(key)).or_insert(Value::Null);
+    if !value.is_mapping() {
+        *value = Value::Mapping(Mapping::new());
+    }
+    value.as_mapping_mut().expect("recién convertido en mapa")
+}
+
+impl QuickAction {
+    // Argumentos de `lando` con los campos del formulario ya sustituidos.
+    // Cada valor va en su propio argumento, así que no hace falta escaparlo.
+    pub fn build_args(&self, values: &HashMap<String, String>) -> Result<Vec<String>, String> {
+        if let Some(field) = self.form_fields.iter().find(|f| values.get(f.key).is_none_or(|v| v.trim().is_empty())) {
+            return Err(format!("Falta '{}'", field.label));
+        }
+
+        Ok(self.command.iter()
+            .map(|arg| {
+                self.form_fields.iter().fold(arg.to_string(), |arg, field| {
+                    arg.replace(&format!("{{{}}}", field.key), values[field.key].trim())
+                })
+            })
+            .collect())
+    }
+
+    pub fn command_preview(&self, values: &HashMap<String, String>) -> String {
+        match self.build_args(values) {
+            Ok(args) => format!("lando {}", args.join(" ")),
+            Err(_) => format!("lando {}", self.command.join(" ")),
+        }
+    }
+}
+
+// Línea para el terminal interactivo: entra en el proyecto y lanza el comando
+pub fn interactive_command_line(project_path: &Path, args: &[String]) -> String {
+    let args = args.iter().map(|a| shell_quote(a)).collect::<Vec<_>>().join(" ");
+    format!("cd {} && lando {}\n", shell_quote(&project_path.to_string_lossy()), args)
+}
@@ -0,0 +1,163 @@
+use std::path::PathBuf;
+use std::sync::mpsc::Sender;
+use crate::core::commands::*;
+use crate::models::commands::LandoCommandOutcome;
+use crate::models::lando::LandoService;
+use crate::models::secret::SecretString;
+use crate::ui::redis::{RedisResult, RedisUI};
+
+// Prefijos con los que redis-cli (sin TTY) devuelve los errores del servidor
+const REDIS_ERROR_PREFIXES: &[&str] = &["(error)", "ERR ", "WRONGTYPE ", "NOAUTH ", "NOPERM "];
+
+impl RedisUI {
+    pub fn run_command(&mut self, service: &LandoService, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>, command: &str) {
+        let command = command.trim();
+        if command.is_empty() || !self.pending_requests.is_empty() {
+            return;
+        }
+
+        let args = match split_redis_args(command) {
+            Ok(args) => args,
+            Err(e) => {
+                self.result = Some(RedisResult {
+                    command: command.to_string(),
+                    entries: Vec::new(),
+                    raw: String::new(),
+                    error: Some(e),
+                });
+                return;
+            }
+        };
+
+        if self.history.last().map(String::as_str) != Some(command) {
+            self.history.push(command.to_string());
+        }
+
+        let request_id = next_request_id();
+        self.pending_requests.insert(request_id, command.to_string());
+        run_shell_capture_tagged(
+            sender.clone(),
+            project_path.clone(),
+            service.service.clone(),
+            redis_cli_command(service, &args),
+            request_id,
+        );
+    }
+
+    pub fn handle_tagged_result(&mut self, request_id: u64, output: &Result<String, String>) -> bool {
+        let Some(command) = self.pending_requests.remove(&request_id) else {
+            return false;
+        };
+
+        self.result = Some(match output {
+            Ok(text) => match redis_error(text) {
+                Some(error) => RedisResult { command, entries: Vec::new(), raw: text.clone(), error: Some(error) },
+                None => RedisResult {
+                    entries: parse_redis_output(&command, text),
+                    command,
+                    raw: text.clone(),
+                    error: None,
+                },
+            },
+            Err(e) => RedisResult { command, entries: Vec::new(), raw: String::new(), error: Some(e.clone()) },
+        });
+        true
+    }
+}
+
+// `redis-cli` con cada argumento entrecomillado; la contraseña solo si `lando info` la expone
+fn redis_cli_command(service: &LandoService, args: &[String]) -> String {
+    let mut command = String::from("redis-cli");
+    if let Some(password) = service.creds.as_ref()
+        .and_then(|c| c.password.as_ref())
+        .map(SecretString::expose)
+        .filter(|p| !p.is_empty())
+    {
+        command.push_str(&format!(" --no-auth-warning -a {}", shell_quote(password)));
+    }
+    for arg in args {
+        command.push(' ');
+        command.push_str(&shell_quote(arg));
+    }
+    command
+}
+
+// Separa el comando en argumentos respetando comillas dobles y simples,
+// como hace redis-cli en modo interactivo (`SET clave "hola mundo"`)
+fn split_redis_args(input: &str) -> Result<Vec<String>, String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut quote: Option<char> = None;
+    let mut in_arg = false;
+    let mut chars = input.chars();
+
+    while let Some(c) = chars.next() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some('"') if c == '\\' => {
+                if let Some(next) = chars.next() {
+                    current.push(next);
+                }
+            }
+            Some(_) => current.push(c),
+            None if c == '"' || c == '\'' => {
+                quote = Some(c);
+                in_arg = true;
+            }
+            None if c.is_whitespace() => {
+                if in_arg {
+                    args.push(std::mem::take(&mut current));
+                    in_arg = false;
+                }
+            }
+            None => {
+                current.push(c);
+                in_arg = true;
+            }
+        }
+    }
+
+    if quote.is_some() {
+        return Err("Comillas sin cerrar en el comando".to_string());
+    }
+    if in_arg {
+        args.push(current);
+    }
+    Ok(args)
+}
+
+fn redis_error(text: &str) -> Option<String> {
+    let first = text.trim_start().lines().next()?;
+    REDIS_ERROR_PREFIXES.iter()
+        .any(|prefix| first.starts_with(prefix))
+        .then(|| first.trim_start_matches("(error)").trim().to_string())
+}
+
+// Convierte la salida en filas clave/valor según el comando ejecutado
+fn parse_redis_output(command: &str, text: &str) -> Vec<(String, String)> {
+    let mut words = command.split_whitespace();
+    let name = words.next().unwrap_or_default().to_uppercase();
+    let lines: Vec<&str> = text.lines().collect();
+
+    match name.as_str() {
+        // Secciones `# Server` y líneas `clave:valor`
+        "INFO" => lines.iter()
+            .filter(|line| !line.trim().is_empty() && !line.starts_with('#'))
+            .filter_map(|line| line.trim().split_once(':'))
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect(),
+        // Respuestas que alternan campo y valor
+        "HGETALL" | "CONFIG" | "XINFO" => lines.chunks(2)
+            .map(|pair| (pair[0].to_string(), pair.get(1).unwrap_or(&"").to_string()))
+            .collect(),
+        "KEYS" | "SMEMBERS" | "LRANGE" | "SCAN" | "SSCAN" | "ZRANGE" | "HKEYS" | "HVALS" | "MGET" => lines.iter()
+            .enumerate()
+            .map(|(i, line)| (format!("{})", i + 1), line.to_string()))
+            .collect(),
+        // Comandos sobre una clave: la clave junto a su valor
+        _ => {
+            let key = words.next().map(String::from).unwrap_or_else(|| name.clone());
+            vec![(key, text.trim_end().to_string())]
+        }
+    }
+}
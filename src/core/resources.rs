@@ -0,0 +1,550 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::Sender;
+
+use serde_yaml::{Mapping, Value};
+
+use crate::core::appserver::parse_docker_stats;
+use crate::core::commands::{compose_project_name, next_request_id, run_container_stats_tagged, run_lando_args};
+use crate::core::recipes::{detect_app_name, mapping_entry};
+use crate::models::commands::LandoCommandOutcome;
+use crate::models::resources::{
+    DiffLine, ResourceEditor, ResourceLimits, ResourcePreview, ResourceUsage, CPUS_KEY, MEM_LIMIT_KEY, SHM_SIZE_KEY,
+};
+use crate::ui::confirm::ConfirmDialog;
+
+// Docker no arranca un contenedor con menos memoria que esto
+const MIN_MEM_LIMIT: u64 = 6 * 1024 * 1024;
+
+// Líneas sin cambios que se muestran alrededor de cada cambio del diff
+pub const DIFF_CONTEXT: usize = 3;
+
+// `512m`, `2g`, `1.5G`, `1048576`: la misma sintaxis que acepta docker.
+// Devuelve los bytes y el valor normalizado (`2G` -> `2g`).
+pub fn parse_size(input: &str) -> Result<(u64, String), String> {
+    let value = input.trim().to_lowercase();
+    if value.is_empty() {
+        return Err("vacío".to_string());
+    }
+    let number_end = value.find(|c: char| !c.is_ascii_digit() && c != '.').unwrap_or(value.len());
+    let (number, unit) = value.split_at(number_end);
+    let multiplier: u64 = match unit {
+        "" | "b" => 1,
+        "k" | "kb" => 1 << 10,
+        "m" | "mb" => 1 << 20,
+        "g" | "gb" => 1 << 30,
+        _ => return Err(format!("unidad '{}' no válida (usa b, k, m o g)", unit)),
+    };
+    let amount: f64 = number.parse().map_err(|_| format!("'{}' no es un número", number))?;
+    let bytes = (amount * multiplier as f64).round();
+    if !bytes.is_finite() || bytes < 1.0 || bytes > u64::MAX as f64 {
+        return Err("debe ser mayor que cero".to_string());
+    }
+    let unit = unit.trim_end_matches('b');
+    Ok((bytes as u64, format!("{}{}", number, unit)))
+}
+
+// Vacío = quitar el límite
+pub fn validate_mem_limit(input: &str) -> Result<Option<String>, String> {
+    if input.trim().is_empty() {
+        return Ok(None);
+    }
+    let (bytes, normalized) = parse_size(input)?;
+    if bytes < MIN_MEM_LIMIT {
+        return Err("docker exige al menos 6m".to_string());
+    }
+    Ok(Some(normalized))
+}
+
+pub fn validate_shm_size(input: &str) -> Result<Option<String>, String> {
+    if input.trim().is_empty() {
+        return Ok(None);
+    }
+    parse_size(input).map(|(_, normalized)| Some(normalized))
+}
+
+// Número de CPUs, admite fracciones (`0.5`)
+pub fn validate_cpus(input: &str) -> Result<Option<String>, String> {
+    let value = input.trim();
+    if value.is_empty() {
+        return Ok(None);
+    }
+    let cpus: f64 = value.parse().map_err(|_| format!("'{}' no es un número", value))?;
+    if !cpus.is_finite() || cpus <= 0.0 {
+        return Err("debe ser mayor que cero".to_string());
+    }
+    if cpus > 1024.0 {
+        return Err("demasiadas CPUs".to_string());
+    }
+    // Como lo relee serde_yaml: `1.50` -> `1.5`
+    Ok(Some(cpus.to_string()))
+}
+
+fn scalar_to_string(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+pub fn limits_from_config(config: &Value, service: &str) -> ResourceLimits {
+    let overrides = config.get("services")
+        .and_then(|services| services.get(service))
+        .and_then(|service| service.get("overrides"));
+    let read = |key: &str| overrides.and_then(|o| o.get(key)).and_then(scalar_to_string);
+    ResourceLimits {
+        mem_limit: read(MEM_LIMIT_KEY),
+        cpus: read(CPUS_KEY),
+        shm_size: read(SHM_SIZE_KEY),
+    }
+}
+
+pub fn read_resource_limits(project_path: &Path, service: &str) -> ResourceLimits {
+    fs::read_to_string(project_path.join(".lando.yml"))
+        .ok()
+        .and_then(|content| serde_yaml::from_str::<Value>(&content).ok())
+        .map(|config| limits_from_config(&config, service))
+        .unwrap_or_default()
+}
+
+fn limit_entries(limits: &ResourceLimits) -> [(&'static str, Option<&str>); 3] {
+    [
+        (MEM_LIMIT_KEY, limits.mem_limit.as_deref()),
+        (CPUS_KEY, limits.cpus.as_deref()),
+        (SHM_SIZE_KEY, limits.shm_size.as_deref()),
+    ]
+}
+
+// Edición línea a línea del YAML en estilo bloque: solo se tocan las líneas
+// de las claves que cambian, así que comentarios y formato del resto quedan igual.
+
+fn indent_of(line: &str) -> usize {
+    line.len() - line.trim_start_matches(' ').len()
+}
+
+fn is_skippable(line: &str) -> bool {
+    let trimmed = line.trim();
+    trimmed.is_empty() || trimmed.starts_with('#')
+}
+
+// Clave y resto de la línea tras los dos puntos (`key: valor # comentario`)
+fn split_key(line: &str) -> Option<(String, &str)> {
+    let trimmed = line.trim_start();
+    if trimmed.starts_with('-') || trimmed.starts_with('#') {
+        return None;
+    }
+    if let Some(quote) = trimmed.chars().next().filter(|c| *c == '"' || *c == '\'') {
+        let close = trimmed[1..].find(quote)? + 1;
+        let rest = trimmed[close + 1..].strip_prefix(':')?;
+        return Some((trimmed[1..close].to_string(), rest));
+    }
+    let colon = trimmed.char_indices()
+        .find(|(i, c)| *c == ':' && trimmed[i + 1..].chars().next().is_none_or(char::is_whitespace))
+        .map(|(i, _)| i)?;
+    Some((trimmed[..colon].trim().to_string(), &trimmed[colon + 1..]))
+}
+
+// Valor en la misma línea sin el comentario final; vacío si abre un bloque
+fn inline_value(rest: &str) -> &str {
+    let value = rest.trim();
+    if value.starts_with('#') {
+        return "";
+    }
+    match value.find(" #") {
+        Some(comment) => value[..comment].trim_end(),
+        None => value,
+    }
+}
+
+// Primera línea tras el bloque que abre `start` (sin las líneas en blanco
+// o de comentario del final, que pertenecen a lo que venga después)
+fn block_end(lines: &[String], start: usize) -> usize {
+    let indent = indent_of(&lines[start]);
+    let mut end = start + 1;
+    for (index, line) in lines.iter().enumerate().skip(start + 1) {
+        if is_skippable(line) {
+            continue;
+        }
+        if indent_of(line) <= indent {
+            break;
+        }
+        end = index + 1;
+    }
+    end
+}
+
+// Rango e indentación de los hijos de `parent` (None: el documento)
+fn children(lines: &[String], parent: Option<usize>) -> (usize, usize, Option<usize>) {
+    let (start, end) = match parent {
+        Some(p) => (p + 1, block_end(lines, p)),
+        None => (0, lines.len()),
+    };
+    let child_indent = lines[start..end].iter().find(|l| !is_skippable(l)).map(|l| indent_of(l));
+    (start, end, child_indent)
+}
+
+fn find_child(lines: &[String], parent: Option<usize>, key: &str) -> Option<usize> {
+    let (start, end, child_indent) = children(lines, parent);
+    let child_indent = child_indent?;
+    (start..end).find(|&i| {
+        !is_skippable(&lines[i])
+            && indent_of(&lines[i]) == child_indent
+            && split_key(&lines[i]).is_some_and(|(k, _)| k == key)
+    })
+}
+
+// Línea del bloque `key` bajo `parent`, creándola al final si falta.
+// None si ya existe con un valor en línea (`overrides: {}`): no es estilo bloque.
+fn ensure_child(lines: &mut Vec<String>, parent: Option<usize>, key: &str, unit: usize) -> Option<usize> {
+    if let Some(index) = find_child(lines, parent, key) {
+        let (_, rest) = split_key(&lines[index])?;
+        return inline_value(rest).is_empty().then_some(index);
+    }
+    let (_, end, child_indent) = children(lines, parent);
+    let indent = child_indent.unwrap_or_else(|| parent.map_or(0, |p| indent_of(&lines[p]) + unit));
+    lines.insert(end, format!("{}{}:", " ".repeat(indent), key));
+    Some(end)
+}
+
+// Indentación que usa el archivo (la de la primera línea sangrada)
+fn indent_unit(lines: &[String]) -> usize {
+    lines.iter()
+        .filter(|l| !is_skippable(l))
+        .map(|l| indent_of(l))
+        .find(|&indent| indent > 0)
+        .unwrap_or(2)
+}
+
+fn set_overrides_in_lines(content: &str, service: &str, limits: &ResourceLimits) -> Option<String> {
+    let mut lines: Vec<String> = content.lines().map(String::from).collect();
+    let unit = indent_unit(&lines);
+    let entries = limit_entries(limits);
+
+    let overrides = if entries.iter().any(|(_, value)| value.is_some()) {
+        let services = ensure_child(&mut lines, None, "services", unit)?;
+        let section = ensure_child(&mut lines, Some(services), service, unit)?;
+        ensure_child(&mut lines, Some(section), "overrides", unit)?
+    } else {
+        // Solo se quitan límites: si no hay bloque no hay nada que hacer
+        let found = find_child(&lines, None, "services")
+            .and_then(|services| find_child(&lines, Some(services), service))
+            .and_then(|section| find_child(&lines, Some(section), "overrides"));
+        match found {
+            Some(index) => index,
+            None => return Some(content.to_string()),
+        }
+    };
+
+    for (key, value) in entries {
+        match (find_child(&lines, Some(overrides), key), value) {
+            (Some(index), Some(value)) => {
+                let (_, rest) = split_key(&lines[index])?;
+                let comment = rest.find(" #").map(|c| rest[c..].to_string()).unwrap_or_default();
+                let end = block_end(&lines, index);
+                lines.drain(index + 1..end);
+                lines[index] = format!("{}{}: {}{}", " ".repeat(indent_of(&lines[index])), key, value, comment);
+            }
+            (Some(index), None) => {
+                let end = block_end(&lines, index);
+                lines.drain(index..end);
+            }
+            (None, Some(value)) => {
+                let (_, end, child_indent) = children(&lines, Some(overrides));
+                let indent = child_indent.unwrap_or(indent_of(&lines[overrides]) + unit);
+                lines.insert(end, format!("{}{}: {}", " ".repeat(indent), key, value));
+            }
+            (None, None) => {}
+        }
+    }
+
+    // Un `overrides:` vacío valdría null; se quita
+    if block_end(&lines, overrides) == overrides + 1 {
+        lines.remove(overrides);
+    }
+
+    let mut output = lines.join("\n");
+    if content.ends_with('\n') || content.is_empty() {
+        output.push('\n');
+    }
+    Some(output)
+}
+
+// Respaldo para YAML en estilo flujo u otras formas que la edición por líneas
+// no entiende: se regenera el archivo entero y se pierden los comentarios
+fn set_overrides_in_value(content: &str, service: &str, limits: &ResourceLimits) -> Result<String, String> {
+    let mut config: Value = serde_yaml::from_str(content)
+        .map_err(|e| format!("El .lando.yml no es YAML válido: {}", e))?;
+    if config.is_null() {
+        config = Value::Mapping(Mapping::new());
+    }
+    let root = config.as_mapping_mut()
+        .ok_or_else(|| "El .lando.yml no tiene claves de primer nivel".to_string())?;
+    let section = mapping_entry(mapping_entry(root, "services"), service);
+    let overrides = mapping_entry(section, "overrides");
+    for (key, value) in limit_entries(limits) {
+        match value {
+            Some(value) => {
+                let value = serde_yaml::from_str::<Value>(value).unwrap_or_else(|_| Value::from(value));
+                overrides.insert(Value::from(key), value);
+            }
+            None => {
+                overrides.remove(key);
+            }
+        }
+    }
+    if overrides.is_empty() {
+        section.remove("overrides");
+    }
+    serde_yaml::to_string(&config).map_err(|e| format!("No se pudo generar el YAML: {}", e))
+}
+
+// Nuevo contenido del `.lando.yml` y si se han conservado los comentarios.
+// El resultado de la edición por líneas se relee para comprobar que dice lo mismo.
+pub fn apply_limits_to_yaml(content: &str, service: &str, limits: &ResourceLimits) -> Result<(String, bool), String> {
+    if let Some(output) = set_overrides_in_lines(content, service, limits) {
+        let round_trip = serde_yaml::from_str::<Value>(&output).ok()
+            .map(|config| limits_from_config(&config, service));
+        if round_trip.as_ref() == Some(limits) {
+            return Ok((output, true));
+        }
+        log::warn!("La edición por líneas del .lando.yml no cuadra; se regenera el YAML");
+    }
+    set_overrides_in_value(content, service, limits).map(|output| (output, false))
+}
+
+// Diff por líneas (subsecuencia común más larga); los .lando.yml son pequeños
+pub fn line_diff(old: &str, new: &str) -> Vec<DiffLine> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut diff = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            diff.push(DiffLine::Same(old[i].to_string()));
+            i += 1;
+            j += 1;
+        } else if j < new.len() && (i == old.len() || lcs[i][j + 1] >= lcs[i + 1][j]) {
+            diff.push(DiffLine::Added(new[j].to_string()));
+            j += 1;
+        } else {
+            diff.push(DiffLine::Removed(old[i].to_string()));
+            i += 1;
+        }
+    }
+    diff
+}
+
+// Índices de las líneas del diff que se muestran: los cambios y su contexto
+pub fn visible_diff_lines(diff: &[DiffLine], context: usize) -> Vec<usize> {
+    let changed: Vec<usize> = diff.iter()
+        .enumerate()
+        .filter(|(_, line)| !matches!(line, DiffLine::Same(_)))
+        .map(|(index, _)| index)
+        .collect();
+    (0..diff.len())
+        .filter(|&index| changed.iter().any(|&c| index.abs_diff(c) <= context))
+        .collect()
+}
+
+impl ResourceEditor {
+    // Los campos se rellenan con lo que haya en el `.lando.yml` del proyecto
+    pub fn load(&mut self, project_path: &Path, service: &str) {
+        self.saved = read_resource_limits(project_path, service);
+        self.mem_limit = self.saved.mem_limit.clone().unwrap_or_default();
+        self.cpus = self.saved.cpus.clone().unwrap_or_default();
+        self.shm_size = self.saved.shm_size.clone().unwrap_or_default();
+        self.project = Some(project_path.to_path_buf());
+        self.preview = None;
+    }
+
+    pub fn ensure_loaded(&mut self, project_path: &PathBuf, service: &str) {
+        if self.project.as_ref() != Some(project_path) {
+            self.load(project_path, service);
+            self.usage = ResourceUsage::Unknown;
+        }
+    }
+
+    pub fn request_usage(&mut self, project_path: &Path, service: &str, sender: &Sender<LandoCommandOutcome>) {
+        let app_name = detect_app_name(project_path)
+            .unwrap_or_else(|| project_path.file_name().unwrap_or_default().to_string_lossy().to_string());
+        let request_id = next_request_id();
+        self.usage = ResourceUsage::Loading(request_id);
+        run_container_stats_tagged(sender.clone(), compose_project_name(&app_name), service.to_string(), request_id);
+    }
+
+    pub fn handle_tagged_result(&mut self, request_id: u64, output: &Result<String, String>) -> bool {
+        if self.usage != ResourceUsage::Loading(request_id) {
+            return false;
+        }
+        self.usage = match output {
+            Ok(text) if text.trim().is_empty() => ResourceUsage::Stopped,
+            Ok(text) => match parse_docker_stats(text) {
+                Some((cpu, memory, memory_percent)) => ResourceUsage::Measured { cpu, memory, memory_percent },
+                None => ResourceUsage::Failed("Salida de docker stats no reconocida".to_string()),
+            },
+            Err(err) => ResourceUsage::Failed(err.clone()),
+        };
+        true
+    }
+
+    // Límites de los campos del formulario, o el primer error de validación
+    pub fn edited_limits(&self) -> Result<ResourceLimits, String> {
+        Ok(ResourceLimits {
+            mem_limit: validate_mem_limit(&self.mem_limit).map_err(|e| format!("Memoria: {}", e))?,
+            cpus: validate_cpus(&self.cpus).map_err(|e| format!("CPUs: {}", e))?,
+            shm_size: validate_shm_size(&self.shm_size).map_err(|e| format!("shm_size: {}", e))?,
+        })
+    }
+
+    pub fn prepare_preview(&mut self, project_path: &Path, service: &str) {
+        let result = self.edited_limits().and_then(|limits| {
+            let file = project_path.join(".lando.yml");
+            let content = fs::read_to_string(&file)
+                .map_err(|e| format!("No se pudo leer {}: {}", file.display(), e))?;
+            let (new_content, comments_preserved) = apply_limits_to_yaml(&content, service, &limits)?;
+            Ok(ResourcePreview { diff: line_diff(&content, &new_content), new_content, comments_preserved })
+        });
+        match result {
+            Ok(preview) => {
+                self.message = None;
+                self.preview = Some(preview);
+            }
+            Err(err) => self.message = Some(Err(err)),
+        }
+    }
+
+    // Guarda el contenido ya revisado; si no se pudieron conservar los
+    // comentarios se deja antes una copia en `.lando.yml.bak`
+    pub fn save_preview(&mut self, project_path: &Path, service: &str) {
+        let Some(preview) = self.preview.take() else {
+            return;
+        };
+        let file = project_path.join(".lando.yml");
+        let result = if preview.comments_preserved {
+            Ok(())
+        } else {
+            fs::copy(&file, project_path.join(".lando.yml.bak"))
+                .map(|_| ())
+                .map_err(|e| format!("No se pudo crear la copia .lando.yml.bak: {}", e))
+        };
+        let result = result.and_then(|_| {
+            fs::write(&file, &preview.new_content).map_err(|e| format!("No se pudo escribir {}: {}", file.display(), e))
+        });
+
+        match result {
+            Ok(()) => {
+                self.load(project_path, service);
+                self.message = Some(Ok("Límites guardados en .lando.yml.".to_string()));
+                self.pending_rebuild = Some(ConfirmDialog::new(
+                    "Reconstruir app",
+                    "Los límites de recursos no se aplican hasta reconstruir la app.",
+                    "lando rebuild -y",
+                    service,
+                ));
+            }
+            Err(err) => self.message = Some(Err(err)),
+        }
+    }
+
+    pub fn run_rebuild(&mut self, project_path: &Path, sender: &Sender<LandoCommandOutcome>) {
+        self.message = Some(Ok("Reconstruyendo... los nuevos límites se verán al terminar.".to_string()));
+        self.usage = ResourceUsage::Unknown;
+        run_lando_args(sender.clone(), vec!["rebuild".to_string(), "-y".to_string()], project_path.to_path_buf());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limits(mem_limit: Option<&str>, cpus: Option<&str>, shm_size: Option<&str>) -> ResourceLimits {
+        ResourceLimits {
+            mem_limit: mem_limit.map(String::from),
+            cpus: cpus.map(String::from),
+            shm_size: shm_size.map(String::from),
+        }
+    }
+
+    #[test]
+    fn size_suffixes() {
+        assert_eq!(parse_size("1048576"), Ok((1 << 20, "1048576".to_string())));
+        assert_eq!(parse_size("512b"), Ok((512, "512".to_string())));
+        assert_eq!(parse_size("64k"), Ok((64 << 10, "64k".to_string())));
+        assert_eq!(parse_size(" 512M "), Ok((512 << 20, "512m".to_string())));
+        assert_eq!(parse_size("2GB"), Ok((2 << 30, "2g".to_string())));
+        assert_eq!(parse_size("1.5g"), Ok((3 << 29, "1.5g".to_string())));
+        assert_eq!(parse_size("2t"), Err("unidad 't' no válida (usa b, k, m o g)".to_string()));
+        assert_eq!(parse_size("2 g"), Err("unidad ' g' no válida (usa b, k, m o g)".to_string()));
+        assert_eq!(parse_size("1.2.3m"), Err("'1.2.3' no es un número".to_string()));
+        assert_eq!(parse_size("m"), Err("'' no es un número".to_string()));
+        assert_eq!(parse_size("0m"), Err("debe ser mayor que cero".to_string()));
+        assert_eq!(parse_size(""), Err("vacío".to_string()));
+    }
+
+    #[test]
+    fn field_validation() {
+        assert_eq!(validate_mem_limit(""), Ok(None));
+        assert_eq!(validate_mem_limit("6m"), Ok(Some("6m".to_string())));
+        assert_eq!(validate_mem_limit("4m"), Err("docker exige al menos 6m".to_string()));
+        assert_eq!(validate_shm_size("64k"), Ok(Some("64k".to_string())));
+        assert_eq!(validate_cpus(" 1.50 "), Ok(Some("1.5".to_string())));
+        assert_eq!(validate_cpus("0"), Err("debe ser mayor que cero".to_string()));
+        assert_eq!(validate_cpus("2000"), Err("demasiadas CPUs".to_string()));
+        assert_eq!(validate_cpus("dos"), Err("'dos' no es un número".to_string()));
+
+        let editor = ResourceEditor { mem_limit: "1G".to_string(), cpus: "x".to_string(), ..Default::default() };
+        assert_eq!(editor.edited_limits(), Err("CPUs: 'x' no es un número".to_string()));
+    }
+
+    #[test]
+    fn block_yaml_keeps_comments() {
+        let content = "name: blog\nrecipe: lamp\n# Servicios\nservices:\n    database:\n        type: mysql:8.0 # de la receta\n        overrides:\n            cpus: 1 # antes 2\n    cache:\n        type: redis\n";
+        let (output, preserved) = apply_limits_to_yaml(content, "database", &limits(Some("1g"), Some("2"), None)).unwrap();
+        assert!(preserved);
+        assert_eq!(output, "name: blog\nrecipe: lamp\n# Servicios\nservices:\n    database:\n        type: mysql:8.0 # de la receta\n        overrides:\n            cpus: 2 # antes 2\n            mem_limit: 1g\n    cache:\n        type: redis\n");
+
+        // Quitar todos los límites borra también el `overrides:` vacío
+        let (output, preserved) = apply_limits_to_yaml(&output, "database", &ResourceLimits::default()).unwrap();
+        assert!(preserved);
+        assert_eq!(output, "name: blog\nrecipe: lamp\n# Servicios\nservices:\n    database:\n        type: mysql:8.0 # de la receta\n    cache:\n        type: redis\n");
+    }
+
+    #[test]
+    fn missing_sections_are_created() {
+        let (output, preserved) = apply_limits_to_yaml("name: blog\n", "appserver", &limits(None, None, Some("256m"))).unwrap();
+        assert!(preserved);
+        assert_eq!(output, "name: blog\nservices:\n  appserver:\n    overrides:\n      shm_size: 256m\n");
+        let (output, _) = apply_limits_to_yaml("name: blog\n", "appserver", &ResourceLimits::default()).unwrap();
+        assert_eq!(output, "name: blog\n");
+    }
+
+    #[test]
+    fn flow_yaml_is_regenerated() {
+        let content = "services: { appserver: { type: php, overrides: {} } }\n";
+        let (output, preserved) = apply_limits_to_yaml(content, "appserver", &limits(Some("512m"), None, None)).unwrap();
+        assert!(!preserved);
+        let config: Value = serde_yaml::from_str(&output).unwrap();
+        assert_eq!(limits_from_config(&config, "appserver"), limits(Some("512m"), None, None));
+        assert!(apply_limits_to_yaml("services: [", "appserver", &limits(Some("512m"), None, None)).is_err());
+    }
+
+    #[test]
+    fn diff_shows_changes_with_context() {
+        let diff = line_diff("a\nb\nc\nd\ne\nf\n", "a\nb\nC\nd\ne\nf\n");
+        assert_eq!(diff[2], DiffLine::Added("C".to_string()));
+        assert_eq!(diff[3], DiffLine::Removed("c".to_string()));
+        assert_eq!(visible_diff_lines(&diff, 1), vec![1, 2, 3, 4]);
+    }
+}
@@ -0,0 +1,790 @@
+// Conversión de la salida en texto de los clientes de BD a filas y columnas.
+
+// Resultado de una consulta convertido a filas y columnas
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ParsedResultSet {
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+}
+
+impl ParsedResultSet {
+    pub fn column_index(&self, name: &str) -> Option<usize> {
+        self.columns.iter().position(|c| c.eq_ignore_ascii_case(name))
+    }
+
+    pub fn value(&self, row: usize, column: &str) -> Option<&str> {
+        let index = self.column_index(column)?;
+        self.rows.get(row)?.get(index).map(|v| v.as_str())
+    }
+
+    // Copia con solo las filas indicadas, en su orden original
+    pub fn with_rows(&self, indices: &std::collections::BTreeSet<usize>) -> ParsedResultSet {
+        ParsedResultSet {
+            columns: self.columns.clone(),
+            rows: indices.iter().filter_map(|i| self.rows.get(*i).cloned()).collect(),
+        }
+    }
+}
+
+// Formatos del menú de copiar resultados
+#[derive(Debug, Clone, Copy, PartialEq, Default, serde::Serialize, serde::Deserialize)]
+pub enum CopyFormat {
+    #[default]
+    Text,
+    Markdown,
+    Json,
+    Csv,
+    Insert,
+}
+
+impl CopyFormat {
+    pub const ALL: [CopyFormat; 5] = [CopyFormat::Text, CopyFormat::Markdown, CopyFormat::Json, CopyFormat::Csv, CopyFormat::Insert];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            CopyFormat::Text => "Texto",
+            CopyFormat::Markdown => "Tabla Markdown",
+            CopyFormat::Json => "JSON",
+            CopyFormat::Csv => "CSV",
+            CopyFormat::Insert => "Sentencias INSERT",
+        }
+    }
+}
+
+// Acepta los tres formatos habituales: tabla ASCII de mysql (`+---+`),
+// tabla alineada de psql (`---+---` y pie `(N rows)`) y el modo batch de
+// mysql separado por tabuladores.
+pub fn parse_result_set(text: &str) -> Option<ParsedResultSet> {
+    let lines: Vec<&str> = text.lines().filter(|l| !l.trim().is_empty()).collect();
+    if lines.is_empty() {
+        return None;
+    }
+
+    if lines[0].trim_start().starts_with('+') {
+        let rows: Vec<Vec<String>> = lines.iter()
+            .filter(|l| l.trim_start().starts_with('|'))
+            .map(|l| split_pipe_row(l))
+            .collect();
+        let (columns, rows) = rows.split_first()?;
+        return Some(ParsedResultSet { columns: columns.clone(), rows: rows.to_vec() });
+    }
+
+    // Sin bordes exteriores: una última celda vacía deja la línea acabada en `|`
+    if lines.len() >= 2 && is_psql_separator(lines[1]) {
+        let split = |line: &str| line.split('|').map(|c| c.trim().to_string()).collect::<Vec<_>>();
+        let columns = split(lines[0]);
+        let rows = lines[2..].iter()
+            .filter(|l| !is_psql_footer(l))
+            .map(|l| split(l))
+            .collect();
+        return Some(ParsedResultSet { columns, rows });
+    }
+
+    let columns = lines[0].split('\t').map(|c| c.trim().to_string()).collect();
+    let rows = lines[1..].iter()
+        .map(|l| l.split('\t').map(unescape_batch_value).collect())
+        .collect();
+    Some(ParsedResultSet { columns, rows })
+}
+
+// El modo batch de mysql escapa los saltos de línea, tabuladores y barras
+// invertidas de los valores; se deshace para mostrar (y editar) el valor real.
+pub fn unescape_batch_value(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => out.push('\n'),
+            Some('t') => out.push('\t'),
+            Some('0') => out.push('\0'),
+            Some('\\') => out.push('\\'),
+            Some(other) => {
+                out.push('\\');
+                out.push(other);
+            }
+            None => out.push('\\'),
+        }
+    }
+    out
+}
+
+fn split_pipe_row(line: &str) -> Vec<String> {
+    let line = line.trim();
+    let line = line.strip_prefix('|').unwrap_or(line);
+    let line = line.strip_suffix('|').unwrap_or(line);
+    line.split('|').map(|c| c.trim().to_string()).collect()
+}
+
+fn is_psql_separator(line: &str) -> bool {
+    let line = line.trim();
+    !line.is_empty() && line.contains('-') && line.chars().all(|c| c == '-' || c == '+')
+}
+
+fn is_psql_footer(line: &str) -> bool {
+    let line = line.trim();
+    line.starts_with('(') && line.ends_with(')') && (line.contains("row") || line.contains("fila"))
+}
+
+// Tipo inferido de una columna a partir de sus valores en texto
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ColumnKind {
+    Numeric,
+    Date,
+    Text,
+}
+
+// Celdas que se consideran vacías al inferir tipos o construir gráficos
+pub fn is_null_cell(value: &str) -> bool {
+    let value = value.trim();
+    value.is_empty() || value.eq_ignore_ascii_case("null")
+}
+
+// Número tal como lo imprimen los clientes: acepta signo, decimales con punto,
+// separadores de miles (`1,234,567.89`, `1 234`, `1_000`) y notación científica.
+pub fn parse_numeric_cell(value: &str) -> Option<f64> {
+    let value = value.trim();
+    if is_null_cell(value) {
+        return None;
+    }
+
+    if let Ok(n) = value.parse::<f64>() {
+        return n.is_finite().then_some(n);
+    }
+
+    let (sign, digits) = match value.strip_prefix('-') {
+        Some(rest) => (-1.0, rest),
+        None => (1.0, value.strip_prefix('+').unwrap_or(value)),
+    };
+    let (integer, fraction) = match digits.split_once('.') {
+        Some((i, f)) => (i, Some(f)),
+        None => (digits, None),
+    };
+
+    // Los separadores solo son válidos entre grupos de tres dígitos
+    let separator = integer.chars().find(|c| matches!(c, ',' | ' ' | '_' | '\u{a0}'))?;
+    let groups: Vec<&str> = integer.split(separator).collect();
+    let well_formed = !groups[0].is_empty()
+        && groups[0].len() <= 3
+        && groups.iter().all(|g| !g.is_empty() && g.chars().all(|c| c.is_ascii_digit()))
+        && groups[1..].iter().all(|g| g.len() == 3);
+    if !well_formed || fraction.is_some_and(|f| f.is_empty() || !f.chars().all(|c| c.is_ascii_digit())) {
+        return None;
+    }
+
+    let plain = match fraction {
+        Some(f) => format!("{}.{}", groups.concat(), f),
+        None => groups.concat(),
+    };
+    plain.parse::<f64>().ok().map(|n| sign * n)
+}
+
+// `YYYY-MM-DD` con hora opcional (`YYYY-MM-DD HH:MM:SS` o ISO con `T`).
+// Devuelve días desde 1970-01-01, con la hora como fracción del día.
+pub fn parse_date_cell(value: &str) -> Option<f64> {
+    let value = value.trim();
+    let (date, time) = match value.split_once([' ', 'T']) {
+        Some((d, t)) => (d, Some(t)),
+        None => (value, None),
+    };
+
+    let mut parts = date.split('-');
+    let year: i64 = parts.next().filter(|p| p.len() == 4)?.parse().ok()?;
+    let month: u32 = parts.next().filter(|p| p.len() == 2)?.parse().ok()?;
+    let day: u32 = parts.next().filter(|p| p.len() == 2)?.parse().ok()?;
+    if parts.next().is_some() || !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+
+    let mut days = days_from_civil(year, month, day) as f64;
+    if let Some(time) = time {
+        let time = time.trim_end_matches('Z');
+        let mut hms = time.split(':');
+        let h: f64 = hms.next()?.parse().ok()?;
+        let m: f64 = hms.next().unwrap_or("0").parse().ok()?;
+        let s: f64 = hms.next().unwrap_or("0").split(['+', '-']).next()?.parse().ok()?;
+        days += (h * 3600.0 + m * 60.0 + s) / 86400.0;
+    }
+    Some(days)
+}
+
+// Inversa de `parse_date_cell`, para las etiquetas del eje X
+pub fn format_day_number(days: f64) -> String {
+    let (y, m, d) = civil_from_days(days.floor() as i64);
+    format!("{:04}-{:02}-{:02}", y, m, d)
+}
+
+// Algoritmos de Howard Hinnant para el calendario gregoriano proléptico
+fn days_from_civil(y: i64, m: u32, d: u32) -> i64 {
+    let y = if m <= 2 { y - 1 } else { y };
+    let era = (if y >= 0 { y } else { y - 399 }) / 400;
+    let yoe = y - era * 400;
+    let m = m as i64;
+    let doy = (153 * (if m > 2 { m - 3 } else { m + 9 }) + 2) / 5 + d as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146097 + doe - 719468
+}
+
+fn civil_from_days(z: i64) -> (i64, u32, u32) {
+    let z = z + 719468;
+    let era = (if z >= 0 { z } else { z - 146096 }) / 146097;
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let m = (if mp < 10 { mp + 3 } else { mp - 9 }) as u32;
+    let y = yoe + era * 400 + if m <= 2 { 1 } else { 0 };
+    (y, m, d)
+}
+
+// Un tipo gana si lo cumplen todas las celdas no vacías (y hay al menos una)
+pub fn infer_column_kind<'a>(values: impl Iterator<Item = &'a str>) -> ColumnKind {
+    let mut numeric = true;
+    let mut date = true;
+    let mut seen = false;
+
+    for value in values.filter(|v| !is_null_cell(v)) {
+        seen = true;
+        numeric &= parse_numeric_cell(value).is_some();
+        date &= parse_date_cell(value).is_some();
+        if !numeric && !date {
+            break;
+        }
+    }
+
+    match (seen, numeric, date) {
+        (false, _, _) => ColumnKind::Text,
+        (true, _, true) => ColumnKind::Date,
+        (true, true, false) => ColumnKind::Numeric,
+        _ => ColumnKind::Text,
+    }
+}
+
+pub const CHART_MAX_POINTS: usize = 5000;
+
+// Puntos listos para dibujar: X categórica (barras) o numérica/fecha (líneas)
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChartData {
+    pub x_kind: ColumnKind,
+    pub points: Vec<(f64, f64)>,
+    pub labels: Vec<String>, // Valor original de X de cada punto
+    pub skipped: usize,      // Filas descartadas por NULL o valores no numéricos
+    pub downsampled_from: Option<usize>,
+}
+
+pub fn build_chart_data(set: &ParsedResultSet, x_column: usize, y_column: usize) -> ChartData {
+    let x_kind = infer_column_kind(set.rows.iter().filter_map(|r| r.get(x_column)).map(String::as_str));
+
+    let mut points = Vec::new();
+    let mut labels = Vec::new();
+    let mut skipped = 0;
+
+    for row in &set.rows {
+        let (Some(x_raw), Some(y_raw)) = (row.get(x_column), row.get(y_column)) else {
+            skipped += 1;
+            continue;
+        };
+        let Some(y) = parse_numeric_cell(y_raw) else {
+            skipped += 1;
+            continue;
+        };
+        let x = match x_kind {
+            ColumnKind::Numeric => parse_numeric_cell(x_raw),
+            ColumnKind::Date => parse_date_cell(x_raw),
+            ColumnKind::Text => Some(points.len() as f64),
+        };
+        let Some(x) = x else {
+            skipped += 1;
+            continue;
+        };
+        points.push((x, y));
+        labels.push(x_raw.trim().to_string());
+    }
+
+    if x_kind != ColumnKind::Text {
+        let mut indexed: Vec<usize> = (0..points.len()).collect();
+        indexed.sort_by(|a, b| points[*a].0.total_cmp(&points[*b].0));
+        points = indexed.iter().map(|i| points[*i]).collect();
+        labels = indexed.iter().map(|i| labels[*i].clone()).collect();
+    }
+
+    // Muestreo uniforme por encima del límite (se conservan el primero y el último)
+    let mut downsampled_from = None;
+    if points.len() > CHART_MAX_POINTS {
+        let total = points.len();
+        let keep: Vec<usize> = (0..CHART_MAX_POINTS)
+            .map(|i| i * (total - 1) / (CHART_MAX_POINTS - 1))
+            .collect();
+        points = keep.iter().map(|i| points[*i]).collect();
+        labels = keep.iter().map(|i| labels[*i].clone()).collect();
+        downsampled_from = Some(total);
+    }
+
+    // Con X categórica la posición es el índice tras el muestreo
+    if x_kind == ColumnKind::Text {
+        for (i, point) in points.iter_mut().enumerate() {
+            point.0 = i as f64;
+        }
+    }
+
+    ChartData { x_kind, points, labels, skipped, downsampled_from }
+}
+
+pub fn chart_data_to_csv(data: &ChartData, x_name: &str, y_name: &str) -> String {
+    let mut csv = format!("{},{}\n", csv_field(x_name), csv_field(y_name));
+    for (label, (_, y)) in data.labels.iter().zip(&data.points) {
+        csv.push_str(&format!("{},{}\n", csv_field(label), y));
+    }
+    csv
+}
+
+// Filas como array de objetos `{columna: valor}`. El cliente no informa del
+// tipo: `NULL` pasa a null y en las columnas numéricas los valores que ya son
+// números JSON válidos se escriben como número; el resto queda como texto.
+pub fn result_set_to_json(set: &ParsedResultSet) -> String {
+    let numeric: Vec<bool> = (0..set.columns.len())
+        .map(|i| infer_column_kind(set.rows.iter().filter_map(|r| r.get(i).map(String::as_str))) == ColumnKind::Numeric)
+        .collect();
+    let rows: Vec<serde_json::Value> = set.rows.iter()
+        .map(|row| {
+            let object = set.columns.iter()
+                .enumerate()
+                .map(|(i, column)| {
+                    let value = match row.get(i) {
+                        Some(value) if value.trim().eq_ignore_ascii_case("null") => serde_json::Value::Null,
+                        Some(value) => numeric[i]
+                            .then(|| serde_json::from_str::<serde_json::Number>(value.trim()).ok())
+                            .flatten()
+                            .map(serde_json::Value::Number)
+                            .unwrap_or_else(|| serde_json::Value::String(value.clone())),
+                        None => serde_json::Value::Null,
+                    };
+                    (column.clone(), value)
+                })
+                .collect();
+            serde_json::Value::Object(object)
+        })
+        .collect();
+    serde_json::to_string_pretty(&rows).unwrap_or_default()
+}
+
+// Plan de `EXPLAIN (FORMAT JSON)` de psql: una sola columna cuyas líneas
+// terminan en `+` cuando el valor continúa. Devuelve el JSON formateado.
+pub fn pretty_json_plan(text: &str) -> Option<String> {
+    let body: String = text.lines()
+        .map(|line| line.trim_end().trim_end_matches('+').trim_end())
+        .skip_while(|line| !line.trim_start().starts_with('['))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let value = serde_json::Deserializer::from_str(&body)
+        .into_iter::<serde_json::Value>()
+        .next()?
+        .ok()?;
+    serde_json::to_string_pretty(&value).ok()
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+pub fn result_set_to_csv(set: &ParsedResultSet) -> String {
+    let line = |cells: &[String]| cells.iter().map(|c| csv_field(c)).collect::<Vec<_>>().join(",");
+    let mut csv = line(&set.columns);
+    csv.push('\n');
+    for row in &set.rows {
+        csv.push_str(&line(row));
+        csv.push('\n');
+    }
+    csv
+}
+
+// Las barras verticales se escapan y los saltos de línea pasan a `<br>` para
+// no romper la fila
+fn markdown_cell(value: &str) -> String {
+    value.replace('\\', "\\\\")
+        .replace('|', "\\|")
+        .replace("\r\n", "<br>")
+        .replace('\n', "<br>")
+}
+
+pub fn result_set_to_markdown(set: &ParsedResultSet) -> String {
+    let line = |cells: &[String]| format!("| {} |", cells.iter().map(|c| markdown_cell(c)).collect::<Vec<_>>().join(" | "));
+    let mut table = vec![
+        line(&set.columns),
+        format!("|{}|", vec!["---"; set.columns.len()].join("|")),
+    ];
+    table.extend(set.rows.iter().map(|row| line(row)));
+    table.join("\n") + "\n"
+}
+
+// Una sentencia por fila. Los identificadores y literales se escriben con las
+// funciones del dialecto; la celda `NULL` de mysql se inserta como NULL.
+pub fn result_set_to_inserts(
+    set: &ParsedResultSet,
+    table: &str,
+    quote_identifier: impl Fn(&str) -> String,
+    quote_literal: impl Fn(&str) -> String,
+) -> String {
+    let columns = set.columns.iter().map(|c| quote_identifier(c)).collect::<Vec<_>>().join(", ");
+    set.rows.iter()
+        .map(|row| {
+            let values = row.iter()
+                .map(|value| if value == "NULL" { "NULL".to_string() } else { quote_literal(value) })
+                .collect::<Vec<_>>()
+                .join(", ");
+            format!("INSERT INTO {} ({}) VALUES ({});\n", quote_identifier(table), columns, values)
+        })
+        .collect()
+}
+// Cómo se pinta una celda de la rejilla. Las columnas tienen un tipo y cada
+// celda puede ser además NULL.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CellKind {
+    Null,
+    Boolean,
+    Number,
+    Date,
+    Json,
+    Binary,
+    Text,
+}
+
+impl CellKind {
+    pub fn is_right_aligned(self) -> bool {
+        matches!(self, CellKind::Number | CellKind::Date)
+    }
+}
+
+// Filas que se miran para adivinar el tipo de una columna sin `data_type`
+pub const CELL_KIND_SAMPLE_ROWS: usize = 200;
+
+// Tipo declarado por la base de datos (`information_schema` / `\d`)
+pub fn kind_from_data_type(data_type: &str) -> Option<CellKind> {
+    let data_type = data_type.trim().to_lowercase();
+    let base = data_type.split(['(', ' ']).next().unwrap_or_default();
+    if data_type.starts_with("tinyint(1)") {
+        return Some(CellKind::Boolean);
+    }
+    match base {
+        "bool" | "boolean" | "bit" => Some(CellKind::Boolean),
+        "json" | "jsonb" => Some(CellKind::Json),
+        "blob" | "tinyblob" | "mediumblob" | "longblob" | "binary" | "varbinary" | "bytea" => Some(CellKind::Binary),
+        "date" | "datetime" | "timestamp" | "timestamptz" => Some(CellKind::Date),
+        "int" | "integer" | "tinyint" | "smallint" | "mediumint" | "bigint" | "decimal" | "numeric"
+        | "float" | "double" | "real" | "serial" | "bigserial" | "smallserial" | "int2" | "int4" | "int8"
+        | "float4" | "float8" | "money" => Some(CellKind::Number),
+        "char" | "varchar" | "text" | "tinytext" | "mediumtext" | "longtext" | "enum" | "set" | "uuid"
+        | "character" => Some(CellKind::Text),
+        _ => None,
+    }
+}
+
+pub fn parse_bool_cell(value: &str) -> Option<bool> {
+    match value.trim().to_lowercase().as_str() {
+        "t" | "true" => Some(true),
+        "f" | "false" => Some(false),
+        _ => None,
+    }
+}
+
+// Solo objetos y arrays: un número o una cadena sueltos no necesitan visor
+pub fn parse_json_cell(value: &str) -> Option<serde_json::Value> {
+    let trimmed = value.trim();
+    if !(trimmed.starts_with('{') || trimmed.starts_with('[')) {
+        return None;
+    }
+    serde_json::from_str(trimmed).ok().filter(|v: &serde_json::Value| v.is_object() || v.is_array())
+}
+
+// psql imprime `bytea` como `\x48656c6c6f`; mysql con `--binary-as-hex`
+// como `0x48656C6C6F` y sin él vuelca los bytes tal cual
+fn hex_digits(value: &str) -> Option<&str> {
+    let digits = value.strip_prefix("\\x").or_else(|| value.strip_prefix("0x"))?;
+    (digits.len() % 2 == 0 && digits.chars().all(|c| c.is_ascii_hexdigit())).then_some(digits)
+}
+
+fn looks_binary(value: &str) -> bool {
+    value.chars().any(|c| c == '\u{fffd}' || (c.is_control() && !matches!(c, '\n' | '\r' | '\t')))
+}
+
+pub fn binary_len(value: &str) -> usize {
+    hex_digits(value).map(|digits| digits.len() / 2).unwrap_or(value.len())
+}
+
+pub fn binary_to_hex(value: &str) -> String {
+    match hex_digits(value) {
+        Some(digits) => digits.to_lowercase(),
+        None => value.bytes().map(|b| format!("{:02x}", b)).collect(),
+    }
+}
+
+// Tipo de la columna: el declarado si se conoce; si no, el que cumplen todas
+// las celdas no vacías de la muestra
+pub fn classify_column(set: &ParsedResultSet, column: usize, data_type: Option<&str>) -> CellKind {
+    if let Some(kind) = data_type.and_then(kind_from_data_type) {
+        return kind;
+    }
+    let sample = || set.rows.iter()
+        .take(CELL_KIND_SAMPLE_ROWS)
+        .filter_map(|r| r.get(column))
+        .map(String::as_str)
+        .filter(|v| !is_null_cell(v));
+    if sample().next().is_none() {
+        return CellKind::Text;
+    }
+    match infer_column_kind(sample()) {
+        ColumnKind::Numeric => return CellKind::Number,
+        ColumnKind::Date => return CellKind::Date,
+        ColumnKind::Text => {}
+    }
+    if sample().all(|v| parse_bool_cell(v).is_some()) {
+        CellKind::Boolean
+    } else if sample().all(|v| parse_json_cell(v).is_some()) {
+        CellKind::Json
+    } else if sample().any(looks_binary) || sample().all(|v| hex_digits(v).is_some()) {
+        CellKind::Binary
+    } else {
+        CellKind::Text
+    }
+}
+
+pub fn classify_columns(set: &ParsedResultSet, data_types: &[Option<String>]) -> Vec<CellKind> {
+    (0..set.columns.len())
+        .map(|i| classify_column(set, i, data_types.get(i).and_then(|t| t.as_deref())))
+        .collect()
+}
+
+// Tipo con el que se pinta una celda concreta. mysql imprime `NULL` y psql
+// deja la celda vacía; en columnas de texto una celda vacía es una cadena vacía.
+pub fn classify_cell(value: &str, column: CellKind) -> CellKind {
+    let null = value.trim().eq_ignore_ascii_case("null") || (value.trim().is_empty() && column != CellKind::Text);
+    if null {
+        return CellKind::Null;
+    }
+    match column {
+        CellKind::Boolean if parse_bool_cell(value).is_none() && !matches!(value.trim(), "0" | "1") => CellKind::Text,
+        CellKind::Json if parse_json_cell(value).is_none() => CellKind::Text,
+        CellKind::Number if parse_numeric_cell(value).is_none() => CellKind::Text,
+        kind => kind,
+    }
+}
+
+// `1234567.891` -> `1,234,567.891`; lo que no es un número se deja igual
+pub fn group_thousands(value: &str) -> String {
+    let trimmed = value.trim();
+    let (sign, rest) = match trimmed.strip_prefix('-') {
+        Some(rest) => ("-", rest),
+        None => ("", trimmed),
+    };
+    let (integer, fraction) = match rest.split_once('.') {
+        Some((i, f)) => (i, Some(f)),
+        None => (rest, None),
+    };
+    if integer.len() <= 3 || !integer.chars().all(|c| c.is_ascii_digit()) {
+        return value.to_string();
+    }
+
+    let mut grouped = String::with_capacity(integer.len() + integer.len() / 3);
+    for (i, c) in integer.chars().enumerate() {
+        if i > 0 && (integer.len() - i) % 3 == 0 {
+            grouped.push(',');
+        }
+        grouped.push(c);
+    }
+    match fraction {
+        Some(f) => format!("{}{}.{}", sign, grouped, f),
+        None => format!("{}{}", sign, grouped),
+    }
+}
+
+// Texto corto de una celda ya clasificada
+pub fn format_cell(value: &str, kind: CellKind, thousands: bool) -> String {
+    match kind {
+        CellKind::Null => "NULL".to_string(),
+        CellKind::Boolean => {
+            let truthy = parse_bool_cell(value).unwrap_or(value.trim() == "1");
+            if truthy { "✓".to_string() } else { "✗".to_string() }
+        }
+        CellKind::Number if thousands => group_thousands(value),
+        CellKind::Binary => format!("<blob {} bytes>", binary_len(value)),
+        CellKind::Json => value.split_whitespace().collect::<Vec<_>>().join(" "),
+        _ => value.replace('\n', "⏎"),
+    }
+}
+
+// Desfase del reloj local en minutos, leído una vez de `date +%z`
+static LOCAL_UTC_OFFSET: std::sync::LazyLock<Option<i64>> = std::sync::LazyLock::new(|| {
+    let output = crate::core::platform::command("date").arg("+%z").output().ok()?;
+    let text = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    let (sign, digits) = match text.strip_prefix('-') {
+        Some(d) => (-1, d),
+        None => (1, text.strip_prefix('+')?),
+    };
+    let hours: i64 = digits.get(..2)?.parse().ok()?;
+    let minutes: i64 = digits.get(2..4)?.parse().ok()?;
+    Some(sign * (hours * 60 + minutes))
+});
+
+// Una marca de tiempo sin zona leída como UTC y pasada a la hora local. Las
+// fechas sin hora no tienen zona que convertir.
+pub fn utc_to_local(value: &str) -> Option<String> {
+    let trimmed = value.trim();
+    if !trimmed.contains([' ', 'T']) {
+        return None;
+    }
+    let offset = (*LOCAL_UTC_OFFSET)?;
+    let total = ((parse_date_cell(trimmed)? + offset as f64 / 1440.0) * 86400.0).round() as i64;
+    let seconds = total.rem_euclid(86400);
+    Some(format!(
+        "{} {:02}:{:02}:{:02} (UTC{:+03}:{:02})",
+        format_day_number(total.div_euclid(86400) as f64),
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60,
+        offset / 60,
+        (offset % 60).abs(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(columns: &[&str], rows: &[&[&str]]) -> ParsedResultSet {
+        ParsedResultSet {
+            columns: columns.iter().map(|c| c.to_string()).collect(),
+            rows: rows.iter().map(|r| r.iter().map(|v| v.to_string()).collect()).collect(),
+        }
+    }
+
+    #[test]
+    fn csv_quotes_only_when_needed() {
+        let set = set(&["id", "note"], &[&["1", "plain"], &["2", "a, b"], &["3", "say \"hi\""], &["4", "two\nlines"]]);
+        assert_eq!(
+            result_set_to_csv(&set),
+            "id,note\n1,plain\n2,\"a, b\"\n3,\"say \"\"hi\"\"\"\n4,\"two\nlines\"\n",
+        );
+    }
+
+    #[test]
+    fn csv_keeps_unicode_and_null_as_is() {
+        let set = set(&["nombre", "ciudad"], &[&["José", "NULL"], &["李", "Zürich 🏔"]]);
+        assert_eq!(result_set_to_csv(&set), "nombre,ciudad\nJosé,NULL\n李,Zürich 🏔\n");
+    }
+
+    #[test]
+    fn markdown_escapes_pipes_and_newlines() {
+        let set = set(&["a|b", "c"], &[&["x|y", "one\ntwo"], &["back\\slash", "crlf\r\nend"]]);
+        assert_eq!(
+            result_set_to_markdown(&set),
+            "| a\\|b | c |\n|---|---|\n| x\\|y | one<br>two |\n| back\\\\slash | crlf<br>end |\n",
+        );
+    }
+
+    #[test]
+    fn json_turns_null_into_null_and_numeric_columns_into_numbers() {
+        let set = set(&["id", "name", "code"], &[&["1", "Ana", "007"], &["2", "NULL", "NULL"], &["3", "línea\nnueva", "12"]]);
+        let json: serde_json::Value = serde_json::from_str(&result_set_to_json(&set)).unwrap();
+        assert_eq!(json, serde_json::json!([
+            {"id": 1, "name": "Ana", "code": "007"},
+            {"id": 2, "name": null, "code": null},
+            {"id": 3, "name": "línea\nnueva", "code": 12},
+        ]));
+    }
+
+    #[test]
+    fn inserts_quote_with_the_dialect_and_keep_null() {
+        let set = set(&["id", "name"], &[&["1", "O'Brien"], &["2", "NULL"]]);
+        let sql = result_set_to_inserts(
+            &set,
+            "users",
+            |name| format!("`{}`", name),
+            |value| format!("'{}'", value.replace('\'', "''")),
+        );
+        assert_eq!(
+            sql,
+            "INSERT INTO `users` (`id`, `name`) VALUES ('1', 'O''Brien');\nINSERT INTO `users` (`id`, `name`) VALUES ('2', NULL);\n",
+        );
+    }
+
+    #[test]
+    fn parses_mysql_table_psql_table_and_batch_output() {
+        let mysql = "+----+-------+\n| id | name  |\n+----+-------+\n|  1 | José  |\n+----+-------+\n1 row in set\n";
+        assert_eq!(parse_result_set(mysql), Some(set(&["id", "name"], &[&["1", "José"]])));
+
+        let psql = " id | name \n----+------\n  1 | Ana\n  2 | \n(2 rows)\n";
+        assert_eq!(parse_result_set(psql), Some(set(&["id", "name"], &[&["1", "Ana"], &["2", ""]])));
+
+        let batch = "id\tnote\n1\tdos\\nlíneas\n2\tcon\\ttab\n";
+        assert_eq!(parse_result_set(batch), Some(set(&["id", "note"], &[&["1", "dos\nlíneas"], &["2", "con\ttab"]])));
+        assert_eq!(parse_result_set("\n\n"), None);
+    }
+
+    #[test]
+    fn parses_numbers_as_clients_print_them() {
+        assert_eq!(parse_numeric_cell(" 42 "), Some(42.0));
+        assert_eq!(parse_numeric_cell("-3.5e2"), Some(-350.0));
+        assert_eq!(parse_numeric_cell("1,234,567.89"), Some(1_234_567.89));
+        assert_eq!(parse_numeric_cell("1 234"), Some(1234.0));
+        assert_eq!(parse_numeric_cell("+1_000"), Some(1000.0));
+        assert_eq!(parse_numeric_cell("12,34"), None);
+        assert_eq!(parse_numeric_cell("1,2345"), None);
+        assert_eq!(parse_numeric_cell("NaN"), None);
+        assert_eq!(parse_numeric_cell("NULL"), None);
+        assert_eq!(parse_numeric_cell("abc"), None);
+    }
+
+    #[test]
+    fn parses_dates_with_optional_time() {
+        assert_eq!(parse_date_cell("1970-01-01"), Some(0.0));
+        assert_eq!(parse_date_cell("1970-01-02 12:00:00"), Some(1.5));
+        assert_eq!(parse_date_cell("1970-01-01T06:00:00Z"), Some(0.25));
+        assert_eq!(parse_date_cell("2024-02-30x"), None);
+        assert_eq!(parse_date_cell("2024-13-01"), None);
+        assert_eq!(parse_date_cell("24-01-01"), None);
+        assert_eq!(format_day_number(parse_date_cell("2024-02-29").unwrap()), "2024-02-29");
+    }
+
+    #[test]
+    fn infers_column_kind_from_non_null_cells() {
+        assert_eq!(infer_column_kind(["1", "2.5", "NULL", ""].into_iter()), ColumnKind::Numeric);
+        assert_eq!(infer_column_kind(["2024-01-01", "null", "2024-01-02 10:00:00"].into_iter()), ColumnKind::Date);
+        assert_eq!(infer_column_kind(["1", "dos"].into_iter()), ColumnKind::Text);
+        assert_eq!(infer_column_kind(["1", "2024-01-01"].into_iter()), ColumnKind::Text);
+        assert_eq!(infer_column_kind(["NULL", ""].into_iter()), ColumnKind::Text);
+    }
+
+    #[test]
+    fn chart_sorts_numeric_x_and_skips_rows_without_y() {
+        let set = set(&["x", "y"], &[&["3", "30"], &["1", "10"], &["2", "NULL"], &["4", "abc"]]);
+        let data = build_chart_data(&set, 0, 1);
+        assert_eq!(data.x_kind, ColumnKind::Numeric);
+        assert_eq!(data.points, vec![(1.0, 10.0), (3.0, 30.0)]);
+        assert_eq!(data.labels, vec!["1", "3"]);
+        assert_eq!(data.skipped, 2);
+    }
+
+    #[test]
+    fn chart_keeps_order_for_categories_and_downsamples() {
+        let set = set(&["name", "total"], &[&["b", "2"], &["a", "1"]]);
+        let data = build_chart_data(&set, 0, 1);
+        assert_eq!(data.x_kind, ColumnKind::Text);
+        assert_eq!(data.points, vec![(0.0, 2.0), (1.0, 1.0)]);
+
+        let rows: Vec<Vec<String>> = (0..CHART_MAX_POINTS * 2).map(|i| vec![i.to_string(), "1".to_string()]).collect();
+        let big = ParsedResultSet { columns: vec!["x".to_string(), "y".to_string()], rows };
+        let data = build_chart_data(&big, 0, 1);
+        assert_eq!(data.points.len(), CHART_MAX_POINTS);
+        assert_eq!(data.downsampled_from, Some(CHART_MAX_POINTS * 2));
+        assert_eq!(data.points.first().unwrap().0, 0.0);
+        assert_eq!(data.points.last().unwrap().0, (CHART_MAX_POINTS * 2 - 1) as f64);
+    }
+}
@@ -0,0 +1,258 @@
+#[cfg(test)]
+use std::collections::VecDeque;
+use std::io::{self, BufReader, Read, Write};
+use std::path::Path;
+#[cfg(test)]
+use std::path::PathBuf;
+use std::process::{Child, ExitStatus, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::Receiver;
+use std::sync::Arc;
+#[cfg(test)]
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+
+use crate::core::logging::command_for_log;
+use crate::core::platform;
+use crate::core::processes::ProcessRegistration;
+
+const CANCEL_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+// Resultado de un proceso ya terminado
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CommandOutput {
+    pub success: bool,
+    pub stdout: Vec<u8>,
+    pub stderr: Vec<u8>,
+}
+
+impl CommandOutput {
+    pub fn stdout_lossy(&self) -> String {
+        String::from_utf8_lossy(&self.stdout).to_string()
+    }
+
+    pub fn stderr_lossy(&self) -> String {
+        String::from_utf8_lossy(&self.stderr).to_string()
+    }
+}
+
+// Abstracción sobre la ejecución de procesos externos, para que la capa de
+// comandos no dependa de tener `lando` instalado.
+pub trait CommandRunner: Send + Sync {
+    // Ejecuta el programa y espera a que termine, capturando toda la salida
+    fn run(&self, program: &str, args: &[String], cwd: Option<&Path>) -> io::Result<CommandOutput>;
+
+    // Ejecuta el programa entregando stdout/stderr a `on_chunk` a medida que llegan.
+    // Si `cancel` se activa se mata el proceso (y sus hijos). Lo que llegue por
+    // `input` se escribe en su stdin; sin `input` el stdin queda cerrado para que
+    // una pregunta inesperada falle en vez de bloquear. Devuelve si terminó con éxito.
+    fn run_streaming(
+        &self,
+        program: &str,
+        args: &[String],
+        cwd: Option<&Path>,
+        on_chunk: &(dyn Fn(Vec<u8>) + Sync),
+        cancel: &AtomicBool,
+        input: Option<&Receiver<Vec<u8>>>,
+    ) -> io::Result<bool>;
+}
+
+// Runner real basado en `std::process::Command`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemRunner;
+
+impl CommandRunner for SystemRunner {
+    fn run(&self, program: &str, args: &[String], cwd: Option<&Path>) -> io::Result<CommandOutput> {
+        let mut command = platform::command(program);
+        command.args(args);
+        if let Some(cwd) = cwd {
+            command.current_dir(cwd);
+        }
+
+        log::info!("$ {}", command_for_log(program, args));
+        let output = command.output().inspect_err(|e| log::error!("No se pudo lanzar {}: {}", program, e))?;
+        log_exit(program, &output.status);
+        Ok(CommandOutput {
+            success: output.status.success(),
+            stdout: output.stdout,
+            stderr: output.stderr,
+        })
+    }
+
+    fn run_streaming(
+        &self,
+        program: &str,
+        args: &[String],
+        cwd: Option<&Path>,
+        on_chunk: &(dyn Fn(Vec<u8>) + Sync),
+        cancel: &AtomicBool,
+        input: Option<&Receiver<Vec<u8>>>,
+    ) -> io::Result<bool> {
+        let mut command = platform::command(program);
+        command.args(args).stdout(Stdio::piped()).stderr(Stdio::piped());
+        command.stdin(if input.is_some() { Stdio::piped() } else { Stdio::null() });
+        if let Some(cwd) = cwd {
+            command.current_dir(cwd);
+        }
+        // Grupo de procesos propio para poder matar también a `docker exec` y compañía
+        #[cfg(unix)]
+        {
+            use std::os::unix::process::CommandExt;
+            command.process_group(0);
+        }
+
+        log::info!("$ {}", command_for_log(program, args));
+        let mut child = command.spawn().inspect_err(|e| log::error!("No se pudo lanzar {}: {}", program, e))?;
+        // Se desapunta al salir de la función, termine como termine
+        let _registration = ProcessRegistration::new(child.id(), command_for_log(program, args), cwd);
+        let stdout = child.stdout.take();
+        let stderr = child.stderr.take();
+        let mut stdin = child.stdin.take();
+
+        // Leer stdout y stderr en paralelo mientras se vigila la cancelación
+        thread::scope(|scope| {
+            if let Some(stdout) = stdout {
+                scope.spawn(move || forward_chunks(stdout, on_chunk));
+            }
+            if let Some(stderr) = stderr {
+                scope.spawn(move || forward_chunks(stderr, on_chunk));
+            }
+
+            let mut killed = false;
+            while child.try_wait()?.is_none() {
+                if !killed && cancel.load(Ordering::Relaxed) {
+                    kill_process_tree(&mut child);
+                    killed = true;
+                }
+                if let (Some(writer), Some(input)) = (stdin.as_mut(), input) {
+                    while let Ok(bytes) = input.try_recv() {
+                        if writer.write_all(&bytes).and_then(|_| writer.flush()).is_err() {
+                            break;
+                        }
+                    }
+                }
+                thread::sleep(CANCEL_POLL_INTERVAL);
+            }
+            Ok::<(), io::Error>(())
+        })?;
+
+        let status = child.wait()?;
+        if cancel.load(Ordering::Relaxed) {
+            log::info!("{} cancelado por el usuario", program);
+        }
+        log_exit(program, &status);
+        Ok(status.success())
+    }
+}
+
+// El código de salida va en debug si todo fue bien y como aviso si no
+fn log_exit(program: &str, status: &ExitStatus) {
+    match status.code() {
+        Some(0) => log::debug!("{} terminó con código 0", program),
+        Some(code) => log::warn!("{} terminó con código {}", program, code),
+        None => log::warn!("{} terminó por una señal", program),
+    }
+}
+
+// Mata el proceso junto con los que haya lanzado (lando -> docker exec -> comando)
+pub fn kill_process_tree(child: &mut Child) {
+    platform::kill_process_tree(child.id());
+    let _ = child.kill();
+}
+
+fn forward_chunks(stream: impl Read, on_chunk: &(dyn Fn(Vec<u8>) + Sync)) {
+    let mut reader = BufReader::new(stream);
+    let mut buffer = [0; 1024];
+    while let Ok(n) = reader.read(&mut buffer) {
+        if n == 0 { break; }
+        on_chunk(buffer[..n].to_vec());
+    }
+}
+
+// Runner por defecto usado por las funciones de `commands`
+pub fn system_runner() -> Arc<dyn CommandRunner> {
+    Arc::new(SystemRunner)
+}
+
+// Invocación registrada por `StubRunner`
+#[cfg(test)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordedCall {
+    pub program: String,
+    pub args: Vec<String>,
+    pub cwd: Option<PathBuf>,
+}
+
+// Runner falso: devuelve respuestas preparadas en orden y registra cada llamada.
+// Permite ejercitar el parseo y los caminos de error sin lanzar procesos.
+#[cfg(test)]
+#[derive(Default)]
+pub struct StubRunner {
+    responses: Mutex<VecDeque<io::Result<CommandOutput>>>,
+    calls: Mutex<Vec<RecordedCall>>,
+}
+
+#[cfg(test)]
+impl StubRunner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn respond(self, response: io::Result<CommandOutput>) -> Self {
+        self.responses.lock().unwrap().push_back(response);
+        self
+    }
+
+    pub fn respond_ok(self, stdout: &str) -> Self {
+        self.respond(Ok(CommandOutput { success: true, stdout: stdout.as_bytes().to_vec(), stderr: vec![] }))
+    }
+
+    pub fn respond_err(self, stderr: &str) -> Self {
+        self.respond(Ok(CommandOutput { success: false, stdout: vec![], stderr: stderr.as_bytes().to_vec() }))
+    }
+
+    pub fn calls(&self) -> Vec<RecordedCall> {
+        self.calls.lock().unwrap().clone()
+    }
+
+    fn next_response(&self, program: &str, args: &[String], cwd: Option<&Path>) -> io::Result<CommandOutput> {
+        self.calls.lock().unwrap().push(RecordedCall {
+            program: program.to_string(),
+            args: args.to_vec(),
+            cwd: cwd.map(Path::to_path_buf),
+        });
+        self.responses.lock().unwrap().pop_front().unwrap_or_else(|| {
+            Err(io::Error::new(io::ErrorKind::NotFound, "sin respuesta preparada"))
+        })
+    }
+}
+
+#[cfg(test)]
+impl CommandRunner for StubRunner {
+    fn run(&self, program: &str, args: &[String], cwd: Option<&Path>) -> io::Result<CommandOutput> {
+        self.next_response(program, args, cwd)
+    }
+
+    fn run_streaming(
+        &self,
+        program: &str,
+        args: &[String],
+        cwd: Option<&Path>,
+        on_chunk: &(dyn Fn(Vec<u8>) + Sync),
+        cancel: &AtomicBool,
+        _input: Option<&Receiver<Vec<u8>>>,
+    ) -> io::Result<bool> {
+        let output = self.next_response(program, args, cwd)?;
+        if cancel.load(Ordering::Relaxed) {
+            return Ok(false);
+        }
+        if !output.stdout.is_empty() {
+            on_chunk(output.stdout);
+        }
+        if !output.stderr.is_empty() {
+            on_chunk(output.stderr);
+        }
+        Ok(output.success)
+    }
+}
@@ -0,0 +1,226 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::mpsc::Sender;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::commands::*;
+use crate::core::result_format::parse_result_set;
+use crate::core::storage::{load_app_file, save_app_file};
+use crate::core::time::now_secs;
+use crate::models::commands::LandoCommandOutcome;
+use crate::models::lando::LandoService;
+use crate::ui::database::{
+    DatabaseUI, PendingRequest, QuerySchedule, ScheduleAlert, ScheduleCondition, ScheduleRun, ScheduleRuntime,
+};
+
+// Consultas guardadas y sus programaciones, por proyecto y servicio
+const SAVED_QUERIES_FILE: &str = "saved_queries.json";
+// Ejecuciones que se conservan por programación
+const SCHEDULE_HISTORY: usize = 5;
+// Tope de la espera tras fallos encadenados (app parada, servicio caído...)
+const MAX_BACKOFF: Duration = Duration::from_secs(30 * 60);
+
+pub const SCHEDULE_MIN_INTERVAL: u64 = 30;
+pub const SCHEDULE_MAX_INTERVAL: u64 = 30 * 60;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct SavedQueries {
+    #[serde(default)]
+    queries: HashMap<String, String>,
+    #[serde(default)]
+    schedules: Vec<QuerySchedule>,
+    #[serde(default)]
+    saved_at: HashMap<String, u64>,
+}
+
+impl DatabaseUI {
+    pub fn ensure_saved_queries(&mut self, service: &LandoService, project_path: &PathBuf) {
+        let key = format!("{}#{}", project_path.display(), service.service);
+        if self.saved_queries_key.as_ref() == Some(&key) {
+            return;
+        }
+
+        let saved = load_app_file::<HashMap<String, SavedQueries>>(SAVED_QUERIES_FILE)
+            .remove(&key)
+            .unwrap_or_default();
+        self.saved_queries = saved.queries;
+        self.saved_query_times = saved.saved_at;
+        self.schedules = saved.schedules;
+        self.saved_queries_key = Some(key);
+        self.schedules_error = None;
+    }
+
+    pub fn persist_saved_queries(&mut self) {
+        let Some(key) = self.saved_queries_key.clone() else {
+            return;
+        };
+
+        let mut saved: HashMap<String, SavedQueries> = load_app_file(SAVED_QUERIES_FILE);
+        saved.insert(key, SavedQueries {
+            queries: self.saved_queries.clone(),
+            schedules: self.schedules.clone(),
+            saved_at: self.saved_query_times.clone(),
+        });
+
+        self.schedules_error = save_app_file(SAVED_QUERIES_FILE, &saved)
+            .err()
+            .map(|e| format!("No se pudieron guardar las consultas: {}", e));
+    }
+
+    // Una programación por consulta guardada; si ya existe se sustituye
+    pub fn add_schedule(&mut self) {
+        let form = self.schedule_form.clone();
+        if !self.saved_queries.contains_key(&form.query_name) {
+            self.schedules_error = Some("Elige una consulta guardada".to_string());
+            return;
+        }
+
+        self.schedules.retain(|s| s.query_name != form.query_name);
+        self.schedules.push(QuerySchedule {
+            query_name: form.query_name,
+            interval_secs: form.interval_secs.clamp(SCHEDULE_MIN_INTERVAL, SCHEDULE_MAX_INTERVAL),
+            condition: form.condition,
+            enabled: true,
+            desktop_notification: form.desktop_notification,
+            runtime: ScheduleRuntime::default(),
+        });
+        self.persist_saved_queries();
+    }
+
+    pub fn remove_schedule(&mut self, index: usize) {
+        if index < self.schedules.len() {
+            self.schedules.remove(index);
+            self.persist_saved_queries();
+        }
+    }
+
+    pub fn set_schedule_enabled(&mut self, index: usize, enabled: bool) {
+        if let Some(schedule) = self.schedules.get_mut(index) {
+            schedule.enabled = enabled;
+            schedule.runtime.next_run = None;
+            schedule.runtime.failures = 0;
+            schedule.runtime.paused_reason = None;
+            self.persist_saved_queries();
+        }
+    }
+
+    // Se llama en cada frame desde la app, aunque la interfaz no esté visible.
+    // No lanza una ejecución si la anterior sigue en curso.
+    pub fn tick_schedules(
+        &mut self,
+        service: &LandoService,
+        project_path: &PathBuf,
+        sender: &Sender<LandoCommandOutcome>,
+        app_running: bool,
+    ) {
+        self.ensure_saved_queries(service, project_path);
+        let now = Instant::now();
+
+        for index in 0..self.schedules.len() {
+            let schedule = &mut self.schedules[index];
+            if !schedule.enabled || schedule.runtime.running {
+                continue;
+            }
+            if !app_running {
+                schedule.runtime.paused_reason = Some("La app está detenida".to_string());
+                schedule.runtime.next_run = None;
+                continue;
+            }
+
+            let Some(query) = self.saved_queries.get(&schedule.query_name).cloned() else {
+                schedule.runtime.paused_reason = Some("La consulta guardada ya no existe".to_string());
+                continue;
+            };
+
+            // Al (re)activarse se ejecuta en el siguiente frame
+            let next_run = *schedule.runtime.next_run.get_or_insert(now);
+            if now < next_run {
+                continue;
+            }
+
+            schedule.runtime.running = true;
+            let request_id = next_request_id();
+            let name = schedule.query_name.clone();
+            self.pending_requests.insert(request_id, PendingRequest::ScheduledQuery { schedule: name });
+            run_db_query(sender.clone(), project_path.clone(), self.db_connection(service), query, request_id);
+        }
+    }
+
+    pub fn handle_schedule_result(&mut self, name: &str, output: &Result<String, String>) {
+        let Some(schedule) = self.schedules.iter_mut().find(|s| s.query_name == name) else {
+            return;
+        };
+        let runtime = &mut schedule.runtime;
+        runtime.running = false;
+
+        let interval = Duration::from_secs(schedule.interval_secs);
+        let row_count = output.as_ref().ok()
+            .and_then(|text| parse_result_set(text))
+            .map(|set| set.rows.len());
+
+        let triggered = match output {
+            Err(msg) => {
+                // Probablemente la app se ha parado: se reintenta con espera creciente
+                runtime.failures += 1;
+                let backoff = interval.saturating_mul(1 << runtime.failures.min(6)).min(MAX_BACKOFF);
+                runtime.next_run = Some(Instant::now() + backoff);
+                runtime.paused_reason = Some(msg.lines().next().unwrap_or_default().to_string());
+                false
+            }
+            Ok(text) => {
+                runtime.failures = 0;
+                runtime.paused_reason = None;
+                runtime.next_run = Some(Instant::now() + interval);
+                match schedule.condition {
+                    ScheduleCondition::Never => false,
+                    ScheduleCondition::RowsAboveZero => row_count.is_some_and(|rows| rows > 0),
+                    ScheduleCondition::ResultChanged => runtime.runs.iter()
+                        .rev()
+                        .find_map(|run| run.output.as_ref().ok())
+                        .is_some_and(|previous| previous != text),
+                }
+            }
+        };
+
+        runtime.runs.push_back(ScheduleRun {
+            finished_at: now_secs(),
+            output: output.clone(),
+            row_count,
+            triggered,
+        });
+        while runtime.runs.len() > SCHEDULE_HISTORY {
+            runtime.runs.pop_front();
+        }
+
+        if triggered {
+            let body = match schedule.condition {
+                ScheduleCondition::ResultChanged => "El resultado cambió desde la última ejecución".to_string(),
+                _ => format!("{} filas", row_count.unwrap_or_default()),
+            };
+            self.schedule_alerts.push(ScheduleAlert {
+                title: format!("⏰ {}", schedule.query_name),
+                body,
+                desktop: schedule.desktop_notification,
+            });
+        }
+    }
+
+    pub fn has_active_schedules(&self) -> bool {
+        self.schedules.iter().any(|s| s.enabled)
+    }
+
+    pub fn format_next_run(&self, schedule: &QuerySchedule) -> String {
+        if schedule.runtime.running {
+            return "⏳ ejecutando".to_string();
+        }
+        if !schedule.enabled {
+            return "desactivada".to_string();
+        }
+        match schedule.runtime.next_run {
+            Some(next) => format!("en {} s", next.saturating_duration_since(Instant::now()).as_secs()),
+            None => "pendiente".to_string(),
+        }
+    }
+}
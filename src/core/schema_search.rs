@@ -0,0 +1,192 @@
+use crate::ui::database::TableInfo;
+
+// Filtro del explorador de schema. Sin comodines busca la subcadena; con `*`
+// o `?` el patrón tiene que cubrir el nombre entero (`field_*_value`).
+// Sin distinguir mayúsculas se compara en minúsculas Unicode (`Ñ` == `ñ`).
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchemaMatcher {
+    pattern: Vec<char>,
+    glob: bool,
+    case_sensitive: bool,
+}
+
+// Coincidencias agrupadas: tablas por nombre y columnas por nombre o tipo
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SchemaMatches {
+    pub tables: Vec<String>,
+    pub columns: Vec<ColumnMatch>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnMatch {
+    pub table: String,
+    pub column: String,
+    pub data_type: String,
+}
+
+impl SchemaMatcher {
+    pub fn new(filter: &str, case_sensitive: bool) -> Option<Self> {
+        let filter = filter.trim();
+        if filter.is_empty() {
+            return None;
+        }
+        let pattern = if case_sensitive { filter.to_string() } else { filter.to_lowercase() };
+        Some(Self {
+            glob: pattern.contains(['*', '?']),
+            pattern: pattern.chars().collect(),
+            case_sensitive,
+        })
+    }
+
+    pub fn matches(&self, text: &str) -> bool {
+        let folded;
+        let text = if self.case_sensitive {
+            text
+        } else {
+            folded = text.to_lowercase();
+            &folded
+        };
+        let text: Vec<char> = text.chars().collect();
+
+        if self.glob {
+            glob_match(&self.pattern, &text)
+        } else {
+            text.windows(self.pattern.len()).any(|window| window == self.pattern.as_slice())
+        }
+    }
+}
+
+// `*` cualquier secuencia, `?` un carácter. Con retroceso al último `*`
+// visto, lineal en la práctica para nombres de tabla.
+fn glob_match(pattern: &[char], text: &[char]) -> bool {
+    let (mut p, mut t) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p, t));
+                p += 1;
+            }
+            Some(c) if *c == '?' || *c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match star {
+                Some((star_p, star_t)) => {
+                    p = star_p + 1;
+                    t = star_t + 1;
+                    star = Some((star_p, star_t + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p.min(pattern.len())..].iter().all(|c| *c == '*')
+}
+
+// Solo recorre los metadatos ya cargados: no lanza consultas
+pub fn search_schema(tables: &[TableInfo], matcher: &SchemaMatcher) -> SchemaMatches {
+    let mut matches = SchemaMatches::default();
+    for table in tables {
+        if matcher.matches(&table.name) {
+            matches.tables.push(table.name.clone());
+        }
+        for column in &table.columns {
+            if matcher.matches(&column.name) || matcher.matches(&column.data_type) {
+                matches.columns.push(ColumnMatch {
+                    table: table.name.clone(),
+                    column: column.name.clone(),
+                    data_type: column.data_type.clone(),
+                });
+            }
+        }
+    }
+    matches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::database::ColumnInfo;
+
+    fn matcher(filter: &str) -> SchemaMatcher {
+        SchemaMatcher::new(filter, false).unwrap()
+    }
+
+    fn table(name: &str, columns: &[(&str, &str)]) -> TableInfo {
+        TableInfo {
+            name: name.to_string(),
+            columns: columns.iter()
+                .map(|(name, data_type)| ColumnInfo {
+                    name: name.to_string(),
+                    data_type: data_type.to_string(),
+                    nullable: true,
+                    default_value: None,
+                    is_primary_key: false,
+                    stats: None,
+                })
+                .collect(),
+            row_count: None,
+            table_type: "table".to_string(),
+            foreign_keys: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn substring_without_wildcards() {
+        assert!(matcher("user").matches("wp_users"));
+        assert!(matcher("USER").matches("wp_users"));
+        assert!(!matcher("usr").matches("wp_users"));
+        assert!(SchemaMatcher::new("   ", false).is_none());
+    }
+
+    #[test]
+    fn globs_cover_the_whole_name() {
+        let field = matcher("field_*_value");
+        assert!(field.matches("field_body_value"));
+        assert!(field.matches("field__value"));
+        assert!(field.matches("field_a_value_b_value"));
+        assert!(!field.matches("field_body_value_2"));
+        assert!(!field.matches("node_field_body_value"));
+
+        assert!(matcher("wp_?").matches("wp_1"));
+        assert!(!matcher("wp_?").matches("wp_10"));
+        assert!(matcher("*").matches(""));
+        assert!(matcher("**a**").matches("banana"));
+        assert!(!matcher("*a?").matches("banana"));
+    }
+
+    #[test]
+    fn unicode_names() {
+        assert!(matcher("AÑO").matches("año_fiscal"));
+        assert!(matcher("ca?ón").matches("Cañón"));
+        assert!(matcher("*ÉTÉ").matches("données_été"));
+        assert!(matcher("日本").matches("テーブル_日本語"));
+
+        let case_sensitive = SchemaMatcher::new("Ñ*", true).unwrap();
+        assert!(case_sensitive.matches("Ñandú"));
+        assert!(!case_sensitive.matches("ñandú"));
+    }
+
+    #[test]
+    fn tables_and_columns_by_name_or_type() {
+        let tables = vec![
+            table("users", &[("id", "int"), ("user_email", "varchar(255)")]),
+            table("orders", &[("id", "int"), ("user_id", "int"), ("meta", "jsonb")]),
+        ];
+        let matches = search_schema(&tables, &matcher("user*"));
+        assert_eq!(matches.tables, vec!["users"]);
+        assert_eq!(
+            matches.columns.iter().map(|c| format!("{}.{}", c.table, c.column)).collect::<Vec<_>>(),
+            vec!["users.user_email", "orders.user_id"],
+        );
+        let json = search_schema(&tables, &matcher("JSON"));
+        assert!(json.tables.is_empty());
+        assert_eq!(json.columns, vec![ColumnMatch {
+            table: "orders".to_string(),
+            column: "meta".to_string(),
+            data_type: "jsonb".to_string(),
+        }]);
+    }
+}
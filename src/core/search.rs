@@ -0,0 +1,290 @@
+use std::sync::mpsc::Sender;
+use std::thread;
+use std::time::Duration;
+
+use serde_json::Value;
+
+use crate::core::http::{http_request, local_host};
+use crate::core::profiles::encode_uri_component;
+use crate::models::commands::LandoCommandOutcome;
+use crate::models::lando::LandoService;
+use crate::models::search::{SearchAction, SearchEngine, SearchIndex};
+use crate::ui::confirm::ConfirmDialog;
+use crate::ui::search::{SearchResult, SearchUI};
+
+// Un `_delete_by_query` sobre un índice grande tarda
+const SEARCH_TIMEOUT: Duration = Duration::from_secs(30);
+
+// Por motor (`solr:8`, o el resuelto de un `compose`) o por nombre
+pub fn search_engine(service: &LandoService) -> Option<SearchEngine> {
+    let engine = service.db_engine().to_lowercase();
+    let name = service.service.to_lowercase();
+    if engine.starts_with("solr") || name.contains("solr") {
+        Some(SearchEngine::Solr)
+    } else if ["elasticsearch", "opensearch"].iter().any(|e| engine.starts_with(e) || name.contains(e)) {
+        Some(SearchEngine::Elasticsearch)
+    } else {
+        None
+    }
+}
+
+// La API HTTP se alcanza desde el host por la conexión externa
+pub fn search_base_url(service: &LandoService) -> Option<String> {
+    let connection = service.external_connection.as_ref().filter(|c| !c.port.is_empty())?;
+    Some(format!("http://{}:{}", local_host(&connection.host), connection.port))
+}
+
+// Los números a veces llegan como texto (`_cat` de Elasticsearch)
+fn as_u64(value: &Value) -> Option<u64> {
+    value.as_u64().or_else(|| value.as_str().and_then(|s| s.parse().ok()))
+}
+
+// `{"status": {"core": {"index": {"numDocs": 10, "size": "1.2 KB"}}}}`
+fn parse_solr_cores(body: &str) -> Result<Vec<SearchIndex>, String> {
+    let payload: Value = serde_json::from_str(body).map_err(|e| format!("Respuesta de Solr no válida: {}", e))?;
+    let cores = payload.get("status")
+        .and_then(Value::as_object)
+        .ok_or_else(|| "La respuesta de Solr no trae `status`".to_string())?;
+    Ok(cores.iter()
+        .map(|(name, core)| {
+            let index = core.get("index");
+            SearchIndex {
+                name: name.clone(),
+                docs: index.and_then(|i| i.get("numDocs")).and_then(as_u64),
+                size: index.and_then(|i| i.get("size")).and_then(Value::as_str).map(String::from),
+                health: None,
+            }
+        })
+        .collect())
+}
+
+// `[{"index": "x", "docs.count": "10", "store.size": "3.4kb", "health": "green"}]`
+fn parse_es_indices(body: &str) -> Result<Vec<SearchIndex>, String> {
+    let payload: Value = serde_json::from_str(body).map_err(|e| format!("Respuesta de Elasticsearch no válida: {}", e))?;
+    let items = payload.as_array().ok_or_else(|| "Elasticsearch no devolvió una lista de índices".to_string())?;
+    let mut indices: Vec<SearchIndex> = items.iter()
+        .filter_map(|item| {
+            Some(SearchIndex {
+                name: item.get("index")?.as_str()?.to_string(),
+                docs: item.get("docs.count").and_then(as_u64),
+                size: item.get("store.size").and_then(Value::as_str).map(String::from),
+                health: item.get("health").and_then(Value::as_str).map(String::from),
+            })
+        })
+        // Los índices internos (`.kibana`, `.security`) no interesan
+        .filter(|index| !index.name.starts_with('.'))
+        .collect();
+    indices.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(indices)
+}
+
+fn fetch_indices(engine: SearchEngine, base_url: &str) -> Result<Vec<SearchIndex>, String> {
+    let url = match engine {
+        SearchEngine::Solr => format!("{}/solr/admin/cores?action=STATUS&wt=json", base_url),
+        SearchEngine::Elasticsearch => format!("{}/_cat/indices?format=json", base_url),
+    };
+    let response = http_request("GET", &url, None, SEARCH_TIMEOUT)?;
+    if response.status >= 400 {
+        return Err(format!("HTTP {} en {}", response.status, url));
+    }
+    match engine {
+        SearchEngine::Solr => parse_solr_cores(&response.body),
+        SearchEngine::Elasticsearch => parse_es_indices(&response.body),
+    }
+}
+
+pub fn load_search_indices(sender: Sender<LandoCommandOutcome>, service: String, engine: SearchEngine, base_url: String) {
+    thread::spawn(move || {
+        let result = fetch_indices(engine, &base_url);
+        let _ = sender.send(LandoCommandOutcome::SearchIndicesLoaded { service, result });
+    });
+}
+
+// Petición (método, URL, cuerpo) de cada acción; también sirve de vista previa
+fn action_request(engine: SearchEngine, base_url: &str, action: &SearchAction) -> (&'static str, String, Option<String>) {
+    match (engine, action) {
+        (SearchEngine::Solr, SearchAction::Reload(core)) => (
+            "GET",
+            format!("{}/solr/admin/cores?action=RELOAD&core={}&wt=json", base_url, encode_uri_component(core)),
+            None,
+        ),
+        (SearchEngine::Solr, SearchAction::DeleteAll(core)) => (
+            "POST",
+            format!("{}/solr/{}/update?commit=true&wt=json", base_url, encode_uri_component(core)),
+            Some(r#"{"delete": {"query": "*:*"}}"#.to_string()),
+        ),
+        (SearchEngine::Elasticsearch, SearchAction::Reload(index)) => (
+            "POST",
+            format!("{}/{}/_refresh", base_url, encode_uri_component(index)),
+            None,
+        ),
+        (SearchEngine::Elasticsearch, SearchAction::DeleteAll(index)) => (
+            "POST",
+            format!("{}/{}/_delete_by_query?refresh=true&conflicts=proceed", base_url, encode_uri_component(index)),
+            Some(r#"{"query": {"match_all": {}}}"#.to_string()),
+        ),
+    }
+}
+
+fn request_preview(method: &str, url: &str, body: Option<&str>) -> String {
+    match body {
+        Some(body) => format!("{} {}\n{}", method, url, body),
+        None => format!("{} {}", method, url),
+    }
+}
+
+// Tras una acción se vuelve a pedir la lista para ver los nuevos totales
+fn run_search_request(
+    sender: Sender<LandoCommandOutcome>,
+    service: String,
+    label: String,
+    request: (&'static str, String, Option<String>),
+    reload: Option<(SearchEngine, String)>,
+) {
+    thread::spawn(move || {
+        let (method, url, body) = request;
+        log::info!("{} {}", method, url);
+        let result = http_request(method, &url, body.as_deref(), SEARCH_TIMEOUT)
+            .map(|response| (response.status, response.body));
+        let _ = sender.send(LandoCommandOutcome::SearchResponse { service: service.clone(), label, result });
+
+        if let Some((engine, base_url)) = reload {
+            let result = fetch_indices(engine, &base_url);
+            let _ = sender.send(LandoCommandOutcome::SearchIndicesLoaded { service, result });
+        }
+    });
+}
+
+impl SearchUI {
+    pub fn refresh_indices(&mut self, service: &LandoService, engine: SearchEngine, sender: &Sender<LandoCommandOutcome>) {
+        let Some(base_url) = search_base_url(service) else {
+            self.indices_error = Some("El servicio no publica una conexión externa; ¿está arrancada la app?".to_string());
+            return;
+        };
+        self.loading_indices = true;
+        self.indices_requested = true;
+        load_search_indices(sender.clone(), service.service.clone(), engine, base_url);
+    }
+
+    pub fn run_query(&mut self, service: &LandoService, engine: SearchEngine, sender: &Sender<LandoCommandOutcome>) {
+        let Some(base_url) = search_base_url(service) else {
+            self.set_local_error("El servicio no publica una conexión externa");
+            return;
+        };
+
+        let request = match engine {
+            SearchEngine::Solr => {
+                let Some(core) = self.selected.clone() else {
+                    self.set_local_error("Elige un core");
+                    return;
+                };
+                let q = if self.solr_q.trim().is_empty() { "*:*" } else { self.solr_q.trim() };
+                let rows = self.solr_rows.trim().parse::<u32>().unwrap_or(10);
+                let mut url = format!(
+                    "{}/solr/{}/select?wt=json&q={}&rows={}",
+                    base_url,
+                    encode_uri_component(&core),
+                    encode_uri_component(q),
+                    rows
+                );
+                for fq in self.solr_fq.lines().map(str::trim).filter(|fq| !fq.is_empty()) {
+                    url.push_str(&format!("&fq={}", encode_uri_component(fq)));
+                }
+                if !self.solr_fields.trim().is_empty() {
+                    url.push_str(&format!("&fl={}", encode_uri_component(self.solr_fields.trim())));
+                }
+                ("GET", url, None)
+            }
+            SearchEngine::Elasticsearch => {
+                // Se valida aquí para no mandar JSON roto y recibir un error críptico
+                if let Err(e) = serde_json::from_str::<Value>(&self.es_body) {
+                    self.set_local_error(&format!("El cuerpo no es JSON válido: {}", e));
+                    return;
+                }
+                let url = match &self.selected {
+                    Some(index) => format!("{}/{}/_search", base_url, encode_uri_component(index)),
+                    None => format!("{}/_search", base_url),
+                };
+                ("POST", url, Some(self.es_body.clone()))
+            }
+        };
+
+        let label = request_preview(request.0, &request.1, None);
+        self.running = true;
+        run_search_request(sender.clone(), service.service.clone(), label, request, None);
+    }
+
+    // Las acciones no se lanzan directamente: primero el diálogo de confirmación
+    pub fn request_action(&mut self, service: &LandoService, engine: SearchEngine, action: SearchAction) {
+        let Some(base_url) = search_base_url(service) else {
+            return;
+        };
+        let (method, url, body) = action_request(engine, &base_url, &action);
+        let preview = request_preview(method, &url, body.as_deref());
+        let dialog = match &action {
+            SearchAction::Reload(name) => ConfirmDialog::new(
+                &format!("Recargar {} {}", engine.index_noun(), name),
+                &format!("Se recargará el {} '{}' en {}.", engine.index_noun(), name, engine.label()),
+                &preview,
+                &service.service,
+            ),
+            SearchAction::DeleteAll(name) => ConfirmDialog::new(
+                "Borrar todos los documentos",
+                &format!("Se borrarán TODOS los documentos del {} '{}'. El esquema se conserva.", engine.index_noun(), name),
+                &preview,
+                &service.service,
+            )
+            .destructive(),
+        };
+        self.pending_action = Some((action, dialog));
+    }
+
+    pub fn run_action(&mut self, service: &LandoService, engine: SearchEngine, action: &SearchAction, sender: &Sender<LandoCommandOutcome>) {
+        let Some(base_url) = search_base_url(service) else {
+            return;
+        };
+        let request = action_request(engine, &base_url, action);
+        let label = request_preview(request.0, &request.1, None);
+        self.running = true;
+        self.loading_indices = true;
+        run_search_request(sender.clone(), service.service.clone(), label, request, Some((engine, base_url)));
+    }
+
+    pub fn handle_indices_loaded(&mut self, result: Result<Vec<SearchIndex>, String>) {
+        self.loading_indices = false;
+        match result {
+            Ok(indices) => {
+                if self.selected.as_ref().is_none_or(|s| !indices.iter().any(|i| &i.name == s)) {
+                    self.selected = indices.first().map(|i| i.name.clone());
+                }
+                self.indices = indices;
+                self.indices_error = None;
+            }
+            Err(e) => self.indices_error = Some(e),
+        }
+    }
+
+    pub fn handle_response(&mut self, label: String, result: Result<(u16, String), String>) {
+        self.running = false;
+        self.result = Some(match result {
+            Ok((status, body)) => SearchResult {
+                label,
+                status: Some(status),
+                json: serde_json::from_str(&body).ok(),
+                raw: body,
+                error: (status >= 400).then(|| format!("HTTP {}", status)),
+            },
+            Err(e) => SearchResult { label, status: None, json: None, raw: String::new(), error: Some(e) },
+        });
+    }
+
+    fn set_local_error(&mut self, message: &str) {
+        self.result = Some(SearchResult {
+            label: String::new(),
+            status: None,
+            json: None,
+            raw: String::new(),
+            error: Some(message.to_string()),
+        });
+    }
+}
@@ -0,0 +1,39 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::Duration;
+
+use eframe::egui;
+
+use crate::models::secret::SecretString;
+
+pub const CLIPBOARD_CLEAR_DELAY: Duration = Duration::from_secs(30);
+
+static CLEAR_CLIPBOARD: AtomicBool = AtomicBool::new(false);
+
+pub fn set_clear_clipboard(enabled: bool) {
+    CLEAR_CLIPBOARD.store(enabled, Ordering::Relaxed);
+}
+
+// Copia el valor real. Con el ajuste activo, pasado el plazo se vacía el
+// portapapeles, pero solo si sigue teniendo el secreto: si el usuario copió
+// otra cosa mientras tanto no se toca
+pub fn copy_secret(ctx: &egui::Context, secret: &SecretString) {
+    ctx.copy_text(secret.expose().to_string());
+    if !CLEAR_CLIPBOARD.load(Ordering::Relaxed) {
+        return;
+    }
+
+    let secret = secret.clone();
+    thread::spawn(move || {
+        thread::sleep(CLIPBOARD_CLEAR_DELAY);
+        let Ok(mut clipboard) = arboard::Clipboard::new() else {
+            log::warn!("No se pudo abrir el portapapeles para borrar la contraseña copiada");
+            return;
+        };
+        if clipboard.get_text().is_ok_and(|text| text == secret.expose()) {
+            if let Err(e) = clipboard.set_text(String::new()) {
+                log::warn!("No se pudo vaciar el portapapeles: {}", e);
+            }
+        }
+    });
+}
@@ -0,0 +1,343 @@
+use std::collections::HashMap;
+use std::ops::Range;
+
+// Plantillas con huecos al estilo de los snippets de los editores:
+// `${1:tabla}` es un hueco con texto por defecto y `${1}` repite el hueco 1.
+// Los rangos van en caracteres (no bytes), como los cursores de egui.
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TabStop {
+    pub index: u32,
+    pub ranges: Vec<Range<usize>>, // El primero es el que se edita; el resto lo copian
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParsedSnippet {
+    pub text: String,
+    pub stops: Vec<TabStop>, // Ordenados por índice
+}
+
+enum Token {
+    Text(String),
+    Stop(u32, Option<String>),
+}
+
+// `${N}` o `${N:defecto}` a partir de `start` (que apunta al `$`); None si no es un hueco
+fn parse_placeholder(chars: &[char], start: usize) -> Option<(u32, Option<String>, usize)> {
+    if chars.get(start + 1) != Some(&'{') {
+        return None;
+    }
+    let mut i = start + 2;
+    let digits_start = i;
+    while chars.get(i).is_some_and(|c| c.is_ascii_digit()) {
+        i += 1;
+    }
+    let index: u32 = chars[digits_start..i].iter().collect::<String>().parse().ok()?;
+    match chars.get(i) {
+        Some('}') => Some((index, None, i + 1)),
+        Some(':') => {
+            let default_start = i + 1;
+            let end = default_start + chars[default_start..].iter().position(|&c| c == '}')?;
+            Some((index, Some(chars[default_start..end].iter().collect()), end + 1))
+        }
+        _ => None,
+    }
+}
+
+pub fn parse_snippet(template: &str) -> ParsedSnippet {
+    let chars: Vec<char> = template.chars().collect();
+    let mut tokens = Vec::new();
+    let mut text = String::new();
+    let mut i = 0;
+    while i < chars.len() {
+        if chars[i] == '$' {
+            if let Some((index, default, next)) = parse_placeholder(&chars, i) {
+                tokens.push(Token::Text(std::mem::take(&mut text)));
+                tokens.push(Token::Stop(index, default));
+                i = next;
+                continue;
+            }
+        }
+        text.push(chars[i]);
+        i += 1;
+    }
+    tokens.push(Token::Text(text));
+
+    // Un `${1}` sin texto repite el de otra aparición del mismo hueco
+    let mut defaults: HashMap<u32, String> = HashMap::new();
+    for token in &tokens {
+        if let Token::Stop(index, Some(default)) = token {
+            defaults.entry(*index).or_insert_with(|| default.clone());
+        }
+    }
+
+    let mut output = String::new();
+    let mut length = 0;
+    let mut stops: Vec<TabStop> = Vec::new();
+    for token in tokens {
+        match token {
+            Token::Text(text) => {
+                length += text.chars().count();
+                output.push_str(&text);
+            }
+            Token::Stop(index, _) => {
+                let value = defaults.get(&index).cloned().unwrap_or_default();
+                let range = length..length + value.chars().count();
+                length = range.end;
+                output.push_str(&value);
+                match stops.iter_mut().find(|stop| stop.index == index) {
+                    Some(stop) => stop.ranges.push(range),
+                    None => stops.push(TabStop { index, ranges: vec![range] }),
+                }
+            }
+        }
+    }
+    stops.sort_by_key(|stop| stop.index);
+    ParsedSnippet { text: output, stops }
+}
+
+// Cambia el texto por defecto del primer hueco que tenga `placeholder`
+// (p. ej. `table_name` por la tabla seleccionada en el esquema)
+pub fn with_default(template: &str, placeholder: &str, value: &str) -> String {
+    let chars: Vec<char> = template.chars().collect();
+    let mut i = 0;
+    while i < chars.len() {
+        if chars[i] == '$' {
+            if let Some((index, Some(default), next)) = parse_placeholder(&chars, i) {
+                if default == placeholder {
+                    let before: String = chars[..i].iter().collect();
+                    let after: String = chars[next..].iter().collect();
+                    return format!("{}${{{}:{}}}{}", before, index, value, after);
+                }
+                i = next;
+                continue;
+            }
+        }
+        i += 1;
+    }
+    template.to_string()
+}
+
+// Nuevo rango de `range` tras sustituir `edit` (en el texto viejo) por
+// `inserted` caracteres. Con `active`, escribir justo al borde lo amplía.
+pub fn adjust_range(range: &Range<usize>, edit: &Range<usize>, inserted: usize, active: bool) -> Range<usize> {
+    let removed = edit.end - edit.start;
+    let shift = |position: usize| position + inserted - removed;
+
+    if edit.is_empty() && active && (edit.start == range.start || edit.start == range.end) {
+        // Escribir al principio o al final del hueco activo (o en uno vacío)
+        range.start..range.end + inserted
+    } else if edit.end <= range.start {
+        shift(range.start)..shift(range.end)
+    } else if edit.start >= range.end {
+        range.clone()
+    } else if edit.start >= range.start && edit.end <= range.end {
+        range.start..shift(range.end)
+    } else {
+        // Solapamiento parcial: el hueco pasa a cubrir también lo editado
+        let start = range.start.min(edit.start);
+        start..shift(range.end.max(edit.end))
+    }
+}
+
+// Tramo cambiado entre dos textos: prefijo y sufijo comunes fuera.
+// Devuelve el rango en el texto viejo y los caracteres que ocupa en el nuevo.
+pub fn changed_span(old: &str, new: &str) -> Option<(Range<usize>, usize)> {
+    let old: Vec<char> = old.chars().collect();
+    let new: Vec<char> = new.chars().collect();
+    if old == new {
+        return None;
+    }
+    let prefix = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+    let max_suffix = old.len().min(new.len()) - prefix;
+    let suffix = old.iter().rev().zip(new.iter().rev())
+        .take(max_suffix)
+        .take_while(|(a, b)| a == b)
+        .count();
+    Some((prefix..old.len() - suffix, new.len() - suffix - prefix))
+}
+
+fn byte_offset(text: &str, char_index: usize) -> usize {
+    text.char_indices().nth(char_index).map_or(text.len(), |(offset, _)| offset)
+}
+
+// Huecos vivos de una plantilla insertada en el editor
+#[derive(Debug, Clone)]
+pub struct SnippetSession {
+    pub stops: Vec<TabStop>,
+    pub current: usize,
+    pub text_len: usize, // Si el editor cambia por otra vía, los rangos ya no valen
+}
+
+impl SnippetSession {
+    // `offset`: caracteres del editor antes de la plantilla
+    pub fn new(snippet: &ParsedSnippet, offset: usize, text_len: usize) -> Option<Self> {
+        if snippet.stops.is_empty() {
+            return None;
+        }
+        let stops = snippet.stops.iter()
+            .map(|stop| TabStop {
+                index: stop.index,
+                ranges: stop.ranges.iter().map(|r| r.start + offset..r.end + offset).collect(),
+            })
+            .collect();
+        Some(Self { stops, current: 0, text_len })
+    }
+
+    pub fn current_range(&self) -> Option<Range<usize>> {
+        self.stops.get(self.current).and_then(|stop| stop.ranges.first().cloned())
+    }
+
+    // false al pasar del último hueco: la sesión termina
+    pub fn next(&mut self) -> bool {
+        self.current += 1;
+        self.current < self.stops.len()
+    }
+
+    pub fn previous(&mut self) {
+        self.current = self.current.saturating_sub(1);
+    }
+
+    fn adjust_all(&mut self, edit: &Range<usize>, inserted: usize, active_stop: Option<usize>) {
+        for (stop_index, stop) in self.stops.iter_mut().enumerate() {
+            let active = active_stop == Some(stop_index);
+            for range in &mut stop.ranges {
+                *range = adjust_range(range, edit, inserted, active);
+            }
+        }
+    }
+
+    // Tras una edición del usuario: mueve los rangos y copia el hueco activo
+    // en sus repeticiones. Devuelve el texto final si hubo que reescribirlo.
+    pub fn apply_edit(&mut self, old: &str, new: &str) -> Option<String> {
+        let Some((edit, inserted)) = changed_span(old, new) else {
+            return None;
+        };
+        self.adjust_all(&edit, inserted, Some(self.current));
+        self.text_len = new.chars().count();
+        self.sync_mirrors(new)
+    }
+
+    fn sync_mirrors(&mut self, text: &str) -> Option<String> {
+        let stop = self.stops.get(self.current)?;
+        let primary = stop.ranges.first()?.clone();
+        let value: String = text.chars().skip(primary.start).take(primary.len()).collect();
+        let value_len = primary.len();
+
+        let mut text = text.to_string();
+        let mut changed = false;
+        for mirror_index in 1..stop.ranges.len() {
+            let mirror = self.stops[self.current].ranges[mirror_index].clone();
+            let current: String = text.chars().skip(mirror.start).take(mirror.len()).collect();
+            if current == value {
+                continue;
+            }
+            let (start, end) = (byte_offset(&text, mirror.start), byte_offset(&text, mirror.end));
+            text.replace_range(start..end, &value);
+            self.adjust_all(&mirror, value_len, None);
+            // Una repetición vacía se habría desplazado en vez de crecer
+            self.stops[self.current].ranges[mirror_index] = mirror.start..mirror.start + value_len;
+            changed = true;
+        }
+
+        if changed {
+            self.text_len = text.chars().count();
+            Some(text)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session(template: &str) -> (SnippetSession, String) {
+        let snippet = parse_snippet(template);
+        let text = snippet.text.clone();
+        (SnippetSession::new(&snippet, 0, text.chars().count()).unwrap(), text)
+    }
+
+    // Reemplaza el rango (en caracteres) como lo haría el editor
+    fn edit(text: &str, range: Range<usize>, inserted: &str) -> String {
+        let mut chars: Vec<char> = text.chars().collect();
+        chars.splice(range, inserted.chars());
+        chars.into_iter().collect()
+    }
+
+    #[test]
+    fn placeholders_and_mirrors() {
+        let snippet = parse_snippet("SELECT ${2:*} FROM ${1:tabla} WHERE ${1}.id = ${3}; -- $5 ${x}");
+        assert_eq!(snippet.text, "SELECT * FROM tabla WHERE tabla.id = ; -- $5 ${x}");
+        assert_eq!(snippet.stops, vec![
+            TabStop { index: 1, ranges: vec![14..19, 26..31] },
+            TabStop { index: 2, ranges: vec![7..8] },
+            TabStop { index: 3, ranges: vec![37..37] },
+        ]);
+        // Los rangos van en caracteres
+        assert_eq!(parse_snippet("-- ñandú ${1:año}").stops[0].ranges, vec![9..12]);
+    }
+
+    #[test]
+    fn defaults_can_be_replaced() {
+        let template = "SELECT * FROM ${1:table_name} LIMIT ${2:100};";
+        assert_eq!(with_default(template, "table_name", "users"), "SELECT * FROM ${1:users} LIMIT ${2:100};");
+        assert_eq!(with_default(template, "schema", "public"), template);
+    }
+
+    #[test]
+    fn ranges_follow_edits() {
+        let range = 10..15;
+        // Antes: se desplaza; después: no cambia
+        assert_eq!(adjust_range(&range, &(2..4), 5, false), 13..18);
+        assert_eq!(adjust_range(&range, &(16..16), 3, false), 10..15);
+        // Dentro: crece o encoge
+        assert_eq!(adjust_range(&range, &(11..13), 0, false), 10..13);
+        assert_eq!(adjust_range(&range, &(12..12), 4, false), 10..19);
+        // En el borde solo crece el hueco activo
+        assert_eq!(adjust_range(&range, &(15..15), 2, true), 10..17);
+        assert_eq!(adjust_range(&range, &(10..10), 2, true), 10..17);
+        assert_eq!(adjust_range(&range, &(10..10), 2, false), 12..17);
+        assert_eq!(adjust_range(&(5..5), &(5..5), 3, true), 5..8);
+        // Solapamiento parcial
+        assert_eq!(adjust_range(&range, &(8..12), 1, false), 8..12);
+    }
+
+    #[test]
+    fn changed_spans() {
+        assert_eq!(changed_span("abc", "abc"), None);
+        assert_eq!(changed_span("SELECT  FROM", "SELECT id FROM"), Some((7..7, 2)));
+        assert_eq!(changed_span("aaa", "aa"), Some((2..3, 0)));
+        assert_eq!(changed_span("año", "años"), Some((3..3, 1)));
+    }
+
+    #[test]
+    fn typing_updates_the_mirrors() {
+        let (mut session, text) = session("SELECT * FROM ${1:t} JOIN x ON x.id = ${1}.x_id${2}");
+        assert_eq!(session.current_range(), Some(14..15));
+
+        // Se selecciona `t` y se escribe `users`
+        let typed = edit(&text, 14..15, "users");
+        let synced = session.apply_edit(&text, &typed).unwrap();
+        assert_eq!(synced, "SELECT * FROM users JOIN x ON x.id = users.x_id");
+        assert_eq!(session.stops[0].ranges, vec![14..19, 37..42]);
+        assert_eq!(session.text_len, synced.chars().count());
+
+        // El hueco final vacío se movió con el texto y crece al escribir en él
+        assert!(session.next());
+        assert_eq!(session.current_range(), Some(47..47));
+        let typed = edit(&synced, 47..47, ";");
+        assert_eq!(session.apply_edit(&synced, &typed), None);
+        assert_eq!(session.current_range(), Some(47..48));
+        assert!(!session.next());
+    }
+
+    #[test]
+    fn snippets_without_stops_have_no_session() {
+        assert!(SnippetSession::new(&parse_snippet("SHOW TABLES;"), 0, 12).is_none());
+        let (mut session, _) = session("${1:a}${2:b}");
+        session.previous();
+        assert_eq!(session.current, 0);
+    }
+}
@@ -0,0 +1,507 @@
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+
+// Ajustes del linter: reglas desactivadas y umbral de `select-estrella`
+pub const SQL_LINT_FILE: &str = "sql_lint.json";
+
+// Pieza léxica de SQL. Cadenas y comentarios no se conservan: para las reglas
+// basta saber que están ahí y que no esconden palabras clave.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SqlToken {
+    Word(String), // Palabra clave, identificador o número, en minúsculas
+    QuotedIdent(String), // `tabla` o "tabla"
+    Str,
+    Symbol(char),
+    OpenParen,
+    CloseParen,
+    Semicolon,
+}
+
+// Cómo se escapan las comillas dentro de '...': en MySQL/MariaDB `\'` no cierra
+// la cadena; en PostgreSQL y SQLite la barra es un carácter más (`'C:\'` es
+// válido) salvo en las cadenas `E'...'` de PostgreSQL
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SqlDialect {
+    MySql,
+    Standard,
+}
+
+impl SqlDialect {
+    pub fn for_type(db_type: &str) -> Self {
+        match db_type.to_lowercase().as_str() {
+            "mysql" | "mariadb" => Self::MySql,
+            _ => Self::Standard,
+        }
+    }
+}
+
+// Trozo del texto original con su token; espacios y comentarios no llevan
+// token. El linter usa los tokens y el formateador reconstruye el texto.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Lexeme<'a> {
+    pub token: Option<SqlToken>,
+    pub text: &'a str,
+}
+
+// Trozos del texto y, si los hay, cadena o comentario sin cerrar. Lo que queda
+// desde lo que no se cierra va en un último trozo sin token.
+pub fn lex_sql(sql: &str, dialect: SqlDialect) -> (Vec<Lexeme<'_>>, Option<&'static str>) {
+    let chars: Vec<(usize, char)> = sql.char_indices().collect();
+    let offset = |index: usize| chars.get(index).map(|(offset, _)| *offset).unwrap_or(sql.len());
+    let mut lexemes = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let start = i;
+        let c = chars[i].1;
+        let next = chars.get(i + 1).map(|(_, ch)| *ch);
+        let token = match c {
+            c if c.is_whitespace() => {
+                while i < chars.len() && chars[i].1.is_whitespace() {
+                    i += 1;
+                }
+                None
+            }
+            '-' if next == Some('-') => {
+                while i < chars.len() && chars[i].1 != '\n' {
+                    i += 1;
+                }
+                None
+            }
+            '/' if next == Some('*') => {
+                let Some(end) = (i + 2..chars.len().saturating_sub(1)).find(|&j| chars[j].1 == '*' && chars[j + 1].1 == '/') else {
+                    lexemes.push(Lexeme { token: None, text: &sql[offset(start)..] });
+                    return (lexemes, Some("comentario /* sin cerrar"));
+                };
+                i = end + 2;
+                None
+            }
+            '\'' => {
+                let escape_string = lexemes.last().is_some_and(|l| l.token == Some(SqlToken::Word("e".to_string())));
+                let backslash_escapes = dialect == SqlDialect::MySql || escape_string;
+                // `''` no cierra la cadena; `\'` tampoco si el dialecto escapa con barra
+                i += 1;
+                loop {
+                    match chars.get(i).map(|(_, ch)| *ch) {
+                        None => {
+                            lexemes.push(Lexeme { token: None, text: &sql[offset(start)..] });
+                            return (lexemes, Some("comilla ' sin cerrar"));
+                        }
+                        Some('\\') if backslash_escapes => i += 2,
+                        Some('\'') if chars.get(i + 1).map(|(_, ch)| *ch) == Some('\'') => i += 2,
+                        Some('\'') => break,
+                        Some(_) => i += 1,
+                    }
+                }
+                i += 1;
+                Some(SqlToken::Str)
+            }
+            '"' | '`' => {
+                let Some(len) = chars[i + 1..].iter().position(|&(_, ch)| ch == c) else {
+                    lexemes.push(Lexeme { token: None, text: &sql[offset(start)..] });
+                    return (lexemes, Some(if c == '"' { "comilla \" sin cerrar" } else { "comilla ` sin cerrar" }));
+                };
+                i += len + 2;
+                Some(SqlToken::QuotedIdent(sql[offset(start + 1)..offset(start + 1 + len)].to_lowercase()))
+            }
+            '(' => {
+                i += 1;
+                Some(SqlToken::OpenParen)
+            }
+            ')' => {
+                i += 1;
+                Some(SqlToken::CloseParen)
+            }
+            ';' => {
+                i += 1;
+                Some(SqlToken::Semicolon)
+            }
+            c if c.is_alphanumeric() || c == '_' || c == '$' => {
+                while i < chars.len() && (chars[i].1.is_alphanumeric() || matches!(chars[i].1, '_' | '$' | '.')) {
+                    i += 1;
+                }
+                Some(SqlToken::Word(sql[offset(start)..offset(i)].to_lowercase()))
+            }
+            c => {
+                i += 1;
+                Some(SqlToken::Symbol(c))
+            }
+        };
+        lexemes.push(Lexeme { token, text: &sql[offset(start)..offset(i)] });
+    }
+    (lexemes, None)
+}
+
+// Tokens y, si los hay, cadena o comentario sin cerrar
+pub fn tokenize_sql(sql: &str, dialect: SqlDialect) -> (Vec<SqlToken>, Option<&'static str>) {
+    let (lexemes, unterminated) = lex_sql(sql, dialect);
+    (lexemes.into_iter().filter_map(|lexeme| lexeme.token).collect(), unterminated)
+}
+
+// Sentencias con contenido, separadas por `;`
+pub fn statement_count(sql: &str, dialect: SqlDialect) -> usize {
+    let (tokens, _) = tokenize_sql(sql, dialect);
+    tokens.split(|t| t == &SqlToken::Semicolon).filter(|s| !s.is_empty()).count()
+}
+
+// Formateo básico: tras cada coma de primer nivel se salta de línea y FROM,
+// WHERE, GROUP BY y ORDER BY empiezan línea. Cadenas, identificadores entre
+// comillas y comentarios quedan tal cual.
+pub fn format_sql(sql: &str, dialect: SqlDialect) -> String {
+    let (lexemes, _) = lex_sql(sql, dialect);
+    let word = |index: usize| match lexemes.get(index).and_then(|l| l.token.as_ref()) {
+        Some(SqlToken::Word(word)) => Some(word.as_str()),
+        _ => None,
+    };
+    let starts_clause = |index: usize| match word(index) {
+        Some("from" | "where") => true,
+        Some("group" | "order") => {
+            let after = if lexemes.get(index + 1).is_some_and(|l| l.token.is_none()) { index + 2 } else { index + 1 };
+            word(after) == Some("by")
+        }
+        _ => false,
+    };
+
+    let mut formatted = String::with_capacity(sql.len());
+    let mut depth = 0usize;
+    let mut after_comma = false;
+    for (index, lexeme) in lexemes.iter().enumerate() {
+        let is_space = lexeme.token.is_none() && lexeme.text.trim().is_empty();
+        if is_space {
+            if !after_comma {
+                formatted.push_str(if starts_clause(index + 1) { "\n" } else { lexeme.text });
+            }
+            continue;
+        }
+        after_comma = false;
+        formatted.push_str(lexeme.text);
+        match lexeme.token {
+            Some(SqlToken::OpenParen) => depth += 1,
+            Some(SqlToken::CloseParen) => depth = depth.saturating_sub(1),
+            Some(SqlToken::Symbol(',')) if depth == 0 => {
+                formatted.push_str("\n    ");
+                after_comma = true;
+            }
+            _ => {}
+        }
+    }
+    formatted
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum LintRule {
+    MissingWhere,
+    DropTruncate,
+    SelectStar,
+    MissingLimit,
+    Unbalanced,
+}
+
+impl LintRule {
+    pub const ALL: [LintRule; 5] = [
+        LintRule::MissingWhere,
+        LintRule::DropTruncate,
+        LintRule::SelectStar,
+        LintRule::MissingLimit,
+        LintRule::Unbalanced,
+    ];
+
+    // Nombre que se muestra junto al aviso, para saber qué desactivar
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::MissingWhere => "sin-where",
+            Self::DropTruncate => "drop-truncate",
+            Self::SelectStar => "select-estrella",
+            Self::MissingLimit => "sin-limit",
+            Self::Unbalanced => "desbalanceado",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Self::MissingWhere => "UPDATE/DELETE sin WHERE (bloquea)",
+            Self::DropTruncate => "DROP y TRUNCATE (bloquea)",
+            Self::SelectStar => "SELECT * sobre tablas grandes",
+            Self::MissingLimit => "SELECT sin LIMIT",
+            Self::Unbalanced => "Comillas o paréntesis sin cerrar",
+        }
+    }
+
+    // Las que obligan a pulsar "ejecutar de todos modos"
+    pub fn is_blocking(&self) -> bool {
+        matches!(self, Self::MissingWhere | Self::DropTruncate)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LintSettings {
+    pub disabled: HashSet<LintRule>,
+    pub select_star_min_rows: i64,
+}
+
+impl Default for LintSettings {
+    fn default() -> Self {
+        Self { disabled: HashSet::new(), select_star_min_rows: 10_000 }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LintWarning {
+    pub rule: LintRule,
+    pub message: String,
+}
+
+// Palabras de una sentencia al nivel de paréntesis 0, es decir, sin subconsultas
+fn top_level_words(statement: &[SqlToken]) -> Vec<(usize, &str)> {
+    let mut depth = 0usize;
+    let mut words = Vec::new();
+    for (index, token) in statement.iter().enumerate() {
+        match token {
+            SqlToken::OpenParen => depth += 1,
+            SqlToken::CloseParen => depth = depth.saturating_sub(1),
+            SqlToken::Word(word) if depth == 0 => words.push((index, word.as_str())),
+            _ => {}
+        }
+    }
+    words
+}
+
+// Verbo principal: el primero, o el que sigue a los CTE de un `WITH`
+fn main_verb<'a>(words: &[(usize, &'a str)]) -> Option<&'a str> {
+    const VERBS: &[&str] = &["select", "insert", "update", "delete", "drop", "truncate", "replace"];
+    let first = words.first()?.1;
+    if first == "with" {
+        words.iter().skip(1).map(|(_, w)| *w).find(|w| VERBS.contains(w))
+    } else {
+        Some(first)
+    }
+}
+
+// `esquema.tabla` -> `tabla`
+fn table_name(token: &SqlToken) -> Option<String> {
+    let name = match token {
+        SqlToken::Word(word) | SqlToken::QuotedIdent(word) => word,
+        _ => return None,
+    };
+    Some(name.rsplit('.').next().unwrap_or(name).to_string())
+}
+
+fn lint_statement(
+    statement: &[SqlToken],
+    settings: &LintSettings,
+    row_count: &dyn Fn(&str) -> Option<i64>,
+    max_rows: usize,
+    warnings: &mut Vec<LintWarning>,
+) {
+    let words = top_level_words(statement);
+    let has_word = |target: &str| words.iter().any(|(_, w)| *w == target);
+    let Some(verb) = main_verb(&words) else {
+        return;
+    };
+
+    match verb {
+        "update" | "delete" if !has_word("where") => warnings.push(LintWarning {
+            rule: LintRule::MissingWhere,
+            message: format!("{} sin WHERE: afectará a todas las filas", verb.to_uppercase()),
+        }),
+        "drop" | "truncate" => warnings.push(LintWarning {
+            rule: LintRule::DropTruncate,
+            message: format!("{} borra datos de forma irreversible", verb.to_uppercase()),
+        }),
+        "select" => {
+            let Some(&(from_index, _)) = words.iter().find(|(_, w)| *w == "from") else {
+                return; // `SELECT 1`, `SELECT now()`...
+            };
+            let table = statement.get(from_index + 1).and_then(table_name);
+
+            // `*` de la lista de columnas, no el de un `count(*)`
+            let select_index = words.iter().find(|(_, w)| *w == "select").map(|(i, _)| *i).unwrap_or(0);
+            let columns = statement.get(select_index + 1..from_index).unwrap_or(&[]);
+            let mut depth = 0usize;
+            let star = columns.iter().any(|token| {
+                match token {
+                    SqlToken::OpenParen => depth += 1,
+                    SqlToken::CloseParen => depth = depth.saturating_sub(1),
+                    _ => {}
+                }
+                depth == 0 && token == &SqlToken::Symbol('*')
+            });
+            if star {
+                if let Some(table) = &table {
+                    if let Some(rows) = row_count(table).filter(|rows| *rows >= settings.select_star_min_rows) {
+                        warnings.push(LintWarning {
+                            rule: LintRule::SelectStar,
+                            message: format!("SELECT * sobre '{}' (~{} filas): elige las columnas", table, rows),
+                        });
+                    }
+                }
+            }
+
+            let limited = has_word("limit") || has_word("fetch") || (has_word("into") && (has_word("outfile") || has_word("dumpfile")));
+            if max_rows > 0 && !limited {
+                warnings.push(LintWarning {
+                    rule: LintRule::MissingLimit,
+                    message: format!("SELECT sin LIMIT: puede devolver más de {} filas", max_rows),
+                });
+            }
+        }
+        _ => {}
+    }
+}
+
+// Avisos de todas las sentencias del texto, ya filtrados por las reglas activas
+pub fn lint_sql(
+    sql: &str,
+    dialect: SqlDialect,
+    settings: &LintSettings,
+    row_count: &dyn Fn(&str) -> Option<i64>,
+    max_rows: usize,
+) -> Vec<LintWarning> {
+    let (tokens, unterminated) = tokenize_sql(sql, dialect);
+    let mut warnings = Vec::new();
+
+    if let Some(what) = unterminated {
+        warnings.push(LintWarning { rule: LintRule::Unbalanced, message: what.to_string() });
+    }
+    let mut depth = 0i64;
+    for token in &tokens {
+        match token {
+            SqlToken::OpenParen => depth += 1,
+            SqlToken::CloseParen => depth -= 1,
+            _ => {}
+        }
+        if depth < 0 {
+            break;
+        }
+    }
+    if depth != 0 {
+        let message = if depth > 0 { "falta cerrar un paréntesis" } else { "sobra un paréntesis de cierre" };
+        warnings.push(LintWarning { rule: LintRule::Unbalanced, message: message.to_string() });
+    }
+
+    for statement in tokens.split(|t| t == &SqlToken::Semicolon).filter(|s| !s.is_empty()) {
+        lint_statement(statement, settings, row_count, max_rows, &mut warnings);
+    }
+
+    warnings.retain(|w| !settings.disabled.contains(&w.rule));
+    warnings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(sql: &str) -> Vec<LintRule> {
+        rules_with(sql, SqlDialect::MySql, &LintSettings::default(), 0)
+    }
+
+    fn rules_with(sql: &str, dialect: SqlDialect, settings: &LintSettings, max_rows: usize) -> Vec<LintRule> {
+        let row_count = |table: &str| (table == "orders").then_some(50_000);
+        lint_sql(sql, dialect, settings, &row_count, max_rows).into_iter().map(|w| w.rule).collect()
+    }
+
+    #[test]
+    fn missing_where_on_update_and_delete() {
+        assert_eq!(rules("UPDATE users SET active = 0"), vec![LintRule::MissingWhere]);
+        assert_eq!(rules("delete from users;"), vec![LintRule::MissingWhere]);
+        assert!(rules("DELETE FROM users WHERE id = 1").is_empty());
+        assert!(rules("UPDATE users SET name = 'where' WHERE id = 1").is_empty());
+    }
+
+    #[test]
+    fn where_inside_a_subquery_does_not_count_for_the_outer_delete() {
+        let sql = "DELETE FROM users WHERE id IN (SELECT user_id FROM bans WHERE until > now())";
+        assert!(rules(sql).is_empty());
+
+        let sql = "DELETE FROM users USING (SELECT id FROM bans WHERE until > now()) AS b";
+        assert_eq!(rules(sql), vec![LintRule::MissingWhere]);
+    }
+
+    #[test]
+    fn where_in_a_string_or_comment_does_not_count() {
+        assert_eq!(rules("UPDATE users SET note = 'no where here'"), vec![LintRule::MissingWhere]);
+        assert_eq!(rules("DELETE FROM users -- WHERE id = 1"), vec![LintRule::MissingWhere]);
+        assert_eq!(rules("DELETE FROM users /* WHERE id = 1 */"), vec![LintRule::MissingWhere]);
+    }
+
+    #[test]
+    fn drop_and_truncate() {
+        assert_eq!(rules("DROP TABLE users"), vec![LintRule::DropTruncate]);
+        assert_eq!(rules("truncate users"), vec![LintRule::DropTruncate]);
+        assert!(rules("SELECT 'drop table users'").is_empty());
+    }
+
+    #[test]
+    fn select_star_only_on_large_known_tables() {
+        assert_eq!(rules("SELECT * FROM orders"), vec![LintRule::SelectStar]);
+        assert_eq!(rules("SELECT o.* FROM shop.orders o"), vec![LintRule::SelectStar]);
+        assert_eq!(rules("SELECT * FROM shop.orders"), vec![LintRule::SelectStar]);
+        assert!(rules("SELECT * FROM users").is_empty());
+        assert!(rules("SELECT count(*) FROM orders").is_empty());
+    }
+
+    #[test]
+    fn missing_limit_only_with_max_rows() {
+        let settings = LintSettings::default();
+        assert_eq!(rules_with("SELECT id FROM users", SqlDialect::MySql, &settings, 1000), vec![LintRule::MissingLimit]);
+        assert!(rules_with("SELECT id FROM users LIMIT 10", SqlDialect::MySql, &settings, 1000).is_empty());
+        assert!(rules_with("SELECT id FROM users FETCH FIRST 10 ROWS ONLY", SqlDialect::Standard, &settings, 1000).is_empty());
+        assert!(rules_with("SELECT id FROM users", SqlDialect::MySql, &settings, 0).is_empty());
+        assert!(rules_with("SELECT now()", SqlDialect::MySql, &settings, 1000).is_empty());
+    }
+
+    #[test]
+    fn unbalanced_quotes_and_parentheses() {
+        assert_eq!(rules("SELECT count(id FROM users"), vec![LintRule::Unbalanced]);
+        assert_eq!(rules("SELECT id) FROM users"), vec![LintRule::Unbalanced]);
+        assert_eq!(rules("SELECT 'abc FROM users"), vec![LintRule::Unbalanced]);
+        assert_eq!(rules("SELECT \"abc FROM users"), vec![LintRule::Unbalanced]);
+        assert_eq!(rules("SELECT 1 /* sin cerrar"), vec![LintRule::Unbalanced]);
+        assert!(rules("SELECT 'it''s' FROM users").is_empty());
+    }
+
+    #[test]
+    fn backslash_escapes_depend_on_the_dialect() {
+        let settings = LintSettings::default();
+        // En MySQL `\'` sigue dentro de la cadena
+        assert!(rules_with(r"SELECT 'it\'s'", SqlDialect::MySql, &settings, 0).is_empty());
+        // En PostgreSQL la barra es literal y la cadena termina en la comilla
+        assert!(rules_with(r"SELECT 'C:\' AS path", SqlDialect::Standard, &settings, 0).is_empty());
+        assert_eq!(rules_with(r"SELECT 'C:\' AS path", SqlDialect::MySql, &settings, 0), vec![LintRule::Unbalanced]);
+        // salvo en las cadenas E'...'
+        assert!(rules_with(r"SELECT E'it\'s'", SqlDialect::Standard, &settings, 0).is_empty());
+    }
+
+    #[test]
+    fn disabled_rules_are_filtered() {
+        let settings = LintSettings { disabled: HashSet::from([LintRule::MissingWhere]), ..Default::default() };
+        assert!(rules_with("DELETE FROM users", SqlDialect::MySql, &settings, 0).is_empty());
+    }
+
+    #[test]
+    fn each_statement_is_linted() {
+        assert_eq!(rules("DELETE FROM users WHERE id = 1; DELETE FROM bans"), vec![LintRule::MissingWhere]);
+        assert_eq!(statement_count("SELECT 1; SELECT 2;", SqlDialect::MySql), 2);
+        assert_eq!(statement_count("SELECT ';'", SqlDialect::MySql), 1);
+    }
+
+    #[test]
+    fn format_breaks_columns_and_clauses() {
+        let formatted = format_sql("SELECT id, name FROM users WHERE active = 1 ORDER BY name", SqlDialect::MySql);
+        assert_eq!(formatted, "SELECT id,\n    name\nFROM users\nWHERE active = 1\nORDER BY name");
+        assert_eq!(format_sql(&formatted, SqlDialect::MySql), formatted);
+    }
+
+    #[test]
+    fn format_leaves_strings_comments_and_nested_commas_alone() {
+        let sql = "select concat(a, b) from t where note = 'x, from y' -- a, b";
+        assert_eq!(
+            format_sql(sql, SqlDialect::MySql),
+            "select concat(a, b)\nfrom t\nwhere note = 'x, from y' -- a, b",
+        );
+        let sql = r"SELECT 'C:\', id FROM t";
+        assert_eq!(format_sql(sql, SqlDialect::Standard), "SELECT 'C:\\',\n    id\nFROM t");
+    }
+}
@@ -0,0 +1,49 @@
+use std::sync::mpsc::Sender;
+use std::thread;
+
+use crate::core::commands::to_args;
+use crate::core::runner::{system_runner, CommandRunner};
+use crate::models::commands::LandoCommandOutcome;
+use crate::models::status::ToolVersions;
+
+// Primera línea no vacía de la salida, si el comando terminó bien
+fn first_line(runner: &dyn CommandRunner, program: &str, args: &[&str]) -> Option<String> {
+    runner.run(program, &to_args(args), None)
+        .ok()
+        .filter(|output| output.success)
+        .and_then(|output| output.stdout_lossy().lines().map(str::trim).find(|l| !l.is_empty()).map(String::from))
+}
+
+// `docker version` con formato del servidor falla si el daemon no responde;
+// en ese caso se muestra al menos la versión del cliente. `lando version`
+// tarda en arrancar, así que en las comprobaciones periódicas se reutiliza.
+pub fn detect_tool_versions(runner: &dyn CommandRunner, known_lando: Option<String>) -> ToolVersions {
+    let lando = known_lando.or_else(|| first_line(runner, "lando", &["version"]));
+    let server = first_line(runner, "docker", &["version", "--format", "{{.Server.Version}}"]);
+    let docker_running = server.is_some();
+    let docker = server.or_else(|| {
+        first_line(runner, "docker", &["--version"])
+            .map(|text| text.trim_start_matches("Docker version ").split(',').next().unwrap_or_default().to_string())
+    });
+    ToolVersions { lando, docker, docker_running }
+}
+
+pub fn check_tool_versions(sender: Sender<LandoCommandOutcome>, known_lando: Option<String>) {
+    thread::spawn(move || {
+        let versions = detect_tool_versions(system_runner().as_ref(), known_lando);
+        let _ = sender.send(LandoCommandOutcome::ToolVersions(versions));
+    });
+}
+
+// Recorta por el centro, que es donde las rutas tienen menos información:
+// `/home/user/…/proyectos/mi-app`
+pub fn elide_middle(text: &str, max_chars: usize) -> String {
+    let count = text.chars().count();
+    if count <= max_chars || max_chars < 3 {
+        return text.to_string();
+    }
+    let keep = max_chars - 1;
+    let head: String = text.chars().take(keep / 2).collect();
+    let tail: String = text.chars().skip(count - (keep - keep / 2)).collect();
+    format!("{}…{}", head, tail)
+}
@@ -0,0 +1,34 @@
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+// Ficheros JSON propios de la app dentro del directorio de datos de eframe
+#[cfg(not(test))]
+fn app_file_path(name: &str) -> Option<PathBuf> {
+    eframe::storage_dir("Lando GUI").map(|dir| dir.join(name))
+}
+
+// Los tests no tocan los datos del usuario
+#[cfg(test)]
+fn app_file_path(name: &str) -> Option<PathBuf> {
+    Some(std::env::temp_dir().join(format!("lando_gui_tests_{}", std::process::id())).join(name))
+}
+
+// Si el fichero no existe o no se puede leer se empieza de cero
+pub fn load_app_file<T: DeserializeOwned + Default>(name: &str) -> T {
+    app_file_path(name)
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+pub fn save_app_file<T: Serialize>(name: &str, value: &T) -> io::Result<()> {
+    let path = app_file_path(name)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "sin directorio de datos"))?;
+    fs::create_dir_all(path.parent().unwrap_or(&path))?;
+    let content = serde_json::to_string_pretty(value).map_err(io::Error::other)?;
+    fs::write(&path, content)
+}
@@ -0,0 +1,290 @@
+use std::path::PathBuf;
+use std::sync::mpsc::Sender;
+use crate::core::commands::*;
+use crate::models::commands::LandoCommandOutcome;
+use crate::models::lando::LandoService;
+use crate::ui::confirm::ConfirmDialog;
+use crate::ui::database::{ColumnInfo, DatabaseUI, InsertField, InsertValueMode, PendingRequest, ToolCommand};
+
+pub const NUMERIC_TYPES: &[&str] = &["int", "serial", "decimal", "numeric", "float", "double", "real"];
+
+// Comprobación previa de un campo del formulario de inserción según su tipo.
+// No sustituye a la BD: solo evita errores obvios antes de generar el INSERT.
+pub fn insert_field_error(field: &InsertField) -> Option<String> {
+    if field.mode != InsertValueMode::Value {
+        return None;
+    }
+    let data_type = field.data_type.to_lowercase();
+    let value = field.value.trim();
+    let numeric = NUMERIC_TYPES.iter().any(|t| data_type.contains(t)) && !data_type.contains("interval");
+    if numeric && value.parse::<f64>().is_err() {
+        return Some(format!("'{}' espera un número ({}); usa NULL o DEFAULT si no tiene valor", field.column, field.data_type));
+    }
+    None
+}
+
+// Literal de cadena según el dialecto. MySQL trata `\` como escape dentro de
+// comillas (salvo NO_BACKSLASH_ESCAPES); PostgreSQL y SQLite no.
+pub fn string_literal(db_type: &str, value: &str) -> String {
+    match db_type.to_lowercase().as_str() {
+        "mysql" | "mariadb" => format!("'{}'", value.replace('\\', "\\\\").replace('\'', "''")),
+        _ => format!("'{}'", value.replace('\'', "''")),
+    }
+}
+
+// Edición de filas en el navegador de tablas: las celdas modificadas, las filas
+// marcadas para borrar y las inserciones se traducen a SQL que solo se ejecuta
+// al pulsar "Ejecutar cambios".
+impl DatabaseUI {
+    pub fn sql_string_literal(&self, db_type: &str, value: &str) -> String {
+        string_literal(db_type, value)
+    }
+
+    pub fn sql_value(&self, db_type: &str, value: Option<&str>) -> String {
+        match value {
+            Some(v) => self.sql_string_literal(db_type, v),
+            None => "NULL".to_string(),
+        }
+    }
+
+    // mysql en modo batch imprime NULL literalmente y psql deja la celda vacía
+    pub fn original_cell_value(&self, row: usize, column: usize) -> Option<String> {
+        let value = self.table_rows.as_ref()?.rows.get(row)?.get(column)?;
+        if value == "NULL" { None } else { Some(value.clone()) }
+    }
+
+    pub fn current_table_columns(&self) -> &[ColumnInfo] {
+        self.tables.iter()
+            .find(|t| t.name == self.current_table)
+            .map(|t| t.columns.as_slice())
+            .unwrap_or(&[])
+    }
+
+    pub fn primary_key_columns(&self) -> Vec<String> {
+        self.current_table_columns().iter()
+            .filter(|c| c.is_primary_key)
+            .map(|c| c.name.clone())
+            .collect()
+    }
+
+    // Motivo por el que no se pueden generar UPDATE/DELETE, o None si se puede
+    pub fn edit_block_reason(&self) -> Option<String> {
+        if self.current_table_columns().is_empty() {
+            return Some("Cargando columnas de la tabla...".to_string());
+        }
+        let pk = self.primary_key_columns();
+        if pk.is_empty() {
+            return Some(format!(
+                "La tabla '{}' no tiene clave primaria: no se puede identificar la fila a modificar o borrar",
+                self.current_table
+            ));
+        }
+        let rows = self.table_rows.as_ref()?;
+        if let Some(missing) = pk.iter().find(|c| rows.column_index(c).is_none()) {
+            return Some(format!("La columna de clave primaria '{}' no está en el resultado", missing));
+        }
+        None
+    }
+
+    fn row_where_clause(&self, db_type: &str, row: usize) -> Result<String, String> {
+        let rows = self.table_rows.as_ref().ok_or("No hay datos cargados")?;
+        let conditions = self.primary_key_columns().iter()
+            .map(|pk| {
+                let index = rows.column_index(pk)
+                    .ok_or_else(|| format!("Falta la columna de clave primaria '{}'", pk))?;
+                let value = self.original_cell_value(row, index)
+                    .ok_or_else(|| format!("La fila {} tiene NULL en la clave primaria '{}'", row + 1, pk))?;
+                Ok(format!("{} = {}", self.quote_identifier(db_type, pk), self.sql_string_literal(db_type, &value)))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        Ok(conditions.join(" AND "))
+    }
+
+    // Sentencias en el orden en que se ejecutarán: UPDATE, DELETE e INSERT
+    pub fn build_table_edit_statements(&self, db_type: &str) -> Result<Vec<String>, String> {
+        let table = self.quote_identifier(db_type, &self.current_table);
+        let edit = &self.table_edit;
+        let mut statements = Vec::new();
+
+        let needs_pk = !edit.edited_cells.is_empty() || !edit.deleted_rows.is_empty();
+        if needs_pk {
+            if let Some(reason) = self.edit_block_reason() {
+                return Err(reason);
+            }
+        }
+
+        if !edit.edited_cells.is_empty() {
+            let rows = self.table_rows.as_ref().ok_or("No hay datos cargados")?;
+            let mut edited_rows: Vec<usize> = edit.edited_cells.keys().map(|(row, _)| *row).collect();
+            edited_rows.sort_unstable();
+            edited_rows.dedup();
+
+            for row in edited_rows.into_iter().filter(|r| !edit.deleted_rows.contains(r)) {
+                let mut cells: Vec<(usize, &Option<String>)> = edit.edited_cells.iter()
+                    .filter(|((r, _), _)| *r == row)
+                    .map(|((_, c), v)| (*c, v))
+                    .collect();
+                cells.sort_by_key(|(c, _)| *c);
+
+                let assignments = cells.iter()
+                    .map(|(c, v)| format!(
+                        "{} = {}",
+                        self.quote_identifier(db_type, &rows.columns[*c]),
+                        self.sql_value(db_type, v.as_deref())
+                    ))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                statements.push(format!("UPDATE {} SET {} WHERE {};", table, assignments, self.row_where_clause(db_type, row)?));
+            }
+        }
+
+        for row in &edit.deleted_rows {
+            statements.push(format!("DELETE FROM {} WHERE {};", table, self.row_where_clause(db_type, *row)?));
+        }
+
+        for fields in &edit.pending_inserts {
+            statements.push(self.build_insert_statement(db_type, fields));
+        }
+
+        Ok(statements)
+    }
+
+    // Las columnas en modo DEFAULT se omiten para que la BD aplique su valor
+    pub fn build_insert_statement(&self, db_type: &str, fields: &[InsertField]) -> String {
+        let table = self.quote_identifier(db_type, &self.current_table);
+        let explicit: Vec<&InsertField> = fields.iter().filter(|f| f.mode != InsertValueMode::Default).collect();
+
+        if explicit.is_empty() {
+            return match db_type.to_lowercase().as_str() {
+                "mysql" | "mariadb" => format!("INSERT INTO {} () VALUES ();", table),
+                _ => format!("INSERT INTO {} DEFAULT VALUES;", table),
+            };
+        }
+
+        let columns = explicit.iter()
+            .map(|f| self.quote_identifier(db_type, &f.column))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let values = explicit.iter()
+            .map(|f| match f.mode {
+                InsertValueMode::Null => "NULL".to_string(),
+                _ => self.sql_string_literal(db_type, &f.value),
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!("INSERT INTO {} ({}) VALUES ({});", table, columns, values)
+    }
+
+    pub fn open_insert_form(&mut self) {
+        let fields = self.current_table_columns().iter()
+            .map(|c| InsertField {
+                column: c.name.clone(),
+                data_type: c.data_type.clone(),
+                nullable: c.nullable,
+                default_value: c.default_value.clone(),
+                value: String::new(),
+                mode: if c.default_value.is_some() || c.is_primary_key {
+                    InsertValueMode::Default
+                } else if c.nullable {
+                    InsertValueMode::Null
+                } else {
+                    InsertValueMode::Value
+                },
+            })
+            .collect();
+        self.table_edit.insert_form = Some(fields);
+    }
+
+    pub fn start_cell_edit(&mut self, row: usize, column: usize) {
+        let current = self.table_edit.edited_cells.get(&(row, column))
+            .cloned()
+            .unwrap_or_else(|| self.original_cell_value(row, column));
+        self.table_edit.editing_cell = Some((row, column, current.unwrap_or_default()));
+    }
+
+    // Un valor igual al original deja de contar como cambio
+    pub fn commit_cell_edit(&mut self) {
+        let Some((row, column, value)) = self.table_edit.editing_cell.take() else {
+            return;
+        };
+        self.set_cell_value(row, column, Some(value));
+    }
+
+    pub fn set_cell_value(&mut self, row: usize, column: usize, value: Option<String>) {
+        if value == self.original_cell_value(row, column) {
+            self.table_edit.edited_cells.remove(&(row, column));
+        } else {
+            self.table_edit.edited_cells.insert((row, column), value);
+        }
+    }
+
+    pub fn toggle_row_deletion(&mut self, row: usize) {
+        if !self.table_edit.deleted_rows.remove(&row) {
+            self.table_edit.deleted_rows.insert(row);
+        }
+    }
+
+    pub fn pending_change_count(&self) -> usize {
+        let edit = &self.table_edit;
+        let mut edited_rows: Vec<usize> = edit.edited_cells.keys().map(|(row, _)| *row).collect();
+        edited_rows.sort_unstable();
+        edited_rows.dedup();
+        edited_rows.len() + edit.deleted_rows.len() + edit.pending_inserts.len()
+    }
+
+    // Conserva el modo edición pero descarta los cambios de la página cargada
+    pub fn reset_table_edits(&mut self) {
+        let enabled = self.table_edit.enabled;
+        self.table_edit = Default::default();
+        self.table_edit.enabled = enabled;
+    }
+
+    // Pide confirmación mostrando el SQL que se va a ejecutar; si hay borrados
+    // el diálogo se marca como destructivo
+    pub fn request_apply_table_edits(&mut self, service: &LandoService) {
+        let statements = match self.build_table_edit_statements(service.db_engine()) {
+            Ok(statements) if statements.is_empty() => return,
+            Ok(statements) => statements,
+            Err(reason) => {
+                self.table_edit.error = Some(reason);
+                return;
+            }
+        };
+
+        let description = format!(
+            "Se aplicarán {} sentencias sobre la tabla '{}'. Esta acción modifica datos y no se puede deshacer.",
+            statements.len(),
+            self.current_table
+        );
+        let mut dialog = ConfirmDialog::new("Aplicar cambios", &description, &statements.join("\n"), &service.service);
+        if !self.table_edit.deleted_rows.is_empty() {
+            dialog = dialog.destructive();
+        }
+        self.pending_tool = Some((ToolCommand::TableEdits, dialog));
+    }
+
+    // Todas las sentencias van en una sola llamada para que se ejecuten en orden
+    pub fn apply_table_edits(&mut self, service: &LandoService, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>, is_loading: &mut bool) {
+        let statements = match self.build_table_edit_statements(service.db_engine()) {
+            Ok(statements) if statements.is_empty() => return,
+            Ok(statements) => statements,
+            Err(reason) => {
+                self.table_edit.error = Some(reason);
+                return;
+            }
+        };
+
+        *is_loading = true;
+        self.table_edit.error = None;
+        self.query_cache.invalidate_service(&service.service);
+        let request_id = next_request_id();
+        self.pending_requests.insert(request_id, PendingRequest::ApplyTableEdits { table: self.current_table.clone() });
+        run_db_query(
+            sender.clone(),
+            project_path.clone(),
+            self.db_connection(service),
+            statements.join("\n"),
+            request_id,
+        );
+    }
+}
@@ -0,0 +1,55 @@
+use std::sync::mpsc;
+use std::time::Instant;
+
+use eframe::egui;
+use egui_term::{BackendCommand, BackendSettings, PtyEvent, TerminalBackend};
+
+use crate::models::terminal::LazyTerminal;
+
+impl LazyTerminal {
+    pub fn new(ctx: egui::Context) -> Self {
+        Self { ctx, backend: None, pending: Vec::new(), error: None }
+    }
+
+    // Crea el terminal si aún no existe y le pasa lo pendiente.
+    // `None` si no se pudo crear.
+    pub fn backend(&mut self) -> Option<&mut TerminalBackend> {
+        if self.backend.is_none() && self.error.is_none() {
+            let started = Instant::now();
+            // El constructor exige un canal; los eventos del PTY no se procesan
+            let (pty_sender, _pty_receiver) = mpsc::channel::<(u64, PtyEvent)>();
+            match TerminalBackend::new(0, self.ctx.clone(), pty_sender, BackendSettings::default()) {
+                Ok(mut backend) => {
+                    for bytes in self.pending.drain(..) {
+                        backend.process_command(BackendCommand::Write(bytes));
+                    }
+                    log::info!("Terminal creado en {} ms", started.elapsed().as_millis());
+                    self.backend = Some(backend);
+                }
+                Err(e) => {
+                    log::error!("No se pudo crear el terminal: {}", e);
+                    self.error = Some(e.to_string());
+                    self.pending.clear();
+                }
+            }
+        }
+        self.backend.as_mut()
+    }
+
+    // Escribe en el terminal o, si aún no existe, lo guarda para cuando se cree
+    pub fn write(&mut self, bytes: Vec<u8>) {
+        if let Some(backend) = self.backend.as_mut() {
+            backend.process_command(BackendCommand::Write(bytes));
+        } else if self.error.is_none() {
+            self.pending.push(bytes);
+        }
+    }
+
+    pub fn clear(&mut self) {
+        if self.backend.is_some() {
+            self.write("clear".into());
+        } else {
+            self.pending.clear();
+        }
+    }
+}
@@ -0,0 +1,197 @@
+use std::time::{Duration, Instant};
+use crate::models::toasts::{Toast, ToastAction, ToastLevel, ToastManager};
+
+pub const TOAST_AUTO_DISMISS: Duration = Duration::from_secs(5);
+const TOAST_MAX_BODY_CHARS: usize = 240;
+const TOAST_MAX_BODY_LINES: usize = 4;
+const TOAST_HISTORY_LIMIT: usize = 200;
+
+impl ToastManager {
+    pub fn success(&mut self, title: &str, body: &str) -> u64 {
+        self.push(ToastLevel::Success, title, body, Instant::now())
+    }
+
+    pub fn info(&mut self, title: &str, body: &str) -> u64 {
+        self.push(ToastLevel::Info, title, body, Instant::now())
+    }
+
+    pub fn warning(&mut self, title: &str, body: &str) -> u64 {
+        self.push(ToastLevel::Warning, title, body, Instant::now())
+    }
+
+    pub fn error(&mut self, title: &str, body: &str) -> u64 {
+        self.push(ToastLevel::Error, title, body, Instant::now())
+    }
+
+    // Encola una notificación. Si ya hay una visible idéntica solo se incrementa
+    // su contador, para que un error repetido no llene la pantalla.
+    pub fn push(&mut self, level: ToastLevel, title: &str, body: &str, now: Instant) -> u64 {
+        let (short_body, truncated) = Self::truncate_body(body);
+        let action = truncated.then(|| ToastAction::CopyDetail(body.to_string()));
+
+        if let Some(existing) = self.active.iter_mut()
+            .find(|t| t.level == level && t.title == title && t.body == short_body)
+        {
+            existing.repeat_count += 1;
+            existing.created_at = now;
+            return existing.id;
+        }
+
+        self.next_id += 1;
+        let toast = Toast {
+            id: self.next_id,
+            level,
+            title: title.to_string(),
+            body: short_body,
+            action,
+            created_at: now,
+            repeat_count: 1,
+        };
+
+        self.history.push(toast.clone());
+        if self.history.len() > TOAST_HISTORY_LIMIT {
+            self.history.remove(0);
+        }
+        self.active.push(toast);
+        self.unread += 1;
+        self.next_id
+    }
+
+    // Sustituye la acción de una notificación (p. ej. "Ver log" en errores de comandos)
+    pub fn set_action(&mut self, id: u64, action: ToastAction) {
+        for toast in self.active.iter_mut().chain(self.history.iter_mut()) {
+            if toast.id == id && toast.action.is_none() {
+                toast.action = Some(action.clone());
+            }
+        }
+    }
+
+    pub fn dismiss(&mut self, id: u64) {
+        self.active.retain(|t| t.id != id);
+    }
+
+    // Éxitos e informativos desaparecen solos; avisos y errores esperan a que se cierren
+    pub fn expire(&mut self, now: Instant) {
+        self.active.retain(|t| !Self::auto_dismisses(t.level) || now.duration_since(t.created_at) < TOAST_AUTO_DISMISS);
+    }
+
+    pub fn auto_dismisses(level: ToastLevel) -> bool {
+        matches!(level, ToastLevel::Success | ToastLevel::Info)
+    }
+
+    // Tiempo hasta la próxima expiración, para pedir un repintado justo entonces
+    pub fn next_expiry(&self, now: Instant) -> Option<Duration> {
+        self.active.iter()
+            .filter(|t| Self::auto_dismisses(t.level))
+            .map(|t| TOAST_AUTO_DISMISS.saturating_sub(now.duration_since(t.created_at)))
+            .min()
+    }
+
+    pub fn mark_read(&mut self) {
+        self.unread = 0;
+    }
+
+    pub fn clear_history(&mut self) {
+        self.history.clear();
+        self.unread = 0;
+    }
+
+    // Los stderr de lando pueden ocupar cientos de líneas: en la tarjeta solo se ve el principio
+    fn truncate_body(body: &str) -> (String, bool) {
+        let trimmed = body.trim();
+        let mut short: String = trimmed.lines().take(TOAST_MAX_BODY_LINES).collect::<Vec<_>>().join("\n");
+        let mut truncated = short.len() < trimmed.len();
+
+        if short.chars().count() > TOAST_MAX_BODY_CHARS {
+            short = short.chars().take(TOAST_MAX_BODY_CHARS).collect();
+            truncated = true;
+        }
+        if truncated {
+            short.push('…');
+        }
+        (short, truncated)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn repeated_messages_are_merged() {
+        let mut toasts = ToastManager::default();
+        let start = Instant::now();
+        let first = toasts.push(ToastLevel::Error, "lando start", "port is already allocated", start);
+        let later = start + Duration::from_secs(2);
+        assert_eq!(toasts.push(ToastLevel::Error, "lando start", "  port is already allocated\n", later), first);
+        assert_eq!(toasts.active.len(), 1);
+        assert_eq!(toasts.active[0].repeat_count, 2);
+        assert_eq!(toasts.active[0].created_at, later);
+        assert_eq!((toasts.history.len(), toasts.unread), (1, 1));
+
+        // Otro nivel u otro título es otra notificación
+        assert_ne!(toasts.push(ToastLevel::Warning, "lando start", "port is already allocated", later), first);
+        assert_ne!(toasts.push(ToastLevel::Error, "lando rebuild", "port is already allocated", later), first);
+        assert_eq!(toasts.active.len(), 3);
+
+        // Una vez cerrada, el mismo mensaje vuelve a mostrarse
+        toasts.dismiss(first);
+        assert_ne!(toasts.push(ToastLevel::Error, "lando start", "port is already allocated", later), first);
+        assert_eq!(toasts.history.len(), 4);
+    }
+
+    #[test]
+    fn only_success_and_info_expire() {
+        let mut toasts = ToastManager::default();
+        let start = Instant::now();
+        toasts.push(ToastLevel::Success, "Guardado", "", start);
+        toasts.push(ToastLevel::Info, "Motor de BD detectado", "pg: postgres", start + Duration::from_secs(2));
+        toasts.push(ToastLevel::Error, "Error", "no space left on device", start);
+
+        assert_eq!(toasts.next_expiry(start + Duration::from_secs(1)), Some(Duration::from_secs(4)));
+        toasts.expire(start + TOAST_AUTO_DISMISS);
+        assert_eq!(toasts.active.iter().map(|t| t.level).collect::<Vec<_>>(), vec![ToastLevel::Info, ToastLevel::Error]);
+        assert_eq!(toasts.next_expiry(start + TOAST_AUTO_DISMISS), Some(Duration::from_secs(2)));
+
+        toasts.expire(start + Duration::from_secs(60));
+        assert_eq!(toasts.active.len(), 1);
+        assert_eq!(toasts.next_expiry(start + Duration::from_secs(60)), None);
+        assert_eq!(toasts.history.len(), 3);
+    }
+
+    #[test]
+    fn long_bodies_keep_the_detail() {
+        let mut toasts = ToastManager::default();
+        let stderr: String = (1..=10).map(|n| format!("línea {}\n", n)).collect();
+        let id = toasts.error("lando rebuild", &stderr);
+        let toast = &toasts.active[0];
+        assert_eq!(toast.body, "línea 1\nlínea 2\nlínea 3\nlínea 4…");
+        assert_eq!(toast.action, Some(ToastAction::CopyDetail(stderr.clone())));
+
+        // La acción de copiar no se sustituye
+        toasts.set_action(id, ToastAction::ShowLog);
+        assert_eq!(toasts.history[0].action, Some(ToastAction::CopyDetail(stderr)));
+
+        let long = "x".repeat(TOAST_MAX_BODY_CHARS + 10);
+        toasts.warning("Aviso", &long);
+        assert_eq!(toasts.active[1].body.chars().count(), TOAST_MAX_BODY_CHARS + 1);
+        let id = toasts.info("Corto", "ok");
+        toasts.set_action(id, ToastAction::ShowLog);
+        assert_eq!(toasts.active[2].action, Some(ToastAction::ShowLog));
+    }
+
+    #[test]
+    fn history_is_bounded() {
+        let mut toasts = ToastManager::default();
+        for n in 0..TOAST_HISTORY_LIMIT + 5 {
+            toasts.info("Aviso", &n.to_string());
+        }
+        assert_eq!(toasts.history.len(), TOAST_HISTORY_LIMIT);
+        assert_eq!(toasts.history[0].body, "5");
+        assert_eq!(toasts.unread, TOAST_HISTORY_LIMIT + 5);
+        toasts.mark_read();
+        assert_eq!(toasts.unread, 0);
+        toasts.clear_history();
+        assert!(toasts.history.is_empty());
+    }
+}
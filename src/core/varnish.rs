@@ -0,0 +1,247 @@
+use std::path::PathBuf;
+use std::sync::mpsc::Sender;
+use std::time::Instant;
+
+use serde_json::Value;
+
+use crate::core::commands::*;
+use crate::models::commands::LandoCommandOutcome;
+use crate::models::lando::LandoService;
+use crate::models::varnish::{BanMode, VarnishCommand, VarnishRequest, VarnishStats};
+use crate::ui::varnish::{ProxyCacheUI, VARNISH_HISTORY_POINTS};
+
+// Las herramientas vienen con la imagen de varnish; si falta alguna el panel
+// se queda en un aviso
+const VARNISH_PROBE: &str = "command -v varnishadm && command -v varnishstat && command -v varnishncsa";
+const VARNISH_STATS: &str = "varnishstat -1 -j";
+
+pub fn is_varnish_service(service: &LandoService) -> bool {
+    service.r#type.to_lowercase().starts_with("varnish") || service.service.to_lowercase() == "varnish"
+}
+
+// `-d` vuelca lo que queda en la memoria compartida y termina
+fn recent_requests_command(limit: usize) -> String {
+    format!(
+        "varnishncsa -d -F {} | tail -n {}",
+        shell_quote("%m\t%U%q\t%s\t%{Varnish:handling}x"),
+        limit
+    )
+}
+
+// URL exacta o expresión regular sobre `req.url`
+pub fn ban_expression(mode: BanMode, value: &str) -> String {
+    match mode {
+        BanMode::Url => format!("req.url == {}", value.trim()),
+        BanMode::Regex => format!("req.url ~ {}", value.trim()),
+    }
+}
+
+// varnishadm lee el secreto de /etc/varnish, por eso se ejecuta como root.
+// El valor va como un solo argumento aunque lleve espacios.
+fn ban_command(expression: &str) -> String {
+    let args: Vec<String> = expression.splitn(3, ' ').map(shell_quote).collect();
+    format!("varnishadm ban {}", args.join(" "))
+}
+
+// Varnish 6.5+ agrupa los contadores bajo `counters`; las versiones
+// anteriores los ponen en la raíz junto a `timestamp`
+pub fn parse_varnishstat(text: &str) -> Result<VarnishStats, String> {
+    let start = text.find('{').ok_or("varnishstat no devolvió JSON")?;
+    let value: Value = serde_json::from_str(&text[start..])
+        .map_err(|e| format!("JSON de varnishstat no válido: {}", e))?;
+    let counters = value.get("counters").unwrap_or(&value);
+    let counter = |name: &str| counters.get(name).and_then(|c| c.get("value")).and_then(Value::as_u64);
+
+    Ok(VarnishStats {
+        hits: counter("MAIN.cache_hit").ok_or("Falta el contador MAIN.cache_hit")?,
+        misses: counter("MAIN.cache_miss").unwrap_or_default(),
+        passes: counter("MAIN.s_pass").unwrap_or_default(),
+        requests: counter("MAIN.client_req").unwrap_or_default(),
+        objects: counter("MAIN.n_object"),
+        uptime: counter("MAIN.uptime"),
+    })
+}
+
+pub fn parse_varnishncsa(text: &str) -> Vec<VarnishRequest> {
+    text.lines()
+        .filter_map(|line| {
+            let mut fields = line.split('\t');
+            Some(VarnishRequest {
+                method: fields.next()?.trim().to_string(),
+                url: fields.next()?.to_string(),
+                status: fields.next()?.trim().to_string(),
+                handling: fields.next().unwrap_or("-").trim().to_string(),
+            })
+        })
+        .collect()
+}
+
+impl ProxyCacheUI {
+    fn send(&mut self, service: &LandoService, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>, command: VarnishCommand) {
+        let shell = match &command {
+            VarnishCommand::Probe => VARNISH_PROBE.to_string(),
+            VarnishCommand::Stats => VARNISH_STATS.to_string(),
+            VarnishCommand::Requests => recent_requests_command(self.request_limit),
+            VarnishCommand::Ban(expression) => ban_command(expression),
+        };
+        let request_id = next_request_id();
+        self.pending_requests.insert(request_id, command);
+        run_root_shell_capture_tagged(sender.clone(), project_path.clone(), service.service.clone(), shell, request_id);
+    }
+
+    fn is_pending(&self, command: &VarnishCommand) -> bool {
+        self.pending_requests.values().any(|c| c == command)
+    }
+
+    pub fn probe(&mut self, service: &LandoService, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>) {
+        if !self.is_pending(&VarnishCommand::Probe) {
+            self.available = None;
+            self.send(service, project_path, sender, VarnishCommand::Probe);
+        }
+    }
+
+    pub fn refresh_stats(&mut self, service: &LandoService, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>) {
+        if !self.is_pending(&VarnishCommand::Stats) {
+            self.last_stats_request = Some(Instant::now());
+            self.send(service, project_path, sender, VarnishCommand::Stats);
+        }
+    }
+
+    pub fn load_requests(&mut self, service: &LandoService, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>) {
+        if !self.is_pending(&VarnishCommand::Requests) {
+            self.send(service, project_path, sender, VarnishCommand::Requests);
+        }
+    }
+
+    pub fn ban(&mut self, service: &LandoService, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>, expression: String) {
+        self.ban_result = None;
+        self.send(service, project_path, sender, VarnishCommand::Ban(expression));
+    }
+
+    pub fn handle_tagged_result(&mut self, request_id: u64, output: &Result<String, String>) -> bool {
+        let Some(command) = self.pending_requests.remove(&request_id) else {
+            return false;
+        };
+
+        match command {
+            VarnishCommand::Probe => {
+                self.available = Some(output.as_ref().is_ok_and(|text| text.lines().count() >= 3));
+            }
+            VarnishCommand::Stats => match output.as_ref().map_err(String::clone).and_then(|text| parse_varnishstat(text)) {
+                Ok(stats) => self.push_stats(stats),
+                Err(e) => self.stats_error = Some(e),
+            },
+            VarnishCommand::Requests => match output {
+                Ok(text) => {
+                    self.requests = parse_varnishncsa(text);
+                    self.requests_error = None;
+                }
+                Err(e) => self.requests_error = Some(e.clone()),
+            },
+            VarnishCommand::Ban(expression) => {
+                self.ban_result = Some(match output {
+                    Ok(_) => Ok(format!("Ban añadido: {}", expression)),
+                    Err(e) => Err(e.clone()),
+                });
+            }
+        }
+        true
+    }
+
+    // La tasa de la gráfica es la del intervalo, no la acumulada desde el arranque
+    fn push_stats(&mut self, stats: VarnishStats) {
+        if let Some(previous) = &self.stats {
+            let hits = stats.hits.saturating_sub(previous.hits);
+            let lookups = hits + stats.misses.saturating_sub(previous.misses);
+            if lookups > 0 {
+                let elapsed = self.started.elapsed().as_secs_f64();
+                self.hit_rate_history.push_back([elapsed, hits as f64 / lookups as f64 * 100.0]);
+                while self.hit_rate_history.len() > VARNISH_HISTORY_POINTS {
+                    self.hit_rate_history.pop_front();
+                }
+            }
+        }
+        self.stats = Some(stats);
+        self.stats_error = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // `varnishstat -1 -j` de Varnish 6.5+, recortado
+    const VARNISHSTAT_COUNTERS: &str = r#"{
+  "version": 1,
+  "timestamp": "2024-03-01T10:00:00",
+  "counters": {
+    "MAIN.uptime": { "description": "Child process uptime", "flag": "c", "format": "d", "value": 3600 },
+    "MAIN.client_req": { "description": "Good client requests received", "flag": "c", "format": "i", "value": 120 },
+    "MAIN.cache_hit": { "description": "Cache hits", "flag": "c", "format": "i", "value": 90 },
+    "MAIN.cache_miss": { "description": "Cache misses", "flag": "c", "format": "i", "value": 30 },
+    "MAIN.s_pass": { "description": "Total pass-ed requests seen", "flag": "c", "format": "i", "value": 4 },
+    "MAIN.n_object": { "description": "object structs made", "flag": "g", "format": "i", "value": 25 }
+  }
+}"#;
+
+    fn stats_output(hits: u64, misses: u64) -> String {
+        format!(
+            r#"{{"timestamp": "2020-01-01T00:00:00", "MAIN.cache_hit": {{"value": {}}}, "MAIN.cache_miss": {{"value": {}}}}}"#,
+            hits, misses,
+        )
+    }
+
+    #[test]
+    fn counters_from_varnish_6_5() {
+        assert_eq!(parse_varnishstat(VARNISHSTAT_COUNTERS), Ok(VarnishStats {
+            hits: 90,
+            misses: 30,
+            passes: 4,
+            requests: 120,
+            objects: Some(25),
+            uptime: Some(3600),
+        }));
+    }
+
+    #[test]
+    fn counters_at_the_root_before_6_5() {
+        // `lando ssh` puede colar avisos antes del JSON
+        let text = format!("Warning: Permanently added 'varnish'\n{}", stats_output(10, 5));
+        let stats = parse_varnishstat(&text).unwrap();
+        assert_eq!((stats.hits, stats.misses, stats.requests, stats.objects), (10, 5, 0, None));
+        assert_eq!(stats.hit_rate(), Some(10.0 / 15.0));
+    }
+
+    #[test]
+    fn unusable_output() {
+        assert_eq!(parse_varnishstat("sh: varnishstat: not found"), Err("varnishstat no devolvió JSON".to_string()));
+        assert!(parse_varnishstat("{ \"counters\": ").unwrap_err().starts_with("JSON de varnishstat no válido"));
+        assert_eq!(parse_varnishstat("{\"counters\": {}}"), Err("Falta el contador MAIN.cache_hit".to_string()));
+        assert_eq!(VarnishStats::default().hit_rate(), None);
+    }
+
+    #[test]
+    fn hit_rate_history_uses_the_interval() {
+        let mut ui = ProxyCacheUI::default();
+        for (request_id, (hits, misses)) in [(1, (100, 100)), (2, (130, 110)), (3, (130, 110))] {
+            ui.pending_requests.insert(request_id, VarnishCommand::Stats);
+            assert!(ui.handle_tagged_result(request_id, &Ok(stats_output(hits, misses))));
+        }
+        // La primera lectura no tiene con qué compararse y la última no tiene consultas
+        assert_eq!(ui.hit_rate_history.iter().map(|p| p[1]).collect::<Vec<_>>(), vec![75.0]);
+        assert!(!ui.handle_tagged_result(4, &Ok(stats_output(1, 1))));
+    }
+
+    #[test]
+    fn ncsa_lines_and_bans() {
+        let requests = parse_varnishncsa("GET\t/\t200\thit\nPOST\t/cart?id=1\t302\tpass\nGET\t/old\n");
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[1], VarnishRequest {
+            method: "POST".to_string(),
+            url: "/cart?id=1".to_string(),
+            status: "302".to_string(),
+            handling: "pass".to_string(),
+        });
+        assert_eq!(ban_command(&ban_expression(BanMode::Regex, " ^/blog/.* ")), "varnishadm ban 'req.url' '~' '^/blog/.*'");
+    }
+}
@@ -0,0 +1,68 @@
+use std::path::{Component, Path, PathBuf};
+use crate::ui::wizard::NewProjectWizard;
+
+impl NewProjectWizard {
+    // Al elegir carpeta se propone su nombre como nombre de la app
+    pub fn set_folder(&mut self, folder: Option<PathBuf>) {
+        self.picking_folder = false;
+        if let Some(folder) = folder {
+            if self.app_name.is_empty() {
+                self.app_name = folder.file_name()
+                    .map(|n| n.to_string_lossy().to_lowercase().replace(' ', "-"))
+                    .unwrap_or_default();
+            }
+            self.folder = Some(folder);
+        }
+    }
+
+    // Siempre con flags no interactivos: `lando init` sin ellos se queda esperando respuestas
+    pub fn init_args(&self) -> Vec<String> {
+        vec![
+            "init".to_string(),
+            "--source".to_string(), "cwd".to_string(),
+            "--recipe".to_string(), self.recipe.clone(),
+            "--webroot".to_string(), self.webroot.trim().to_string(),
+            "--name".to_string(), self.app_name.trim().to_string(),
+        ]
+    }
+
+    pub fn command_preview(&self) -> String {
+        format!("lando {}", self.init_args().join(" "))
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        let Some(folder) = &self.folder else {
+            return Err("Elige la carpeta del proyecto".to_string());
+        };
+        if folder.join(".lando.yml").exists() {
+            return Err(format!("'{}' ya contiene un .lando.yml", folder.display()));
+        }
+
+        Self::validate_app_name(self.app_name.trim())?;
+
+        let webroot = self.webroot.trim();
+        if webroot.is_empty() {
+            return Err("El webroot no puede estar vacío (usa '.' para la raíz)".to_string());
+        }
+        let webroot_path = Path::new(webroot);
+        if webroot_path.is_absolute() || webroot_path.components().any(|c| c == Component::ParentDir) {
+            return Err("El webroot debe ser una ruta relativa dentro del proyecto".to_string());
+        }
+
+        Ok(())
+    }
+
+    // Lando usa el nombre para contenedores y dominios `*.lndo.site`
+    fn validate_app_name(name: &str) -> Result<(), String> {
+        if name.is_empty() {
+            return Err("El nombre de la app es obligatorio".to_string());
+        }
+        if !name.chars().next().is_some_and(|c| c.is_ascii_alphanumeric()) {
+            return Err("El nombre debe empezar por una letra o un número".to_string());
+        }
+        if let Some(c) = name.chars().find(|c| !(c.is_ascii_alphanumeric() || *c == '-' || *c == '_')) {
+            return Err(format!("Carácter no permitido en el nombre: '{}'", c));
+        }
+        Ok(())
+    }
+}
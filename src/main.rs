@@ -4,13 +4,16 @@ mod models;
 mod ui;
 mod core;
 
+use crate::core::logging::{init_logging, StartupTimer};
 use models::app::LandoGui;
 
 fn main() -> eframe::Result<()> {
+    let startup = StartupTimer::start();
+    init_logging();
     let native_options = eframe::NativeOptions::default();
     eframe::run_native(
         "Lando GUI",
         native_options,
-        Box::new(|cc| Ok(Box::new(LandoGui::new(cc)))),
+        Box::new(move |cc| Ok(Box::new(LandoGui::new(cc, startup)))),
     )
 }
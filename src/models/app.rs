@@ -1,37 +1,157 @@
+use crate::models::bulk::BulkRun;
 use crate::models::commands::LandoCommandOutcome;
+use crate::models::dashboard::ProjectDashboard;
+use crate::models::drift::ConfigDriftView;
+use crate::models::journal::CommandJournal;
+use crate::models::notes::{NotesLocation, ProjectNotes};
+use crate::models::phases::PhaseTracker;
 use crate::models::lando::{LandoApp, LandoService};
+use crate::ui::confirm::{ConfirmDialog, DestroyDialog};
+use crate::ui::diagnostics::{DiagnosticsWindow, FrameTimeOverlay};
+use crate::ui::graph::ServiceGraphView;
+use crate::ui::notes::NotesSearch;
 use crate::ui::service::ServiceUIManager;
-use egui_term::TerminalBackend;
+use crate::ui::status_bar::StatusBar;
+use crate::ui::wizard::NewProjectWizard;
+use crate::models::toasts::ToastManager;
+use crate::models::prompts::InteractiveCommand;
+use crate::models::processes::RunningProcess;
+use crate::core::logging::StartupTimer;
+use eframe::egui::ThemePreference;
+use log::LevelFilter;
+use crate::models::terminal::LazyTerminal;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
 use std::path::PathBuf;
 use std::sync::mpsc::{Receiver, Sender};
 use std::cell::{Cell, RefCell};
 use std::rc::Rc;
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+use std::time::Instant;
+
+// Clave con la que se guarda el tema elegido en el almacenamiento de eframe
+pub const THEME_STORAGE_KEY: &str = "theme";
+pub const AUTO_YES_STORAGE_KEY: &str = "auto_yes";
+pub const PINNED_PROJECTS_STORAGE_KEY: &str = "pinned_projects";
+pub const RECENT_PROJECTS_STORAGE_KEY: &str = "recent_projects";
+pub const LOG_LEVEL_STORAGE_KEY: &str = "log_level";
+pub const NOTES_LOCATION_STORAGE_KEY: &str = "notes_location";
+pub const AUTO_RETRY_INFO_STORAGE_KEY: &str = "auto_retry_info";
+pub const PROJECT_LAST_OPENED_STORAGE_KEY: &str = "project_last_opened";
+pub const CLEAR_CLIPBOARD_STORAGE_KEY: &str = "clear_clipboard_secrets";
+pub const JOURNAL_LIMIT_STORAGE_KEY: &str = "command_journal_limit";
+pub const NOTIFICATIONS_STORAGE_KEY: &str = "notifications_enabled";
+pub const MAX_RECENT_PROJECTS: usize = 8;
+
+// Posición y tamaño de una interfaz de BD abierta en su propia ventana
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct WindowGeometry {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ServicesView {
+    #[default]
+    Cards,
+    Graph,
+}
 
 pub struct LandoGui {
     // Estado de la UI
     pub(crate) apps: Vec<LandoApp>,
     pub(crate) projects: Vec<PathBuf>,
+    pub(crate) pinned_projects: Vec<PathBuf>, // Favoritos; no dependen de la búsqueda
+    pub(crate) recent_projects: Vec<PathBuf>, // Últimos seleccionados, el más reciente primero
+    pub(crate) project_last_opened: HashMap<PathBuf, u64>, // Segundos Unix de la última selección
+    pub(crate) dashboard: ProjectDashboard,
     pub(crate) selected_project_path: Option<PathBuf>,
     pub(crate) services: Vec<LandoService>,
     pub(crate) db_query_input: String,
-    pub(crate) db_query_result: Option<String>,
-    pub(crate) shell_command_input: String,
-    pub(crate) error_message: Option<String>,
-    pub(crate) success_message: Option<String>,
+    pub(crate) db_query_result: Option<Arc<str>>,
+    pub(crate) toasts: ToastManager,
     pub(crate) is_loading: Cell<bool>,
+    pub(crate) last_refresh: Option<Instant>,
+    pub(crate) apps_requested: bool, // `lando list` ya se pidió; al arrancar se aplaza
+    pub(crate) started_at: Instant,
+    pub(crate) startup: Option<StartupTimer>, // Se consume al pintar el primer frame
+    pub(crate) running_commands: Vec<(String, Instant)>, // Comandos en curso y cuándo empezaron
+    pub(crate) interactive_commands: Vec<InteractiveCommand>, // Comandos con stdin abierto
+    pub(crate) orphaned_processes: Vec<RunningProcess>, // Procesos de lando que sobrevivieron a la sesión anterior
+    pub(crate) exit_dialog_open: bool, // Se pidió cerrar con comandos en ejecución
+    pub(crate) exit_cancel_started: Option<Instant>, // Cancelando comandos antes de salir
+    pub(crate) allow_close: bool,
+
+    // Ajustes
+    pub(crate) show_settings: bool,
+    pub(crate) notifications_enabled: bool,
+    pub(crate) theme: ThemePreference,
+    pub(crate) auto_yes_enabled: bool,
+    pub(crate) auto_retry_info: bool, // Reintentar lando info tras start mientras levantan los servicios
+    pub(crate) info_retry_cancel: Option<Arc<AtomicBool>>,
+    pub(crate) clear_clipboard_secrets: bool, // Vaciar el portapapeles tras copiar una contraseña
+    pub(crate) log_level: LevelFilter,
+    pub(crate) diagnostics: DiagnosticsWindow,
+    pub(crate) frame_time: FrameTimeOverlay, // Ajuste de depuración, no se guarda
+    pub(crate) status_bar: StatusBar,
+    pub(crate) notes: Option<ProjectNotes>, // Notas del proyecto seleccionado
+    pub(crate) notes_location: NotesLocation,
+    pub(crate) notes_search: NotesSearch,
+    pub(crate) notes_open_request: bool, // Desplegar las notas en el próximo frame
 
     pub(crate) sender: Sender<LandoCommandOutcome>,
     pub(crate) receiver: Receiver<LandoCommandOutcome>,
 
     // Terminal
-    pub(crate) terminal: Rc<RefCell<TerminalBackend>>,
+    pub(crate) terminal: Rc<RefCell<LazyTerminal>>, // Se crea al mostrarse por primera vez
     pub(crate) show_terminal_popup: bool,
     pub(crate) terminal_filter: String,
+    pub(crate) terminal_filter_regex: bool,
+    pub(crate) terminal_filter_case_insensitive: bool,
+    pub(crate) terminal_filter_compiled: Option<Regex>,
+    pub(crate) terminal_filter_error: Option<String>,
     pub(crate) log_buffer: Vec<String>,
+    pub(crate) log_pending_bytes: Vec<u8>, // Bytes de un carácter UTF-8 aún incompleto
+    pub(crate) phase_tracker: Option<PhaseTracker>, // Pasos del último start/rebuild/destroy
+    pub(crate) log_generation: u64, // Aumenta al limpiar el terminal
+
+    // Historial de comandos por proyecto y salida abierta desde él (título, texto)
+    pub(crate) command_journal: CommandJournal,
+    pub(crate) journal_output: Option<(String, String)>,
+
+    // Diferencias entre la configuración en uso y los landofiles del proyecto abierto
+    pub(crate) config_drift: ConfigDriftView,
 
     // Gestor de UIs especializadas
     pub(crate) service_ui_manager: Rc<RefCell<ServiceUIManager>>,
 
     // Estado para controlar la interfaz de base de datos
     pub(crate) open_database_interface: Option<String>, // Nombre del servicio de BD abierto
+    pub(crate) detached_database_interface: Option<String>, // Servicio de BD en ventana separada
+    pub(crate) detached_geometry: Option<WindowGeometry>, // Geometría actual, se guarda al cerrar
+    pub(crate) detached_initial_geometry: Option<WindowGeometry>, // Con la que se abrió la ventana
+
+    // Comando de Lando a la espera de confirmación (ej. poweroff)
+    pub(crate) pending_lando_command: Option<(String, ConfirmDialog)>,
+    pub(crate) pending_destroy: Option<DestroyDialog>,
+    pub(crate) pending_bulk: Option<(BulkRun, ConfirmDialog)>, // start/stop masivo a confirmar
+    pub(crate) bulk_run: Option<BulkRun>,
+    pub(crate) bulk_selection: BTreeSet<PathBuf>, // Por ruta: sobrevive a las búsquedas y refrescos de la lista
+    pub(crate) bulk_stop_on_error: bool,
+
+    // Asistente de `lando init` abierto
+    pub(crate) new_project_wizard: Option<NewProjectWizard>,
+
+    // Vista de servicios: tarjetas o grafo de dependencias
+    pub(crate) services_view: ServicesView,
+    pub(crate) service_graph: ServiceGraphView,
+    pub(crate) scroll_to_service: Option<String>, // Tarjeta a la que saltar desde el grafo
+
+    // Motor de BD por `proyecto#servicio`: elegido a mano (persistente) y preguntado al contenedor
+    pub(crate) db_engine_overrides: HashMap<String, String>,
+    pub(crate) probed_db_engines: HashMap<String, Option<String>>,
 }
\ No newline at end of file
@@ -0,0 +1,10 @@
+use serde::{Deserialize, Serialize};
+
+// Backup registrado en el índice de la carpeta de backups del proyecto
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BackupEntry {
+    pub file_name: String,
+    pub service: Option<String>, // None si el archivo apareció sin pasar por la app
+    pub created_at: u64,         // Segundos desde UNIX_EPOCH
+    pub size: u64,
+}
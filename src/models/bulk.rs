@@ -0,0 +1,23 @@
+use std::path::PathBuf;
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+
+// Estado de cada proyecto en un start/stop masivo
+#[derive(Debug, Clone, PartialEq)]
+pub enum BulkStatus {
+    Pending,
+    Running,
+    Done,
+    Failed(String),
+    Skipped, // Se detuvo la operación antes de llegar a él
+}
+
+// `lando start`/`lando stop` sobre varios proyectos (todos, los seleccionados o
+// el resto), de uno en uno
+pub struct BulkRun {
+    pub command: String,
+    pub title: String,
+    pub entries: Vec<(PathBuf, BulkStatus)>,
+    pub cancel: Arc<AtomicBool>, // Para no seguir con los que faltan
+    pub finished: bool,
+}
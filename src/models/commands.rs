@@ -1,5 +1,20 @@
+use crate::models::backups::BackupEntry;
+use crate::models::bulk::BulkStatus;
+use crate::models::csv_import::CsvPreview;
+use crate::models::dashboard::ProjectCardInfo;
+use crate::models::drift::DriftState;
+use crate::models::health::ProbeResult;
+use crate::models::journal::Invocation;
 use crate::models::lando::{LandoApp, LandoService};
+use crate::models::prompts::LandoPrompt;
+use crate::models::processes::RunningProcess;
+use crate::models::search::SearchIndex;
+use crate::models::status::ToolVersions;
 use std::path::PathBuf;
+use std::sync::atomic::AtomicBool;
+use std::time::Duration;
+use std::sync::mpsc::Sender;
+use std::sync::Arc;
 
 // Mensajes que los hilos de trabajo envían a la UI.
 #[derive(Debug)]
@@ -7,9 +22,35 @@ pub enum LandoCommandOutcome {
     List(Vec<LandoApp>),
     Projects(Vec<PathBuf>),
     Info(Vec<LandoService>),
-    DbQueryResult(String),
+    DbTaggedResult { service: String, request_id: u64, output: Result<String, String> }, // Resultado correlacionado con su petición y el servicio que la lanzó
+    ShellTaggedResult { request_id: u64, output: Result<String, String> }, // Salida capturada de `lando ssh`
+    ShellStreamChunk { request_id: u64, chunk: Vec<u8> }, // Salida parcial de `run_shell_stream_tagged`
+    ShellStreamFinished { request_id: u64, result: Result<(), String> },
     Error(String),
     CommandSuccess(String),
+    CommandStarted { command: String, project: PathBuf, invocation: Option<Invocation> }, // Un comando de larga duración empezó a ejecutarse; sin `invocation` no se apunta en el historial
     FinishedLoading, // Para indicar que una tarea en segundo plano ha terminado
     LogOutput(Vec<u8>), // Para enviar la salida del comando en tiempo real
+    NewProjectFolderPicked(Option<PathBuf>), // Carpeta elegida en el asistente de nuevo proyecto
+    ProjectCreated(PathBuf), // `lando init` terminó bien en esa carpeta
+    SqlFileLoaded { service: String, path: PathBuf, content: String }, // Archivo .sql soltado sobre la ventana
+    OpenTerminal, // Mostrar el terminal (p. ej. tras lanzar una sesión interactiva)
+    CommandInput { command: String, input: Sender<Vec<u8>>, cancel: Arc<AtomicBool> }, // stdin y cancelación de un comando en curso
+    PromptDetected { command: String, prompt: LandoPrompt }, // El comando está esperando una respuesta
+    DbSessionReady { service: String }, // La sesión persistente del modo transacción respondió
+    DbSessionEnded { service: String, error: Option<String> }, // La sesión terminó; `error` si fue inesperado
+    BackupsLoaded { service: String, result: Result<Vec<BackupEntry>, String> }, // Backups de la carpeta ya reconciliados con el índice
+    DbEngineProbed { service: String, engine: Option<String> }, // Respuesta de `--version` dentro del contenedor
+    DbEngineOverride { service: String, engine: Option<String> }, // Motor elegido a mano; `None` vuelve a la detección
+    ConfigDriftChecked { project: PathBuf, state: DriftState }, // Config en uso vs. landofiles
+    HealthProbed { service: String, results: Vec<ProbeResult> }, // Comprobación TCP/HTTP del panel de salud
+    SearchIndicesLoaded { service: String, result: Result<Vec<SearchIndex>, String> }, // Cores de Solr o índices de Elasticsearch
+    SearchResponse { service: String, label: String, result: Result<(u16, String), String> }, // Estado HTTP y cuerpo; `Err` si no hubo conexión
+    BulkProgress { index: usize, status: BulkStatus }, // Proyecto `index` de la operación masiva en curso
+    BulkFinished,
+    OrphanedProcesses(Vec<RunningProcess>), // Resultado de la comprobación de procesos huérfanos del arranque
+    ToolVersions(ToolVersions), // lando/docker detectados y si el daemon responde
+    InfoRetry { attempt: u32, max: u32, next_in: Option<Duration>, error: String }, // `lando info` falló tras un arranque; None al rendirse
+    CsvPreviewLoaded { service: String, result: Result<CsvPreview, String> }, // Primeras filas del CSV a importar; error vacío si se canceló
+    DashboardCard { project: PathBuf, info: ProjectCardInfo }, // Datos de una tarjeta del panel de proyectos
 }
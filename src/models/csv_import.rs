@@ -0,0 +1,53 @@
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CsvEncoding {
+    Utf8,
+    Latin1, // También sirve para la mayoría de archivos Windows-1252
+}
+
+// Formato del archivo: se detecta al abrirlo y se puede corregir a mano
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CsvOptions {
+    pub delimiter: char,
+    pub quote: char,
+    pub encoding: CsvEncoding,
+    pub has_header: bool,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        Self { delimiter: ',', quote: '"', encoding: CsvEncoding::Utf8, has_header: true }
+    }
+}
+
+// Primeras filas del archivo leídas con `options`. Sin cabecera, `headers`
+// son nombres genéricos (`columna_1`...).
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsvPreview {
+    pub path: PathBuf,
+    pub options: CsvOptions,
+    pub headers: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+    pub file_size: u64,
+}
+
+// Columnas de la tabla sin columna del CSV asignada
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MissingColumns {
+    Default, // No se incluyen en el INSERT: la BD pone su valor por defecto
+    Null,
+}
+
+// Filas con menos campos que la cabecera
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ShortRows {
+    FillEmpty,
+    Skip,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BatchErrorMode {
+    Stop,
+    SkipAndContinue,
+}
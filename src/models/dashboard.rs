@@ -0,0 +1,29 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+// Lo que se lee del `.lando.yml` de cada tarjeta en un hilo aparte
+#[derive(Debug, Clone, Default)]
+pub struct ProjectCardInfo {
+    pub app_name: Option<String>,
+    pub recipe: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub enum CardStatus {
+    Loading,
+    Ready(ProjectCardInfo),
+}
+
+// Vista de inicio con una tarjeta por proyecto conocido. Los datos se cargan
+// al aparecer cada tarjeta en pantalla y se guardan para toda la sesión.
+#[derive(Debug, Default)]
+pub struct ProjectDashboard {
+    pub cards: HashMap<PathBuf, CardStatus>,
+    pub search: String,
+}
+
+pub enum DashboardAction {
+    Select(PathBuf),
+    Lando(PathBuf, &'static str), // start / stop desde la tarjeta
+    Refresh,
+}
@@ -0,0 +1,42 @@
+use std::sync::mpsc::Sender;
+
+// Órdenes de la UI al hilo que gestiona la shell de base de datos persistente
+#[derive(Debug, Clone, PartialEq)]
+pub enum SessionCommand {
+    Execute { request_id: u64, sql: String },
+    Close { rollback: bool },
+}
+
+// Ciclo de vida de una sesión abierta. Al cerrarse (o morir) la sesión se
+// descarta; para volver a usarla se crea otra.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SessionStatus {
+    Starting,   // La shell arrancó pero el cliente aún no respondió
+    Ready,
+    Busy(u64),  // Ejecutando la petición con ese `request_id`
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SessionEvent {
+    Connected,
+    Submitted(u64),
+    Finished(u64),
+}
+
+// Transacción según las sentencias que se han enviado por la sesión
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum TransactionState {
+    #[default]
+    Autocommit,
+    Open { statements: usize },
+}
+
+// Sesión persistente (`lando ssh -s <svc> -c mysql/psql`) del modo transacción.
+// Al soltarla se cierra con ROLLBACK.
+#[derive(Debug)]
+pub struct DbSession {
+    pub service: String,
+    pub status: SessionStatus,
+    pub transaction: TransactionState,
+    pub commands: Sender<SessionCommand>,
+}
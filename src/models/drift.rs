@@ -0,0 +1,42 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::PathBuf;
+
+// Lo que se puede comparar de un servicio. `None` o vacío: el lado del disco
+// no lo fija y no se compara.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ServiceSnapshot {
+    pub r#type: Option<String>,
+    pub version: Option<String>,
+    pub ports: BTreeSet<String>, // Puertos del host publicados explícitamente
+    pub env: BTreeMap<String, String>,
+    pub inspected: bool, // Puertos y variables leídos de su contenedor
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ConfigSnapshot {
+    pub services: BTreeMap<String, ServiceSnapshot>,
+    pub from_recipe: bool, // Con receta hay servicios que no aparecen en el .lando.yml
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConfigDifference {
+    OnlyOnDisk(String), // Servicio nuevo que aún no existe
+    OnlyInUse(String),  // Servicio borrado del .lando.yml que sigue en marcha
+    Changed { service: String, field: String, in_use: String, on_disk: String },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DriftState {
+    InvalidYaml(String),
+    NeverStarted, // Lando no ha guardado todavía la configuración de la app
+    Failed(String),
+    Compared(Vec<ConfigDifference>),
+}
+
+// Panel "Config en uso vs. en disco" del proyecto abierto
+#[derive(Debug, Default)]
+pub struct ConfigDriftView {
+    pub project: Option<PathBuf>, // Proyecto al que corresponde `state`
+    pub state: Option<DriftState>,
+    pub checked_at: Option<u64>,
+}
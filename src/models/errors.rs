@@ -0,0 +1,28 @@
+// Fallos habituales de lando/docker reconocidos en stderr
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LandoErrorKind {
+    DockerNotRunning,
+    PortInUse,
+    AppNotRunning,
+    MissingPlugin,
+    DiskFull,
+    DatabaseAuth,
+    Unknown,
+}
+
+// Acción que la notificación ofrece para resolver el error
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ErrorFix {
+    StartApp,      // "Iniciar app": `lando start` en el proyecto seleccionado
+    OpenDocker,    // "Abrir Docker Desktop"
+}
+
+// Error clasificado: resumen legible y sugerencia; `raw` conserva el stderr completo
+#[derive(Debug, Clone, PartialEq)]
+pub struct LandoError {
+    pub kind: LandoErrorKind,
+    pub raw: String,
+    pub summary: String,
+    pub suggestion: Option<String>,
+    pub fix: Option<ErrorFix>,
+}
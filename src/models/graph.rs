@@ -0,0 +1,41 @@
+// Grafo de servicios de un proyecto: nodos de `lando info`/`.lando.yml` y
+// rutas del proxy; aristas por `depends_on`, proxy y credenciales de BD
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GraphNodeKind {
+    AppServer,
+    Node,
+    Database,
+    Cache,
+    Proxy,
+    Other,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphNode {
+    pub id: String,
+    pub label: String,
+    pub kind: GraphNodeKind,
+    pub service: Option<String>, // None en las rutas del proxy
+    pub detail: String,          // Tipo del servicio o destino de la ruta
+    pub tooling: Vec<String>,    // Comandos de `tooling:` que corren en este servicio
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GraphEdgeKind {
+    DependsOn,
+    Proxy,
+    Database,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphEdge {
+    pub from: String,
+    pub to: String,
+    pub kind: GraphEdgeKind,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ServiceGraph {
+    pub nodes: Vec<GraphNode>,
+    pub edges: Vec<GraphEdge>,
+}
@@ -0,0 +1,32 @@
+use std::time::Instant;
+
+// Comprobación de "¿responde de verdad?" para un servicio
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProbeTarget {
+    Tcp { host: String, port: u16 },
+    Http { url: String }, // Solo `http://`; ver `probe_targets` para las https
+}
+
+impl ProbeTarget {
+    pub fn label(&self) -> String {
+        match self {
+            Self::Tcp { host, port } => format!("TCP {}:{}", host, port),
+            Self::Http { url } => format!("GET {}", url),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ProbeResult {
+    pub target: ProbeTarget,
+    pub latency_ms: f64,
+    pub outcome: Result<String, String>, // Detalle (código HTTP, "conectado") o el error
+}
+
+// Estado del panel por servicio
+#[derive(Debug, Default)]
+pub struct HealthState {
+    pub running: bool,
+    pub results: Vec<ProbeResult>,
+    pub checked_at: Option<Instant>,
+}
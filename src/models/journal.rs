@@ -0,0 +1,65 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::Instant;
+
+use serde::{Deserialize, Serialize};
+
+// Cómo volver a lanzar un comando del historial
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Invocation {
+    Lando(Vec<String>), // `lando <args>`
+    Shell { service: String, command: String }, // `lando ssh -s <service> -c <command>`
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum JournalStatus {
+    Running,
+    Success,
+    Failed,
+    Interrupted, // Seguía en marcha cuando se cerró la app
+}
+
+// Trozos de `log_buffer` escritos mientras corría el comando. `generation`
+// cambia al limpiar el terminal: entonces la salida ya no está disponible.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LogSpan {
+    pub generation: u64,
+    pub start: usize,
+    pub end: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub invocation: Invocation,
+    pub command: String, // Tal como aparece entre comillas en el mensaje de fin
+    pub started_at: u64, // Segundos desde epoch
+    #[serde(default)]
+    pub duration_ms: Option<u64>,
+    pub status: JournalStatus,
+    #[serde(skip)]
+    pub started: Option<Instant>,
+    #[serde(skip)]
+    pub log: Option<LogSpan>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProjectJournal {
+    #[serde(default)]
+    pub entries: Vec<JournalEntry>, // El más antiguo primero
+    #[serde(default)]
+    pub favorites: Vec<Invocation>,
+}
+
+// Historial de comandos de lando por proyecto
+#[derive(Debug, Default)]
+pub struct CommandJournal {
+    pub projects: HashMap<PathBuf, ProjectJournal>,
+    pub limit: usize, // Entradas por proyecto; al pasarse se descartan las más antiguas
+}
+
+// Lo que pide el panel del historial al resto de la app
+#[derive(Debug, Clone, PartialEq)]
+pub enum JournalAction {
+    Rerun(Invocation),
+    ShowOutput { title: String, output: String },
+}
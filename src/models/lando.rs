@@ -1,5 +1,7 @@
 use serde::Deserialize;
 
+use crate::models::secret::SecretString;
+
 #[derive(Deserialize, Clone, Debug, Default)]
 pub struct LandoApp {
     #[serde(default)]
@@ -8,6 +10,7 @@ pub struct LandoApp {
     pub location: String,
     #[serde(default)]
     pub urls: Vec<String>,
+    #[serde(default)]
     pub running: bool,
 }
 
@@ -26,12 +29,30 @@ pub struct LandoService {
     pub external_connection: Option<ServiceConnectionInfo>,
     #[serde(default)]
     pub creds: Option<ServiceCreds>,
+    // Objeto completo de `lando info`, con los campos que este struct no modela
+    #[serde(skip)]
+    pub raw: serde_json::Value,
+    // Motor de BD real (`mysql`, `postgres`...) aunque el tipo sea `compose` o
+    // una receta propia; lo rellena `LandoGui::apply_db_engines`
+    #[serde(skip)]
+    pub resolved_db_engine: Option<String>,
+    #[serde(skip)]
+    pub db_engine_override: Option<String>, // Elegido a mano en la interfaz
+}
+
+impl LandoService {
+    // Lo que usan las plantillas, dialectos y consultas en lugar de `r#type`
+    pub fn db_engine(&self) -> &str {
+        self.resolved_db_engine.as_deref().unwrap_or(&self.r#type)
+    }
 }
 
 // Información de conexión para un servicio
 #[derive(Deserialize, Clone, Debug, Default)]
 pub struct ServiceConnectionInfo {
+    #[serde(default)]
     pub host: String,
+    #[serde(default)]
     pub port: String,
 }
 
@@ -39,6 +60,16 @@ pub struct ServiceConnectionInfo {
 #[derive(Deserialize, Clone, Debug, Default)]
 pub struct ServiceCreds {
     pub user: Option<String>,
-    pub password: Option<String>,
+    pub password: Option<SecretString>,
+    pub database: Option<String>,
+}
+
+// Servicio y credenciales con las que se lanza `lando db-cli`. Sin usuario se
+// deja que lando use el suyo por defecto.
+#[derive(Clone, Debug, Default)]
+pub struct DbConnection {
+    pub service: String,
+    pub user: Option<String>,
+    pub password: Option<SecretString>,
     pub database: Option<String>,
 }
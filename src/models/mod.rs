@@ -1,3 +1,25 @@
 pub(crate) mod lando;
 pub(crate) mod commands;
-pub(crate) mod app;
\ No newline at end of file
+pub(crate) mod app;
+pub(crate) mod toasts;
+pub(crate) mod recipes;
+pub(crate) mod prompts;
+pub(crate) mod processes;
+pub(crate) mod errors;
+pub(crate) mod db_session;
+pub(crate) mod backups;
+pub(crate) mod graph;
+pub(crate) mod csv_import;
+pub(crate) mod health;
+pub(crate) mod search;
+pub(crate) mod bulk;
+pub(crate) mod status;
+pub(crate) mod phases;
+pub(crate) mod notes;
+pub(crate) mod varnish;
+pub(crate) mod dashboard;
+pub(crate) mod secret;
+pub(crate) mod resources;
+pub(crate) mod journal;
+pub(crate) mod drift;
+pub(crate) mod terminal;
\ No newline at end of file
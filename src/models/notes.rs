@@ -0,0 +1,43 @@
+use std::path::PathBuf;
+use std::time::{Instant, SystemTime};
+
+use serde::{Deserialize, Serialize};
+
+// Dónde vive el fichero de notas de cada proyecto
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum NotesLocation {
+    #[default]
+    Project, // `.lando-gui-notes.md` en la raíz del proyecto
+    AppData, // Directorio de datos de la app, fuera del repositorio
+}
+
+impl NotesLocation {
+    pub fn label(self) -> &'static str {
+        match self {
+            NotesLocation::Project => "📂 En el proyecto",
+            NotesLocation::AppData => "🗃️ En los datos de la app",
+        }
+    }
+}
+
+// Notas del proyecto abierto, con el estado del autoguardado
+#[derive(Debug, Clone)]
+pub struct ProjectNotes {
+    pub project: PathBuf,
+    pub path: Option<PathBuf>, // None si no hay directorio de datos
+    pub text: String,
+    pub editing: bool,
+    pub dirty_since: Option<Instant>, // Primera edición sin guardar
+    pub modified: Option<SystemTime>, // Fecha del fichero tras la última lectura o escritura
+    pub changed_on_disk: bool, // Otro programa lo modificó desde que se cargó
+    pub last_disk_check: Instant,
+    pub error: Option<String>,
+}
+
+// Línea de unas notas que coincide con la búsqueda global
+#[derive(Debug, Clone, PartialEq)]
+pub struct NoteMatch {
+    pub project: PathBuf,
+    pub line_number: usize,
+    pub line: String,
+}
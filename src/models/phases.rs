@@ -0,0 +1,29 @@
+use std::time::Instant;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PhaseState {
+    Running,
+    Done,
+    Failed,
+}
+
+// Fase reconocida en la salida de `lando start`/`rebuild`/`destroy`
+#[derive(Debug, Clone)]
+pub struct BuildPhase {
+    pub key: String,   // `pull:appserver`, `containers`... para no repetir la misma fase
+    pub label: String, // Texto mientras está en curso
+    pub done_label: String,
+    pub started: Instant,
+    pub finished: Option<Instant>,
+    pub state: PhaseState,
+}
+
+// Progreso del comando largo en curso; la salida sigue yendo al terminal igual
+#[derive(Debug, Clone)]
+pub struct PhaseTracker {
+    pub command: String,
+    pub phases: Vec<BuildPhase>,
+    pub started: Instant,
+    pub result: Option<bool>, // `Some` al terminar el comando: si fue bien
+    pub line_buffer: String,  // Línea aún sin `\n`
+}
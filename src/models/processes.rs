@@ -0,0 +1,14 @@
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+// Proceso `lando` de larga duración (rebuild, logs --follow, ssh...). Se
+// apunta en el fichero de estado mientras vive para poder detectarlo como
+// huérfano si la app se cierra sin terminarlo.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunningProcess {
+    pub pid: u32,
+    pub command: String,
+    pub project: PathBuf,
+    pub started_at: u64,
+}
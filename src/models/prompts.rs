@@ -0,0 +1,31 @@
+use std::sync::atomic::AtomicBool;
+use std::sync::mpsc::Sender;
+use std::sync::Arc;
+use std::time::Instant;
+
+// Tipo de respuesta que espera una pregunta de lando
+#[derive(Debug, Clone, PartialEq)]
+pub enum PromptKind {
+    Confirm { default_yes: bool }, // `? ... (y/N)`
+    Choice { options: Vec<String>, selected: usize }, // Menú de inquirer con `❯`
+    Text, // `? Nombre de la app?` sin salto de línea
+}
+
+// Pregunta detectada en la salida de un comando
+#[derive(Debug, Clone, PartialEq)]
+pub struct LandoPrompt {
+    pub text: String,
+    pub kind: PromptKind,
+}
+
+// Comando de lando en curso con stdin abierto: permite responder a sus
+// preguntas o cancelarlo si parece bloqueado.
+#[derive(Debug)]
+pub struct InteractiveCommand {
+    pub command: String,
+    pub input: Sender<Vec<u8>>,
+    pub cancel: Arc<AtomicBool>,
+    pub last_output: Instant,
+    pub prompt: Option<LandoPrompt>,
+    pub answer: String, // Texto escrito en el modal para preguntas abiertas
+}
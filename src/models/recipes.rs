@@ -0,0 +1,84 @@
+// Acciones rápidas por receta de Lando. Para añadir una receta basta con
+// declarar su lista aquí: la tarjeta del appserver las pinta tal cual.
+
+// Campo de texto que el usuario rellena antes de ejecutar la acción.
+// Su valor sustituye a `{key}` en los argumentos del comando.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QuickActionField {
+    pub key: &'static str,
+    pub label: &'static str,
+    pub placeholder: &'static str,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QuickAction {
+    pub label: &'static str,
+    pub command: &'static [&'static str], // Argumentos de `lando`, con `{key}` para los campos
+    pub needs_confirmation: bool,
+    pub form_fields: &'static [QuickActionField],
+    pub interactive: bool, // Se abre en el terminal en vez de transmitir la salida
+}
+
+impl QuickAction {
+    const fn new(label: &'static str, command: &'static [&'static str]) -> Self {
+        Self { label, command, needs_confirmation: false, form_fields: &[], interactive: false }
+    }
+
+    const fn confirmed(mut self) -> Self {
+        self.needs_confirmation = true;
+        self
+    }
+
+    const fn with_fields(mut self, fields: &'static [QuickActionField]) -> Self {
+        self.form_fields = fields;
+        self
+    }
+
+    const fn interactive(mut self) -> Self {
+        self.interactive = true;
+        self
+    }
+}
+
+const DRUPAL_ACTIONS: &[QuickAction] = &[
+    QuickAction::new("🧹 drush cr", &["drush", "cr"]),
+    QuickAction::new("🔑 drush uli", &["drush", "uli"]),
+    QuickAction::new("⬆️ drush updb", &["drush", "updb", "-y"]).confirmed(),
+    QuickAction::new("📤 drush cex", &["drush", "cex", "-y"]).confirmed(),
+    QuickAction::new("📥 drush cim", &["drush", "cim", "-y"]).confirmed(),
+];
+
+const LARAVEL_ACTIONS: &[QuickAction] = &[
+    QuickAction::new("🗃️ artisan migrate", &["artisan", "migrate"]).confirmed(),
+    QuickAction::new("🧹 artisan cache:clear", &["artisan", "cache:clear"]),
+    QuickAction::new("🐚 artisan tinker", &["artisan", "tinker"]).interactive(),
+];
+
+const WORDPRESS_ACTIONS: &[QuickAction] = &[
+    QuickAction::new("🧹 wp cache flush", &["wp", "cache", "flush"]),
+    QuickAction::new("👥 wp user list", &["wp", "user", "list"]),
+    QuickAction::new("🔁 wp search-replace", &["wp", "search-replace", "{old_url}", "{new_url}"])
+        .confirmed()
+        .with_fields(&[
+            QuickActionField { key: "old_url", label: "URL actual", placeholder: "https://ejemplo.com" },
+            QuickActionField { key: "new_url", label: "URL nueva", placeholder: "https://ejemplo.lndo.site" },
+        ]),
+];
+
+// Familia de receta -> acciones. Las versiones (`drupal10`, `drupal9`...) se
+// agrupan quitando los dígitos finales.
+const RECIPE_QUICK_ACTIONS: &[(&str, &[QuickAction])] = &[
+    ("drupal", DRUPAL_ACTIONS),
+    ("laravel", LARAVEL_ACTIONS),
+    ("wordpress", WORDPRESS_ACTIONS),
+];
+
+// Las recetas desconocidas no tienen acciones extra
+pub fn quick_actions_for(recipe: &str) -> Vec<QuickAction> {
+    let family = recipe.trim().to_lowercase();
+    let family = family.trim_end_matches(|c: char| c.is_ascii_digit());
+    RECIPE_QUICK_ACTIONS.iter()
+        .find(|(name, _)| *name == family)
+        .map(|(_, actions)| actions.to_vec())
+        .unwrap_or_default()
+}
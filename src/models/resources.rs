@@ -0,0 +1,56 @@
+use std::path::PathBuf;
+
+use crate::ui::confirm::ConfirmDialog;
+
+// Claves de `services.<nombre>.overrides` que edita la sección de recursos
+pub const MEM_LIMIT_KEY: &str = "mem_limit";
+pub const CPUS_KEY: &str = "cpus";
+pub const SHM_SIZE_KEY: &str = "shm_size";
+
+// Límites tal y como están escritos en el `.lando.yml` (None: sin límite)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ResourceLimits {
+    pub mem_limit: Option<String>,
+    pub cpus: Option<String>,
+    pub shm_size: Option<String>,
+}
+
+// Consumo real del contenedor según `docker stats`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum ResourceUsage {
+    #[default]
+    Unknown,
+    Loading(u64), // Id de la petición en curso
+    Stopped,
+    Measured { cpu: f64, memory: String, memory_percent: f64 },
+    Failed(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DiffLine {
+    Same(String),
+    Added(String),
+    Removed(String),
+}
+
+// Cambio del `.lando.yml` pendiente de confirmar
+#[derive(Debug, Clone)]
+pub struct ResourcePreview {
+    pub new_content: String,
+    pub diff: Vec<DiffLine>,
+    pub comments_preserved: bool, // false si hubo que regenerar el YAML entero
+}
+
+// Sección "Recursos" de cada servicio
+#[derive(Debug, Default)]
+pub struct ResourceEditor {
+    pub project: Option<PathBuf>, // Proyecto del que se leyeron los límites
+    pub saved: ResourceLimits,
+    pub mem_limit: String,
+    pub cpus: String,
+    pub shm_size: String,
+    pub usage: ResourceUsage,
+    pub preview: Option<ResourcePreview>,
+    pub pending_rebuild: Option<ConfirmDialog>,
+    pub message: Option<Result<String, String>>,
+}
@@ -0,0 +1,39 @@
+// Motores de búsqueda con panel propio
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SearchEngine {
+    Solr,
+    Elasticsearch, // También OpenSearch, que habla la misma API
+}
+
+impl SearchEngine {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Solr => "Solr",
+            Self::Elasticsearch => "Elasticsearch",
+        }
+    }
+
+    // Cómo se llama a cada colección en la UI
+    pub fn index_noun(&self) -> &'static str {
+        match self {
+            Self::Solr => "core",
+            Self::Elasticsearch => "índice",
+        }
+    }
+}
+
+// Core de Solr o índice de Elasticsearch
+#[derive(Debug, Clone)]
+pub struct SearchIndex {
+    pub name: String,
+    pub docs: Option<u64>,
+    pub size: Option<String>,   // Tal como lo da el servidor ("1.2 MB", "3.4kb")
+    pub health: Option<String>, // Solo Elasticsearch: green/yellow/red
+}
+
+// Acciones sobre un core/índice que pasan por confirmación
+#[derive(Debug, Clone, PartialEq)]
+pub enum SearchAction {
+    Reload(String),    // Solr: recargar el core; ES: refrescar el índice
+    DeleteAll(String), // Borrar todos los documentos, dejando el esquema
+}
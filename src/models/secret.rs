@@ -0,0 +1,71 @@
+use std::fmt;
+
+use serde::Deserialize;
+
+// Contraseña que no se puede imprimir por descuido: `Debug` la oculta, no
+// implementa `Display` ni `Serialize` y el valor real solo sale con `expose`
+#[derive(Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(transparent)]
+pub struct SecretString(String);
+
+impl SecretString {
+    pub fn new(value: impl Into<String>) -> Self {
+        Self(value.into())
+    }
+
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+
+    // Para editarla en un `TextEdit` sin copiarla a un `String` suelto
+    pub fn expose_mut(&mut self) -> &mut String {
+        &mut self.0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl From<String> for SecretString {
+    fn from(value: String) -> Self {
+        Self(value)
+    }
+}
+
+impl fmt::Debug for SecretString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SecretString(***)")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::lando::{DbConnection, ServiceCreds};
+
+    #[test]
+    fn debug_hides_the_value() {
+        let secret = SecretString::new("s3cret");
+        assert_eq!(format!("{:?}", secret), "SecretString(***)");
+        assert_eq!(format!("{:?}", Some(secret.clone())), "Some(SecretString(***))");
+        assert_eq!(secret.expose(), "s3cret");
+    }
+
+    #[test]
+    fn debug_of_credentials_hides_the_password() {
+        let creds: ServiceCreds = serde_json::from_str(r#"{"user": "app", "password": "s3cret", "database": "blog"}"#).unwrap();
+        let printed = format!("{:?}", creds);
+        assert!(!printed.contains("s3cret"), "{}", printed);
+        assert!(printed.contains("app"));
+
+        let connection = DbConnection { service: "database".to_string(), password: creds.password, ..Default::default() };
+        assert!(!format!("{:?}", connection).contains("s3cret"));
+    }
+
+    #[test]
+    fn deserializes_from_a_plain_string() {
+        let secret: SecretString = serde_json::from_str(r#""s3cret""#).unwrap();
+        assert_eq!(secret.expose(), "s3cret");
+    }
+}
@@ -0,0 +1,7 @@
+// Versiones de las herramientas y estado del daemon de Docker, para la barra de estado
+#[derive(Debug, Clone, Default)]
+pub struct ToolVersions {
+    pub lando: Option<String>,
+    pub docker: Option<String>, // Del servidor si responde; si no, la del cliente
+    pub docker_running: bool,
+}
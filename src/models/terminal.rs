@@ -0,0 +1,11 @@
+use eframe::egui;
+use egui_term::TerminalBackend;
+
+// El terminal (y su PTY) se crea la primera vez que se muestra. Hasta
+// entonces lo que se escribe se guarda en `pending` y se vuelca al crearlo.
+pub struct LazyTerminal {
+    pub ctx: egui::Context,
+    pub backend: Option<TerminalBackend>,
+    pub pending: Vec<Vec<u8>>,
+    pub error: Option<String>, // No se pudo crear; no se vuelve a intentar
+}
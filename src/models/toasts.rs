@@ -0,0 +1,40 @@
+use std::time::Instant;
+
+use crate::models::errors::ErrorFix;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ToastLevel {
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+
+// Botón opcional que acompaña a una notificación
+#[derive(Debug, Clone, PartialEq)]
+pub enum ToastAction {
+    ShowLog,            // "Ver log": abre el terminal
+    CopyDetail(String), // "Copiar detalle": texto completo de un mensaje truncado
+    Fix(ErrorFix),      // Solución sugerida para un error reconocido
+}
+
+#[derive(Debug, Clone)]
+pub struct Toast {
+    pub id: u64,
+    pub level: ToastLevel,
+    pub title: String,
+    pub body: String,
+    pub action: Option<ToastAction>,
+    pub created_at: Instant,
+    pub repeat_count: u32, // Veces que llegó el mismo mensaje mientras seguía visible
+}
+
+// Cola de notificaciones visibles más el historial de todo lo recibido
+#[derive(Debug, Default)]
+pub struct ToastManager {
+    pub active: Vec<Toast>,
+    pub history: Vec<Toast>,
+    pub unread: usize,
+    pub show_history: bool,
+    pub next_id: u64,
+}
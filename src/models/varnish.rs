@@ -0,0 +1,42 @@
+// Contadores de `varnishstat` que interesan en el panel
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct VarnishStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub passes: u64,
+    pub requests: u64,
+    pub objects: Option<u64>,
+    pub uptime: Option<u64>, // Segundos
+}
+
+impl VarnishStats {
+    // Desde que arrancó varnish; la tasa entre dos lecturas se calcula aparte
+    pub fn hit_rate(&self) -> Option<f64> {
+        let lookups = self.hits + self.misses;
+        (lookups > 0).then(|| self.hits as f64 / lookups as f64)
+    }
+}
+
+// Una línea de `varnishncsa`
+#[derive(Debug, Clone, PartialEq)]
+pub struct VarnishRequest {
+    pub method: String,
+    pub url: String,
+    pub status: String,
+    pub handling: String, // hit, miss, pass, pipe, synth
+}
+
+// Qué se pidió con cada comando en vuelo
+#[derive(Debug, Clone, PartialEq)]
+pub enum VarnishCommand {
+    Probe,
+    Stats,
+    Requests,
+    Ban(String), // Expresión, para el mensaje de resultado
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BanMode {
+    Url,
+    Regex,
+}
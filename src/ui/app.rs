@@ -1,20 +1,116 @@
 use std::cell::Cell;
 use crate::core::commands::*;
-use crate::models::app::LandoGui;
+use crate::core::dashboard::{find_lando_app, running_projects_except};
+use crate::core::time::now_secs;
+use crate::core::errors::classify_error;
+use crate::core::secret::{set_clear_clipboard, CLIPBOARD_CLEAR_DELAY};
+use crate::core::graph::build_service_graph;
+use crate::core::logging::{redact_secrets, set_log_level, LOG_LEVELS};
+use crate::core::platform::{reveal_in_file_manager, start_docker};
+use crate::core::processes::{active_processes, forget_process, terminate_active_processes, terminate_process};
+use crate::core::prompts::{choice_answer, confirm_answer, set_auto_yes, strip_ansi, text_answer};
+use crate::models::app::{LandoGui, ServicesView, WindowGeometry, AUTO_RETRY_INFO_STORAGE_KEY, AUTO_YES_STORAGE_KEY, CLEAR_CLIPBOARD_STORAGE_KEY, JOURNAL_LIMIT_STORAGE_KEY, LOG_LEVEL_STORAGE_KEY, MAX_RECENT_PROJECTS, NOTES_LOCATION_STORAGE_KEY, NOTIFICATIONS_STORAGE_KEY, PINNED_PROJECTS_STORAGE_KEY, PROJECT_LAST_OPENED_STORAGE_KEY, RECENT_PROJECTS_STORAGE_KEY, THEME_STORAGE_KEY};
+use crate::models::bulk::{BulkRun, BulkStatus};
+use crate::models::dashboard::DashboardAction;
+use crate::models::journal::{Invocation, JournalAction, LogSpan};
+use crate::models::notes::{NotesLocation, ProjectNotes};
+use crate::models::phases::PhaseTracker;
+use crate::models::prompts::{InteractiveCommand, PromptKind};
 use crate::models::commands::LandoCommandOutcome;
 use crate::models::lando::LandoService;
+use crate::core::recipes::detect_app_name;
+use crate::core::notes::{modified_secs, NOTES_AUTOSAVE_DELAY, NOTES_FILE_NAME};
+use crate::core::phases::tracks_phases;
+use crate::core::query_cache::format_cache_age;
+use crate::core::status::check_tool_versions;
+use crate::ui::confirm::{ConfirmDialog, ConfirmResponse, DestroyDialog};
+use crate::ui::diagnostics::FRAME_TARGET_MS;
+use crate::ui::notes::show_markdown;
+use crate::ui::phases::show_phase_list;
+use crate::ui::status_bar::{StatusBarAction, DOCKER_CHECK_INTERVAL};
+use crate::ui::toasts::ToastRequest;
+use crate::ui::wizard::{NewProjectWizard, WizardResponse};
+use crate::ui::theme::{error_color, focus_outline, muted_color, success_color, warning_color};
+use crate::models::toasts::ToastAction;
+use crate::models::errors::ErrorFix;
 use eframe::egui;
-use egui_term::{BackendCommand, TerminalView};
+use egui_term::TerminalView;
+use regex::RegexBuilder;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 use std::thread;
+use std::time::{Duration, Instant};
+
+// Listas de proyectos de la barra lateral que se pintan con `render_projects_list`
+#[derive(Clone, Copy)]
+enum ProjectList {
+    Pinned,
+    Discovered,
+}
+
+const REFRESH_DEBOUNCE: Duration = Duration::from_millis(1000);
+const NOTIFY_MIN_DURATION: Duration = Duration::from_secs(5);
+const INPUT_WATCHDOG: Duration = Duration::from_secs(60);
+// `lando list` puede tardar varios segundos con Docker dormido: no se lanza
+// hasta que se ve el panel o la lista de apps, o pasado este margen
+const APPS_LIST_DELAY: Duration = Duration::from_secs(2);
+// Máximo que se espera a que terminen los comandos cancelados al salir
+const EXIT_CANCEL_TIMEOUT: Duration = Duration::from_secs(5);
 
 impl eframe::App for LandoGui {
-    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+    fn update(&mut self, ctx: &egui::Context, frame: &mut eframe::Frame) {
+        self.frame_time.record(frame.info().cpu_usage);
         self.handle_receiver_messages(ctx);
+        self.tick_query_schedules(ctx);
+        self.stop_hidden_query_watches();
+        self.handle_dropped_files(ctx);
+        self.handle_global_shortcuts(ctx);
+        self.handle_close_request(ctx);
         self.show_terminal_popup(ctx);
+        self.show_pending_lando_command(ctx);
+        self.show_pending_destroy(ctx);
+        self.show_pending_bulk(ctx);
+        self.show_bulk_window(ctx);
+        self.show_interactive_prompts(ctx);
+        self.show_settings_window(ctx);
+        self.diagnostics.show(ctx);
+        self.show_new_project_wizard(ctx);
+        self.show_notes_search(ctx);
+        self.show_journal_output(ctx);
+        self.show_toasts(ctx);
 
         self.show_top_panel(ctx);
+        self.show_status_bar(ctx);
         self.show_side_panel(ctx);
         self.show_central_panel(ctx);
+        self.show_drop_overlay(ctx);
+        self.frame_time.show(ctx);
+
+        // Después de procesar los mensajes: los resultados ya están en su DatabaseUI
+        self.show_detached_database_window(ctx);
+
+        self.schedule_apps_list(ctx);
+        if let Some(startup) = self.startup.take() {
+            startup.finish();
+        }
+    }
+
+    fn save(&mut self, storage: &mut dyn eframe::Storage) {
+        eframe::set_value(storage, THEME_STORAGE_KEY, &self.theme);
+        eframe::set_value(storage, AUTO_YES_STORAGE_KEY, &self.auto_yes_enabled);
+        eframe::set_value(storage, AUTO_RETRY_INFO_STORAGE_KEY, &self.auto_retry_info);
+        eframe::set_value(storage, NOTIFICATIONS_STORAGE_KEY, &self.notifications_enabled);
+        eframe::set_value(storage, CLEAR_CLIPBOARD_STORAGE_KEY, &self.clear_clipboard_secrets);
+        eframe::set_value(storage, PINNED_PROJECTS_STORAGE_KEY, &self.pinned_projects);
+        eframe::set_value(storage, RECENT_PROJECTS_STORAGE_KEY, &self.recent_projects);
+        eframe::set_value(storage, PROJECT_LAST_OPENED_STORAGE_KEY, &self.project_last_opened);
+        eframe::set_value(storage, LOG_LEVEL_STORAGE_KEY, &self.log_level.as_str());
+        eframe::set_value(storage, NOTES_LOCATION_STORAGE_KEY, &self.notes_location);
+        eframe::set_value(storage, JOURNAL_LIMIT_STORAGE_KEY, &self.command_journal.limit);
+        if let Some(notes) = &mut self.notes {
+            notes.flush();
+        }
+        self.save_detached_window_geometry();
     }
 }
 
@@ -22,59 +118,766 @@ impl LandoGui {
     fn handle_receiver_messages(&mut self, ctx: &egui::Context) {
         if let Ok(outcome) = self.receiver.try_recv() {
             self.is_loading.set(false);
-            self.error_message = None;
-            self.success_message = None;
 
             match outcome {
                 LandoCommandOutcome::List(apps) => self.apps = apps,
+                LandoCommandOutcome::OrphanedProcesses(processes) => {
+                    // Los lanzados en esta sesión mientras se buscaban no son huérfanos
+                    let active = active_processes();
+                    self.orphaned_processes = processes.into_iter()
+                        .filter(|p| !active.iter().any(|a| a.pid == p.pid))
+                        .collect();
+                }
                 LandoCommandOutcome::Projects(new_projects) => {
                     self.projects.extend(new_projects);
                     self.projects.sort();
                     self.projects.dedup();
                 }
-                LandoCommandOutcome::Info(services) => self.services = services,
-                LandoCommandOutcome::DbQueryResult(result) => {
-                    self.handle_db_query_result(result);
-                },
+                LandoCommandOutcome::Info(services) => {
+                    self.info_retry_cancel = None;
+                    self.status_bar.info_retry = None;
+                    self.services = services;
+                    self.apply_db_engines();
+                    self.service_graph.invalidate();
+                }
+                LandoCommandOutcome::DbTaggedResult { service, request_id, output } => {
+                    self.handle_db_query_result(&service, request_id, output);
+                }
+                LandoCommandOutcome::ShellTaggedResult { request_id, output } => {
+                    let mut manager = self.service_ui_manager.borrow_mut();
+                    let handled = manager.node_uis.values_mut()
+                        .any(|node_ui| node_ui.handle_tagged_result(request_id, &output))
+                        || manager.redis_uis.values_mut()
+                            .any(|redis_ui| redis_ui.handle_tagged_result(request_id, &output))
+                        || manager.mongo_uis.values_mut()
+                            .any(|mongo_ui| mongo_ui.handle_tagged_result(request_id, &output))
+                        || manager.proxy_cache_uis.values_mut()
+                            .any(|proxy_ui| proxy_ui.handle_tagged_result(request_id, &output))
+                        || manager.resources.values_mut()
+                            .any(|editor| editor.handle_tagged_result(request_id, &output));
+                    if !handled {
+                        for appserver_ui in manager.appserver_uis.values_mut() {
+                            if appserver_ui.handle_tagged_result(request_id, &output) {
+                                break;
+                            }
+                        }
+                    }
+                }
+                LandoCommandOutcome::ShellStreamChunk { request_id, chunk } => {
+                    let mut manager = self.service_ui_manager.borrow_mut();
+                    let handled = manager.generic_uis.values_mut()
+                        .any(|generic_ui| generic_ui.handle_stream_chunk(request_id, &chunk));
+                    if !handled {
+                        for appserver_ui in manager.appserver_uis.values_mut() {
+                            if appserver_ui.handle_stream_chunk(request_id, &chunk) {
+                                break;
+                            }
+                        }
+                    }
+                }
+                LandoCommandOutcome::ShellStreamFinished { request_id, result } => {
+                    let mut manager = self.service_ui_manager.borrow_mut();
+                    let handled = manager.generic_uis.values_mut()
+                        .any(|generic_ui| generic_ui.handle_stream_finished(request_id, &result));
+                    if !handled {
+                        for appserver_ui in manager.appserver_uis.values_mut() {
+                            if appserver_ui.handle_stream_finished(request_id, &result) {
+                                break;
+                            }
+                        }
+                    }
+                }
                 LandoCommandOutcome::Error(msg) => {
+                    log::error!("{}", redact_secrets(&msg));
+                    self.finish_phase_tracker(&msg, false);
+                    self.command_journal.finish(&msg, false, self.log_buffer.len());
+                    self.finish_interactive_command(&msg);
+                    self.notify_if_long_running(ctx, &msg, false);
                     self.handle_error_message(msg);
                 }
-                LandoCommandOutcome::CommandSuccess(msg) => self.success_message = Some(msg),
+                LandoCommandOutcome::CommandSuccess(msg) => {
+                    self.finish_phase_tracker(&msg, true);
+                    self.command_journal.finish(&msg, true, self.log_buffer.len());
+                    self.finish_interactive_command(&msg);
+                    self.notify_if_long_running(ctx, &msg, true);
+                    self.toasts.success("Completado", &msg);
+                    self.refresh_info_after_start(&msg);
+                    self.config_drift.invalidate();
+                    if self.selected_project_path.is_none() {
+                        // start/stop desde el panel: se actualiza el estado de las tarjetas
+                        self.apps_requested = true;
+                        list_apps(self.sender.clone());
+                    }
+                }
+                LandoCommandOutcome::DashboardCard { project, info } => {
+                    self.dashboard.handle_card(project, info);
+                }
+                LandoCommandOutcome::InfoRetry { attempt, max, next_in, error } => {
+                    if next_in.is_some() {
+                        self.status_bar.info_retry = Some((attempt, max));
+                    } else {
+                        self.info_retry_cancel = None;
+                        self.status_bar.info_retry = None;
+                        let body = format!("lando info sigue fallando tras {} intentos: {}", max, error);
+                        let id = self.toasts.error("Los servicios no responden", &body);
+                        self.toasts.set_action(id, ToastAction::ShowLog);
+                    }
+                }
+                LandoCommandOutcome::ConfigDriftChecked { project, state } => {
+                    self.config_drift.handle_checked(project, state);
+                }
+                LandoCommandOutcome::HealthProbed { service, results } => {
+                    self.service_ui_manager.borrow_mut().handle_health_probed(&service, results);
+                }
+                LandoCommandOutcome::SearchIndicesLoaded { service, result } => {
+                    if let Some(search_ui) = self.service_ui_manager.borrow_mut().search_uis.get_mut(&service) {
+                        search_ui.handle_indices_loaded(result);
+                    }
+                }
+                LandoCommandOutcome::SearchResponse { service, label, result } => {
+                    if let Some(search_ui) = self.service_ui_manager.borrow_mut().search_uis.get_mut(&service) {
+                        search_ui.handle_response(label, result);
+                    }
+                }
+                LandoCommandOutcome::BulkProgress { index, status } => {
+                    if let Some(run) = &mut self.bulk_run {
+                        if let Some(entry) = run.entries.get_mut(index) {
+                            entry.1 = status;
+                        }
+                    }
+                }
+                LandoCommandOutcome::ToolVersions(versions) => {
+                    self.status_bar.handle_versions(versions);
+                }
+                LandoCommandOutcome::BulkFinished => {
+                    self.handle_bulk_finished();
+                }
+                LandoCommandOutcome::DbEngineProbed { service, engine } => {
+                    self.handle_db_engine_probed(&service, engine);
+                }
+                LandoCommandOutcome::DbEngineOverride { service, engine } => {
+                    self.set_db_engine_override(&service, engine);
+                }
+                LandoCommandOutcome::NewProjectFolderPicked(folder) => {
+                    if let Some(wizard) = &mut self.new_project_wizard {
+                        wizard.set_folder(folder);
+                    }
+                }
+                LandoCommandOutcome::ProjectCreated(folder) => {
+                    self.handle_project_created(folder);
+                }
+                LandoCommandOutcome::CommandStarted { command, project, invocation } => {
+                    let log = LogSpan { generation: self.log_generation, start: self.log_buffer.len(), end: None };
+                    if let Some(invocation) = invocation {
+                        self.command_journal.start(project, invocation, command.clone(), log);
+                    }
+                    if tracks_phases(&command) {
+                        self.phase_tracker = Some(PhaseTracker::new(&command));
+                    }
+                    self.running_commands.push((command, Instant::now()));
+                }
                 LandoCommandOutcome::FinishedLoading => { /* No hacer nada */ }
                 LandoCommandOutcome::LogOutput(output) => {
+                    // La salida no indica de qué comando viene: cualquier salida
+                    // reinicia el contador de inactividad de todos
+                    for command in &mut self.interactive_commands {
+                        command.last_output = Instant::now();
+                    }
                     self.handle_log_output(output);
                 }
+                LandoCommandOutcome::SqlFileLoaded { service, path, content } => {
+                    if let Some(key) = self.database_ui_key(&service) {
+                        let mut manager = self.service_ui_manager.borrow_mut();
+                        let database_ui = manager.database_uis.entry(key).or_default();
+                        database_ui.receive_sql_file(path, content);
+                    }
+                }
+                LandoCommandOutcome::OpenTerminal => {
+                    self.show_terminal_popup = true;
+                }
+                LandoCommandOutcome::CommandInput { command, input, cancel } => {
+                    self.interactive_commands.push(InteractiveCommand {
+                        command,
+                        input,
+                        cancel,
+                        last_output: Instant::now(),
+                        prompt: None,
+                        answer: String::new(),
+                    });
+                }
+                LandoCommandOutcome::DbSessionReady { service } => {
+                    if let Some(key) = self.database_ui_key(&service) {
+                        if let Some(database_ui) = self.service_ui_manager.borrow_mut().database_uis.get_mut(&key) {
+                            database_ui.handle_session_ready();
+                        }
+                    }
+                }
+                LandoCommandOutcome::BackupsLoaded { service, result } => {
+                    if let Some(key) = self.database_ui_key(&service) {
+                        if let Some(database_ui) = self.service_ui_manager.borrow_mut().database_uis.get_mut(&key) {
+                            database_ui.handle_backups_loaded(result);
+                        }
+                    }
+                }
+                LandoCommandOutcome::CsvPreviewLoaded { service, result } => {
+                    if let Some(key) = self.database_ui_key(&service) {
+                        if let Some(database_ui) = self.service_ui_manager.borrow_mut().database_uis.get_mut(&key) {
+                            database_ui.handle_csv_preview(result);
+                        }
+                    }
+                }
+                LandoCommandOutcome::DbSessionEnded { service, error } => {
+                    self.handle_db_session_ended(&service, error);
+                }
+                LandoCommandOutcome::PromptDetected { command, prompt } => {
+                    if let Some(interactive) = self.interactive_commands.iter_mut().find(|c| c.command == command) {
+                        interactive.prompt = Some(prompt);
+                        interactive.answer.clear();
+                    }
+                }
+            }
+
+            // Cualquier mensaje limpia `is_loading`, pero si otra consulta sigue
+            // en curso la interfaz no debe parecer libre para lanzar más
+            if self.service_ui_manager.borrow().database_uis.values().any(|ui| ui.has_running_queries()) {
+                self.is_loading.set(true);
             }
         }
     }
 
-    fn handle_db_query_result(&mut self, result: String) {
-        self.db_query_result = Some(result.clone());
-        for database_ui in self.service_ui_manager.take().database_uis.values_mut() {
-            database_ui.process_query_result(result.clone(), false);
+    // Consultas programadas de las BD del proyecto abierto: corren aunque su
+    // interfaz no esté visible y se pausan mientras la app está detenida
+    fn tick_query_schedules(&mut self, ctx: &egui::Context) {
+        let Some(project_path) = self.selected_project_path.clone() else {
+            return;
+        };
+        let app_running = self.is_project_running(&project_path);
+        let services: Vec<LandoService> = self.get_database_services().into_iter().cloned().collect();
+
+        let mut alerts = Vec::new();
+        let mut active = false;
+        {
+            let mut manager = self.service_ui_manager.borrow_mut();
+            for service in &services {
+                let key = format!("{}_{}", service.service, service.r#type);
+                let database_ui = manager.database_uis.entry(key).or_default();
+                database_ui.tick_schedules(service, &project_path, &self.sender, app_running);
+                alerts.append(&mut database_ui.schedule_alerts);
+                active |= database_ui.has_active_schedules();
+            }
+        }
+
+        for alert in alerts {
+            self.toasts.info(&alert.title, &alert.body);
+            if alert.desktop && self.notifications_enabled {
+                send_desktop_notification(alert.title, alert.body);
+            }
+        }
+
+        // Cuenta atrás de la lista y disparo puntual aunque no haya eventos de entrada
+        if active {
+            ctx.request_repaint_after(Duration::from_secs(1));
+        }
+    }
+
+    // Archivos soltados sobre la ventana: carpetas con `.lando.yml` se añaden como
+    // proyectos y los `.sql` van a la interfaz de base de datos abierta.
+    fn handle_dropped_files(&mut self, ctx: &egui::Context) {
+        let dropped = ctx.input(|i| i.raw.dropped_files.clone());
+
+        for file in dropped {
+            let Some(path) = file.path else { continue };
+
+            if path.is_dir() {
+                if is_lando_project(&path) {
+                    self.add_dropped_project(path);
+                } else {
+                    // Carpeta padre: se buscan proyectos dentro como con "Buscar Proyectos"
+                    self.toasts.info(
+                        "Buscando proyectos",
+                        &format!("Escaneando '{}' (hasta {} niveles)", path.display(), PROJECT_SCAN_DEPTH),
+                    );
+                    self.is_loading.set(true);
+                    scan_for_projects(self.sender.clone(), path);
+                }
+                continue;
+            }
+
+            // El propio .lando.yml cuenta como su carpeta
+            let is_lando_file = path.file_name().is_some_and(|name| name == ".lando.yml" || name == ".lando.yaml");
+            if is_lando_file {
+                if let Some(parent) = path.parent() {
+                    self.add_dropped_project(parent.to_path_buf());
+                }
+                continue;
+            }
+
+            let is_sql = path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("sql"));
+            if is_sql {
+                self.handle_dropped_sql_file(path);
+            }
+        }
+    }
+
+    fn add_dropped_project(&mut self, path: std::path::PathBuf) {
+        if self.projects.contains(&path) {
+            return;
+        }
+        self.toasts.info("Proyecto añadido", &path.display().to_string());
+        self.projects.push(path);
+        self.projects.sort();
+    }
+
+    // Velo sobre toda la ventana mientras se arrastran archivos encima
+    fn show_drop_overlay(&self, ctx: &egui::Context) {
+        if ctx.input(|i| i.raw.hovered_files.is_empty()) {
+            return;
+        }
+
+        let text = if self.open_database_interface.is_some() || self.detached_database_interface.is_some() {
+            "📂 Suelta carpetas para añadir proyectos\no archivos .sql para la base de datos abierta"
+        } else {
+            "📂 Suelta carpetas para añadir proyectos"
+        };
+        let painter = ctx.layer_painter(egui::LayerId::new(egui::Order::Foreground, egui::Id::new("drop_overlay")));
+        let screen = ctx.screen_rect();
+        painter.rect_filled(screen, 0.0, egui::Color32::from_black_alpha(160));
+        painter.text(
+            screen.center(),
+            egui::Align2::CENTER_CENTER,
+            text,
+            egui::FontId::proportional(22.0),
+            egui::Color32::WHITE,
+        );
+    }
+
+    fn handle_dropped_sql_file(&mut self, path: std::path::PathBuf) {
+        let Some(service) = self.open_database_interface.as_ref()
+            .or(self.detached_database_interface.as_ref())
+            .and_then(|name| self.services.iter().find(|s| s.service == *name))
+            .cloned()
+        else {
+            self.toasts.warning("Archivo .sql ignorado", "Abre la interfaz de una base de datos para soltar archivos .sql");
+            return;
+        };
+
+        let size = std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+        let service_key = format!("{}_{}", service.service, service.r#type);
+        let mut manager = self.service_ui_manager.borrow_mut();
+        let database_ui = manager.database_uis.entry(service_key).or_default();
+
+        if size <= database_ui.sql_drop_threshold_kb * 1024 {
+            load_sql_file(self.sender.clone(), service.service.clone(), path);
+        } else {
+            database_ui.request_import(&service, &path);
+        }
+    }
+
+    fn database_ui_key(&self, service_name: &str) -> Option<String> {
+        self.services.iter()
+            .find(|s| s.service == service_name)
+            .map(|s| format!("{}_{}", s.service, s.r#type))
+    }
+
+    fn is_project_running(&self, project_path: &std::path::Path) -> bool {
+        find_lando_app(&self.apps, project_path).is_some_and(|app| app.running)
+    }
+
+    // Los mensajes de fin incluyen el comando entre comillas: así se empareja
+    // con su `CommandStarted` y se sabe cuánto tardó
+    fn notify_if_long_running(&mut self, ctx: &egui::Context, message: &str, success: bool) {
+        let Some(index) = self.running_commands.iter()
+            .position(|(command, _)| message.contains(&format!("'{}'", command)))
+        else {
+            return;
+        };
+        let (command, started) = self.running_commands.remove(index);
+
+        let elapsed = started.elapsed();
+        if !self.notifications_enabled || elapsed < NOTIFY_MIN_DURATION {
+            return;
+        }
+        if ctx.input(|i| i.focused) {
+            return;
+        }
+
+        let outcome = if success { "✅ Completado" } else { "❌ Falló" };
+        send_desktop_notification(
+            format!("{}: {}", outcome, command),
+            format!("{} ({} s)", message, elapsed.as_secs()),
+        );
+    }
+
+    fn finish_interactive_command(&mut self, message: &str) {
+        self.interactive_commands.retain(|c| !message.contains(&format!("'{}'", c.command)));
+    }
+
+    // Preguntas de lando pendientes y avisos de comandos que llevan un rato sin salida
+    fn show_interactive_prompts(&mut self, ctx: &egui::Context) {
+        if self.interactive_commands.is_empty() {
+            return;
+        }
+        // Para que el aviso de inactividad aparezca aunque no llegue nada
+        ctx.request_repaint_after(Duration::from_secs(1));
+
+        let mut answers: Vec<(usize, Vec<u8>)> = Vec::new();
+        let mut cancelled: Vec<usize> = Vec::new();
+        let mut keep_waiting: Vec<usize> = Vec::new();
+
+        for (index, command) in self.interactive_commands.iter_mut().enumerate() {
+            if let Some(prompt) = &command.prompt {
+                egui::Window::new("⌨️ Lando necesita una respuesta")
+                    .id(egui::Id::new(("lando_prompt", index)))
+                    .collapsible(false)
+                    .resizable(false)
+                    .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                    .show(ctx, |ui| {
+                        ui.label(format!("Comando: lando {}", command.command));
+                        ui.separator();
+                        ui.strong(&prompt.text);
+
+                        match &prompt.kind {
+                            PromptKind::Confirm { default_yes } => {
+                                ui.horizontal(|ui| {
+                                    if ui.button("✅ Sí").clicked() {
+                                        answers.push((index, confirm_answer(true)));
+                                    }
+                                    if ui.button("❌ No").clicked() {
+                                        answers.push((index, confirm_answer(false)));
+                                    }
+                                    ui.weak(if *default_yes { "(por defecto: sí)" } else { "(por defecto: no)" });
+                                });
+                            }
+                            PromptKind::Choice { options, selected } => {
+                                for (option_index, option) in options.iter().enumerate() {
+                                    if ui.selectable_label(option_index == *selected, option).clicked() {
+                                        answers.push((index, choice_answer(*selected, option_index)));
+                                    }
+                                }
+                            }
+                            PromptKind::Text => {
+                                ui.horizontal(|ui| {
+                                    let response = ui.text_edit_singleline(&mut command.answer);
+                                    let submitted = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                                    if ui.button("📨 Enviar").clicked() || submitted {
+                                        answers.push((index, text_answer(&command.answer)));
+                                    }
+                                });
+                            }
+                        }
+
+                        ui.separator();
+                        if ui.button("⏹ Cancelar comando").clicked() {
+                            cancelled.push(index);
+                        }
+                    });
+            } else if command.last_output.elapsed() >= INPUT_WATCHDOG && !command.cancel.load(Ordering::Relaxed) {
+                egui::Window::new("⏳ ¿Esperando entrada?")
+                    .id(egui::Id::new(("lando_watchdog", index)))
+                    .collapsible(false)
+                    .resizable(false)
+                    .anchor(egui::Align2::RIGHT_BOTTOM, [-10.0, -10.0])
+                    .show(ctx, |ui| {
+                        ui.label(format!(
+                            "'lando {}' lleva {} s sin mostrar salida; puede estar esperando una respuesta.",
+                            command.command,
+                            command.last_output.elapsed().as_secs()
+                        ));
+                        ui.horizontal(|ui| {
+                            if ui.button("↵ Enviar salto de línea").clicked() {
+                                answers.push((index, b"\n".to_vec()));
+                            }
+                            if ui.button("⏳ Seguir esperando").clicked() {
+                                keep_waiting.push(index);
+                            }
+                            if ui.button("⏹ Cancelar").clicked() {
+                                cancelled.push(index);
+                            }
+                        });
+                    });
+            }
+        }
+
+        for (index, bytes) in answers {
+            if let Some(command) = self.interactive_commands.get_mut(index) {
+                let _ = command.input.send(bytes);
+                command.prompt = None;
+                command.answer.clear();
+                command.last_output = Instant::now();
+            }
+        }
+        for index in keep_waiting {
+            if let Some(command) = self.interactive_commands.get_mut(index) {
+                command.last_output = Instant::now();
+            }
+        }
+        for index in cancelled {
+            if let Some(command) = self.interactive_commands.get_mut(index) {
+                command.cancel.store(true, Ordering::Relaxed);
+                command.prompt = None;
+            }
+        }
+    }
+
+    fn show_toasts(&mut self, ctx: &egui::Context) {
+        match self.toasts.show(ctx) {
+            Some(ToastRequest::ShowLog) => self.show_terminal_popup = true,
+            Some(ToastRequest::Fix(fix)) => self.apply_error_fix(fix),
+            None => {}
+        }
+        self.toasts.show_history_window(ctx);
+    }
+
+    fn show_settings_window(&mut self, ctx: &egui::Context) {
+        egui::Window::new("⚙️ Ajustes")
+            .open(&mut self.show_settings)
+            .resizable(false)
+            .collapsible(false)
+            .show(ctx, |ui| {
+                ui.checkbox(&mut self.notifications_enabled, "🔔 Notificaciones del sistema")
+                    .on_hover_text(format!(
+                        "Avisar cuando un comando de más de {} s termine con la ventana en segundo plano",
+                        NOTIFY_MIN_DURATION.as_secs()
+                    ));
+
+                if ui.checkbox(&mut self.auto_yes_enabled, "✅ Añadir -y a destroy y rebuild")
+                    .on_hover_text("Evita que lando se quede esperando una confirmación")
+                    .changed()
+                {
+                    set_auto_yes(self.auto_yes_enabled);
+                }
+                ui.checkbox(&mut self.auto_retry_info, "🔁 Reintentar lando info tras start")
+                    .on_hover_text(format!(
+                        "Si los servicios aún no responden, reintentar hasta {} veces con espera creciente",
+                        INFO_RETRY_ATTEMPTS
+                    ));
+                if ui.checkbox(&mut self.clear_clipboard_secrets, "📋 Vaciar el portapapeles tras copiar una contraseña")
+                    .on_hover_text(format!(
+                        "A los {} s, solo si el portapapeles todavía contiene la contraseña",
+                        CLIPBOARD_CLEAR_DELAY.as_secs()
+                    ))
+                    .changed()
+                {
+                    set_clear_clipboard(self.clear_clipboard_secrets);
+                }
+                ui.horizontal(|ui| {
+                    ui.label("🕘 Comandos en el historial por proyecto:");
+                    let mut limit = self.command_journal.limit;
+                    if ui.add(egui::DragValue::new(&mut limit).range(10..=5000)).changed() {
+                        self.command_journal.set_limit(limit);
+                        self.command_journal.save();
+                    }
+                });
+
+                ui.separator();
+
+                ui.horizontal(|ui| {
+                    ui.label("🎨 Tema:");
+                    let previous = self.theme;
+                    ui.radio_value(&mut self.theme, egui::ThemePreference::System, "💻 Sistema");
+                    ui.radio_value(&mut self.theme, egui::ThemePreference::Light, "☀️ Claro");
+                    ui.radio_value(&mut self.theme, egui::ThemePreference::Dark, "🌙 Oscuro");
+                    if self.theme != previous {
+                        ctx.set_theme(self.theme);
+                    }
+                });
+
+                ui.horizontal(|ui| {
+                    ui.label("🗒️ Notas de proyecto:");
+                    let previous = self.notes_location;
+                    egui::ComboBox::from_id_salt("settings_notes_location")
+                        .selected_text(self.notes_location.label())
+                        .show_ui(ui, |ui| {
+                            for location in [NotesLocation::Project, NotesLocation::AppData] {
+                                ui.selectable_value(&mut self.notes_location, location, location.label());
+                            }
+                        });
+                    if self.notes_location != previous {
+                        // Lo pendiente se guarda donde estaba y se leen las de la nueva ubicación
+                        if let Some(notes) = &mut self.notes {
+                            notes.flush();
+                        }
+                        self.notes = None;
+                    }
+                }).response.on_hover_text(format!("En el proyecto se guardan en `{}` (añádelo al .gitignore si no quieres versionarlas)", NOTES_FILE_NAME));
+
+                ui.horizontal(|ui| {
+                    ui.label("📝 Nivel de log:");
+                    let previous = self.log_level;
+                    egui::ComboBox::from_id_salt("settings_log_level")
+                        .selected_text(self.log_level.as_str())
+                        .show_ui(ui, |ui| {
+                            for level in LOG_LEVELS {
+                                ui.selectable_value(&mut self.log_level, *level, level.as_str());
+                            }
+                        });
+                    if self.log_level != previous {
+                        set_log_level(self.log_level);
+                        log::info!("Nivel de log cambiado a {}", self.log_level);
+                    }
+                });
+
+                ui.checkbox(&mut self.frame_time.enabled, "⏱️ Mostrar tiempo de frame")
+                    .on_hover_text(format!("Depuración: media de tiempo de CPU por frame (objetivo: menos de {} ms)", FRAME_TARGET_MS));
+            });
+    }
+
+    // Solo la interfaz del servicio que lanzó la consulta la procesa: primero
+    // como petición en segundo plano (`pending_requests`) y, si no lo es,
+    // rellenando el resultado provisional que la lanzó
+    fn handle_db_query_result(&mut self, service: &str, request_id: u64, output: Result<String, String>) {
+        let Some(key) = self.database_ui_key(service) else {
+            return;
+        };
+        if let Some(database_ui) = self.service_ui_manager.borrow_mut().database_uis.get_mut(&key) {
+            if database_ui.handle_tagged_result(request_id, &output) {
+                return;
+            }
+        }
+
+        let (text, has_error) = match output {
+            Ok(text) => (text, false),
+            Err(msg) => {
+                self.show_error_toast(&msg);
+                (msg, true)
+            }
+        };
+
+        self.db_query_result = Some(Arc::from(text.as_str()));
+        let Some(key) = self.database_ui_key(service) else {
+            return;
+        };
+        if let Some(database_ui) = self.service_ui_manager.borrow_mut().database_uis.get_mut(&key) {
+            database_ui.process_query_result(request_id, text, has_error);
+        }
+    }
+
+    // Si la sesión murió sola la interfaz vuelve al modo sin estado con un aviso
+    fn handle_db_session_ended(&mut self, service: &str, error: Option<String>) {
+        if let Some(error) = &error {
+            self.toasts.warning("Modo transacción desactivado", error);
+        }
+        let Some(key) = self.database_ui_key(service) else {
+            return;
+        };
+        if let Some(database_ui) = self.service_ui_manager.borrow_mut().database_uis.get_mut(&key) {
+            database_ui.handle_session_ended(error);
+        }
+    }
+
+    // La vigilancia de una consulta solo sigue mientras su interfaz está
+    // abierta; sin proyecto seleccionado no queda ninguna
+    fn stop_hidden_query_watches(&mut self) {
+        let visible: Vec<String> = [&self.open_database_interface, &self.detached_database_interface]
+            .into_iter()
+            .flatten()
+            .filter(|_| self.selected_project_path.is_some())
+            .filter_map(|service| self.database_ui_key(service))
+            .collect();
+        for (key, database_ui) in self.service_ui_manager.borrow_mut().database_uis.iter_mut() {
+            if !visible.contains(key) {
+                database_ui.stop_query_watch(Some("Se cerró la interfaz"));
+            }
+        }
+    }
+
+    // Al cambiar de proyecto o salir, las sesiones abiertas se cierran con ROLLBACK
+    fn close_db_sessions(&mut self) {
+        for database_ui in self.service_ui_manager.borrow_mut().database_uis.values_mut() {
+            database_ui.stop_transaction_mode();
         }
     }
 
     fn handle_error_message(&mut self, msg: String) {
-        self.error_message = Some(msg.clone());
-        if self.db_query_result.is_some() || !self.db_query_input.is_empty() {
-            self.db_query_result = self.error_message.clone();
-            for database_ui in self.service_ui_manager.take().database_uis.values_mut() {
-                database_ui.process_query_result(msg.clone(), true);
+        self.show_error_toast(&msg);
+    }
+
+    // Los errores reconocidos muestran un resumen y la solución sugerida;
+    // el resto se muestra tal cual con acceso al log
+    fn show_error_toast(&mut self, raw: &str) {
+        let error = classify_error(raw);
+        let Some(suggestion) = &error.suggestion else {
+            let id = self.toasts.error("Error", raw);
+            self.toasts.set_action(id, ToastAction::ShowLog);
+            return;
+        };
+
+        let id = self.toasts.error(&error.summary, suggestion);
+        let action = error.fix.map_or(ToastAction::ShowLog, ToastAction::Fix);
+        self.toasts.set_action(id, action);
+    }
+
+    // Tras start/restart/rebuild se vuelven a leer los servicios; con el
+    // reintento activo se espera a que los contenedores respondan
+    fn refresh_info_after_start(&mut self, message: &str) {
+        let started = ["start", "restart", "rebuild"].iter()
+            .any(|command| message.starts_with(&format!("Comando '{}", command)));
+        if !started {
+            return;
+        }
+        let Some(path) = self.selected_project_path.clone() else {
+            return;
+        };
+
+        self.cancel_info_retry();
+        if self.auto_retry_info {
+            let cancel = Arc::new(AtomicBool::new(false));
+            self.info_retry_cancel = Some(cancel.clone());
+            get_project_info_with_retry(self.sender.clone(), path, INFO_RETRY_ATTEMPTS, cancel);
+        } else {
+            get_project_info(self.sender.clone(), path);
+        }
+    }
+
+    fn cancel_info_retry(&mut self) {
+        if let Some(cancel) = self.info_retry_cancel.take() {
+            cancel.store(true, Ordering::Relaxed);
+        }
+        self.status_bar.info_retry = None;
+    }
+
+    fn apply_error_fix(&mut self, fix: ErrorFix) {
+        match fix {
+            ErrorFix::StartApp => {
+                let Some(path) = self.selected_project_path.clone() else {
+                    self.toasts.warning("Sin proyecto", "Selecciona la app que quieres iniciar");
+                    return;
+                };
+                self.is_loading.set(true);
+                run_lando_command(self.sender.clone(), "start".to_string(), path);
+            }
+            ErrorFix::OpenDocker => {
+                if let Err(e) = start_docker() {
+                    self.toasts.error("No se pudo iniciar Docker", &e.to_string());
+                }
             }
         }
     }
 
     fn handle_log_output(&mut self, output: Vec<u8>) {
-        self.log_buffer.push(String::try_from(output.clone().to_owned()).unwrap());
-        if self.terminal_filter.is_empty()
-            || String::from_utf8_lossy(&output).contains(self.terminal_filter.as_str())
-        {
-            self.terminal.borrow_mut().process_command(BackendCommand::Write(output));
+        let text = decode_utf8_stream(&mut self.log_pending_bytes, &output);
+        if text.is_empty() {
+            return;
         }
+
+        if self.terminal_line_matches(&text) {
+            self.terminal.borrow_mut().write(text.clone().into_bytes());
+        }
+        if let Some(tracker) = self.phase_tracker.as_mut().filter(|t| t.result.is_none()) {
+            tracker.feed(&text);
+        }
+        self.log_buffer.push(text);
         self.show_terminal_popup = true;
     }
 
+    fn finish_phase_tracker(&mut self, message: &str, success: bool) {
+        if let Some(tracker) = self.phase_tracker.as_mut() {
+            if tracker.result.is_none() && message.contains(&format!("'{}'", tracker.command)) {
+                tracker.finish(success);
+            }
+        }
+    }
+
     fn show_terminal_popup(&mut self, ctx: &egui::Context) {
         if !self.show_terminal_popup {
             return;
@@ -85,37 +888,97 @@ impl LandoGui {
             .default_width(800.0)
             .default_height(400.0)
             .show(ctx, |ui| {
+                if let Some(tracker) = &self.phase_tracker {
+                    egui::SidePanel::left("phase_list_panel")
+                        .resizable(true)
+                        .default_width(260.0)
+                        .show_inside(ui, |ui| show_phase_list(ui, tracker));
+                }
                 self.render_terminal_controls(ui);
                 ui.separator();
-                TerminalView::new(ui, &mut self.terminal.borrow_mut());
+                let mut terminal = self.terminal.borrow_mut();
+                match terminal.backend() {
+                    Some(backend) => {
+                        TerminalView::new(ui, backend);
+                    }
+                    None => {
+                        let error = terminal.error.clone().unwrap_or_default();
+                        ui.colored_label(error_color(ui.visuals()), format!("❌ No se pudo abrir el terminal: {}", error));
+                    }
+                }
             });
     }
 
     fn render_terminal_controls(&mut self, ui: &mut egui::Ui) {
         ui.horizontal(|ui| {
             ui.label("🔍 Filtro:");
-            if ui.text_edit_singleline(&mut self.terminal_filter).changed() {
+            let mut changed = ui.text_edit_singleline(&mut self.terminal_filter).changed();
+            changed |= ui.checkbox(&mut self.terminal_filter_regex, "Regex").changed();
+            changed |= ui.checkbox(&mut self.terminal_filter_case_insensitive, "Ignorar mayúsculas").changed();
+            if changed {
+                self.compile_terminal_filter();
                 self.reapply_terminal_filter();
             }
             if ui.button("🗑️ Limpiar ").clicked() {
                 self.clear_terminal();
             }
         });
+
+        if let Some(err) = &self.terminal_filter_error {
+            ui.colored_label(warning_color(ui.visuals()), format!("⚠ Regex inválida, se usa búsqueda de texto: {}", err));
+        }
+    }
+
+    fn compile_terminal_filter(&mut self) {
+        self.terminal_filter_compiled = None;
+        self.terminal_filter_error = None;
+
+        if !self.terminal_filter_regex || self.terminal_filter.is_empty() {
+            return;
+        }
+
+        match RegexBuilder::new(&self.terminal_filter)
+            .case_insensitive(self.terminal_filter_case_insensitive)
+            .build()
+        {
+            Ok(regex) => self.terminal_filter_compiled = Some(regex),
+            Err(e) => self.terminal_filter_error = Some(e.to_string()),
+        }
+    }
+
+    // Si la regex no compila se recurre a la búsqueda por subcadena
+    fn terminal_line_matches(&self, line: &str) -> bool {
+        if self.terminal_filter.is_empty() {
+            return true;
+        }
+
+        if let Some(regex) = &self.terminal_filter_compiled {
+            return regex.is_match(line);
+        }
+
+        if self.terminal_filter_case_insensitive {
+            line.to_lowercase().contains(&self.terminal_filter.to_lowercase())
+        } else {
+            line.contains(&self.terminal_filter)
+        }
     }
 
     fn reapply_terminal_filter(&mut self) {
-        self.terminal.borrow_mut().process_command(BackendCommand::Write("clear".into()));
+        self.terminal.borrow_mut().clear();
         for log in &self.log_buffer {
-            if self.terminal_filter.is_empty() || log.contains(&self.terminal_filter) {
-                self.terminal.borrow_mut().process_command(BackendCommand::Write(log.clone().into()));
+            if self.terminal_line_matches(log) {
+                self.terminal.borrow_mut().write(log.clone().into());
             }
         }
     }
 
     fn clear_terminal(&mut self) {
-        self.terminal.borrow_mut().process_command(BackendCommand::Write("clear".into()));
+        self.terminal.borrow_mut().clear();
         self.log_buffer.clear();
+        self.log_generation += 1;
+        self.log_pending_bytes.clear();
         self.terminal_filter.clear();
+        self.compile_terminal_filter();
     }
 
     fn show_top_panel(&mut self, ctx: &egui::Context) {
@@ -129,6 +992,39 @@ impl LandoGui {
         });
     }
 
+    // Va antes que los paneles laterales y el central para ocupar todo el ancho
+    fn show_status_bar(&mut self, ctx: &egui::Context) {
+        if self.status_bar.needs_check() {
+            self.status_bar.checking = true;
+            let known_lando = self.status_bar.versions.as_ref().and_then(|v| v.lando.clone());
+            check_tool_versions(self.sender.clone(), known_lando);
+        }
+        ctx.request_repaint_after(DOCKER_CHECK_INTERVAL);
+
+        let open_interface = self.open_database_interface.as_deref().or(self.detached_database_interface.as_deref());
+        // Con el terminal cerrado los pasos se resumen en un chip
+        let phases = self.phase_tracker.as_ref()
+            .filter(|_| !self.show_terminal_popup)
+            .map(|t| (t.progress(), t.result));
+        let action = self.status_bar.show(ctx, self.selected_project_path.as_deref(), open_interface, &self.running_commands, phases);
+        match action {
+            Some(StatusBarAction::RevealProject(path)) => {
+                if let Err(e) = reveal_in_file_manager(&path) {
+                    self.toasts.warning("No se pudo abrir la carpeta", &e.to_string());
+                }
+            }
+            Some(StatusBarAction::OpenTerminal) => {
+                self.show_terminal_popup = true;
+            }
+            Some(StatusBarAction::OpenDiagnostics) => {
+                if !self.diagnostics.open {
+                    self.diagnostics.toggle();
+                }
+            }
+            None => {}
+        }
+    }
+
     fn render_quick_stats(&self, ui: &mut egui::Ui) {
         ui.label(format!("📦 Apps: {}", self.apps.len()));
         ui.label(format!("📂 Proyectos: {}", self.projects.len()));
@@ -142,10 +1038,27 @@ impl LandoGui {
                 ui.label("Cargando...");
             }
 
-            if ui.button("🔄 Refrescar Todo ").clicked() && !self.is_loading.get() {
+            if ui.button("🔄 Refrescar Todo ").on_hover_text("Ctrl+R / F5").clicked() {
                 self.refresh_all();
             }
 
+            let bell = if self.toasts.unread > 0 {
+                format!("🔔 {}", self.toasts.unread)
+            } else {
+                "🔔".to_string()
+            };
+            if ui.button(bell).on_hover_text("Historial de notificaciones").clicked() {
+                self.toasts.show_history = !self.toasts.show_history;
+            }
+
+            if ui.button("🩺").on_hover_text("Acerca de / Diagnóstico").clicked() {
+                self.diagnostics.toggle();
+            }
+
+            if ui.button("⚙️ Ajustes ").clicked() {
+                self.show_settings = !self.show_settings;
+            }
+
             if ui.button("📟 Terminal ").clicked() {
                 self.show_terminal_popup = !self.show_terminal_popup;
             }
@@ -153,11 +1066,165 @@ impl LandoGui {
             if ui.button("🏠 Home ").clicked() {
                 self.navigate_home();
             }
+
+            ui.separator();
+            let bulk_idle = !self.projects.is_empty() && self.bulk_run.as_ref().is_none_or(|run| run.finished);
+            if ui.add_enabled(bulk_idle, egui::Button::new("⏹️ Parar todos"))
+                .on_hover_text("lando stop en todos los proyectos descubiertos")
+                .clicked()
+            {
+                self.request_bulk("stop", self.projects.clone(), "Parar todos los proyectos", "en todos los proyectos");
+            }
+            if ui.add_enabled(bulk_idle, egui::Button::new("▶️ Arrancar todos"))
+                .on_hover_text("lando start en todos los proyectos descubiertos")
+                .clicked()
+            {
+                self.request_bulk("start", self.projects.clone(), "Arrancar todos los proyectos", "en todos los proyectos");
+            }
+        });
+    }
+
+    fn handle_global_shortcuts(&mut self, ctx: &egui::Context) {
+        let refresh = ctx.input(|i| {
+            i.key_pressed(egui::Key::F5) || (i.modifiers.command && i.key_pressed(egui::Key::R))
+        });
+        if refresh {
+            self.refresh_all();
+        }
+        if ctx.input_mut(|i| i.consume_key(egui::Modifiers::COMMAND, egui::Key::K)) {
+            self.notes_search.toggle();
+        }
+    }
+
+    // Al elegir una coincidencia se abre su proyecto con las notas a la vista
+    fn show_notes_search(&mut self, ctx: &egui::Context) {
+        let Some(project) = self.notes_search.show(ctx, &self.projects, self.notes_location) else {
+            return;
+        };
+        let previous = self.selected_project_path.replace(project);
+        self.handle_project_selection_change(previous);
+        if let Some(notes) = &mut self.notes {
+            notes.editing = false;
+        }
+        self.notes_open_request = true;
+    }
+
+    // Cerrar con comandos en ejecución los dejaría huérfanos: se pregunta antes
+    // y, si se cancelan, se espera a que terminen (con un límite) para salir
+    fn handle_close_request(&mut self, ctx: &egui::Context) {
+        if ctx.input(|i| i.viewport().close_requested()) {
+            self.close_db_sessions();
+        }
+        if ctx.input(|i| i.viewport().close_requested()) && !self.allow_close {
+            let running = active_processes();
+            if !running.is_empty() || self.exit_cancel_started.is_some() {
+                ctx.send_viewport_cmd(egui::ViewportCommand::CancelClose);
+                if self.exit_cancel_started.is_none() {
+                    self.exit_dialog_open = true;
+                }
+            }
+        }
+
+        if let Some(started) = self.exit_cancel_started {
+            if active_processes().is_empty() || started.elapsed() > EXIT_CANCEL_TIMEOUT {
+                self.allow_close = true;
+                ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+            } else {
+                ctx.request_repaint_after(Duration::from_millis(100));
+            }
+        }
+
+        if !self.exit_dialog_open && self.exit_cancel_started.is_none() {
+            return;
+        }
+
+        let running = active_processes();
+        egui::Window::new("⚠️ Comandos en ejecución")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                if self.exit_cancel_started.is_some() {
+                    ui.horizontal(|ui| {
+                        ui.spinner();
+                        ui.label(format!("Cancelando {} comandos...", running.len()));
+                    });
+                    return;
+                }
+
+                ui.label(format!("Hay {} comandos en ejecución — ¿cancelarlos antes de salir?", running.len()));
+                for process in &running {
+                    ui.label(format!("• {} (pid {})", process.command, process.pid));
+                }
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui.button("⏹ Cancelar y salir").clicked() {
+                        terminate_active_processes();
+                        self.exit_dialog_open = false;
+                        self.exit_cancel_started = Some(Instant::now());
+                    }
+                    if ui.button("🚪 Salir sin cancelar")
+                        .on_hover_text("Seguirán en marcha; se avisará de ellos en el próximo arranque")
+                        .clicked()
+                    {
+                        self.exit_dialog_open = false;
+                        self.allow_close = true;
+                        ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+                    }
+                    if ui.button("↩️ Volver").clicked() {
+                        self.exit_dialog_open = false;
+                    }
+                });
+            });
+    }
+
+    // Procesos de lando que siguen vivos desde una sesión anterior
+    fn render_orphaned_processes_banner(&mut self, ui: &mut egui::Ui) {
+        if self.orphaned_processes.is_empty() {
+            return;
+        }
+
+        let mut resolved = Vec::new();
+        ui.group(|ui| {
+            ui.colored_label(
+                warning_color(ui.visuals()),
+                format!("⚠️ {} comandos de una sesión anterior siguen en ejecución", self.orphaned_processes.len()),
+            );
+            for process in &self.orphaned_processes {
+                ui.horizontal(|ui| {
+                    ui.monospace(format!("pid {}", process.pid));
+                    ui.label(&process.command);
+                    if !process.project.as_os_str().is_empty() {
+                        ui.label(format!("📁 {}", process.project.display()));
+                    }
+                    if ui.small_button("⏹ Terminar").clicked() {
+                        terminate_process(process.pid);
+                        resolved.push(process.pid);
+                    }
+                    if ui.small_button("Ignorar").on_hover_text("Dejarlo en marcha y no volver a avisar").clicked() {
+                        forget_process(process.pid);
+                        resolved.push(process.pid);
+                    }
+                });
+            }
         });
+        ui.separator();
+
+        self.orphaned_processes.retain(|p| !resolved.contains(&p.pid));
     }
 
     fn refresh_all(&mut self) {
+        // Evitar refrescos solapados (doble clic o atajo repetido)
+        if self.is_loading.get() {
+            return;
+        }
+        if self.last_refresh.is_some_and(|t| t.elapsed() < REFRESH_DEBOUNCE) {
+            return;
+        }
+        self.last_refresh = Some(Instant::now());
+
         self.is_loading.set(true);
+        self.apps_requested = true;
         list_apps(self.sender.clone());
         if let Some(path) = &self.selected_project_path {
             get_project_info(self.sender.clone(), path.clone());
@@ -165,11 +1232,10 @@ impl LandoGui {
     }
 
     fn navigate_home(&mut self) {
+        self.close_db_sessions();
         self.selected_project_path = None;
         self.services.clear();
         self.db_query_result = None;
-        self.error_message = None;
-        self.success_message = None;
     }
 
     fn show_side_panel(&mut self, ctx: &egui::Context) {
@@ -186,6 +1252,16 @@ impl LandoGui {
                 self.render_database_services_section(ui);
                 ui.separator();
 
+                if !self.pinned_projects.is_empty() {
+                    self.render_pinned_projects_section(ui);
+                    ui.separator();
+                }
+
+                if !self.recent_projects.is_empty() {
+                    self.render_recent_projects_section(ui);
+                    ui.separator();
+                }
+
                 self.render_discovered_projects_section(ui);
                 ui.separator();
 
@@ -210,6 +1286,15 @@ impl LandoGui {
                     });
                 }
 
+                if ui.button("➕ Añadir proyecto ").on_hover_text("Añadir una carpeta con .lando.yml sin escanear").clicked() && !self.is_loading.get() {
+                    self.is_loading.set(true);
+                    add_project_folder(self.sender.clone());
+                }
+
+                if ui.button("➕ Nuevo proyecto ").clicked() && self.new_project_wizard.is_none() {
+                    self.new_project_wizard = Some(NewProjectWizard::default());
+                }
+
                 if ui.small_button("🗑️").on_hover_text("Limpiar lista ").clicked() {
                     self.clear_projects_list();
                 }
@@ -217,18 +1302,53 @@ impl LandoGui {
         });
     }
 
+    fn show_new_project_wizard(&mut self, ctx: &egui::Context) {
+        let Some(wizard) = &mut self.new_project_wizard else {
+            return;
+        };
+
+        match wizard.show(ctx, &self.sender) {
+            WizardResponse::Pending => {}
+            WizardResponse::Cancelled => self.new_project_wizard = None,
+            WizardResponse::Create => {
+                if let Some(folder) = wizard.folder.clone() {
+                    self.is_loading.set(true);
+                    self.show_terminal_popup = true;
+                    run_lando_init(self.sender.clone(), wizard.init_args(), folder);
+                }
+                self.new_project_wizard = None;
+            }
+        }
+    }
+
+    fn handle_project_created(&mut self, folder: std::path::PathBuf) {
+        if !self.projects.contains(&folder) {
+            self.projects.push(folder.clone());
+            self.projects.sort();
+        }
+
+        let previous_selection = self.selected_project_path.replace(folder);
+        self.handle_project_selection_change(previous_selection);
+    }
+
+    // Los favoritos siguen en su sección, así que si el seleccionado lo es se mantiene
     fn clear_projects_list(&mut self) {
         self.projects.clear();
-        if self.selected_project_path.is_some() {
+        let selected_pinned = self.selected_project_path.as_ref().is_some_and(|p| self.pinned_projects.contains(p));
+        if self.selected_project_path.is_some() && !selected_pinned {
             self.selected_project_path = None;
             self.services.clear();
         }
     }
 
+    // Servicios SQL: redis y mongo tienen su propio panel y no admiten consultas ni `db-export`
     fn get_database_services(&self) -> Vec<&LandoService> {
+        let manager = self.service_ui_manager.borrow();
         self.services.iter()
-            .filter(|s| self.service_ui_manager.borrow_mut().is_database_service(&s.service) ||
-                s.r#type.to_lowercase() == "database")
+            .filter(|s| manager.is_database_service(&s.service) ||
+                s.r#type.to_lowercase() == "database" ||
+                s.resolved_db_engine.is_some())
+            .filter(|s| !manager.is_redis_service(s) && !manager.is_mongo_service(s))
             .collect()
     }
 
@@ -264,7 +1384,14 @@ impl LandoGui {
             ui.label(format!("📊 {}", service_name));
             ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                 if ui.small_button("🚀").on_hover_text("Abrir interfaz ").clicked() {
-                    self.open_database_interface = Some(service_name.to_string());
+                    if self.detached_database_interface.as_deref() == Some(service_name) {
+                        ui.ctx().send_viewport_cmd_to(
+                            Self::database_viewport_id(service_name),
+                            egui::ViewportCommand::Focus,
+                        );
+                    } else {
+                        self.open_database_interface = Some(service_name.to_string());
+                    }
                 }
             });
         });
@@ -274,41 +1401,172 @@ impl LandoGui {
         }
     }
 
+    fn render_pinned_projects_section(&mut self, ui: &mut egui::Ui) {
+        egui::CollapsingHeader::new(format!("⭐ Favoritos ({})", self.pinned_projects.len()))
+            .default_open(true)
+            .show(ui, |ui| {
+                self.render_projects_list(ui, ProjectList::Pinned, "pinned_projects");
+            });
+    }
+
+    // Las rutas que ya no existen se muestran atenuadas y solo se pueden quitar
+    fn render_recent_projects_section(&mut self, ui: &mut egui::Ui) {
+        let mut open = None;
+        let mut remove = None;
+
+        ui.collapsing(format!("🕘 Recientes ({})", self.recent_projects.len()), |ui| {
+            for project_path in &self.recent_projects {
+                let project_name = project_path.file_name().unwrap_or_default().to_string_lossy();
+                let exists = project_path.exists();
+                let is_selected = self.selected_project_path.as_ref() == Some(project_path);
+
+                ui.horizontal(|ui| {
+                    if exists {
+                        if ui.selectable_label(is_selected, format!("🕘 {}", project_name))
+                            .on_hover_text(project_path.to_string_lossy())
+                            .clicked()
+                        {
+                            open = Some(project_path.clone());
+                        }
+                    } else {
+                        ui.add_enabled(false, egui::Label::new(format!("🕘 {}", project_name)))
+                            .on_disabled_hover_text(format!("{} ya no existe", project_path.display()));
+                    }
+                    if ui.small_button("✖").on_hover_text("Quitar de recientes").clicked() {
+                        remove = Some(project_path.clone());
+                    }
+                });
+            }
+        });
+
+        if let Some(project_path) = remove {
+            self.recent_projects.retain(|p| *p != project_path);
+        }
+        if let Some(project_path) = open {
+            if self.selected_project_path.as_ref() == Some(&project_path) {
+                self.is_loading.set(true);
+                get_project_info(self.sender.clone(), project_path);
+            } else {
+                let previous_selection = self.selected_project_path.replace(project_path);
+                self.handle_project_selection_change(previous_selection);
+            }
+        }
+    }
+
+    fn remember_recent_project(&mut self, project_path: &std::path::Path) {
+        self.recent_projects.retain(|p| p != project_path);
+        self.recent_projects.insert(0, project_path.to_path_buf());
+        self.recent_projects.truncate(MAX_RECENT_PROJECTS);
+        self.project_last_opened.insert(project_path.to_path_buf(), now_secs());
+    }
+
     fn render_discovered_projects_section(&mut self, ui: &mut egui::Ui) {
         ui.collapsing(format!("📂 Proyectos Descubiertos ({})", self.projects.len()), |ui| {
             if self.projects.is_empty() {
                 self.render_empty_projects_message(ui);
             } else {
-                self.render_projects_list(ui);
+                self.render_projects_list(ui, ProjectList::Discovered, "discovered_projects");
+                self.render_bulk_selection_bar(ui);
             }
         });
     }
 
+    // Acciones sobre los proyectos marcados; aparece con dos o más
+    fn render_bulk_selection_bar(&mut self, ui: &mut egui::Ui) {
+        let selected: Vec<std::path::PathBuf> = self.projects.iter()
+            .filter(|p| self.bulk_selection.contains(*p))
+            .cloned()
+            .collect();
+        if selected.len() < 2 {
+            return;
+        }
+
+        let idle = self.bulk_run.as_ref().is_none_or(|run| run.finished);
+        ui.group(|ui| {
+            ui.horizontal(|ui| {
+                ui.label(format!("☑ {} seleccionados", selected.len()));
+                if ui.small_button("✖").on_hover_text("Quitar la selección").clicked() {
+                    self.bulk_selection.clear();
+                }
+            });
+            ui.horizontal_wrapped(|ui| {
+                if ui.add_enabled(idle, egui::Button::new("▶️ Arrancar")).on_hover_text("lando start en los seleccionados").clicked() {
+                    self.request_bulk("start", selected.clone(), "Arrancar los proyectos seleccionados", "en la selección");
+                }
+                if ui.add_enabled(idle, egui::Button::new("⏹️ Parar")).on_hover_text("lando stop en los seleccionados").clicked() {
+                    self.request_bulk("stop", selected.clone(), "Parar los proyectos seleccionados", "en la selección");
+                }
+                let others = running_projects_except(&self.apps, &self.projects, &selected);
+                if ui.add_enabled(idle && !others.is_empty(), egui::Button::new(format!("⏹️ Parar el resto ({})", others.len())))
+                    .on_hover_text("lando stop en las apps en marcha que no están seleccionadas")
+                    .clicked()
+                {
+                    self.request_bulk("stop", others, "Parar el resto de proyectos", "en el resto de proyectos");
+                }
+            });
+            ui.checkbox(&mut self.bulk_stop_on_error, "Detener al primer error");
+        });
+    }
+
+    fn toggle_pinned_project(&mut self, project_path: &std::path::Path) {
+        if let Some(index) = self.pinned_projects.iter().position(|p| p == project_path) {
+            self.pinned_projects.remove(index);
+        } else {
+            self.pinned_projects.push(project_path.to_path_buf());
+            self.pinned_projects.sort();
+        }
+    }
+
     fn render_empty_projects_message(&self, ui: &mut egui::Ui) {
         ui.label("💭 No hay proyectos descubiertos ");
         ui.label(r#"Usa el botón "Buscar Proyectos" para encontrarlos "#);
     }
 
-    fn render_projects_list(&mut self, ui: &mut egui::Ui) {
+    fn render_projects_list(&mut self, ui: &mut egui::Ui, list: ProjectList, id_salt: &str) {
         // 1. Primero recolectar todos los datos necesarios (solo lectura)
-        let projects: Vec<_> = self.projects.iter().cloned().collect();
         let previous_selection = self.selected_project_path.clone();
+        let projects = match list {
+            ProjectList::Pinned => &self.pinned_projects,
+            ProjectList::Discovered => &self.projects,
+        };
 
-        // 2. Variable para capturar la nueva selección
+        // 2. Variables para capturar la nueva selección y el favorito cambiado
         let mut new_selection = previous_selection.clone();
+        let mut pin_toggled = None;
+        let mut bulk_toggled = None;
 
         egui::ScrollArea::vertical()
+            .id_salt(id_salt)
             .max_height(200.0)
             .show(ui, |ui| {
-                for project_path in &projects {
-                    let selected = self.render_project_item_ui(ui, project_path, &new_selection);
+                let mut items = Vec::with_capacity(projects.len());
+                let multi_select = matches!(list, ProjectList::Discovered);
+                for project_path in projects {
+                    let (selected, pin_clicked, bulk_clicked, item) =
+                        self.render_project_item_ui(ui, project_path, &new_selection, multi_select);
                     if selected {
                         new_selection = Some(project_path.clone());
                     }
+                    if pin_clicked {
+                        pin_toggled = Some(project_path.clone());
+                    }
+                    if bulk_clicked {
+                        bulk_toggled = Some(project_path.clone());
+                    }
+                    items.push(item);
                 }
+                Self::handle_projects_keyboard(ui, &items);
             });
 
         // 3. Aplicar los cambios fuera del closure
+        if let Some(project_path) = pin_toggled {
+            self.toggle_pinned_project(&project_path);
+        }
+        if let Some(project_path) = bulk_toggled {
+            if !self.bulk_selection.remove(&project_path) {
+                self.bulk_selection.insert(project_path);
+            }
+        }
         if new_selection != previous_selection {
             self.selected_project_path = new_selection.clone();
             self.handle_project_selection_change(previous_selection);
@@ -320,46 +1578,108 @@ impl LandoGui {
         ui: &mut egui::Ui,
         project_path: &std::path::PathBuf,
         current_selection: &Option<std::path::PathBuf>,
-    ) -> bool {
+        multi_select: bool,
+    ) -> (bool, bool, bool, egui::Response) {
         let project_name = project_path.file_name().unwrap_or_default().to_string_lossy();
         let is_selected = current_selection.as_ref() == Some(project_path);
+        let is_pinned = self.pinned_projects.contains(project_path);
 
         let mut was_clicked = false;
         let mut copy_clicked = false;
+        let mut pin_clicked = false;
+        let mut bulk_clicked = false;
+
+        let item = ui.horizontal(|ui| {
+            if multi_select {
+                let mut checked = self.bulk_selection.contains(project_path);
+                if ui.checkbox(&mut checked, "").on_hover_text("Seleccionar para acciones en bloque (Ctrl+clic)").changed() {
+                    bulk_clicked = true;
+                }
+            }
 
-        ui.horizontal(|ui| {
-            if ui.selectable_label(is_selected, format!("📁 {}", project_name)).clicked() {
-                was_clicked = true;
+            // `clicked()` también se dispara con Enter/Espacio cuando tiene el foco
+            let item = ui.selectable_label(is_selected, format!("📁 {}", project_name))
+                .on_hover_text(project_path.to_string_lossy());
+            if item.clicked() {
+                if multi_select && ui.input(|i| i.modifiers.command) {
+                    bulk_clicked = true;
+                } else {
+                    was_clicked = true;
+                }
             }
 
             if ui.small_button("📄").on_hover_text("Copiar ruta ").clicked() {
                 copy_clicked = true;
             }
-        });
+            let (pin_icon, pin_hint) = if is_pinned { ("⭐", "Quitar de favoritos") } else { ("☆", "Añadir a favoritos") };
+            if ui.small_button(pin_icon).on_hover_text(pin_hint).clicked() {
+                pin_clicked = true;
+            }
+            item
+        }).inner;
 
         // Manejar la copia inmediatamente (no afecta el estado de self)
         if copy_clicked {
             ui.ctx().copy_text(project_path.to_string_lossy().to_string());
         }
 
-        was_clicked
+        (was_clicked, pin_clicked, bulk_clicked, item)
+    }
+
+    // ↑/↓ mueven el foco entre proyectos de la lista. Se bloquea la navegación
+    // espacial de egui con flechas para que no salte al botón de copiar.
+    fn handle_projects_keyboard(ui: &egui::Ui, items: &[egui::Response]) {
+        let Some(focused) = items.iter().position(|item| item.has_focus()) else {
+            return;
+        };
+        ui.memory_mut(|m| m.set_focus_lock_filter(items[focused].id, egui::EventFilter {
+            vertical_arrows: true,
+            ..Default::default()
+        }));
+
+        let (up, down) = ui.input(|i| (i.key_pressed(egui::Key::ArrowUp), i.key_pressed(egui::Key::ArrowDown)));
+        let target = if down {
+            Some(focused + 1)
+        } else if up {
+            focused.checked_sub(1)
+        } else {
+            None
+        };
+
+        if let Some(item) = target.and_then(|index| items.get(index)) {
+            item.request_focus();
+            item.scroll_to_me(None);
+        }
     }
     fn handle_project_selection_change(&mut self, previous_path: Option<std::path::PathBuf>) {
         if self.selected_project_path != previous_path {
-            if let Some(path) = &self.selected_project_path {
+            self.close_db_sessions();
+            self.cancel_info_retry();
+            if let Some(path) = self.selected_project_path.clone() {
+                self.remember_recent_project(&path);
                 self.is_loading.set(true);
                 self.services.clear();
                 self.db_query_input.clear();
                 self.db_query_result = None;
-                self.shell_command_input.clear();
-                get_project_info(self.sender.clone(), path.clone());
+                {
+                    // Van por nombre de servicio: otro proyecto puede repetir nombres
+                    let mut manager = self.service_ui_manager.borrow_mut();
+                    manager.health.clear();
+                    manager.search_uis.clear();
+                }
+                get_project_info(self.sender.clone(), path);
             }
         }
     }
 
-    fn render_running_apps_section(&self, ui: &mut egui::Ui) {
-        ui.collapsing(format!("⚙️ Apps en Ejecución ({})", self.apps.len()), |ui| {
-            if self.apps.is_empty() {
+    fn render_running_apps_section(&mut self, ui: &mut egui::Ui) {
+        let response = ui.collapsing(format!("⚙️ Apps en Ejecución ({})", self.apps.len()), |ui| {
+            if !self.apps_requested || (self.apps.is_empty() && self.is_loading.get()) {
+                ui.horizontal(|ui| {
+                    ui.spinner();
+                    ui.label("Consultando lando list...");
+                });
+            } else if self.apps.is_empty() {
                 ui.label("💭 No hay aplicaciones ejecutándose ");
             } else {
                 for app in &self.apps {
@@ -369,6 +1689,29 @@ impl LandoGui {
                 }
             }
         });
+        if response.body_returned.is_some() {
+            self.ensure_apps_listed();
+        }
+    }
+
+    // Pide `lando list` una sola vez en segundo plano, sin el spinner de la cabecera
+    fn ensure_apps_listed(&mut self) {
+        if !self.apps_requested {
+            self.apps_requested = true;
+            list_apps(self.sender.clone());
+        }
+    }
+
+    fn schedule_apps_list(&mut self, ctx: &egui::Context) {
+        if self.apps_requested {
+            return;
+        }
+        let elapsed = self.started_at.elapsed();
+        if elapsed >= APPS_LIST_DELAY {
+            self.ensure_apps_listed();
+        } else {
+            ctx.request_repaint_after(APPS_LIST_DELAY - elapsed);
+        }
     }
 
     fn render_selected_project_info(&self, ui: &mut egui::Ui) {
@@ -386,11 +1729,15 @@ impl LandoGui {
 
     fn show_central_panel(&mut self, ctx: &egui::Context) {
         egui::CentralPanel::default().show(ctx, |ui| {
+            self.render_orphaned_processes_banner(ui);
+
             let selected_path = self.selected_project_path.clone();
             if let Some(selected_path) = selected_path {
                 self.render_project_interface(ui, &selected_path);
-            } else {
+            } else if self.projects.is_empty() && self.pinned_projects.is_empty() && self.recent_projects.is_empty() {
                 self.render_welcome_screen(ui);
+            } else {
+                self.render_dashboard(ui);
             }
 
             ui.separator();
@@ -401,9 +1748,20 @@ impl LandoGui {
         self.render_project_header(ui, selected_path);
         ui.separator();
 
+        let favorite = self.command_journal.show_favorites(ui, selected_path, self.is_loading.get());
+        if let Some(invocation) = favorite {
+            self.rerun_invocation(&invocation, selected_path);
+        }
         self.render_lando_controls(ui, selected_path);
+        self.render_command_journal(ui, selected_path);
+        if self.config_drift.show(ui, selected_path, &self.sender, self.is_loading.get()) {
+            self.is_loading.set(true);
+            run_lando_command(self.sender.clone(), "rebuild".to_string(), selected_path.clone());
+        }
         ui.separator();
 
+        self.render_project_notes(ui, selected_path);
+
         self.render_database_services_interface(ui, selected_path);
 
         self.render_open_database_interface(ui, selected_path);
@@ -422,6 +1780,145 @@ impl LandoGui {
         });
     }
 
+    fn render_project_notes(&mut self, ui: &mut egui::Ui, selected_path: &std::path::PathBuf) {
+        if self.notes.as_ref().is_none_or(|notes| &notes.project != selected_path) {
+            if let Some(notes) = &mut self.notes {
+                notes.flush();
+            }
+            self.notes = Some(ProjectNotes::load(selected_path, self.notes_location));
+        }
+        let location = self.notes_location;
+        let open_request = std::mem::take(&mut self.notes_open_request);
+        let Some(notes) = &mut self.notes else {
+            return;
+        };
+
+        notes.check_disk();
+        if notes.autosave_due() {
+            notes.save();
+        }
+        if notes.dirty_since.is_some() {
+            ui.ctx().request_repaint_after(NOTES_AUTOSAVE_DELAY);
+        }
+
+        let mut reload = false;
+        let lines = notes.text.lines().filter(|l| !l.trim().is_empty()).count();
+        let header = if lines == 0 { "📝 Notas".to_string() } else { format!("📝 Notas ({} líneas)", lines) };
+        egui::CollapsingHeader::new(header)
+            .id_salt("project_notes")
+            .open(open_request.then_some(true))
+            .show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    ui.selectable_value(&mut notes.editing, false, "👁️ Vista previa");
+                    ui.selectable_value(&mut notes.editing, true, "✏️ Editar");
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        if let Some(path) = &notes.path {
+                            if ui.small_button("📂").on_hover_text(path.display().to_string()).clicked() {
+                                if let Err(e) = reveal_in_file_manager(path.parent().unwrap_or(path.as_path())) {
+                                    log::warn!("No se pudo abrir la carpeta de las notas: {}", e);
+                                }
+                            }
+                        }
+                        if notes.dirty_since.is_some() {
+                            ui.colored_label(warning_color(ui.visuals()), "● Sin guardar");
+                        } else if let Some(modified) = notes.modified {
+                            ui.colored_label(muted_color(ui.visuals()), format!("Modificado {}", format_cache_age(modified_secs(modified))));
+                        }
+                    });
+                });
+
+                if notes.changed_on_disk {
+                    ui.horizontal(|ui| {
+                        ui.colored_label(warning_color(ui.visuals()), "⚠️ El fichero cambió en disco desde que se cargó");
+                        if ui.small_button("🔄 Recargar").on_hover_text("Descarta los cambios sin guardar").clicked() {
+                            reload = true;
+                        }
+                        if ui.small_button("💾 Sobrescribir").clicked() {
+                            notes.save();
+                        }
+                    });
+                }
+                if let Some(error) = &notes.error {
+                    ui.colored_label(error_color(ui.visuals()), format!("❌ {}", error));
+                }
+
+                if notes.editing {
+                    let response = ui.add(
+                        egui::TextEdit::multiline(&mut notes.text)
+                            .code_editor()
+                            .desired_rows(8)
+                            .desired_width(f32::INFINITY)
+                            .hint_text("Rama, URLs, credenciales de prueba... (Markdown)"),
+                    );
+                    if response.changed() {
+                        notes.mark_dirty();
+                    }
+                    if response.lost_focus() {
+                        notes.flush();
+                    }
+                } else if notes.text.trim().is_empty() {
+                    ui.colored_label(muted_color(ui.visuals()), "Sin notas todavía. Pulsa ✏️ Editar para añadirlas.");
+                } else {
+                    egui::ScrollArea::vertical()
+                        .id_salt("project_notes_preview")
+                        .max_height(300.0)
+                        .show(ui, |ui| show_markdown(ui, &notes.text));
+                }
+            });
+
+        if reload {
+            notes.reload(location);
+        }
+        ui.separator();
+    }
+
+    fn render_command_journal(&mut self, ui: &mut egui::Ui, selected_path: &std::path::PathBuf) {
+        let action = self.command_journal.show(ui, selected_path, self.log_generation, &self.log_buffer);
+        match action {
+            Some(JournalAction::Rerun(invocation)) => self.rerun_invocation(&invocation, selected_path),
+            Some(JournalAction::ShowOutput { title, output }) => self.journal_output = Some((title, output)),
+            None => {}
+        }
+    }
+
+    // destroy y poweroff vuelven a pasar por su confirmación
+    fn rerun_invocation(&mut self, invocation: &Invocation, selected_path: &std::path::PathBuf) {
+        if let Invocation::Lando(args) = invocation {
+            match args.first().map(String::as_str) {
+                Some("destroy") => return self.request_destroy(selected_path),
+                Some("poweroff") => return self.request_poweroff(),
+                _ => {}
+            }
+        }
+        self.is_loading.set(true);
+        invocation.run(&self.sender, selected_path);
+    }
+
+    fn show_journal_output(&mut self, ctx: &egui::Context) {
+        let Some((title, output)) = &self.journal_output else {
+            return;
+        };
+
+        let mut open = true;
+        egui::Window::new(format!("📟 {}", title))
+            .id(egui::Id::new("journal_output"))
+            .open(&mut open)
+            .resizable(true)
+            .default_size([640.0, 360.0])
+            .show(ctx, |ui| {
+                if ui.button("📋 Copiar").clicked() {
+                    ui.ctx().copy_text(redact_secrets(output));
+                }
+                ui.separator();
+                egui::ScrollArea::both().stick_to_bottom(true).show(ui, |ui| {
+                    ui.monospace(redact_secrets(&strip_ansi(output)));
+                });
+            });
+        if !open {
+            self.journal_output = None;
+        }
+    }
+
     fn render_lando_controls(&mut self, ui: &mut egui::Ui, selected_path: &std::path::PathBuf) {
         ui.group(|ui| {
             ui.label("⚙️ Controles de Lando:");
@@ -438,16 +1935,224 @@ impl LandoGui {
                     let btn = ui.add_enabled(!self.is_loading.get(),
                                              egui::Button::new(label).fill(color.gamma_multiply(0.1))
                     );
+                    focus_outline(ui, &btn);
 
                     if btn.clicked() {
-                        self.is_loading.set(true);
-                        run_lando_command(self.sender.clone(), cmd.to_string(), selected_path.clone());
+                        if cmd == "poweroff" {
+                            self.request_poweroff();
+                        } else {
+                            self.is_loading.set(true);
+                            run_lando_command(self.sender.clone(), cmd.to_string(), selected_path.clone());
+                        }
                     }
                 }
+
+                let destroy = ui.add_enabled(!self.is_loading.get(),
+                                             egui::Button::new("🧨 Destroy").fill(egui::Color32::DARK_RED.gamma_multiply(0.2))
+                );
+                focus_outline(ui, &destroy);
+                if destroy.on_hover_text("Elimina contenedores y volúmenes de esta app").clicked() {
+                    self.request_destroy(selected_path);
+                }
             });
         });
     }
 
+    // `poweroff` afecta a todas las apps de la máquina: se listan las que están en marcha
+    fn request_poweroff(&mut self) {
+        let running: Vec<String> = self.apps.iter()
+            .filter(|app| app.running)
+            .map(|app| if app.location.is_empty() { app.name.clone() } else { format!("{} ({})", app.name, app.location) })
+            .collect();
+        let description = if running.is_empty() {
+            "Se detendrán TODAS las apps de Lando de esta máquina. Ahora mismo no hay ninguna en marcha.".to_string()
+        } else {
+            format!("Se detendrán TODAS las apps de Lando de esta máquina, no solo la del proyecto actual. En marcha ({}):", running.len())
+        };
+        let dialog = ConfirmDialog::new("Apagar Lando", &description, "lando poweroff", "").with_details(running);
+        self.pending_lando_command = Some(("poweroff".to_string(), dialog));
+    }
+
+    // Arrancar o parar varios proyectos puede tardar minutos: se confirma
+    // con la lista completa a la vista. `scope` completa el título de la ventana
+    // de progreso ("lando stop en la selección").
+    fn request_bulk(&mut self, command: &str, projects: Vec<std::path::PathBuf>, title: &str, scope: &str) {
+        let details = projects.iter().map(|p| p.display().to_string()).collect();
+        let mut description = format!(
+            "Se ejecutará `lando {}` en {} proyectos, uno detrás de otro.",
+            command,
+            projects.len()
+        );
+        if self.bulk_stop_on_error {
+            description.push_str(" Se detendrá al primer error.");
+        }
+        let dialog = ConfirmDialog::new(title, &description, &format!("lando {}", command), "").with_details(details);
+        let run = BulkRun {
+            command: command.to_string(),
+            title: format!("lando {} {}", command, scope),
+            entries: projects.into_iter().map(|p| (p, BulkStatus::Pending)).collect(),
+            cancel: Arc::new(AtomicBool::new(false)),
+            finished: false,
+        };
+        self.pending_bulk = Some((run, dialog));
+    }
+
+    fn show_pending_bulk(&mut self, ctx: &egui::Context) {
+        let Some((_, dialog)) = self.pending_bulk.as_mut() else {
+            return;
+        };
+
+        match dialog.show(ctx) {
+            ConfirmResponse::Pending => {}
+            ConfirmResponse::Cancelled => {
+                self.pending_bulk = None;
+            }
+            ConfirmResponse::Confirmed => {
+                if let Some((run, _)) = self.pending_bulk.take() {
+                    let projects = run.entries.iter().map(|(p, _)| p.clone()).collect();
+                    run_lando_bulk(self.sender.clone(), run.command.clone(), projects, run.cancel.clone(), self.bulk_stop_on_error);
+                    self.bulk_run = Some(run);
+                }
+            }
+        }
+    }
+
+    fn handle_bulk_finished(&mut self) {
+        let Some(run) = &mut self.bulk_run else {
+            return;
+        };
+        run.finished = true;
+        let failed = run.entries.iter().filter(|(_, s)| matches!(s, BulkStatus::Failed(_))).count();
+        let message = format!("{}: {} proyectos, {} con errores", run.title, run.entries.len(), failed);
+        if failed == 0 {
+            self.toasts.success("Operación masiva terminada", &message);
+        } else {
+            self.toasts.warning("Operación masiva terminada", &message);
+        }
+        // El estado de las apps ha cambiado
+        self.last_refresh = None;
+        self.refresh_all();
+    }
+
+    // Progreso por proyecto de la operación masiva en curso o recién terminada
+    fn show_bulk_window(&mut self, ctx: &egui::Context) {
+        let Some(run) = &self.bulk_run else {
+            return;
+        };
+
+        let mut close = false;
+        egui::Window::new(format!("📋 {}", run.title))
+            .id(egui::Id::new("bulk_run"))
+            .collapsible(true)
+            .resizable(true)
+            .default_width(480.0)
+            .show(ctx, |ui| {
+                let done = run.entries.iter().filter(|(_, s)| !matches!(s, BulkStatus::Pending | BulkStatus::Running)).count();
+                ui.add(egui::ProgressBar::new(done as f32 / run.entries.len().max(1) as f32)
+                    .text(format!("{} / {}", done, run.entries.len())));
+                if let Some((current, _)) = run.entries.iter().find(|(_, s)| *s == BulkStatus::Running) {
+                    ui.label(format!("⏳ Procesando {}", current.file_name().unwrap_or_default().to_string_lossy()));
+                } else if run.finished {
+                    let failed = run.entries.iter().filter(|(_, s)| matches!(s, BulkStatus::Failed(_))).count();
+                    let skipped = run.entries.iter().filter(|(_, s)| *s == BulkStatus::Skipped).count();
+                    ui.label(format!("✅ {} correctos · ❌ {} con errores · ⏭️ {} omitidos", done - failed - skipped, failed, skipped));
+                }
+
+                egui::ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+                    for (path, status) in &run.entries {
+                        let name = path.file_name().unwrap_or_default().to_string_lossy();
+                        ui.horizontal(|ui| {
+                            match status {
+                                BulkStatus::Pending => {
+                                    ui.colored_label(muted_color(ui.visuals()), format!("⏳ {}", name));
+                                }
+                                BulkStatus::Running => {
+                                    ui.spinner();
+                                    ui.label(name.to_string());
+                                }
+                                BulkStatus::Done => {
+                                    ui.colored_label(success_color(ui.visuals()), format!("✅ {}", name));
+                                }
+                                BulkStatus::Failed(error) => {
+                                    ui.colored_label(error_color(ui.visuals()), format!("❌ {}", name))
+                                        .on_hover_text(error.as_str());
+                                }
+                                BulkStatus::Skipped => {
+                                    ui.colored_label(muted_color(ui.visuals()), format!("⏭️ {} (omitido)", name));
+                                }
+                            }
+                        }).response.on_hover_text(path.display().to_string());
+                    }
+                });
+
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if run.finished {
+                        if ui.button("Cerrar").clicked() {
+                            close = true;
+                        }
+                    } else if run.cancel.load(Ordering::Relaxed) {
+                        ui.colored_label(warning_color(ui.visuals()), "Se parará tras el proyecto actual…");
+                    } else if ui.button("⏹️ Detener tras el actual").clicked() {
+                        run.cancel.store(true, Ordering::Relaxed);
+                    }
+                });
+            });
+
+        if close {
+            self.bulk_run = None;
+        }
+    }
+
+    fn request_destroy(&mut self, project_path: &std::path::Path) {
+        let app_name = detect_app_name(project_path)
+            .unwrap_or_else(|| project_path.file_name().unwrap_or_default().to_string_lossy().to_string());
+        let services = self.services.iter().map(|s| format!("{} ({})", s.service, s.r#type)).collect();
+        let database_services = self.get_database_services().iter().map(|s| s.service.clone()).collect();
+        self.pending_destroy = Some(DestroyDialog::new(&app_name, services, database_services));
+    }
+
+    fn show_pending_destroy(&mut self, ctx: &egui::Context) {
+        let Some(dialog) = self.pending_destroy.as_mut() else {
+            return;
+        };
+
+        match dialog.show(ctx) {
+            ConfirmResponse::Pending => {}
+            ConfirmResponse::Cancelled => {
+                self.pending_destroy = None;
+            }
+            ConfirmResponse::Confirmed => {
+                if let (Some(dialog), Some(path)) = (self.pending_destroy.take(), &self.selected_project_path) {
+                    self.is_loading.set(true);
+                    self.show_terminal_popup = true;
+                    run_lando_chain(self.sender.clone(), dialog.steps(), path.clone(), false);
+                }
+            }
+        }
+    }
+
+    fn show_pending_lando_command(&mut self, ctx: &egui::Context) {
+        let Some((_, dialog)) = self.pending_lando_command.as_mut() else {
+            return;
+        };
+
+        match dialog.show(ctx) {
+            ConfirmResponse::Pending => {}
+            ConfirmResponse::Cancelled => {
+                self.pending_lando_command = None;
+            }
+            ConfirmResponse::Confirmed => {
+                if let Some((command, _)) = self.pending_lando_command.take() {
+                    if let Some(path) = &self.selected_project_path {
+                        self.is_loading.set(true);
+                        run_lando_command(self.sender.clone(), command, path.clone());
+                    }
+                }
+            }
+        }
+    }
+
     fn render_database_services_interface(
         &mut self,
         ui: &mut egui::Ui,
@@ -465,6 +2170,7 @@ impl LandoGui {
 
         let service_ui_manager = &self.service_ui_manager;
         let terminal = &self.terminal;
+        let app_running = self.is_project_running(selected_path);
 
         ui.group(|ui| {
             ui.horizontal(|ui| {
@@ -477,7 +2183,6 @@ impl LandoGui {
                         .clicked()
                         && !is_loading.get()
                     {
-                        sender_clone.send(LandoCommandOutcome::CommandSuccess("refresh".to_string())).ok();
                         get_project_info(sender_clone.clone(), path_clone.clone());
                     }
                 });
@@ -496,6 +2201,7 @@ impl LandoGui {
                                 // Aquí mejor pasar flags por RefCell o Arc<Mutex>
                                 &mut self.is_loading.get(),
                                 &mut *terminal.borrow_mut(),
+                                app_running,
                             );
                         });
                         ui.separator();
@@ -507,6 +2213,7 @@ impl LandoGui {
 
 
     fn render_open_database_interface(&mut self, ui: &mut egui::Ui, selected_path: &std::path::PathBuf) {
+        let mut detach = false;
         if let Some(open_db_service) = &self.open_database_interface {
             if let Some(service) = self.services.iter().find(|s| s.service == *open_db_service) {
                 ui.group(|ui| {
@@ -516,6 +2223,9 @@ impl LandoGui {
                             if ui.button("❌ Cerrar ").clicked() {
                                 self.open_database_interface = None;
                             }
+                            if ui.button("🗖 Abrir en ventana").on_hover_text("Mover la interfaz a una ventana propia").clicked() {
+                                detach = true;
+                            }
                         });
                     });
 
@@ -536,6 +2246,116 @@ impl LandoGui {
                 ui.separator();
             }
         }
+
+        // Solo hay una ventana separada: la anterior, si la hay, se cierra
+        if detach {
+            self.save_detached_window_geometry();
+            self.detached_database_interface = self.open_database_interface.take();
+            self.detached_initial_geometry = self.detached_database_interface.as_ref()
+                .and_then(|service| Self::load_database_window_geometry(&Self::database_window_key(selected_path, service)));
+            self.detached_geometry = self.detached_initial_geometry;
+        }
+    }
+
+    fn database_viewport_id(service: &str) -> egui::ViewportId {
+        egui::ViewportId::from_hash_of(("database_window", service))
+    }
+
+    // Interfaz de BD en una ventana nativa propia. Se pinta en el mismo frame
+    // que la principal (viewport inmediato), así que comparte el receptor de
+    // mensajes y el estado del DatabaseUI. Al cerrarla vuelve a la vista en línea.
+    fn show_detached_database_window(&mut self, ctx: &egui::Context) {
+        let Some(service_name) = self.detached_database_interface.clone() else {
+            return;
+        };
+        let (Some(project_path), Some(service)) = (
+            self.selected_project_path.clone(),
+            self.services.iter().find(|s| s.service == service_name).cloned(),
+        ) else {
+            self.detached_database_interface = None;
+            return;
+        };
+
+        let mut builder = egui::ViewportBuilder::default()
+            .with_title(format!("🗄️ {} — Lando GUI", service_name))
+            .with_inner_size([1000.0, 700.0]);
+        if let Some(geometry) = self.detached_initial_geometry {
+            builder = builder
+                .with_position([geometry.x, geometry.y])
+                .with_inner_size([geometry.width, geometry.height]);
+        }
+
+        let mut close = false;
+        ctx.show_viewport_immediate(Self::database_viewport_id(&service_name), builder, |ctx, class| {
+            if class == egui::ViewportClass::Embedded {
+                // El backend no admite varias ventanas: se muestra como ventana interna
+                let mut open = true;
+                egui::Window::new(format!("🗄️ {}", service_name))
+                    .open(&mut open)
+                    .default_size([900.0, 600.0])
+                    .show(ctx, |ui| {
+                        close = self.render_detached_database(ui, &service, &project_path);
+                    });
+                close |= !open;
+                return;
+            }
+
+            egui::CentralPanel::default().show(ctx, |ui| {
+                close = self.render_detached_database(ui, &service, &project_path);
+            });
+
+            let (close_requested, outer_rect, inner_rect) = ctx.input(|i| {
+                let viewport = i.viewport();
+                (viewport.close_requested(), viewport.outer_rect, viewport.inner_rect)
+            });
+            if let (Some(outer), Some(inner)) = (outer_rect, inner_rect) {
+                self.detached_geometry = Some(WindowGeometry {
+                    x: outer.min.x,
+                    y: outer.min.y,
+                    width: inner.width(),
+                    height: inner.height(),
+                });
+            }
+            close |= close_requested;
+        });
+
+        if close {
+            self.save_detached_window_geometry();
+            self.open_database_interface = self.detached_database_interface.take();
+            self.detached_geometry = None;
+            self.detached_initial_geometry = None;
+        }
+    }
+
+    // Devuelve true si se pidió devolver la interfaz a la ventana principal
+    fn render_detached_database(&self, ui: &mut egui::Ui, service: &LandoService, project_path: &std::path::PathBuf) -> bool {
+        let mut reattach = false;
+        ui.horizontal(|ui| {
+            ui.heading(format!("🗄️ Interfaz de Base de Datos: {}", service.service));
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if ui.button("↩️ Volver a la ventana principal").clicked() {
+                    reattach = true;
+                }
+            });
+        });
+        ui.separator();
+
+        let service_key = format!("{}_{}", service.service, service.r#type);
+        let mut is_loading = self.is_loading.get();
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            if let Some(database_ui) = self.service_ui_manager.borrow_mut().database_uis.get_mut(&service_key) {
+                database_ui.show_full_interface(
+                    ui,
+                    service,
+                    project_path,
+                    &self.sender,
+                    &mut is_loading,
+                    &mut self.terminal.borrow_mut()
+                );
+            }
+        });
+        self.is_loading.set(is_loading);
+        reattach
     }
 
     fn render_services_section(&mut self, ui: &mut egui::Ui, selected_path: &std::path::PathBuf) {
@@ -547,28 +2367,38 @@ impl LandoGui {
                         self.is_loading.set(true) ;
                         get_project_info(self.sender.clone(), selected_path.clone());
                     }
+                    ui.selectable_value(&mut self.services_view, ServicesView::Graph, "🕸️ Grafo");
+                    ui.selectable_value(&mut self.services_view, ServicesView::Cards, "📋 Tarjetas");
                 });
             });
         });
 
-        if !self.services.is_empty() {
+        if !self.services.is_empty() && self.services_view == ServicesView::Graph {
+            self.render_services_graph(ui, selected_path);
+        } else if !self.services.is_empty() {
+            let scroll_to = self.scroll_to_service.take();
             egui::ScrollArea::vertical()
                 .auto_shrink([false; 2])
                 .show(ui, |ui| {
-                    let services = self.services.clone();
-                    let selected_path_clone = selected_path.clone();
+                    // Todo lo que usan las tarjetas es de solo lectura o va por
+                    // RefCell/Cell, así que se recorre `self.services` sin copiarlo
+                    let app_running = self.is_project_running(selected_path);
 
-                    for service in &services {
-                        ui.push_id(&service.service, |ui| {
+                    for service in &self.services {
+                        let card = ui.push_id(&service.service, |ui| {
                             self.service_ui_manager.borrow_mut().show_service_details(
                                 ui,
                                 service,
-                                &selected_path_clone,
+                                selected_path,
                                 &self.sender,
                                 &mut self.is_loading.get(),
                                 &mut self.terminal.borrow_mut(),
+                                app_running,
                             );
                         });
+                        if scroll_to.as_ref() == Some(&service.service) {
+                            card.response.scroll_to_me(Some(egui::Align::TOP));
+                        }
                         ui.separator();
                     }
                 });
@@ -577,6 +2407,18 @@ impl LandoGui {
         }
     }
 
+    // El grafo se reconstruye al recibir un `lando info` nuevo
+    fn render_services_graph(&mut self, ui: &mut egui::Ui, selected_path: &std::path::PathBuf) {
+        if self.service_graph.graph.is_none() {
+            self.service_graph.set_graph(build_service_graph(selected_path, &self.services));
+        }
+        let running = self.is_project_running(selected_path);
+        if let Some(service) = self.service_graph.show(ui, running) {
+            self.services_view = ServicesView::Cards;
+            self.scroll_to_service = Some(service);
+        }
+    }
+
     fn render_no_services_message(&mut self, ui: &mut egui::Ui, selected_path: &std::path::PathBuf) {
         ui.vertical_centered(|ui| {
             ui.add_space(50.0);
@@ -595,7 +2437,7 @@ impl LandoGui {
     fn render_query_results_section(&mut self, ui: &mut egui::Ui) {
         if let Some(result) = &self.db_query_result {
             ui.separator();
-            let result_clone = result.clone();
+            let result = Arc::clone(result);
             let mut clear_result = false;
             let mut copy_result = false;
 
@@ -615,7 +2457,7 @@ impl LandoGui {
                 egui::ScrollArea::vertical()
                     .max_height(300.0)
                     .show(ui, |ui| {
-                        let mut result_str = result_clone.clone();
+                        let mut result_str: &str = &result;
                         ui.add(
                             egui::TextEdit::multiline(&mut result_str)
                                 .code_editor()
@@ -629,8 +2471,51 @@ impl LandoGui {
                 self.db_query_result = None;
             }
             if copy_result {
-                ui.ctx().copy_text(result_clone);
+                ui.ctx().copy_text(result.to_string());
+            }
+        }
+    }
+
+    // Descubiertos, favoritos y recientes, sin repetir
+    fn known_projects(&self) -> Vec<std::path::PathBuf> {
+        let mut projects: Vec<_> = self.projects.iter()
+            .chain(&self.pinned_projects)
+            .chain(&self.recent_projects)
+            .cloned()
+            .collect();
+        projects.sort();
+        projects.dedup();
+        projects
+    }
+
+    fn render_dashboard(&mut self, ui: &mut egui::Ui) {
+        // Las tarjetas necesitan saber qué apps están en marcha
+        self.ensure_apps_listed();
+        let projects = self.known_projects();
+        let action = self.dashboard.show(
+            ui,
+            &projects,
+            &self.apps,
+            &self.project_last_opened,
+            self.is_loading.get(),
+            &self.sender,
+        );
+
+        match action {
+            Some(DashboardAction::Select(project)) => {
+                let previous_selection = self.selected_project_path.replace(project);
+                self.handle_project_selection_change(previous_selection);
+            }
+            Some(DashboardAction::Lando(project, command)) => {
+                self.is_loading.set(true);
+                run_lando_command(self.sender.clone(), command.to_string(), project);
+            }
+            Some(DashboardAction::Refresh) => {
+                self.is_loading.set(true);
+                self.apps_requested = true;
+                list_apps(self.sender.clone());
             }
+            None => {}
         }
     }
 
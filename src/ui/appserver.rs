@@ -1,11 +1,20 @@
+use std::collections::HashMap;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc::Sender;
+use std::sync::Arc;
+use std::time::Instant;
 
 use eframe::egui;
-use egui_term::TerminalBackend;
+use crate::models::terminal::LazyTerminal;
 
+use crate::core::appserver::MONITORING_REFRESH_INTERVAL;
 use crate::models::commands::LandoCommandOutcome;
 use crate::models::lando::LandoService;
+use crate::models::recipes::QuickAction;
+use crate::ui::confirm::{ConfirmDialog, ConfirmResponse};
+use crate::ui::shortcuts::{consume_tab_shortcut, tab_shortcut_hint};
+use crate::ui::theme::{error_color, icon_label, info_color, muted_color, success_color, warning_color};
 
 pub struct AppServerUI {
     pub command_input: String,
@@ -22,6 +31,122 @@ pub struct AppServerUI {
     pub environment_vars: Vec<(String, String)>,
     pub new_env_key: String,
     pub new_env_value: String,
+
+    // Acciones rápidas según la receta del proyecto
+    pub recipe: Option<String>,
+    pub recipe_project: Option<PathBuf>,
+    pub quick_actions: Vec<QuickAction>,
+    pub quick_action_values: HashMap<String, String>, // Valores de los formularios, por clave de campo
+    pub pending_quick_action: Option<(QuickAction, Vec<String>, ConfirmDialog)>,
+    pub tooling: Vec<String>, // Comandos de `tooling:` del .lando.yml
+
+    // Xdebug y php.ini (solo servicios PHP)
+    pub xdebug: XdebugState,
+    pub php_ini: PhpIniState,
+    pub pending_rebuild: Option<ConfirmDialog>,
+
+    // Composer
+    pub composer: ComposerState,
+
+    // Peticiones correlacionadas por id
+    pub pending_requests: HashMap<u64, AppServerPendingRequest>,
+
+    // Recarga o limpieza de caché en curso; su salida va a la pestaña de logs
+    pub maintenance: Option<MaintenanceRun>,
+
+    // Pestaña de monitoreo
+    pub monitoring: MonitoringState,
+}
+
+// Métricas del contenedor; None hasta la primera lectura
+#[derive(Debug, Clone, Default)]
+pub struct MonitoringState {
+    pub cpu_percent: Option<f64>,
+    pub memory_usage: Option<String>, // "45.2MiB / 7.7GiB" tal cual lo da docker
+    pub memory_percent: Option<f64>,
+    pub connections: Option<usize>,
+    pub load_average: Option<[f64; 3]>,
+    pub processes: Option<String>, // "en ejecución/total" de /proc/loadavg
+    pub last_refresh: Option<Instant>,
+    pub pending: usize,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct MaintenanceRun {
+    pub request_id: u64,
+    pub label: String,
+    pub updates_status: bool, // false para los comandos personalizados
+    pub pending_bytes: Vec<u8>,
+}
+
+// Qué esperaba cada petición de `run_shell_capture_tagged` lanzada desde AppServerUI
+#[derive(Debug, Clone, PartialEq)]
+pub enum AppServerPendingRequest {
+    XdebugStatus,
+    PhpIni,
+    ComposerOutdated,
+    ComposerPhpVersion,
+    ContainerStats,
+    Connections,
+    LoadAverage,
+}
+
+// Dependencia directa de composer.json con la versión fijada en composer.lock
+#[derive(Debug, Clone, PartialEq)]
+pub struct ComposerPackage {
+    pub name: String,
+    pub constraint: String,
+    pub version: Option<String>, // None si no está en composer.lock
+    pub is_dev: bool,
+    pub latest: Option<String>, // Solo si `composer outdated` ofrece una más nueva
+}
+
+// Comando de composer en curso; su salida llega por `ShellStreamChunk`
+#[derive(Debug, Clone)]
+pub struct ComposerRun {
+    pub request_id: u64,
+    pub command: String,
+    pub cancel: Arc<AtomicBool>,
+    pub refresh_after: bool,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ComposerState {
+    pub has_composer_json: bool,
+    pub packages: Vec<ComposerPackage>,
+    pub has_lock: bool,
+    pub php_constraint: Option<String>,
+    pub platform_php: Option<String>, // `config.platform.php`
+    pub container_php: Option<String>,
+    pub package_name: String,
+    pub dev: bool,
+    pub output: String,
+    pub pending_bytes: Vec<u8>,
+    pub running: Option<ComposerRun>,
+    pub loaded_project: Option<PathBuf>,
+    pub needs_refresh: bool,
+    pub loading_outdated: bool,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct XdebugState {
+    pub checked: bool,
+    pub loading: bool,
+    pub loaded: Option<bool>,
+    pub mode: Option<String>,
+    pub message: Option<Result<String, String>>,
+}
+
+// Salida de `php --ini` + `php -i` y los ajustes que interesan de ella
+#[derive(Debug, Clone, Default)]
+pub struct PhpIniState {
+    pub open: bool,
+    pub loading: bool,
+    pub output: String,
+    pub settings: Vec<(String, String)>,
+    pub error: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -48,6 +173,7 @@ pub enum AppServerTab {
     Configuration,
     Environment,
     Monitoring,
+    Composer,
 }
 
 impl Default for AppServerUI {
@@ -72,6 +198,19 @@ impl Default for AppServerUI {
             environment_vars: Vec::new(),
             new_env_key: String::new(),
             new_env_value: String::new(),
+            recipe: None,
+            recipe_project: None,
+            quick_actions: Vec::new(),
+            quick_action_values: HashMap::new(),
+            pending_quick_action: None,
+            tooling: Vec::new(),
+            xdebug: XdebugState::default(),
+            php_ini: PhpIniState::default(),
+            pending_rebuild: None,
+            composer: ComposerState::default(),
+            pending_requests: HashMap::new(),
+            maintenance: None,
+            monitoring: MonitoringState::default(),
         }
     }
 }
@@ -84,16 +223,36 @@ impl AppServerUI {
         project_path: &PathBuf,
         sender: &Sender<LandoCommandOutcome>,
         is_loading: &mut bool,
-        terminal: &mut TerminalBackend,
+        terminal: &mut LazyTerminal,
     ) {
+        self.refresh_recipe(project_path);
+        self.show_pending_quick_action(ui.ctx(), project_path, sender, is_loading, terminal);
+        self.show_pending_rebuild(ui.ctx(), project_path, sender, is_loading);
+        self.show_php_ini_window(ui.ctx(), service);
+
+        let composer_available = self.has_composer(service);
+        if !composer_available && self.current_tab == AppServerTab::Composer {
+            self.current_tab = AppServerTab::Control;
+        }
+        if composer_available && self.composer.running.is_none() && (self.composer.needs_refresh
+            || (self.current_tab == AppServerTab::Composer && self.composer.loaded_project.as_ref() != Some(project_path)))
+        {
+            self.refresh_composer(service, project_path, sender);
+        }
+
         ui.collapsing(format!("🔥️ App Server: {} ({})", service.service, service.r#type), |ui| {
             // Información del servicio y estado
             self.show_service_header(ui, service);
             
             ui.separator();
+
+            if !self.quick_actions.is_empty() {
+                self.show_quick_actions(ui, service, project_path, sender, is_loading, terminal);
+                ui.separator();
+            }
             
             // Pestañas de navegación
-            self.show_tab_navigation(ui);
+            self.show_tab_navigation(ui, composer_available);
             
             ui.separator();
 
@@ -114,6 +273,9 @@ impl AppServerUI {
                 AppServerTab::Monitoring => {
                     self.show_monitoring_panel(ui, service, project_path, sender, is_loading);
                 }
+                AppServerTab::Composer => {
+                    self.show_composer_panel(ui, service, project_path, sender);
+                }
             }
 
             // Terminal embebido
@@ -138,11 +300,12 @@ impl AppServerUI {
             // Estado del servicio
             ui.vertical(|ui| {
                 ui.label("Estado del Servicio:");
+                let visuals = ui.visuals();
                 let (color, icon, text) = match &self.service_status {
-                    ServiceStatus::Running => (egui::Color32::GREEN, "✅", "Ejecutándose"),
-                    ServiceStatus::Stopped => (egui::Color32::RED, "⏹️", "Detenido"),
-                    ServiceStatus::Error(err) => (egui::Color32::RED, "❌", err.as_str()),
-                    ServiceStatus::Unknown => (egui::Color32::GRAY, "❓", "Desconocido"),
+                    ServiceStatus::Running => (success_color(visuals), "✅", "Ejecutándose"),
+                    ServiceStatus::Stopped => (error_color(visuals), "⏹️", "Detenido"),
+                    ServiceStatus::Error(err) => (error_color(visuals), "❌", err.as_str()),
+                    ServiceStatus::Unknown => (muted_color(visuals), "❓", "Desconocido"),
                 };
                 
                 ui.colored_label(color, format!("{} {}", icon, text));
@@ -167,13 +330,407 @@ impl AppServerUI {
         });
     }
 
-    fn show_tab_navigation(&mut self, ui: &mut egui::Ui) {
+    fn show_quick_actions(
+        &mut self,
+        ui: &mut egui::Ui,
+        service: &LandoService,
+        project_path: &PathBuf,
+        sender: &Sender<LandoCommandOutcome>,
+        is_loading: &mut bool,
+        terminal: &mut LazyTerminal,
+    ) {
+        let mut clicked = None;
+
+        ui.group(|ui| {
+            ui.label(format!("⚡ Acciones rápidas ({})", self.recipe.as_deref().unwrap_or_default()));
+
+            ui.horizontal_wrapped(|ui| {
+                for action in self.quick_actions.iter().filter(|a| a.form_fields.is_empty()) {
+                    let button = ui.add_enabled(!*is_loading, egui::Button::new(action.label))
+                        .on_hover_text(action.command_preview(&self.quick_action_values));
+                    if button.clicked() {
+                        clicked = Some(*action);
+                    }
+                }
+            });
+
+            // Las acciones con formulario muestran sus campos debajo
+            for action in self.quick_actions.iter().filter(|a| !a.form_fields.is_empty()) {
+                ui.horizontal_wrapped(|ui| {
+                    for field in action.form_fields {
+                        ui.label(format!("{}:", field.label));
+                        ui.add(
+                            egui::TextEdit::singleline(self.quick_action_values.entry(field.key.to_string()).or_default())
+                                .hint_text(field.placeholder)
+                                .desired_width(180.0)
+                        );
+                    }
+
+                    let ready = action.build_args(&self.quick_action_values).is_ok();
+                    let button = ui.add_enabled(ready && !*is_loading, egui::Button::new(action.label))
+                        .on_hover_text(action.command_preview(&self.quick_action_values));
+                    if button.clicked() {
+                        clicked = Some(*action);
+                    }
+                });
+            }
+        });
+
+        if let Some(action) = clicked {
+            self.request_quick_action(action, service, project_path, sender, is_loading, terminal);
+        }
+    }
+
+    fn show_pending_quick_action(
+        &mut self,
+        ctx: &egui::Context,
+        project_path: &PathBuf,
+        sender: &Sender<LandoCommandOutcome>,
+        is_loading: &mut bool,
+        terminal: &mut LazyTerminal,
+    ) {
+        let Some((_, _, dialog)) = self.pending_quick_action.as_mut() else {
+            return;
+        };
+
+        match dialog.show(ctx) {
+            ConfirmResponse::Pending => {}
+            ConfirmResponse::Cancelled => self.pending_quick_action = None,
+            ConfirmResponse::Confirmed => {
+                if let Some((action, args, _)) = self.pending_quick_action.take() {
+                    self.run_quick_action(&action, args, project_path, sender, is_loading, terminal);
+                }
+            }
+        }
+    }
+
+    fn show_pending_rebuild(
+        &mut self,
+        ctx: &egui::Context,
+        project_path: &PathBuf,
+        sender: &Sender<LandoCommandOutcome>,
+        is_loading: &mut bool,
+    ) {
+        let Some(dialog) = self.pending_rebuild.as_mut() else {
+            return;
+        };
+
+        match dialog.show(ctx) {
+            ConfirmResponse::Pending => {}
+            ConfirmResponse::Cancelled => self.pending_rebuild = None,
+            ConfirmResponse::Confirmed => {
+                self.pending_rebuild = None;
+                self.run_rebuild(project_path, sender, is_loading);
+            }
+        }
+    }
+
+    fn show_xdebug_section(
+        &mut self,
+        ui: &mut egui::Ui,
+        service: &LandoService,
+        project_path: &PathBuf,
+        sender: &Sender<LandoCommandOutcome>,
+        is_loading: &mut bool,
+    ) {
+        if !self.xdebug.checked {
+            self.check_xdebug(service, project_path, sender);
+        }
+
+        ui.group(|ui| {
+            ui.horizontal(|ui| {
+                ui.strong("🐞 Xdebug:");
+                if self.xdebug.loading {
+                    ui.spinner();
+                    ui.label("Comprobando...");
+                } else {
+                    match self.xdebug.loaded {
+                        Some(true) => { ui.colored_label(success_color(ui.visuals()), "✅ Cargado"); }
+                        Some(false) => { ui.colored_label(muted_color(ui.visuals()), "⭕ No cargado"); }
+                        None => { ui.colored_label(muted_color(ui.visuals()), "❔ Desconocido"); }
+                    }
+                    if let Some(mode) = &self.xdebug.mode {
+                        ui.label(format!("Modo: {}", mode));
+                    }
+                }
+                if ui.small_button("🔄").on_hover_text("Volver a comprobar").clicked() {
+                    self.check_xdebug(service, project_path, sender);
+                }
+            });
+
+            let via = |tool: &str| {
+                if self.tooling.iter().any(|t| t == tool) {
+                    format!("Ejecuta 'lando {}'", tool)
+                } else {
+                    "Cambia xdebug en .lando.yml y pide reconstruir".to_string()
+                }
+            };
+            let on_hint = via("xdebug-on");
+            let off_hint = via("xdebug-off");
+            ui.horizontal(|ui| {
+                if ui.add_enabled(!*is_loading, egui::Button::new("🟢 Activar")).on_hover_text(on_hint).clicked() {
+                    self.set_xdebug(true, service, project_path, sender, is_loading);
+                }
+                if ui.add_enabled(!*is_loading, egui::Button::new("⚪ Desactivar")).on_hover_text(off_hint).clicked() {
+                    self.set_xdebug(false, service, project_path, sender, is_loading);
+                }
+                if ui.button("📄 php.ini efectivo").clicked() {
+                    self.load_php_ini(service, project_path, sender);
+                }
+            });
+
+            match &self.xdebug.message {
+                Some(Ok(msg)) => { ui.colored_label(success_color(ui.visuals()), msg); }
+                Some(Err(err)) => { ui.colored_label(error_color(ui.visuals()), err); }
+                None => {}
+            }
+        });
+    }
+
+    fn show_php_ini_window(&mut self, ctx: &egui::Context, service: &LandoService) {
+        if !self.php_ini.open {
+            return;
+        }
+
+        let mut open = self.php_ini.open;
+        egui::Window::new(format!("📄 php.ini efectivo ({})", service.service))
+            .id(egui::Id::new(("php_ini", &service.service)))
+            .open(&mut open)
+            .resizable(true)
+            .default_width(520.0)
+            .show(ctx, |ui| {
+                if self.php_ini.loading {
+                    ui.horizontal(|ui| {
+                        ui.spinner();
+                        ui.label("Ejecutando php --ini y php -i...");
+                    });
+                    return;
+                }
+                if let Some(error) = &self.php_ini.error {
+                    ui.colored_label(error_color(ui.visuals()), error);
+                    return;
+                }
+
+                egui::Grid::new(("php_ini_grid", &service.service)).striped(true).show(ui, |ui| {
+                    for (key, value) in &self.php_ini.settings {
+                        ui.strong(key);
+                        ui.label(value);
+                        ui.end_row();
+                    }
+                });
+                ui.label(
+                    egui::RichText::new("Valores del PHP de línea de comandos; el del servidor web puede cargar otro php.ini.")
+                        .small()
+                        .color(muted_color(ui.visuals())),
+                );
+
+                ui.separator();
+                if ui.button("📋 Copiar salida completa").clicked() {
+                    ui.ctx().copy_text(self.php_ini.output.clone());
+                }
+                ui.collapsing("Salida completa", |ui| {
+                    egui::ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+                        ui.add(
+                            egui::TextEdit::multiline(&mut self.php_ini.output.as_str())
+                                .code_editor()
+                                .desired_width(f32::INFINITY)
+                        );
+                    });
+                });
+            });
+        self.php_ini.open = open;
+    }
+
+    fn show_tab_navigation(&mut self, ui: &mut egui::Ui, composer_available: bool) {
+        let mut tabs = vec![
+            (AppServerTab::Control, "🎛️ Control"),
+            (AppServerTab::Logs, "📜 Logs"),
+            (AppServerTab::Configuration, "⚙️ Config"),
+            (AppServerTab::Environment, "🌍 Env"),
+            (AppServerTab::Monitoring, "📊 Monitor"),
+        ];
+        if composer_available {
+            tabs.push((AppServerTab::Composer, "🎼 Composer"));
+        }
+
+        if let Some(index) = consume_tab_shortcut(ui, tabs.len()) {
+            self.current_tab = tabs[index].0.clone();
+        }
+
+        ui.horizontal(|ui| {
+            for (index, (tab, label)) in tabs.into_iter().enumerate() {
+                ui.selectable_value(&mut self.current_tab, tab, label)
+                    .on_hover_text(tab_shortcut_hint(index));
+            }
+        });
+    }
+
+    fn show_composer_panel(
+        &mut self,
+        ui: &mut egui::Ui,
+        service: &LandoService,
+        project_path: &PathBuf,
+        sender: &Sender<LandoCommandOutcome>,
+    ) {
+        ui.heading("🎼 Composer");
+
+        // Versión de PHP exigida frente a la del contenedor
+        ui.group(|ui| {
+            ui.horizontal(|ui| {
+                ui.label(format!(
+                    "🐘 PHP requerido: {}",
+                    self.composer.php_constraint.as_deref().unwrap_or("sin restricción")
+                ));
+                if let Some(platform) = &self.composer.platform_php {
+                    ui.label(format!("(config.platform.php: {})", platform));
+                }
+                ui.label(format!(
+                    "· Contenedor: {}",
+                    self.composer.container_php.as_deref().unwrap_or("?")
+                ));
+            });
+            match self.composer_php_conflict() {
+                Some(true) => {
+                    ui.colored_label(
+                        warning_color(ui.visuals()),
+                        "⚠️ La versión de PHP del contenedor no cumple la restricción de composer.json",
+                    );
+                }
+                None if self.composer.php_constraint.is_some() && self.composer.container_php.is_some() => {
+                    ui.colored_label(muted_color(ui.visuals()), "No se pudo evaluar la restricción de PHP");
+                }
+                _ => {}
+            }
+        });
+
+        let running = self.composer.running.is_some();
+
+        ui.group(|ui| {
+            ui.horizontal(|ui| {
+                ui.add(egui::TextEdit::singleline(&mut self.composer.package_name).hint_text("vendor/paquete[:versión]"));
+                ui.checkbox(&mut self.composer.dev, "--dev");
+                if ui.add_enabled(!running, egui::Button::new("📥 Require")).clicked() {
+                    self.require_composer_package(service, project_path, sender);
+                }
+            });
+
+            ui.horizontal(|ui| {
+                if ui.add_enabled(!running, egui::Button::new("📦 install")).clicked() {
+                    self.run_composer_command(service, project_path, sender, "install", true);
+                }
+                if ui.add_enabled(!running, egui::Button::new("🧩 dump-autoload")).clicked() {
+                    self.run_composer_command(service, project_path, sender, "dump-autoload", false);
+                }
+                if ui.add_enabled(!running, egui::Button::new("✅ validate")).clicked() {
+                    self.run_composer_command(service, project_path, sender, "validate", false);
+                }
+                if ui.add_enabled(!running, egui::Button::new("🔄 Actualizar Lista")).clicked() {
+                    self.refresh_composer(service, project_path, sender);
+                }
+            });
+
+            if let Some(run) = &self.composer.running {
+                let cancel = run.cancel.clone();
+                ui.horizontal(|ui| {
+                    ui.spinner();
+                    ui.label(&run.command);
+                    if ui.small_button("⏹ Cancelar").clicked() {
+                        cancel.store(true, Ordering::Relaxed);
+                    }
+                });
+            }
+        });
+
+        if let Some(error) = &self.composer.error {
+            ui.colored_label(error_color(ui.visuals()), error);
+        }
+
+        // Paquetes agrupados en producción y desarrollo
+        ui.group(|ui| {
+            ui.horizontal(|ui| {
+                ui.label("Paquetes:");
+                if self.composer.loading_outdated {
+                    ui.spinner();
+                    ui.label("Buscando actualizaciones...");
+                }
+            });
+            if !self.composer.has_lock && !self.composer.packages.is_empty() {
+                ui.colored_label(muted_color(ui.visuals()), "Sin composer.lock: se muestran solo las restricciones de composer.json");
+            }
+
+            egui::ScrollArea::vertical()
+                .id_salt("composer_packages")
+                .max_height(300.0)
+                .show(ui, |ui| {
+                    for (title, dev) in [("Producción", false), ("Desarrollo", true)] {
+                        let packages: Vec<_> = self.composer.packages.iter().filter(|p| p.is_dev == dev).cloned().collect();
+                        egui::CollapsingHeader::new(format!("{} ({})", title, packages.len()))
+                            .id_salt(("composer_group", dev))
+                            .default_open(true)
+                            .show(ui, |ui| {
+                                for package in &packages {
+                                    self.show_composer_package_row(ui, package, running, service, project_path, sender);
+                                }
+                            });
+                    }
+                });
+        });
+
+        ui.collapsing("📜 Salida", |ui| {
+            if ui.small_button("🧹 Limpiar").clicked() {
+                self.composer.output.clear();
+            }
+            egui::ScrollArea::vertical()
+                .id_salt("composer_output")
+                .max_height(250.0)
+                .stick_to_bottom(true)
+                .show(ui, |ui| {
+                    ui.add(
+                        egui::TextEdit::multiline(&mut self.composer.output.as_str())
+                            .code_editor()
+                            .desired_width(f32::INFINITY)
+                    );
+                });
+        });
+    }
+
+    fn show_composer_package_row(
+        &mut self,
+        ui: &mut egui::Ui,
+        package: &ComposerPackage,
+        running: bool,
+        service: &LandoService,
+        project_path: &PathBuf,
+        sender: &Sender<LandoCommandOutcome>,
+    ) {
         ui.horizontal(|ui| {
-            ui.selectable_value(&mut self.current_tab, AppServerTab::Control, "🎛️ Control");
-            ui.selectable_value(&mut self.current_tab, AppServerTab::Logs, "📜 Logs");
-            ui.selectable_value(&mut self.current_tab, AppServerTab::Configuration, "⚙️ Config");
-            ui.selectable_value(&mut self.current_tab, AppServerTab::Environment, "🌍 Env");
-            ui.selectable_value(&mut self.current_tab, AppServerTab::Monitoring, "📊 Monitor");
+            let color = if package.latest.is_some() {
+                warning_color(ui.visuals())
+            } else if package.is_dev {
+                info_color(ui.visuals())
+            } else {
+                ui.visuals().text_color()
+            };
+            ui.colored_label(color, format!("📦 {}", package.name));
+            match &package.version {
+                Some(version) => { ui.label(version); }
+                None => { ui.colored_label(muted_color(ui.visuals()), "no instalado"); }
+            }
+            ui.colored_label(muted_color(ui.visuals()), &package.constraint);
+            if let Some(latest) = &package.latest {
+                ui.colored_label(warning_color(ui.visuals()), format!("→ {}", latest));
+            }
+
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if ui.add_enabled(!running, egui::Button::new("🗑️").small()).on_hover_text("composer remove").clicked() {
+                    self.remove_composer_package(service, project_path, sender, &package.name);
+                }
+                if package.latest.is_some()
+                    && ui.add_enabled(!running, egui::Button::new("⬆️").small()).on_hover_text("composer update").clicked()
+                {
+                    self.update_composer_package(service, project_path, sender, &package.name);
+                }
+            });
         });
     }
 
@@ -210,13 +767,23 @@ impl AppServerUI {
             });
 
             ui.horizontal(|ui| {
-                if ui.button("🔄 Reload Config").clicked() {
+                let idle = self.maintenance.is_none();
+                if ui.add_enabled(idle, egui::Button::new("🔄 Reload Config"))
+                    .on_hover_text("Recarga la configuración sin cortar conexiones")
+                    .clicked()
+                {
                     self.reload_configuration(service, project_path, sender, is_loading);
                 }
 
-                if ui.button("🧹 Clear Cache").clicked() {
+                if ui.add_enabled(idle, egui::Button::new("🧹 Clear Cache"))
+                    .on_hover_text("Caché del framework si se detecta; si no, OPcache")
+                    .clicked()
+                {
                     self.clear_cache(service, project_path, sender, is_loading);
                 }
+                if !idle {
+                    ui.spinner();
+                }
 
                 if ui.button("🔍 Test Connection").clicked() {
                     self.test_connection(service, project_path, sender, is_loading);
@@ -231,10 +798,12 @@ impl AppServerUI {
             ui.label("Comandos Personalizados:");
             
             ui.horizontal(|ui| {
-                ui.text_edit_singleline(&mut self.command_input);
-                
-                let execute_btn = ui.add_enabled(!*is_loading, egui::Button::new("▶️ Ejecutar"));
-                if execute_btn.clicked() {
+                let input = ui.text_edit_singleline(&mut self.command_input);
+                let submitted = input.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+
+                let idle = self.maintenance.is_none();
+                let execute_btn = ui.add_enabled(idle, egui::Button::new("▶️ Ejecutar"));
+                if execute_btn.clicked() || (submitted && idle) {
                     self.execute_custom_command(service, project_path, sender, is_loading);
                 }
             });
@@ -255,7 +824,7 @@ impl AppServerUI {
             // Historial de comandos
             if !self.command_history.is_empty() {
                 ui.collapsing("📜 Historial", |ui| {
-                    for cmd in &self.command_history {
+                    for cmd in self.command_history.iter().rev() {
                         if ui.small_button(cmd).clicked() {
                             self.command_input = cmd.clone();
                         }
@@ -263,6 +832,11 @@ impl AppServerUI {
                 });
             }
         });
+
+        if self.is_php_service(service) {
+            ui.separator();
+            self.show_xdebug_section(ui, service, project_path, sender, is_loading);
+        }
     }
 
     fn show_logs_panel(
@@ -436,7 +1010,7 @@ impl AppServerUI {
                 ui.label("=");
                 ui.text_edit_singleline(value);
                 
-                if ui.button("🗑️").clicked() {
+                if icon_label(ui.button("🗑️"), "Eliminar variable").clicked() {
                     to_remove = Some(i);
                 }
             });
@@ -469,24 +1043,50 @@ impl AppServerUI {
     ) {
         ui.heading("📊 Monitoreo del Servidor");
 
-        // Métricas básicas (placeholder)
+        self.refresh_monitoring_if_due(service, project_path, sender, is_loading);
+        ui.ctx().request_repaint_after(MONITORING_REFRESH_INTERVAL);
+
+        let monitoring = &self.monitoring;
+        let na = || "—".to_string();
         ui.columns(3, |columns| {
             columns[0].group(|ui| {
                 ui.label("CPU Usage");
-                ui.label("0%"); // Placeholder
+                ui.heading(monitoring.cpu_percent.map_or_else(na, |cpu| format!("{:.1}%", cpu)));
             });
 
             columns[1].group(|ui| {
                 ui.label("Memory Usage");
-                ui.label("0 MB"); // Placeholder
+                ui.heading(monitoring.memory_usage.clone().unwrap_or_else(na));
+                if let Some(percent) = monitoring.memory_percent {
+                    ui.colored_label(muted_color(ui.visuals()), format!("{:.1}% del límite", percent));
+                }
             });
 
             columns[2].group(|ui| {
                 ui.label("Active Connections");
-                ui.label("0"); // Placeholder
+                ui.heading(monitoring.connections.map_or_else(na, |count| count.to_string()))
+                    .on_hover_text("Conexiones TCP establecidas a los puertos 80, 443, 8080 y 9000");
             });
         });
 
+        ui.horizontal(|ui| {
+            if let Some([one, five, fifteen]) = monitoring.load_average {
+                ui.label(format!("⚡ Carga: {:.2} · {:.2} · {:.2}", one, five, fifteen))
+                    .on_hover_text("Media de 1, 5 y 15 minutos del host visto desde el contenedor");
+            }
+            if let Some(processes) = &monitoring.processes {
+                ui.label(format!("🧵 Procesos: {}", processes)).on_hover_text("En ejecución / total");
+            }
+            if monitoring.pending > 0 {
+                ui.spinner();
+            } else if let Some(refreshed) = monitoring.last_refresh {
+                ui.colored_label(muted_color(ui.visuals()), format!("hace {} s", refreshed.elapsed().as_secs()));
+            }
+        });
+        if let Some(error) = &monitoring.error {
+            ui.colored_label(error_color(ui.visuals()), error);
+        }
+
         ui.separator();
 
         // Botones de monitoreo
@@ -505,7 +1105,7 @@ impl AppServerUI {
         });
     }
 
-    fn show_terminal_section(&mut self, ui: &mut egui::Ui, terminal: &mut TerminalBackend) {
+    fn show_terminal_section(&mut self, ui: &mut egui::Ui, terminal: &mut LazyTerminal) {
         ui.collapsing("💻 Terminal del Servidor", |ui| {
             ui.label("Terminal integrado para comandos avanzados:");
             // Placeholder para el terminal
@@ -0,0 +1,240 @@
+use eframe::egui;
+
+use crate::ui::theme::error_color;
+
+// Resultado de un diálogo de confirmación en el frame actual
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConfirmResponse {
+    Pending,
+    Confirmed,
+    Cancelled,
+}
+
+// Diálogo modal genérico para acciones destructivas: muestra el comando exacto
+// que se va a ejecutar y, para las más peligrosas, exige marcar "entiendo".
+#[derive(Debug, Clone)]
+pub struct ConfirmDialog {
+    pub title: String,
+    pub description: String,
+    pub command_preview: String,
+    pub service: String,
+    pub details: Vec<String>,
+    pub requires_acknowledgement: bool,
+    pub acknowledged: bool,
+    // Hay otro comando en marcha: no se puede ejecutar todavía
+    pub busy: bool,
+}
+
+impl ConfirmDialog {
+    pub fn new(title: &str, description: &str, command_preview: &str, service: &str) -> Self {
+        Self {
+            title: title.to_string(),
+            description: description.to_string(),
+            command_preview: command_preview.to_string(),
+            service: service.to_string(),
+            details: Vec::new(),
+            requires_acknowledgement: false,
+            acknowledged: false,
+            busy: false,
+        }
+    }
+
+    // Lista de elementos afectados que se muestra bajo la descripción
+    pub fn with_details(mut self, details: Vec<String>) -> Self {
+        self.details = details;
+        self
+    }
+
+    pub fn destructive(mut self) -> Self {
+        self.requires_acknowledgement = true;
+        self
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context) -> ConfirmResponse {
+        let mut response = ConfirmResponse::Pending;
+
+        egui::Window::new(format!("⚠️ {}", self.title))
+            .collapsible(false)
+            .resizable(true)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.label(&self.description);
+                if !self.service.is_empty() {
+                    ui.label(format!("🎯 Servicio afectado: {}", self.service));
+                }
+                for detail in &self.details {
+                    ui.label(format!("• {}", detail));
+                }
+
+                ui.separator();
+
+                ui.label("Comando a ejecutar:");
+                egui::ScrollArea::vertical()
+                    .max_height(200.0)
+                    .show(ui, |ui| {
+                        ui.add(
+                            egui::TextEdit::multiline(&mut self.command_preview.as_str())
+                                .code_editor()
+                                .desired_width(f32::INFINITY)
+                        );
+                    });
+
+                if self.requires_acknowledgement {
+                    ui.separator();
+                    ui.checkbox(&mut self.acknowledged, "Entiendo que esta acción no se puede deshacer");
+                }
+
+                ui.separator();
+
+                ui.horizontal(|ui| {
+                    let can_confirm = (!self.requires_acknowledgement || self.acknowledged) && !self.busy;
+                    let confirm = ui.add_enabled(can_confirm, egui::Button::new("✅ Ejecutar"));
+                    let confirm = if self.busy {
+                        confirm.on_disabled_hover_text("Espera a que termine el comando en curso")
+                    } else {
+                        confirm
+                    };
+                    if confirm.clicked() {
+                        response = ConfirmResponse::Confirmed;
+                    }
+                    if ui.button("❌ Cancelar").clicked() {
+                        response = ConfirmResponse::Cancelled;
+                    }
+                });
+            });
+
+        response
+    }
+}
+
+// Confirmación de `lando destroy`: hay que escribir el nombre de la app y se
+// puede encadenar un `db-export` de cada base de datos antes de destruir.
+#[derive(Debug, Clone)]
+pub struct DestroyDialog {
+    pub app_name: String,
+    pub typed_name: String,
+    pub services: Vec<String>,
+    pub database_services: Vec<String>,
+    pub export_first: bool,
+}
+
+impl DestroyDialog {
+    pub fn new(app_name: &str, services: Vec<String>, database_services: Vec<String>) -> Self {
+        Self {
+            app_name: app_name.to_string(),
+            typed_name: String::new(),
+            services,
+            export_first: !database_services.is_empty(),
+            database_services,
+        }
+    }
+
+    // Pasos en orden; el destroy solo se lanza si todos los anteriores terminan bien
+    pub fn steps(&self) -> Vec<Vec<String>> {
+        let mut steps = Vec::new();
+        if self.export_first {
+            for service in &self.database_services {
+                steps.push(vec!["db-export".to_string(), "-s".to_string(), service.clone()]);
+            }
+        }
+        steps.push(vec!["destroy".to_string(), "-y".to_string()]);
+        steps
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context) -> ConfirmResponse {
+        let mut response = ConfirmResponse::Pending;
+
+        egui::Window::new("🧨 Destruir app")
+            .collapsible(false)
+            .resizable(true)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.label(format!(
+                    "Se eliminarán los contenedores y volúmenes de '{}'. El código del proyecto no se toca.",
+                    self.app_name
+                ));
+
+                if !self.services.is_empty() {
+                    ui.separator();
+                    ui.label("Servicios que se destruyen:");
+                    for service in &self.services {
+                        ui.label(format!("• {}", service));
+                    }
+                }
+
+                ui.separator();
+                ui.colored_label(
+                    error_color(ui.visuals()),
+                    "⚠️ Los datos de las bases de datos se pierden salvo que se exporten antes.",
+                );
+                ui.add_enabled(
+                    !self.database_services.is_empty(),
+                    egui::Checkbox::new(&mut self.export_first, "💾 Exportar las bases de datos primero (lando db-export)"),
+                );
+
+                ui.separator();
+                ui.label("Comandos a ejecutar:");
+                let mut preview = self.steps().iter()
+                    .map(|args| format!("lando {}", args.join(" ")))
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                ui.add(
+                    egui::TextEdit::multiline(&mut preview)
+                        .code_editor()
+                        .interactive(false)
+                        .desired_rows(2)
+                        .desired_width(f32::INFINITY)
+                );
+
+                ui.separator();
+                ui.label(format!("Escribe '{}' para confirmar:", self.app_name));
+                ui.text_edit_singleline(&mut self.typed_name);
+
+                ui.separator();
+
+                ui.horizontal(|ui| {
+                    let can_confirm = self.typed_name.trim() == self.app_name;
+                    if ui.add_enabled(can_confirm, egui::Button::new("🧨 Destruir")).clicked() {
+                        response = ConfirmResponse::Confirmed;
+                    }
+                    if ui.button("❌ Cancelar").clicked() {
+                        response = ConfirmResponse::Cancelled;
+                    }
+                });
+            });
+
+        response
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use eframe::egui::accesskit::Role;
+
+    use super::*;
+
+    fn confirm_button_enabled(dialog: &mut ConfirmDialog) -> bool {
+        let ctx = egui::Context::default();
+        ctx.enable_accesskit();
+        // En el primer frame la ventana solo se mide; se mira el segundo
+        let _ = ctx.run(egui::RawInput::default(), |ctx| {
+            dialog.show(ctx);
+        });
+        let output = ctx.run(egui::RawInput::default(), |ctx| {
+            dialog.show(ctx);
+        });
+        let update = output.platform_output.accesskit_update.expect("AccessKit activado");
+        let (_, node) = update.nodes.iter()
+            .find(|(_, node)| node.role() == Role::Button && node.label() == Some("✅ Ejecutar"))
+            .expect("botón Ejecutar");
+        !node.is_disabled()
+    }
+
+    #[test]
+    fn busy_dialog_cannot_be_confirmed() {
+        let mut dialog = ConfirmDialog::new("Vaciar tabla", "Se borran todas las filas.", "TRUNCATE t;", "database");
+        assert!(confirm_button_enabled(&mut dialog));
+        dialog.busy = true;
+        assert!(!confirm_button_enabled(&mut dialog));
+    }
+}
@@ -0,0 +1,166 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::Sender;
+
+use eframe::egui;
+
+use crate::core::dashboard::{find_lando_app, format_last_opened, primary_url};
+use crate::models::commands::LandoCommandOutcome;
+use crate::models::dashboard::{CardStatus, DashboardAction, ProjectDashboard};
+use crate::models::lando::LandoApp;
+use crate::ui::theme::{muted_color, success_color};
+
+const CARD_WIDTH: f32 = 260.0;
+const CARD_HEIGHT: f32 = 118.0;
+
+// Barra gris que late mientras se leen los datos de la tarjeta
+fn shimmer(ui: &mut egui::Ui, width: f32) {
+    let (rect, _) = ui.allocate_exact_size(egui::vec2(width, 10.0), egui::Sense::hover());
+    let phase = (ui.input(|i| i.time) * 3.0).sin() as f32 * 0.5 + 0.5;
+    let color = ui.visuals().widgets.inactive.bg_fill.gamma_multiply(0.5 + 0.5 * phase);
+    ui.painter().rect_filled(rect, 3.0, color);
+    ui.ctx().request_repaint();
+}
+
+impl ProjectDashboard {
+    pub fn show(
+        &mut self,
+        ui: &mut egui::Ui,
+        projects: &[PathBuf],
+        apps: &[LandoApp],
+        last_opened: &HashMap<PathBuf, u64>,
+        busy: bool,
+        sender: &Sender<LandoCommandOutcome>,
+    ) -> Option<DashboardAction> {
+        let mut action = None;
+
+        ui.horizontal(|ui| {
+            ui.heading(format!("🏠 Proyectos ({})", projects.len()));
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if ui.add_enabled(!busy, egui::Button::new("🔄")).on_hover_text("Volver a leer estado y recetas").clicked() {
+                    self.refresh();
+                    action = Some(DashboardAction::Refresh);
+                }
+                ui.add(egui::TextEdit::singleline(&mut self.search)
+                    .hint_text("🔍 Filtrar proyectos")
+                    .desired_width(220.0));
+            });
+        });
+        ui.add_space(6.0);
+
+        let visible = self.visible_projects(projects, |project| last_opened.get(project).copied());
+        if visible.is_empty() {
+            ui.colored_label(muted_color(ui.visuals()), "Ningún proyecto coincide con la búsqueda");
+            return action;
+        }
+
+        let spacing = ui.spacing().item_spacing.x;
+        let available = ui.available_width();
+        let columns = (((available + spacing) / (CARD_WIDTH + spacing)).floor() as usize).max(1);
+        let card_width = (available - spacing * (columns - 1) as f32) / columns as f32;
+        let rows = visible.len().div_ceil(columns);
+
+        // Solo se dibujan (y se cargan) las filas visibles
+        egui::ScrollArea::vertical()
+            .id_salt("project_dashboard")
+            .auto_shrink([false; 2])
+            .show_rows(ui, CARD_HEIGHT, rows, |ui, range| {
+                for row in range {
+                    ui.horizontal(|ui| {
+                        for project in visible.iter().skip(row * columns).take(columns) {
+                            let app = find_lando_app(apps, project);
+                            let opened = last_opened.get(project).copied();
+                            if let Some(card_action) = self.show_card(ui, project, app, opened, card_width, busy, sender) {
+                                action = Some(card_action);
+                            }
+                        }
+                    });
+                }
+            });
+
+        action
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn show_card(
+        &mut self,
+        ui: &mut egui::Ui,
+        project: &Path,
+        app: Option<&LandoApp>,
+        last_opened: Option<u64>,
+        width: f32,
+        busy: bool,
+        sender: &Sender<LandoCommandOutcome>,
+    ) -> Option<DashboardAction> {
+        let mut action = None;
+        let running = app.is_some_and(|app| app.running);
+        let status = self.request_card(sender, project).clone();
+
+        // El fondo se reserva antes que los botones para que estos reciban el clic
+        let (rect, response) = ui.allocate_exact_size(egui::vec2(width, CARD_HEIGHT), egui::Sense::click());
+        let visuals = ui.visuals();
+        let fill = if response.hovered() { visuals.widgets.hovered.weak_bg_fill } else { visuals.faint_bg_color };
+        ui.painter().rect(rect, 6.0, fill, visuals.widgets.noninteractive.bg_stroke, egui::StrokeKind::Inside);
+
+        let mut card = ui.new_child(egui::UiBuilder::new()
+            .max_rect(rect.shrink(8.0))
+            .layout(egui::Layout::top_down(egui::Align::Min)));
+        let ui = &mut card;
+
+        let folder = project.file_name().unwrap_or_default().to_string_lossy();
+        ui.horizontal(|ui| {
+            let (dot, color) = if running {
+                ("●", success_color(ui.visuals()))
+            } else {
+                ("○", muted_color(ui.visuals()))
+            };
+            ui.colored_label(color, dot).on_hover_text(if running { "En marcha" } else { "Parada" });
+            match &status {
+                CardStatus::Ready(info) => {
+                    ui.strong(info.app_name.as_deref().unwrap_or(folder.as_ref()));
+                }
+                CardStatus::Loading => {
+                    ui.strong(folder.as_ref());
+                }
+            }
+        });
+
+        match &status {
+            CardStatus::Loading => {
+                shimmer(ui, width * 0.5);
+                shimmer(ui, width * 0.7);
+            }
+            CardStatus::Ready(info) => {
+                let recipe = info.recipe.as_deref().unwrap_or("sin receta");
+                ui.colored_label(muted_color(ui.visuals()), format!("🍳 {}", recipe));
+                match app.and_then(primary_url).filter(|_| running) {
+                    Some(url) => {
+                        ui.hyperlink_to(format!("🌐 {}", url), url);
+                    }
+                    None => {
+                        ui.colored_label(muted_color(ui.visuals()), "🌐 —");
+                    }
+                }
+            }
+        }
+
+        ui.with_layout(egui::Layout::bottom_up(egui::Align::Min), |ui| {
+            ui.horizontal(|ui| {
+                let (label, command) = if running { ("⏹️ stop", "stop") } else { ("▶️ start", "start") };
+                if ui.add_enabled(!busy, egui::Button::new(label).small()).clicked() {
+                    action = Some(DashboardAction::Lando(project.to_path_buf(), command));
+                }
+                if ui.small_button("📂 Abrir").clicked() {
+                    action = Some(DashboardAction::Select(project.to_path_buf()));
+                }
+                let opened = last_opened.map_or_else(|| "Nunca abierto".to_string(), format_last_opened);
+                ui.colored_label(muted_color(ui.visuals()), format!("🕘 {}", opened));
+            });
+        });
+
+        if response.on_hover_text(project.to_string_lossy()).clicked() && action.is_none() {
+            action = Some(DashboardAction::Select(project.to_path_buf()));
+        }
+        action
+    }
+}
@@ -1,22 +1,66 @@
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap, VecDeque};
+use std::time::Instant;
 use std::path::PathBuf;
 use std::sync::mpsc::Sender;
+use std::sync::Arc;
 
 use eframe::egui;
-use egui_term::TerminalBackend;
+use crate::models::terminal::LazyTerminal;
+use serde::{Deserialize, Serialize};
 
 use crate::core::commands::*;
 use crate::models::commands::LandoCommandOutcome;
 use crate::models::lando::LandoService;
+use crate::models::db_session::{DbSession, SessionStatus, TransactionState};
+use crate::core::result_format::{classify_cell, classify_columns, format_day_number, parse_json_cell, pretty_json_plan, CellKind, ChartData, ColumnKind, CopyFormat, ParsedResultSet};
+use crate::core::database::COPY_FORMAT_FILE;
+use crate::ui::result_grid::{cell_hover_text, cell_menu, cell_rich_text, show_result_grid, GridCellAction};
+use crate::core::snippets::SnippetSession;
+use crate::core::sql_lint::{LintSettings, LintWarning, SQL_LINT_FILE};
+use crate::core::storage::load_app_file;
+use crate::core::query_cache::format_cache_age;
+use crate::core::schedules::{SCHEDULE_MAX_INTERVAL, SCHEDULE_MIN_INTERVAL};
+use crate::core::diagram::{DIAGRAM_HEADER_HEIGHT, DIAGRAM_MAX_COLUMNS, DIAGRAM_ROW_HEIGHT};
+use crate::core::privileges::{generate_password, privilege_dialect, PrivilegeDialect};
+use crate::core::schema_search::{search_schema, SchemaMatcher, SchemaMatches};
+use crate::core::table_editor::insert_field_error;
+use crate::core::backups::{backups_beyond_retention, format_backup_date, format_size, DEFAULT_BACKUP_DIR};
+use crate::core::platform::reveal_in_file_manager;
+use crate::core::process_list::KillMode;
+use crate::core::secret::copy_secret;
+use crate::core::profiles::ConnectionStringFormat;
+use crate::core::query_search::{QuerySearchSource, QuerySearchState};
+use crate::core::history_export::HistoryExportFormat;
+use crate::core::dashboard::format_last_opened;
+use crate::core::time::now_secs;
+use crate::core::query_watch::{WatchDiff, WATCH_MAX_INTERVAL, WATCH_MIN_INTERVAL};
+use crate::core::csv_import::{CsvImportJob, CSV_PREVIEW_ROWS, DEFAULT_BATCH_SIZE};
+use crate::models::csv_import::{BatchErrorMode, CsvEncoding, CsvPreview, MissingColumns, ShortRows};
+use crate::models::secret::SecretString;
+use crate::models::backups::BackupEntry;
+use crate::ui::confirm::ConfirmDialog;
+use crate::ui::shortcuts::{consume_tab_shortcut, tab_shortcut_hint};
+use crate::ui::service::show_db_engine_selector;
+use crate::ui::theme::{error_color, focus_outline, icon_label, info_color, muted_color, success_color, warning_color};
+use crate::ui::secret::secret_text_edit;
 
 #[derive(Debug, Clone)]
 pub struct QueryResult {
     pub query: String,
-    pub result: String,
+    pub result: Arc<str>, // Compartido: el render y la caché lo toman prestado sin copiarlo
     pub execution_time: f64,
     pub timestamp: u64,
     pub rows_affected: Option<i32>,
     pub has_error: bool,
+    pub cached_at: Option<u64>, // Momento en que se obtuvo, si se sirvió desde la caché
+    pub request_id: Option<u64>, // Petición en curso cuya respuesta rellenará este resultado
+}
+
+// Resultado apartado para compararlo con las siguientes ejecuciones
+pub struct PinnedResult {
+    pub result: QueryResult,
+    pub set: Option<Arc<ParsedResultSet>>,
+    pub as_text: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -25,6 +69,24 @@ pub struct TableInfo {
     pub columns: Vec<ColumnInfo>,
     pub row_count: Option<i64>,
     pub table_type: String, // table, view, etc.
+    pub foreign_keys: Vec<ForeignKeyInfo>,
+}
+
+// Clave foránea vista desde su tabla: `column -> ref_table.ref_column`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ForeignKeyInfo {
+    pub column: String,
+    pub ref_table: String,
+    pub ref_column: String,
+}
+
+// Estado del navegador de tablas antes de seguir una clave foránea
+#[derive(Debug, Clone)]
+pub struct TableNavStep {
+    pub table: String,
+    pub page: usize,
+    pub filter: String,
+    pub target: String, // "users #42"
 }
 
 #[derive(Debug, Clone)]
@@ -34,6 +96,421 @@ pub struct ColumnInfo {
     pub nullable: bool,
     pub default_value: Option<String>,
     pub is_primary_key: bool,
+    pub stats: Option<ColumnStats>,
+}
+
+// Estadísticas rápidas de una columna (conteos, mínimo/máximo y valores frecuentes)
+#[derive(Debug, Clone, Default)]
+pub struct ColumnStats {
+    pub query: String,
+    pub values: Vec<(String, String)>,
+    pub top_values: Vec<(String, String)>,
+    pub loading: bool,
+    pub error: Option<String>,
+}
+
+// Peticiones en curso que esperan un `DbTaggedResult`
+#[derive(Debug, Clone, PartialEq)]
+pub enum PendingRequest {
+    DescribeTable { table: String },
+    ColumnStats { table: String, column: String },
+    ColumnTopValues { table: String, column: String },
+    TableData { table: String },
+    ApplyTableEdits { table: String },
+    ForeignKeys { db_type: String },
+    ScheduledQuery { schedule: String },
+    Users { db_type: String },
+    UserGrants { db_type: String, user: String, host: Option<String> },
+    ProcessList { db_type: String },
+    WatchQuery,
+    CsvImportBatch,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InsertValueMode {
+    Value,
+    Null,
+    Default,
+}
+
+// Campo del formulario "insertar fila", generado a partir de `ColumnInfo`
+#[derive(Debug, Clone, PartialEq)]
+pub struct InsertField {
+    pub column: String,
+    pub data_type: String,
+    pub nullable: bool,
+    pub default_value: Option<String>,
+    pub value: String,
+    pub mode: InsertValueMode,
+}
+
+// Cambios pendientes del modo edición del navegador de tablas.
+// Las celdas se indexan por (fila, columna) de la página cargada; `None` es NULL.
+#[derive(Debug, Clone, Default)]
+pub struct TableEditState {
+    pub enabled: bool,
+    pub edited_cells: HashMap<(usize, usize), Option<String>>,
+    pub editing_cell: Option<(usize, usize, String)>,
+    pub deleted_rows: BTreeSet<usize>,
+    pub pending_inserts: Vec<Vec<InsertField>>,
+    pub insert_form: Option<Vec<InsertField>>,
+    pub show_review: bool,
+    pub error: Option<String>,
+}
+
+// Asistente de importación de CSV. `mapping` tiene, por cada columna del CSV,
+// la columna de la tabla a la que va (None = se omite).
+pub struct CsvImportState {
+    pub open: bool,
+    pub loading: bool,
+    pub error: Option<String>,
+    pub preview: Option<CsvPreview>,
+    pub table: String,
+    pub mapping: Vec<Option<String>>,
+    pub mapped_for: Option<String>, // Tabla con la que se calculó `mapping`
+    pub columns_requested: Option<String>,
+    pub missing_columns: MissingColumns,
+    pub short_rows: ShortRows,
+    pub batch_size: usize,
+    pub on_error: BatchErrorMode,
+    pub job: Option<CsvImportJob>,
+}
+
+impl Default for CsvImportState {
+    fn default() -> Self {
+        Self {
+            open: false,
+            loading: false,
+            error: None,
+            preview: None,
+            table: String::new(),
+            mapping: Vec::new(),
+            mapped_for: None,
+            columns_requested: None,
+            missing_columns: MissingColumns::Default,
+            short_rows: ShortRows::FillEmpty,
+            batch_size: DEFAULT_BATCH_SIZE,
+            on_error: BatchErrorMode::Stop,
+            job: None,
+        }
+    }
+}
+
+// Modo vigilancia del editor. Solo vive mientras dura la sesión del editor;
+// no se guarda entre reinicios.
+#[derive(Debug, Clone)]
+pub struct QueryWatchState {
+    pub enabled: bool,
+    pub interval_secs: u64,
+    pub query: String,
+    pub request_id: Option<u64>,
+    pub next_run: Option<Instant>,
+    pub failures: u32,
+    pub current: Option<ParsedResultSet>,
+    pub diff: Option<WatchDiff>, // Respecto a la ejecución anterior
+    pub last_update: Option<Instant>,
+    pub error: Option<String>,
+    pub stop_reason: Option<String>,
+}
+
+impl Default for QueryWatchState {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval_secs: 5,
+            query: String::new(),
+            request_id: None,
+            next_run: None,
+            failures: 0,
+            current: None,
+            diff: None,
+            last_update: None,
+            error: None,
+            stop_reason: None,
+        }
+    }
+}
+
+// Gráfico rápido del resultado actual. `source` identifica el resultado y las
+// columnas con que se calculó `data`, para no reparsear en cada frame.
+#[derive(Debug, Clone, Default)]
+pub struct ChartState {
+    pub enabled: bool,
+    pub x_column: usize,
+    pub y_column: usize,
+    pub source: Option<(usize, u64, usize, usize)>,
+    pub result_set: Option<ParsedResultSet>,
+    pub data: Option<ChartData>,
+    pub export_status: Option<Result<String, String>>,
+}
+
+// Relación `table.column -> ref_table.ref_column` leída del catálogo de la BD
+#[derive(Debug, Clone, PartialEq)]
+pub struct ForeignKey {
+    pub table: String,
+    pub column: String,
+    pub ref_table: String,
+    pub ref_column: String,
+}
+
+// Diagrama de relaciones. Las posiciones de las tablas se guardan por
+// proyecto y servicio en `layout_key`.
+#[derive(Debug, Clone, Default)]
+pub struct DiagramState {
+    pub foreign_keys: Vec<ForeignKey>,
+    pub positions: HashMap<String, egui::Pos2>,
+    pub layout_key: Option<String>,
+    pub loaded: bool,
+    pub loading: bool,
+    pub error: Option<String>,
+}
+
+// Usuario del servidor. `host` solo existe en MySQL; en PostgreSQL los
+// atributos del rol (superusuario, crear BD...) van en `attributes`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DbUser {
+    pub name: String,
+    pub host: Option<String>,
+    pub attributes: Vec<String>,
+    pub grants: Vec<UserGrant>,
+    pub grants_loaded: bool,
+}
+
+// Permisos sobre un objeto (`db.*`, `DATABASE app`, `public.users`...)
+#[derive(Debug, Clone, PartialEq)]
+pub struct UserGrant {
+    pub privileges: Vec<String>,
+    pub target: String,
+    pub grant_option: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UserAction {
+    Create,
+    ChangePassword,
+    Drop,
+    Grant,
+    Revoke,
+}
+
+// Proceso (conexión) activo del servidor. `id` es el Id de MySQL o el pid de PostgreSQL.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DbProcess {
+    pub id: u64,
+    pub user: String,
+    pub database: Option<String>,
+    pub state: String,
+    pub seconds: Option<u64>,
+    pub query: Option<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ProcessListState {
+    pub processes: Vec<DbProcess>,
+    pub loaded: bool,
+    pub loading: bool,
+    pub stale: bool, // Volver a cargar en el próximo frame (tras un KILL)
+    pub reload_after_query: bool,
+    pub error: Option<String>,
+}
+
+// Gestor de backups de la pestaña de herramientas. `dir` es relativo al proyecto;
+// `loaded_for` es el proyecto cuya carpeta se listó (la interfaz se reutiliza entre proyectos).
+#[derive(Debug, Clone)]
+pub struct BackupManagerState {
+    pub dir: String,
+    pub entries: Vec<BackupEntry>,
+    pub selected: Option<String>,
+    pub loaded_for: Option<PathBuf>,
+    pub loading: bool,
+    pub error: Option<String>,
+    pub keep_last: usize,
+}
+
+impl Default for BackupManagerState {
+    fn default() -> Self {
+        Self {
+            dir: DEFAULT_BACKUP_DIR.to_string(),
+            entries: Vec::new(),
+            selected: None,
+            loaded_for: None,
+            loading: false,
+            error: None,
+            keep_last: 5,
+        }
+    }
+}
+
+// Pestaña de usuarios: lista cargada y formulario de la acción elegida.
+// `table` vacío significa toda la base de datos.
+#[derive(Debug, Clone)]
+pub struct UserManagerState {
+    pub users: Vec<DbUser>,
+    pub selected: Option<usize>,
+    pub loaded: bool,
+    pub loading: bool,
+    pub reload_after_query: bool,
+    pub error: Option<String>,
+    pub action: UserAction,
+    pub name: String,
+    pub host: String,
+    pub password: SecretString,
+    pub show_password: bool,
+    pub privileges: Vec<String>,
+    pub database: String,
+    pub table: String,
+}
+
+impl Default for UserManagerState {
+    fn default() -> Self {
+        Self {
+            users: Vec::new(),
+            selected: None,
+            loaded: false,
+            loading: false,
+            reload_after_query: false,
+            error: None,
+            action: UserAction::Create,
+            name: String::new(),
+            host: "%".to_string(),
+            password: SecretString::default(),
+            show_password: false,
+            privileges: Vec::new(),
+            database: String::new(),
+            table: String::new(),
+        }
+    }
+}
+
+// Resultado guardado en la caché, indexado por servicio y consulta normalizada
+#[derive(Debug, Clone)]
+pub struct CachedQuery {
+    pub service: String,
+    pub query: String,
+    pub result: QueryResult,
+    pub fetched_at: u64,
+}
+
+// Caché LRU de resultados: las entradas usadas más recientemente van al final.
+// El tamaño se estima por la longitud del texto de consulta y resultado.
+#[derive(Debug, Clone)]
+pub struct QueryCache {
+    pub entries: Vec<CachedQuery>,
+    pub max_entries: usize,
+    pub max_bytes: usize,
+}
+
+impl Default for QueryCache {
+    fn default() -> Self {
+        Self {
+            entries: Vec::new(),
+            max_entries: 50,
+            max_bytes: 4 * 1024 * 1024,
+        }
+    }
+}
+
+// Cuándo avisa una consulta programada
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ScheduleCondition {
+    Never,
+    RowsAboveZero,
+    ResultChanged,
+}
+
+// Ejecución terminada de una consulta programada
+#[derive(Debug, Clone)]
+pub struct ScheduleRun {
+    pub finished_at: u64,
+    pub output: Result<String, String>,
+    pub row_count: Option<usize>,
+    pub triggered: bool,
+}
+
+// Estado de ejecución de una programación; no se guarda en disco
+#[derive(Debug, Clone, Default)]
+pub struct ScheduleRuntime {
+    pub next_run: Option<Instant>,
+    pub running: bool,
+    pub failures: u32,
+    pub paused_reason: Option<String>,
+    pub runs: VecDeque<ScheduleRun>,
+}
+
+// Consulta guardada que se relanza cada `interval_secs` en segundo plano
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuerySchedule {
+    pub query_name: String,
+    pub interval_secs: u64,
+    pub condition: ScheduleCondition,
+    pub enabled: bool,
+    pub desktop_notification: bool,
+    #[serde(skip)]
+    pub runtime: ScheduleRuntime,
+}
+
+// Formulario "programar consulta" de la pestaña de herramientas
+#[derive(Debug, Clone)]
+pub struct ScheduleForm {
+    pub query_name: String,
+    pub interval_secs: u64,
+    pub condition: ScheduleCondition,
+    pub desktop_notification: bool,
+}
+
+impl Default for ScheduleForm {
+    fn default() -> Self {
+        Self {
+            query_name: String::new(),
+            interval_secs: 60,
+            condition: ScheduleCondition::RowsAboveZero,
+            desktop_notification: false,
+        }
+    }
+}
+
+// Aviso de una programación que la app muestra como toast (y notificación nativa)
+#[derive(Debug, Clone)]
+pub struct ScheduleAlert {
+    pub title: String,
+    pub body: String,
+    pub desktop: bool,
+}
+
+// Juego de credenciales guardado para un servicio (ej. usuario de la app vs root)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ConnectionProfile {
+    pub name: String,
+    pub user: String,
+    // No se escribe en disco: tras reiniciar la app hay que volver a escribirla
+    #[serde(default, skip_serializing)]
+    pub password: SecretString,
+    #[serde(default)]
+    pub database: String,
+}
+
+// Operaciones de mantenimiento que se aplican tabla por tabla
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MaintenanceOp {
+    Optimize,
+    Repair,
+    Analyze,
+}
+
+// Comando pendiente de confirmación desde la pestaña de herramientas
+#[derive(Debug, Clone, PartialEq)]
+pub enum ToolCommand {
+    Sql(String),
+    // SQL que se ejecuta como desde el editor (historial y resultados)
+    Editor(String),
+    Lando(Vec<String>),
+    // Varios comandos de lando seguidos; si uno falla no se lanzan los siguientes.
+    // Con `secret` llevan contraseñas y no se apuntan en el historial.
+    LandoChain { steps: Vec<Vec<String>>, secret: bool },
+    // Cambios pendientes del modo edición del navegador de tablas
+    TableEdits,
+    // Archivos de la carpeta de backups
+    DeleteBackups(Vec<String>),
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -44,24 +521,47 @@ pub enum DatabaseTab {
     Connections,
     QueryHistory,
     Tools,
+    Diagram,
+    Users,
 }
 
 pub struct DatabaseUI {
     // Query Editor
     pub query_input: String,
+    pub snippet: Option<SnippetSession>, // Huecos de la última plantilla insertada
+    pub snippet_select_pending: bool, // Seleccionar el hueco actual en el próximo frame
     pub query_results: Vec<QueryResult>,
+    pub parsed_result: Option<(Arc<str>, Option<Arc<ParsedResultSet>>)>, // Tabla del resultado visible, por texto
     pub current_result_index: usize,
+    pub copy_format: CopyFormat, // Último formato usado al copiar resultados
+    pub connection_format: ConnectionStringFormat, // Último formato usado al copiar la conexión
+    pub results_export_status: Option<Result<String, String>>,
+    pub chart: ChartState,
+    pub query_watch: QueryWatchState,
     pub query_history: Vec<String>,
+    pub query_history_times: HashMap<String, u64>, // Última ejecución de cada consulta del historial
     pub selected_history_index: Option<usize>,
     pub saved_queries: HashMap<String, String>,
+    pub saved_queries_key: Option<String>,
+    pub saved_query_times: HashMap<String, u64>, // Cuándo se guardó cada consulta
+    pub query_search: QuerySearchState,
     pub query_name_input: String,
+    pub saved_combo_filter: String,
+    pub history_combo_filter: String,
+    pub history_filter: String,
+    pub history_selection: BTreeSet<String>, // Consultas marcadas para "Exportar seleccionadas"
+    pub history_export_status: Option<Result<String, String>>,
     
     // Schema Browser
     pub tables: Vec<TableInfo>,
     pub selected_table: Option<String>,
     pub schema_filter: String,
+    pub schema_case_sensitive: bool,
+    pub schema_highlight: Option<(String, Option<String>)>, // (tabla, columna) elegida en los resultados
+    pub schema_jump_pending: bool, // Abrir la tabla y desplazarse a ella en el próximo frame
     pub show_views: bool,
     pub show_procedures: bool,
+    pub stats_popup: Option<(String, String)>, // (tabla, columna)
     
     // Table Browser
     pub table_data: String,
@@ -71,13 +571,33 @@ pub struct DatabaseUI {
     pub table_sort_column: String,
     pub table_sort_desc: bool,
     pub table_filter: String,
+    pub table_rows: Option<ParsedResultSet>,
+    pub table_selection: BTreeSet<usize>, // Filas marcadas para copiar
+    pub table_edit: TableEditState,
+    pub needs_table_reload: bool,
+    pub table_nav: Vec<TableNavStep>, // Pila del botón "Atrás"
+    pub needs_foreign_keys: bool,     // Pedir las claves foráneas tras recargar el schema
+
+    // Diagrama de relaciones
+    pub diagram: DiagramState,
+    pub user_manager: UserManagerState,
+    pub backups: BackupManagerState,
+    pub process_list: ProcessListState,
+    pub csv_import: CsvImportState,
     
     // Connection Management
     pub new_user: String,
-    pub new_password: String,
+    pub new_password: SecretString,
     pub new_database: String,
     pub connection_status: ConnectionStatus,
     pub connection_test_result: String,
+    pub credentials_error: Option<String>,
+    pub connection_profiles: Vec<ConnectionProfile>,
+    pub active_profile: Option<usize>,
+    pub profiles_key: Option<String>,
+    pub profile_name_input: String,
+    pub profile_error: Option<String>,
+    pub external_client_error: Option<String>,
     
     // UI State
     pub current_tab: DatabaseTab,
@@ -90,7 +610,45 @@ pub struct DatabaseUI {
     // Performance
     pub query_timeout: u32,
     pub max_rows: usize,
+    pub result_as_text: bool, // Resultados como texto en vez de rejilla
+    pub pinned_result: Option<PinnedResult>, // Se muestra al lado y no lo desplazan las nuevas ejecuciones
+    pub thousands_separators: bool, // `1,234,567` en las columnas numéricas
+    pub json_viewer: Option<(String, serde_json::Value, bool)>, // Columna, valor y vista de texto
+    pub auto_limit_selects: bool, // Añadir `LIMIT max_rows` sin preguntar a los SELECT sin límite
+    pub pending_limit_query: Option<String>, // SELECT sin LIMIT esperando decisión
+    pub lint_settings: LintSettings,
+    pub lint_cache: Option<(String, Vec<LintWarning>)>, // Avisos del texto actual del editor
+    pub lint_blocked: bool, // Se intentó ejecutar con un aviso que bloquea
     pub enable_query_cache: bool,
+    pub explain_json: bool,
+    pub query_cache: QueryCache,
+    pub pending_cache_keys: HashMap<u64, (String, String)>,
+
+    // Tools
+    pub maintenance_tables: Vec<String>,
+    pub pending_tool: Option<(ToolCommand, ConfirmDialog)>,
+    // Modo transacción: sesión persistente y aviso si se cerró sola
+    pub db_session: Option<DbSession>,
+    pub session_warning: Option<String>,
+    pub schedules: Vec<QuerySchedule>,
+    pub schedule_form: ScheduleForm,
+    pub schedule_alerts: Vec<ScheduleAlert>,
+    pub schedules_error: Option<String>,
+
+    // Archivos .sql soltados sobre la ventana
+    pub sql_drop_threshold_kb: u64,
+    pub pending_sql_file: Option<(PathBuf, String)>,
+
+    // Peticiones correlacionadas por id
+    pub pending_requests: HashMap<u64, PendingRequest>,
+}
+
+fn schedule_condition_label(condition: ScheduleCondition) -> &'static str {
+    match condition {
+        ScheduleCondition::Never => "Solo registrar",
+        ScheduleCondition::RowsAboveZero => "Avisar si hay filas",
+        ScheduleCondition::ResultChanged => "Avisar si cambia",
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -106,19 +664,40 @@ impl Default for DatabaseUI {
         Self {
             // Query Editor
             query_input: String::new(),
+            snippet: None,
+            snippet_select_pending: false,
             query_results: Vec::new(),
+            parsed_result: None,
             current_result_index: 0,
+            copy_format: load_app_file(COPY_FORMAT_FILE),
+            connection_format: ConnectionStringFormat::default(),
+            results_export_status: None,
+            chart: ChartState::default(),
+            query_watch: QueryWatchState::default(),
             query_history: Vec::new(),
+            query_history_times: HashMap::new(),
             selected_history_index: None,
             saved_queries: HashMap::new(),
+            saved_queries_key: None,
+            saved_query_times: HashMap::new(),
+            query_search: QuerySearchState::default(),
             query_name_input: String::new(),
+            saved_combo_filter: String::new(),
+            history_combo_filter: String::new(),
+            history_filter: String::new(),
+            history_selection: BTreeSet::new(),
+            history_export_status: None,
             
             // Schema Browser
             tables: Vec::new(),
             selected_table: None,
             schema_filter: String::new(),
+            schema_case_sensitive: false,
+            schema_highlight: None,
+            schema_jump_pending: false,
             show_views: true,
             show_procedures: true,
+            stats_popup: None,
             
             // Table Browser
             table_data: String::new(),
@@ -128,13 +707,33 @@ impl Default for DatabaseUI {
             table_sort_column: String::new(),
             table_sort_desc: false,
             table_filter: String::new(),
+            table_rows: None,
+            table_selection: BTreeSet::new(),
+            table_edit: TableEditState::default(),
+            needs_table_reload: false,
+            table_nav: Vec::new(),
+            needs_foreign_keys: false,
+
+            // Diagrama de relaciones
+            diagram: DiagramState::default(),
+            user_manager: UserManagerState::default(),
+            backups: BackupManagerState::default(),
+            process_list: ProcessListState::default(),
+            csv_import: CsvImportState::default(),
             
             // Connection Management
             new_user: String::new(),
-            new_password: String::new(),
+            new_password: SecretString::default(),
             new_database: String::new(),
             connection_status: ConnectionStatus::Disconnected,
             connection_test_result: String::new(),
+            credentials_error: None,
+            connection_profiles: Vec::new(),
+            active_profile: None,
+            profiles_key: None,
+            profile_name_input: String::new(),
+            profile_error: None,
+            external_client_error: None,
             
             // UI State
             current_tab: DatabaseTab::QueryEditor,
@@ -147,7 +746,36 @@ impl Default for DatabaseUI {
             // Performance
             query_timeout: 30,
             max_rows: 1000,
+            result_as_text: false,
+            pinned_result: None,
+            thousands_separators: false,
+            json_viewer: None,
+            auto_limit_selects: false,
+            pending_limit_query: None,
+            lint_settings: load_app_file(SQL_LINT_FILE),
+            lint_cache: None,
+            lint_blocked: false,
             enable_query_cache: true,
+            explain_json: false,
+            query_cache: QueryCache::default(),
+            pending_cache_keys: HashMap::new(),
+
+            // Tools
+            maintenance_tables: Vec::new(),
+            pending_tool: None,
+            db_session: None,
+            session_warning: None,
+            schedules: Vec::new(),
+            schedule_form: ScheduleForm::default(),
+            schedule_alerts: Vec::new(),
+            schedules_error: None,
+
+            // Archivos .sql soltados sobre la ventana
+            sql_drop_threshold_kb: 1024,
+            pending_sql_file: None,
+
+            // Peticiones correlacionadas por id
+            pending_requests: HashMap::new(),
         }
     }
 }
@@ -161,8 +789,11 @@ impl DatabaseUI {
         project_path: &PathBuf,
         sender: &Sender<LandoCommandOutcome>,
         is_loading: &mut bool,
-        _terminal: &mut TerminalBackend,
+        _terminal: &mut LazyTerminal,
     ) {
+        self.ensure_connection_profiles(service, project_path);
+        self.ensure_saved_queries(service, project_path);
+
         // Botón prominente para abrir la interfaz de base de datos
         ui.horizontal(|ui| {
             ui.heading(format!("🗄️ {} ({})", service.service, service.r#type));
@@ -178,7 +809,7 @@ impl DatabaseUI {
         // Información básica del servicio
         ui.group(|ui| {
             ui.horizontal(|ui| {
-                ui.label(format!("📊 Tipo: {}", service.r#type));
+                ui.label(format!("📊 Tipo: {}", service.db_engine()));
                 ui.label(format!("🏷️ Versión: {}", service.version));
                 
                 if let Some(creds) = &service.creds {
@@ -223,13 +854,7 @@ impl DatabaseUI {
         // Estado de conexión con botón de test
         ui.horizontal(|ui| {
             ui.label("🔗 Estado:");
-            let (color, icon, text) = match &self.connection_status {
-                ConnectionStatus::Connected => (egui::Color32::GREEN, "✅", "Conectado"),
-                ConnectionStatus::Disconnected => (egui::Color32::RED, "❌", "Desconectado"),
-                ConnectionStatus::Testing => (egui::Color32::YELLOW, "⏳", "Probando..."),
-                ConnectionStatus::Error(err) => (egui::Color32::RED, "🚫", err.as_str()),
-            };
-            ui.colored_label(color, format!("{} {}", icon, text));
+            self.show_connection_status_label(ui);
             
             ui.separator();
             
@@ -245,7 +870,7 @@ impl DatabaseUI {
         ui.heading("🔧 Interfaz Completa de Base de Datos");
         
         // Navegación por pestañas
-        self.show_tab_navigation(ui);
+        self.show_tab_navigation(ui, service);
         
         ui.separator();
         
@@ -253,6 +878,11 @@ impl DatabaseUI {
         if self.show_save_query_dialog {
             self.show_save_query_dialog(ui);
         }
+
+        self.show_pending_tool_dialog(ui.ctx(), service, project_path, sender, is_loading);
+        self.show_limit_prompt(ui.ctx(), service, project_path, sender, is_loading);
+        self.show_json_viewer(ui.ctx());
+        self.show_pending_sql_file_dialog(ui.ctx());
         
         // Contenido según la pestaña seleccionada
         match self.current_tab {
@@ -278,6 +908,12 @@ impl DatabaseUI {
             DatabaseTab::Tools => {
                 self.show_database_tools(ui, service, project_path, sender, is_loading);
             },
+            DatabaseTab::Diagram => {
+                self.show_diagram(ui, service, project_path, sender, is_loading);
+            },
+            DatabaseTab::Users => {
+                self.show_user_manager(ui, service, project_path, sender);
+            },
         }
     }
 
@@ -288,12 +924,27 @@ impl DatabaseUI {
         project_path: &PathBuf,
         sender: &Sender<LandoCommandOutcome>,
         is_loading: &mut bool,
-        terminal: &mut TerminalBackend,
+        terminal: &mut LazyTerminal,
     ) {
+        self.ensure_connection_profiles(service, project_path);
+        self.ensure_saved_queries(service, project_path);
+
         // Navegación por pestañas
-        self.show_tab_navigation(ui);
+        self.show_tab_navigation(ui, service);
+
+        self.tick_query_watch(service, project_path, sender);
+        self.tick_csv_import(service, project_path, sender);
+        self.show_csv_import_window(ui.ctx(), service, project_path, sender);
+        if self.query_watch.enabled {
+            ui.ctx().request_repaint_after(std::time::Duration::from_secs(1));
+        }
         
         ui.separator();
+
+        self.show_pending_tool_dialog(ui.ctx(), service, project_path, sender, is_loading);
+        self.show_limit_prompt(ui.ctx(), service, project_path, sender, is_loading);
+        self.show_json_viewer(ui.ctx());
+        self.show_pending_sql_file_dialog(ui.ctx());
         
         // Contenido según la pestaña seleccionada
         match self.current_tab {
@@ -319,6 +970,12 @@ impl DatabaseUI {
             DatabaseTab::Tools => {
                 self.show_database_tools(ui, service, project_path, sender, is_loading);
             },
+            DatabaseTab::Diagram => {
+                self.show_diagram(ui, service, project_path, sender, is_loading);
+            },
+            DatabaseTab::Users => {
+                self.show_user_manager(ui, service, project_path, sender);
+            },
         }
     }
 
@@ -327,7 +984,7 @@ impl DatabaseUI {
             // Información básica
             ui.vertical(|ui| {
                 ui.heading(format!("🗄️ {}", service.service));
-                ui.label(format!("📊 Tipo: {}", service.r#type));
+                ui.label(format!("📊 Tipo: {}", service.db_engine()));
                 ui.label(format!("🏷️ Versión: {}", service.version));
             });
             
@@ -336,13 +993,7 @@ impl DatabaseUI {
             // Estado de conexión
             ui.vertical(|ui| {
                 ui.label("🔗 Estado de Conexión:");
-                let (color, icon, text) = match &self.connection_status {
-                    ConnectionStatus::Connected => (egui::Color32::GREEN, "✅", "Conectado"),
-                    ConnectionStatus::Disconnected => (egui::Color32::RED, "❌", "Desconectado"),
-                    ConnectionStatus::Testing => (egui::Color32::YELLOW, "⏳", "Probando..."),
-                    ConnectionStatus::Error(err) => (egui::Color32::RED, "🚫", err.as_str()),
-                };
-                ui.colored_label(color, format!("{} {}", icon, text));
+                self.show_connection_status_label(ui);
                 
                 if let Some(conn) = &service.external_connection {
                     ui.label(format!("🌐 {}:{}", conn.host, conn.port));
@@ -361,14 +1012,32 @@ impl DatabaseUI {
         });
     }
     
-    fn show_tab_navigation(&mut self, ui: &mut egui::Ui) {
+    fn show_tab_navigation(&mut self, ui: &mut egui::Ui, service: &LandoService) {
+        let mut tabs = vec![
+            (DatabaseTab::QueryEditor, "✏️ Editor SQL"),
+            (DatabaseTab::SchemaExplorer, "🗂️ Schema"),
+            (DatabaseTab::TableBrowser, "📋 Tablas"),
+            (DatabaseTab::Connections, "🔗 Conexiones"),
+            (DatabaseTab::QueryHistory, "📜 Historial"),
+            (DatabaseTab::Tools, "🔧 Herramientas"),
+            (DatabaseTab::Diagram, "🕸️ Diagrama"),
+        ];
+        // SQLite no tiene usuarios: la pestaña solo aparece en MySQL/PostgreSQL
+        if self.supports_user_management(service.db_engine()) {
+            tabs.push((DatabaseTab::Users, "👥 Usuarios"));
+        } else if self.current_tab == DatabaseTab::Users {
+            self.current_tab = DatabaseTab::QueryEditor;
+        }
+
+        if let Some(index) = consume_tab_shortcut(ui, tabs.len()) {
+            self.current_tab = tabs[index].0.clone();
+        }
+
         ui.horizontal(|ui| {
-            ui.selectable_value(&mut self.current_tab, DatabaseTab::QueryEditor, "✏️ Editor SQL");
-            ui.selectable_value(&mut self.current_tab, DatabaseTab::SchemaExplorer, "🗂️ Schema");
-            ui.selectable_value(&mut self.current_tab, DatabaseTab::TableBrowser, "📋 Tablas");
-            ui.selectable_value(&mut self.current_tab, DatabaseTab::Connections, "🔗 Conexiones");
-            ui.selectable_value(&mut self.current_tab, DatabaseTab::QueryHistory, "📜 Historial");
-            ui.selectable_value(&mut self.current_tab, DatabaseTab::Tools, "🔧 Herramientas");
+            for (index, (tab, label)) in tabs.into_iter().enumerate() {
+                ui.selectable_value(&mut self.current_tab, tab, label)
+                    .on_hover_text(tab_shortcut_hint(index));
+            }
         });
     }
     
@@ -387,7 +1056,7 @@ impl DatabaseUI {
                 ui.separator();
                 
                 // Templates SQL específicos por tipo de BD
-                let templates = self.get_sql_templates(&service.r#type);
+                let templates = self.get_sql_templates(service.db_engine());
                 let mut template_to_insert = None;
                 for (name, sql) in templates {
                     if ui.small_button(name).clicked() {
@@ -402,7 +1071,7 @@ impl DatabaseUI {
                 
                 // Herramientas del editor
                 if ui.button("📝 Formato").on_hover_text("Formatear SQL (Ctrl+Shift+F)").clicked() {
-                    self.format_query();
+                    self.format_query(service.db_engine());
                 }
                 
                 if ui.button("🗑️ Limpiar").on_hover_text("Limpiar editor (Ctrl+L)").clicked() {
@@ -422,11 +1091,16 @@ impl DatabaseUI {
                 ui.separator();
                 ui.checkbox(&mut self.split_view, "📱 Vista dividida");
             });
+
+            if self.supports_transaction_mode(service) {
+                self.show_transaction_controls(ui, service, project_path, sender, is_loading);
+            }
         });
         
         ui.separator();
         
         // Editor de consultas principal
+        let mut picked_query = false;
         ui.vertical(|ui| {
             ui.horizontal(|ui| {
                 ui.label("📝 Query SQL:");
@@ -434,54 +1108,90 @@ impl DatabaseUI {
                     // Queries guardadas
                     if !self.saved_queries.is_empty() {
                         egui::ComboBox::new("saved_queries_combo", "💾 Guardadas")
+                            .height(300.0)
                             .show_ui(ui, |ui| {
-                                for (name, query) in &self.saved_queries {
-                                    if ui.selectable_label(false, name).clicked() {
+                                ui.add(egui::TextEdit::singleline(&mut self.saved_combo_filter)
+                                    .hint_text("🔍 Filtrar...")
+                                    .desired_width(200.0));
+
+                                // Orden alfabético: el HashMap no garantiza un orden estable entre frames
+                                let filter = self.saved_combo_filter.to_lowercase();
+                                let mut names: Vec<&String> = self.saved_queries.keys()
+                                    .filter(|name| filter.is_empty() || name.to_lowercase().contains(&filter))
+                                    .collect();
+                                names.sort_by_key(|name| name.to_lowercase());
+
+                                if names.is_empty() {
+                                    ui.label(egui::RichText::new("Sin coincidencias").color(muted_color(ui.visuals())));
+                                }
+                                for name in names {
+                                    let query = &self.saved_queries[name];
+                                    if ui.selectable_label(false, name).on_hover_text(query).clicked() {
                                         self.query_input = query.clone();
+                                        picked_query = true;
                                     }
                                 }
                             });
                     }
                     
-                    // Historial de queries
+                    // Historial de queries (más recientes primero)
                     if !self.query_history.is_empty() {
                         egui::ComboBox::new("history_combo", "📜 Historial")
+                            .height(300.0)
                             .show_ui(ui, |ui| {
-                                for (i, query) in self.query_history.iter().enumerate().rev().take(10) {
-                                    let preview = if query.len() > 50 {
-                                        format!("{}...", &query[..50])
+                                ui.add(egui::TextEdit::singleline(&mut self.history_combo_filter)
+                                    .hint_text("🔍 Buscar en el historial...")
+                                    .desired_width(260.0));
+
+                                let filter = self.history_combo_filter.to_lowercase();
+                                let matches: Vec<&String> = self.query_history.iter()
+                                    .rev()
+                                    .filter(|query| filter.is_empty() || query.to_lowercase().contains(&filter))
+                                    .collect();
+
+                                if matches.is_empty() {
+                                    ui.label(egui::RichText::new("Sin coincidencias").color(muted_color(ui.visuals())));
+                                }
+                                for query in matches {
+                                    let single_line = query.split_whitespace().collect::<Vec<_>>().join(" ");
+                                    let preview = if single_line.chars().count() > 50 {
+                                        format!("{}...", single_line.chars().take(50).collect::<String>())
                                     } else {
-                                        query.clone()
+                                        single_line
                                     };
-                                    if ui.selectable_label(false, preview).clicked() {
+                                    if ui.selectable_label(false, preview).on_hover_text(query).clicked() {
                                         self.query_input = query.clone();
+                                        picked_query = true;
                                     }
                                 }
                             });
                     }
                 });
             });
+            if picked_query {
+                self.show_cached_result(service);
+            }
             
             let editor_rows = self.get_editor_rows();
-            let text_edit = ui.add(
-                egui::TextEdit::multiline(&mut self.query_input)
+            let text_edit = self.show_sql_editor(ui, |editor| {
+                editor
                     .hint_text("-- Escribe tu consulta SQL aquí\n-- Ejemplos:\nSELECT * FROM users LIMIT 10;\nSHOW TABLES;\nDESCRIBE table_name;")
                     .code_editor()
                     .desired_rows(editor_rows)
                     .desired_width(f32::INFINITY)
                     .lock_focus(true)
-            );
+            });
             
             // Shortcuts de teclado mejorados
             if text_edit.has_focus() {
                 ui.ctx().input(|i| {
                     // Ejecutar query
                     if i.key_pressed(egui::Key::F9) || (i.modifiers.ctrl && i.key_pressed(egui::Key::Enter)) {
-                        self.execute_query(service, project_path, sender, is_loading);
+                        self.run_editor_query(service, project_path, sender, is_loading);
                     }
                     // Formatear
                     if i.modifiers.ctrl && i.modifiers.shift && i.key_pressed(egui::Key::F) {
-                        self.format_query();
+                        self.format_query(service.db_engine());
                     }
                     // Limpiar
                     if i.modifiers.ctrl && i.key_pressed(egui::Key::L) {
@@ -503,12 +1213,14 @@ impl DatabaseUI {
                 if !self.query_input.is_empty() {
                     ui.separator();
                     if self.is_valid_sql(&self.query_input) {
-                        ui.colored_label(egui::Color32::GREEN, "✓ SQL válido");
+                        ui.colored_label(success_color(ui.visuals()), "✓ SQL válido");
                     } else {
-                        ui.colored_label(egui::Color32::YELLOW, "⚠ Revisar sintaxis");
+                        ui.colored_label(warning_color(ui.visuals()), "⚠ Revisar sintaxis");
                     }
                 }
             });
+
+            self.show_lint_warnings(ui, service, project_path, sender, is_loading);
         });
         
         ui.separator();
@@ -522,20 +1234,37 @@ impl DatabaseUI {
                     .fill(if can_execute { egui::Color32::from_rgb(34, 139, 34) } else { egui::Color32::GRAY })
             );
             
-            if execute_btn.clicked() {
-                self.execute_query(service, project_path, sender, is_loading);
+            focus_outline(ui, &execute_btn);
+            if execute_btn.on_hover_text("Ejecutar (F9 / Ctrl+Enter)").clicked() {
+                self.run_editor_query(service, project_path, sender, is_loading);
             }
+            self.show_watch_controls(ui);
             
             // Botones de acción rápida
             if ui.button("⏹️ Explicar").on_hover_text("EXPLAIN query").clicked() {
                 self.explain_query(service, project_path, sender, is_loading);
             }
+
+            let analyze_prefix = self.explain_analyze_prefix(service.db_engine());
+            let analyze_hint = format!("{} — ejecuta la consulta de verdad para medir tiempos reales", analyze_prefix.unwrap_or("EXPLAIN ANALYZE"));
+            if ui.add_enabled(can_execute && analyze_prefix.is_some(), egui::Button::new("⏱️ Analizar"))
+                .on_hover_text(analyze_hint)
+                .on_disabled_hover_text("No disponible para este motor")
+                .clicked()
+            {
+                self.explain_analyze_query(service, project_path, sender, is_loading);
+            }
+            if matches!(service.db_engine().to_lowercase().as_str(), "postgresql" | "postgres") {
+                ui.checkbox(&mut self.explain_json, "JSON").on_hover_text("EXPLAIN (ANALYZE, FORMAT JSON)");
+            }
             
             ui.separator();
             
             // Configuración de ejecución
             ui.label("📋 Límite:");
             ui.add(egui::DragValue::new(&mut self.max_rows).range(1..=50000).speed(10));
+            ui.checkbox(&mut self.auto_limit_selects, "Auto LIMIT")
+                .on_hover_text("Añadir LIMIT a los SELECT que no lo tienen sin preguntar");
             
             ui.label("⏰ Timeout:");
             ui.add(egui::DragValue::new(&mut self.query_timeout).range(5..=600).suffix("s"));
@@ -550,81 +1279,10 @@ impl DatabaseUI {
         ui.separator();
         
         // Área de resultados mejorada
-        self.show_query_results(ui);
-    }
-    
-    fn show_query_results(&mut self, ui: &mut egui::Ui) {
-        if !self.query_results.is_empty() {
-            ui.group(|ui| {
-                ui.horizontal(|ui| {
-                    ui.strong(format!("📊 Resultados ({}):", self.query_results.len()));
-                    
-                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                        if ui.small_button("📋").on_hover_text("Copiar resultado").clicked() {
-                            if let Some(result) = self.query_results.get(self.current_result_index) {
-                                ui.ctx().copy_text(result.result.clone());
-                            }
-                        }
-                        
-                        if ui.small_button("💾").on_hover_text("Exportar a CSV").clicked() {
-                            self.export_results_to_csv();
-                        }
-                        
-                        if self.query_results.len() > 1 {
-                            ui.separator();
-                            if ui.small_button("◀️").clicked() && self.current_result_index > 0 {
-                                self.current_result_index -= 1;
-                            }
-                            ui.label(format!("{}/{}", self.current_result_index + 1, self.query_results.len()));
-                            if ui.small_button("▶️").clicked() && self.current_result_index < self.query_results.len() - 1 {
-                                self.current_result_index += 1;
-                            }
-                        }
-                    });
-                });
-                
-                if let Some(result) = self.query_results.get(self.current_result_index) {
-                    // Información de la consulta
-                    ui.horizontal(|ui| {
-                        ui.label(format!("⏱️ Tiempo: {:.2}ms", result.execution_time));
-                        if let Some(rows) = result.rows_affected {
-                            ui.label(format!("📋 Filas: {}", rows));
-                        }
-                        ui.label(format!("🗺️ {}", self.format_timestamp(result.timestamp)));
-                        
-                        if result.has_error {
-                            ui.colored_label(egui::Color32::RED, "❌ Error");
-                        } else {
-                            ui.colored_label(egui::Color32::GREEN, "✅ Éxito");
-                        }
-                    });
-                    
-                    ui.separator();
-                    
-                    // Contenido del resultado
-                    egui::ScrollArea::vertical()
-                        .max_height(400.0)
-                        .show(ui, |ui| {
-                            ui.add(
-                                egui::TextEdit::multiline(&mut result.result.clone())
-                                    .code_editor()
-                                    .desired_width(f32::INFINITY)
-                                    .interactive(false)
-                            );
-                        });
-                }
-            });
-        } else {
-            ui.vertical_centered(|ui| {
-                ui.add_space(50.0);
-                ui.label("💭 No hay resultados aún");
-                ui.label("Ejecuta una consulta para ver los resultados aquí");
-                ui.add_space(50.0);
-            });
-        }
+        self.show_query_results(ui, service, project_path, sender, is_loading);
     }
     
-    fn show_split_query_editor(
+    fn show_transaction_controls(
         &mut self,
         ui: &mut egui::Ui,
         service: &LandoService,
@@ -632,47 +1290,854 @@ impl DatabaseUI {
         sender: &Sender<LandoCommandOutcome>,
         is_loading: &mut bool,
     ) {
-        ui.columns(2, |columns| {
-            // Panel izquierdo - Editor
-            columns[0].vertical(|ui| {
-                ui.strong("✏️ Editor SQL");
-                ui.separator();
-                
-                // Controles del editor
-                ui.horizontal_wrapped(|ui| {
-                    if ui.button("📋 SELECT").clicked() {
-                        self.insert_template("SELECT * FROM table_name LIMIT 10;");
-                    }
-                    if ui.button("🔍 DESCRIBE").clicked() {
-                        self.insert_template(&self.get_describe_template(&service.r#type));
-                    }
-                    if ui.button("📊 COUNT").clicked() {
-                        self.insert_template("SELECT COUNT(*) FROM table_name;");
-                    }
-                });
-                
-                ui.separator();
-                
-                // Editor principal
-                ui.add(
-                    egui::TextEdit::multiline(&mut self.query_input)
-                        .hint_text("-- Tu consulta SQL")
-                        .code_editor()
-                        .desired_rows(15)
-                        .desired_width(f32::INFINITY)
-                );
-                
-                ui.horizontal(|ui| {
+        ui.horizontal(|ui| {
+            let mut enabled = self.db_session.is_some();
+            if ui.checkbox(&mut enabled, "🔒 Modo transacción")
+                .on_hover_text("Las consultas se envían por una sesión persistente en vez de una por consulta. Al desactivarlo se hace ROLLBACK de lo no confirmado.")
+                .changed()
+            {
+                if enabled {
+                    self.start_transaction_mode(service, project_path, sender);
+                } else {
+                    self.stop_transaction_mode();
+                }
+            }
+
+            let Some(session) = &self.db_session else {
+                return;
+            };
+            ui.separator();
+
+            let (status, color) = match (&session.status, session.transaction) {
+                (SessionStatus::Starting, _) => (format!("⏳ Conectando con {}...", session.service), muted_color(ui.visuals())),
+                (_, TransactionState::Open { statements }) => (format!("🟠 transacción abierta ({} sentencias)", statements), warning_color(ui.visuals())),
+                (_, TransactionState::Autocommit) => (format!("🟢 sesión abierta con {} (autocommit)", session.service), success_color(ui.visuals())),
+            };
+            ui.colored_label(color, status);
+
+            let ready = session.status.accepts_statements() && !*is_loading;
+            let open = session.transaction.is_open();
+            let mut statement = None;
+            if ui.add_enabled(ready && !open, egui::Button::new("▶️ BEGIN")).clicked() {
+                statement = Some("BEGIN;");
+            }
+            if ui.add_enabled(ready && open, egui::Button::new("✅ COMMIT")).clicked() {
+                statement = Some("COMMIT;");
+            }
+            if ui.add_enabled(ready && open, egui::Button::new("↩️ ROLLBACK")).clicked() {
+                statement = Some("ROLLBACK;");
+            }
+            if let Some(statement) = statement {
+                self.run_transaction_statement(statement, is_loading);
+            }
+        });
+
+        if let Some(warning) = &self.session_warning {
+            let mut dismiss = false;
+            ui.horizontal(|ui| {
+                ui.colored_label(warning_color(ui.visuals()), format!("⚠️ {}", warning));
+                dismiss = ui.small_button("✖").on_hover_text("Cerrar aviso").clicked();
+            });
+            if dismiss {
+                self.session_warning = None;
+            }
+        }
+    }
+
+    fn show_result_chart(&mut self, ui: &mut egui::Ui) {
+        let columns = self.chart.result_set.as_ref().map(|set| set.columns.clone()).unwrap_or_default();
+
+        ui.horizontal(|ui| {
+            for (label, salt, column) in [
+                ("X:", "chart_x_column", &mut self.chart.x_column),
+                ("Y:", "chart_y_column", &mut self.chart.y_column),
+            ] {
+                ui.label(label);
+                egui::ComboBox::from_id_salt(salt)
+                    .selected_text(columns.get(*column).map(String::as_str).unwrap_or(""))
+                    .show_ui(ui, |ui| {
+                        for (index, name) in columns.iter().enumerate() {
+                            ui.selectable_value(column, index, name);
+                        }
+                    });
+            }
+
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if ui.button("💾 Exportar datos del gráfico a CSV").clicked() {
+                    self.export_chart_to_csv();
+                }
+            });
+        });
+
+        // Las columnas elegidas pueden haber cambiado este frame
+        self.refresh_chart();
+        let Some(data) = &self.chart.data else {
+            return;
+        };
+        let x_name = self.chart_column_name(self.chart.x_column).to_string();
+        let y_name = self.chart_column_name(self.chart.y_column).to_string();
+
+        ui.horizontal(|ui| {
+            ui.label(format!("📍 {} puntos", data.points.len()));
+            if data.skipped > 0 {
+                ui.colored_label(warning_color(ui.visuals()), format!("⚠️ {} filas omitidas (NULL o no numéricas)", data.skipped));
+            }
+            if let Some(total) = data.downsampled_from {
+                ui.colored_label(warning_color(ui.visuals()), format!("⚠️ Muestreados {} de {} puntos", data.points.len(), total));
+            }
+        });
+        match &self.chart.export_status {
+            Some(Ok(message)) => { ui.colored_label(success_color(ui.visuals()), format!("✅ {}", message)); }
+            Some(Err(message)) => { ui.colored_label(error_color(ui.visuals()), format!("❌ {}", message)); }
+            None => {}
+        }
+
+        if data.points.is_empty() {
+            ui.label(format!("No hay valores numéricos en '{}' para dibujar", y_name));
+            return;
+        }
+
+        let x_kind = data.x_kind;
+        let labels = data.labels.clone();
+        let points = data.points.clone();
+
+        let plot = egui_plot::Plot::new("query_result_chart")
+            .height(320.0)
+            .legend(egui_plot::Legend::default())
+            .x_axis_label(x_name.clone())
+            .y_axis_label(y_name.clone());
+
+        let axis_labels = labels.clone();
+        let plot = match x_kind {
+            ColumnKind::Text => plot.x_axis_formatter(move |mark, _range| {
+                let index = mark.value.round();
+                if (mark.value - index).abs() > f64::EPSILON || index < 0.0 {
+                    return String::new();
+                }
+                axis_labels.get(index as usize).cloned().unwrap_or_default()
+            }),
+            ColumnKind::Date => plot.x_axis_formatter(|mark, _range| format_day_number(mark.value)),
+            ColumnKind::Numeric => plot,
+        };
+
+        let hover_x_name = x_name.clone();
+        let hover_y_name = y_name.clone();
+        let plot = plot.label_formatter(move |name, value| {
+            if name.is_empty() {
+                return String::new();
+            }
+            // Valor original de X del punto más cercano al cursor
+            let x_label = points
+                .binary_search_by(|(x, _)| x.total_cmp(&value.x))
+                .ok()
+                .and_then(|i| labels.get(i).cloned())
+                .unwrap_or_else(|| match x_kind {
+                    ColumnKind::Date => format_day_number(value.x),
+                    _ => value.x.to_string(),
+                });
+            format!("{}: {}\n{}: {}", hover_x_name, x_label, hover_y_name, value.y)
+        });
+
+        plot.show(ui, |plot_ui| match x_kind {
+            ColumnKind::Text => {
+                let bars = data.points.iter()
+                    .zip(&data.labels)
+                    .map(|((x, y), label)| egui_plot::Bar::new(*x, *y).name(label))
+                    .collect();
+                let x_name = x_name.clone();
+                let y_name = y_name.clone();
+                plot_ui.bar_chart(
+                    egui_plot::BarChart::new(bars)
+                        .name(&y_name)
+                        .element_formatter(Box::new(move |bar: &egui_plot::Bar, _chart: &egui_plot::BarChart| {
+                            format!("{}: {}\n{}: {}", x_name, bar.name, y_name, bar.value)
+                        }))
+                );
+            }
+            ColumnKind::Date | ColumnKind::Numeric => {
+                let series: egui_plot::PlotPoints = data.points.iter().map(|(x, y)| [*x, *y]).collect();
+                plot_ui.line(egui_plot::Line::new(series).name(&y_name));
+            }
+        });
+    }
+
+    fn show_connection_status_label(&self, ui: &mut egui::Ui) {
+        let visuals = ui.visuals();
+        let (color, icon, text) = match &self.connection_status {
+            ConnectionStatus::Connected => (success_color(visuals), "✅", "Conectado"),
+            ConnectionStatus::Disconnected => (error_color(visuals), "❌", "Desconectado"),
+            ConnectionStatus::Testing => (warning_color(visuals), "⏳", "Probando..."),
+            ConnectionStatus::Error(err) => (error_color(visuals), "🚫", err.as_str()),
+        };
+        ui.colored_label(color, format!("{} {}", icon, text));
+    }
+
+    // Botón que copia con el último formato usado y desplegable para elegir
+    // otro. Los formatos por filas solo se ofrecen si el resultado se pudo
+    // leer como tabla; INSERT solo con la tabla de origen. Devuelve el formato
+    // pulsado: el texto se genera entonces y no en cada frame.
+    fn show_copy_menu(&self, ui: &mut egui::Ui, has_set: bool, has_table: bool) -> Option<CopyFormat> {
+        let available: Vec<CopyFormat> = CopyFormat::ALL.into_iter()
+            .filter(|format| match format {
+                CopyFormat::Text => true,
+                CopyFormat::Insert => has_set && has_table,
+                _ => has_set,
+            })
+            .collect();
+        let last = if available.contains(&self.copy_format) { self.copy_format } else { CopyFormat::Text };
+
+        let mut chosen = None;
+        ui.menu_button("▾", |ui| {
+            for format in &available {
+                if ui.button(format.label()).clicked() {
+                    chosen = Some(*format);
+                    ui.close_menu();
+                }
+            }
+        }).response.on_hover_text("Elegir formato");
+        if ui.small_button(format!("📋 {}", last.label())).on_hover_text("Copiar resultado").clicked() {
+            chosen = Some(last);
+        }
+        chosen
+    }
+
+    fn show_query_results(
+        &mut self,
+        ui: &mut egui::Ui,
+        service: &LandoService,
+        project_path: &PathBuf,
+        sender: &Sender<LandoCommandOutcome>,
+        is_loading: &mut bool,
+    ) {
+        if self.pinned_result.is_none() || self.query_watch.enabled {
+            self.show_current_results(ui, service, project_path, sender, is_loading);
+            return;
+        }
+
+        // Con un resultado fijado, el actual a la izquierda y el fijado a la derecha
+        ui.columns(2, |columns| {
+            self.show_current_results(&mut columns[0], service, project_path, sender, is_loading);
+            self.show_pinned_result(&mut columns[1]);
+        });
+    }
+
+    fn show_pinned_result(&mut self, ui: &mut egui::Ui) {
+        let Some(pinned) = &mut self.pinned_result else {
+            return;
+        };
+        let current = self.query_results.get(self.current_result_index)
+            .filter(|r| r.request_id.is_none() && !r.has_error);
+        let mut unpin = false;
+        let mut load_query = None;
+        let mut open_json = None;
+
+        ui.group(|ui| {
+            ui.horizontal(|ui| {
+                ui.strong("📌 Resultado fijado");
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui.small_button("✖").on_hover_text("Quitar el resultado fijado").clicked() {
+                        unpin = true;
+                    }
+                    if ui.small_button("✏️").on_hover_text("Llevar su consulta al editor").clicked() {
+                        load_query = Some(pinned.result.query.clone());
+                    }
+                    if pinned.set.is_some() {
+                        ui.toggle_value(&mut pinned.as_text, "🔤 Texto");
+                    }
+                });
+            });
+            ui.add(egui::Label::new(egui::RichText::new(pinned.result.query.replace('\n', " ")).monospace()).truncate())
+                .on_hover_text(pinned.result.query.as_str());
+
+            ui.horizontal(|ui| {
+                ui.label(format!("⏱️ Tiempo: {:.2}ms", pinned.result.execution_time));
+                if let Some(current) = current.filter(|_| pinned.result.execution_time > 0.0) {
+                    let delta = (current.execution_time - pinned.result.execution_time) / pinned.result.execution_time * 100.0;
+                    let color = if delta <= 0.0 { success_color(ui.visuals()) } else { warning_color(ui.visuals()) };
+                    ui.colored_label(color, format!("actual {:+.0}%", delta))
+                        .on_hover_text("Tiempo del resultado actual respecto al fijado");
+                }
+                if let Some(set) = &pinned.set {
+                    ui.label(format!("📋 {} filas × {} columnas", set.rows.len(), set.columns.len()));
+                }
+            });
+
+            ui.separator();
+
+            match (&pinned.set, pinned.as_text) {
+                (Some(set), false) => {
+                    let kinds = classify_columns(set, &[]);
+                    if let Some(GridCellAction::OpenJson { column, value }) =
+                        show_result_grid(ui, "pinned_result_grid", set, &kinds, self.thousands_separators, 400.0)
+                    {
+                        open_json = Some((column, value));
+                    }
+                }
+                _ => {
+                    egui::ScrollArea::vertical()
+                        .id_salt("pinned_result_text")
+                        .max_height(400.0)
+                        .show(ui, |ui| {
+                            ui.add(
+                                egui::TextEdit::multiline(&mut &*pinned.result.result)
+                                    .code_editor()
+                                    .desired_width(f32::INFINITY)
+                                    .interactive(false)
+                            );
+                        });
+                }
+            }
+        });
+
+        if let Some((column, value)) = open_json {
+            self.json_viewer = Some((column, value, false));
+        }
+        if let Some(query) = load_query {
+            self.query_input = query;
+        }
+        if unpin {
+            self.pinned_result = None;
+        }
+    }
+
+    fn show_current_results(
+        &mut self,
+        ui: &mut egui::Ui,
+        service: &LandoService,
+        project_path: &PathBuf,
+        sender: &Sender<LandoCommandOutcome>,
+        is_loading: &mut bool,
+    ) {
+        if self.query_watch.enabled {
+            self.show_watch_results(ui);
+            return;
+        }
+
+        self.refresh_chart();
+        // Las filas se interpretan una vez por resultado y la rejilla las indexa
+        let grid_set = if self.result_as_text { None } else { self.current_result_set() };
+        let mut rerun_query = None;
+        let mut pin_result = false;
+        if !self.query_results.is_empty() {
+            ui.group(|ui| {
+                ui.horizontal(|ui| {
+                    ui.strong(format!("📊 Resultados ({}):", self.query_results.len()));
+                    
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        if let Some(raw) = self.query_results.get(self.current_result_index).map(|r| Arc::clone(&r.result)) {
+                            let set = self.current_result_set();
+                            if let Some(format) = self.show_copy_menu(ui, set.is_some(), false) {
+                                if let Some(text) = self.format_copy(service.db_engine(), format, Some(&raw), set.as_deref(), None) {
+                                    ui.ctx().copy_text(text);
+                                }
+                                self.set_copy_format(format);
+                            }
+                        }
+                        
+                        if ui.small_button("💾").on_hover_text("Exportar a CSV").clicked() {
+                            self.export_results_to_csv();
+                        }
+                        
+                        if self.query_results.len() > 1 {
+                            ui.separator();
+                            let (mut previous, mut next) = ui.input_mut(|i| (
+                                i.consume_key(egui::Modifiers::COMMAND, egui::Key::PageUp),
+                                i.consume_key(egui::Modifiers::COMMAND, egui::Key::PageDown),
+                            ));
+                            // Con el foco en un campo de texto estas teclas mueven el cursor
+                            let (mut first, mut last) = (false, false);
+                            if !ui.ctx().wants_keyboard_input() {
+                                ui.input_mut(|i| {
+                                    previous |= i.consume_key(egui::Modifiers::ALT, egui::Key::ArrowLeft);
+                                    next |= i.consume_key(egui::Modifiers::ALT, egui::Key::ArrowRight);
+                                    first = i.consume_key(egui::Modifiers::COMMAND, egui::Key::Home);
+                                    last = i.consume_key(egui::Modifiers::COMMAND, egui::Key::End);
+                                });
+                            }
+                            if (icon_label(ui.small_button("◀️"), "Resultado anterior (Ctrl+RePág / Alt+←, Ctrl+Inicio: primero)").clicked() || previous)
+                                && self.current_result_index > 0
+                            {
+                                self.current_result_index -= 1;
+                            }
+                            ui.label(format!("{}/{}", self.current_result_index + 1, self.query_results.len()));
+                            if (icon_label(ui.small_button("▶️"), "Resultado siguiente (Ctrl+AvPág / Alt+→, Ctrl+Fin: último)").clicked() || next)
+                                && self.current_result_index < self.query_results.len() - 1
+                            {
+                                self.current_result_index += 1;
+                            }
+                            if first {
+                                self.current_result_index = 0;
+                            }
+                            if last {
+                                self.current_result_index = self.query_results.len() - 1;
+                            }
+                        }
+                    });
+                });
+                
+                match &self.results_export_status {
+                    Some(Ok(message)) => { ui.colored_label(success_color(ui.visuals()), format!("✅ {}", message)); }
+                    Some(Err(message)) => { ui.colored_label(error_color(ui.visuals()), format!("❌ {}", message)); }
+                    None => {}
+                }
+
+                let show_chart = self.chart.enabled && self.chart.result_set.is_some();
+                if let Some(result) = self.query_results.get(self.current_result_index) {
+                    if let Some(fetched_at) = result.cached_at {
+                        ui.horizontal(|ui| {
+                            ui.colored_label(
+                                info_color(ui.visuals()),
+                                egui::RichText::new(format!("💾 Resultado en caché, {} —", format_cache_age(fetched_at))).strong(),
+                            );
+                            if ui.add_enabled(!*is_loading, egui::Button::new("🔄 Volver a ejecutar")).clicked() {
+                                rerun_query = Some(result.query.clone());
+                            }
+                        });
+                    }
+
+                    // Información de la consulta
+                    let timestamp = self.format_timestamp(result.timestamp);
+                    ui.horizontal(|ui| {
+                        ui.label(format!("⏱️ Tiempo: {:.2}ms", result.execution_time));
+                        if let Some(rows) = result.rows_affected {
+                            ui.label(format!("📋 Filas: {}", rows));
+                        }
+                        ui.label(format!("🗺️ {}", timestamp));
+                        
+                        if result.has_error {
+                            ui.colored_label(error_color(ui.visuals()), "❌ Error");
+                        } else {
+                            ui.colored_label(success_color(ui.visuals()), "✅ Éxito");
+                        }
+
+                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                            if self.chart.result_set.is_some() {
+                                ui.toggle_value(&mut self.chart.enabled, "📈 Gráfico");
+                            }
+                            if !result.has_error {
+                                ui.toggle_value(&mut self.result_as_text, "🔤 Texto")
+                                    .on_hover_text("Salida original del cliente en vez de la rejilla");
+                            }
+                            if result.request_id.is_none()
+                                && ui.small_button("📌 Fijar")
+                                    .on_hover_text("Apartar este resultado para compararlo con las siguientes ejecuciones")
+                                    .clicked()
+                            {
+                                pin_result = true;
+                            }
+                        });
+                    });
+                    
+                    ui.separator();
+                    
+                    if let (false, Some(set)) = (show_chart, &grid_set) {
+                        ui.label(format!("{} filas × {} columnas", set.rows.len(), set.columns.len()));
+                        // Las consultas libres no traen tipos: se deducen de los valores
+                        let kinds = classify_columns(set, &[]);
+                        let action = show_result_grid(ui, ("result_grid", self.current_result_index), set, &kinds, self.thousands_separators, 400.0);
+                        if let Some(GridCellAction::OpenJson { column, value }) = action {
+                            self.json_viewer = Some((column, value, false));
+                        }
+                    } else if !show_chart {
+                        // Los planes JSON de Postgres se muestran formateados
+                        let is_json_plan = !result.has_error && result.query.to_uppercase().contains("FORMAT JSON");
+                        let pretty = is_json_plan.then(|| pretty_json_plan(&result.result)).flatten();
+                        let mut text: &str = pretty.as_deref().unwrap_or(&result.result);
+
+                        // Contenido del resultado
+                        egui::ScrollArea::vertical()
+                            .max_height(400.0)
+                            .show(ui, |ui| {
+                                ui.add(
+                                    egui::TextEdit::multiline(&mut text)
+                                        .code_editor()
+                                        .desired_width(f32::INFINITY)
+                                        .interactive(false)
+                                );
+                            });
+                    }
+                }
+
+                if show_chart {
+                    self.show_result_chart(ui);
+                }
+            });
+
+            if pin_result {
+                self.pin_current_result();
+            }
+            if let Some(query) = rerun_query {
+                self.query_input = query;
+                self.execute_query(service, project_path, sender, is_loading);
+            }
+        } else {
+            ui.vertical_centered(|ui| {
+                ui.add_space(50.0);
+                ui.label("💭 No hay resultados aún");
+                ui.label("Ejecuta una consulta para ver los resultados aquí");
+                ui.add_space(50.0);
+            });
+        }
+    }
+    
+    fn show_csv_import_window(
+        &mut self,
+        ctx: &egui::Context,
+        service: &LandoService,
+        project_path: &PathBuf,
+        sender: &Sender<LandoCommandOutcome>,
+    ) {
+        if !self.csv_import.open {
+            return;
+        }
+        self.ensure_csv_mapping(service, project_path, sender);
+
+        let running = self.csv_import.job.as_ref().is_some_and(|job| !job.finished);
+        let mut open = true;
+        let mut reload_options = None;
+        let mut pick_other = false;
+        let mut start = false;
+        let mut cancel = false;
+
+        egui::Window::new("📥 Importar CSV")
+            .open(&mut open)
+            .default_width(640.0)
+            .show(ctx, |ui| {
+                let Some(preview) = &self.csv_import.preview else {
+                    return;
+                };
+
+                ui.horizontal(|ui| {
+                    ui.label(format!("📄 {} ({})", preview.path.display(), format_size(preview.file_size)));
+                    if ui.add_enabled(!running, egui::Button::new("📂 Otro archivo")).clicked() {
+                        pick_other = true;
+                    }
+                    if self.csv_import.loading {
+                        ui.spinner();
+                    }
+                });
+
+                // Formato detectado, corregible a mano
+                let mut options = preview.options;
+                ui.add_enabled_ui(!running, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label("Separador:");
+                        egui::ComboBox::from_id_salt("csv_delimiter")
+                            .selected_text(match options.delimiter { '\t' => "Tabulador".to_string(), c => format!("'{}'", c) })
+                            .show_ui(ui, |ui| {
+                                for (value, label) in [(',', "','"), (';', "';'"), ('\t', "Tabulador"), ('|', "'|'")] {
+                                    ui.selectable_value(&mut options.delimiter, value, label);
+                                }
+                            });
+                        ui.label("Comillas:");
+                        egui::ComboBox::from_id_salt("csv_quote")
+                            .selected_text(format!("{}", options.quote))
+                            .show_ui(ui, |ui| {
+                                ui.selectable_value(&mut options.quote, '"', "\"");
+                                ui.selectable_value(&mut options.quote, '\'', "'");
+                            });
+                        ui.label("Codificación:");
+                        egui::ComboBox::from_id_salt("csv_encoding")
+                            .selected_text(match options.encoding { CsvEncoding::Utf8 => "UTF-8", CsvEncoding::Latin1 => "Latin-1" })
+                            .show_ui(ui, |ui| {
+                                ui.selectable_value(&mut options.encoding, CsvEncoding::Utf8, "UTF-8");
+                                ui.selectable_value(&mut options.encoding, CsvEncoding::Latin1, "Latin-1 / Windows-1252");
+                            });
+                        ui.checkbox(&mut options.has_header, "Primera fila es cabecera");
+                    });
+                });
+                if options != preview.options {
+                    reload_options = Some(options);
+                }
+
+                ui.label(format!("Vista previa (primeras {} filas):", CSV_PREVIEW_ROWS));
+                egui::ScrollArea::both()
+                    .id_salt("csv_preview")
+                    .max_height(180.0)
+                    .show(ui, |ui| {
+                        egui::Grid::new("csv_preview_grid").striped(true).show(ui, |ui| {
+                            for header in &preview.headers {
+                                ui.strong(header);
+                            }
+                            ui.end_row();
+                            for row in &preview.rows {
+                                for value in row {
+                                    ui.label(value.replace('\n', "⏎"));
+                                }
+                                ui.end_row();
+                            }
+                        });
+                    });
+
+                ui.separator();
+                let headers = preview.headers.clone();
+                let tables: Vec<String> = self.tables.iter().map(|t| t.name.clone()).collect();
+                let columns = self.table_columns(&self.csv_import.table).to_vec();
+                let state = &mut self.csv_import;
+
+                ui.add_enabled_ui(!running, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label("📋 Tabla destino:");
+                        if tables.is_empty() {
+                            ui.label("Carga el schema para elegir la tabla");
+                        }
+                        egui::ComboBox::from_id_salt("csv_target_table")
+                            .selected_text(state.table.as_str())
+                            .show_ui(ui, |ui| {
+                                for table in &tables {
+                                    ui.selectable_value(&mut state.table, table.clone(), table);
+                                }
+                            });
+                    });
+
+                    if !state.table.is_empty() && columns.is_empty() {
+                        ui.horizontal(|ui| {
+                            ui.spinner();
+                            ui.label("Cargando columnas...");
+                        });
+                    } else if !columns.is_empty() {
+                        state.mapping.resize(headers.len(), None);
+                        egui::Grid::new("csv_mapping_grid").striped(true).show(ui, |ui| {
+                            ui.strong("Columna del CSV");
+                            ui.strong("Columna de la tabla");
+                            ui.end_row();
+                            for (header, target) in headers.iter().zip(state.mapping.iter_mut()) {
+                                ui.label(header);
+                                egui::ComboBox::from_id_salt(("csv_mapping", header))
+                                    .selected_text(target.as_deref().unwrap_or("— omitir —"))
+                                    .show_ui(ui, |ui| {
+                                        ui.selectable_value(target, None, "— omitir —");
+                                        for column in &columns {
+                                            ui.selectable_value(target, Some(column.name.clone()), format!("{} ({})", column.name, column.data_type));
+                                        }
+                                    });
+                                ui.end_row();
+                            }
+                        });
+
+                        let unmapped_required: Vec<&str> = columns.iter()
+                            .filter(|c| !c.nullable && c.default_value.is_none() && !state.mapping.contains(&Some(c.name.clone())))
+                            .map(|c| c.name.as_str())
+                            .collect();
+                        if !unmapped_required.is_empty() {
+                            ui.colored_label(
+                                warning_color(ui.visuals()),
+                                format!("⚠️ Sin asignar y sin valor por defecto: {}", unmapped_required.join(", ")),
+                            );
+                        }
+                    }
+
+                    ui.horizontal(|ui| {
+                        ui.label("Columnas sin asignar:");
+                        ui.radio_value(&mut state.missing_columns, MissingColumns::Default, "Valor por defecto");
+                        ui.radio_value(&mut state.missing_columns, MissingColumns::Null, "NULL");
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label("Filas con campos de menos:");
+                        ui.radio_value(&mut state.short_rows, ShortRows::FillEmpty, "Completar vacíos");
+                        ui.radio_value(&mut state.short_rows, ShortRows::Skip, "Saltar la fila");
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label("Filas por INSERT:");
+                        ui.add(egui::DragValue::new(&mut state.batch_size).range(1..=5000).speed(10));
+                        ui.separator();
+                        ui.label("Si un lote falla:");
+                        ui.radio_value(&mut state.on_error, BatchErrorMode::Stop, "Parar");
+                        ui.radio_value(&mut state.on_error, BatchErrorMode::SkipAndContinue, "Saltarlo y seguir");
+                    });
+                });
+
+                ui.separator();
+                let can_start = !running && !columns.is_empty() && state.mapping.iter().any(Option::is_some);
+                ui.horizontal(|ui| {
+                    if ui.add_enabled(can_start, egui::Button::new("▶️ Importar")).clicked() {
+                        start = true;
+                    }
+                    if running && ui.button("⏹ Cancelar").clicked() {
+                        cancel = true;
+                    }
+                });
+
+                if let Some(job) = &state.job {
+                    let progress = job.progress();
+                    ui.add(egui::ProgressBar::new(progress).show_percentage().animate(running));
+                    ui.label(format!(
+                        "{} filas importadas, {} saltadas, {} lotes ({} con error)",
+                        job.rows_imported, job.rows_skipped, job.batches_sent, job.batches_failed,
+                    ));
+                    if job.finished {
+                        let (color, text) = match (job.cancelled, job.errors.is_empty()) {
+                            (true, _) => (warning_color(ui.visuals()), "⏹ Importación cancelada"),
+                            (false, true) => (success_color(ui.visuals()), "✅ Importación terminada"),
+                            (false, false) => (warning_color(ui.visuals()), "⚠️ Importación terminada con errores"),
+                        };
+                        ui.colored_label(color, text);
+                    }
+                    for error in &job.errors {
+                        ui.colored_label(error_color(ui.visuals()), format!("❌ {}", error));
+                    }
+                }
+                if let Some(error) = &state.error {
+                    ui.colored_label(error_color(ui.visuals()), format!("❌ {}", error));
+                }
+            });
+
+        if let Some(options) = reload_options {
+            self.reload_csv_preview(service, sender, options);
+        }
+        if pick_other {
+            self.pick_csv_file(service, sender);
+        }
+        if start {
+            self.start_csv_import();
+        }
+        if cancel {
+            self.cancel_csv_import();
+        }
+        // Cerrar la ventana durante la importación la cancela
+        if !open {
+            self.cancel_csv_import();
+            self.csv_import.open = false;
+        }
+    }
+
+    fn show_watch_controls(&mut self, ui: &mut egui::Ui) {
+        let watching = self.query_watch.enabled;
+        let can_watch = watching || !self.query_input.trim().is_empty();
+        if ui.add_enabled(can_watch, egui::SelectableLabel::new(watching, "👁 Watch"))
+            .on_hover_text("Volver a ejecutar la consulta cada pocos segundos y resaltar los cambios")
+            .clicked()
+        {
+            if watching { self.stop_query_watch(None) } else { self.start_query_watch() }
+        }
+        ui.add(egui::DragValue::new(&mut self.query_watch.interval_secs)
+            .range(WATCH_MIN_INTERVAL..=WATCH_MAX_INTERVAL)
+            .suffix("s"))
+            .on_hover_text("Intervalo de la vigilancia");
+        if let Some(reason) = &self.query_watch.stop_reason {
+            ui.colored_label(warning_color(ui.visuals()), format!("⏹ {}", reason));
+        }
+    }
+
+    // Último resultado de la vigilancia: filas nuevas en verde, celdas
+    // cambiadas en amarillo y las filas que desaparecieron tachadas en rojo
+    // hasta la siguiente ejecución
+    fn show_watch_results(&self, ui: &mut egui::Ui) {
+        let watch = &self.query_watch;
+        ui.group(|ui| {
+            ui.horizontal(|ui| {
+                ui.strong("👁 Vigilando");
+                if watch.request_id.is_some() {
+                    ui.spinner();
+                }
+                let mut status = match watch.last_update {
+                    Some(at) => format!("última actualización hace {}s", at.elapsed().as_secs()),
+                    None => "esperando la primera ejecución".to_string(),
+                };
+                if let Some(diff) = &watch.diff {
+                    let changes = diff.change_count();
+                    status.push_str(&format!(", {} {}", changes, if changes == 1 { "cambio" } else { "cambios" }));
+                }
+                ui.label(status);
+            });
+            if let Some(error) = &watch.error {
+                ui.colored_label(error_color(ui.visuals()), format!("❌ {} ({} de 3)", error, watch.failures));
+            }
+
+            let Some(set) = &watch.current else {
+                return;
+            };
+            let diff = watch.diff.clone().unwrap_or_default();
+            let added = egui::Color32::from_rgba_unmultiplied(0, 200, 0, 50);
+            let changed = egui::Color32::from_rgba_unmultiplied(255, 200, 0, 60);
+            let removed_color = error_color(ui.visuals());
+
+            ui.separator();
+            egui::ScrollArea::both()
+                .max_height(400.0)
+                .show(ui, |ui| {
+                    egui::Grid::new("query_watch_grid")
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for column in &set.columns {
+                                ui.strong(column);
+                            }
+                            ui.end_row();
+
+                            for (row_index, row) in set.rows.iter().enumerate() {
+                                for (col_index, value) in row.iter().enumerate() {
+                                    let mut text = egui::RichText::new(value.replace('\n', "⏎"));
+                                    if diff.added.contains(&row_index) {
+                                        text = text.background_color(added);
+                                    } else if diff.changed.contains(&(row_index, col_index)) {
+                                        text = text.background_color(changed);
+                                    }
+                                    ui.label(text);
+                                }
+                                ui.end_row();
+                            }
+
+                            for row in &diff.removed {
+                                for value in row {
+                                    ui.label(egui::RichText::new(value.replace('\n', "⏎")).strikethrough().color(removed_color));
+                                }
+                                ui.end_row();
+                            }
+                        });
+                });
+        });
+    }
+
+    fn show_split_query_editor(
+        &mut self,
+        ui: &mut egui::Ui,
+        service: &LandoService,
+        project_path: &PathBuf,
+        sender: &Sender<LandoCommandOutcome>,
+        is_loading: &mut bool,
+    ) {
+        ui.columns(2, |columns| {
+            // Panel izquierdo - Editor
+            columns[0].vertical(|ui| {
+                ui.strong("✏️ Editor SQL");
+                ui.separator();
+                
+                // Controles del editor
+                ui.horizontal_wrapped(|ui| {
+                    if ui.button("📋 SELECT").clicked() {
+                        self.insert_template("SELECT * FROM ${1:table_name} LIMIT ${2:10};");
+                    }
+                    if ui.button("🔍 DESCRIBE").clicked() {
+                        self.insert_template(&self.get_describe_template(service.db_engine()));
+                    }
+                    if ui.button("📊 COUNT").clicked() {
+                        self.insert_template("SELECT COUNT(*) FROM ${1:table_name};");
+                    }
+                });
+                
+                ui.separator();
+                
+                // Editor principal
+                self.show_sql_editor(ui, |editor| {
+                    editor
+                        .hint_text("-- Tu consulta SQL")
+                        .code_editor()
+                        .desired_rows(15)
+                        .desired_width(f32::INFINITY)
+                });
+                
+                ui.horizontal(|ui| {
                     let execute_btn = ui.add_enabled(
                         !*is_loading && !self.query_input.trim().is_empty(),
                         egui::Button::new("▶️ Ejecutar")
                     );
                     
                     if execute_btn.clicked() {
-                        self.execute_query(service, project_path, sender, is_loading);
+                        self.run_editor_query(service, project_path, sender, is_loading);
                     }
+                    self.show_watch_controls(ui);
                     
-                    if ui.button("🗑️").clicked() {
+                    if icon_label(ui.button("🗑️"), "Limpiar editor").clicked() {
                         self.query_input.clear();
                     }
                 });
@@ -682,7 +2147,7 @@ impl DatabaseUI {
             columns[1].vertical(|ui| {
                 ui.strong("📊 Resultados");
                 ui.separator();
-                self.show_query_results(ui);
+                self.show_query_results(ui, service, project_path, sender, is_loading);
             });
         });
     }
@@ -710,12 +2175,26 @@ impl DatabaseUI {
         // Filtros
         ui.horizontal(|ui| {
             ui.label("🔍 Filtro:");
-            ui.text_edit_singleline(&mut self.schema_filter);
+            let filter_changed = ui.add(
+                egui::TextEdit::singleline(&mut self.schema_filter).hint_text("tabla, columna, tipo o field_*_value")
+            ).changed();
+            let case_changed = ui.checkbox(&mut self.schema_case_sensitive, "Aa")
+                .on_hover_text("Distinguir mayúsculas")
+                .changed();
+            if filter_changed || case_changed {
+                self.schema_highlight = None;
+            }
             
             ui.separator();
             ui.checkbox(&mut self.show_views, "Vistas");
             ui.checkbox(&mut self.show_procedures, "Procedimientos");
         });
+
+        let matcher = SchemaMatcher::new(&self.schema_filter, self.schema_case_sensitive);
+        let matches = matcher.as_ref().map(|m| search_schema(&self.tables, m));
+        if let Some(matches) = &matches {
+            self.show_schema_matches(ui, matches);
+        }
         
         ui.separator();
         
@@ -731,55 +2210,258 @@ impl DatabaseUI {
                         ui.add_space(50.0);
                     });
                 } else {
-                    for table in &self.tables.clone() {
-                        if !self.schema_filter.is_empty() && !table.name.to_lowercase().contains(&self.schema_filter.to_lowercase()) {
-                            continue;
+                    let mut load_columns = None;
+                    let mut stats_request = None;
+                    let mut query_template = None;
+                    let mut jumped = false;
+
+                    for table in &self.tables {
+                        // Con filtro: tablas que coinciden por nombre o por alguna columna
+                        if let Some(matches) = &matches {
+                            let hit = matches.tables.contains(&table.name)
+                                || matches.columns.iter().any(|c| c.table == table.name);
+                            if !hit {
+                                continue;
+                            }
                         }
-                        
-                        ui.collapsing(format!("📋 {}", table.name), |ui| {
+
+                        let highlighted_table = self.schema_highlight.as_ref()
+                            .is_some_and(|(name, _)| *name == table.name);
+                        let highlighted_column = self.schema_highlight.as_ref()
+                            .filter(|_| highlighted_table)
+                            .and_then(|(_, column)| column.clone());
+                        let jump = highlighted_table && self.schema_jump_pending;
+
+                        let mut title = egui::RichText::new(format!("📋 {}", table.name));
+                        if highlighted_table && highlighted_column.is_none() {
+                            title = title.background_color(ui.visuals().selection.bg_fill);
+                        }
+                        let header = egui::CollapsingHeader::new(title)
+                            .id_salt(("schema_table", &table.name))
+                            .open(jump.then_some(true))
+                            .show(ui, |ui| {
                             ui.label(format!("Tipo: {}", table.table_type));
                             if let Some(count) = table.row_count {
                                 ui.label(format!("Filas: {}", count));
                             }
                             
                             ui.separator();
-                            ui.strong("Columnas:");
+                            ui.horizontal(|ui| {
+                                ui.strong("Columnas:");
+                                if ui.small_button("🔄").on_hover_text("Cargar columnas").clicked() {
+                                    load_columns = Some(table.name.clone());
+                                }
+                            });
                             
                             for column in &table.columns {
-                                ui.horizontal(|ui| {
+                                let row = ui.horizontal(|ui| {
                                     let icon = if column.is_primary_key { "🔑" } else { "📜" };
-                                    ui.label(format!("{} {}", icon, column.name));
+                                    let mut name = egui::RichText::new(format!("{} {}", icon, column.name));
+                                    if highlighted_column.as_deref() == Some(column.name.as_str()) {
+                                        name = name.background_color(ui.visuals().selection.bg_fill).strong();
+                                    }
+                                    ui.label(name);
                                     ui.label(format!("({})", column.data_type));
                                     
                                     if !column.nullable {
-                                        ui.colored_label(egui::Color32::RED, "NOT NULL");
+                                        ui.colored_label(error_color(ui.visuals()), "NOT NULL");
                                     }
                                     
                                     if let Some(default) = &column.default_value {
                                         ui.label(format!("= {}", default));
                                     }
+
+                                    if ui.small_button("📊").on_hover_text("Estadísticas").clicked() {
+                                        stats_request = Some((table.name.clone(), column.name.clone()));
+                                    }
                                 });
+                                if jump && highlighted_column.as_deref() == Some(column.name.as_str()) {
+                                    row.response.scroll_to_me(Some(egui::Align::Center));
+                                }
                             }
                             
                             ui.separator();
                             ui.horizontal(|ui| {
                                 if ui.button("📋 SELECT").clicked() {
-                                    self.query_input = format!("SELECT * FROM {} LIMIT 10;", table.name);
-                                    self.current_tab = DatabaseTab::QueryEditor;
+                                    query_template = Some(format!("SELECT * FROM {} LIMIT 10;", table.name));
                                 }
                                 if ui.button("🔍 DESCRIBE").clicked() {
-                                    self.query_input = format!("DESCRIBE {};", table.name);
-                                    self.current_tab = DatabaseTab::QueryEditor;
+                                    query_template = Some(format!("DESCRIBE {};", table.name));
                                 }
                                 if ui.button("📊 COUNT").clicked() {
-                                    self.query_input = format!("SELECT COUNT(*) FROM {};", table.name);
-                                    self.current_tab = DatabaseTab::QueryEditor;
+                                    query_template = Some(format!("SELECT COUNT(*) FROM {};", table.name));
                                 }
                             });
                         });
+                        if jump {
+                            if highlighted_column.is_none() {
+                                header.header_response.scroll_to_me(Some(egui::Align::Min));
+                            }
+                            jumped = true;
+                        }
+                    }
+
+                    if jumped {
+                        self.schema_jump_pending = false;
+                    }
+                    if let Some(query) = query_template {
+                        self.query_input = query;
+                        self.current_tab = DatabaseTab::QueryEditor;
+                    }
+
+                    if let Some(table) = load_columns {
+                        self.load_table_columns(service, project_path, sender, &table);
+                    }
+
+                    if let Some((table, column)) = stats_request {
+                        // Resultado ya cacheado en la columna: solo reabrir el popup
+                        let cached = self.find_column_mut(&table, &column)
+                            .and_then(|c| c.stats.as_ref())
+                            .is_some_and(|stats| !stats.loading && stats.error.is_none());
+                        if cached {
+                            self.stats_popup = Some((table, column));
+                        } else {
+                            self.request_column_stats(service, project_path, sender, &table, &column);
+                        }
+                    }
+                }
+            });
+
+        self.show_column_stats_popup(ui.ctx(), service, project_path, sender);
+    }
+
+    // Resultados agrupados del filtro; al elegir uno se abre su tabla y se resalta
+    fn show_schema_matches(&mut self, ui: &mut egui::Ui, matches: &SchemaMatches) {
+        let loaded_columns = self.tables.iter().filter(|t| !t.columns.is_empty()).count();
+        let mut jump_to = None;
+
+        egui::CollapsingHeader::new(format!("Tablas ({})", matches.tables.len()))
+            .id_salt("schema_table_matches")
+            .default_open(true)
+            .show(ui, |ui| {
+                ui.horizontal_wrapped(|ui| {
+                    for table in &matches.tables {
+                        if ui.small_button(format!("📋 {}", table)).clicked() {
+                            jump_to = Some((table.clone(), None));
+                        }
+                    }
+                });
+            });
+
+        egui::CollapsingHeader::new(format!("Columnas ({})", matches.columns.len()))
+            .id_salt("schema_column_matches")
+            .default_open(true)
+            .show(ui, |ui| {
+                egui::ScrollArea::vertical()
+                    .id_salt("schema_column_matches_scroll")
+                    .max_height(160.0)
+                    .show(ui, |ui| {
+                        for hit in &matches.columns {
+                            let label = format!("{}.{} ({})", hit.table, hit.column, hit.data_type);
+                            if ui.selectable_label(false, label).clicked() {
+                                jump_to = Some((hit.table.clone(), Some(hit.column.clone())));
+                            }
+                        }
+                    });
+                if loaded_columns < self.tables.len() {
+                    ui.label(
+                        egui::RichText::new(format!(
+                            "Solo se buscan columnas de las {} de {} tablas con columnas cargadas",
+                            loaded_columns,
+                            self.tables.len()
+                        ))
+                        .small()
+                        .color(muted_color(ui.visuals())),
+                    );
+                }
+            });
+
+        if let Some(target) = jump_to {
+            self.schema_highlight = Some(target);
+            self.schema_jump_pending = true;
+        }
+    }
+
+    fn show_column_stats_popup(&mut self, ctx: &egui::Context, service: &LandoService, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>) {
+        let Some((table, column)) = self.stats_popup.clone() else {
+            return;
+        };
+        let Some(stats) = self.find_column_mut(&table, &column).and_then(|c| c.stats.clone()) else {
+            self.stats_popup = None;
+            return;
+        };
+
+        let mut open = true;
+        let mut refresh = false;
+        let mut open_in_editor = false;
+
+        egui::Window::new(format!("📊 {}.{}", table, column))
+            .open(&mut open)
+            .resizable(true)
+            .show(ctx, |ui| {
+                if stats.loading {
+                    ui.horizontal(|ui| {
+                        ui.spinner();
+                        ui.label("Calculando estadísticas...");
+                    });
+                }
+
+                if let Some(err) = &stats.error {
+                    ui.colored_label(error_color(ui.visuals()), format!("❌ {}", err));
+                }
+
+                egui::Grid::new("column_stats_grid").striped(true).show(ui, |ui| {
+                    for (name, value) in &stats.values {
+                        ui.label(name);
+                        ui.monospace(value);
+                        ui.end_row();
                     }
+                });
+
+                if !stats.top_values.is_empty() {
+                    ui.separator();
+                    ui.strong("Valores más frecuentes:");
+                    egui::Grid::new("column_top_values_grid").striped(true).show(ui, |ui| {
+                        for (value, frequency) in &stats.top_values {
+                            ui.monospace(value);
+                            ui.label(frequency);
+                            ui.end_row();
+                        }
+                    });
                 }
+
+                ui.separator();
+                ui.label("SQL generado:");
+                ui.add(
+                    egui::TextEdit::multiline(&mut stats.query.as_str())
+                        .code_editor()
+                        .desired_rows(3)
+                        .desired_width(f32::INFINITY)
+                );
+
+                ui.horizontal(|ui| {
+                    if ui.button("📋 Copiar SQL").clicked() {
+                        ui.ctx().copy_text(stats.query.clone());
+                    }
+                    if ui.button("✏️ Abrir en editor").clicked() {
+                        open_in_editor = true;
+                    }
+                    if ui.add_enabled(!stats.loading, egui::Button::new("🔄 Recalcular")).clicked() {
+                        refresh = true;
+                    }
+                });
             });
+
+        if open_in_editor {
+            self.query_input = stats.query.clone();
+            self.current_tab = DatabaseTab::QueryEditor;
+        }
+        if refresh {
+            self.request_column_stats(service, project_path, sender, &table, &column);
+        }
+        if !open {
+            self.stats_popup = None;
+        }
     }
     
     fn show_table_browser(
@@ -790,6 +2472,15 @@ impl DatabaseUI {
         sender: &Sender<LandoCommandOutcome>,
         is_loading: &mut bool,
     ) {
+        if self.needs_table_reload {
+            self.needs_table_reload = false;
+            self.load_table_data(service, project_path, sender, is_loading);
+        }
+        if self.needs_foreign_keys {
+            self.needs_foreign_keys = false;
+            self.load_foreign_keys(service, project_path, sender);
+        }
+
         ui.horizontal(|ui| {
             ui.heading("📋 Navegador de Tablas");
             
@@ -812,20 +2503,36 @@ impl DatabaseUI {
                     self.refresh_schema(service, project_path, sender, is_loading);
                 }
             } else {
+                let mut chosen = None;
                 egui::ComboBox::new("table_selector", self.current_table.as_str())
                     .show_ui(ui, |ui| {
-                        let tables_clone = self.tables.clone();
-                        for table in &tables_clone {
+                        for table in &self.tables {
                             if ui.selectable_label(false, &table.name).clicked() {
-                                self.current_table = table.name.clone();
-                                self.table_page = 0;
-                                self.table_filter.clear();
-                                self.load_table_data(service, project_path, sender, is_loading);
+                                chosen = Some(table.name.clone());
                             }
                         }
                     });
+                if let Some(table) = chosen {
+                    self.current_table = table;
+                    self.table_page = 0;
+                    self.table_filter.clear();
+                    self.table_nav.clear();
+                    self.load_table_data(service, project_path, sender, is_loading);
+                }
             }
         });
+
+        if let Some(breadcrumb) = self.table_breadcrumb() {
+            ui.horizontal(|ui| {
+                if ui.add_enabled(!*is_loading, egui::Button::new("⬅️ Atrás"))
+                    .on_hover_text("Volver a la tabla, página y filtro anteriores")
+                    .clicked()
+                {
+                    self.navigate_back(service, project_path, sender, is_loading);
+                }
+                ui.colored_label(muted_color(ui.visuals()), breadcrumb);
+            });
+        }
         
         if !self.current_table.is_empty() {
             ui.separator();
@@ -876,15 +2583,21 @@ impl DatabaseUI {
                     ui.spinner();
                     ui.label("Cargando datos de la tabla...");
                 });
+            } else if self.table_rows.is_some() {
+                self.show_table_edit_toolbar(ui, service, project_path, sender);
+                if let Some((fk, value)) = self.show_table_grid(ui) {
+                    self.follow_foreign_key(service, project_path, sender, is_loading, &fk, &value);
+                }
+                self.show_insert_row_form(ui);
+                self.show_table_edit_review(ui, service, is_loading);
             } else if !self.table_data.is_empty() {
                 egui::ScrollArea::both()
                     .max_height(400.0)
                     .show(ui, |ui| {
                         ui.add(
-                            egui::TextEdit::multiline(&mut self.table_data.clone())
+                            egui::TextEdit::multiline(&mut self.table_data.as_str())
                                 .code_editor()
                                 .desired_width(f32::INFINITY)
-                                .interactive(false)
                         );
                     });
             } else {
@@ -898,6 +2611,406 @@ impl DatabaseUI {
         }
     }
     
+    fn show_table_edit_toolbar(&mut self, ui: &mut egui::Ui, service: &LandoService, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>) {
+        let mut toggled = false;
+        ui.horizontal(|ui| {
+            toggled = ui.checkbox(&mut self.table_edit.enabled, "✏️ Modo edición")
+                .on_hover_text("Doble clic en una celda para editarla; clic fuera para confirmar, Esc para cancelar")
+                .changed();
+
+            if self.table_edit.enabled {
+                ui.separator();
+                let can_insert = self.edit_block_reason().is_none() && self.table_edit.insert_form.is_none();
+                if ui.add_enabled(can_insert, egui::Button::new("➕ Insertar fila")).clicked() {
+                    self.open_insert_form();
+                }
+
+                let changes = self.pending_change_count();
+                if ui.add_enabled(changes > 0, egui::Button::new(format!("📝 Revisar cambios ({})", changes))).clicked() {
+                    self.table_edit.show_review = !self.table_edit.show_review;
+                }
+                if ui.add_enabled(changes > 0, egui::Button::new("↩️ Descartar")).clicked() {
+                    self.reset_table_edits();
+                }
+            }
+
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                let Some(rows) = &self.table_rows else {
+                    return;
+                };
+                if let Some(format) = self.show_copy_menu(ui, true, true) {
+                    // Con filas marcadas se copian solo esas
+                    let selected = (!self.table_selection.is_empty()).then(|| rows.with_rows(&self.table_selection));
+                    let raw = selected.is_none().then_some(self.table_data.as_str());
+                    let set = selected.as_ref().unwrap_or(rows);
+                    if let Some(text) = self.format_copy(service.db_engine(), format, raw, Some(set), Some(&self.current_table)) {
+                        ui.ctx().copy_text(text);
+                    }
+                    self.set_copy_format(format);
+                }
+                if !self.table_selection.is_empty() {
+                    ui.label(format!("{} filas seleccionadas", self.table_selection.len()));
+                    if ui.small_button("✖").on_hover_text("Quitar la selección").clicked() {
+                        self.table_selection.clear();
+                    }
+                }
+            });
+        });
+
+        // Las columnas (y su clave primaria) se cargan al entrar en modo edición
+        if toggled && self.table_edit.enabled && self.current_table_columns().is_empty() {
+            let table = self.current_table.clone();
+            self.load_table_columns(service, project_path, sender, &table);
+        }
+
+        if self.table_edit.enabled {
+            if let Some(reason) = self.edit_block_reason() {
+                ui.colored_label(warning_color(ui.visuals()), format!("⚠️ {}", reason));
+            }
+        }
+
+        self.show_pending_changes_banner(ui, service);
+    }
+
+    // Aviso visible mientras haya cambios sin aplicar en la página cargada
+    fn show_pending_changes_banner(&mut self, ui: &mut egui::Ui, service: &LandoService) {
+        let changes = self.pending_change_count();
+        if changes == 0 {
+            return;
+        }
+
+        let color = warning_color(ui.visuals());
+        egui::Frame::group(ui.style())
+            .stroke(egui::Stroke::new(1.0, color))
+            .show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    let noun = if changes == 1 { "fila modificada" } else { "filas modificadas" };
+                    ui.colored_label(color, format!("📝 {} {} sin aplicar en '{}'", changes, noun, self.current_table));
+                    if ui.small_button("👁️ Revisar").clicked() {
+                        self.table_edit.show_review = true;
+                    }
+                    if ui.small_button("▶️ Aplicar...").clicked() {
+                        self.request_apply_table_edits(service);
+                    }
+                });
+            });
+    }
+
+    // Devuelve la clave foránea y el valor de la celda si se pulsa un enlace
+    fn show_table_grid(&mut self, ui: &mut egui::Ui) -> Option<(ForeignKeyInfo, String)> {
+        let can_edit = self.table_edit.enabled && self.edit_block_reason().is_none();
+        let mut start_edit = None;
+        let mut commit_edit = false;
+        let mut cancel_edit = false;
+        let mut set_value = None;
+        let mut toggle_delete = None;
+
+        let mut toggle_selection = None;
+
+        let mut open_json = None;
+        let mut follow = None;
+
+        let Some(set) = &self.table_rows else {
+            return None;
+        };
+        let foreign_keys = self.current_table_foreign_keys().to_vec();
+        let data_types: Vec<Option<String>> = set.columns.iter()
+            .map(|name| self.current_table_columns().iter().find(|c| &c.name == name).map(|c| c.data_type.clone()))
+            .collect();
+        let kinds = classify_columns(set, &data_types);
+        let thousands = self.thousands_separators;
+        let selection = &self.table_selection;
+        let edit = &mut self.table_edit;
+        let edited_color = egui::Color32::from_rgba_unmultiplied(255, 200, 0, 60);
+
+        egui::ScrollArea::both()
+            .max_height(400.0)
+            .show(ui, |ui| {
+                egui::Grid::new("table_browser_grid")
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.label("");
+                        if can_edit {
+                            ui.label("");
+                        }
+                        for column in &set.columns {
+                            ui.strong(column);
+                        }
+                        ui.end_row();
+
+                        for (row_index, row) in set.rows.iter().enumerate() {
+                            let deleted = edit.deleted_rows.contains(&row_index);
+
+                            let selected = selection.contains(&row_index);
+                            if ui.selectable_label(selected, if selected { "☑" } else { "☐" })
+                                .on_hover_text("Seleccionar para copiar")
+                                .clicked()
+                            {
+                                toggle_selection = Some(row_index);
+                            }
+
+                            if can_edit {
+                                let hint = if deleted { "Quitar la marca de borrado" } else { "Marcar para borrar (se confirma al aplicar los cambios)" };
+                                if ui.selectable_label(deleted, "🗑️").on_hover_text(hint).clicked() {
+                                    toggle_delete = Some(row_index);
+                                }
+                            }
+
+                            for (col_index, original) in row.iter().enumerate() {
+                                if let Some((r, c, buffer)) = &mut edit.editing_cell {
+                                    if *r == row_index && *c == col_index {
+                                        let response = ui.add(
+                                            egui::TextEdit::multiline(buffer)
+                                                .desired_rows(1)
+                                                .desired_width(160.0)
+                                        );
+                                        if !response.has_focus() && !response.lost_focus() {
+                                            response.request_focus();
+                                        }
+                                        if ui.input(|i| i.key_pressed(egui::Key::Escape)) {
+                                            cancel_edit = true;
+                                        } else if response.lost_focus() {
+                                            commit_edit = true;
+                                        }
+                                        continue;
+                                    }
+                                }
+
+                                let edited = edit.edited_cells.get(&(row_index, col_index));
+                                let value = match edited {
+                                    Some(v) => v.as_deref(),
+                                    None if original == "NULL" => None,
+                                    None => Some(original.as_str()),
+                                };
+                                let column_kind = kinds.get(col_index).copied().unwrap_or(CellKind::Text);
+                                let kind = value.map(|v| classify_cell(v, column_kind)).unwrap_or(CellKind::Null);
+                                let column_name = set.columns.get(col_index).map(String::as_str).unwrap_or_default();
+                                let foreign_key = value.and(foreign_keys.iter().find(|fk| fk.column == column_name));
+
+                                let mut text = cell_rich_text(value.unwrap_or("NULL"), kind, thousands, ui.visuals());
+                                if foreign_key.is_some() && !can_edit {
+                                    text = text.underline().color(ui.visuals().hyperlink_color);
+                                }
+                                if edited.is_some() {
+                                    text = text.background_color(edited_color);
+                                }
+                                if deleted {
+                                    text = text.strikethrough().color(error_color(ui.visuals()));
+                                }
+
+                                let label = egui::Label::new(text).sense(egui::Sense::click());
+                                let mut response = if kind.is_right_aligned() {
+                                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| ui.add(label)).inner
+                                } else {
+                                    ui.add(label)
+                                };
+                                if let Some(hover) = cell_hover_text(value.unwrap_or_default(), kind) {
+                                    response = response.on_hover_text(hover);
+                                }
+                                // Con la edición activa el clic es para editar y el enlace pasa al menú
+                                if let (Some(fk), Some(value)) = (foreign_key, value) {
+                                    if !can_edit {
+                                        response = response
+                                            .on_hover_cursor(egui::CursorIcon::PointingHand)
+                                            .on_hover_text(format!("🔗 Ir a {}.{} = {}", fk.ref_table, fk.ref_column, value));
+                                        if response.clicked() {
+                                            follow = Some((fk.clone(), value.to_string()));
+                                        }
+                                    }
+                                }
+                                // Con la edición activa el doble clic edita y el JSON se abre desde el menú
+                                if kind == CellKind::Json && response.clicked() && !can_edit {
+                                    open_json = value.and_then(parse_json_cell).map(|json| (column_name.to_string(), json));
+                                }
+                                let editable = can_edit && !deleted;
+                                if editable && response.double_clicked() {
+                                    start_edit = Some((row_index, col_index));
+                                }
+                                response.context_menu(|ui| {
+                                    if let Some(GridCellAction::OpenJson { column, value }) = cell_menu(ui, column_name, value.unwrap_or_default(), kind) {
+                                        open_json = Some((column, value));
+                                    }
+                                    if let (Some(fk), Some(value)) = (foreign_key, value) {
+                                        if ui.button(format!("🔗 Ir a {} #{}", fk.ref_table, value)).clicked() {
+                                            follow = Some((fk.clone(), value.to_string()));
+                                            ui.close_menu();
+                                        }
+                                    }
+                                    if editable {
+                                        ui.separator();
+                                        if ui.button("∅ Establecer NULL").clicked() {
+                                            set_value = Some((row_index, col_index, None));
+                                            ui.close_menu();
+                                        }
+                                        if edited.is_some() && ui.button("↩️ Restaurar original").clicked() {
+                                            let original = if original == "NULL" { None } else { Some(original.clone()) };
+                                            set_value = Some((row_index, col_index, original));
+                                            ui.close_menu();
+                                        }
+                                    }
+                                });
+                            }
+                            ui.end_row();
+                        }
+                    });
+            });
+
+        if cancel_edit {
+            self.table_edit.editing_cell = None;
+        } else if commit_edit {
+            self.commit_cell_edit();
+        }
+        if let Some((row, column)) = start_edit {
+            self.commit_cell_edit();
+            self.start_cell_edit(row, column);
+        }
+        if let Some((row, column, value)) = set_value {
+            self.set_cell_value(row, column, value);
+        }
+        if let Some(row) = toggle_delete {
+            self.toggle_row_deletion(row);
+        }
+        if let Some(row) = toggle_selection {
+            if !self.table_selection.remove(&row) {
+                self.table_selection.insert(row);
+            }
+        }
+        if let Some((column, value)) = open_json {
+            self.json_viewer = Some((column, value, false));
+        }
+        follow
+    }
+
+    fn show_insert_row_form(&mut self, ui: &mut egui::Ui) {
+        let Some(fields) = &mut self.table_edit.insert_form else {
+            return;
+        };
+        let mut add = false;
+        let mut cancel = false;
+
+        let errors: Vec<String> = fields.iter().filter_map(insert_field_error).collect();
+
+        ui.group(|ui| {
+            ui.strong("➕ Insertar fila");
+            egui::Grid::new("insert_row_grid").striped(true).show(ui, |ui| {
+                for field in fields.iter_mut() {
+                    ui.label(&field.column);
+                    ui.weak(&field.data_type);
+                    ui.horizontal(|ui| {
+                        ui.radio_value(&mut field.mode, InsertValueMode::Value, "Valor");
+                        ui.add_enabled_ui(field.nullable, |ui| {
+                            ui.radio_value(&mut field.mode, InsertValueMode::Null, "NULL");
+                        }).response.on_disabled_hover_text("La columna es NOT NULL");
+                        let default_hint = field.default_value.as_deref().unwrap_or("valor por defecto de la columna");
+                        ui.radio_value(&mut field.mode, InsertValueMode::Default, "DEFAULT")
+                            .on_hover_text(default_hint);
+                    });
+                    let hint = match &field.default_value {
+                        Some(default) => format!("{} (por defecto: {})", field.data_type, default),
+                        None => field.data_type.clone(),
+                    };
+                    ui.add_enabled(
+                        field.mode == InsertValueMode::Value,
+                        egui::TextEdit::singleline(&mut field.value).hint_text(hint).desired_width(200.0)
+                    );
+                    ui.end_row();
+                }
+            });
+
+            for error in &errors {
+                ui.colored_label(warning_color(ui.visuals()), format!("⚠️ {}", error));
+            }
+
+            ui.horizontal(|ui| {
+                if ui.add_enabled(errors.is_empty(), egui::Button::new("✅ Añadir a los cambios")).clicked() {
+                    add = true;
+                }
+                if ui.button("❌ Cancelar").clicked() {
+                    cancel = true;
+                }
+            });
+        });
+
+        if add {
+            if let Some(fields) = self.table_edit.insert_form.take() {
+                self.table_edit.pending_inserts.push(fields);
+            }
+        } else if cancel {
+            self.table_edit.insert_form = None;
+        }
+    }
+
+    fn show_table_edit_review(&mut self, ui: &mut egui::Ui, service: &LandoService, is_loading: &bool) {
+        if !self.table_edit.show_review || self.pending_change_count() == 0 {
+            return;
+        }
+
+        let statements = self.build_table_edit_statements(service.db_engine());
+        let mut execute = false;
+
+        ui.group(|ui| {
+            ui.strong("📝 Revisar cambios");
+            match &statements {
+                Ok(statements) => {
+                    ui.add(
+                        egui::TextEdit::multiline(&mut statements.join("\n").as_str())
+                            .code_editor()
+                            .desired_rows(4)
+                            .desired_width(f32::INFINITY)
+                    );
+                }
+                Err(reason) => {
+                    ui.colored_label(warning_color(ui.visuals()), format!("⚠️ {}", reason));
+                }
+            }
+
+            if let Some(err) = &self.table_edit.error {
+                ui.colored_label(error_color(ui.visuals()), format!("❌ {}", err));
+            }
+
+            let can_execute = statements.is_ok() && !*is_loading;
+            if ui.add_enabled(can_execute, egui::Button::new("▶️ Ejecutar cambios")).clicked() {
+                execute = true;
+            }
+        });
+
+        if execute {
+            self.request_apply_table_edits(service);
+        }
+    }
+    
+    // Menú de formatos + botón con el último usado, como el de copiar resultados
+    fn show_connection_copy_menu(&mut self, ui: &mut egui::Ui, service: &LandoService) {
+        let db_type = service.db_engine();
+        let available: Vec<(ConnectionStringFormat, String)> = ConnectionStringFormat::ALL.into_iter()
+            .filter_map(|format| self.connection_string(service, format, true).map(|preview| (format, preview)))
+            .collect();
+        let Some(first) = available.first().map(|(format, _)| *format) else {
+            return;
+        };
+        let last = if available.iter().any(|(format, _)| *format == self.connection_format) { self.connection_format } else { first };
+
+        let mut chosen = None;
+        ui.menu_button("▾", |ui| {
+            for (format, preview) in &available {
+                if ui.button(format.label(db_type)).on_hover_text(egui::RichText::new(preview).monospace()).clicked() {
+                    chosen = Some(*format);
+                    ui.close_menu();
+                }
+            }
+        }).response.on_hover_text("Elegir formato");
+        if ui.button(format!("📋 Copiar {}", last.label(db_type))).on_hover_text("Incluye la contraseña").clicked() {
+            chosen = Some(last);
+        }
+
+        if let Some(format) = chosen {
+            self.connection_format = format;
+            if let Some(text) = self.connection_string(service, format, false) {
+                copy_secret(ui.ctx(), &SecretString::new(text));
+            }
+        }
+    }
+
     fn show_connection_manager(
         &mut self,
         ui: &mut egui::Ui,
@@ -911,6 +3024,7 @@ impl DatabaseUI {
         // Información de conexión actual
         ui.group(|ui| {
             ui.strong("Conexión Actual:");
+            show_db_engine_selector(ui, service, sender);
             
             if let Some(creds) = &service.creds {
                 ui.horizontal(|ui| {
@@ -932,10 +3046,86 @@ impl DatabaseUI {
                     ui.label(format!("{}:{}", conn.host, conn.port));
                 });
             }
+
+            // La URI visible lleva la contraseña oculta; la copiada, completa
+            if let Some(masked_uri) = self.external_connection_uri(service, true) {
+                ui.horizontal(|ui| {
+                    ui.label("🔌 URI:");
+                    ui.monospace(masked_uri);
+                });
+                ui.horizontal(|ui| {
+                    self.show_connection_copy_menu(ui, service);
+                    if ui.button("🚀 Abrir en cliente externo")
+                        .on_hover_text("TablePlus, DBeaver... el que tenga registrado el sistema para la URI")
+                        .clicked()
+                    {
+                        self.open_in_external_client(service);
+                    }
+                });
+                if let Some(error) = &self.external_client_error {
+                    ui.colored_label(error_color(ui.visuals()), error);
+                }
+            } else if service.external_connection.is_none() {
+                ui.label(
+                    egui::RichText::new("El servicio no publica un puerto externo: no se puede abrir en otro cliente.")
+                        .small()
+                        .color(muted_color(ui.visuals())),
+                );
+                ui.horizontal(|ui| self.show_connection_copy_menu(ui, service));
+            }
         });
         
         ui.separator();
-        
+
+        // Perfiles guardados para este servicio
+        ui.group(|ui| {
+            ui.strong("Perfiles de Conexión:");
+
+            let selected_text = self.active_profile
+                .and_then(|i| self.connection_profiles.get(i))
+                .map(|p| format!("{} ({})", p.name, p.user))
+                .unwrap_or_else(|| "Predeterminado de lando".to_string());
+            let mut selection = self.active_profile;
+            ui.horizontal(|ui| {
+                ui.label("🗂️ Perfil activo:");
+                egui::ComboBox::from_id_salt("connection_profile")
+                    .selected_text(selected_text)
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut selection, None, "Predeterminado de lando");
+                        for (index, profile) in self.connection_profiles.iter().enumerate() {
+                            ui.selectable_value(&mut selection, Some(index), format!("{} ({})", profile.name, profile.user));
+                        }
+                    });
+
+                if ui.add_enabled(self.active_profile.is_some(), egui::Button::new("🗑️ Eliminar"))
+                    .on_hover_text("Elimina el perfil activo")
+                    .clicked()
+                {
+                    self.delete_active_profile();
+                }
+            });
+            if selection != self.active_profile {
+                self.select_connection_profile(selection);
+            }
+
+            ui.label(
+                egui::RichText::new("Las consultas usan el perfil activo; sin perfil, las credenciales del servicio (o root si no tiene).")
+                    .small()
+                    .color(muted_color(ui.visuals())),
+            );
+            ui.label(
+                egui::RichText::new("Las contraseñas de los perfiles no se guardan en disco: al reiniciar la app hay que volver a escribirlas.")
+                    .small()
+                    .color(muted_color(ui.visuals())),
+            );
+
+            if let Some(error) = &self.profile_error {
+                ui.colored_label(error_color(ui.visuals()), error);
+            }
+        });
+
+        ui.separator();
+
         // Nuevas credenciales
         ui.group(|ui| {
             ui.strong("Actualizar Credenciales:");
@@ -947,7 +3137,7 @@ impl DatabaseUI {
             
             ui.horizontal(|ui| {
                 ui.label("🔐 Contraseña:");
-                ui.add(egui::TextEdit::singleline(&mut self.new_password).password(true));
+                secret_text_edit(ui, "connections_new_password", &mut self.new_password);
             });
             
             ui.horizontal(|ui| {
@@ -960,8 +3150,22 @@ impl DatabaseUI {
                     self.test_connection(service, project_path, sender, is_loading);
                 }
                 
-                if ui.button("💾 Aplicar Cambios").clicked() && !*is_loading {
-                    self.update_credentials(service, project_path, sender, is_loading);
+                if ui.add_enabled(!*is_loading, egui::Button::new("💾 Aplicar Cambios"))
+                    .on_hover_text("Requiere reconstruir el servicio")
+                    .clicked()
+                {
+                    self.update_credentials(service);
+                }
+            });
+            if let Some(error) = &self.credentials_error {
+                ui.colored_label(error_color(ui.visuals()), format!("⚠️ {}", error));
+            }
+
+            ui.horizontal(|ui| {
+                ui.label("🏷️ Nombre del perfil:");
+                ui.text_edit_singleline(&mut self.profile_name_input);
+                if ui.button("📌 Guardar como perfil").clicked() {
+                    self.save_connection_profile();
                 }
             });
         });
@@ -975,6 +3179,68 @@ impl DatabaseUI {
         }
     }
     
+    // Editor SQL con los huecos de la plantilla insertada: Tab/Shift+Tab saltan
+    // entre ellos, lo que se escribe sustituye al seleccionado y Escape los deja
+    fn show_sql_editor(
+        &mut self,
+        ui: &mut egui::Ui,
+        configure: impl for<'t> FnOnce(egui::TextEdit<'t>) -> egui::TextEdit<'t>,
+    ) -> egui::Response {
+        let id = ui.make_persistent_id("sql_editor");
+        // El historial, el formateo o un .sql cargado cambian el texto por otra vía
+        if self.snippet.as_ref().is_some_and(|s| s.text_len != self.query_input.chars().count()) {
+            self.snippet = None;
+        }
+
+        if self.snippet.is_some() && ui.memory(|m| m.has_focus(id)) {
+            // Shift+Tab primero: `consume_key` sin modificadores también lo aceptaría
+            let (back, forward, escape) = ui.input_mut(|i| (
+                i.consume_key(egui::Modifiers::SHIFT, egui::Key::Tab),
+                i.consume_key(egui::Modifiers::NONE, egui::Key::Tab),
+                i.key_pressed(egui::Key::Escape),
+            ));
+            if let Some(session) = &mut self.snippet {
+                if back {
+                    session.previous();
+                    self.snippet_select_pending = true;
+                } else if forward {
+                    self.snippet_select_pending = session.next();
+                    if !self.snippet_select_pending {
+                        self.snippet = None;
+                    }
+                }
+            }
+            if escape {
+                self.snippet = None;
+            }
+        }
+
+        let before = self.snippet.is_some().then(|| self.query_input.clone());
+        let response = ui.add(configure(egui::TextEdit::multiline(&mut self.query_input).id(id)));
+        if let (Some(before), Some(session)) = (before, &mut self.snippet) {
+            if response.changed() {
+                if let Some(text) = session.apply_edit(&before, &self.query_input) {
+                    self.query_input = text;
+                }
+            }
+        }
+
+        if self.snippet_select_pending {
+            self.snippet_select_pending = false;
+            if let Some(range) = self.snippet.as_ref().and_then(SnippetSession::current_range) {
+                let mut state = egui::TextEdit::load_state(ui.ctx(), id).unwrap_or_default();
+                state.cursor.set_char_range(Some(egui::text::CCursorRange::two(
+                    egui::text::CCursor::new(range.start),
+                    egui::text::CCursor::new(range.end),
+                )));
+                state.store(ui.ctx(), id);
+                ui.memory_mut(|m| m.request_focus(id));
+            }
+        }
+
+        response
+    }
+
     fn show_query_history_panel(
         &mut self,
         ui: &mut egui::Ui,
@@ -991,17 +3257,52 @@ impl DatabaseUI {
                 
                 if ui.button("🗑️ Limpiar").clicked() {
                     self.query_history.clear();
+                    self.query_history_times.clear();
                     self.query_results.clear();
+                    self.history_selection.clear();
+                }
+
+                let selected = self.history_selection.len();
+                ui.add_enabled_ui(!self.query_history.is_empty(), |ui| {
+                    ui.menu_button("📤 Exportar", |ui| {
+                        for format in HistoryExportFormat::ALL {
+                            if ui.button(format!("Todo · {}", format.label())).clicked() {
+                                self.export_history(format, false);
+                                ui.close_menu();
+                            }
+                        }
+                        ui.separator();
+                        for format in HistoryExportFormat::ALL {
+                            let button = ui.add_enabled(
+                                selected > 0,
+                                egui::Button::new(format!("Seleccionadas ({}) · {}", selected, format.label())),
+                            );
+                            if button.on_disabled_hover_text("Marca consultas en la lista").clicked() {
+                                self.export_history(format, true);
+                                ui.close_menu();
+                            }
+                        }
+                    });
+                });
+
+                if selected > 0 && ui.small_button("✖").on_hover_text("Quitar la selección").clicked() {
+                    self.history_selection.clear();
                 }
             });
         });
+
+        match &self.history_export_status {
+            Some(Ok(message)) => { ui.colored_label(success_color(ui.visuals()), format!("✅ {}", message)); }
+            Some(Err(message)) => { ui.colored_label(error_color(ui.visuals()), format!("❌ {}", message)); }
+            None => {}
+        }
         
         ui.separator();
         
         // Filtro de búsqueda
         ui.horizontal(|ui| {
             ui.label("🔍 Buscar:");
-            ui.text_edit_singleline(&mut self.schema_filter); // Reutilizamos este campo para búsqueda
+            ui.text_edit_singleline(&mut self.history_filter);
         });
         
         ui.separator();
@@ -1014,24 +3315,29 @@ impl DatabaseUI {
                 ui.add_space(50.0);
             });
         } else {
-            let queries = self.query_history.clone(); // Clone para evitar borrowing issues
             let mut execute_query_request = None;
             let mut copy_text = None;
             let mut edit_query_request = None;
+            let mut save_query_request = None;
             
-            // Filtrar queries si hay texto de búsqueda
-            let filtered_queries: Vec<_> = if !self.schema_filter.is_empty() {
-                queries.iter()
-                    .filter(|query| query.to_lowercase().contains(&self.schema_filter.to_lowercase()))
-                    .collect()
-            } else {
-                queries.iter().collect()
-            };
+            // Filtrar queries si hay texto de búsqueda; se toman prestadas, sin copiar el historial
+            let filter = self.history_filter.to_lowercase();
+            let filtered_queries: Vec<&String> = self.query_history.iter()
+                .filter(|query| filter.is_empty() || query.to_lowercase().contains(&filter))
+                .collect();
             
             egui::ScrollArea::vertical().show(ui, |ui| {
                 for (i, query) in filtered_queries.iter().enumerate().rev() {
                     ui.group(|ui| {
                         ui.horizontal(|ui| {
+                            let mut selected = self.history_selection.contains(*query);
+                            if ui.checkbox(&mut selected, "").on_hover_text("Marcar para exportar").changed() {
+                                if selected {
+                                    self.history_selection.insert(query.to_string());
+                                } else {
+                                    self.history_selection.remove(*query);
+                                }
+                            }
                             ui.label(format!("{}", i + 1));
                             
                             let query_preview = if query.len() > 100 {
@@ -1056,8 +3362,7 @@ impl DatabaseUI {
                                 }
                                 
                                 if ui.small_button("💾").on_hover_text("Guardar").clicked() {
-                                    self.query_input = query.to_string();
-                                    self.show_save_query_dialog = true;
+                                    save_query_request = Some(query.to_string());
                                 }
                             });
                         });
@@ -1070,20 +3375,539 @@ impl DatabaseUI {
             if let Some(query) = execute_query_request {
                 self.query_input = query.to_string();
                 self.current_tab = DatabaseTab::QueryEditor;
-                self.execute_query(service, project_path, sender, is_loading);
+                self.run_query_cached(service, project_path, sender, is_loading);
             }
             
             if let Some(text) = copy_text {
                 ui.ctx().copy_text(text.to_string());
             }
-            
-            if let Some(query) = edit_query_request {
-                self.query_input = query.to_string();
-                self.current_tab = DatabaseTab::QueryEditor;
+            
+            if let Some(query) = edit_query_request {
+                self.query_input = query.to_string();
+                self.current_tab = DatabaseTab::QueryEditor;
+            }
+
+            if let Some(query) = save_query_request {
+                self.query_input = query;
+                self.show_save_query_dialog = true;
+            }
+        }
+    }
+    
+    fn show_user_manager(
+        &mut self,
+        ui: &mut egui::Ui,
+        service: &LandoService,
+        project_path: &PathBuf,
+        sender: &Sender<LandoCommandOutcome>,
+    ) {
+        let Some(dialect) = privilege_dialect(service.db_engine()) else {
+            return;
+        };
+        ui.heading("👥 Usuarios y privilegios");
+
+        if !self.user_manager.loaded {
+            self.load_users(service, project_path, sender);
+        }
+
+        ui.horizontal(|ui| {
+            if ui.button("🔄 Recargar").clicked() {
+                self.load_users(service, project_path, sender);
+            }
+            if self.user_manager.loading {
+                ui.spinner();
+            }
+            ui.label(format!("{} usuarios", self.user_manager.users.len()));
+        });
+        if let Some(error) = &self.user_manager.error {
+            ui.colored_label(error_color(ui.visuals()), format!("❌ {}", error));
+        }
+
+        ui.separator();
+
+        let mut load_grants = None;
+        ui.columns(2, |columns| {
+            egui::ScrollArea::vertical()
+                .id_salt("db_users_list")
+                .max_height(260.0)
+                .show(&mut columns[0], |ui| {
+                    for (index, user) in self.user_manager.users.iter().enumerate() {
+                        let label = match &user.host {
+                            Some(host) => format!("👤 {}@{}", user.name, host),
+                            None => format!("👤 {}", user.name),
+                        };
+                        let selected = self.user_manager.selected == Some(index);
+                        let response = ui.selectable_label(selected, label);
+                        if !user.attributes.is_empty() {
+                            response.clone().on_hover_text(user.attributes.join(", "));
+                        }
+                        if response.clicked() {
+                            self.user_manager.selected = Some(index);
+                            if !user.grants_loaded {
+                                load_grants = Some(index);
+                            }
+                        }
+                    }
+                });
+
+            let ui = &mut columns[1];
+            match self.user_manager.selected_user() {
+                None => {
+                    ui.label(egui::RichText::new("Selecciona un usuario para ver sus permisos").color(muted_color(ui.visuals())));
+                }
+                Some(user) => {
+                    ui.strong(format!("🔑 Permisos de {}", user.name));
+                    if !user.attributes.is_empty() {
+                        ui.label(format!("🏷️ {}", user.attributes.join(", ")));
+                    }
+                    if !user.grants_loaded {
+                        ui.spinner();
+                    } else if user.grants.is_empty() {
+                        ui.label(egui::RichText::new("Sin permisos explícitos").color(muted_color(ui.visuals())));
+                    }
+                    egui::ScrollArea::vertical()
+                        .id_salt("db_user_grants")
+                        .max_height(240.0)
+                        .show(ui, |ui| {
+                            for grant in &user.grants {
+                                ui.horizontal_wrapped(|ui| {
+                                    ui.monospace(&grant.target);
+                                    ui.label(grant.privileges.join(", "));
+                                    if grant.grant_option {
+                                        ui.colored_label(warning_color(ui.visuals()), "WITH GRANT OPTION");
+                                    }
+                                });
+                            }
+                        });
+                }
+            }
+        });
+        if let Some(index) = load_grants {
+            self.load_user_grants(service, project_path, sender, index);
+        }
+
+        ui.separator();
+        self.show_user_form(ui, service, dialect);
+    }
+
+    fn show_user_form(&mut self, ui: &mut egui::Ui, service: &LandoService, dialect: &dyn PrivilegeDialect) {
+        ui.group(|ui| {
+            ui.horizontal(|ui| {
+                let form = &mut self.user_manager;
+                ui.selectable_value(&mut form.action, UserAction::Create, "➕ Crear");
+                ui.selectable_value(&mut form.action, UserAction::ChangePassword, "🔑 Contraseña");
+                ui.selectable_value(&mut form.action, UserAction::Grant, "✅ Conceder");
+                ui.selectable_value(&mut form.action, UserAction::Revoke, "⛔ Revocar");
+                ui.selectable_value(&mut form.action, UserAction::Drop, "🗑️ Eliminar");
+            });
+            ui.separator();
+
+            let action = self.user_manager.action;
+            if action != UserAction::Create {
+                let target = self.user_manager.selected_user()
+                    .map(|u| match &u.host {
+                        Some(host) => format!("{}@{}", u.name, host),
+                        None => u.name.clone(),
+                    })
+                    .unwrap_or_else(|| "ninguno (elígelo en la lista)".to_string());
+                ui.label(format!("👤 Usuario: {}", target));
+            }
+
+            match action {
+                UserAction::Create => {
+                    ui.horizontal(|ui| {
+                        ui.label("Nombre:");
+                        ui.add(egui::TextEdit::singleline(&mut self.user_manager.name).desired_width(160.0));
+                        if dialect.uses_host() {
+                            ui.label("Host:");
+                            ui.add(egui::TextEdit::singleline(&mut self.user_manager.host).desired_width(100.0))
+                                .on_hover_text("% permite conectar desde cualquier host");
+                        }
+                    });
+                    self.show_password_field(ui);
+                }
+                UserAction::ChangePassword => self.show_password_field(ui),
+                UserAction::Grant | UserAction::Revoke => self.show_privilege_picker(ui, service, dialect),
+                UserAction::Drop => {
+                    ui.colored_label(warning_color(ui.visuals()), "⚠️ Se eliminará el usuario con todos sus permisos");
+                }
+            }
+
+            ui.separator();
+            ui.label("SQL generado:");
+            match self.build_user_sql(service.db_engine()) {
+                Ok(sql) => {
+                    // La vista previa oculta la contraseña; el diálogo de confirmación muestra el SQL real
+                    let preview = if self.user_manager.password.is_empty() || self.user_manager.show_password {
+                        sql
+                    } else {
+                        sql.replace(self.user_manager.password.expose(), "••••••")
+                    };
+                    ui.add(
+                        egui::TextEdit::multiline(&mut preview.as_str())
+                            .code_editor()
+                            .desired_rows(2)
+                            .desired_width(f32::INFINITY)
+                    );
+                    if ui.button("▶️ Revisar y ejecutar").clicked() {
+                        self.request_user_action(service);
+                    }
+                }
+                Err(reason) => {
+                    ui.label(egui::RichText::new(reason).color(muted_color(ui.visuals())));
+                }
+            }
+        });
+    }
+
+    fn show_password_field(&mut self, ui: &mut egui::Ui) {
+        let form = &mut self.user_manager;
+        ui.horizontal(|ui| {
+            ui.label("Contraseña:");
+            ui.add(
+                egui::TextEdit::singleline(form.password.expose_mut())
+                    .password(!form.show_password)
+                    .desired_width(180.0)
+            );
+            ui.checkbox(&mut form.show_password, "👁️");
+            if ui.button("🎲 Generar").on_hover_text("Contraseña aleatoria de 20 caracteres").clicked() {
+                form.password = SecretString::new(generate_password(20));
+                form.show_password = true;
+            }
+            if !form.password.is_empty() && ui.small_button("📋").on_hover_text("Copiar contraseña").clicked() {
+                copy_secret(ui.ctx(), &form.password);
+            }
+        });
+    }
+
+    fn show_privilege_picker(&mut self, ui: &mut egui::Ui, service: &LandoService, dialect: &dyn PrivilegeDialect) {
+        if self.user_manager.database.is_empty() {
+            self.user_manager.database = self.db_connection(service).database.unwrap_or_default();
+        }
+        let tables: Vec<String> = self.tables.iter().map(|t| t.name.clone()).collect();
+        let form = &mut self.user_manager;
+
+        ui.horizontal_wrapped(|ui| {
+            for privilege in dialect.privileges() {
+                let mut checked = form.privileges.iter().any(|p| p == privilege);
+                if ui.checkbox(&mut checked, *privilege).changed() {
+                    if checked {
+                        form.privileges.push(privilege.to_string());
+                    } else {
+                        form.privileges.retain(|p| p != privilege);
+                    }
+                }
+            }
+        });
+
+        ui.horizontal(|ui| {
+            ui.label("💾 Base de datos:");
+            ui.add(
+                egui::TextEdit::singleline(&mut form.database)
+                    .hint_text(if dialect.uses_host() { "* (todas)" } else { "" })
+                    .desired_width(140.0)
+            );
+            ui.label("📋 Tabla:");
+            egui::ComboBox::from_id_salt("user_grant_table")
+                .selected_text(if form.table.is_empty() { "Todas" } else { form.table.as_str() })
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut form.table, String::new(), "Todas");
+                    for table in &tables {
+                        ui.selectable_value(&mut form.table, table.clone(), table);
+                    }
+                });
+        });
+        if tables.is_empty() {
+            ui.label(
+                egui::RichText::new("Carga el schema para elegir una tabla concreta")
+                    .small()
+                    .color(muted_color(ui.visuals())),
+            );
+        }
+    }
+
+    fn show_diagram(
+        &mut self,
+        ui: &mut egui::Ui,
+        service: &LandoService,
+        project_path: &PathBuf,
+        sender: &Sender<LandoCommandOutcome>,
+        is_loading: &mut bool,
+    ) {
+        ui.heading("🕸️ Diagrama de relaciones");
+
+        if self.tables.is_empty() {
+            ui.label("No hay tablas cargadas todavía.");
+            if ui.add_enabled(!*is_loading, egui::Button::new("📋 Cargar tablas")).clicked() {
+                self.refresh_schema(service, project_path, sender, is_loading);
+            }
+            return;
+        }
+
+        if !self.diagram.loaded {
+            self.load_diagram(service, project_path, sender);
+        }
+        self.ensure_diagram_layout(service, project_path);
+
+        ui.horizontal(|ui| {
+            if ui.button("🔄 Recargar").clicked() {
+                self.load_diagram(service, project_path, sender);
+            }
+            if ui.button("📐 Reordenar").on_hover_text("Volver a colocar las tablas en rejilla").clicked() {
+                self.reset_diagram_layout();
+            }
+            if self.diagram.loading {
+                ui.spinner();
+            }
+            ui.label(format!("{} tablas, {} relaciones", self.tables.len(), self.diagram.foreign_keys.len()));
+        });
+        if let Some(err) = &self.diagram.error {
+            ui.colored_label(error_color(ui.visuals()), format!("❌ {}", err));
+        }
+
+        ui.separator();
+
+        // Lienzo con el tamaño justo para abarcar todas las cajas
+        let sizes: HashMap<String, egui::Vec2> = self.tables.iter()
+            .map(|t| (t.name.clone(), self.diagram_node_size(&t.name)))
+            .collect();
+        let extent = self.diagram.positions.iter()
+            .filter_map(|(table, pos)| sizes.get(table).map(|size| *pos + *size))
+            .fold(egui::Pos2::ZERO, |acc, p| acc.max(p));
+
+        let mut drag_finished = false;
+        egui::ScrollArea::both()
+            .max_height(500.0)
+            .show(ui, |ui| {
+                let canvas_size = (extent.to_vec2() + egui::vec2(20.0, 20.0)).max(ui.available_size());
+                let (canvas, painter) = ui.allocate_painter(canvas_size, egui::Sense::hover());
+                let origin = canvas.rect.min.to_vec2();
+
+                // Primero se procesan los arrastres para pintar ya con la posición nueva
+                for table in &self.tables {
+                    let (Some(pos), Some(size)) = (self.diagram.positions.get_mut(&table.name), sizes.get(&table.name)) else {
+                        continue;
+                    };
+                    let rect = egui::Rect::from_min_size(*pos + origin, *size);
+                    let response = ui.interact(rect, ui.id().with(("diagram_node", &table.name)), egui::Sense::drag());
+                    if response.dragged() {
+                        *pos = (*pos + response.drag_delta()).max(egui::Pos2::ZERO);
+                    }
+                    if response.drag_stopped() {
+                        drag_finished = true;
+                    }
+                    if response.hovered() || response.dragged() {
+                        ui.ctx().set_cursor_icon(egui::CursorIcon::Grab);
+                    }
+                }
+
+                let visuals = ui.visuals();
+                let text_color = visuals.text_color();
+                let line_stroke = egui::Stroke::new(1.5, info_color(visuals));
+                let font = egui::FontId::proportional(12.0);
+
+                // Conectores: del lado de la caja más cercano a la otra tabla,
+                // a la altura de la columna si ya se conocen las columnas
+                let anchor = |table: &str, column: &str, towards_right: bool| -> Option<egui::Pos2> {
+                    let pos = *self.diagram.positions.get(table)? + origin;
+                    let size = *sizes.get(table)?;
+                    let row = self.tables.iter()
+                        .find(|t| t.name == table)
+                        .and_then(|t| t.columns.iter().position(|c| c.name == column))
+                        .filter(|row| *row < DIAGRAM_MAX_COLUMNS);
+                    let y = match row {
+                        Some(row) => pos.y + DIAGRAM_HEADER_HEIGHT + DIAGRAM_ROW_HEIGHT * (row as f32 + 0.5),
+                        None => pos.y + size.y / 2.0,
+                    };
+                    let x = if towards_right { pos.x + size.x } else { pos.x };
+                    Some(egui::pos2(x, y))
+                };
+                for fk in &self.diagram.foreign_keys {
+                    let (Some(from), Some(to)) = (self.diagram.positions.get(&fk.table), self.diagram.positions.get(&fk.ref_table)) else {
+                        continue;
+                    };
+                    let left_to_right = from.x <= to.x;
+                    let (Some(start), Some(end)) = (
+                        anchor(&fk.table, &fk.column, left_to_right),
+                        anchor(&fk.ref_table, &fk.ref_column, !left_to_right),
+                    ) else {
+                        continue;
+                    };
+                    painter.line_segment([start, end], line_stroke);
+                    painter.circle_filled(end, 3.5, line_stroke.color);
+                }
+
+                // Cajas de las tablas
+                for table in &self.tables {
+                    let (Some(pos), Some(size)) = (self.diagram.positions.get(&table.name), sizes.get(&table.name)) else {
+                        continue;
+                    };
+                    let rect = egui::Rect::from_min_size(*pos + origin, *size);
+                    let header = egui::Rect::from_min_size(rect.min, egui::vec2(rect.width(), DIAGRAM_HEADER_HEIGHT));
+
+                    painter.rect_filled(rect, 4.0, visuals.window_fill);
+                    painter.rect_filled(header, 4.0, visuals.selection.bg_fill);
+                    painter.rect_stroke(rect, 4.0, visuals.widgets.noninteractive.bg_stroke, egui::StrokeKind::Inside);
+                    painter.text(
+                        header.left_center() + egui::vec2(6.0, 0.0),
+                        egui::Align2::LEFT_CENTER,
+                        &table.name,
+                        egui::FontId::proportional(13.0),
+                        visuals.selection.stroke.color,
+                    );
+
+                    if table.columns.is_empty() {
+                        painter.text(
+                            rect.left_top() + egui::vec2(6.0, DIAGRAM_HEADER_HEIGHT + 2.0),
+                            egui::Align2::LEFT_TOP,
+                            "Cargando columnas...",
+                            font.clone(),
+                            muted_color(visuals),
+                        );
+                        continue;
+                    }
+
+                    for (row, column) in table.columns.iter().take(DIAGRAM_MAX_COLUMNS).enumerate() {
+                        let is_fk = self.diagram.foreign_keys.iter().any(|fk| fk.table == table.name && fk.column == column.name);
+                        let icon = if column.is_primary_key { "🔑 " } else if is_fk { "🔗 " } else { "" };
+                        painter.text(
+                            rect.left_top() + egui::vec2(6.0, DIAGRAM_HEADER_HEIGHT + DIAGRAM_ROW_HEIGHT * row as f32 + 2.0),
+                            egui::Align2::LEFT_TOP,
+                            format!("{}{}  {}", icon, column.name, column.data_type),
+                            font.clone(),
+                            text_color,
+                        );
+                    }
+                    if table.columns.len() > DIAGRAM_MAX_COLUMNS {
+                        painter.text(
+                            rect.left_top() + egui::vec2(6.0, DIAGRAM_HEADER_HEIGHT + DIAGRAM_ROW_HEIGHT * DIAGRAM_MAX_COLUMNS as f32 + 2.0),
+                            egui::Align2::LEFT_TOP,
+                            format!("… {} más", table.columns.len() - DIAGRAM_MAX_COLUMNS),
+                            font.clone(),
+                            muted_color(visuals),
+                        );
+                    }
+                }
+            });
+
+        if drag_finished {
+            self.save_diagram_layout();
+        }
+    }
+
+    // Búsqueda conjunta en el historial y las consultas guardadas
+    fn show_query_search(
+        &mut self,
+        ui: &mut egui::Ui,
+        service: &LandoService,
+        project_path: &PathBuf,
+        sender: &Sender<LandoCommandOutcome>,
+        is_loading: &mut bool,
+    ) {
+        let mut run_query = None;
+        let mut edit_query = None;
+
+        ui.group(|ui| {
+            ui.strong("🔎 Buscar consultas:");
+            ui.horizontal_wrapped(|ui| {
+                ui.add(
+                    egui::TextEdit::singleline(&mut self.query_search.text)
+                        .hint_text("Nombre, SQL o resultado")
+                        .desired_width(220.0),
+                );
+                ui.label("📅 Desde:");
+                ui.add(egui::TextEdit::singleline(&mut self.query_search.from).hint_text("AAAA-MM-DD").desired_width(90.0));
+                ui.label("Hasta:");
+                ui.add(egui::TextEdit::singleline(&mut self.query_search.to).hint_text("AAAA-MM-DD").desired_width(90.0));
+                ui.checkbox(&mut self.query_search.include_results, "En resultados")
+                    .on_hover_text("Busca también en el último resultado de cada consulta del historial que siga en memoria");
+                if ui.small_button("✖").on_hover_text("Limpiar la búsqueda").clicked() {
+                    self.query_search = QuerySearchState::default();
+                }
+            });
+
+            let hits = match self.search_queries() {
+                Ok(hits) => hits,
+                Err(err) => {
+                    ui.colored_label(error_color(ui.visuals()), err);
+                    return;
+                }
+            };
+            let searching = !self.query_search.text.trim().is_empty()
+                || !self.query_search.from.trim().is_empty()
+                || !self.query_search.to.trim().is_empty();
+            if !searching {
+                return;
+            }
+            if hits.is_empty() {
+                ui.colored_label(muted_color(ui.visuals()), "Sin coincidencias");
+                return;
             }
+
+            let needle = self.query_search.text.trim();
+            ui.colored_label(muted_color(ui.visuals()), format!("{} coincidencias", hits.len()));
+            egui::ScrollArea::vertical()
+                .id_salt("query_search_results")
+                .max_height(260.0)
+                .show(ui, |ui| {
+                    for (index, hit) in hits.iter().enumerate() {
+                        ui.push_id(index, |ui| {
+                            ui.horizontal(|ui| {
+                                match &hit.source {
+                                    QuerySearchSource::History => {
+                                        ui.label("📜").on_hover_text("Historial");
+                                    }
+                                    QuerySearchSource::Saved(name) => {
+                                        ui.label("💾").on_hover_text("Consulta guardada");
+                                        ui.label(highlight_matches(ui.style(), name, needle, false));
+                                    }
+                                }
+                                if let Some(timestamp) = hit.timestamp {
+                                    ui.colored_label(muted_color(ui.visuals()), format_last_opened(timestamp));
+                                }
+
+                                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                                    if ui.small_button("▶️").on_hover_text("Ejecutar").clicked() {
+                                        run_query = Some(hit.query.clone());
+                                    }
+                                    if ui.small_button("✏️").on_hover_text("Editar").clicked() {
+                                        edit_query = Some(hit.query.clone());
+                                    }
+                                    if ui.small_button("📋").on_hover_text("Copiar").clicked() {
+                                        ui.ctx().copy_text(hit.query.clone());
+                                    }
+                                });
+                            });
+
+                            let preview: String = hit.query.split_whitespace().collect::<Vec<_>>().join(" ");
+                            let preview = if preview.chars().count() > 200 {
+                                format!("{}…", preview.chars().take(200).collect::<String>())
+                            } else {
+                                preview
+                            };
+                            ui.label(highlight_matches(ui.style(), &preview, needle, true));
+                            if let Some(excerpt) = &hit.result_excerpt {
+                                ui.horizontal(|ui| {
+                                    ui.colored_label(muted_color(ui.visuals()), "↳ resultado:");
+                                    ui.label(highlight_matches(ui.style(), excerpt, needle, true));
+                                });
+                            }
+                            ui.separator();
+                        });
+                    }
+                });
+        });
+
+        if let Some(query) = run_query {
+            self.query_input = query;
+            self.current_tab = DatabaseTab::QueryEditor;
+            self.run_query_cached(service, project_path, sender, is_loading);
+        } else if let Some(query) = edit_query {
+            self.query_input = query;
+            self.current_tab = DatabaseTab::QueryEditor;
         }
     }
-    
+
     fn show_database_tools(
         &mut self,
         ui: &mut egui::Ui,
@@ -1093,30 +3917,95 @@ impl DatabaseUI {
         is_loading: &mut bool,
     ) {
         ui.heading("🔧 Herramientas de Base de Datos");
+
+        self.show_query_search(ui, service, project_path, sender, is_loading);
+
+        ui.separator();
         
         // Herramientas de administración
         ui.group(|ui| {
             ui.strong("🛠️ Administración:");
             
             ui.horizontal_wrapped(|ui| {
-                if ui.button("📊 Optimizar").clicked() && !*is_loading {
-                    self.optimize_database(service, project_path, sender, is_loading);
-                }
-                
                 if ui.button("📝 Backup").clicked() && !*is_loading {
-                    self.backup_database(service, project_path, sender, is_loading);
-                }
-                
-                if ui.button("🔄 Repair").clicked() && !*is_loading {
-                    self.repair_database(service, project_path, sender, is_loading);
+                    self.backup_database(service);
                 }
-                
-                if ui.button("📊 Analyze").clicked() && !*is_loading {
-                    self.analyze_database(service, project_path, sender, is_loading);
+
+                if matches!(service.db_engine().to_lowercase().as_str(), "mysql" | "mariadb") {
+                    if ui.button("🧹 Flush").on_hover_text("FLUSH TABLES").clicked() && !*is_loading {
+                        self.request_flush(service);
+                    }
                 }
             });
         });
         
+        ui.separator();
+
+        self.show_backup_manager(ui, service, project_path, sender, is_loading);
+
+        ui.separator();
+
+        if self.supports_process_list(service) {
+            self.show_process_list(ui, service, project_path, sender);
+            ui.separator();
+        }
+
+        // Mantenimiento por tabla: primero se eligen las tablas, luego la operación
+        ui.group(|ui| {
+            ui.strong("🧰 Mantenimiento de Tablas:");
+
+            if self.tables.is_empty() {
+                ui.horizontal(|ui| {
+                    ui.label("No hay tablas cargadas");
+                    if ui.button("🔄 Cargar Tablas").clicked() && !*is_loading {
+                        self.refresh_schema(service, project_path, sender, is_loading);
+                    }
+                });
+            } else {
+                ui.horizontal(|ui| {
+                    if ui.small_button("Todas").clicked() {
+                        self.maintenance_tables = self.tables.iter().map(|t| t.name.clone()).collect();
+                    }
+                    if ui.small_button("Ninguna").clicked() {
+                        self.maintenance_tables.clear();
+                    }
+                    ui.label(format!("{} seleccionadas", self.maintenance_tables.len()));
+                });
+
+                let mut toggled = None;
+                egui::ScrollArea::vertical()
+                    .id_salt("maintenance_tables")
+                    .max_height(150.0)
+                    .show(ui, |ui| {
+                        for table in &self.tables {
+                            let mut selected = self.maintenance_tables.contains(&table.name);
+                            if ui.checkbox(&mut selected, &table.name).changed() {
+                                toggled = Some(table.name.clone());
+                            }
+                        }
+                    });
+                if let Some(table) = toggled {
+                    self.toggle_maintenance_table(&table);
+                }
+
+                ui.horizontal_wrapped(|ui| {
+                    let can_run = !*is_loading && !self.maintenance_tables.is_empty();
+
+                    if ui.add_enabled(can_run, egui::Button::new("📊 Optimizar")).clicked() {
+                        self.request_maintenance(service, MaintenanceOp::Optimize);
+                    }
+
+                    if ui.add_enabled(can_run, egui::Button::new("🔄 Repair")).clicked() {
+                        self.request_maintenance(service, MaintenanceOp::Repair);
+                    }
+
+                    if ui.add_enabled(can_run, egui::Button::new("📊 Analyze")).clicked() {
+                        self.request_maintenance(service, MaintenanceOp::Analyze);
+                    }
+                });
+            }
+        });
+        
         ui.separator();
         
         // Herramientas de desarrollo
@@ -1132,15 +4021,23 @@ impl DatabaseUI {
                     self.export_data();
                 }
                 
-                if ui.button("📥 Import Data").clicked() {
-                    self.import_data();
+                if ui.button("📥 Import Data").clicked() && !*is_loading {
+                    self.import_data(service);
+                }
+
+                if ui.add_enabled(!self.csv_import.loading, egui::Button::new("📄 Importar CSV")).clicked() {
+                    self.pick_csv_file(service, sender);
                 }
             });
+            if let Some(error) = self.csv_import.error.as_ref().filter(|_| !self.csv_import.open) {
+                ui.colored_label(error_color(ui.visuals()), format!("❌ {}", error));
+            }
         });
         
         ui.separator();
         
         // Gestión de queries guardadas
+        let mut run_saved_query = None;
         ui.group(|ui| {
             ui.strong("💾 Queries Guardadas:");
             
@@ -1151,15 +4048,16 @@ impl DatabaseUI {
                     .max_height(200.0)
                     .show(ui, |ui| {
                         let mut queries_to_remove = Vec::new();
+                        let mut saved: Vec<(&String, &String)> = self.saved_queries.iter().collect();
+                        saved.sort_by_key(|(name, _)| name.to_lowercase());
                         
-                        for (name, query) in &self.saved_queries {
+                        for (name, query) in saved {
                             ui.horizontal(|ui| {
                                 ui.label(format!("📝 {}", name));
                                 
                                 ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                                     if ui.small_button("▶️").on_hover_text("Ejecutar").clicked() {
-                                        self.query_input = query.clone();
-                                        self.current_tab = DatabaseTab::QueryEditor;
+                                        run_saved_query = Some(query.clone());
                                     }
                                     
                                     if ui.small_button("✏️").on_hover_text("Editar").clicked() {
@@ -1175,12 +4073,26 @@ impl DatabaseUI {
                         }
                         
                         // Eliminar queries marcadas para eliminación
-                        for name in queries_to_remove {
-                            self.saved_queries.remove(&name);
+                        if !queries_to_remove.is_empty() {
+                            for name in queries_to_remove {
+                                self.saved_queries.remove(&name);
+                                self.saved_query_times.remove(&name);
+                            }
+                            self.persist_saved_queries();
                         }
                     });
             }
         });
+
+        ui.separator();
+
+        self.show_query_schedules(ui);
+
+        if let Some(query) = run_saved_query {
+            self.query_input = query;
+            self.current_tab = DatabaseTab::QueryEditor;
+            self.run_query_cached(service, project_path, sender, is_loading);
+        }
         
         ui.separator();
         
@@ -1198,14 +4110,416 @@ impl DatabaseUI {
                 ui.add(egui::DragValue::new(&mut self.query_timeout).range(5..=300));
             });
             
+            ui.checkbox(&mut self.auto_limit_selects, "Añadir LIMIT automáticamente a los SELECT sin límite");
+            ui.checkbox(&mut self.thousands_separators, "Separador de miles en las columnas numéricas");
+            self.show_lint_settings(ui);
             ui.checkbox(&mut self.enable_query_cache, "Habilitar caché de consultas");
+
+            ui.horizontal(|ui| {
+                ui.label("Archivos .sql soltados: cargar en el editor hasta");
+                ui.add(egui::DragValue::new(&mut self.sql_drop_threshold_kb).range(1..=102400).suffix(" KB"));
+            }).response.on_hover_text("Los archivos más grandes se importan con lando db-import");
+        });
+
+        ui.separator();
+
+        self.show_query_cache_inspector(ui);
+    }
+
+    fn show_process_list(&mut self, ui: &mut egui::Ui, service: &LandoService, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>) {
+        if self.process_list.stale && !self.process_list.loading {
+            self.load_process_list(service, project_path, sender);
+        }
+
+        let mut kill = None;
+        ui.group(|ui| {
+            ui.horizontal(|ui| {
+                ui.strong("⚡ Consultas en ejecución:");
+                let label = if self.process_list.loaded { "🔄 Actualizar" } else { "📋 Cargar procesos" };
+                if ui.add_enabled(!self.process_list.loading, egui::Button::new(label)).clicked() {
+                    self.load_process_list(service, project_path, sender);
+                }
+                if self.process_list.loading {
+                    ui.spinner();
+                }
+            });
+
+            if let Some(error) = &self.process_list.error {
+                ui.colored_label(error_color(ui.visuals()), format!("❌ {}", error));
+            }
+            if !self.process_list.loaded {
+                return;
+            }
+            if self.process_list.processes.is_empty() {
+                ui.label(egui::RichText::new("💭 No hay otras conexiones activas").color(muted_color(ui.visuals())));
+                return;
+            }
+
+            egui::ScrollArea::vertical()
+                .id_salt("process_list")
+                .max_height(200.0)
+                .show(ui, |ui| {
+                    egui::Grid::new("process_list_grid").striped(true).show(ui, |ui| {
+                        ui.strong("Id");
+                        ui.strong("Usuario");
+                        ui.strong("BD");
+                        ui.strong("Estado");
+                        ui.strong("Tiempo");
+                        ui.strong("Consulta");
+                        ui.label("");
+                        ui.end_row();
+
+                        for process in &self.process_list.processes {
+                            ui.monospace(process.id.to_string());
+                            ui.label(&process.user);
+                            ui.label(process.database.as_deref().unwrap_or("—"));
+                            ui.label(&process.state);
+                            ui.label(process.seconds.map(|s| format!("{} s", s)).unwrap_or_default());
+                            match &process.query {
+                                Some(query) => {
+                                    let preview: String = query.chars().take(60).collect();
+                                    ui.monospace(preview.replace('\n', " ")).on_hover_text(query);
+                                }
+                                None => {
+                                    ui.label(egui::RichText::new("—").color(muted_color(ui.visuals())));
+                                }
+                            }
+                            ui.horizontal(|ui| {
+                                if ui.add_enabled(process.query.is_some(), egui::Button::new("⏹").small())
+                                    .on_hover_text("Cancelar la consulta")
+                                    .clicked()
+                                {
+                                    kill = Some((process.clone(), KillMode::CancelQuery));
+                                }
+                                if ui.small_button("💀").on_hover_text("Terminar la conexión").clicked() {
+                                    kill = Some((process.clone(), KillMode::Terminate));
+                                }
+                            });
+                            ui.end_row();
+                        }
+                    });
+                });
+        });
+
+        if let Some((process, mode)) = kill {
+            self.request_kill_process(service, &process, mode);
+        }
+    }
+
+    fn show_backup_manager(
+        &mut self,
+        ui: &mut egui::Ui,
+        service: &LandoService,
+        project_path: &PathBuf,
+        sender: &Sender<LandoCommandOutcome>,
+        is_loading: &mut bool,
+    ) {
+        // La carpeta se reconcilia con el índice la primera vez que se abre
+        if self.backups.loaded_for.as_ref() != Some(project_path) && !self.backups.loading {
+            self.refresh_backups(service, project_path, sender);
+        }
+
+        let mut restore = None;
+        let mut delete = None;
+        let mut reveal = None;
+
+        ui.group(|ui| {
+            ui.strong("🗄️ Backups:");
+
+            ui.horizontal(|ui| {
+                ui.label("📁 Carpeta:");
+                let response = ui.add(egui::TextEdit::singleline(&mut self.backups.dir)
+                    .hint_text(DEFAULT_BACKUP_DIR)
+                    .desired_width(160.0));
+                if response.lost_focus() {
+                    self.refresh_backups(service, project_path, sender);
+                }
+                if ui.small_button("🔄").on_hover_text("Volver a leer la carpeta").clicked() {
+                    self.refresh_backups(service, project_path, sender);
+                }
+                if ui.add_enabled(!*is_loading, egui::Button::new("💾 Nuevo backup"))
+                    .on_hover_text(format!("lando db-export de {} a la carpeta de backups", service.service))
+                    .clicked()
+                {
+                    self.create_backup(service, project_path, sender, is_loading);
+                }
+                if self.backups.loading {
+                    ui.spinner();
+                }
+            });
+
+            if let Some(error) = &self.backups.error {
+                ui.colored_label(error_color(ui.visuals()), format!("❌ {}", error));
+            }
+
+            if self.backups.entries.is_empty() {
+                ui.label(egui::RichText::new("💭 No hay backups en la carpeta").color(muted_color(ui.visuals())));
+                return;
+            }
+
+            egui::ScrollArea::vertical()
+                .id_salt("backup_list")
+                .max_height(180.0)
+                .show(ui, |ui| {
+                    egui::Grid::new("backup_grid").striped(true).show(ui, |ui| {
+                        ui.strong("Archivo");
+                        ui.strong("Servicio");
+                        ui.strong("Fecha");
+                        ui.strong("Tamaño");
+                        ui.end_row();
+
+                        for entry in &self.backups.entries {
+                            let selected = self.backups.selected.as_ref() == Some(&entry.file_name);
+                            if ui.selectable_label(selected, &entry.file_name).clicked() {
+                                self.backups.selected = Some(entry.file_name.clone());
+                            }
+                            match &entry.service {
+                                Some(name) => ui.label(name),
+                                None => ui.label(egui::RichText::new("—").color(muted_color(ui.visuals()))),
+                            };
+                            ui.label(format_backup_date(entry.created_at));
+                            ui.label(format_size(entry.size));
+                            ui.end_row();
+                        }
+                    });
+                });
+
+            ui.horizontal_wrapped(|ui| {
+                let selected = self.backups.selected.clone();
+                let has_selection = selected.is_some();
+                if ui.add_enabled(has_selection && !*is_loading, egui::Button::new("♻️ Restaurar"))
+                    .on_hover_text("lando db-import del backup seleccionado")
+                    .clicked()
+                {
+                    restore = selected.clone();
+                }
+                if ui.add_enabled(has_selection, egui::Button::new("📂 Mostrar en carpeta")).clicked() {
+                    reveal = selected.clone();
+                }
+                if ui.add_enabled(has_selection, egui::Button::new("🗑️ Eliminar")).clicked() {
+                    delete = selected.map(|name| vec![name]);
+                }
+
+                ui.separator();
+
+                ui.label("Conservar últimos");
+                ui.add(egui::DragValue::new(&mut self.backups.keep_last).range(1..=100));
+                let surplus: Vec<String> = backups_beyond_retention(&self.backups.entries, &service.service, self.backups.keep_last)
+                    .into_iter()
+                    .map(|e| e.file_name.clone())
+                    .collect();
+                if ui.add_enabled(!surplus.is_empty(), egui::Button::new(format!("🧹 Borrar antiguos ({})", surplus.len())))
+                    .on_hover_text(format!("Solo cuenta los backups de {}", service.service))
+                    .clicked()
+                {
+                    delete = Some(surplus);
+                }
+            });
+        });
+
+        if let Some(file_name) = restore {
+            self.request_restore_backup(service, project_path, &file_name);
+        }
+        if let Some(files) = delete {
+            self.request_delete_backups(service, project_path, files);
+        }
+        if let Some(file_name) = reveal {
+            if let Err(e) = reveal_in_file_manager(&self.backup_dir(project_path).join(file_name)) {
+                self.backups.error = Some(format!("No se pudo abrir el gestor de archivos: {}", e));
+            }
+        }
+    }
+
+    fn show_query_schedules(&mut self, ui: &mut egui::Ui) {
+        ui.group(|ui| {
+            ui.strong("⏰ Consultas Programadas:");
+
+            if self.saved_queries.is_empty() {
+                ui.label("Guarda una consulta para poder programarla");
+            } else {
+                let mut names: Vec<String> = self.saved_queries.keys().cloned().collect();
+                names.sort();
+                ui.horizontal_wrapped(|ui| {
+                    egui::ComboBox::from_id_salt("schedule_query")
+                        .selected_text(if self.schedule_form.query_name.is_empty() {
+                            "Consulta guardada"
+                        } else {
+                            self.schedule_form.query_name.as_str()
+                        })
+                        .show_ui(ui, |ui| {
+                            for name in &names {
+                                ui.selectable_value(&mut self.schedule_form.query_name, name.clone(), name);
+                            }
+                        });
+
+                    ui.label("cada");
+                    ui.add(
+                        egui::DragValue::new(&mut self.schedule_form.interval_secs)
+                            .range(SCHEDULE_MIN_INTERVAL..=SCHEDULE_MAX_INTERVAL)
+                            .suffix(" s")
+                    );
+
+                    egui::ComboBox::from_id_salt("schedule_condition")
+                        .selected_text(schedule_condition_label(self.schedule_form.condition))
+                        .show_ui(ui, |ui| {
+                            for condition in [ScheduleCondition::Never, ScheduleCondition::RowsAboveZero, ScheduleCondition::ResultChanged] {
+                                ui.selectable_value(&mut self.schedule_form.condition, condition, schedule_condition_label(condition));
+                            }
+                        });
+
+                    ui.checkbox(&mut self.schedule_form.desktop_notification, "🔔 Notificación del sistema");
+
+                    if ui.button("➕ Programar").clicked() {
+                        self.add_schedule();
+                    }
+                });
+            }
+
+            if let Some(error) = &self.schedules_error {
+                ui.colored_label(error_color(ui.visuals()), error);
+            }
+
+            let mut toggled = None;
+            let mut removed = None;
+            for (index, schedule) in self.schedules.iter().enumerate() {
+                ui.separator();
+                ui.horizontal(|ui| {
+                    let mut enabled = schedule.enabled;
+                    if ui.checkbox(&mut enabled, "").on_hover_text("Activar/desactivar").changed() {
+                        toggled = Some((index, enabled));
+                    }
+                    ui.strong(&schedule.query_name);
+                    ui.label(format!("cada {} s · {}", schedule.interval_secs, schedule_condition_label(schedule.condition)));
+
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        if ui.small_button("🗑️").on_hover_text("Eliminar programación").clicked() {
+                            removed = Some(index);
+                        }
+                        ui.label(format!("Próxima: {}", self.format_next_run(schedule)));
+                        let last_run = schedule.runtime.runs.back()
+                            .map(|run| format_cache_age(run.finished_at))
+                            .unwrap_or_else(|| "nunca".to_string());
+                        ui.label(format!("Última: {}", last_run));
+                    });
+                });
+
+                if let Some(reason) = &schedule.runtime.paused_reason {
+                    ui.colored_label(warning_color(ui.visuals()), format!("⏸ En pausa: {}", reason));
+                }
+
+                if !schedule.runtime.runs.is_empty() {
+                    egui::CollapsingHeader::new(format!("Últimas {} ejecuciones", schedule.runtime.runs.len()))
+                        .id_salt(("schedule_runs", &schedule.query_name))
+                        .show(ui, |ui| {
+                            for run in schedule.runtime.runs.iter().rev() {
+                                let status = match (&run.output, run.triggered) {
+                                    (Err(_), _) => "❌",
+                                    (Ok(_), true) => "🔔",
+                                    (Ok(_), false) => "✅",
+                                };
+                                let rows = run.row_count.map(|r| format!("{} filas", r)).unwrap_or_default();
+                                ui.collapsing(format!("{} {} {}", status, format_cache_age(run.finished_at), rows), |ui| {
+                                    let mut text = match &run.output {
+                                        Ok(text) | Err(text) => text.as_str(),
+                                    };
+                                    ui.add(
+                                        egui::TextEdit::multiline(&mut text)
+                                            .code_editor()
+                                            .desired_width(f32::INFINITY)
+                                    );
+                                });
+                            }
+                        });
+                }
+            }
+
+            if let Some((index, enabled)) = toggled {
+                self.set_schedule_enabled(index, enabled);
+            }
+            if let Some(index) = removed {
+                self.remove_schedule(index);
+            }
+        });
+    }
+
+    fn show_query_cache_inspector(&mut self, ui: &mut egui::Ui) {
+        ui.group(|ui| {
+            ui.horizontal(|ui| {
+                ui.strong("🗄️ Caché de Consultas:");
+                ui.label(format!(
+                    "{} entradas · {:.1} KB",
+                    self.query_cache.entries.len(),
+                    self.query_cache.total_bytes() as f64 / 1024.0
+                ));
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui.add_enabled(!self.query_cache.entries.is_empty(), egui::Button::new("🗑️ Vaciar caché")).clicked() {
+                        self.query_cache.clear();
+                    }
+                });
+            });
+
+            if !self.enable_query_cache {
+                ui.colored_label(muted_color(ui.visuals()), "La caché está deshabilitada; las entradas existentes no se usan");
+            }
+
+            let mut max_kb = self.query_cache.max_bytes / 1024;
+            let limits_changed = ui.horizontal(|ui| {
+                ui.label("Máx entradas:");
+                let entries = ui.add(egui::DragValue::new(&mut self.query_cache.max_entries).range(1..=1000)).changed();
+                ui.label("Tamaño máx:");
+                let size = ui.add(egui::DragValue::new(&mut max_kb).range(64..=102400).suffix(" KB")).changed();
+                entries || size
+            }).inner;
+            if limits_changed {
+                self.query_cache.max_bytes = max_kb * 1024;
+                self.query_cache.enforce_limits();
+            }
+
+            if self.query_cache.entries.is_empty() {
+                ui.label("No hay resultados en caché");
+                return;
+            }
+
+            let mut entry_to_remove = None;
+            egui::ScrollArea::vertical()
+                .id_salt("query_cache_entries")
+                .max_height(180.0)
+                .show(ui, |ui| {
+                    egui::Grid::new("query_cache_grid")
+                        .num_columns(5)
+                        .striped(true)
+                        .show(ui, |ui| {
+                            ui.strong("Servicio");
+                            ui.strong("Consulta");
+                            ui.strong("Tamaño");
+                            ui.strong("Obtenido");
+                            ui.label("");
+                            ui.end_row();
+
+                            // Las más recientes primero
+                            for (index, entry) in self.query_cache.entries.iter().enumerate().rev() {
+                                ui.label(&entry.service);
+                                let preview: String = entry.query.chars().take(60).collect();
+                                ui.monospace(preview).on_hover_text(&entry.query);
+                                ui.label(format!("{:.1} KB", entry.size() as f64 / 1024.0));
+                                ui.label(format_cache_age(entry.fetched_at));
+                                if ui.small_button("🗑️").on_hover_text("Quitar de la caché").clicked() {
+                                    entry_to_remove = Some(index);
+                                }
+                                ui.end_row();
+                            }
+                        });
+                });
+            if let Some(index) = entry_to_remove {
+                self.query_cache.remove(index);
+            }
         });
     }
 
     fn show_save_query_dialog(&mut self, ui: &mut egui::Ui) {
-        let mut query_name = self.query_name_input.clone();
-        let mut query_content = self.query_input.clone();
-        let mut saved_queries_clone = self.saved_queries.clone();
+        let query_name = &mut self.query_name_input;
+        let mut query_content = self.query_input.as_str();
+        let mut save = false;
         let mut should_close = false;
         
         egui::Window::new("💾 Guardar Query")
@@ -1213,7 +4527,7 @@ impl DatabaseUI {
             .show(ui.ctx(), |ui| {
                 ui.vertical(|ui| {
                     ui.label("Nombre de la query:");
-                    ui.text_edit_singleline(&mut query_name);
+                    ui.text_edit_singleline(query_name);
                     
                     ui.separator();
                     
@@ -1230,8 +4544,7 @@ impl DatabaseUI {
                     ui.horizontal(|ui| {
                         if ui.button("💾 Guardar").clicked() {
                             if !query_name.is_empty() && !query_content.is_empty() {
-                                saved_queries_clone.insert(query_name.clone(), query_content.clone());
-                                query_name.clear();
+                                save = true;
                                 should_close = true;
                             }
                         }
@@ -1244,11 +4557,52 @@ impl DatabaseUI {
                 });
             });
         
+        if save {
+            let name = std::mem::take(&mut self.query_name_input);
+            self.saved_query_times.insert(name.clone(), now_secs());
+            self.saved_queries.insert(name, self.query_input.clone());
+            self.persist_saved_queries();
+        }
         if should_close {
             self.show_save_query_dialog = false;
         }
-        self.query_name_input = query_name;
-        self.saved_queries = saved_queries_clone;
     }
 
 }
+
+// Texto con las apariciones de `needle` resaltadas (sin distinguir mayúsculas)
+fn highlight_matches(style: &egui::Style, text: &str, needle: &str, monospace: bool) -> egui::text::LayoutJob {
+    let text_style = if monospace { egui::TextStyle::Monospace } else { egui::TextStyle::Body };
+    let plain = egui::text::TextFormat {
+        font_id: text_style.resolve(style),
+        color: style.visuals.text_color(),
+        ..Default::default()
+    };
+    let marked = egui::text::TextFormat {
+        color: style.visuals.strong_text_color(),
+        background: style.visuals.selection.bg_fill,
+        ..plain.clone()
+    };
+
+    let mut job = egui::text::LayoutJob::default();
+    let lower = text.to_lowercase();
+    let needle = needle.to_lowercase();
+    // Si pasar a minúsculas cambia la longitud, los índices no sirven para el original
+    if needle.is_empty() || lower.len() != text.len() {
+        job.append(text, 0.0, plain);
+        return job;
+    }
+
+    let mut last = 0;
+    for (start, _) in lower.match_indices(&needle) {
+        let end = start + needle.len();
+        if start < last || !text.is_char_boundary(start) || !text.is_char_boundary(end) {
+            continue;
+        }
+        job.append(&text[last..start], 0.0, plain.clone());
+        job.append(&text[start..end], 0.0, marked.clone());
+        last = end;
+    }
+    job.append(&text[last..], 0.0, plain);
+    job
+}
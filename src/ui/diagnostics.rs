@@ -0,0 +1,216 @@
+use std::collections::VecDeque;
+
+use eframe::egui;
+use log::{Level, LevelFilter};
+
+use crate::core::logging::{line_level, log_file_path, read_log_tail, LOG_LEVELS, LOG_TAIL_LINES};
+use crate::core::platform::reveal_in_file_manager;
+use crate::ui::theme::{error_color, info_color, muted_color, success_color, warning_color};
+
+// Ventana "Acerca de / Diagnóstico": dónde está el log, sus últimas líneas y
+// un informe listo para pegar en un bug
+pub struct DiagnosticsWindow {
+    pub open: bool,
+    lines: Vec<String>,
+    error: Option<String>,
+    filter: LevelFilter, // Se muestran las líneas de este nivel o más graves
+    status: Option<String>,
+}
+
+impl Default for DiagnosticsWindow {
+    fn default() -> Self {
+        Self {
+            open: false,
+            lines: Vec::new(),
+            error: None,
+            filter: LevelFilter::Trace,
+            status: None,
+        }
+    }
+}
+
+impl DiagnosticsWindow {
+    pub fn toggle(&mut self) {
+        self.open = !self.open;
+        if self.open {
+            self.reload();
+        }
+    }
+
+    fn reload(&mut self) {
+        match read_log_tail(LOG_TAIL_LINES) {
+            Ok(lines) => {
+                self.lines = lines;
+                self.error = None;
+            }
+            Err(e) => self.error = Some(format!("No se pudo leer el log: {}", e)),
+        }
+    }
+
+    // Cada línea con su nivel; las de continuación heredan el de la anterior
+    fn leveled_lines(&self) -> Vec<(&str, Option<Level>)> {
+        let mut current = None;
+        self.lines.iter()
+            .map(|line| {
+                if let Some(level) = line_level(line) {
+                    current = Some(level);
+                }
+                (line.as_str(), current)
+            })
+            .collect()
+    }
+
+    fn report(&self) -> String {
+        let log_path = log_file_path().map(|p| p.display().to_string()).unwrap_or_else(|| "-".to_string());
+        format!(
+            "Lando GUI {}\nSistema: {} {}\nNivel de log: {}\nLog: {}\n\n--- Últimas {} líneas ---\n{}\n",
+            env!("CARGO_PKG_VERSION"),
+            std::env::consts::OS,
+            std::env::consts::ARCH,
+            log::max_level(),
+            log_path,
+            self.lines.len(),
+            self.lines.join("\n")
+        )
+    }
+
+    fn level_color(level: Option<Level>, visuals: &egui::Visuals) -> egui::Color32 {
+        match level {
+            Some(Level::Error) => error_color(visuals),
+            Some(Level::Warn) => warning_color(visuals),
+            Some(Level::Info) => info_color(visuals),
+            _ => muted_color(visuals),
+        }
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context) {
+        let mut open = self.open;
+        egui::Window::new("🩺 Acerca de / Diagnóstico")
+            .open(&mut open)
+            .default_size([720.0, 480.0])
+            .show(ctx, |ui| {
+                ui.label(format!("🚀 Lando GUI {}", env!("CARGO_PKG_VERSION")));
+                ui.label(format!("💻 {} {}", std::env::consts::OS, std::env::consts::ARCH));
+
+                let log_path = log_file_path();
+                ui.horizontal(|ui| {
+                    ui.label("📄 Log:");
+                    match &log_path {
+                        Some(path) => {
+                            ui.monospace(path.display().to_string());
+                            if ui.small_button("📂").on_hover_text("Mostrar en el gestor de archivos").clicked() {
+                                if let Err(e) = reveal_in_file_manager(path) {
+                                    self.status = Some(format!("No se pudo abrir la carpeta: {}", e));
+                                }
+                            }
+                        }
+                        None => {
+                            ui.colored_label(muted_color(ui.visuals()), "sin directorio de datos");
+                        }
+                    }
+                });
+
+                ui.separator();
+
+                ui.horizontal(|ui| {
+                    ui.label("Mostrar:");
+                    egui::ComboBox::from_id_salt("diagnostics_level_filter")
+                        .selected_text(self.filter.as_str())
+                        .show_ui(ui, |ui| {
+                            for level in LOG_LEVELS {
+                                ui.selectable_value(&mut self.filter, *level, level.as_str());
+                            }
+                        });
+                    if ui.button("🔄 Recargar").clicked() {
+                        self.reload();
+                    }
+                    if ui.button("📋 Copiar informe").on_hover_text("Versión, sistema y últimas líneas del log").clicked() {
+                        ui.ctx().copy_text(self.report());
+                        self.status = Some("Informe copiado al portapapeles.".to_string());
+                    }
+                });
+
+                if let Some(status) = &self.status {
+                    ui.colored_label(muted_color(ui.visuals()), status);
+                }
+                if let Some(error) = &self.error {
+                    ui.colored_label(error_color(ui.visuals()), error);
+                }
+
+                let visible: Vec<(&str, Option<Level>)> = self.leveled_lines().into_iter()
+                    .filter(|(_, level)| level.is_none_or(|l| l <= self.filter))
+                    .collect();
+                if visible.is_empty() {
+                    ui.colored_label(muted_color(ui.visuals()), "No hay líneas con este nivel.");
+                    return;
+                }
+
+                egui::ScrollArea::both()
+                    .auto_shrink([false, false])
+                    .stick_to_bottom(true)
+                    .show(ui, |ui| {
+                        for (line, level) in visible {
+                            ui.label(
+                                egui::RichText::new(line)
+                                    .monospace()
+                                    .color(Self::level_color(level, ui.visuals())),
+                            );
+                        }
+                    });
+            });
+        self.open = open;
+    }
+}
+
+const FRAME_SAMPLES: usize = 60;
+// Objetivo en estado estable con 20 servicios, 50 consultas en el historial y
+// un resultado de 1 MB cargado
+pub const FRAME_TARGET_MS: f32 = 2.0;
+
+// Media móvil del tiempo de CPU por frame, para medir el coste del render
+#[derive(Default)]
+pub struct FrameTimeOverlay {
+    pub enabled: bool,
+    samples: VecDeque<f32>, // En milisegundos
+}
+
+impl FrameTimeOverlay {
+    pub fn record(&mut self, cpu_usage: Option<f32>) {
+        if !self.enabled {
+            self.samples.clear();
+            return;
+        }
+        if let Some(seconds) = cpu_usage {
+            if self.samples.len() == FRAME_SAMPLES {
+                self.samples.pop_front();
+            }
+            self.samples.push_back(seconds * 1000.0);
+        }
+    }
+
+    pub fn show(&self, ctx: &egui::Context) {
+        if !self.enabled || self.samples.is_empty() {
+            return;
+        }
+
+        let average = self.samples.iter().sum::<f32>() / self.samples.len() as f32;
+        let worst = self.samples.iter().copied().fold(0.0, f32::max);
+        egui::Area::new(egui::Id::new("frame_time_overlay"))
+            .anchor(egui::Align2::LEFT_BOTTOM, [8.0, -8.0])
+            .order(egui::Order::Foreground)
+            .interactable(false)
+            .show(ctx, |ui| {
+                egui::Frame::popup(ui.style()).show(ui, |ui| {
+                    let color = if average > FRAME_TARGET_MS {
+                        warning_color(ui.visuals())
+                    } else {
+                        success_color(ui.visuals())
+                    };
+                    ui.colored_label(
+                        color,
+                        format!("⏱️ {:.2} ms (máx. {:.2} ms, {} frames)", average, worst, self.samples.len()),
+                    );
+                });
+            });
+    }
+}
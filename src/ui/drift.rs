@@ -0,0 +1,123 @@
+use std::path::PathBuf;
+use std::sync::mpsc::Sender;
+
+use eframe::egui;
+
+use crate::core::dashboard::format_last_opened;
+use crate::core::time::now_secs;
+use crate::core::drift::check_config_drift;
+use crate::models::commands::LandoCommandOutcome;
+use crate::models::drift::{ConfigDifference, ConfigDriftView, DriftState};
+use crate::ui::theme::{error_color, muted_color, success_color, warning_color};
+
+impl ConfigDriftView {
+    // Se compara una vez al abrir el proyecto y de nuevo tras cada comando terminado
+    pub fn ensure_checked(&mut self, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>) {
+        if self.project.as_ref() != Some(project_path) {
+            self.project = Some(project_path.clone());
+            self.state = None;
+            check_config_drift(sender.clone(), project_path.clone());
+        }
+    }
+
+    pub fn invalidate(&mut self) {
+        self.project = None;
+    }
+
+    pub fn handle_checked(&mut self, project: PathBuf, state: DriftState) {
+        if self.project.as_ref() == Some(&project) {
+            self.state = Some(state);
+            self.checked_at = Some(now_secs());
+        }
+    }
+
+    // Devuelve true si se pulsa "Rebuild" en el aviso
+    pub fn show(&mut self, ui: &mut egui::Ui, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>, busy: bool) -> bool {
+        self.ensure_checked(project_path, sender);
+
+        let mut rebuild = false;
+        if let Some(DriftState::Compared(differences)) = &self.state {
+            if !differences.is_empty() {
+                ui.horizontal(|ui| {
+                    ui.colored_label(
+                        warning_color(ui.visuals()),
+                        format!("⚠️ La app necesita rebuild: el .lando.yml tiene {} cambio(s) sin aplicar", differences.len()),
+                    );
+                    if ui.add_enabled(!busy, egui::Button::new("🔧 Rebuild")).clicked() {
+                        rebuild = true;
+                    }
+                });
+            }
+        }
+
+        egui::CollapsingHeader::new("🧭 Config en uso vs. en disco")
+            .id_salt("config_drift")
+            .show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    let checking = self.state.is_none();
+                    if ui.add_enabled(!checking, egui::Button::new("🔄 Comparar")).clicked() {
+                        self.invalidate();
+                        self.ensure_checked(project_path, sender);
+                    }
+                    if checking {
+                        ui.spinner();
+                    } else if let Some(checked_at) = self.checked_at {
+                        ui.colored_label(muted_color(ui.visuals()), format_last_opened(checked_at));
+                    }
+                });
+
+                match &self.state {
+                    None => {}
+                    Some(DriftState::InvalidYaml(err)) => {
+                        ui.colored_label(error_color(ui.visuals()), format!("❌ El landofile no es YAML válido: {}", err));
+                    }
+                    Some(DriftState::NeverStarted) => {
+                        ui.colored_label(muted_color(ui.visuals()), "La app no se ha arrancado nunca: todavía no hay configuración en uso");
+                    }
+                    Some(DriftState::Failed(err)) => {
+                        ui.colored_label(error_color(ui.visuals()), format!("❌ {}", err));
+                    }
+                    Some(DriftState::Compared(differences)) if differences.is_empty() => {
+                        ui.colored_label(success_color(ui.visuals()), "✅ La configuración en uso coincide con la del disco");
+                    }
+                    Some(DriftState::Compared(differences)) => show_differences(ui, differences),
+                }
+            });
+
+        rebuild
+    }
+}
+
+fn show_differences(ui: &mut egui::Ui, differences: &[ConfigDifference]) {
+    egui::Grid::new("config_drift_grid").striped(true).num_columns(4).show(ui, |ui| {
+        ui.strong("Servicio");
+        ui.strong("Campo");
+        ui.strong("En uso");
+        ui.strong("En disco");
+        ui.end_row();
+
+        for difference in differences {
+            match difference {
+                ConfigDifference::OnlyOnDisk(service) => {
+                    ui.label(service);
+                    ui.colored_label(success_color(ui.visuals()), "➕ servicio nuevo");
+                    ui.label("—");
+                    ui.label("definido");
+                }
+                ConfigDifference::OnlyInUse(service) => {
+                    ui.label(service);
+                    ui.colored_label(error_color(ui.visuals()), "➖ servicio eliminado");
+                    ui.label("en marcha");
+                    ui.label("—");
+                }
+                ConfigDifference::Changed { service, field, in_use, on_disk } => {
+                    ui.label(service);
+                    ui.colored_label(warning_color(ui.visuals()), field);
+                    ui.monospace(in_use);
+                    ui.monospace(on_disk);
+                }
+            }
+            ui.end_row();
+        }
+    });
+}
@@ -0,0 +1,118 @@
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::Sender;
+use std::sync::Arc;
+
+use eframe::egui;
+
+use crate::models::commands::LandoCommandOutcome;
+use crate::models::lando::LandoService;
+
+// Comandos rápidos del panel shell de servicios genéricos
+pub const QUICK_SHELL_COMMANDS: &[(&str, &str)] = &[
+    ("🌱 env", "env"),
+    ("📋 ps aux", "ps aux"),
+    ("💽 df -h", "df -h"),
+    ("🐧 os-release", "cat /etc/os-release"),
+];
+
+// Comando en curso del panel shell
+pub struct RunningShellCommand {
+    pub request_id: u64,
+    pub cancel: Arc<AtomicBool>,
+}
+
+// Estado por servicio de la tarjeta genérica: panel shell con historial y
+// salida propia (no el terminal global), que se conserva durante la sesión
+pub struct GenericServiceUI {
+    pub command_input: String,
+    pub history: Vec<String>,
+    pub history_cursor: Option<usize>,
+    pub scrollback: String,
+    pub pending_bytes: Vec<u8>,
+    pub running: Option<RunningShellCommand>,
+    pub engine_probe_requested: bool, // Ya se preguntó al contenedor por su motor de BD
+}
+
+impl Default for GenericServiceUI {
+    fn default() -> Self {
+        Self {
+            command_input: String::new(),
+            history: Vec::new(),
+            history_cursor: None,
+            scrollback: String::new(),
+            pending_bytes: Vec::new(),
+            running: None,
+            engine_probe_requested: false,
+        }
+    }
+}
+
+impl GenericServiceUI {
+    pub fn show_shell_panel(
+        &mut self,
+        ui: &mut egui::Ui,
+        service: &LandoService,
+        project_path: &PathBuf,
+        sender: &Sender<LandoCommandOutcome>,
+    ) {
+        ui.strong("💻 Shell");
+
+        let is_running = self.running.is_some();
+
+        ui.horizontal(|ui| {
+            let response = ui.add_enabled(
+                !is_running,
+                egui::TextEdit::singleline(&mut self.command_input)
+                    .hint_text("Comando (↑/↓ historial, Enter ejecuta)")
+                    .code_editor()
+                    .desired_width(320.0)
+            );
+
+            if response.has_focus() {
+                if ui.input(|i| i.key_pressed(egui::Key::ArrowUp)) {
+                    self.recall_history(true);
+                } else if ui.input(|i| i.key_pressed(egui::Key::ArrowDown)) {
+                    self.recall_history(false);
+                }
+            }
+
+            let submitted = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+            if is_running {
+                ui.spinner();
+                if ui.button("⏹ Cancelar").clicked() {
+                    if let Some(running) = &self.running {
+                        running.cancel.store(true, Ordering::Relaxed);
+                    }
+                }
+            } else if ui.button("▶️ Ejecutar").clicked() || submitted {
+                let command = self.command_input.clone();
+                self.run_command(service, project_path, sender, &command);
+                response.request_focus();
+            }
+        });
+
+        ui.horizontal_wrapped(|ui| {
+            for (label, command) in QUICK_SHELL_COMMANDS {
+                if ui.add_enabled(!is_running, egui::Button::new(*label)).clicked() {
+                    self.run_command(service, project_path, sender, command);
+                }
+            }
+            if ui.small_button("🗑️").on_hover_text("Limpiar salida").clicked() {
+                self.scrollback.clear();
+            }
+        });
+
+        egui::ScrollArea::vertical()
+            .id_salt(("generic_shell_scrollback", &service.service))
+            .max_height(220.0)
+            .stick_to_bottom(true)
+            .show(ui, |ui| {
+                ui.add(
+                    egui::TextEdit::multiline(&mut self.scrollback.as_str())
+                        .code_editor()
+                        .desired_width(f32::INFINITY)
+                );
+            });
+    }
+}
@@ -0,0 +1,347 @@
+use std::collections::HashMap;
+
+use eframe::egui;
+
+use crate::models::graph::{GraphEdgeKind, GraphNode, GraphNodeKind, ServiceGraph};
+use crate::ui::theme::{info_color, muted_color, success_color, warning_color};
+
+const NODE_SIZE: egui::Vec2 = egui::vec2(150.0, 44.0);
+const NODE_GAP: f32 = 30.0;
+const LAYER_HEIGHT: f32 = 120.0;
+const FORCE_ITERATIONS: usize = 200;
+const OVERLAP_PASSES: usize = 50;
+const MIN_ZOOM: f32 = 0.3;
+const MAX_ZOOM: f32 = 2.5;
+
+fn node_icon(kind: GraphNodeKind) -> &'static str {
+    match kind {
+        GraphNodeKind::AppServer => "🖥",
+        GraphNodeKind::Node => "🟩",
+        GraphNodeKind::Database => "🛢",
+        GraphNodeKind::Cache => "⚡",
+        GraphNodeKind::Proxy => "🌐",
+        GraphNodeKind::Other => "⚙",
+    }
+}
+
+// Fila de la disposición por capas: proxy arriba, apps en medio, datos abajo
+fn node_layer(kind: GraphNodeKind) -> usize {
+    match kind {
+        GraphNodeKind::Proxy => 0,
+        GraphNodeKind::AppServer | GraphNodeKind::Node | GraphNodeKind::Other => 1,
+        GraphNodeKind::Database | GraphNodeKind::Cache => 2,
+    }
+}
+
+fn edge_color(kind: GraphEdgeKind, visuals: &egui::Visuals) -> egui::Color32 {
+    match kind {
+        GraphEdgeKind::DependsOn => info_color(visuals),
+        GraphEdgeKind::Proxy => success_color(visuals),
+        GraphEdgeKind::Database => warning_color(visuals),
+    }
+}
+
+fn overlaps(a: egui::Pos2, b: egui::Pos2) -> bool {
+    (a.x - b.x).abs() < NODE_SIZE.x + NODE_GAP && (a.y - b.y).abs() < NODE_SIZE.y + NODE_GAP
+}
+
+// Disposición inicial por capas; cada capa centrada sobre x = 0
+fn layered_positions(nodes: &[GraphNode]) -> HashMap<String, egui::Pos2> {
+    let mut layers: [Vec<&GraphNode>; 3] = Default::default();
+    for node in nodes {
+        layers[node_layer(node.kind)].push(node);
+    }
+    let mut positions = HashMap::new();
+    for (row, layer) in layers.iter().enumerate() {
+        let width = layer.len() as f32 * (NODE_SIZE.x + NODE_GAP);
+        for (column, node) in layer.iter().enumerate() {
+            let x = column as f32 * (NODE_SIZE.x + NODE_GAP) - width / 2.0;
+            positions.insert(node.id.clone(), egui::pos2(x, row as f32 * LAYER_HEIGHT));
+        }
+    }
+    positions
+}
+
+// Disposición de fuerzas partiendo de las capas: repulsión entre todos los
+// nodos, muelles en las aristas y atracción a la fila de su capa. Las
+// posiciones en `fixed` (arrastradas o ya conocidas) no se mueven. Devuelve
+// None si no consigue dejar los nodos sin solaparse.
+pub fn compute_layout(graph: &ServiceGraph, fixed: &HashMap<String, egui::Pos2>) -> Option<HashMap<String, egui::Pos2>> {
+    let mut positions = layered_positions(&graph.nodes);
+    for (id, pos) in fixed {
+        if let Some(current) = positions.get_mut(id) {
+            *current = *pos;
+        }
+    }
+    let ids: Vec<String> = graph.nodes.iter().map(|n| n.id.clone()).collect();
+    let movable = |id: &String| !fixed.contains_key(id);
+    let ideal = NODE_SIZE.x + NODE_GAP;
+
+    for iteration in 0..FORCE_ITERATIONS {
+        let cooling = 1.0 - iteration as f32 / FORCE_ITERATIONS as f32;
+        let mut forces: HashMap<&String, egui::Vec2> = ids.iter().map(|id| (id, egui::Vec2::ZERO)).collect();
+
+        for (i, a) in ids.iter().enumerate() {
+            for b in &ids[i + 1..] {
+                let delta = positions[a] - positions[b];
+                let distance = delta.length().max(1.0);
+                let push = delta / distance * (ideal * ideal / distance);
+                *forces.get_mut(a).unwrap() += push;
+                *forces.get_mut(b).unwrap() -= push;
+            }
+        }
+        for edge in &graph.edges {
+            let (Some(from), Some(to)) = (positions.get(&edge.from), positions.get(&edge.to)) else {
+                continue;
+            };
+            let delta = *to - *from;
+            let pull = delta * (delta.length() / (ideal * 4.0)).min(1.0) * 0.5;
+            *forces.get_mut(&edge.from).unwrap() += pull;
+            *forces.get_mut(&edge.to).unwrap() -= pull;
+        }
+        for node in &graph.nodes {
+            let row_y = node_layer(node.kind) as f32 * LAYER_HEIGHT;
+            forces.get_mut(&node.id).unwrap().y += (row_y - positions[&node.id].y) * 0.8;
+        }
+
+        for (id, force) in forces {
+            if movable(id) {
+                let step = force.length().min(30.0 * cooling);
+                if step > 0.0 {
+                    *positions.get_mut(id).unwrap() += force.normalized() * step;
+                }
+            }
+        }
+    }
+
+    // Separación final en horizontal de lo que aún se solapa
+    for _ in 0..OVERLAP_PASSES {
+        let mut moved = false;
+        for (i, a) in ids.iter().enumerate() {
+            for b in &ids[i + 1..] {
+                let (pa, pb) = (positions[a], positions[b]);
+                if !overlaps(pa, pb) {
+                    continue;
+                }
+                let push = (NODE_SIZE.x + NODE_GAP - (pa.x - pb.x).abs()) / 2.0 + 1.0;
+                let direction = if pa.x < pb.x || (pa.x == pb.x && a < b) { -1.0 } else { 1.0 };
+                match (movable(a), movable(b)) {
+                    (true, true) => {
+                        positions.get_mut(a).unwrap().x += direction * push;
+                        positions.get_mut(b).unwrap().x -= direction * push;
+                    }
+                    (true, false) => positions.get_mut(a).unwrap().x += direction * push * 2.0,
+                    (false, true) => positions.get_mut(b).unwrap().x -= direction * push * 2.0,
+                    (false, false) => {}
+                }
+                moved = true;
+            }
+        }
+        if !moved {
+            break;
+        }
+    }
+
+    let valid = positions.values().all(|p| p.x.is_finite() && p.y.is_finite())
+        && ids.iter().enumerate().all(|(i, a)| ids[i + 1..].iter().all(|b| !overlaps(positions[a], positions[b])));
+    valid.then_some(positions)
+}
+
+// Vista de grafo de los servicios del proyecto. Las posiciones son centros de
+// nodo en coordenadas del grafo; `pan` y `zoom` las llevan a pantalla.
+#[derive(Default)]
+pub struct ServiceGraphView {
+    pub graph: Option<ServiceGraph>,
+    positions: HashMap<String, egui::Pos2>,
+    dragged: HashMap<String, egui::Pos2>, // Nodos que el usuario colocó a mano
+    pan: egui::Vec2,
+    zoom: f32,
+    layout_failed: bool,
+}
+
+impl ServiceGraphView {
+    // Sustituye el grafo conservando donde estaban los nodos que siguen existiendo
+    pub fn set_graph(&mut self, graph: ServiceGraph) {
+        if self.graph.as_ref() == Some(&graph) {
+            return;
+        }
+        self.dragged.retain(|id, _| graph.nodes.iter().any(|n| &n.id == id));
+        match compute_layout(&graph, &self.dragged) {
+            Some(positions) => {
+                self.positions = positions;
+                self.layout_failed = false;
+            }
+            None => {
+                self.positions.clear();
+                self.layout_failed = true;
+            }
+        }
+        self.graph = Some(graph);
+    }
+
+    pub fn invalidate(&mut self) {
+        self.graph = None;
+    }
+
+    pub fn reset_view(&mut self) {
+        self.pan = egui::Vec2::ZERO;
+        self.zoom = 1.0;
+    }
+
+    fn relayout(&mut self) {
+        self.dragged.clear();
+        if let Some(graph) = self.graph.take() {
+            self.set_graph(graph);
+        }
+        self.reset_view();
+    }
+
+    // Devuelve el servicio cuyo nodo se ha pulsado
+    pub fn show(&mut self, ui: &mut egui::Ui, running: bool) -> Option<String> {
+        if self.zoom == 0.0 {
+            self.zoom = 1.0;
+        }
+        let graph = self.graph.clone()?;
+
+        ui.horizontal(|ui| {
+            if ui.small_button("➕").on_hover_text("Acercar").clicked() {
+                self.zoom = (self.zoom * 1.2).min(MAX_ZOOM);
+            }
+            if ui.small_button("➖").on_hover_text("Alejar").clicked() {
+                self.zoom = (self.zoom / 1.2).max(MIN_ZOOM);
+            }
+            if ui.small_button("⟲").on_hover_text("Centrar la vista").clicked() {
+                self.reset_view();
+            }
+            if ui.small_button("📐").on_hover_text("Recolocar los nodos").clicked() {
+                self.relayout();
+            }
+            ui.label(format!("{} nodos, {} conexiones", graph.nodes.len(), graph.edges.len()));
+        });
+
+        if self.layout_failed {
+            return Self::show_list(ui, &graph, running);
+        }
+
+        let mut clicked = None;
+        let size = egui::vec2(ui.available_width(), ui.available_height().clamp(300.0, 600.0));
+        let (canvas, painter) = ui.allocate_painter(size, egui::Sense::click_and_drag());
+        let painter = painter.with_clip_rect(canvas.rect);
+        if canvas.dragged() {
+            self.pan += canvas.drag_delta();
+        }
+        if canvas.hovered() {
+            let zoom_delta = ui.input(|i| i.zoom_delta());
+            if zoom_delta != 1.0 {
+                self.zoom = (self.zoom * zoom_delta).clamp(MIN_ZOOM, MAX_ZOOM);
+            }
+        }
+
+        let zoom = self.zoom;
+        let origin = canvas.rect.center() + self.pan;
+        let to_screen = |pos: egui::Pos2| origin + (pos.to_vec2() - egui::vec2(0.0, LAYER_HEIGHT)) * zoom;
+
+        // Primero los arrastres, para pintar ya con la posición nueva
+        for node in &graph.nodes {
+            let Some(pos) = self.positions.get_mut(&node.id) else { continue };
+            let rect = egui::Rect::from_center_size(to_screen(*pos), NODE_SIZE * zoom);
+            let response = ui.interact(rect, ui.id().with(("graph_node", &node.id)), egui::Sense::click_and_drag());
+            if response.dragged() {
+                *pos += response.drag_delta() / zoom;
+                self.dragged.insert(node.id.clone(), *pos);
+            }
+            if response.clicked() {
+                clicked = node.service.clone();
+            }
+            if response.hovered() {
+                ui.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
+            }
+            let mut hover = format!("{}\n{}", node.label, node.detail);
+            if !node.tooling.is_empty() {
+                hover.push_str(&format!("\nTooling: {}", node.tooling.join(", ")));
+            }
+            response.on_hover_text(hover);
+        }
+
+        let visuals = ui.visuals();
+        let text_color = visuals.text_color();
+        let font = egui::FontId::proportional(12.0 * zoom);
+
+        for edge in &graph.edges {
+            let (Some(from), Some(to)) = (self.positions.get(&edge.from), self.positions.get(&edge.to)) else {
+                continue;
+            };
+            let (from, to) = (to_screen(*from), to_screen(*to));
+            let stroke = egui::Stroke::new(1.5, edge_color(edge.kind, visuals));
+            // Se corta la línea en el borde de la caja destino para que se vea la flecha
+            let target = egui::Rect::from_center_size(to, NODE_SIZE * zoom);
+            let direction = (to - from).normalized();
+            let mut tip = to;
+            while target.contains(tip) && (tip - from).length() > 1.0 {
+                tip -= direction * 4.0;
+            }
+            if edge.kind == GraphEdgeKind::Database {
+                painter.extend(egui::Shape::dashed_line(&[from, tip], stroke, 6.0, 4.0));
+            } else {
+                painter.line_segment([from, tip], stroke);
+            }
+            let side = egui::vec2(-direction.y, direction.x) * 4.0;
+            let back = tip - direction * 8.0;
+            painter.add(egui::Shape::convex_polygon(vec![tip, back + side, back - side], stroke.color, egui::Stroke::NONE));
+        }
+
+        for node in &graph.nodes {
+            let Some(pos) = self.positions.get(&node.id) else { continue };
+            let rect = egui::Rect::from_center_size(to_screen(*pos), NODE_SIZE * zoom);
+            // Las rutas del proxy no son contenedores, no tienen estado propio
+            let status = if node.service.is_some() && running { success_color(visuals) } else { muted_color(visuals) };
+            painter.rect_filled(rect, 6.0, visuals.extreme_bg_color);
+            painter.rect_stroke(rect, 6.0, egui::Stroke::new(2.0, status), egui::StrokeKind::Inside);
+            painter.text(
+                rect.center(),
+                egui::Align2::CENTER_CENTER,
+                format!("{} {}", node_icon(node.kind), node.label),
+                font.clone(),
+                text_color,
+            );
+        }
+
+        ui.horizontal_wrapped(|ui| {
+            let visuals = ui.visuals().clone();
+            for (label, kind) in [
+                ("── depends_on", GraphEdgeKind::DependsOn),
+                ("── proxy", GraphEdgeKind::Proxy),
+                ("- - base de datos", GraphEdgeKind::Database),
+            ] {
+                ui.colored_label(edge_color(kind, &visuals), label);
+            }
+            ui.colored_label(muted_color(&visuals), "Arrastra para mover · Ctrl+rueda para zoom · Clic para ir al servicio");
+        });
+
+        clicked
+    }
+
+    // Alternativa cuando la disposición no consigue separar los nodos
+    fn show_list(ui: &mut egui::Ui, graph: &ServiceGraph, running: bool) -> Option<String> {
+        let mut clicked = None;
+        ui.colored_label(warning_color(ui.visuals()), "⚠ No se pudo dibujar el grafo; se muestra como lista.");
+        for node in &graph.nodes {
+            ui.horizontal(|ui| {
+                let status = if node.service.is_some() && running { success_color(ui.visuals()) } else { muted_color(ui.visuals()) };
+                ui.colored_label(status, "●");
+                let label = format!("{} {} ({})", node_icon(node.kind), node.label, node.detail);
+                if ui.link(label).clicked() {
+                    clicked = node.service.clone();
+                }
+                let targets: Vec<&str> = graph.edges.iter()
+                    .filter(|e| e.from == node.id)
+                    .map(|e| e.to.as_str())
+                    .collect();
+                if !targets.is_empty() {
+                    ui.label(format!("→ {}", targets.join(", ")));
+                }
+            });
+        }
+        clicked
+    }
+}
@@ -0,0 +1,103 @@
+use std::path::Path;
+
+use eframe::egui;
+
+use crate::core::dashboard::format_last_opened;
+use crate::core::journal::format_duration_ms;
+use crate::models::journal::{CommandJournal, Invocation, JournalAction, JournalStatus};
+use crate::ui::theme::{error_color, info_color, muted_color, success_color, warning_color};
+
+impl CommandJournal {
+    // Fila de favoritos encima de los controles de Lando; devuelve el que se pulse
+    pub fn show_favorites(&self, ui: &mut egui::Ui, project: &Path, busy: bool) -> Option<Invocation> {
+        let favorites = self.favorites(project);
+        if favorites.is_empty() {
+            return None;
+        }
+
+        let mut clicked = None;
+        ui.horizontal_wrapped(|ui| {
+            ui.label("⭐");
+            for invocation in favorites {
+                let label = invocation.label();
+                let short = label.strip_prefix("lando ").unwrap_or(&label);
+                if ui.add_enabled(!busy, egui::Button::new(short).small()).on_hover_text(&label).clicked() {
+                    clicked = Some(invocation.clone());
+                }
+            }
+        });
+        clicked
+    }
+
+    pub fn show(&mut self, ui: &mut egui::Ui, project: &Path, generation: u64, log_buffer: &[String]) -> Option<JournalAction> {
+        let mut action = None;
+        let mut toggled: Option<Invocation> = None;
+        let count = self.entries(project).len();
+        let header = if count == 0 { "🕘 Historial de comandos".to_string() } else { format!("🕘 Historial de comandos ({})", count) };
+
+        egui::CollapsingHeader::new(header)
+            .id_salt("command_journal")
+            .show(ui, |ui| {
+                if count == 0 {
+                    ui.colored_label(muted_color(ui.visuals()), "Todavía no se ha ejecutado ningún comando en este proyecto");
+                    return;
+                }
+
+                egui::ScrollArea::vertical()
+                    .id_salt("command_journal_scroll")
+                    .max_height(260.0)
+                    .show(ui, |ui| {
+                        for (index, entry) in self.entries(project).iter().enumerate().rev() {
+                            ui.push_id(index, |ui| {
+                                ui.horizontal(|ui| {
+                                    let (icon, color, hint) = match entry.status {
+                                        JournalStatus::Running => ("⏳", info_color(ui.visuals()), "En curso"),
+                                        JournalStatus::Success => ("✅", success_color(ui.visuals()), "Terminó bien"),
+                                        JournalStatus::Failed => ("❌", error_color(ui.visuals()), "Falló"),
+                                        JournalStatus::Interrupted => ("⚠️", warning_color(ui.visuals()), "Interrumpido al cerrar la app"),
+                                    };
+                                    ui.colored_label(color, icon).on_hover_text(hint);
+
+                                    let favorite = self.is_favorite(project, &entry.invocation);
+                                    let star = if favorite { "★" } else { "☆" };
+                                    let star_hint = if favorite { "Quitar de favoritos" } else { "Fijar en favoritos" };
+                                    if ui.small_button(star).on_hover_text(star_hint).clicked() {
+                                        toggled = Some(entry.invocation.clone());
+                                    }
+                                    if ui.small_button("🔁").on_hover_text("Volver a ejecutar").clicked() {
+                                        action = Some(JournalAction::Rerun(entry.invocation.clone()));
+                                    }
+                                    let label = entry.invocation.label();
+                                    if ui.small_button("📋").on_hover_text("Copiar").clicked() {
+                                        ui.ctx().copy_text(label.clone());
+                                    }
+
+                                    ui.monospace(&label);
+                                    if let Some(service) = entry.invocation.service() {
+                                        ui.colored_label(muted_color(ui.visuals()), format!("🎯 {}", service));
+                                    }
+                                    let mut details = format_last_opened(entry.started_at);
+                                    if let Some(duration) = entry.duration_ms {
+                                        details.push_str(&format!(" · {}", format_duration_ms(duration)));
+                                    }
+                                    ui.colored_label(muted_color(ui.visuals()), details);
+
+                                    if entry.status == JournalStatus::Failed {
+                                        if let Some(output) = CommandJournal::entry_output(entry, generation, log_buffer) {
+                                            if ui.link("📟 Ver salida").clicked() {
+                                                action = Some(JournalAction::ShowOutput { title: label.clone(), output });
+                                            }
+                                        }
+                                    }
+                                });
+                            });
+                        }
+                    });
+            });
+
+        if let Some(invocation) = toggled {
+            self.toggle_favorite(project, &invocation);
+        }
+        action
+    }
+}
@@ -0,0 +1,204 @@
+use eframe::egui;
+use eframe::egui::text::{LayoutJob, TextFormat};
+use serde_json::Value;
+
+use crate::core::json_tree::{build_json_tree, copy_value_text, count_matches, JsonKey, JsonTreeNode};
+use crate::ui::theme::{info_color, muted_color, success_color, warning_color};
+
+// Hijos que se pintan por nodo; el resto se resume para no congelar la UI
+// con respuestas enormes
+const JSON_VIEW_MAX_CHILDREN: usize = 200;
+// Niveles abiertos al mostrar un documento nuevo
+const JSON_VIEW_OPEN_DEPTH: usize = 2;
+// Con más documentos se abren plegados
+const JSON_VIEW_OPEN_DOCUMENTS: usize = 3;
+
+// Visor de JSON plegable con alternativa de texto formateado y botón de copiar.
+// `id_salt` distingue varios visores en la misma pantalla.
+pub fn show_json(ui: &mut egui::Ui, id_salt: &str, value: &Value, show_raw: &mut bool) {
+    let search = json_search(ui, id_salt);
+    let tree = build_json_tree(value, &search);
+    let matches = tree.as_ref().map(count_matches).unwrap_or(0);
+    show_toolbar(ui, id_salt, show_raw, search.as_str(), matches, || serde_json::to_string_pretty(value).unwrap_or_default());
+
+    egui::ScrollArea::vertical()
+        .id_salt((id_salt, "json_view"))
+        .max_height(400.0)
+        .auto_shrink([false, true])
+        .show(ui, |ui| {
+            if *show_raw {
+                let pretty = serde_json::to_string_pretty(value).unwrap_or_default();
+                ui.add(
+                    egui::TextEdit::multiline(&mut pretty.as_str())
+                        .code_editor()
+                        .desired_width(f32::INFINITY),
+                );
+            } else if let Some(tree) = &tree {
+                show_node(ui, egui::Id::new(id_salt), tree, 0, !search.is_empty());
+            } else {
+                ui.colored_label(muted_color(ui.visuals()), "Sin coincidencias");
+            }
+        });
+}
+
+// Varios documentos (resultados de MongoDB) con una búsqueda común a todos
+pub fn show_json_documents(ui: &mut egui::Ui, id_salt: &str, documents: &[(String, Value)], show_raw: &mut bool) {
+    let search = json_search(ui, id_salt);
+    let trees: Vec<(usize, JsonTreeNode)> = documents.iter()
+        .enumerate()
+        .filter_map(|(index, (_, document))| build_json_tree(document, &search).map(|tree| (index, tree)))
+        .collect();
+    let matches = trees.iter().map(|(_, tree)| count_matches(tree)).sum();
+    let pretty = || documents.iter()
+        .map(|(_, document)| serde_json::to_string_pretty(document).unwrap_or_default())
+        .collect::<Vec<_>>()
+        .join("\n");
+    show_toolbar(ui, id_salt, show_raw, search.as_str(), matches, pretty);
+
+    let searching = !search.is_empty();
+    let summary = if searching {
+        format!("{} de {} documentos", trees.len(), documents.len())
+    } else {
+        format!("{} documentos", documents.len())
+    };
+    ui.colored_label(muted_color(ui.visuals()), summary);
+
+    egui::ScrollArea::vertical()
+        .id_salt((id_salt, "json_documents"))
+        .max_height(400.0)
+        .auto_shrink([false, true])
+        .show(ui, |ui| {
+            if *show_raw {
+                let pretty = pretty();
+                ui.add(
+                    egui::TextEdit::multiline(&mut pretty.as_str())
+                        .code_editor()
+                        .desired_width(f32::INFINITY),
+                );
+                return;
+            }
+
+            for (index, tree) in &trees {
+                let label = documents.get(*index).map(|(label, _)| label.as_str()).unwrap_or_default();
+                let id = egui::Id::new((id_salt, "document", *index));
+                let response = egui::CollapsingHeader::new(format!("📄 {}", label))
+                    .id_salt(id)
+                    .default_open(documents.len() <= JSON_VIEW_OPEN_DOCUMENTS)
+                    .open(searching.then_some(true))
+                    .show(ui, |ui| show_children(ui, id, tree, 0, searching));
+                response.header_response.context_menu(|ui| node_menu(ui, tree));
+            }
+        });
+}
+
+// La búsqueda vive en la memoria de egui para que cualquier visor la tenga
+// sin guardar estado propio
+fn json_search(ui: &egui::Ui, id_salt: &str) -> String {
+    ui.data(|d| d.get_temp::<String>(egui::Id::new((id_salt, "json_search")))).unwrap_or_default()
+}
+
+fn show_toolbar(ui: &mut egui::Ui, id_salt: &str, show_raw: &mut bool, search: &str, matches: usize, pretty: impl Fn() -> String) {
+    ui.horizontal(|ui| {
+        ui.checkbox(show_raw, "Texto");
+        if ui.small_button("📋 Copiar").clicked() {
+            ui.ctx().copy_text(pretty());
+        }
+
+        ui.separator();
+        let mut input = search.to_string();
+        let response = ui.add(
+            egui::TextEdit::singleline(&mut input)
+                .hint_text("🔍 Buscar claves o valores")
+                .desired_width(180.0),
+        );
+        if response.changed() {
+            ui.data_mut(|d| d.insert_temp(egui::Id::new((id_salt, "json_search")), input.clone()));
+        }
+        if !input.is_empty() {
+            let color = if matches == 0 { warning_color(ui.visuals()) } else { muted_color(ui.visuals()) };
+            ui.colored_label(color, format!("{} coincidencias", matches));
+            if ui.small_button("✖").on_hover_text("Quitar la búsqueda").clicked() {
+                ui.data_mut(|d| d.remove::<String>(egui::Id::new((id_salt, "json_search"))));
+            }
+        }
+    });
+}
+
+fn show_node(ui: &mut egui::Ui, id: egui::Id, node: &JsonTreeNode, depth: usize, searching: bool) {
+    let summary = match node.value {
+        Value::Object(_) => format!("{{…}} {} campos", node.total_children),
+        Value::Array(_) => format!("[…] {} elementos", node.total_children),
+        _ => {
+            let label = node_label(ui.style(), node, leaf_value(node.value, ui.visuals()), searching);
+            let response = ui.add(egui::Label::new(label).sense(egui::Sense::click()));
+            response.context_menu(|ui| node_menu(ui, node));
+            return;
+        }
+    };
+
+    let header = node_label(ui.style(), node, (summary, muted_color(ui.visuals())), searching);
+    let response = egui::CollapsingHeader::new(header)
+        .id_salt(id.with(&node.path))
+        .default_open(depth < JSON_VIEW_OPEN_DEPTH)
+        .open(searching.then_some(true))
+        .show(ui, |ui| show_children(ui, id, node, depth, searching));
+    response.header_response.context_menu(|ui| node_menu(ui, node));
+}
+
+fn show_children(ui: &mut egui::Ui, id: egui::Id, node: &JsonTreeNode, depth: usize, searching: bool) {
+    for child in node.children.iter().take(JSON_VIEW_MAX_CHILDREN) {
+        show_node(ui, id, child, depth + 1, searching);
+    }
+    if node.children.len() > JSON_VIEW_MAX_CHILDREN {
+        ui.colored_label(
+            muted_color(ui.visuals()),
+            format!("… {} más (usa la vista de texto)", node.children.len() - JSON_VIEW_MAX_CHILDREN),
+        );
+    }
+}
+
+fn node_menu(ui: &mut egui::Ui, node: &JsonTreeNode) {
+    if !node.path.is_empty() && ui.button("📋 Copiar ruta").on_hover_text(&node.path).clicked() {
+        ui.ctx().copy_text(node.path.clone());
+        ui.close_menu();
+    }
+    if ui.button("📋 Copiar valor").clicked() {
+        ui.ctx().copy_text(copy_value_text(node.value));
+        ui.close_menu();
+    }
+}
+
+// Clave (o índice como distintivo) seguida del valor; lo que coincide con la búsqueda se resalta
+fn node_label(style: &egui::Style, node: &JsonTreeNode, (text, color): (String, egui::Color32), searching: bool) -> LayoutJob {
+    let visuals = &style.visuals;
+    let font = egui::TextStyle::Monospace.resolve(style);
+    let highlight = if searching && node.is_match { visuals.selection.bg_fill } else { egui::Color32::TRANSPARENT };
+    let mut job = LayoutJob::default();
+
+    match &node.key {
+        Some(JsonKey::Field(name)) => {
+            job.append(name, 0.0, TextFormat { font_id: font.clone(), color: visuals.strong_text_color(), background: highlight, ..Default::default() });
+            job.append(": ", 0.0, TextFormat { font_id: font.clone(), color: visuals.text_color(), ..Default::default() });
+        }
+        Some(JsonKey::Index(index)) => {
+            job.append(
+                &format!(" {} ", index),
+                0.0,
+                TextFormat { font_id: font.clone(), color: visuals.weak_text_color(), background: visuals.widgets.inactive.bg_fill, ..Default::default() },
+            );
+            job.append(" ", 0.0, TextFormat { font_id: font.clone(), ..Default::default() });
+        }
+        None => {}
+    }
+    job.append(&text, 0.0, TextFormat { font_id: font, color, background: highlight, ..Default::default() });
+    job
+}
+
+fn leaf_value(value: &Value, visuals: &egui::Visuals) -> (String, egui::Color32) {
+    match value {
+        Value::String(text) => (format!("\"{}\"", text), success_color(visuals)),
+        Value::Number(number) => (number.to_string(), info_color(visuals)),
+        Value::Bool(flag) => (flag.to_string(), warning_color(visuals)),
+        _ => ("null".to_string(), muted_color(visuals)),
+    }
+}
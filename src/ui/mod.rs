@@ -3,3 +3,26 @@ pub mod database;
 pub mod node;
 pub mod service;
 pub mod app;
+pub mod confirm;
+pub mod wizard;
+pub mod toasts;
+pub mod generic;
+pub mod theme;
+pub mod redis;
+pub mod mongo;
+pub mod shortcuts;
+
+pub mod graph;
+pub mod diagnostics;
+pub mod json_view;
+pub mod search;
+pub mod status_bar;
+pub mod result_grid;
+pub mod phases;
+pub mod notes;
+pub mod varnish;
+pub mod dashboard;
+pub mod secret;
+pub mod resources;
+pub mod journal;
+pub mod drift;
\ No newline at end of file
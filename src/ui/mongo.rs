@@ -0,0 +1,214 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::mpsc::Sender;
+
+use eframe::egui;
+use serde_json::Value;
+
+use crate::models::commands::LandoCommandOutcome;
+use crate::models::lando::LandoService;
+use crate::ui::json_view::show_json_documents;
+use crate::ui::theme::{error_color, muted_color};
+
+// Plantillas del editor; `{coll}` se sustituye por la colección seleccionada
+pub const MONGO_TEMPLATES: &[(&str, &str)] = &[
+    ("🗄️ show dbs", "show dbs"),
+    ("📚 show collections", "show collections"),
+    ("🔍 find", "db.{coll}.find().limit(10)"),
+    ("🔢 count", "db.{coll}.countDocuments()"),
+    ("📊 stats", "db.stats()"),
+];
+
+// Resultado de una evaluación: JSON formateado si mongosh devolvió JSON válido.
+// Los arrays se separan en documentos (etiqueta por `_id` y documento parseado).
+pub struct MongoResult {
+    pub command: String,
+    pub documents: Vec<(String, Value)>,
+    pub pretty: String,
+    pub error: Option<String>,
+}
+
+// Editor de documentos para servicios MongoDB: cada comando se evalúa con
+// `mongosh --eval` (o `mongo` en imágenes antiguas) dentro del contenedor
+pub struct MongoUI {
+    pub command_input: String,
+    pub database: String,
+    pub collections: Vec<String>,
+    pub history: Vec<String>,
+    pub result: Option<MongoResult>,
+    pub show_raw: bool, // Resultado como texto en vez de árbol
+    pub pending_requests: HashMap<u64, MongoPendingRequest>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MongoPendingRequest {
+    Eval { command: String },
+    Collections,
+}
+
+impl Default for MongoUI {
+    fn default() -> Self {
+        Self {
+            command_input: String::new(),
+            database: String::new(),
+            collections: Vec::new(),
+            history: Vec::new(),
+            result: None,
+            show_raw: false,
+            pending_requests: HashMap::new(),
+        }
+    }
+}
+
+impl MongoUI {
+    pub fn show(
+        &mut self,
+        ui: &mut egui::Ui,
+        service: &LandoService,
+        project_path: &PathBuf,
+        sender: &Sender<LandoCommandOutcome>,
+    ) {
+        if self.database.is_empty() {
+            self.database = service.creds.as_ref()
+                .and_then(|c| c.database.clone())
+                .filter(|d| !d.is_empty())
+                .unwrap_or_else(|| "test".to_string());
+        }
+
+        ui.collapsing(format!("🍃 MongoDB: {} ({})", service.service, service.r#type), |ui| {
+            ui.label(format!("📦 Versión: {}", service.version));
+            if let Some(conn) = &service.external_connection {
+                ui.label(format!("🌐 Conexión Externa: {}:{}", conn.host, conn.port));
+            }
+
+            ui.separator();
+            self.show_database_bar(ui, service, project_path, sender);
+
+            ui.separator();
+            self.show_editor(ui, service, project_path, sender);
+
+            ui.separator();
+            self.show_result(ui, service);
+        });
+    }
+
+    fn show_database_bar(
+        &mut self,
+        ui: &mut egui::Ui,
+        service: &LandoService,
+        project_path: &PathBuf,
+        sender: &Sender<LandoCommandOutcome>,
+    ) {
+        ui.horizontal(|ui| {
+            ui.label("💾 Base de datos:");
+            let response = ui.add(egui::TextEdit::singleline(&mut self.database).desired_width(140.0));
+            if response.lost_focus() {
+                self.collections.clear();
+            }
+
+            if ui.button("🔄 Colecciones").clicked() {
+                self.load_collections(service, project_path, sender);
+            }
+        });
+
+        if !self.collections.is_empty() {
+            ui.horizontal_wrapped(|ui| {
+                ui.label("📚");
+                for collection in self.collections.clone() {
+                    if ui.small_button(&collection).on_hover_text("Ver 10 documentos").clicked() {
+                        self.command_input = format!("db.{}.find().limit(10)", collection);
+                        self.run_command(service, project_path, sender);
+                    }
+                }
+            });
+        }
+    }
+
+    fn show_editor(
+        &mut self,
+        ui: &mut egui::Ui,
+        service: &LandoService,
+        project_path: &PathBuf,
+        sender: &Sender<LandoCommandOutcome>,
+    ) {
+        let is_running = !self.pending_requests.is_empty();
+
+        ui.horizontal_wrapped(|ui| {
+            let collection = self.collections.first().cloned().unwrap_or_else(|| "coleccion".to_string());
+            for (label, template) in MONGO_TEMPLATES {
+                if ui.small_button(*label).on_hover_text(*template).clicked() {
+                    self.command_input = template.replace("{coll}", &collection);
+                }
+            }
+
+            if !self.history.is_empty() {
+                egui::ComboBox::from_id_salt(("mongo_history", &service.service))
+                    .selected_text("🕘 Historial")
+                    .show_ui(ui, |ui| {
+                        for command in self.history.iter().rev() {
+                            if ui.selectable_label(false, command).clicked() {
+                                self.command_input = command.clone();
+                            }
+                        }
+                    });
+            }
+        });
+
+        let response = ui.add_enabled(
+            !is_running,
+            egui::TextEdit::multiline(&mut self.command_input)
+                .hint_text("db.users.find({ active: true }).limit(10)")
+                .code_editor()
+                .desired_rows(3)
+                .desired_width(f32::INFINITY)
+        );
+        let submitted = response.has_focus()
+            && ui.input(|i| i.modifiers.ctrl && i.key_pressed(egui::Key::Enter));
+
+        ui.horizontal(|ui| {
+            if is_running {
+                ui.spinner();
+                ui.label("Evaluando...");
+            } else if ui.button("▶️ Ejecutar").on_hover_text("Ctrl+Enter").clicked() || submitted {
+                self.run_command(service, project_path, sender);
+            }
+        });
+    }
+
+    fn show_result(&mut self, ui: &mut egui::Ui, service: &LandoService) {
+        let Some(result) = &self.result else {
+            ui.label(egui::RichText::new("Sin resultados todavía").color(muted_color(ui.visuals())));
+            return;
+        };
+
+        ui.horizontal(|ui| {
+            ui.strong(format!("> {}", result.command));
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if ui.small_button("📋").on_hover_text("Copiar resultado").clicked() {
+                    ui.ctx().copy_text(result.pretty.clone());
+                }
+            });
+        });
+
+        if let Some(error) = &result.error {
+            ui.colored_label(error_color(ui.visuals()), format!("❌ {}", error));
+            return;
+        }
+
+        if result.documents.is_empty() {
+            egui::ScrollArea::vertical()
+                .id_salt(("mongo_result", &service.service))
+                .max_height(320.0)
+                .show(ui, |ui| {
+                    ui.add(
+                        egui::TextEdit::multiline(&mut result.pretty.as_str())
+                            .code_editor()
+                            .desired_width(f32::INFINITY)
+                    );
+                });
+            return;
+        }
+
+        show_json_documents(ui, &format!("mongo_result_{}", service.service), &result.documents, &mut self.show_raw);
+    }
+}
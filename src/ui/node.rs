@@ -1,12 +1,17 @@
+use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::mpsc::Sender;
+use std::time::Instant;
 
 use eframe::egui;
-use egui_term::TerminalBackend;
+use crate::models::terminal::LazyTerminal;
 
 use crate::models::commands::LandoCommandOutcome;
 use crate::core::commands::*;
+use crate::core::node::{detect_ecosystem_files, LOGS_REFRESH_INTERVAL};
 use crate::models::lando::LandoService;
+use crate::ui::shortcuts::{consume_tab_shortcut, tab_shortcut_hint};
+use crate::ui::theme::{error_color, icon_label, info_color, muted_color, success_color, warning_color};
 
 pub struct NodeUI {
     pub command_input: String,
@@ -19,6 +24,9 @@ pub struct NodeUI {
     pub available_scripts: Vec<String>,
     pub logs_output: String,
     pub debug_port: String,
+    pub debug_entry: String,
+    pub debug_urls: Vec<String>,
+    pub debug_warning: Option<String>,
     pub current_tab: NodeTab,
     pub node_version: String,
     pub npm_version: String,
@@ -28,6 +36,89 @@ pub struct NodeUI {
     pub show_global_packages: bool,
     pub environment_mode: EnvironmentMode,
     pub pm2_processes: Vec<PM2Process>,
+    pub pm2_start_target: String, // Ecosystem file o script para `pm2 start`
+    pub ecosystem_files: Option<Vec<String>>, // None: sin buscar todavía en el proyecto
+    pub needs_pm2_refresh: bool,
+    pub pm2_log_target: Option<String>, // None: los logs de todos los procesos
+    pub logs_source: Option<NodeLogSource>, // Lo último mostrado, para "Actualizar Logs"
+    pub logs_auto_refresh: bool,
+    pub logs_refreshed_at: Option<Instant>,
+    pub pending_requests: HashMap<u64, NodePendingRequest>,
+    pub needs_packages_refresh: bool, // Se pide en el próximo frame, cuando hay servicio y sender
+    pub coverage_summary: Option<CoverageSummary>,
+    pub npm_config_scope: NpmConfigScope,
+    pub npm_config: Option<Vec<NpmConfigEntry>>, // None: `npm config list` sin lanzar todavía
+    pub npmrc_editor: Option<NpmrcEditor>,
+    pub needs_npm_config_refresh: bool,
+    pub package_manager: PackageManager,
+    pub package_manager_checked: bool, // Ya se buscaron los lockfiles en el servicio
+    pub package_manager_manual: bool,  // Elegido en el selector: la detección no lo cambia
+    pub package_manager_hint: Option<String>, // Por qué se eligió (lockfile o `packageManager`)
+}
+
+// Gestor de paquetes con el que se traducen scripts, instalaciones y listados
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum PackageManager {
+    #[default]
+    Npm,
+    Yarn,
+    Pnpm,
+}
+
+// Qué .npmrc se lista y se edita en la pestaña de entorno
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NpmConfigScope {
+    Project, // .npmrc en la raíz del proyecto
+    Global,  // `npm config get globalconfig` dentro del contenedor
+}
+
+// Una línea `clave = valor` de `npm config list` y la sección de la que viene
+#[derive(Debug, Clone, PartialEq)]
+pub struct NpmConfigEntry {
+    pub source: String, // "project", "user", "global", "cli"...
+    pub key: String,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NpmrcEditor {
+    pub scope: NpmConfigScope,
+    pub path: String, // Ruta en el host (proyecto) o en el contenedor (global)
+    pub content: String,
+    pub original: String,
+}
+
+// Porcentajes del resumen de cobertura de Jest/Istanbul
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CoverageSummary {
+    pub statements: Option<f32>,
+    pub branches: Option<f32>,
+    pub functions: Option<f32>,
+    pub lines: Option<f32>,
+}
+
+// Qué esperaba cada petición de `run_shell_capture_tagged` lanzada desde NodeUI
+#[derive(Debug, Clone, PartialEq)]
+pub enum NodePendingRequest {
+    ListPackages { global: bool, manager: PackageManager },
+    OutdatedPackages { manager: PackageManager },
+    DetectPackageManager,
+    PackageChange { command: String },
+    SearchPackage { query: String },
+    Coverage { command: String },
+    Pm2List,
+    Pm2Command { command: String },
+    Logs { command: String },
+    NpmConfigList,
+    NpmrcRead,
+    NpmrcWrite { path: String },
+}
+
+// De dónde salen los logs de la pestaña Logs
+#[derive(Debug, Clone, PartialEq)]
+pub enum NodeLogSource {
+    Pm2(Option<String>),
+    Npm,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -94,6 +185,9 @@ impl Default for NodeUI {
             ],
             logs_output: String::new(),
             debug_port: "9229".to_string(),
+            debug_entry: ".".to_string(),
+            debug_urls: Vec::new(),
+            debug_warning: None,
             current_tab: NodeTab::Scripts,
             node_version: "N/A".to_string(),
             npm_version: "N/A".to_string(),
@@ -103,6 +197,24 @@ impl Default for NodeUI {
             show_global_packages: false,
             environment_mode: EnvironmentMode::Development,
             pm2_processes: Vec::new(),
+            pm2_start_target: "ecosystem.config.js".to_string(),
+            ecosystem_files: None,
+            needs_pm2_refresh: false,
+            pm2_log_target: None,
+            logs_source: None,
+            logs_auto_refresh: false,
+            logs_refreshed_at: None,
+            pending_requests: HashMap::new(),
+            needs_packages_refresh: false,
+            coverage_summary: None,
+            npm_config_scope: NpmConfigScope::Project,
+            npm_config: None,
+            npmrc_editor: None,
+            needs_npm_config_refresh: false,
+            package_manager: PackageManager::Npm,
+            package_manager_checked: false,
+            package_manager_manual: false,
+            package_manager_hint: None,
         }
     }
 }
@@ -115,8 +227,25 @@ impl NodeUI {
         project_path: &PathBuf,
         sender: &Sender<LandoCommandOutcome>,
         is_loading: &mut bool,
-        terminal: &mut TerminalBackend,
+        terminal: &mut LazyTerminal,
     ) {
+        if self.needs_packages_refresh {
+            self.needs_packages_refresh = false;
+            self.refresh_packages_list(service, project_path, sender, is_loading);
+        }
+        if self.needs_pm2_refresh {
+            self.needs_pm2_refresh = false;
+            self.refresh_pm2_processes(service, project_path, sender, is_loading);
+        }
+        if !self.package_manager_checked {
+            self.package_manager_checked = true;
+            self.detect_package_manager(service, project_path, sender);
+        }
+        if self.needs_npm_config_refresh {
+            self.needs_npm_config_refresh = false;
+            self.show_npm_config(service, project_path, sender, is_loading);
+        }
+
         ui.collapsing(format!("️ Node.js: {} ({})", service.service, service.r#type), |ui| {
             // Información del servicio
             self.show_service_header(ui, service);
@@ -162,6 +291,31 @@ impl NodeUI {
                 ui.label(format!("📦 Versión: {}", service.version));
                 ui.label(format!("🟢 Node: {}", self.node_version));
                 ui.label(format!("📦 NPM: {}", self.npm_version));
+                ui.horizontal(|ui| {
+                    ui.label("🧶 Gestor:");
+                    let previous = self.package_manager;
+                    egui::ComboBox::from_id_salt(("package_manager", &service.service))
+                        .selected_text(self.package_manager.binary())
+                        .show_ui(ui, |ui| {
+                            for manager in PackageManager::ALL {
+                                ui.selectable_value(&mut self.package_manager, manager, manager.binary());
+                            }
+                        });
+                    if self.package_manager != previous {
+                        self.package_manager_manual = true;
+                        self.package_manager_hint = None;
+                        self.needs_packages_refresh = !self.installed_packages.is_empty();
+                    }
+                    match &self.package_manager_hint {
+                        Some(hint) => {
+                            ui.colored_label(muted_color(ui.visuals()), format!("(detectado por {})", hint));
+                        }
+                        None if self.package_manager_manual => {
+                            ui.colored_label(muted_color(ui.visuals()), "(elegido a mano)");
+                        }
+                        None => {}
+                    }
+                });
             });
 
             ui.separator();
@@ -182,13 +336,24 @@ impl NodeUI {
     }
 
     fn show_tab_navigation(&mut self, ui: &mut egui::Ui) {
+        let tabs = [
+            (NodeTab::Scripts, "🚀 Scripts"),
+            (NodeTab::Packages, "📦 Packages"),
+            (NodeTab::Debug, "🐛 Debug"),
+            (NodeTab::Environment, "🌍 Env"),
+            (NodeTab::PM2, "⚡ PM2"),
+            (NodeTab::Logs, "📜 Logs"),
+        ];
+
+        if let Some(index) = consume_tab_shortcut(ui, tabs.len()) {
+            self.current_tab = tabs[index].0.clone();
+        }
+
         ui.horizontal(|ui| {
-            ui.selectable_value(&mut self.current_tab, NodeTab::Scripts, "🚀 Scripts");
-            ui.selectable_value(&mut self.current_tab, NodeTab::Packages, "📦 Packages");
-            ui.selectable_value(&mut self.current_tab, NodeTab::Debug, "🐛 Debug");
-            ui.selectable_value(&mut self.current_tab, NodeTab::Environment, "🌍 Env");
-            ui.selectable_value(&mut self.current_tab, NodeTab::PM2, "⚡ PM2");
-            ui.selectable_value(&mut self.current_tab, NodeTab::Logs, "📜 Logs");
+            for (index, (tab, label)) in tabs.into_iter().enumerate() {
+                ui.selectable_value(&mut self.current_tab, tab, label)
+                    .on_hover_text(tab_shortcut_hint(index));
+            }
         });
     }
 
@@ -223,7 +388,7 @@ impl NodeUI {
             ui.label("Ejecutar Script Personalizado:");
             
             ui.horizontal(|ui| {
-                ui.label("npm run");
+                ui.label(format!("{} run", self.package_manager.binary()));
                 ui.text_edit_singleline(&mut self.script_name);
                 
                 let btn = ui.add_enabled(!*is_loading, egui::Button::new("▶️ Ejecutar"));
@@ -238,9 +403,10 @@ impl NodeUI {
 
         // Comandos NPM comunes
         ui.group(|ui| {
-            ui.label("Comandos NPM:");
-            
+            ui.label(format!("Comandos {}:", self.package_manager.binary()));
+
             ui.horizontal(|ui| {
+                ui.label(self.package_manager.binary());
                 ui.text_edit_singleline(&mut self.npm_command_input);
                 
                 let btn = ui.add_enabled(!*is_loading, egui::Button::new("▶️ Ejecutar"));
@@ -325,7 +491,7 @@ impl NodeUI {
                     self.install_package(service, project_path, sender, is_loading);
                 }
                 
-                if ui.button("🔍 Buscar en NPM").clicked() {
+                if ui.add_enabled(!*is_loading, egui::Button::new("🔍 Buscar en NPM")).clicked() {
                     self.search_package(service, project_path, sender, is_loading);
                 }
             });
@@ -352,14 +518,22 @@ impl NodeUI {
             egui::ScrollArea::vertical()
                 .max_height(300.0)
                 .show(ui, |ui| {
+                    if self.installed_packages.is_empty() {
+                        ui.label("💭 Sin paquetes cargados. Usa '🔄 Actualizar Lista'");
+                    }
+
                     for package in &self.installed_packages.clone() {
+                        if package.is_dev_dependency && !self.show_dev_dependencies {
+                            continue;
+                        }
+
                         ui.horizontal(|ui| {
                             let color = if package.is_outdated {
-                                egui::Color32::YELLOW
+                                warning_color(ui.visuals())
                             } else if package.is_dev_dependency {
-                                egui::Color32::LIGHT_BLUE
+                                info_color(ui.visuals())
                             } else {
-                                egui::Color32::WHITE
+                                ui.visuals().text_color()
                             };
                             
                             ui.colored_label(color, format!("📦 {}", package.name));
@@ -401,7 +575,11 @@ impl NodeUI {
             
             ui.horizontal(|ui| {
                 ui.label("Puerto de debug:");
-                ui.text_edit_singleline(&mut self.debug_port);
+                ui.add(egui::TextEdit::singleline(&mut self.debug_port).desired_width(60.0));
+
+                ui.label("Entrada:");
+                ui.add(egui::TextEdit::singleline(&mut self.debug_entry).desired_width(120.0))
+                    .on_hover_text("Script que recibe node ('.' usa el campo main de package.json)");
                 
                 ui.label("Modo:");
                 egui::ComboBox::from_label("")
@@ -443,31 +621,80 @@ impl NodeUI {
             ui.label("Herramientas de Desarrollo:");
             
             ui.horizontal_wrapped(|ui| {
-                if ui.button("🔧 ESLint").clicked() {
-                    self.run_eslint(service, project_path, sender, is_loading);
-                }
-                
-                if ui.button("🎨 Prettier").clicked() {
-                    self.run_prettier(service, project_path, sender, is_loading);
-                }
-                
-                if ui.button("🧪 Jest").clicked() {
-                    self.run_tests(service, project_path, sender, is_loading);
-                }
-                
-                if ui.button("📈 Coverage").clicked() {
-                    self.run_coverage(service, project_path, sender, is_loading);
-                }
+                ui.add_enabled_ui(!*is_loading, |ui| {
+                    if ui.button("🔧 ESLint").on_hover_text("npx eslint .").clicked() {
+                        self.run_eslint(service, project_path, sender, is_loading);
+                    }
+
+                    if ui.button("🎨 Prettier").on_hover_text("npx prettier --check .").clicked() {
+                        self.run_prettier(service, project_path, sender, is_loading, false);
+                    }
+
+                    if ui.button("🎨 Prettier --write").on_hover_text("Formatea los archivos en el sitio").clicked() {
+                        self.run_prettier(service, project_path, sender, is_loading, true);
+                    }
+
+                    if ui.button("🧪 Jest").on_hover_text("npm test").clicked() {
+                        self.run_tests(service, project_path, sender, is_loading);
+                    }
+
+                    if ui.button("📈 Coverage").clicked() {
+                        self.run_coverage(service, project_path, sender, is_loading);
+                    }
+                });
             });
+
+            if let Some(summary) = &self.coverage_summary {
+                ui.separator();
+                ui.label("Cobertura:");
+                egui::Grid::new("coverage_summary_grid").striped(true).show(ui, |ui| {
+                    for (label, value) in [
+                        ("Sentencias", summary.statements),
+                        ("Ramas", summary.branches),
+                        ("Funciones", summary.functions),
+                        ("Líneas", summary.lines),
+                    ] {
+                        ui.label(label);
+                        match value {
+                            Some(pct) => {
+                                let color = if pct >= 80.0 {
+                                    success_color(ui.visuals())
+                                } else if pct >= 50.0 {
+                                    warning_color(ui.visuals())
+                                } else {
+                                    error_color(ui.visuals())
+                                };
+                                ui.colored_label(color, format!("{:.2}%", pct));
+                            }
+                            None => {
+                                ui.label("—");
+                            }
+                        }
+                        ui.end_row();
+                    }
+                });
+            }
         });
 
         ui.separator();
 
         // Información de debug
+        if let Some(warning) = &self.debug_warning {
+            ui.colored_label(warning_color(ui.visuals()), format!("⚠️ {}", warning));
+        }
+
         ui.collapsing("🔍 Información de Debug", |ui| {
             ui.label("URL de Inspector:");
-            ui.code(format!("chrome://inspect/#devices"));
+            ui.code("chrome://inspect/#devices");
             ui.label(format!("Puerto: {}", self.debug_port));
+            for url in &self.debug_urls {
+                ui.horizontal(|ui| {
+                    ui.code(url);
+                    if ui.small_button("📋").on_hover_text("Copiar").clicked() {
+                        ui.ctx().copy_text(url.clone());
+                    }
+                });
+            }
         });
     }
 
@@ -525,20 +752,62 @@ impl NodeUI {
         // Configuración de NPM
         ui.group(|ui| {
             ui.label("Configuración NPM:");
-            
+
             ui.horizontal(|ui| {
-                if ui.button("📋 npm config list").clicked() {
+                let editing = self.npmrc_editor.is_some();
+                ui.add_enabled_ui(!editing, |ui| {
+                    ui.selectable_value(&mut self.npm_config_scope, NpmConfigScope::Project, "📁 Proyecto")
+                        .on_hover_text(".npmrc en la raíz del proyecto");
+                    ui.selectable_value(&mut self.npm_config_scope, NpmConfigScope::Global, "🌐 Global")
+                        .on_hover_text("npmrc global del contenedor");
+                });
+                ui.separator();
+
+                if ui.add_enabled(!*is_loading, egui::Button::new("📋 npm config list")).clicked() {
                     self.show_npm_config(service, project_path, sender, is_loading);
                 }
-                
-                if ui.button("🔧 npm config edit").clicked() {
+
+                if ui.add_enabled(!*is_loading && !editing, egui::Button::new("🔧 npm config edit")).clicked() {
                     self.edit_npm_config(service, project_path, sender, is_loading);
                 }
             });
+
+            self.show_npm_config_table(ui);
+            self.show_npmrc_editor(ui, service, project_path, sender, is_loading);
         });
     }
 
-    fn show_pm2_panel(
+    fn show_npm_config_table(&self, ui: &mut egui::Ui) {
+        let Some(entries) = &self.npm_config else {
+            return;
+        };
+        let source = match self.npm_config_scope {
+            NpmConfigScope::Project => "project",
+            NpmConfigScope::Global => "global",
+        };
+        let entries: Vec<&NpmConfigEntry> = entries.iter().filter(|e| e.source == source).collect();
+
+        ui.add_space(4.0);
+        if entries.is_empty() {
+            ui.colored_label(muted_color(ui.visuals()), format!("Sin valores en la configuración \"{}\"", source));
+            return;
+        }
+
+        egui::ScrollArea::vertical().id_salt("npm_config_table").max_height(200.0).show(ui, |ui| {
+            egui::Grid::new("npm_config_grid").striped(true).num_columns(2).show(ui, |ui| {
+                ui.strong("Clave");
+                ui.strong("Valor");
+                ui.end_row();
+                for entry in entries {
+                    ui.monospace(&entry.key);
+                    ui.monospace(&entry.value);
+                    ui.end_row();
+                }
+            });
+        });
+    }
+
+    fn show_npmrc_editor(
         &mut self,
         ui: &mut egui::Ui,
         service: &LandoService,
@@ -546,27 +815,96 @@ impl NodeUI {
         sender: &Sender<LandoCommandOutcome>,
         is_loading: &mut bool,
     ) {
-        ui.heading("⚡ Gestión PM2");
+        let Some(editor) = self.npmrc_editor.as_mut() else {
+            return;
+        };
 
-        // Controles PM2
+        ui.separator();
+        ui.label(format!("✏️ {}", editor.path));
+        ui.add(
+            egui::TextEdit::multiline(&mut editor.content)
+                .code_editor()
+                .desired_rows(8)
+                .desired_width(f32::INFINITY)
+                .hint_text("registry=https://registry.npmjs.org/"),
+        );
+
+        let changed = editor.content != editor.original;
+        let mut save = false;
+        let mut cancel = false;
         ui.horizontal(|ui| {
-            if ui.button("🔄 Actualizar Lista").clicked() {
-                self.refresh_pm2_processes(service, project_path, sender, is_loading);
+            if ui.add_enabled(changed && !*is_loading, egui::Button::new("💾 Guardar")).clicked() {
+                save = true;
             }
-            
-            if ui.button("🚀 PM2 Start").clicked() {
-                self.pm2_start(service, project_path, sender, is_loading);
+            if ui.button("❌ Cancelar").clicked() {
+                cancel = true;
             }
-            
-            if ui.button("⏹️ PM2 Stop All").clicked() {
-                self.pm2_stop_all(service, project_path, sender, is_loading);
+            if changed {
+                ui.colored_label(warning_color(ui.visuals()), "Cambios sin guardar");
             }
-            
-            if ui.button("🔄 PM2 Restart All").clicked() {
-                self.pm2_restart_all(service, project_path, sender, is_loading);
+        });
+
+        if save {
+            self.save_npmrc(service, project_path, sender, is_loading);
+        } else if cancel {
+            self.npmrc_editor = None;
+        }
+    }
+
+    fn show_pm2_panel(
+        &mut self,
+        ui: &mut egui::Ui,
+        service: &LandoService,
+        project_path: &PathBuf,
+        sender: &Sender<LandoCommandOutcome>,
+        is_loading: &mut bool,
+    ) {
+        ui.heading("⚡ Gestión PM2");
+
+        let ecosystem_files = self.ecosystem_files
+            .get_or_insert_with(|| detect_ecosystem_files(project_path))
+            .clone();
+
+        // Qué arranca `pm2 start`
+        ui.horizontal(|ui| {
+            ui.label("Arrancar:");
+            ui.add(egui::TextEdit::singleline(&mut self.pm2_start_target)
+                .hint_text("ecosystem.config.js o script")
+                .desired_width(200.0));
+            if !ecosystem_files.is_empty() {
+                egui::ComboBox::from_id_salt("pm2_ecosystem_files")
+                    .selected_text("📄 Ecosystem")
+                    .show_ui(ui, |ui| {
+                        for file in &ecosystem_files {
+                            ui.selectable_value(&mut self.pm2_start_target, file.clone(), file);
+                        }
+                    });
+            } else {
+                ui.colored_label(muted_color(ui.visuals()), "Sin ecosystem file en el proyecto");
             }
         });
 
+        // Controles PM2
+        ui.horizontal(|ui| {
+            ui.add_enabled_ui(!*is_loading, |ui| {
+                if ui.button("🔄 Actualizar Lista").clicked() {
+                    self.refresh_pm2_processes(service, project_path, sender, is_loading);
+                }
+
+                if ui.button("🚀 PM2 Start").on_hover_text(format!("pm2 start {}", self.pm2_start_target.trim())).clicked() {
+                    self.pm2_start(service, project_path, sender, is_loading);
+                }
+
+                if ui.button("⏹️ PM2 Stop All").clicked() {
+                    self.pm2_stop_all(service, project_path, sender, is_loading);
+                }
+
+                if ui.button("🔄 PM2 Restart All").clicked() {
+                    self.pm2_restart_all(service, project_path, sender, is_loading);
+                }
+            });
+        });
+
         ui.separator();
 
         // Lista de procesos PM2
@@ -578,10 +916,10 @@ impl NodeUI {
                         ui.group(|ui| {
                             ui.horizontal(|ui| {
                                 let status_color = match process.status.as_str() {
-                                    "online" => egui::Color32::GREEN,
-                                    "stopped" => egui::Color32::RED,
-                                    "error" => egui::Color32::RED,
-                                    _ => egui::Color32::GRAY,
+                                    "online" => success_color(ui.visuals()),
+                                    "stopped" => error_color(ui.visuals()),
+                                    "error" => error_color(ui.visuals()),
+                                    _ => muted_color(ui.visuals()),
                                 };
                                 
                                 ui.colored_label(status_color, format!("● {}", process.name));
@@ -591,13 +929,13 @@ impl NodeUI {
                                 ui.label(format!("Uptime: {}", process.uptime));
                                 
                                 ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                                    if ui.small_button("🗑️").clicked() {
+                                    if icon_label(ui.small_button("🗑️"), "Eliminar proceso").clicked() {
                                         self.pm2_delete_process(service, project_path, sender, is_loading, &process.name);
                                     }
-                                    if ui.small_button("⏹️").clicked() {
+                                    if icon_label(ui.small_button("⏹️"), "Detener proceso").clicked() {
                                         self.pm2_stop_process(service, project_path, sender, is_loading, &process.name);
                                     }
-                                    if ui.small_button("🔄").clicked() {
+                                    if icon_label(ui.small_button("🔄"), "Reiniciar proceso").clicked() {
                                         self.pm2_restart_process(service, project_path, sender, is_loading, &process.name);
                                     }
                                 });
@@ -620,20 +958,45 @@ impl NodeUI {
     ) {
         ui.heading("📜 Logs de Node.js");
 
+        if self.logs_auto_refresh && self.logs_refreshed_at.is_some_and(|t| t.elapsed() >= LOGS_REFRESH_INTERVAL) {
+            self.refresh_logs(service, project_path, sender, is_loading);
+        }
+        if self.logs_auto_refresh {
+            ui.ctx().request_repaint_after(LOGS_REFRESH_INTERVAL);
+        }
+
         // Controles de logs
         ui.horizontal(|ui| {
             if ui.button("🔄 Actualizar Logs").clicked() {
                 self.refresh_logs(service, project_path, sender, is_loading);
             }
-            
-            if ui.button("📜 NPM Logs").clicked() {
+
+            if ui.button("📜 NPM Logs").on_hover_text("Último log de depuración de ~/.npm/_logs").clicked() {
                 self.show_npm_logs(service, project_path, sender, is_loading);
             }
-            
+
+            let target_label = self.pm2_log_target.clone().unwrap_or_else(|| "Todos los procesos".to_string());
+            egui::ComboBox::from_id_salt("pm2_log_target")
+                .selected_text(target_label)
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut self.pm2_log_target, None, "Todos los procesos");
+                    for process in &self.pm2_processes {
+                        ui.selectable_value(&mut self.pm2_log_target, Some(process.name.clone()), &process.name);
+                    }
+                });
+            if self.pm2_processes.is_empty()
+                && ui.small_button("🔄").on_hover_text("Cargar la lista de procesos PM2").clicked()
+            {
+                self.refresh_pm2_processes(service, project_path, sender, is_loading);
+            }
+
             if ui.button("⚡ PM2 Logs").clicked() {
                 self.show_pm2_logs(service, project_path, sender, is_loading);
             }
-            
+
+            ui.add_enabled(self.logs_source.is_some(), egui::Checkbox::new(&mut self.logs_auto_refresh, "Auto"))
+                .on_hover_text(format!("Volver a leer cada {} s", LOGS_REFRESH_INTERVAL.as_secs()));
+
             if ui.button("🗑️ Limpiar").clicked() {
                 self.logs_output.clear();
             }
@@ -654,7 +1017,7 @@ impl NodeUI {
             });
     }
 
-    fn show_terminal_section(&mut self, ui: &mut egui::Ui, terminal: &mut TerminalBackend) {
+    fn show_terminal_section(&mut self, ui: &mut egui::Ui, terminal: &mut LazyTerminal) {
         ui.collapsing("💻 Terminal Node.js", |ui| {
             ui.label("Terminal integrado para Node.js:");
             // Placeholder para el terminal
@@ -0,0 +1,215 @@
+use std::path::PathBuf;
+
+use eframe::egui;
+
+use crate::core::notes::search_notes;
+use crate::models::notes::{NoteMatch, NotesLocation};
+use crate::ui::theme::muted_color;
+
+// Trozo de una línea con su formato en línea
+enum Inline<'a> {
+    Text(&'a str),
+    Bold(&'a str),
+    Code(&'a str),
+    Link(&'a str, &'a str),
+}
+
+// `**negrita**`, `` `código` `` y `[texto](url)`; lo que no cierra se deja como texto
+fn parse_inline(line: &str) -> Vec<Inline<'_>> {
+    let mut parts = Vec::new();
+    let mut rest = line;
+    while !rest.is_empty() {
+        let next = [rest.find("**"), rest.find('`'), rest.find('[')]
+            .into_iter()
+            .flatten()
+            .min();
+        let Some(start) = next else {
+            parts.push(Inline::Text(rest));
+            break;
+        };
+        if start > 0 {
+            parts.push(Inline::Text(&rest[..start]));
+            rest = &rest[start..];
+        }
+
+        let parsed = if let Some(body) = rest.strip_prefix("**") {
+            body.find("**").map(|end| (Inline::Bold(&body[..end]), end + 4))
+        } else if let Some(body) = rest.strip_prefix('`') {
+            body.find('`').map(|end| (Inline::Code(&body[..end]), end + 2))
+        } else {
+            rest.find("](").filter(|mid| !rest[1..*mid].contains(']')).and_then(|mid| {
+                let url_end = rest[mid + 2..].find(')')? + mid + 2;
+                Some((Inline::Link(&rest[1..mid], &rest[mid + 2..url_end]), url_end + 1))
+            })
+        };
+        match parsed {
+            Some((part, len)) => {
+                parts.push(part);
+                rest = &rest[len..];
+            }
+            None => {
+                // Marca sin cerrar: se pinta el primer carácter y se sigue
+                let len = rest.chars().next().map(char::len_utf8).unwrap_or(1);
+                parts.push(Inline::Text(&rest[..len]));
+                rest = &rest[len..];
+            }
+        }
+    }
+    parts
+}
+
+fn show_inline(ui: &mut egui::Ui, line: &str, size: Option<f32>) {
+    ui.horizontal_wrapped(|ui| {
+        ui.spacing_mut().item_spacing.x = 0.0;
+        for part in parse_inline(line) {
+            let sized = |text: egui::RichText| match size {
+                Some(size) => text.size(size).strong(),
+                None => text,
+            };
+            match part {
+                Inline::Text(text) => {
+                    ui.label(sized(egui::RichText::new(text)));
+                }
+                Inline::Bold(text) => {
+                    ui.label(sized(egui::RichText::new(text).strong()));
+                }
+                Inline::Code(text) => {
+                    ui.label(sized(egui::RichText::new(text).code()));
+                }
+                Inline::Link(text, url) => {
+                    // Los enlaces se abren en el navegador del sistema
+                    ui.hyperlink_to(sized(egui::RichText::new(text)), url);
+                }
+            }
+        }
+    });
+}
+
+// Markdown básico: títulos, listas, bloques de código y formato en línea
+pub fn show_markdown(ui: &mut egui::Ui, text: &str) {
+    let mut in_code = false;
+    for line in text.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") {
+            in_code = !in_code;
+            continue;
+        }
+        if in_code {
+            ui.label(egui::RichText::new(line).code());
+            continue;
+        }
+        if trimmed.is_empty() {
+            ui.add_space(6.0);
+            continue;
+        }
+
+        let heading = trimmed.chars().take_while(|c| *c == '#').count();
+        if (1..=3).contains(&heading) && trimmed[heading..].starts_with(' ') {
+            let size = [22.0, 18.0, 15.0][heading - 1];
+            show_inline(ui, trimmed[heading..].trim(), Some(size));
+            continue;
+        }
+
+        let indent = (line.len() - trimmed.len()) as f32 * 4.0;
+        let bullet = trimmed.strip_prefix("- ").or_else(|| trimmed.strip_prefix("* "));
+        let numbered = trimmed.split_once(". ")
+            .filter(|(n, _)| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()));
+        match (bullet, numbered) {
+            (Some(item), _) => {
+                ui.horizontal(|ui| {
+                    ui.add_space(indent);
+                    ui.label("•");
+                    show_inline(ui, item, None);
+                });
+            }
+            (None, Some((number, item))) => {
+                ui.horizontal(|ui| {
+                    ui.add_space(indent);
+                    ui.label(format!("{}.", number));
+                    show_inline(ui, item, None);
+                });
+            }
+            (None, None) => show_inline(ui, trimmed, None),
+        }
+    }
+}
+
+// Buscador global (Ctrl+K) sobre las notas de todos los proyectos
+#[derive(Default)]
+pub struct NotesSearch {
+    pub open: bool,
+    pub query: String,
+    pub results: Vec<NoteMatch>,
+    searched: Option<String>, // Búsqueda a la que corresponden `results`
+    focus_pending: bool,
+}
+
+impl NotesSearch {
+    pub fn toggle(&mut self) {
+        self.open = !self.open;
+        self.focus_pending = self.open;
+    }
+
+    // Devuelve el proyecto elegido
+    pub fn show(&mut self, ctx: &egui::Context, projects: &[PathBuf], location: NotesLocation) -> Option<PathBuf> {
+        if !self.open {
+            return None;
+        }
+
+        let mut chosen = None;
+        let mut open = true;
+        egui::Window::new("🔎 Buscar en las notas")
+            .open(&mut open)
+            .collapsible(false)
+            .default_width(480.0)
+            .anchor(egui::Align2::CENTER_TOP, [0.0, 60.0])
+            .show(ctx, |ui| {
+                let response = ui.add(
+                    egui::TextEdit::singleline(&mut self.query)
+                        .hint_text("p. ej. stripe test key")
+                        .desired_width(f32::INFINITY),
+                );
+                if self.focus_pending {
+                    response.request_focus();
+                    self.focus_pending = false;
+                }
+                // Se lee del disco solo cuando cambia el texto
+                if self.searched.as_deref() != Some(self.query.as_str()) {
+                    self.results = search_notes(projects, location, &self.query);
+                    self.searched = Some(self.query.clone());
+                }
+                let enter = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                if enter {
+                    chosen = self.results.first().map(|m| m.project.clone());
+                }
+
+                ui.separator();
+                if self.query.trim().is_empty() {
+                    ui.colored_label(muted_color(ui.visuals()), "Escribe para buscar en las notas de todos los proyectos");
+                    return;
+                }
+                if self.results.is_empty() {
+                    ui.colored_label(muted_color(ui.visuals()), "Sin coincidencias");
+                    return;
+                }
+                egui::ScrollArea::vertical().max_height(320.0).show(ui, |ui| {
+                    for result in &self.results {
+                        let name = result.project.file_name().unwrap_or_default().to_string_lossy();
+                        let text = format!("📝 {} · {}: {}", name, result.line_number, result.line);
+                        if ui.add(egui::Button::new(text).truncate().frame(false))
+                            .on_hover_text(result.project.display().to_string())
+                            .clicked()
+                        {
+                            chosen = Some(result.project.clone());
+                        }
+                    }
+                });
+            });
+
+        if chosen.is_some() || ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+            open = false;
+        }
+        self.open = open;
+        chosen
+    }
+}
@@ -0,0 +1,51 @@
+use eframe::egui;
+
+use crate::models::phases::{PhaseState, PhaseTracker};
+use crate::ui::theme::{error_color, muted_color, success_color};
+
+// Lista de pasos del start/rebuild/destroy en curso, junto al terminal
+pub fn show_phase_list(ui: &mut egui::Ui, tracker: &PhaseTracker) {
+    let (done, total) = tracker.progress();
+    ui.strong(format!("🧭 lando {}", tracker.command));
+    match tracker.result {
+        None => {
+            ui.horizontal(|ui| {
+                ui.spinner();
+                ui.label(format!("{}/{} pasos · {} s", done, total, tracker.started.elapsed().as_secs()));
+            });
+        }
+        Some(true) => {
+            ui.colored_label(success_color(ui.visuals()), format!("✅ Terminado en {} s", tracker.started.elapsed().as_secs()));
+        }
+        Some(false) => {
+            ui.colored_label(error_color(ui.visuals()), "❌ Terminó con errores");
+        }
+    }
+    ui.separator();
+
+    if tracker.phases.is_empty() {
+        ui.colored_label(muted_color(ui.visuals()), "Esperando salida reconocible…");
+        return;
+    }
+
+    egui::ScrollArea::vertical().id_salt("phase_list").show(ui, |ui| {
+        for phase in &tracker.phases {
+            let elapsed = phase.finished.unwrap_or_else(std::time::Instant::now).duration_since(phase.started);
+            ui.horizontal(|ui| {
+                match phase.state {
+                    PhaseState::Running => {
+                        ui.spinner();
+                        ui.label(format!("{}…", phase.label));
+                    }
+                    PhaseState::Done => {
+                        ui.label(&phase.done_label);
+                    }
+                    PhaseState::Failed => {
+                        ui.colored_label(error_color(ui.visuals()), format!("❌ {}", phase.label));
+                    }
+                }
+                ui.colored_label(muted_color(ui.visuals()), format!("{:.0} s", elapsed.as_secs_f32()));
+            });
+        }
+    });
+}
@@ -0,0 +1,174 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::mpsc::Sender;
+
+use eframe::egui;
+
+use crate::models::commands::LandoCommandOutcome;
+use crate::models::lando::LandoService;
+use crate::ui::theme::{error_color, muted_color};
+
+// Plantillas de comandos redis-cli; el texto se copia al campo para completarlo
+pub const REDIS_TEMPLATES: &[(&str, &str)] = &[
+    ("🔑 KEYS", "KEYS *"),
+    ("📖 GET", "GET clave"),
+    ("✏️ SET", "SET clave valor"),
+    ("⏳ TTL", "TTL clave"),
+    ("ℹ️ INFO", "INFO"),
+];
+
+// Resultado de un comando ya interpretado como lista clave/valor
+pub struct RedisResult {
+    pub command: String,
+    pub entries: Vec<(String, String)>,
+    pub raw: String,
+    pub error: Option<String>,
+}
+
+// Panel de comandos para servicios redis: se ejecutan con `redis-cli`
+// dentro del contenedor y la salida se muestra como lista clave/valor
+pub struct RedisUI {
+    pub command_input: String,
+    pub history: Vec<String>,
+    pub result: Option<RedisResult>,
+    pub show_raw: bool,
+    pub pending_requests: HashMap<u64, String>,
+}
+
+impl Default for RedisUI {
+    fn default() -> Self {
+        Self {
+            command_input: String::new(),
+            history: Vec::new(),
+            result: None,
+            show_raw: false,
+            pending_requests: HashMap::new(),
+        }
+    }
+}
+
+impl RedisUI {
+    pub fn show(
+        &mut self,
+        ui: &mut egui::Ui,
+        service: &LandoService,
+        project_path: &PathBuf,
+        sender: &Sender<LandoCommandOutcome>,
+    ) {
+        ui.collapsing(format!("🧱 Redis: {} ({})", service.service, service.r#type), |ui| {
+            ui.label(format!("📦 Versión: {}", service.version));
+            if let Some(conn) = &service.external_connection {
+                ui.label(format!("🌐 Conexión Externa: {}:{}", conn.host, conn.port));
+            }
+
+            ui.separator();
+            self.show_command_bar(ui, service, project_path, sender);
+
+            ui.separator();
+            self.show_result(ui, service);
+        });
+    }
+
+    fn show_command_bar(
+        &mut self,
+        ui: &mut egui::Ui,
+        service: &LandoService,
+        project_path: &PathBuf,
+        sender: &Sender<LandoCommandOutcome>,
+    ) {
+        let is_running = !self.pending_requests.is_empty();
+
+        ui.horizontal(|ui| {
+            ui.label("redis-cli>");
+            let response = ui.add_enabled(
+                !is_running,
+                egui::TextEdit::singleline(&mut self.command_input)
+                    .hint_text("Comando Redis, p. ej. GET clave")
+                    .code_editor()
+                    .desired_width(320.0)
+            );
+
+            let submitted = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+            if is_running {
+                ui.spinner();
+            } else if ui.button("▶️ Ejecutar").clicked() || submitted {
+                let command = self.command_input.clone();
+                self.run_command(service, project_path, sender, &command);
+                response.request_focus();
+            }
+        });
+
+        ui.horizontal_wrapped(|ui| {
+            for (label, template) in REDIS_TEMPLATES {
+                if ui.button(*label).on_hover_text(*template).clicked() {
+                    self.command_input = template.to_string();
+                }
+            }
+
+            if !self.history.is_empty() {
+                egui::ComboBox::from_id_salt(("redis_history", &service.service))
+                    .selected_text("🕘 Historial")
+                    .show_ui(ui, |ui| {
+                        for command in self.history.iter().rev() {
+                            if ui.selectable_label(false, command).clicked() {
+                                self.command_input = command.clone();
+                            }
+                        }
+                    });
+            }
+        });
+    }
+
+    fn show_result(&mut self, ui: &mut egui::Ui, service: &LandoService) {
+        let Some(result) = &self.result else {
+            ui.label(egui::RichText::new("Sin resultados todavía").color(muted_color(ui.visuals())));
+            return;
+        };
+
+        ui.horizontal(|ui| {
+            ui.strong(format!("$ {}", result.command));
+            ui.checkbox(&mut self.show_raw, "Salida cruda");
+        });
+
+        if let Some(error) = &result.error {
+            ui.colored_label(error_color(ui.visuals()), format!("❌ {}", error));
+            return;
+        }
+
+        if self.show_raw || result.entries.is_empty() {
+            egui::ScrollArea::vertical()
+                .id_salt(("redis_raw", &service.service))
+                .max_height(260.0)
+                .show(ui, |ui| {
+                    ui.add(
+                        egui::TextEdit::multiline(&mut result.raw.as_str())
+                            .code_editor()
+                            .desired_width(f32::INFINITY)
+                    );
+                });
+            return;
+        }
+
+        ui.label(egui::RichText::new(format!("{} entradas", result.entries.len())).color(muted_color(ui.visuals())));
+        egui::ScrollArea::vertical()
+            .id_salt(("redis_entries", &service.service))
+            .max_height(260.0)
+            .show(ui, |ui| {
+                egui::Grid::new(("redis_entries_grid", &service.service))
+                    .num_columns(2)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for (key, value) in &result.entries {
+                            ui.monospace(key);
+                            if ui.add(egui::Label::new(egui::RichText::new(value).monospace()).sense(egui::Sense::click()))
+                                .on_hover_text("Click para copiar")
+                                .clicked()
+                            {
+                                ui.ctx().copy_text(value.clone());
+                            }
+                            ui.end_row();
+                        }
+                    });
+            });
+    }
+}
@@ -0,0 +1,178 @@
+use std::path::PathBuf;
+use std::sync::mpsc::Sender;
+
+use eframe::egui;
+
+use crate::core::resources::{validate_cpus, validate_mem_limit, validate_shm_size, visible_diff_lines, DIFF_CONTEXT};
+use crate::models::commands::LandoCommandOutcome;
+use crate::models::lando::LandoService;
+use crate::models::resources::{DiffLine, ResourceEditor, ResourceUsage};
+use crate::ui::confirm::ConfirmResponse;
+use crate::ui::theme::{error_color, muted_color, success_color, warning_color};
+
+// Campo de texto con el error de validación debajo
+fn limit_input(
+    ui: &mut egui::Ui,
+    label: &str,
+    hint: &str,
+    value: &mut String,
+    validate: fn(&str) -> Result<Option<String>, String>,
+) -> bool {
+    let mut valid = true;
+    ui.label(label);
+    ui.vertical(|ui| {
+        ui.add(egui::TextEdit::singleline(value).hint_text(hint).desired_width(100.0));
+        if let Err(err) = validate(value) {
+            valid = false;
+            ui.colored_label(error_color(ui.visuals()), format!("⚠️ {}", err));
+        }
+    });
+    ui.end_row();
+    valid
+}
+
+impl ResourceEditor {
+    pub fn show(&mut self, ui: &mut egui::Ui, service: &LandoService, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>) {
+        self.show_preview_window(ui.ctx(), &service.service, project_path);
+        self.show_pending_rebuild(ui.ctx(), project_path, sender);
+
+        egui::CollapsingHeader::new("📐 Recursos")
+            .id_salt(("resources_panel", &service.service))
+            .show(ui, |ui| {
+                self.ensure_loaded(project_path, &service.service);
+                self.show_usage(ui, service, project_path, sender);
+                ui.separator();
+
+                let mut valid = true;
+                egui::Grid::new(("resource_limits", &service.service)).num_columns(2).show(ui, |ui| {
+                    valid &= limit_input(ui, "Memoria (mem_limit):", "p. ej. 512m o 2g", &mut self.mem_limit, validate_mem_limit);
+                    valid &= limit_input(ui, "CPUs (cpus):", "p. ej. 1.5", &mut self.cpus, validate_cpus);
+                    valid &= limit_input(ui, "Memoria compartida (shm_size):", "p. ej. 256m", &mut self.shm_size, validate_shm_size);
+                });
+                ui.colored_label(muted_color(ui.visuals()), "Vacío = sin límite (se borra la clave de overrides)");
+
+                let changed = self.edited_limits().is_ok_and(|limits| limits != self.saved);
+                ui.horizontal(|ui| {
+                    if ui.add_enabled(valid && changed, egui::Button::new("👁 Revisar y aplicar")).clicked() {
+                        self.prepare_preview(project_path, &service.service);
+                    }
+                    if ui.button("↩️ Descartar").on_hover_text("Volver a leer .lando.yml").clicked() {
+                        self.load(project_path, &service.service);
+                        self.message = None;
+                    }
+                });
+
+                match &self.message {
+                    Some(Ok(message)) => {
+                        ui.colored_label(success_color(ui.visuals()), format!("✅ {}", message));
+                    }
+                    Some(Err(err)) => {
+                        ui.colored_label(error_color(ui.visuals()), format!("❌ {}", err));
+                    }
+                    None => {}
+                }
+            });
+    }
+
+    fn show_usage(&mut self, ui: &mut egui::Ui, service: &LandoService, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>) {
+        ui.horizontal(|ui| {
+            let loading = matches!(self.usage, ResourceUsage::Loading(_));
+            if ui.add_enabled(!loading, egui::Button::new("📊 Medir uso")).on_hover_text("docker stats del contenedor").clicked() {
+                self.request_usage(project_path, &service.service, sender);
+            }
+            match &self.usage {
+                ResourceUsage::Unknown => {
+                    ui.colored_label(muted_color(ui.visuals()), "Uso actual sin medir");
+                }
+                ResourceUsage::Loading(_) => {
+                    ui.spinner();
+                }
+                ResourceUsage::Stopped => {
+                    ui.colored_label(muted_color(ui.visuals()), "El contenedor no está en marcha");
+                }
+                ResourceUsage::Measured { cpu, memory, memory_percent } => {
+                    let color = if *memory_percent >= 90.0 {
+                        error_color(ui.visuals())
+                    } else if *memory_percent >= 75.0 {
+                        warning_color(ui.visuals())
+                    } else {
+                        ui.visuals().text_color()
+                    };
+                    ui.label(format!("CPU {:.1}%", cpu));
+                    ui.colored_label(color, format!("Mem {} ({:.1}%)", memory, memory_percent));
+                }
+                ResourceUsage::Failed(err) => {
+                    ui.colored_label(error_color(ui.visuals()), format!("❌ {}", err));
+                }
+            }
+        });
+    }
+
+    fn show_preview_window(&mut self, ctx: &egui::Context, service: &str, project_path: &PathBuf) {
+        let Some(preview) = &self.preview else {
+            return;
+        };
+
+        let mut save = false;
+        let mut cancel = false;
+        egui::Window::new(format!("📐 Cambios en .lando.yml ({})", service))
+            .collapsible(false)
+            .resizable(true)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                if !preview.comments_preserved {
+                    ui.colored_label(
+                        warning_color(ui.visuals()),
+                        "⚠️ El archivo no se puede editar línea a línea: se regenerará entero y se perderán los comentarios (copia en .lando.yml.bak).",
+                    );
+                }
+
+                egui::ScrollArea::vertical().max_height(320.0).show(ui, |ui| {
+                    let visible = visible_diff_lines(&preview.diff, DIFF_CONTEXT);
+                    let mut previous: Option<usize> = None;
+                    for index in visible {
+                        if previous.is_some_and(|p| p + 1 != index) {
+                            ui.colored_label(muted_color(ui.visuals()), "…");
+                        }
+                        previous = Some(index);
+                        let (prefix, text, color) = match &preview.diff[index] {
+                            DiffLine::Same(text) => (" ", text, muted_color(ui.visuals())),
+                            DiffLine::Added(text) => ("+", text, success_color(ui.visuals())),
+                            DiffLine::Removed(text) => ("-", text, error_color(ui.visuals())),
+                        };
+                        ui.label(egui::RichText::new(format!("{} {}", prefix, text)).monospace().color(color));
+                    }
+                });
+
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui.button("💾 Guardar").clicked() {
+                        save = true;
+                    }
+                    if ui.button("❌ Cancelar").clicked() {
+                        cancel = true;
+                    }
+                });
+            });
+
+        if save {
+            self.save_preview(project_path, service);
+        } else if cancel {
+            self.preview = None;
+        }
+    }
+
+    fn show_pending_rebuild(&mut self, ctx: &egui::Context, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>) {
+        let Some(dialog) = self.pending_rebuild.as_mut() else {
+            return;
+        };
+        match dialog.show(ctx) {
+            ConfirmResponse::Confirmed => {
+                self.pending_rebuild = None;
+                self.run_rebuild(project_path, sender);
+            }
+            ConfirmResponse::Cancelled => self.pending_rebuild = None,
+            ConfirmResponse::Pending => {}
+        }
+    }
+}
@@ -0,0 +1,146 @@
+use eframe::egui;
+use egui_extras::{Column, TableBuilder};
+
+use crate::core::result_format::{
+    binary_to_hex, classify_cell, format_cell, parse_json_cell, utc_to_local, CellKind, ParsedResultSet,
+};
+use crate::ui::theme::{error_color, info_color, muted_color, success_color};
+
+// Ancho inicial de cada columna; se puede ajustar arrastrando el borde
+const GRID_COLUMN_WIDTH: f32 = 140.0;
+
+// Lo que se pidió desde una celda en este frame
+#[derive(Debug, Clone, PartialEq)]
+pub enum GridCellAction {
+    OpenJson { column: String, value: serde_json::Value },
+}
+
+// Texto de la celda según su tipo: NULL atenuado, ✓/✗, `<blob N bytes>`...
+pub fn cell_rich_text(value: &str, kind: CellKind, thousands: bool, visuals: &egui::Visuals) -> egui::RichText {
+    let text = egui::RichText::new(format_cell(value, kind, thousands));
+    match kind {
+        CellKind::Null => text.italics().weak(),
+        CellKind::Boolean if format_cell(value, kind, false) == "✓" => text.color(success_color(visuals)),
+        CellKind::Boolean => text.color(error_color(visuals)),
+        CellKind::Json => text.monospace().color(info_color(visuals)),
+        CellKind::Binary => text.italics().color(muted_color(visuals)),
+        CellKind::Number | CellKind::Date => text.monospace(),
+        CellKind::Text => text,
+    }
+}
+
+pub fn cell_hover_text(value: &str, kind: CellKind) -> Option<String> {
+    match kind {
+        CellKind::Date => utc_to_local(value).map(|local| format!("Como UTC, en hora local: {}", local)),
+        CellKind::Json => Some("Clic para ver el JSON formateado".to_string()),
+        CellKind::Binary | CellKind::Null | CellKind::Boolean => None,
+        _ if value.len() > 40 || value.contains('\n') => Some(value.to_string()),
+        _ => None,
+    }
+}
+
+// Entradas propias del tipo para el menú contextual de una celda
+pub fn cell_menu(ui: &mut egui::Ui, column: &str, value: &str, kind: CellKind) -> Option<GridCellAction> {
+    let mut action = None;
+    match kind {
+        CellKind::Binary => {
+            if ui.button("📋 Copiar como hex").clicked() {
+                ui.ctx().copy_text(binary_to_hex(value));
+                ui.close_menu();
+            }
+        }
+        CellKind::Json => {
+            if ui.button("🔍 Ver JSON").clicked() {
+                action = parse_json_cell(value).map(|value| GridCellAction::OpenJson { column: column.to_string(), value });
+                ui.close_menu();
+            }
+        }
+        _ => {}
+    }
+    if kind != CellKind::Null && ui.button("📋 Copiar valor").clicked() {
+        ui.ctx().copy_text(value.to_string());
+        ui.close_menu();
+    }
+    action
+}
+
+fn show_cell(ui: &mut egui::Ui, column: &str, value: &str, column_kind: CellKind, thousands: bool) -> Option<GridCellAction> {
+    let kind = classify_cell(value, column_kind);
+    let label = egui::Label::new(cell_rich_text(value, kind, thousands, ui.visuals()))
+        .truncate()
+        .sense(egui::Sense::click());
+    let mut response = if kind.is_right_aligned() {
+        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| ui.add(label)).inner
+    } else {
+        ui.add(label)
+    };
+    if let Some(hover) = cell_hover_text(value, kind) {
+        response = response.on_hover_text(hover);
+    }
+
+    let mut action = None;
+    if kind == CellKind::Json && response.clicked() {
+        action = parse_json_cell(value).map(|value| GridCellAction::OpenJson { column: column.to_string(), value });
+    }
+    response.context_menu(|ui| {
+        if let Some(menu_action) = cell_menu(ui, column, value, kind) {
+            action = Some(menu_action);
+        }
+    });
+    action
+}
+
+// Rejilla de resultados virtualizada: solo se maquetan las filas visibles, así
+// que decenas de miles de filas se desplazan igual de fluidas que diez.
+// `kinds` trae el tipo de cada columna (ver `classify_columns`).
+pub fn show_result_grid(
+    ui: &mut egui::Ui,
+    id_salt: impl std::hash::Hash,
+    set: &ParsedResultSet,
+    kinds: &[CellKind],
+    thousands: bool,
+    max_height: f32,
+) -> Option<GridCellAction> {
+    let row_height = ui.text_style_height(&egui::TextStyle::Body) + 6.0;
+    let mut action = None;
+
+    ui.push_id(id_salt, |ui| {
+        egui::ScrollArea::horizontal().show(ui, |ui| {
+            TableBuilder::new(ui)
+                .striped(true)
+                .resizable(true)
+                .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+                .column(Column::auto().at_least(40.0)) // Número de fila
+                .columns(Column::initial(GRID_COLUMN_WIDTH).at_least(40.0).clip(true), set.columns.len())
+                .min_scrolled_height(0.0)
+                .max_scroll_height(max_height)
+                .header(row_height, |mut header| {
+                    header.col(|ui| {
+                        ui.strong("#");
+                    });
+                    for column in &set.columns {
+                        header.col(|ui| {
+                            ui.strong(column);
+                        });
+                    }
+                })
+                .body(|body| {
+                    body.rows(row_height, set.rows.len(), |mut row| {
+                        let index = row.index();
+                        row.col(|ui| {
+                            ui.weak((index + 1).to_string());
+                        });
+                        for (column, cell) in set.rows[index].iter().enumerate().take(set.columns.len()) {
+                            let kind = kinds.get(column).copied().unwrap_or(CellKind::Text);
+                            row.col(|ui| {
+                                if let Some(cell_action) = show_cell(ui, &set.columns[column], cell, kind, thousands) {
+                                    action = Some(cell_action);
+                                }
+                            });
+                        }
+                    });
+                });
+        });
+    });
+    action
+}
@@ -0,0 +1,288 @@
+use std::sync::mpsc::Sender;
+
+use eframe::egui;
+
+use crate::core::search::search_base_url;
+use crate::models::commands::LandoCommandOutcome;
+use crate::models::lando::LandoService;
+use crate::models::search::{SearchAction, SearchEngine, SearchIndex};
+use crate::ui::confirm::{ConfirmDialog, ConfirmResponse};
+use crate::ui::json_view::show_json;
+use crate::ui::theme::{error_color, muted_color, success_color, warning_color};
+
+const DEFAULT_ES_QUERY: &str = "{\n  \"query\": { \"match_all\": {} },\n  \"size\": 10\n}";
+
+// Respuesta de una consulta o acción; `json` si el cuerpo se pudo interpretar
+pub struct SearchResult {
+    pub label: String,
+    pub status: Option<u16>,
+    pub json: Option<serde_json::Value>,
+    pub raw: String,
+    pub error: Option<String>,
+}
+
+// Panel para Solr y Elasticsearch: lista de cores/índices y consola de consultas
+// contra la API HTTP publicada en la conexión externa
+pub struct SearchUI {
+    pub indices: Vec<SearchIndex>,
+    pub selected: Option<String>,
+    pub loading_indices: bool,
+    pub indices_requested: bool, // La lista se pide sola la primera vez que se abre
+    pub indices_error: Option<String>,
+    pub solr_q: String,
+    pub solr_fq: String, // Un filtro por línea
+    pub solr_rows: String,
+    pub solr_fields: String,
+    pub es_body: String,
+    pub running: bool,
+    pub result: Option<SearchResult>,
+    pub show_raw: bool,
+    pub pending_action: Option<(SearchAction, ConfirmDialog)>,
+}
+
+impl Default for SearchUI {
+    fn default() -> Self {
+        Self {
+            indices: Vec::new(),
+            selected: None,
+            loading_indices: false,
+            indices_requested: false,
+            indices_error: None,
+            solr_q: "*:*".to_string(),
+            solr_fq: String::new(),
+            solr_rows: "10".to_string(),
+            solr_fields: String::new(),
+            es_body: DEFAULT_ES_QUERY.to_string(),
+            running: false,
+            result: None,
+            show_raw: false,
+            pending_action: None,
+        }
+    }
+}
+
+impl SearchUI {
+    pub fn show(
+        &mut self,
+        ui: &mut egui::Ui,
+        service: &LandoService,
+        engine: SearchEngine,
+        sender: &Sender<LandoCommandOutcome>,
+    ) {
+        ui.collapsing(format!("🔎 {}: {} ({})", engine.label(), service.service, service.r#type), |ui| {
+            ui.label(format!("📦 Versión: {}", service.version));
+            match search_base_url(service) {
+                Some(url) => {
+                    ui.horizontal(|ui| {
+                        ui.label(format!("🌐 API: {}", url));
+                        if ui.small_button("↗").on_hover_text("Abrir en el navegador").clicked() {
+                            let _ = open::that(&url);
+                        }
+                    });
+                }
+                None => {
+                    ui.colored_label(warning_color(ui.visuals()), "⚠️ Sin conexión externa: arranca la app para usar la API");
+                    return;
+                }
+            }
+
+            if !self.indices_requested {
+                self.refresh_indices(service, engine, sender);
+            }
+
+            ui.separator();
+            self.show_indices(ui, service, engine, sender);
+
+            ui.separator();
+            self.show_query_console(ui, service, engine, sender);
+
+            ui.separator();
+            self.show_result(ui, service);
+        });
+
+        self.show_pending_action(ui.ctx(), service, engine, sender);
+    }
+
+    fn show_indices(&mut self, ui: &mut egui::Ui, service: &LandoService, engine: SearchEngine, sender: &Sender<LandoCommandOutcome>) {
+        ui.horizontal(|ui| {
+            ui.strong(match engine {
+                SearchEngine::Solr => "📚 Cores",
+                SearchEngine::Elasticsearch => "📚 Índices",
+            });
+            if self.loading_indices {
+                ui.spinner();
+            } else if ui.small_button("🔄").on_hover_text("Volver a cargar la lista").clicked() {
+                self.refresh_indices(service, engine, sender);
+            }
+        });
+
+        if let Some(error) = &self.indices_error {
+            ui.colored_label(error_color(ui.visuals()), format!("🔌 No se pudo consultar {}: {}", engine.label(), error));
+            return;
+        }
+        if self.indices.is_empty() {
+            if !self.loading_indices {
+                ui.colored_label(muted_color(ui.visuals()), format!("No hay ningún {}", engine.index_noun()));
+            }
+            return;
+        }
+
+        let mut requested = None;
+        egui::Grid::new(("search_indices", &service.service))
+            .striped(true)
+            .num_columns(4)
+            .show(ui, |ui| {
+                ui.strong("Nombre");
+                ui.strong("Documentos");
+                ui.strong("Tamaño");
+                ui.strong("");
+                ui.end_row();
+
+                for index in &self.indices {
+                    let selected = self.selected.as_deref() == Some(index.name.as_str());
+                    ui.horizontal(|ui| {
+                        if let Some(health) = &index.health {
+                            let color = match health.as_str() {
+                                "green" => success_color(ui.visuals()),
+                                "yellow" => warning_color(ui.visuals()),
+                                _ => error_color(ui.visuals()),
+                            };
+                            ui.colored_label(color, "●").on_hover_text(health.as_str());
+                        }
+                        if ui.selectable_label(selected, &index.name).clicked() {
+                            self.selected = Some(index.name.clone());
+                        }
+                    });
+                    ui.label(index.docs.map(|d| d.to_string()).unwrap_or_else(|| "-".to_string()));
+                    ui.label(index.size.as_deref().unwrap_or("-"));
+                    ui.horizontal(|ui| {
+                        let reload_hint = match engine {
+                            SearchEngine::Solr => "Recargar el core (p. ej. tras cambiar el esquema)",
+                            SearchEngine::Elasticsearch => "Refrescar el índice para ver los últimos cambios",
+                        };
+                        if ui.small_button("♻️").on_hover_text(reload_hint).clicked() {
+                            requested = Some(SearchAction::Reload(index.name.clone()));
+                        }
+                        if ui.small_button("🗑️").on_hover_text("Borrar todos los documentos").clicked() {
+                            requested = Some(SearchAction::DeleteAll(index.name.clone()));
+                        }
+                    });
+                    ui.end_row();
+                }
+            });
+
+        if let Some(action) = requested {
+            self.request_action(service, engine, action);
+        }
+    }
+
+    fn show_query_console(&mut self, ui: &mut egui::Ui, service: &LandoService, engine: SearchEngine, sender: &Sender<LandoCommandOutcome>) {
+        let target = self.selected.clone().unwrap_or_else(|| "todos".to_string());
+        ui.strong(format!("🔍 Consulta sobre {}", target));
+
+        match engine {
+            SearchEngine::Solr => {
+                egui::Grid::new(("solr_query", &service.service))
+                    .num_columns(2)
+                    .show(ui, |ui| {
+                        ui.label("q");
+                        ui.add(egui::TextEdit::singleline(&mut self.solr_q).code_editor().desired_width(320.0));
+                        ui.end_row();
+                        ui.label("fq");
+                        ui.add(
+                            egui::TextEdit::multiline(&mut self.solr_fq)
+                                .code_editor()
+                                .desired_rows(1)
+                                .desired_width(320.0)
+                                .hint_text("Un filtro por línea"),
+                        );
+                        ui.end_row();
+                        ui.label("rows");
+                        ui.add(egui::TextEdit::singleline(&mut self.solr_rows).desired_width(60.0));
+                        ui.end_row();
+                        ui.label("fl");
+                        ui.add(
+                            egui::TextEdit::singleline(&mut self.solr_fields)
+                                .code_editor()
+                                .desired_width(320.0)
+                                .hint_text("id,title,score"),
+                        );
+                        ui.end_row();
+                    });
+            }
+            SearchEngine::Elasticsearch => {
+                ui.add(
+                    egui::TextEdit::multiline(&mut self.es_body)
+                        .code_editor()
+                        .desired_rows(6)
+                        .desired_width(f32::INFINITY),
+                );
+            }
+        }
+
+        ui.horizontal(|ui| {
+            if self.running {
+                ui.spinner();
+            } else if ui.button("▶️ Ejecutar").clicked() {
+                self.run_query(service, engine, sender);
+            }
+            if engine == SearchEngine::Elasticsearch && ui.button("↺ Restablecer").clicked() {
+                self.es_body = DEFAULT_ES_QUERY.to_string();
+            }
+        });
+    }
+
+    fn show_result(&mut self, ui: &mut egui::Ui, service: &LandoService) {
+        let Some(result) = &self.result else {
+            ui.label(egui::RichText::new("Sin resultados todavía").color(muted_color(ui.visuals())));
+            return;
+        };
+
+        if !result.label.is_empty() {
+            ui.horizontal(|ui| {
+                ui.strong(&result.label);
+                if let Some(status) = result.status {
+                    let color = if status < 400 { success_color(ui.visuals()) } else { error_color(ui.visuals()) };
+                    ui.colored_label(color, format!("HTTP {}", status));
+                }
+            });
+        }
+        if let Some(error) = &result.error {
+            ui.colored_label(error_color(ui.visuals()), format!("❌ {}", error));
+        }
+
+        // Los errores de Elasticsearch también vienen en JSON y conviene verlos
+        match &result.json {
+            Some(json) => show_json(ui, &format!("search_result_{}", service.service), json, &mut self.show_raw),
+            None if !result.raw.is_empty() => {
+                egui::ScrollArea::vertical()
+                    .id_salt(("search_raw", &service.service))
+                    .max_height(300.0)
+                    .show(ui, |ui| {
+                        ui.add(
+                            egui::TextEdit::multiline(&mut result.raw.as_str())
+                                .code_editor()
+                                .desired_width(f32::INFINITY),
+                        );
+                    });
+            }
+            None => {}
+        }
+    }
+
+    fn show_pending_action(&mut self, ctx: &egui::Context, service: &LandoService, engine: SearchEngine, sender: &Sender<LandoCommandOutcome>) {
+        let Some((_, dialog)) = self.pending_action.as_mut() else {
+            return;
+        };
+
+        match dialog.show(ctx) {
+            ConfirmResponse::Pending => {}
+            ConfirmResponse::Cancelled => self.pending_action = None,
+            ConfirmResponse::Confirmed => {
+                if let Some((action, _)) = self.pending_action.take() {
+                    self.run_action(service, engine, &action, sender);
+                }
+            }
+        }
+    }
+}
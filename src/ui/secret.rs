@@ -0,0 +1,41 @@
+use std::hash::Hash;
+
+use eframe::egui;
+
+use crate::core::secret::copy_secret;
+use crate::models::secret::SecretString;
+
+// Si el secreto se está mostrando; vive en la memoria de egui para no añadir
+// un campo por cada contraseña que aparece en pantalla
+fn toggle_reveal(ui: &mut egui::Ui, id: egui::Id) -> bool {
+    let mut revealed = ui.data(|d| d.get_temp::<bool>(id)).unwrap_or(false);
+    let (icon, hint) = if revealed { ("🙈", "Ocultar") } else { ("👁", "Mostrar") };
+    if ui.small_button(icon).on_hover_text(hint).clicked() {
+        revealed = !revealed;
+        ui.data_mut(|d| d.insert_temp(id, revealed));
+    }
+    revealed
+}
+
+// Contraseña oculta con botones para verla y para copiar el valor real
+pub fn show_secret(ui: &mut egui::Ui, id_salt: impl Hash, label: &str, secret: &SecretString) {
+    let id = ui.make_persistent_id(id_salt);
+    ui.horizontal(|ui| {
+        ui.label(label);
+        let revealed = ui.data(|d| d.get_temp::<bool>(id)).unwrap_or(false);
+        ui.monospace(if revealed { secret.expose() } else { "••••••••" });
+        toggle_reveal(ui, id);
+        if ui.small_button("📋").on_hover_text("Copiar contraseña").clicked() {
+            copy_secret(ui.ctx(), secret);
+        }
+    });
+}
+
+// Campo de contraseña editable que se escribe oculto salvo que se pulse el ojo
+pub fn secret_text_edit(ui: &mut egui::Ui, id_salt: impl Hash, value: &mut SecretString) -> egui::Response {
+    let id = ui.make_persistent_id(id_salt);
+    let revealed = ui.data(|d| d.get_temp::<bool>(id)).unwrap_or(false);
+    let response = ui.add(egui::TextEdit::singleline(value.expose_mut()).password(!revealed));
+    toggle_reveal(ui, id);
+    response
+}
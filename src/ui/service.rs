@@ -1,22 +1,42 @@
 use std::path::PathBuf;
 use std::sync::mpsc::Sender;
 use std::collections::HashMap;
+use std::time::Instant;
 
 use eframe::egui;
-use egui_term::TerminalBackend;
+use crate::models::terminal::LazyTerminal;
 
+use crate::core::db_engine::{is_db_candidate, probe_db_engine, DB_ENGINES};
+use crate::core::health::{probe_targets, run_health_probe};
+use crate::core::search::search_engine;
+use crate::core::varnish::is_varnish_service;
+use crate::models::health::{HealthState, ProbeResult};
 use crate::models::commands::LandoCommandOutcome;
 use crate::models::lando::LandoService;
-use crate::core::commands::*;
+use crate::models::resources::ResourceEditor;
 use crate::ui::database::DatabaseUI;
 use crate::ui::appserver::AppServerUI;
 use crate::ui::node::NodeUI;
+use crate::ui::generic::GenericServiceUI;
+use crate::ui::redis::RedisUI;
+use crate::ui::mongo::MongoUI;
+use crate::ui::search::SearchUI;
+use crate::ui::secret::show_secret;
+use crate::ui::varnish::ProxyCacheUI;
+use crate::ui::theme::{error_color, muted_color, success_color, warning_color};
 
 // Gestor de estado para las diferentes UIs especializadas
 pub struct ServiceUIManager {
     pub database_uis: HashMap<String, DatabaseUI>,
     pub appserver_uis: HashMap<String, AppServerUI>,
     pub node_uis: HashMap<String, NodeUI>,
+    pub generic_uis: HashMap<String, GenericServiceUI>,
+    pub redis_uis: HashMap<String, RedisUI>,
+    pub mongo_uis: HashMap<String, MongoUI>,
+    pub search_uis: HashMap<String, SearchUI>, // Por nombre de servicio, como llegan las respuestas HTTP
+    pub proxy_cache_uis: HashMap<String, ProxyCacheUI>,
+    pub health: HashMap<String, HealthState>, // Panel "¿está arriba?" por nombre de servicio
+    pub resources: HashMap<String, ResourceEditor>, // Límites de memoria/CPU por nombre de servicio
 }
 
 impl Default for ServiceUIManager {
@@ -25,6 +45,13 @@ impl Default for ServiceUIManager {
             database_uis: HashMap::new(),
             appserver_uis: HashMap::new(),
             node_uis: HashMap::new(),
+            generic_uis: HashMap::new(),
+            redis_uis: HashMap::new(),
+            mongo_uis: HashMap::new(),
+            search_uis: HashMap::new(),
+            proxy_cache_uis: HashMap::new(),
+            health: HashMap::new(),
+            resources: HashMap::new(),
         }
     }
 }
@@ -37,12 +64,51 @@ impl ServiceUIManager {
         project_path: &PathBuf,
         sender: &Sender<LandoCommandOutcome>,
         is_loading: &mut bool,
-        terminal: &mut TerminalBackend,
+        terminal: &mut LazyTerminal,
+        app_running: bool,
     ) {
         let service_key = format!("{}_{}", service.service, service.r#type);
+
+        self.show_status_indicator(ui, service, app_running);
+        Self::show_raw_info(ui, service);
+        self.show_health_panel(ui, service, sender);
+        self.resources
+            .entry(service.service.clone())
+            .or_default()
+            .show(ui, service, project_path, sender);
         
         // Determinar el tipo de servicio y mostrar la UI apropiada
         match self.classify_service(service) {
+            ServiceType::Redis => {
+                let redis_ui = self.redis_uis
+                    .entry(service_key)
+                    .or_default();
+
+                redis_ui.show(ui, service, project_path, sender);
+            },
+            ServiceType::Mongo => {
+                let mongo_ui = self.mongo_uis
+                    .entry(service_key)
+                    .or_default();
+
+                mongo_ui.show(ui, service, project_path, sender);
+            },
+            ServiceType::Search => {
+                if let Some(engine) = search_engine(service) {
+                    let search_ui = self.search_uis
+                        .entry(service.service.clone())
+                        .or_default();
+
+                    search_ui.show(ui, service, engine, sender);
+                }
+            },
+            ServiceType::ProxyCache => {
+                let proxy_cache_ui = self.proxy_cache_uis
+                    .entry(service_key)
+                    .or_default();
+
+                proxy_cache_ui.show(ui, service, project_path, sender);
+            },
             ServiceType::Database => {
                 let database_ui = self.database_uis
                     .entry(service_key)
@@ -66,17 +132,132 @@ impl ServiceUIManager {
             },
             ServiceType::Generic => {
                 // Fallback a la UI genérica original para servicios no clasificados
-                self.show_generic_service_ui(ui, service, project_path, sender, is_loading);
+                let generic_ui = self.generic_uis
+                    .entry(service_key)
+                    .or_default();
+
+                Self::show_generic_service_ui(generic_ui, ui, service, project_path, sender);
             },
         }
     }
 
+    // Punto verde/rojo según si la app del servicio aparece en `lando list`
+    fn show_status_indicator(&self, ui: &mut egui::Ui, service: &LandoService, app_running: bool) {
+        let (color, status) = if app_running {
+            (success_color(ui.visuals()), "En ejecución")
+        } else {
+            (error_color(ui.visuals()), "Detenido")
+        };
+
+        ui.horizontal(|ui| {
+            let (rect, response) = ui.allocate_exact_size(egui::vec2(12.0, 12.0), egui::Sense::hover());
+            ui.painter().circle_filled(rect.center(), 5.0, color);
+            response.on_hover_text(status);
+            ui.label(format!("{} — {}", service.service, status));
+        });
+    }
+
+    pub fn handle_health_probed(&mut self, service: &str, results: Vec<ProbeResult>) {
+        let state = self.health.entry(service.to_string()).or_default();
+        state.running = false;
+        state.results = results;
+        state.checked_at = Some(Instant::now());
+    }
+
+    // Healthcheck de lando (si lo trae `lando info`) y prueba TCP/HTTP bajo demanda
+    fn show_health_panel(&mut self, ui: &mut egui::Ui, service: &LandoService, sender: &Sender<LandoCommandOutcome>) {
+        let targets = probe_targets(service);
+        let lando_health = service.raw.get("healthy").and_then(|h| h.as_bool().or_else(|| h.as_str().map(|s| s == "true" || s == "healthy")));
+        if targets.is_empty() && lando_health.is_none() {
+            return;
+        }
+
+        let state = self.health.entry(service.service.clone()).or_default();
+        egui::CollapsingHeader::new("🩺 Salud")
+            .id_salt(("health_panel", &service.service))
+            .show(ui, |ui| {
+                if let Some(healthy) = lando_health {
+                    if healthy {
+                        ui.colored_label(success_color(ui.visuals()), "✅ Healthcheck de lando: sano");
+                    } else {
+                        ui.colored_label(error_color(ui.visuals()), "❌ Healthcheck de lando: no sano");
+                    }
+                }
+                if targets.is_empty() {
+                    return;
+                }
+
+                ui.horizontal(|ui| {
+                    if ui.add_enabled(!state.running, egui::Button::new("🔌 Comprobar")).clicked() {
+                        state.running = true;
+                        run_health_probe(sender.clone(), service.service.clone(), targets.clone());
+                    }
+                    if state.running {
+                        ui.spinner();
+                    } else if let Some(checked_at) = state.checked_at {
+                        ui.colored_label(muted_color(ui.visuals()), format!("hace {} s", checked_at.elapsed().as_secs()));
+                    }
+                });
+
+                if state.results.is_empty() {
+                    for target in &targets {
+                        ui.colored_label(muted_color(ui.visuals()), format!("⚪ {}", target.label()));
+                    }
+                    return;
+                }
+                for result in &state.results {
+                    match &result.outcome {
+                        Ok(detail) => {
+                            let color = if result.latency_ms > 500.0 { warning_color(ui.visuals()) } else { success_color(ui.visuals()) };
+                            ui.colored_label(color, format!("🟢 {} — {} ({:.0} ms)", result.target.label(), detail, result.latency_ms));
+                        }
+                        Err(error) => {
+                            ui.colored_label(error_color(ui.visuals()), format!("🔴 {} — {} ({:.0} ms)", result.target.label(), error, result.latency_ms));
+                        }
+                    }
+                }
+            });
+    }
+
+    // JSON completo de `lando info` para depurar recetas poco habituales
+    fn show_raw_info(ui: &mut egui::Ui, service: &LandoService) {
+        egui::CollapsingHeader::new("🧾 Raw")
+            .id_salt(("raw_info", &service.service))
+            .show(ui, |ui| {
+                let pretty = serde_json::to_string_pretty(&service.raw).unwrap_or_default();
+                if ui.small_button("📋 Copiar").clicked() {
+                    ui.ctx().copy_text(pretty.clone());
+                }
+                egui::ScrollArea::vertical()
+                    .id_salt(("raw_info_scroll", &service.service))
+                    .max_height(300.0)
+                    .show(ui, |ui| {
+                        ui.add(
+                            egui::TextEdit::multiline(&mut pretty.as_str())
+                                .code_editor()
+                                .desired_width(f32::INFINITY),
+                        );
+                    });
+            });
+    }
+
     fn classify_service(&self, service: &LandoService) -> ServiceType {
         let service_type = service.r#type.to_lowercase();
         let service_name = service.service.to_lowercase();
 
-        // Clasificar por nombre de servicio primero (más confiable)
-        let result = if service_name == "database" {
+        // Clasificar por nombre de servicio primero (más confiable); redis y
+        // mongo van antes porque no hablan SQL aunque cuenten como base de datos
+        let result = if self.is_redis_service(service) {
+            ServiceType::Redis
+        } else if self.is_mongo_service(service) {
+            ServiceType::Mongo
+        } else if search_engine(service).is_some() {
+            ServiceType::Search
+        } else if is_varnish_service(service) {
+            ServiceType::ProxyCache
+        } else if service.resolved_db_engine.is_some() {
+            ServiceType::Database
+        } else if service_name == "database" {
             ServiceType::Database
         } else if self.is_database_service(&service_name) {
             ServiceType::Database
@@ -109,6 +290,16 @@ impl ServiceUIManager {
         )
     }
 
+    // Por motor (`redis:7`, o el resuelto de un `compose`) o por nombre
+    pub fn is_redis_service(&self, service: &LandoService) -> bool {
+        service.db_engine().to_lowercase().starts_with("redis") || service.service.to_lowercase() == "redis"
+    }
+
+    pub fn is_mongo_service(&self, service: &LandoService) -> bool {
+        service.db_engine().to_lowercase().starts_with("mongo")
+            || matches!(service.service.to_lowercase().as_str(), "mongo" | "mongodb")
+    }
+
     fn is_appserver_service(&self, service_name: &str) -> bool {
         matches!(service_name, 
             "apache" | "nginx" | "httpd" | "php" | "python" | 
@@ -121,16 +312,22 @@ impl ServiceUIManager {
     }
 
     fn show_generic_service_ui(
-        &self,
+        generic_ui: &mut GenericServiceUI,
         ui: &mut egui::Ui,
         service: &LandoService,
         project_path: &PathBuf,
         sender: &Sender<LandoCommandOutcome>,
-        is_loading: &mut bool,
     ) {
         ui.collapsing(&service.service, |ui| {
-            ui.label(format!("🏷️ Tipo: {}", service.r#type));
+            // La primera vez que se despliega un posible servicio de BD sin motor
+            // conocido se le pregunta al contenedor
+            if is_db_candidate(service) && !generic_ui.engine_probe_requested {
+                generic_ui.engine_probe_requested = true;
+                probe_db_engine(sender.clone(), project_path, &service.service);
+            }
+
             ui.label(format!("📦 Versión: {}", service.version));
+            show_db_engine_selector(ui, service, sender);
 
             if let Some(creds) = &service.creds {
                 ui.separator();
@@ -139,8 +336,7 @@ impl ServiceUIManager {
                     ui.label(format!("👤 Usuario: {}", user));
                 }
                 if let Some(password) = &creds.password {
-                    ui.add(egui::Label::new(format!("🔐 Contraseña: {}", "••••••••")).sense(egui::Sense::click()))
-                        .on_hover_text("Click para copiar");
+                    show_secret(ui, ("service_password", &service.service), "🔐 Contraseña:", password);
                 }
                 if let Some(database) = &creds.database {
                     ui.label(format!("💾 Base de datos: {}", database));
@@ -157,37 +353,47 @@ impl ServiceUIManager {
             ui.separator();
             ui.label("⚠️ Servicio genérico - Funcionalidad limitada");
             ui.label("Considera configurar una interfaz especializada para este tipo de servicio.");
-            
-            // Comando shell básico
+
             ui.separator();
-            ui.horizontal(|ui| {
-                if ui.button("📊 Status").clicked() && !*is_loading {
-                    *is_loading = true;
-                    run_shell_command(
-                        sender.clone(),
-                        project_path.clone(),
-                        service.service.clone(),
-                        "status".to_string(),
-                    );
-                }
-                
-                if ui.button("🔄 Restart").clicked() && !*is_loading {
-                    *is_loading = true;
-                    run_shell_command(
-                        sender.clone(),
-                        project_path.clone(),
-                        service.service.clone(),
-                        "restart".to_string(),
-                    );
-               }
-            });
+            generic_ui.show_shell_panel(ui, service, project_path, sender);
         });
     }
 }
 
+// Tipo de lando, motor de BD resuelto y selector para corregir la detección.
+// El cambio viaja por el canal para que `LandoGui` lo guarde y lo aplique.
+pub fn show_db_engine_selector(ui: &mut egui::Ui, service: &LandoService, sender: &Sender<LandoCommandOutcome>) {
+    ui.horizontal(|ui| {
+        ui.label(format!("🏷️ Tipo lando: {}", service.r#type));
+        match &service.resolved_db_engine {
+            Some(engine) => ui.label(format!("⚙️ Motor: {}", engine)),
+            None => ui.colored_label(muted_color(ui.visuals()), "⚙️ Motor: desconocido"),
+        };
+
+        let mut choice = service.db_engine_override.clone();
+        egui::ComboBox::from_id_salt(("db_engine_override", &service.service))
+            .selected_text(choice.as_deref().unwrap_or("Automático"))
+            .show_ui(ui, |ui| {
+                ui.selectable_value(&mut choice, None, "Automático");
+                for engine in DB_ENGINES {
+                    ui.selectable_value(&mut choice, Some(engine.to_string()), *engine);
+                }
+            })
+            .response
+            .on_hover_text("Motor a usar si la detección se equivoca");
+        if choice != service.db_engine_override {
+            let _ = sender.send(LandoCommandOutcome::DbEngineOverride { service: service.service.clone(), engine: choice });
+        }
+    });
+}
+
 #[derive(Debug, Clone, PartialEq)]
 enum ServiceType {
     Database,
+    Redis,
+    ProxyCache,
+    Mongo,
+    Search,
     AppServer,
     Node,
     Generic,
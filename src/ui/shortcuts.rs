@@ -0,0 +1,25 @@
+use eframe::egui;
+
+const TAB_KEYS: [egui::Key; 9] = [
+    egui::Key::Num1,
+    egui::Key::Num2,
+    egui::Key::Num3,
+    egui::Key::Num4,
+    egui::Key::Num5,
+    egui::Key::Num6,
+    egui::Key::Num7,
+    egui::Key::Num8,
+    egui::Key::Num9,
+];
+
+// Ctrl+1..9 de una barra de pestañas. La tecla se consume para que, con
+// varios paneles abiertos, solo cambie el primero que se pinta en el frame.
+pub fn consume_tab_shortcut(ui: &egui::Ui, tab_count: usize) -> Option<usize> {
+    TAB_KEYS.iter()
+        .take(tab_count)
+        .position(|key| ui.input_mut(|i| i.consume_key(egui::Modifiers::COMMAND, *key)))
+}
+
+pub fn tab_shortcut_hint(index: usize) -> String {
+    format!("Ctrl+{}", index + 1)
+}
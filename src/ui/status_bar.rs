@@ -0,0 +1,194 @@
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use eframe::egui;
+
+use crate::core::recipes::detect_recipe;
+use crate::core::status::elide_middle;
+use crate::models::status::ToolVersions;
+use crate::ui::theme::{error_color, muted_color, success_color};
+
+// Cada cuánto se vuelve a comprobar si el daemon de Docker responde
+pub const DOCKER_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+const STATUS_PATH_CHARS: usize = 48;
+
+// Lo que se pidió desde la barra en este frame
+#[derive(Debug, Clone, PartialEq)]
+pub enum StatusBarAction {
+    RevealProject(PathBuf),
+    OpenDiagnostics,
+    OpenTerminal,
+}
+
+// Contexto global siempre visible: proyecto, interfaz abierta, tareas en
+// curso y versiones de lando/docker
+pub struct StatusBar {
+    pub versions: Option<ToolVersions>,
+    pub checking: bool,
+    pub last_check: Option<Instant>,
+    pub show_tasks: bool,
+    pub info_retry: Option<(u32, u32)>, // Intento fallido de `lando info` y máximo
+    recipe: Option<(PathBuf, Option<String>)>, // Se lee del `.lando.yml` una vez por proyecto
+}
+
+impl Default for StatusBar {
+    fn default() -> Self {
+        Self {
+            versions: None,
+            checking: false,
+            last_check: None,
+            show_tasks: false,
+            info_retry: None,
+            recipe: None,
+        }
+    }
+}
+
+impl StatusBar {
+    pub fn needs_check(&self) -> bool {
+        !self.checking && self.last_check.is_none_or(|t| t.elapsed() >= DOCKER_CHECK_INTERVAL)
+    }
+
+    pub fn handle_versions(&mut self, versions: ToolVersions) {
+        self.checking = false;
+        self.last_check = Some(Instant::now());
+        self.versions = Some(versions);
+    }
+
+    fn recipe_for(&mut self, project: &Path) -> Option<&str> {
+        if self.recipe.as_ref().is_none_or(|(path, _)| path != project) {
+            self.recipe = Some((project.to_path_buf(), detect_recipe(project)));
+        }
+        self.recipe.as_ref().and_then(|(_, recipe)| recipe.as_deref())
+    }
+
+    pub fn show(
+        &mut self,
+        ctx: &egui::Context,
+        project: Option<&Path>,
+        open_interface: Option<&str>,
+        running: &[(String, Instant)],
+        phases: Option<((usize, usize), Option<bool>)>, // Progreso y resultado del start/rebuild
+    ) -> Option<StatusBarAction> {
+        let mut action = None;
+        egui::TopBottomPanel::bottom("status_bar")
+            .exact_height(22.0)
+            .show(ctx, |ui| {
+                ui.horizontal_centered(|ui| {
+                    ui.spacing_mut().item_spacing.x = 10.0;
+                    match project {
+                        Some(path) => {
+                            let name = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+                            let response = ui.add(egui::Label::new(format!("📂 {}", name)).sense(egui::Sense::click()))
+                                .on_hover_text(format!("{}\nClic: abrir en el gestor de archivos", path.display()));
+                            if response.clicked() {
+                                action = Some(StatusBarAction::RevealProject(path.to_path_buf()));
+                            }
+                            if let Some(recipe) = self.recipe_for(path) {
+                                ui.colored_label(muted_color(ui.visuals()), format!("🍳 {}", recipe));
+                            }
+                            ui.add(egui::Label::new(
+                                egui::RichText::new(elide_middle(&path.display().to_string(), STATUS_PATH_CHARS))
+                                    .small()
+                                    .color(muted_color(ui.visuals())),
+                            ).truncate());
+                        }
+                        None => {
+                            ui.colored_label(muted_color(ui.visuals()), "Sin proyecto seleccionado");
+                        }
+                    }
+
+                    if let Some(service) = open_interface {
+                        ui.separator();
+                        ui.label(format!("🗄️ {}", service));
+                    }
+
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        self.show_docker_status(ui);
+
+                        let lando = self.versions.as_ref().and_then(|v| v.lando.as_deref()).unwrap_or("?");
+                        if ui.add(egui::Label::new(format!("Lando {}", lando)).sense(egui::Sense::click()))
+                            .on_hover_text("Abrir Acerca de / Diagnóstico")
+                            .clicked()
+                        {
+                            action = Some(StatusBarAction::OpenDiagnostics);
+                        }
+
+                        ui.separator();
+                        if let Some(((done, total), result)) = phases {
+                            let text = match result {
+                                None => format!("🧭 {}/{} pasos", done, total),
+                                Some(true) => format!("✅ {}/{} pasos", done, total),
+                                Some(false) => format!("❌ {}/{} pasos", done, total),
+                            };
+                            if ui.add(egui::Button::new(text).small())
+                                .on_hover_text("Ver los pasos y el terminal")
+                                .clicked()
+                            {
+                                action = Some(StatusBarAction::OpenTerminal);
+                            }
+                        }
+                        if let Some((attempt, max)) = self.info_retry {
+                            ui.spinner();
+                            ui.label(format!("🔁 Esperando servicios ({}/{})", attempt, max))
+                                .on_hover_text("lando info falló tras el arranque; se reintenta con espera creciente");
+                        }
+                        let tasks = if running.is_empty() {
+                            ui.add(egui::Label::new(
+                                egui::RichText::new("Sin tareas").color(muted_color(ui.visuals())),
+                            ).sense(egui::Sense::click()))
+                        } else {
+                            ui.spinner();
+                            ui.add(egui::Label::new(format!("{} en curso", running.len())).sense(egui::Sense::click()))
+                        };
+                        if tasks.on_hover_text("Ver tareas en curso").clicked() {
+                            self.show_tasks = !self.show_tasks;
+                        }
+                    });
+                });
+            });
+
+        self.show_tasks_window(ctx, running);
+        action
+    }
+
+    fn show_docker_status(&self, ui: &mut egui::Ui) {
+        let (color, hover) = match &self.versions {
+            Some(v) if v.docker_running => (success_color(ui.visuals()), "El daemon de Docker responde"),
+            Some(_) => (error_color(ui.visuals()), "El daemon de Docker no responde"),
+            None => (muted_color(ui.visuals()), "Comprobando Docker…"),
+        };
+        let (rect, response) = ui.allocate_exact_size(egui::vec2(10.0, 10.0), egui::Sense::hover());
+        ui.painter().circle_filled(rect.center(), 4.0, color);
+        response.on_hover_text(hover);
+
+        let docker = self.versions.as_ref().and_then(|v| v.docker.as_deref()).unwrap_or("?");
+        ui.label(format!("Docker {}", docker));
+    }
+
+    fn show_tasks_window(&mut self, ctx: &egui::Context, running: &[(String, Instant)]) {
+        if !self.show_tasks {
+            return;
+        }
+
+        let mut open = true;
+        egui::Window::new("⏱️ Tareas en curso")
+            .open(&mut open)
+            .resizable(true)
+            .default_width(360.0)
+            .show(ctx, |ui| {
+                if running.is_empty() {
+                    ui.colored_label(muted_color(ui.visuals()), "No hay comandos en ejecución.");
+                    return;
+                }
+                for (command, started) in running {
+                    ui.horizontal(|ui| {
+                        ui.spinner();
+                        ui.monospace(format!("lando {}", command));
+                        ui.colored_label(muted_color(ui.visuals()), format!("{} s", started.elapsed().as_secs()));
+                    });
+                }
+            });
+        self.show_tasks = open;
+    }
+}
@@ -0,0 +1,106 @@
+use eframe::egui;
+
+// Colores semánticos que dependen del tema activo. Los tonos puros
+// (`Color32::YELLOW`, `GREEN`, `WHITE`...) solo se leen sobre fondo oscuro.
+
+pub fn success_color(visuals: &egui::Visuals) -> egui::Color32 {
+    if visuals.dark_mode {
+        egui::Color32::from_rgb(0, 200, 80)
+    } else {
+        egui::Color32::from_rgb(0, 128, 50)
+    }
+}
+
+pub fn warning_color(visuals: &egui::Visuals) -> egui::Color32 {
+    visuals.warn_fg_color
+}
+
+pub fn error_color(visuals: &egui::Visuals) -> egui::Color32 {
+    visuals.error_fg_color
+}
+
+pub fn info_color(visuals: &egui::Visuals) -> egui::Color32 {
+    if visuals.dark_mode {
+        egui::Color32::LIGHT_BLUE
+    } else {
+        egui::Color32::from_rgb(0, 90, 180)
+    }
+}
+
+// Texto secundario (estados desconocidos, elementos deshabilitados)
+pub fn muted_color(visuals: &egui::Visuals) -> egui::Color32 {
+    visuals.weak_text_color()
+}
+
+// Los botones con `fill` propio tapan el resaltado de foco de egui: al navegar
+// con teclado se dibuja un contorno alrededor
+pub fn focus_outline(ui: &egui::Ui, response: &egui::Response) {
+    if response.has_focus() {
+        let visuals = ui.visuals();
+        ui.painter().rect_stroke(
+            response.rect.expand(2.0),
+            visuals.widgets.inactive.corner_radius,
+            egui::Stroke::new(2.0, visuals.selection.stroke.color),
+            egui::StrokeKind::Outside,
+        );
+    }
+}
+
+// Un botón de solo icono se anuncia en los lectores de pantalla por su emoji:
+// `label` pasa a ser su nombre accesible y el texto al pasar el ratón
+pub fn icon_label(response: egui::Response, label: &str) -> egui::Response {
+    response.widget_info(|| egui::WidgetInfo::labeled(egui::WidgetType::Button, response.enabled(), label));
+    response.on_hover_text(label)
+}
+
+#[cfg(test)]
+mod tests {
+    use eframe::egui::accesskit::{Role, TreeUpdate};
+
+    use super::*;
+    use crate::ui::shortcuts::consume_tab_shortcut;
+
+    fn accesskit_frame(ctx: &egui::Context, events: Vec<egui::Event>, mut add_contents: impl FnMut(&mut egui::Ui)) -> TreeUpdate {
+        let input = egui::RawInput { events, ..Default::default() };
+        let output = ctx.run(input, |ctx| {
+            egui::CentralPanel::default().show(ctx, |ui| add_contents(ui));
+        });
+        output.platform_output.accesskit_update.expect("AccessKit activado")
+    }
+
+    fn labels(update: &TreeUpdate, role: Role) -> Vec<String> {
+        update.nodes.iter()
+            .filter(|(_, node)| node.role() == role)
+            .filter_map(|(_, node)| node.label().map(String::from))
+            .collect()
+    }
+
+    #[test]
+    fn icon_buttons_have_accessible_names() {
+        let ctx = egui::Context::default();
+        ctx.enable_accesskit();
+        let update = accesskit_frame(&ctx, Vec::new(), |ui| {
+            icon_label(ui.small_button("⏹️"), "Detener proceso");
+            let _ = ui.button("💾 Guardar");
+        });
+        assert_eq!(labels(&update, Role::Button), vec!["Detener proceso", "💾 Guardar"]);
+    }
+
+    #[test]
+    fn tab_shortcuts_select_the_tab() {
+        let ctx = egui::Context::default();
+        ctx.enable_accesskit();
+        let mut selected = None;
+        let ctrl_3 = egui::Event::Key {
+            key: egui::Key::Num3,
+            physical_key: None,
+            pressed: true,
+            repeat: false,
+            modifiers: egui::Modifiers::COMMAND,
+        };
+        accesskit_frame(&ctx, vec![ctrl_3.clone()], |ui| selected = consume_tab_shortcut(ui, 5));
+        assert_eq!(selected, Some(2));
+        accesskit_frame(&ctx, vec![ctrl_3], |ui| selected = consume_tab_shortcut(ui, 2));
+        assert_eq!(selected, None);
+    }
+}
@@ -0,0 +1,169 @@
+use std::time::Instant;
+
+use eframe::egui;
+
+use crate::models::errors::ErrorFix;
+use crate::models::toasts::{Toast, ToastAction, ToastLevel, ToastManager};
+use crate::ui::theme::{error_color, info_color, success_color, warning_color};
+
+// Lo que pidió el usuario al pulsar el botón de acción de una tarjeta
+#[derive(Debug, Clone, PartialEq)]
+pub enum ToastRequest {
+    ShowLog,
+    Fix(ErrorFix),
+}
+
+impl ToastManager {
+    // Tarjetas apiladas en la esquina superior derecha
+    pub fn show(&mut self, ctx: &egui::Context) -> Option<ToastRequest> {
+        let now = Instant::now();
+        self.expire(now);
+        if let Some(wait) = self.next_expiry(now) {
+            ctx.request_repaint_after(wait);
+        }
+
+        let mut request = None;
+        let mut dismissed = Vec::new();
+
+        egui::Area::new(egui::Id::new("toast_area"))
+            .anchor(egui::Align2::RIGHT_TOP, [-10.0, 40.0])
+            .order(egui::Order::Foreground)
+            .show(ctx, |ui| {
+                ui.set_max_width(360.0);
+                for toast in self.active.iter().rev() {
+                    egui::Frame::popup(ui.style())
+                        .stroke(egui::Stroke::new(1.0, Self::level_color(toast.level, ui.visuals())))
+                        .show(ui, |ui| {
+                            if Self::show_card(ui, toast, &mut request) {
+                                dismissed.push(toast.id);
+                            }
+                        });
+                    ui.add_space(4.0);
+                }
+            });
+
+        for id in dismissed {
+            self.dismiss(id);
+        }
+        request
+    }
+
+    // Devuelve true si se pulsó cerrar
+    fn show_card(ui: &mut egui::Ui, toast: &Toast, request: &mut Option<ToastRequest>) -> bool {
+        let mut close = false;
+
+        ui.horizontal(|ui| {
+            ui.colored_label(Self::level_color(toast.level, ui.visuals()), format!("{} {}", Self::level_icon(toast.level), toast.title));
+            if toast.repeat_count > 1 {
+                ui.label(format!("×{}", toast.repeat_count));
+            }
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if ui.small_button("✖").on_hover_text("Cerrar").clicked() {
+                    close = true;
+                }
+            });
+        });
+
+        if !toast.body.is_empty() {
+            ui.label(&toast.body);
+        }
+
+        if let Some(action) = &toast.action {
+            match action {
+                ToastAction::ShowLog => {
+                    if ui.small_button("📟 Ver log").clicked() {
+                        *request = Some(ToastRequest::ShowLog);
+                    }
+                }
+                ToastAction::CopyDetail(detail) => {
+                    if ui.small_button("📋 Copiar detalle").clicked() {
+                        ui.ctx().copy_text(detail.clone());
+                    }
+                }
+                ToastAction::Fix(fix) => {
+                    let label = match fix {
+                        ErrorFix::StartApp => "▶️ Iniciar app",
+                        ErrorFix::OpenDocker => "🐳 Abrir Docker",
+                    };
+                    if ui.small_button(label).clicked() {
+                        *request = Some(ToastRequest::Fix(*fix));
+                        close = true;
+                    }
+                }
+            }
+        }
+
+        close
+    }
+
+    pub fn show_history_window(&mut self, ctx: &egui::Context) {
+        if !self.show_history {
+            return;
+        }
+        self.mark_read();
+
+        let mut open = true;
+        let mut clear = false;
+
+        egui::Window::new("🔔 Notificaciones")
+            .open(&mut open)
+            .resizable(true)
+            .default_width(420.0)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(format!("{} notificaciones", self.history.len()));
+                    if ui.small_button("🗑️ Limpiar").clicked() {
+                        clear = true;
+                    }
+                });
+                ui.separator();
+
+                egui::ScrollArea::vertical()
+                    .max_height(400.0)
+                    .show(ui, |ui| {
+                        if self.history.is_empty() {
+                            ui.label("💭 Sin notificaciones");
+                        }
+                        for toast in self.history.iter().rev() {
+                            ui.horizontal(|ui| {
+                                ui.colored_label(Self::level_color(toast.level, ui.visuals()), Self::level_icon(toast.level));
+                                ui.strong(&toast.title);
+                                ui.label(format!("hace {} s", toast.created_at.elapsed().as_secs()));
+                            });
+                            if !toast.body.is_empty() {
+                                ui.label(&toast.body);
+                            }
+                            if let Some(ToastAction::CopyDetail(detail)) = &toast.action {
+                                if ui.small_button("📋 Copiar detalle").clicked() {
+                                    ui.ctx().copy_text(detail.clone());
+                                }
+                            }
+                            ui.separator();
+                        }
+                    });
+            });
+
+        if clear {
+            self.clear_history();
+        }
+        self.show_history = open;
+    }
+
+    fn level_color(level: ToastLevel, visuals: &egui::Visuals) -> egui::Color32 {
+        match level {
+            ToastLevel::Info => info_color(visuals),
+            ToastLevel::Success => success_color(visuals),
+            ToastLevel::Warning => warning_color(visuals),
+            ToastLevel::Error => error_color(visuals),
+        }
+    }
+
+    fn level_icon(level: ToastLevel) -> &'static str {
+        match level {
+            ToastLevel::Info => "ℹ️",
+            ToastLevel::Success => "✅",
+            ToastLevel::Warning => "⚠️",
+            ToastLevel::Error => "❌",
+        }
+    }
+}
@@ -0,0 +1,303 @@
+use std::collections::{HashMap, VecDeque};
+use std::path::PathBuf;
+use std::sync::mpsc::Sender;
+use std::time::{Duration, Instant};
+
+use eframe::egui;
+
+use crate::core::varnish::ban_expression;
+use crate::models::commands::LandoCommandOutcome;
+use crate::models::lando::LandoService;
+use crate::models::varnish::{BanMode, VarnishCommand, VarnishRequest, VarnishStats};
+use crate::ui::confirm::{ConfirmDialog, ConfirmResponse};
+use crate::ui::theme::{error_color, info_color, muted_color, success_color, warning_color};
+
+// Cada cuánto se vuelven a leer los contadores con la actualización automática
+pub const VARNISH_REFRESH_INTERVAL: Duration = Duration::from_secs(5);
+// Puntos de la gráfica de tasa de aciertos (unos 10 minutos a 5 s)
+pub const VARNISH_HISTORY_POINTS: usize = 120;
+const DEFAULT_REQUEST_LIMIT: usize = 50;
+
+// Panel para varnish: contadores de aciertos, bans y últimas peticiones.
+// Todo pasa por `lando ssh` dentro del contenedor.
+pub struct ProxyCacheUI {
+    pub available: Option<bool>, // None mientras se comprueba si están las herramientas
+    pub stats: Option<VarnishStats>,
+    pub stats_error: Option<String>,
+    pub hit_rate_history: VecDeque<[f64; 2]>, // (segundos desde que se abrió, % aciertos)
+    pub started: Instant,
+    pub auto_refresh: bool,
+    pub last_stats_request: Option<Instant>,
+    pub ban_mode: BanMode,
+    pub ban_input: String,
+    pub ban_result: Option<Result<String, String>>,
+    pub pending_purge_all: Option<ConfirmDialog>,
+    pub requests: Vec<VarnishRequest>,
+    pub requests_error: Option<String>,
+    pub request_limit: usize,
+    pub pending_requests: HashMap<u64, VarnishCommand>,
+}
+
+impl Default for ProxyCacheUI {
+    fn default() -> Self {
+        Self {
+            available: None,
+            stats: None,
+            stats_error: None,
+            hit_rate_history: VecDeque::new(),
+            started: Instant::now(),
+            auto_refresh: false,
+            last_stats_request: None,
+            ban_mode: BanMode::Url,
+            ban_input: String::new(),
+            ban_result: None,
+            pending_purge_all: None,
+            requests: Vec::new(),
+            requests_error: None,
+            request_limit: DEFAULT_REQUEST_LIMIT,
+            pending_requests: HashMap::new(),
+        }
+    }
+}
+
+impl ProxyCacheUI {
+    pub fn show(
+        &mut self,
+        ui: &mut egui::Ui,
+        service: &LandoService,
+        project_path: &PathBuf,
+        sender: &Sender<LandoCommandOutcome>,
+    ) {
+        ui.collapsing(format!("🚀 Varnish: {} ({})", service.service, service.r#type), |ui| {
+            ui.label(format!("📦 Versión: {}", service.version));
+
+            match self.available {
+                None => {
+                    if self.pending_requests.is_empty() {
+                        self.probe(service, project_path, sender);
+                    }
+                    ui.horizontal(|ui| {
+                        ui.spinner();
+                        ui.label("Comprobando varnishadm y varnishstat…");
+                    });
+                    return;
+                }
+                Some(false) => {
+                    ui.colored_label(
+                        warning_color(ui.visuals()),
+                        "⚠️ varnishadm, varnishstat o varnishncsa no están disponibles en el contenedor (¿la app está arrancada?)",
+                    );
+                    if ui.button("🔄 Reintentar").clicked() {
+                        self.probe(service, project_path, sender);
+                    }
+                    return;
+                }
+                Some(true) => {}
+            }
+
+            self.show_stats(ui, service, project_path, sender);
+            ui.separator();
+            self.show_ban_form(ui, service, project_path, sender);
+            ui.separator();
+            self.show_requests(ui, service, project_path, sender);
+        });
+
+        self.show_pending_purge_all(ui.ctx(), service, project_path, sender);
+    }
+
+    fn show_stats(&mut self, ui: &mut egui::Ui, service: &LandoService, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>) {
+        let due = (self.stats.is_none() && self.stats_error.is_none() && self.last_stats_request.is_none())
+            || (self.auto_refresh && self.last_stats_request.is_none_or(|t| t.elapsed() >= VARNISH_REFRESH_INTERVAL));
+        if due {
+            self.refresh_stats(service, project_path, sender);
+        }
+        if self.auto_refresh {
+            ui.ctx().request_repaint_after(VARNISH_REFRESH_INTERVAL);
+        }
+
+        ui.horizontal(|ui| {
+            ui.strong("📊 Caché");
+            if self.pending_requests.values().any(|c| *c == VarnishCommand::Stats) {
+                ui.spinner();
+            } else if ui.small_button("🔄").on_hover_text("Leer los contadores").clicked() {
+                self.refresh_stats(service, project_path, sender);
+            }
+            ui.checkbox(&mut self.auto_refresh, format!("Auto ({} s)", VARNISH_REFRESH_INTERVAL.as_secs()));
+        });
+
+        if let Some(error) = &self.stats_error {
+            ui.colored_label(error_color(ui.visuals()), format!("❌ {}", error));
+        }
+        let Some(stats) = &self.stats else {
+            return;
+        };
+
+        egui::Grid::new(("varnish_stats", &service.service))
+            .num_columns(2)
+            .show(ui, |ui| {
+                ui.label("Aciertos");
+                ui.colored_label(success_color(ui.visuals()), stats.hits.to_string());
+                ui.end_row();
+                ui.label("Fallos");
+                ui.colored_label(warning_color(ui.visuals()), stats.misses.to_string());
+                ui.end_row();
+                ui.label("Pass");
+                ui.label(stats.passes.to_string());
+                ui.end_row();
+                ui.label("Peticiones");
+                ui.label(stats.requests.to_string());
+                ui.end_row();
+                if let Some(rate) = stats.hit_rate() {
+                    ui.label("Tasa de aciertos");
+                    ui.strong(format!("{:.1} %", rate * 100.0));
+                    ui.end_row();
+                }
+                if let Some(objects) = stats.objects {
+                    ui.label("Objetos en caché");
+                    ui.label(objects.to_string());
+                    ui.end_row();
+                }
+                if let Some(uptime) = stats.uptime {
+                    ui.label("Arrancado hace");
+                    ui.label(format!("{} h {} min", uptime / 3600, uptime / 60 % 60));
+                    ui.end_row();
+                }
+            });
+
+        if self.hit_rate_history.len() >= 2 {
+            let points: Vec<[f64; 2]> = self.hit_rate_history.iter().copied().collect();
+            egui_plot::Plot::new(("varnish_hit_rate", &service.service))
+                .height(60.0)
+                .include_y(0.0)
+                .include_y(100.0)
+                .show_axes([false, true])
+                .allow_drag(false)
+                .allow_zoom(false)
+                .allow_scroll(false)
+                .show(ui, |plot| {
+                    plot.line(egui_plot::Line::new(points).name("% aciertos por intervalo"));
+                });
+        } else if self.auto_refresh {
+            ui.colored_label(muted_color(ui.visuals()), "La gráfica aparece tras dos lecturas con tráfico");
+        }
+    }
+
+    fn show_ban_form(&mut self, ui: &mut egui::Ui, service: &LandoService, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>) {
+        ui.strong("🚫 Invalidar (ban)");
+        ui.horizontal(|ui| {
+            ui.radio_value(&mut self.ban_mode, BanMode::Url, "URL exacta");
+            ui.radio_value(&mut self.ban_mode, BanMode::Regex, "Regex");
+        });
+
+        let banning = self.pending_requests.values().any(|c| matches!(c, VarnishCommand::Ban(_)));
+        ui.horizontal(|ui| {
+            let hint = match self.ban_mode {
+                BanMode::Url => "/ruta?con=query",
+                BanMode::Regex => "^/blog/",
+            };
+            let response = ui.add(egui::TextEdit::singleline(&mut self.ban_input).code_editor().hint_text(hint).desired_width(280.0));
+            let submitted = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+            let can_ban = !banning && !self.ban_input.trim().is_empty();
+            if banning {
+                ui.spinner();
+            } else if ui.add_enabled(can_ban, egui::Button::new("🚫 Ban")).clicked() || (submitted && can_ban) {
+                let expression = ban_expression(self.ban_mode, &self.ban_input);
+                self.ban(service, project_path, sender, expression);
+            }
+            if ui.add_enabled(!banning, egui::Button::new("🧹 Purgar todo")).clicked() {
+                let expression = ban_expression(BanMode::Regex, ".");
+                self.pending_purge_all = Some(
+                    ConfirmDialog::new(
+                        "Purgar toda la caché",
+                        "Se invalidarán todos los objetos de varnish; las siguientes peticiones irán al backend.",
+                        &format!("varnishadm ban {}", expression),
+                        &service.service,
+                    )
+                    .destructive(),
+                );
+            }
+        });
+
+        match &self.ban_result {
+            Some(Ok(message)) => {
+                ui.colored_label(success_color(ui.visuals()), format!("✅ {}", message));
+            }
+            Some(Err(message)) => {
+                ui.colored_label(error_color(ui.visuals()), format!("❌ {}", message));
+            }
+            None => {}
+        }
+    }
+
+    fn show_requests(&mut self, ui: &mut egui::Ui, service: &LandoService, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>) {
+        ui.horizontal(|ui| {
+            ui.strong("📜 Últimas peticiones");
+            ui.add(egui::DragValue::new(&mut self.request_limit).range(10..=500));
+            if self.pending_requests.values().any(|c| *c == VarnishCommand::Requests) {
+                ui.spinner();
+            } else if ui.small_button("🔄 Cargar").on_hover_text("varnishncsa -d").clicked() {
+                self.load_requests(service, project_path, sender);
+            }
+        });
+
+        if let Some(error) = &self.requests_error {
+            ui.colored_label(error_color(ui.visuals()), format!("❌ {}", error));
+            return;
+        }
+        if self.requests.is_empty() {
+            ui.colored_label(muted_color(ui.visuals()), "Sin peticiones cargadas");
+            return;
+        }
+
+        egui::ScrollArea::vertical()
+            .id_salt(("varnish_requests", &service.service))
+            .max_height(240.0)
+            .show(ui, |ui| {
+                egui::Grid::new(("varnish_requests_grid", &service.service))
+                    .striped(true)
+                    .num_columns(4)
+                    .show(ui, |ui| {
+                        ui.strong("Método");
+                        ui.strong("URL");
+                        ui.strong("Estado");
+                        ui.strong("Caché");
+                        ui.end_row();
+                        // Las más recientes primero
+                        for request in self.requests.iter().rev() {
+                            ui.monospace(&request.method);
+                            ui.add(egui::Label::new(egui::RichText::new(&request.url).monospace()).truncate())
+                                .on_hover_text(&request.url);
+                            let status_color = if request.status.starts_with(['4', '5']) {
+                                error_color(ui.visuals())
+                            } else {
+                                ui.visuals().text_color()
+                            };
+                            ui.colored_label(status_color, &request.status);
+                            let handling_color = match request.handling.as_str() {
+                                "hit" => success_color(ui.visuals()),
+                                "miss" => warning_color(ui.visuals()),
+                                "pass" | "pipe" => info_color(ui.visuals()),
+                                _ => muted_color(ui.visuals()),
+                            };
+                            ui.colored_label(handling_color, &request.handling);
+                            ui.end_row();
+                        }
+                    });
+            });
+    }
+
+    fn show_pending_purge_all(&mut self, ctx: &egui::Context, service: &LandoService, project_path: &PathBuf, sender: &Sender<LandoCommandOutcome>) {
+        let Some(dialog) = self.pending_purge_all.as_mut() else {
+            return;
+        };
+
+        match dialog.show(ctx) {
+            ConfirmResponse::Pending => {}
+            ConfirmResponse::Cancelled => self.pending_purge_all = None,
+            ConfirmResponse::Confirmed => {
+                self.pending_purge_all = None;
+                self.ban(service, project_path, sender, ban_expression(BanMode::Regex, "."));
+            }
+        }
+    }
+}
@@ -0,0 +1,126 @@
+use std::path::PathBuf;
+use std::sync::mpsc::Sender;
+use std::thread;
+
+use eframe::egui;
+
+use crate::models::commands::LandoCommandOutcome;
+use crate::ui::theme::warning_color;
+
+// Recetas de Lando que no requieren autenticación contra un proveedor externo
+pub const LANDO_RECIPES: &[&str] = &[
+    "lamp", "lemp", "mean", "laravel", "symfony", "wordpress",
+    "drupal10", "drupal9", "drupal7", "backdrop", "joomla",
+];
+
+// Resultado del asistente en el frame actual
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WizardResponse {
+    Pending,
+    Create,
+    Cancelled,
+}
+
+// Asistente para crear un proyecto nuevo con `lando init`
+#[derive(Debug, Clone)]
+pub struct NewProjectWizard {
+    pub folder: Option<PathBuf>,
+    pub recipe: String,
+    pub app_name: String,
+    pub webroot: String,
+    pub picking_folder: bool,
+}
+
+impl Default for NewProjectWizard {
+    fn default() -> Self {
+        Self {
+            folder: None,
+            recipe: "lamp".to_string(),
+            app_name: String::new(),
+            webroot: ".".to_string(),
+            picking_folder: false,
+        }
+    }
+}
+
+impl NewProjectWizard {
+    pub fn show(&mut self, ctx: &egui::Context, sender: &Sender<LandoCommandOutcome>) -> WizardResponse {
+        let mut response = WizardResponse::Pending;
+        let validation = self.validate();
+
+        egui::Window::new("➕ Nuevo proyecto")
+            .collapsible(false)
+            .resizable(true)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                egui::Grid::new("new_project_grid")
+                    .num_columns(2)
+                    .spacing([10.0, 6.0])
+                    .show(ui, |ui| {
+                        ui.label("📂 Carpeta:");
+                        ui.horizontal(|ui| {
+                            match &self.folder {
+                                Some(folder) => ui.monospace(folder.display().to_string()),
+                                None => ui.label("(sin elegir)"),
+                            };
+                            if self.picking_folder {
+                                ui.spinner();
+                            } else if ui.button("📁 Elegir").clicked() {
+                                self.picking_folder = true;
+                                let sender = sender.clone();
+                                thread::spawn(move || {
+                                    let folder = rfd::FileDialog::new().pick_folder();
+                                    let _ = sender.send(LandoCommandOutcome::NewProjectFolderPicked(folder));
+                                });
+                            }
+                        });
+                        ui.end_row();
+
+                        ui.label("🍳 Receta:");
+                        egui::ComboBox::from_id_salt("new_project_recipe")
+                            .selected_text(&self.recipe)
+                            .show_ui(ui, |ui| {
+                                for recipe in LANDO_RECIPES {
+                                    ui.selectable_value(&mut self.recipe, recipe.to_string(), *recipe);
+                                }
+                            });
+                        ui.end_row();
+
+                        ui.label("🏷️ Nombre:");
+                        ui.text_edit_singleline(&mut self.app_name);
+                        ui.end_row();
+
+                        ui.label("🌐 Webroot:");
+                        ui.text_edit_singleline(&mut self.webroot);
+                        ui.end_row();
+                    });
+
+                ui.separator();
+
+                ui.label("Comando a ejecutar:");
+                ui.add(
+                    egui::TextEdit::multiline(&mut self.command_preview().as_str())
+                        .code_editor()
+                        .desired_rows(2)
+                        .desired_width(f32::INFINITY)
+                );
+
+                if let Err(err) = &validation {
+                    ui.colored_label(warning_color(ui.visuals()), format!("⚠️ {}", err));
+                }
+
+                ui.separator();
+
+                ui.horizontal(|ui| {
+                    if ui.add_enabled(validation.is_ok(), egui::Button::new("🚀 Crear")).clicked() {
+                        response = WizardResponse::Create;
+                    }
+                    if ui.button("❌ Cancelar").clicked() {
+                        response = WizardResponse::Cancelled;
+                    }
+                });
+            });
+
+        response
+    }
+}